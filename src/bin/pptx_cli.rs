@@ -2,6 +2,7 @@
 use std::process;
 
 use clap::{Parser, Subcommand};
+use pptx::export::html::{HtmlExportOptions, HtmlExporter, HtmlImages, HtmlLayout};
 use pptx::repair::{PptxRepairer, PptxValidator, Severity};
 use pptx::shapes::ShapeTree;
 use pptx::Presentation;
@@ -35,6 +36,42 @@ enum Command {
         /// Output HTML file path (defaults to stdout)
         #[arg(short, long)]
         output: Option<String>,
+        /// Write the HTML and image files into this directory instead
+        #[arg(long, conflicts_with = "output")]
+        dir: Option<String>,
+        /// Write one HTML file per slide (with --dir)
+        #[arg(long, requires = "dir")]
+        per_slide: bool,
+        /// Write images as separate files rather than embedding them (with --dir)
+        #[arg(long, requires = "dir")]
+        external_images: bool,
+        /// Leave out speaker notes
+        #[arg(long)]
+        no_notes: bool,
+    },
+    /// Export the presentation as a Markdown outline
+    ExportMarkdown {
+        /// Path to the PPTX file
+        file: String,
+        /// Output Markdown file path (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Export the presentation to PDF
+    ExportPdf {
+        /// Path to the PPTX file
+        file: String,
+        /// Output PDF file path
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Export each slide to an SVG file
+    ExportSvg {
+        /// Path to the PPTX file
+        file: String,
+        /// Directory to write `slide1.svg`, `slide2.svg`, ... into
+        #[arg(long)]
+        dir: String,
     },
     /// Validate the PPTX file and show issues
     Validate {
@@ -64,7 +101,34 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     match cli.command {
         Command::Info { file } => cmd_info(&file),
         Command::Slides { file } => cmd_slides(&file),
-        Command::ExportHtml { file, output } => cmd_export_html(&file, output.as_deref()),
+        Command::ExportHtml {
+            file,
+            output,
+            dir,
+            per_slide,
+            external_images,
+            no_notes,
+        } => {
+            let options = HtmlExportOptions::new()
+                .with_layout(if per_slide {
+                    HtmlLayout::PerSlide
+                } else {
+                    HtmlLayout::SingleFile
+                })
+                .with_images(if external_images {
+                    HtmlImages::External
+                } else {
+                    HtmlImages::Embedded
+                })
+                .with_notes(!no_notes);
+            match dir {
+                Some(dir) => cmd_export_html_files(&file, &dir, options),
+                None => cmd_export_html(&file, output.as_deref(), options),
+            }
+        }
+        Command::ExportMarkdown { file, output } => cmd_export_markdown(&file, output.as_deref()),
+        Command::ExportPdf { file, output } => cmd_export_pdf(&file, &output),
+        Command::ExportSvg { file, dir } => cmd_export_svg(&file, &dir),
         Command::Validate { file } => cmd_validate(&file),
         Command::Repair { file, output } => cmd_repair(&file, output.as_deref()),
     }
@@ -155,9 +219,13 @@ fn cmd_slides(file: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn cmd_export_html(file: &str, output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_export_html(
+    file: &str,
+    output: Option<&str>,
+    options: HtmlExportOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
     let prs = Presentation::open(file)?;
-    let html = prs.export_html()?;
+    let html = HtmlExporter::with_options(&prs, options).export()?;
 
     if let Some(path) = output {
         std::fs::write(path, &html)?;
@@ -169,6 +237,58 @@ fn cmd_export_html(file: &str, output: Option<&str>) -> Result<(), Box<dyn std::
     Ok(())
 }
 
+fn cmd_export_html_files(
+    file: &str,
+    dir: &str,
+    options: HtmlExportOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let prs = Presentation::open(file)?;
+    let files = prs.export_html_files(options)?;
+    let dir = std::path::Path::new(dir);
+    for file in &files {
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, &file.data)?;
+    }
+    println!("{} file(s) exported to {}", files.len(), dir.display());
+    Ok(())
+}
+
+fn cmd_export_markdown(file: &str, output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let prs = Presentation::open(file)?;
+    let markdown = prs.export_markdown()?;
+
+    if let Some(path) = output {
+        std::fs::write(path, &markdown)?;
+        println!("Markdown exported to {path}");
+    } else {
+        print!("{markdown}");
+    }
+
+    Ok(())
+}
+
+fn cmd_export_pdf(file: &str, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let prs = Presentation::open(file)?;
+    std::fs::write(output, prs.export_pdf()?)?;
+    println!("PDF exported to {output}");
+    Ok(())
+}
+
+fn cmd_export_svg(file: &str, dir: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let prs = Presentation::open(file)?;
+    let slides = prs.export_svg()?;
+    let dir = std::path::Path::new(dir);
+    std::fs::create_dir_all(dir)?;
+    for (idx, svg) in slides.iter().enumerate() {
+        std::fs::write(dir.join(format!("slide{}.svg", idx + 1)), svg)?;
+    }
+    println!("{} slide(s) exported to {}", slides.len(), dir.display());
+    Ok(())
+}
+
 fn cmd_validate(file: &str) -> Result<(), Box<dyn std::error::Error>> {
     let prs = Presentation::open(file)?;
     let issues = PptxValidator::validate(&prs);
@@ -0,0 +1,476 @@
+//! Applying a [`Chart`] model to chart XML.
+//!
+//! The chart XML writers only know about data. A `Chart` carries the rest
+//! — title, legend, style, axis settings, fills and lines — and is merged
+//! into a chart part here, element by element, leaving the series data and
+//! anything the model does not describe untouched.
+
+use crate::enums::chart::XlAxisCrosses;
+use crate::enums::chart::{XlTickLabelPosition, XlTickMark};
+use crate::error::PptxResult;
+use crate::text::font::Font;
+use crate::text::TextFrame;
+use crate::xml_util::{xml_escape, WriteXml};
+
+use super::axis::{AxisTitle, TickLabels};
+use super::chart::{Chart, ChartFormat};
+use super::edit::{apply_edits, set_child, Edit, AXIS_ORDER};
+use super::legend::Legend;
+use super::parse::XmlNode;
+
+/// Schema order of the children of `c:chartSpace`.
+const CHART_SPACE_ORDER: &[&str] = &[
+    "date1904",
+    "lang",
+    "roundedCorners",
+    "AlternateContent",
+    "style",
+    "clrMapOvr",
+    "pivotSource",
+    "protection",
+    "chart",
+    "spPr",
+    "txPr",
+    "externalData",
+    "printSettings",
+    "userShapes",
+    "extLst",
+];
+
+/// Schema order of the children of `c:chart`.
+const CHART_ORDER: &[&str] = &[
+    "title",
+    "autoTitleDeleted",
+    "pivotFmts",
+    "view3D",
+    "floor",
+    "sideWall",
+    "backWall",
+    "plotArea",
+    "legend",
+    "plotVisOnly",
+    "dispBlanksAs",
+    "showDLblsOverMax",
+    "extLst",
+];
+
+/// Schema order of the leading children of `c:ser`, up to its formatting.
+const SER_ORDER: &[&str] = &["idx", "order", "tx", "spPr"];
+
+// ---------------------------------------------------------------------------
+// Element XML
+// ---------------------------------------------------------------------------
+
+/// `<c:spPr>` for a fill and line, or `None` if both are inherited.
+fn sp_pr_xml(format: &ChartFormat) -> Option<String> {
+    if format.fill.is_none() && format.line.is_none() {
+        return None;
+    }
+    let mut xml = String::from("<c:spPr>");
+    if let Some(fill) = &format.fill {
+        fill.write_xml(&mut xml)
+            .unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"));
+    }
+    if let Some(line) = &format.line {
+        line.write_xml(&mut xml)
+            .unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"));
+    }
+    xml.push_str("</c:spPr>");
+    Some(xml)
+}
+
+/// `<c:txPr>` making `font` the default for the element's text.
+fn tx_pr_xml(font: &Font) -> String {
+    // Reuse Font::to_xml_string() but replace <a:rPr with <a:defRPr
+    let def_r_pr = font
+        .to_xml_string()
+        .replace("<a:rPr ", "<a:defRPr ")
+        .replace("<a:rPr/>", "<a:defRPr/>")
+        .replace("</a:rPr>", "</a:defRPr>");
+    format!(
+        "<c:txPr><a:bodyPr/><a:lstStyle/><a:p><a:pPr>{def_r_pr}</a:pPr>\
+         <a:endParaRPr lang=\"en-US\"/></a:p></c:txPr>"
+    )
+}
+
+/// `<c:title>`; without a text frame the title text is automatic.
+fn title_xml(text_frame: Option<&TextFrame>, format: Option<&ChartFormat>) -> String {
+    let mut xml = String::from("<c:title>");
+    if let Some(tf) = text_frame {
+        xml.push_str("<c:tx>");
+        tf.write_xml_with_tag(&mut xml, "c:rich")
+            .unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"));
+        xml.push_str("</c:tx>");
+    }
+    xml.push_str("<c:overlay val=\"0\"/>");
+    if let Some(sp_pr) = format.and_then(sp_pr_xml) {
+        xml.push_str(&sp_pr);
+    }
+    xml.push_str("</c:title>");
+    xml
+}
+
+fn val_xml(name: &str, val: impl std::fmt::Display) -> String {
+    format!("<c:{name} val=\"{val}\"/>")
+}
+
+fn bool_val(value: bool) -> &'static str {
+    if value {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+fn legend_xml(legend: &Legend) -> String {
+    let mut xml = String::from("<c:legend>");
+    xml.push_str(&val_xml("legendPos", legend.position().to_xml_str()));
+    for entry in legend.legend_entries() {
+        xml.push_str("<c:legendEntry>");
+        xml.push_str(&val_xml("idx", entry.index()));
+        if entry.is_deleted() {
+            xml.push_str(&val_xml("delete", "1"));
+        } else if let Some(font) = entry.font() {
+            xml.push_str(&tx_pr_xml(font));
+        }
+        xml.push_str("</c:legendEntry>");
+    }
+    if let Some(x) = legend.horz_offset() {
+        xml.push_str(
+            "<c:layout><c:manualLayout><c:xMode val=\"factor\"/><c:yMode val=\"factor\"/>",
+        );
+        xml.push_str(&val_xml("x", x));
+        xml.push_str("</c:manualLayout></c:layout>");
+    }
+    let overlay = legend.overlay() || !legend.include_in_layout();
+    xml.push_str(&val_xml("overlay", bool_val(overlay)));
+    if let Some(font) = legend.font() {
+        xml.push_str(&tx_pr_xml(font));
+    }
+    xml.push_str("</c:legend>");
+    xml
+}
+
+// ---------------------------------------------------------------------------
+// Axes
+// ---------------------------------------------------------------------------
+
+/// The settings of either axis model, in the form they are written.
+struct AxisStyle<'a> {
+    visible: bool,
+    /// `Some` if the axis has a title.
+    title: Option<Option<&'a AxisTitle>>,
+    major_tick_mark: XlTickMark,
+    minor_tick_mark: XlTickMark,
+    tick_label_position: XlTickLabelPosition,
+    /// `Some` if the gridlines are shown, with their format.
+    major_gridlines: Option<Option<&'a ChartFormat>>,
+    minor_gridlines: Option<Option<&'a ChartFormat>>,
+    crosses: XlAxisCrosses,
+    crosses_at: Option<f64>,
+    /// An explicitly set number format and whether it is source-linked.
+    number_format: Option<(&'a str, bool)>,
+    reverse_order: bool,
+    minimum_scale: Option<f64>,
+    maximum_scale: Option<f64>,
+    major_unit: Option<f64>,
+    minor_unit: Option<f64>,
+    format: Option<&'a ChartFormat>,
+    font: Option<&'a Font>,
+}
+
+/// Read the settings both axis models expose through identically named
+/// methods; value-axis-only settings are left empty.
+macro_rules! axis_style {
+    ($axis:expr) => {{
+        let axis = $axis;
+        AxisStyle {
+            visible: axis.visible(),
+            title: axis.has_title().then(|| axis.axis_title()),
+            major_tick_mark: axis.major_tick_mark(),
+            minor_tick_mark: axis.minor_tick_mark(),
+            tick_label_position: axis.tick_label_position(),
+            major_gridlines: axis
+                .has_major_gridlines()
+                .then(|| axis.major_gridline_format()),
+            minor_gridlines: axis
+                .has_minor_gridlines()
+                .then(|| axis.minor_gridline_format()),
+            crosses: axis.crosses(),
+            crosses_at: None,
+            number_format: axis.tick_labels().and_then(|t: &TickLabels| {
+                t.number_format
+                    .as_deref()
+                    .map(|f| (f, t.number_format_is_linked))
+            }),
+            reverse_order: axis.reverse_order(),
+            minimum_scale: None,
+            maximum_scale: None,
+            major_unit: None,
+            minor_unit: None,
+            format: axis.format(),
+            font: axis.tick_labels().and_then(TickLabels::font),
+        }
+    }};
+}
+
+fn gridlines_xml(name: &str, format: Option<&ChartFormat>) -> String {
+    match format.and_then(sp_pr_xml) {
+        Some(sp_pr) => format!("<c:{name}>{sp_pr}</c:{name}>"),
+        None => format!("<c:{name}/>"),
+    }
+}
+
+fn axis_edits(xml: &str, ax: &XmlNode, s: &AxisStyle<'_>) -> Vec<Edit> {
+    let mut edits = Vec::new();
+
+    if let Some(scaling) = ax.child("scaling") {
+        // Rewritten whole, since the writers emit an empty <c:scaling/>
+        let orientation = if s.reverse_order { "maxMin" } else { "minMax" };
+        let mut content = String::from("<c:scaling>");
+        if let Some(log_base) = scaling.child("logBase") {
+            content.push_str(&xml[log_base.range.clone()]);
+        }
+        content.push_str(&val_xml("orientation", orientation));
+        if let Some(max) = s.maximum_scale {
+            content.push_str(&val_xml("max", max));
+        }
+        if let Some(min) = s.minimum_scale {
+            content.push_str(&val_xml("min", min));
+        }
+        content.push_str("</c:scaling>");
+        edits.push((scaling.range.clone(), content));
+    }
+
+    let number_format = s.number_format.map(|(code, linked)| {
+        format!(
+            "<c:numFmt formatCode=\"{}\" sourceLinked=\"{}\"/>",
+            xml_escape(code),
+            bool_val(linked)
+        )
+    });
+    let is_value_axis = ax.name == "valAx";
+
+    let mut set = |name: &str, content: Option<String>| {
+        set_child(&mut edits, xml, ax, AXIS_ORDER, name, content);
+    };
+    set("delete", Some(val_xml("delete", bool_val(!s.visible))));
+    set(
+        "majorGridlines",
+        s.major_gridlines
+            .map(|f| gridlines_xml("majorGridlines", f)),
+    );
+    set(
+        "minorGridlines",
+        s.minor_gridlines
+            .map(|f| gridlines_xml("minorGridlines", f)),
+    );
+    set(
+        "title",
+        s.title.map(|t| {
+            title_xml(
+                t.and_then(|t| t.text_frame.as_ref()),
+                t.and_then(|t| t.format.as_ref()),
+            )
+        }),
+    );
+    if number_format.is_some() {
+        set("numFmt", number_format);
+    }
+    set(
+        "majorTickMark",
+        Some(val_xml("majorTickMark", s.major_tick_mark.to_xml_str())),
+    );
+    set(
+        "minorTickMark",
+        Some(val_xml("minorTickMark", s.minor_tick_mark.to_xml_str())),
+    );
+    set(
+        "tickLblPos",
+        Some(val_xml("tickLblPos", s.tick_label_position.to_xml_str())),
+    );
+    if let Some(sp_pr) = s.format.and_then(sp_pr_xml) {
+        set("spPr", Some(sp_pr));
+    }
+    if let Some(font) = s.font {
+        set("txPr", Some(tx_pr_xml(font)));
+    }
+    // A custom crossing point without a value leaves the XML as it is
+    match (s.crosses, s.crosses_at) {
+        (XlAxisCrosses::Custom, Some(at)) => {
+            set("crosses", None);
+            set("crossesAt", Some(val_xml("crossesAt", at)));
+        }
+        (XlAxisCrosses::Custom, None) => {}
+        (crosses, _) => {
+            set("crosses", Some(val_xml("crosses", crosses.to_xml_str())));
+            set("crossesAt", None);
+        }
+    }
+    if is_value_axis {
+        set("majorUnit", s.major_unit.map(|v| val_xml("majorUnit", v)));
+        set("minorUnit", s.minor_unit.map(|v| val_xml("minorUnit", v)));
+    }
+    edits
+}
+
+/// Whether a value axis runs horizontally (the X axis of XY charts).
+fn is_horizontal(ax: &XmlNode) -> bool {
+    ax.child("axPos")
+        .and_then(|p| p.attr("val"))
+        .is_some_and(|v| v == "b" || v == "t")
+}
+
+fn axes_edits(xml: &str, chart: &Chart, plot_area: &XmlNode) -> Vec<Edit> {
+    let cat_ax = plot_area
+        .children
+        .iter()
+        .find(|c| matches!(c.name.as_str(), "catAx" | "dateAx"));
+    let val_axes: Vec<&XmlNode> = plot_area.children_named("valAx").collect();
+    // XY and bubble charts have two value axes; the horizontal one plays
+    // the role of the category axis.
+    let (x_ax, y_ax) = match cat_ax {
+        Some(ax) => (Some(ax), val_axes.first().copied()),
+        None if val_axes.len() > 1 => (
+            val_axes.iter().copied().find(|a| is_horizontal(a)),
+            val_axes.iter().copied().find(|a| !is_horizontal(a)),
+        ),
+        None => (None, val_axes.first().copied()),
+    };
+
+    let mut edits = Vec::new();
+    if let (Some(ax), Some(axis)) = (x_ax, chart.category_axis()) {
+        let mut style = axis_style!(axis);
+        if style.number_format.is_none() && axis.number_format() != "General" {
+            style.number_format = Some((axis.number_format(), false));
+        }
+        edits.extend(axis_edits(xml, ax, &style));
+    }
+    if let (Some(ax), Some(axis)) = (y_ax, chart.value_axis()) {
+        let mut style = axis_style!(axis);
+        if style.number_format.is_none()
+            && (axis.number_format() != "General" || !axis.number_format_is_linked())
+        {
+            style.number_format = Some((axis.number_format(), axis.number_format_is_linked()));
+        }
+        style.crosses_at = axis.crosses_at();
+        style.minimum_scale = axis.minimum_scale();
+        style.maximum_scale = axis.maximum_scale();
+        style.major_unit = axis.major_unit();
+        style.minor_unit = axis.minor_unit();
+        edits.extend(axis_edits(xml, ax, &style));
+    }
+    edits
+}
+
+// ---------------------------------------------------------------------------
+// Chart
+// ---------------------------------------------------------------------------
+
+impl Chart {
+    /// Apply this chart's settings to a chart part.
+    ///
+    /// Meant for XML produced by [`ChartXmlWriter`](super::ChartXmlWriter),
+    /// which carries only data. The following are written from the model,
+    /// replacing what the XML had:
+    ///
+    /// - title (or its absence), legend (or its absence) and chart style;
+    /// - chart area fill/line and default font, when set;
+    /// - category and value axis visibility, title, gridlines, tick marks,
+    ///   tick label position, crossing point, orientation and scaling, and
+    ///   number format, fill/line and tick label font when set;
+    /// - fill/line of each series that has a format, matched by position.
+    ///
+    /// Series data, data labels, markers and plot properties are kept as
+    /// they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed or has no `<c:chart>`.
+    pub fn apply_to_chart_xml(&self, chart_xml: &[u8]) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(chart_xml)?;
+        let chart_space = XmlNode::parse(chart_xml)?;
+        let chart_node = chart_space.child("chart").ok_or_else(|| {
+            crate::error::PptxError::InvalidXml("chart part has no <c:chart>".to_string())
+        })?;
+        let mut edits = Vec::new();
+
+        if let Some(style) = self.chart_style() {
+            let content = Some(val_xml("style", style));
+            set_child(
+                &mut edits,
+                xml,
+                &chart_space,
+                CHART_SPACE_ORDER,
+                "style",
+                content,
+            );
+        }
+        if let Some(sp_pr) = self.chart_format().and_then(sp_pr_xml) {
+            set_child(
+                &mut edits,
+                xml,
+                &chart_space,
+                CHART_SPACE_ORDER,
+                "spPr",
+                Some(sp_pr),
+            );
+        }
+        if let Some(font) = self.font() {
+            let content = Some(tx_pr_xml(font));
+            set_child(
+                &mut edits,
+                xml,
+                &chart_space,
+                CHART_SPACE_ORDER,
+                "txPr",
+                content,
+            );
+        }
+
+        let title = self.has_title().then(|| {
+            let title = self.chart_title();
+            title_xml(
+                title.and_then(|t| t.text_frame.as_ref()),
+                title.and_then(|t| t.format.as_ref()),
+            )
+        });
+        let auto_title_deleted = val_xml("autoTitleDeleted", bool_val(!self.has_title()));
+        let legend = self.legend().filter(|_| self.has_legend()).map(legend_xml);
+        for (name, content) in [
+            ("title", title),
+            ("autoTitleDeleted", Some(auto_title_deleted)),
+            ("legend", legend),
+        ] {
+            set_child(&mut edits, xml, chart_node, CHART_ORDER, name, content);
+        }
+
+        if let Some(plot_area) = chart_node.child("plotArea") {
+            edits.extend(axes_edits(xml, self, plot_area));
+
+            let sers = plot_area
+                .children
+                .iter()
+                .filter(|c| c.name.ends_with("Chart"))
+                .flat_map(|plot| plot.children_named("ser"));
+            let series = self.plots().iter().flat_map(|plot| plot.series.iter());
+            for (ser, series) in sers.zip(series) {
+                let format = series.format().and_then(|f| {
+                    sp_pr_xml(&ChartFormat {
+                        fill: f.fill.clone(),
+                        line: f.line.clone(),
+                    })
+                });
+                if format.is_some() {
+                    set_child(&mut edits, xml, ser, SER_ORDER, "spPr", format);
+                }
+            }
+        }
+
+        Ok(apply_edits(xml, 0..xml.len(), edits).into_bytes())
+    }
+}
+
+#[cfg(test)]
+#[path = "apply_tests.rs"]
+mod tests;
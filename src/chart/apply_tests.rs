@@ -0,0 +1,119 @@
+use crate::chart::axis::AxisTitle;
+use crate::chart::data::{CategoryChartData, XyChartData};
+use crate::chart::xmlwriter::ChartXmlWriter;
+use crate::chart::{Chart, ChartFormat};
+use crate::dml::color::ColorFormat;
+use crate::dml::fill::FillFormat;
+use crate::enums::chart::{XlChartType, XlLegendPosition, XlTickMark};
+
+fn column_xml() -> String {
+    let mut data = CategoryChartData::new();
+    data.add_category("Q1");
+    data.add_category("Q2");
+    data.add_series("Sales", &[1.0, 2.0]);
+    data.add_series("Costs", &[3.0, 4.0]);
+    ChartXmlWriter::write_category(&data, XlChartType::ColumnClustered).unwrap()
+}
+
+fn apply(chart: &Chart, xml: &str) -> String {
+    String::from_utf8(chart.apply_to_chart_xml(xml.as_bytes()).unwrap()).unwrap()
+}
+
+#[test]
+fn test_apply_title_legend_and_style() {
+    let mut style = Chart::new(XlChartType::ColumnClustered);
+    style.set_title("Quarterly");
+    style.set_chart_style(Some(26));
+    style.set_legend_position(XlLegendPosition::Bottom);
+
+    let xml = apply(&style, &column_xml());
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(chart.title(), Some("Quarterly"));
+    assert_eq!(chart.chart_style(), Some(26));
+    assert!(chart.has_legend());
+    assert_eq!(chart.legend().unwrap().position(), XlLegendPosition::Bottom);
+    assert!(xml.contains("<c:autoTitleDeleted val=\"0\"/>"));
+    // The data is untouched
+    assert_eq!(chart.series().len(), 2);
+    assert_eq!(chart.series().get(1).unwrap().name(), "Costs");
+}
+
+#[test]
+fn test_apply_removes_title_and_legend() {
+    let mut with = Chart::new(XlChartType::ColumnClustered);
+    with.set_title("Gone");
+    with.set_has_legend(true);
+    let xml = apply(&with, &column_xml());
+
+    let mut without = Chart::new(XlChartType::ColumnClustered);
+    without.set_has_title(false);
+    without.set_has_legend(false);
+    let xml = apply(&without, &xml);
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert!(!chart.has_title());
+    assert!(!chart.has_legend());
+    assert!(xml.contains("<c:autoTitleDeleted val=\"1\"/>"));
+}
+
+#[test]
+fn test_apply_value_axis_settings() {
+    let mut style = Chart::new(XlChartType::ColumnClustered);
+    let axis = style.value_axis_mut().unwrap();
+    axis.set_minimum_scale(Some(0.0));
+    axis.set_maximum_scale(Some(10.0));
+    axis.set_major_unit(Some(2.5));
+    axis.set_has_major_gridlines(false);
+    axis.set_major_tick_mark(XlTickMark::Cross);
+    axis.set_axis_title(AxisTitle::from_text("Units"));
+    style.category_axis_mut().unwrap().set_reverse_order(true);
+
+    let xml = apply(&style, &column_xml());
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    let axis = chart.value_axis().unwrap();
+    assert_eq!(axis.minimum_scale(), Some(0.0));
+    assert_eq!(axis.maximum_scale(), Some(10.0));
+    assert_eq!(axis.major_unit(), Some(2.5));
+    assert!(!axis.has_major_gridlines());
+    assert_eq!(axis.major_tick_mark(), XlTickMark::Cross);
+    assert!(axis.has_title());
+    assert!(chart.category_axis().unwrap().reverse_order());
+}
+
+#[test]
+fn test_apply_series_and_chart_area_formats() {
+    let mut style = Chart::new(XlChartType::ColumnClustered);
+    style.set_chart_format(ChartFormat {
+        fill: Some(FillFormat::solid(ColorFormat::rgb(0, 0, 0xFF))),
+        line: None,
+    });
+    let mut source = Chart::from_xml(column_xml().as_bytes()).unwrap();
+    let series = source.series_mut().get_mut(1).unwrap();
+    series.format_mut().fill = Some(FillFormat::solid(ColorFormat::rgb(0xFF, 0, 0)));
+    *style.plots_mut() = source.plots().to_vec();
+
+    let xml = apply(&style, &column_xml());
+    assert!(xml.contains("0000FF"));
+    assert_eq!(xml.matches("FF0000").count(), 1);
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert!(chart.series().get(1).unwrap().format().is_some());
+    assert!(chart.chart_format().is_some());
+}
+
+#[test]
+fn test_apply_xy_axes_by_position() {
+    let mut data = XyChartData::new();
+    data.add_series("S").add_data_point(1.0, 2.0);
+    let xml = ChartXmlWriter::write_xy(&data, XlChartType::XyScatter).unwrap();
+
+    let mut style = Chart::new(XlChartType::XyScatter);
+    style
+        .value_axis_mut()
+        .unwrap()
+        .set_maximum_scale(Some(50.0));
+    style.category_axis_mut().unwrap().set_visible(false);
+    let xml = apply(&style, &xml);
+
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(chart.value_axis().unwrap().maximum_scale(), Some(50.0));
+    assert!(!chart.category_axis().unwrap().visible());
+}
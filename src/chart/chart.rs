@@ -267,6 +267,10 @@ impl Chart {
 
     /// Replace the chart data and regenerate the chart XML.
     ///
+    /// The XML is generated from scratch; to update a chart that is already
+    /// part of a presentation while keeping its formatting, use
+    /// [`Presentation::replace_chart_data`](crate::Presentation::replace_chart_data).
+    ///
     /// # Errors
     /// Returns an error if the chart type is unsupported or XML generation fails.
     pub fn replace_data(&self, chart_data: &CategoryChartData) -> PptxResult<String> {
@@ -3,7 +3,7 @@
 use crate::error::PptxResult;
 
 use super::super::super::xmlwriter::ChartXmlWriter;
-use super::chart_data::CategorySeriesData;
+use super::chart_data::{CategoryChartData, CategorySeriesData};
 
 // ---------------------------------------------------------------------------
 // ComboChartData
@@ -178,6 +178,20 @@ impl ComboChartData {
             .collect()
     }
 
+    /// Convert to a `CategoryChartData` holding all series in order,
+    /// regardless of their sub-chart type.
+    #[must_use]
+    pub fn to_category_chart_data(&self) -> CategoryChartData {
+        let mut data = CategoryChartData::with_number_format(&self.number_format);
+        for category in &self.categories {
+            data.add_category(category);
+        }
+        for series in &self.series {
+            data.add_series_with_options(series.name(), series.values());
+        }
+        data
+    }
+
     /// Generate the combo chart XML.
     ///
     /// # Errors
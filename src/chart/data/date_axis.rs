@@ -106,6 +106,14 @@ impl DateAxisChartData {
         self.date_format = format.to_string();
     }
 
+    /// The dates as Excel serial day numbers (1900 date system).
+    ///
+    /// Dates not written as `yyyy-mm-dd` or `yyyy/mm/dd` yield `None`.
+    #[must_use]
+    pub fn date_serials(&self) -> Vec<Option<f64>> {
+        self.dates.iter().map(|d| excel_serial(d)).collect()
+    }
+
     /// Convert to a `CategoryChartData` using the dates as category labels.
     /// This allows reuse of existing category chart XML generation.
     #[must_use]
@@ -127,3 +135,26 @@ impl Default for DateAxisChartData {
         Self::new()
     }
 }
+
+/// Days from 1899-12-30 (Excel's day zero in the 1900 date system) to a
+/// `yyyy-mm-dd` or `yyyy/mm/dd` date.
+fn excel_serial(date: &str) -> Option<f64> {
+    let mut parts = date.trim().split(['-', '/']);
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Days since 1970-01-01 (proleptic Gregorian), after Howard Hinnant's
+    // `days_from_civil`.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    // 1899-12-30 is 25569 days before the Unix epoch
+    #[allow(clippy::cast_precision_loss)]
+    Some((days + 25_569) as f64)
+}
@@ -15,6 +15,59 @@ pub use category::{
 pub use date_axis::DateAxisChartData;
 pub use xy::{XyChartData, XyDataPoint, XySeriesData};
 
+// ---------------------------------------------------------------------------
+// ChartData
+// ---------------------------------------------------------------------------
+
+/// Chart data of any supported kind.
+///
+/// Used by APIs that accept more than one kind of data, such as
+/// [`Presentation::replace_chart_data`](crate::Presentation::replace_chart_data).
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum ChartData {
+    /// Category data for bar, line, pie, area, radar and similar charts.
+    Category(CategoryChartData),
+    /// X/Y pairs for scatter charts.
+    Xy(XyChartData),
+    /// X/Y/size triples for bubble charts.
+    Bubble(BubbleChartData),
+    /// Category data split between a bar and a line plot.
+    Combo(ComboChartData),
+    /// Category data whose categories are dates.
+    DateAxis(DateAxisChartData),
+}
+
+impl From<CategoryChartData> for ChartData {
+    fn from(data: CategoryChartData) -> Self {
+        Self::Category(data)
+    }
+}
+
+impl From<XyChartData> for ChartData {
+    fn from(data: XyChartData) -> Self {
+        Self::Xy(data)
+    }
+}
+
+impl From<BubbleChartData> for ChartData {
+    fn from(data: BubbleChartData) -> Self {
+        Self::Bubble(data)
+    }
+}
+
+impl From<ComboChartData> for ChartData {
+    fn from(data: ComboChartData) -> Self {
+        Self::Combo(data)
+    }
+}
+
+impl From<DateAxisChartData> for ChartData {
+    fn from(data: DateAxisChartData) -> Self {
+        Self::DateAxis(data)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Categories / CategoryLevel / Category
 // ---------------------------------------------------------------------------
@@ -95,6 +95,15 @@ fn test_date_axis_chart_data_set_date_format() {
     assert_eq!(data.date_format(), "mm/dd/yyyy");
 }
 
+#[test]
+fn test_date_axis_chart_data_date_serials() {
+    let mut data = DateAxisChartData::new();
+    data.add_date("2024-01-01");
+    data.add_date("1900/03/01");
+    data.add_date("Jan 2024");
+    assert_eq!(data.date_serials(), vec![Some(45292.0), Some(61.0), None]);
+}
+
 #[test]
 fn test_date_axis_chart_data_to_category() {
     let mut data = DateAxisChartData::new();
@@ -0,0 +1,109 @@
+//! Byte-range edits on chart XML.
+//!
+//! Chart parts are changed by splicing new markup into the source text at
+//! the element ranges recorded by [`XmlNode`], so that everything not being
+//! edited is carried over unchanged.
+
+use std::ops::Range;
+
+use super::parse::XmlNode;
+
+/// Schema order of the children of `c:catAx`, `c:valAx` and `c:dateAx`,
+/// merged into one sequence.
+pub(super) const AXIS_ORDER: &[&str] = &[
+    "axId",
+    "scaling",
+    "delete",
+    "axPos",
+    "majorGridlines",
+    "minorGridlines",
+    "title",
+    "numFmt",
+    "majorTickMark",
+    "minorTickMark",
+    "tickLblPos",
+    "spPr",
+    "txPr",
+    "crossAx",
+    "crosses",
+    "crossesAt",
+    "crossBetween",
+    "auto",
+    "lblAlgn",
+    "lblOffset",
+    "baseTimeUnit",
+    "majorUnit",
+    "majorTimeUnit",
+    "minorUnit",
+    "minorTimeUnit",
+    "tickLblSkip",
+    "tickMarkSkip",
+    "noMultiLvlLbl",
+    "dispUnits",
+    "extLst",
+];
+
+/// A replacement of `xml[range]`; an empty range inserts.
+pub(super) type Edit = (Range<usize>, String);
+
+/// Apply non-overlapping edits to `xml[within]`.
+///
+/// Edits are applied in document order; insertions at the same position
+/// keep the order in which they were pushed.
+pub(super) fn apply_edits(xml: &str, within: Range<usize>, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|(r, _)| (r.start, r.end));
+    let mut out = String::with_capacity(within.len());
+    let mut cursor = within.start;
+    for (range, replacement) in edits {
+        out.push_str(&xml[cursor..range.start]);
+        out.push_str(&replacement);
+        cursor = range.end;
+    }
+    out.push_str(&xml[cursor..within.end]);
+    out
+}
+
+/// Set, replace or (with `None`) remove the child `name` of `node`.
+///
+/// A new child is placed according to `order`, the schema sequence of the
+/// parent's children: after the last existing child that precedes it, or
+/// else before the first existing child that follows it.
+pub(super) fn set_child(
+    edits: &mut Vec<Edit>,
+    xml: &str,
+    node: &XmlNode,
+    order: &[&str],
+    name: &str,
+    content: Option<String>,
+) {
+    if let Some(existing) = node.child(name) {
+        edits.push((existing.range.clone(), content.unwrap_or_default()));
+        return;
+    }
+    let Some(content) = content else {
+        return;
+    };
+    let rank = |child: &XmlNode| order.iter().position(|n| *n == child.name);
+    let own = order.iter().position(|n| *n == name).unwrap_or(order.len());
+    let at = node
+        .children
+        .iter()
+        .filter(|c| rank(c).is_some_and(|r| r < own))
+        .next_back()
+        .map(|c| c.range.end)
+        .or_else(|| {
+            node.children
+                .iter()
+                .find(|c| rank(c).is_some_and(|r| r > own))
+                .map(|c| c.range.start)
+        })
+        .unwrap_or_else(|| content_start(xml, node));
+    edits.push((at..at, content));
+}
+
+/// The position just after the start tag of `node`.
+fn content_start(xml: &str, node: &XmlNode) -> usize {
+    xml[node.range.clone()]
+        .find('>')
+        .map_or(node.range.start, |i| node.range.start + i + 1)
+}
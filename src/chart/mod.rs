@@ -20,18 +20,29 @@
 //! assert!(chart_xml.contains("<c:barChart>"));
 //! ```
 
+/// Write to a String (infallible - `fmt::Write` for `String` never fails).
+macro_rules! write_str {
+    ($dst:expr, $($arg:tt)*) => {
+        write!($dst, $($arg)*).unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"))
+    };
+}
+
 pub mod axis;
 // Allow module_inception: `chart::chart` mirrors the python-pptx structure
 // (`pptx.chart.chart.Chart`) for familiarity.
+mod apply;
 #[allow(clippy::module_inception)]
 pub mod chart;
 pub mod chart_format;
 pub mod chart_plot;
 pub mod data;
 pub mod datalabel;
+mod edit;
 pub mod legend;
 pub mod marker;
+mod parse;
 pub mod plot;
+mod rewrite;
 pub mod series;
 pub mod xlsx;
 pub mod xmlwriter;
@@ -40,8 +51,8 @@ pub mod xmlwriter;
 pub use axis::{AxisTitle, CategoryAxis, DateAxis, TickLabels, ValueAxis};
 pub use chart::{Chart, ChartFormat, ChartTitle, Plot};
 pub use data::{
-    BubbleChartData, Categories, Category, CategoryChartData, CategoryLevel, ComboChartData,
-    ComboSeriesData, ComboSeriesType, DateAxisChartData, XyChartData,
+    BubbleChartData, Categories, Category, CategoryChartData, CategoryLevel, ChartData,
+    ComboChartData, ComboSeriesData, ComboSeriesType, DateAxisChartData, XyChartData,
 };
 pub use datalabel::{DataLabel, DataLabels};
 pub use legend::{Legend, LegendEntry};
@@ -49,3 +60,5 @@ pub use marker::{Marker, MarkerFormat};
 pub use plot::PlotProperties;
 pub use series::{Point, Series, SeriesCollection, SeriesFormat};
 pub use xmlwriter::ChartXmlWriter;
+
+pub(crate) use rewrite::replace_series_data;
@@ -0,0 +1,836 @@
+//! Parsing of chart parts (`/ppt/charts/chartN.xml`) into the [`Chart`] model.
+//!
+//! Chart XML is deeply nested and mostly made of `val`-attribute leaf
+//! elements, so the part is first read into a small element tree which is
+//! then walked to fill in the model.
+
+use std::ops::Range;
+
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::enums::chart::{
+    XlAxisCrosses, XlChartType, XlDataLabelPosition, XlLegendPosition, XlMarkerStyle,
+    XlTickLabelPosition, XlTickMark,
+};
+use crate::error::{PptxError, PptxResult};
+use crate::shapes::parser::{parse_sp_pr, parse_text_frame_from_xml};
+use crate::xml_util::{local_name_str, offset};
+
+use super::axis::{AxisTitle, CategoryAxis, TickLabels, ValueAxis};
+use super::chart::{Chart, ChartFormat, ChartTitle, Plot};
+use super::datalabel::DataLabels;
+use super::legend::{Legend, LegendEntry};
+use super::marker::{Marker, MarkerFormat};
+use super::series::{Point, Series, SeriesFormat};
+
+/// An element of a parsed chart part.
+#[derive(Debug, Default)]
+pub(super) struct XmlNode {
+    /// Local name (namespace prefix stripped).
+    pub(super) name: String,
+    /// Attributes keyed by local name.
+    attrs: Vec<(String, String)>,
+    /// Concatenated text content of the element itself.
+    pub(super) text: String,
+    pub(super) children: Vec<XmlNode>,
+    /// Byte range of the whole element in the source XML.
+    pub(super) range: Range<usize>,
+}
+
+impl XmlNode {
+    /// Read `xml` into a tree and return its root element.
+    pub(super) fn parse(xml: &[u8]) -> PptxResult<Self> {
+        let mut reader = Reader::from_reader(xml);
+        let mut stack: Vec<Self> = Vec::new();
+        let mut root: Option<Self> = None;
+
+        loop {
+            let start = offset(&reader);
+            match reader.read_event()? {
+                Event::Start(ref e) => stack.push(Self::from_start(e, start)?),
+                Event::Empty(ref e) => {
+                    let mut node = Self::from_start(e, start)?;
+                    node.range.end = offset(&reader);
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(node),
+                        None => root = Some(node),
+                    }
+                }
+                Event::Text(ref t) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text
+                            .push_str(&t.decode().map_err(quick_xml::Error::from)?);
+                    }
+                }
+                Event::CData(ref t) => {
+                    if let Some(node) = stack.last_mut() {
+                        node.text
+                            .push_str(&t.decode().map_err(quick_xml::Error::from)?);
+                    }
+                }
+                Event::GeneralRef(ref r) => {
+                    if let Some(node) = stack.last_mut() {
+                        if let Some(ch) = r.resolve_char_ref()? {
+                            node.text.push(ch);
+                        } else {
+                            let entity = r.decode().map_err(quick_xml::Error::from)?;
+                            if let Some(resolved) = resolve_predefined_entity(&entity) {
+                                node.text.push_str(resolved);
+                            }
+                        }
+                    }
+                }
+                Event::End(_) => {
+                    if let Some(mut node) = stack.pop() {
+                        node.range.end = offset(&reader);
+                        match stack.last_mut() {
+                            Some(parent) => parent.children.push(node),
+                            None => root = Some(node),
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        root.ok_or_else(|| PptxError::InvalidXml("chart XML has no root element".to_string()))
+    }
+
+    fn from_start(e: &BytesStart<'_>, start: usize) -> PptxResult<Self> {
+        let qname = e.name();
+        let mut attrs = Vec::new();
+        for attr in e.attributes() {
+            let attr = attr.map_err(PptxError::XmlAttr)?;
+            let value = attr.unescape_value()?.into_owned();
+            attrs.push((local_name_str(attr.key.as_ref()).to_string(), value));
+        }
+        Ok(Self {
+            name: local_name_str(qname.as_ref()).to_string(),
+            attrs,
+            text: String::new(),
+            children: Vec::new(),
+            range: start..start,
+        })
+    }
+
+    /// The value of the attribute with local name `key`.
+    pub(super) fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The first child element named `name`.
+    pub(super) fn child(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// All child elements named `name`.
+    pub(super) fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Self> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// The first descendant reached by following `path` from this element.
+    pub(super) fn descend(&self, path: &[&str]) -> Option<&Self> {
+        path.iter().try_fold(self, |node, name| node.child(name))
+    }
+
+    /// The `val` attribute of the child element `name`.
+    fn child_val(&self, name: &str) -> Option<&str> {
+        self.child(name).and_then(|c| c.attr("val"))
+    }
+
+    /// An `xsd:boolean` `val` of the child element `name`.
+    ///
+    /// A present element without `val` means `true`, as in the schema.
+    fn child_bool(&self, name: &str) -> Option<bool> {
+        self.child(name)
+            .map(|c| !matches!(c.attr("val"), Some("0" | "false")))
+    }
+
+    fn child_parsed<T: std::str::FromStr>(&self, name: &str) -> Option<T> {
+        self.child_val(name).and_then(|v| v.parse().ok())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Data caches
+// ---------------------------------------------------------------------------
+
+/// The `(idx, text)` points of a `strCache`, `numCache` or `lvl` element,
+/// along with its `ptCount`.
+fn cache_points(cache: &XmlNode) -> (usize, Vec<(usize, &str)>) {
+    let points: Vec<(usize, &str)> = cache
+        .children_named("pt")
+        .filter_map(|pt| {
+            let idx = pt.attr("idx")?.parse().ok()?;
+            Some((idx, pt.child("v").map_or("", |v| v.text.as_str())))
+        })
+        .collect();
+    let count = cache
+        .child_parsed::<usize>("ptCount")
+        .unwrap_or_else(|| points.iter().map(|(i, _)| i + 1).max().unwrap_or(0));
+    (count, points)
+}
+
+/// The cache element of a data reference (`c:cat`, `c:val`, `c:tx`, ...).
+fn cache_of(data: &XmlNode) -> Option<&XmlNode> {
+    const CACHES: [(&str, &str); 5] = [
+        ("numRef", "numCache"),
+        ("strRef", "strCache"),
+        ("multiLvlStrRef", "multiLvlStrCache"),
+        ("numLit", ""),
+        ("strLit", ""),
+    ];
+    CACHES.iter().find_map(|(reference, cache)| {
+        let r = data.child(reference)?;
+        if cache.is_empty() {
+            Some(r)
+        } else {
+            r.child(cache)
+        }
+    })
+}
+
+/// Read the numbers cached under `data`; missing points are `None`.
+pub(super) fn numbers(data: &XmlNode) -> Vec<Option<f64>> {
+    let Some(cache) = cache_of(data) else {
+        return Vec::new();
+    };
+    let (count, points) = cache_points(cache);
+    let mut values = vec![None; count];
+    for (idx, text) in points {
+        if let Some(slot) = values.get_mut(idx) {
+            *slot = text.trim().parse().ok();
+        }
+    }
+    values
+}
+
+/// Read the labels cached under `data`; for multi-level categories the
+/// innermost (leaf) level is returned.
+pub(super) fn labels(data: &XmlNode) -> Vec<String> {
+    let Some(cache) = cache_of(data) else {
+        return Vec::new();
+    };
+    let cache = cache.child("lvl").unwrap_or(cache);
+    let (count, points) = cache_points(cache);
+    let mut values = vec![String::new(); count];
+    for (idx, text) in points {
+        if let Some(slot) = values.get_mut(idx) {
+            *slot = text.to_string();
+        }
+    }
+    values
+}
+
+/// The name of a series from its `c:tx` element.
+fn series_name(ser: &XmlNode) -> String {
+    ser.child("tx").map_or_else(String::new, |tx| {
+        tx.child("v").map_or_else(
+            || labels(tx).into_iter().next().unwrap_or_default(),
+            |v| v.text.clone(),
+        )
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Formatting
+// ---------------------------------------------------------------------------
+
+/// Fill and line of the `c:spPr` child of `node`.
+fn sp_pr_of(xml: &[u8], node: &XmlNode) -> PptxResult<Option<ChartFormat>> {
+    let Some(sp_pr) = node.child("spPr") else {
+        return Ok(None);
+    };
+    let (fill, line) = parse_sp_pr(&xml[sp_pr.range.clone()])?;
+    Ok(Some(ChartFormat { fill, line }))
+}
+
+fn series_format(format: Option<ChartFormat>) -> Option<SeriesFormat> {
+    format.map(|f| SeriesFormat {
+        fill: f.fill,
+        line: f.line,
+    })
+}
+
+/// Rich text of a `c:title` element, if it is not an automatic title.
+fn title_text(xml: &[u8], title: &XmlNode) -> PptxResult<Option<crate::text::TextFrame>> {
+    match title.descend(&["tx", "rich"]) {
+        Some(rich) => parse_text_frame_from_xml(&xml[rich.range.clone()]),
+        None => Ok(None),
+    }
+}
+
+fn data_labels(d_lbls: &XmlNode) -> DataLabels {
+    let mut labels = DataLabels::new();
+    let flag = |name| d_lbls.child_bool(name).unwrap_or(false);
+    labels.set_show_value(flag("showVal"));
+    labels.set_show_category_name(flag("showCatName"));
+    labels.set_show_series_name(flag("showSerName"));
+    labels.set_show_percent(flag("showPercent"));
+    labels.set_show_legend_key(flag("showLegendKey"));
+    labels.set_show_bubble_size(flag("showBubbleSize"));
+    labels.set_show_leader_lines(flag("showLeaderLines"));
+    if let Some(num_fmt) = d_lbls.child("numFmt") {
+        labels.set_number_format(num_fmt.attr("formatCode"));
+        labels.set_number_format_is_linked(num_fmt.attr("sourceLinked") == Some("1"));
+    }
+    labels.set_position(
+        d_lbls
+            .child_val("dLblPos")
+            .and_then(XlDataLabelPosition::from_xml_str),
+    );
+    labels
+}
+
+fn marker(xml: &[u8], node: &XmlNode) -> PptxResult<Marker> {
+    let style = node
+        .child_val("symbol")
+        .and_then(XlMarkerStyle::from_xml_str)
+        .unwrap_or(XlMarkerStyle::Automatic);
+    let mut marker = Marker::new(style);
+    marker.set_size(node.child_parsed("size"));
+    if let Some(format) = sp_pr_of(xml, node)? {
+        marker.set_format(MarkerFormat {
+            fill: format.fill,
+            line: format.line,
+        });
+    }
+    Ok(marker)
+}
+
+// ---------------------------------------------------------------------------
+// Chart type detection
+// ---------------------------------------------------------------------------
+
+/// Whether every series of a plot hides its markers.
+fn markers_hidden(plot: &XmlNode) -> bool {
+    let mut series = plot.children_named("ser").peekable();
+    series.peek().is_some()
+        && series
+            .all(|s| s.descend(&["marker", "symbol"]).and_then(|m| m.attr("val")) == Some("none"))
+}
+
+/// Whether every series of a plot draws no connecting line.
+fn lines_hidden(plot: &XmlNode) -> bool {
+    let mut series = plot.children_named("ser").peekable();
+    series.peek().is_some() && series.all(|s| s.descend(&["spPr", "ln", "noFill"]).is_some())
+}
+
+fn any_exploded(plot: &XmlNode) -> bool {
+    plot.children_named("ser")
+        .any(|s| s.child_parsed::<u32>("explosion").unwrap_or(0) > 0)
+}
+
+fn bar_chart_type(plot: &XmlNode, is_3d: bool) -> XlChartType {
+    use XlChartType as T;
+    let is_bar = plot.child_val("barDir") == Some("bar");
+    let grouping = plot.child_val("grouping").unwrap_or("clustered");
+    // 0 = clustered (or standard), 1 = stacked, 2 = 100% stacked
+    let stack = match grouping {
+        "stacked" => 1,
+        "percentStacked" => 2,
+        _ => 0,
+    };
+    let shape = if is_3d {
+        plot.child_val("shape")
+            .or_else(|| {
+                plot.children_named("ser")
+                    .find_map(|s| s.child_val("shape"))
+            })
+            .unwrap_or("box")
+    } else {
+        "flat"
+    };
+    let family: [[T; 3]; 2] = match shape {
+        "flat" => [
+            [T::ColumnClustered, T::ColumnStacked, T::ColumnStacked100],
+            [T::BarClustered, T::BarStacked, T::BarStacked100],
+        ],
+        "cone" | "coneToMax" => [
+            [T::ConeColClustered, T::ConeColStacked, T::ConeColStacked100],
+            [T::ConeBarClustered, T::ConeBarStacked, T::ConeBarStacked100],
+        ],
+        "cylinder" => [
+            [
+                T::CylinderColClustered,
+                T::CylinderColStacked,
+                T::CylinderColStacked100,
+            ],
+            [
+                T::CylinderBarClustered,
+                T::CylinderBarStacked,
+                T::CylinderBarStacked100,
+            ],
+        ],
+        "pyramid" | "pyramidToMax" => [
+            [
+                T::PyramidColClustered,
+                T::PyramidColStacked,
+                T::PyramidColStacked100,
+            ],
+            [
+                T::PyramidBarClustered,
+                T::PyramidBarStacked,
+                T::PyramidBarStacked100,
+            ],
+        ],
+        _ => [
+            [
+                T::ColumnClustered3D,
+                T::ColumnStacked3D,
+                T::ColumnStacked100_3D,
+            ],
+            [T::BarClustered3D, T::BarStacked3D, T::BarStacked100_3D],
+        ],
+    };
+    // Standard grouping on a 3-D column chart places the series one
+    // behind the other; the closest model type is the plain 3-D variant.
+    if is_3d && !is_bar && grouping == "standard" {
+        return match shape {
+            "cone" | "coneToMax" => T::ConeCol,
+            "cylinder" => T::CylinderCol,
+            "pyramid" | "pyramidToMax" => T::PyramidCol,
+            _ => T::ColumnClustered3D,
+        };
+    }
+    family[usize::from(is_bar)][stack]
+}
+
+/// Determine the chart type of a plot element (`c:barChart`, ...).
+fn plot_chart_type(plot: &XmlNode) -> Option<XlChartType> {
+    use XlChartType as T;
+    let grouping = plot.child_val("grouping").unwrap_or("standard");
+    let chart_type = match plot.name.as_str() {
+        "barChart" => bar_chart_type(plot, false),
+        "bar3DChart" => bar_chart_type(plot, true),
+        "lineChart" => match (grouping, markers_hidden(plot)) {
+            ("stacked", true) => T::LineStacked,
+            ("stacked", false) => T::LineMarkersStacked,
+            ("percentStacked", true) => T::LineStacked100,
+            ("percentStacked", false) => T::LineMarkersStacked100,
+            (_, true) => T::Line,
+            (_, false) => T::LineMarkers,
+        },
+        "line3DChart" => T::Line3D,
+        "pieChart" | "ofPieChart" if any_exploded(plot) => T::PieExploded,
+        "pieChart" | "ofPieChart" => T::Pie,
+        "pie3DChart" if any_exploded(plot) => T::ExplodedPie3D,
+        "pie3DChart" => T::Pie3D,
+        "doughnutChart" if any_exploded(plot) => T::DoughnutExploded,
+        "doughnutChart" => T::Doughnut,
+        "areaChart" => match grouping {
+            "stacked" => T::AreaStacked,
+            "percentStacked" => T::AreaStacked100,
+            _ => T::Area,
+        },
+        "area3DChart" => match grouping {
+            "stacked" => T::AreaStacked3D,
+            "percentStacked" => T::AreaStacked100_3D,
+            _ => T::Area3D,
+        },
+        "radarChart" => match plot.child_val("radarStyle") {
+            Some("filled") => T::RadarFilled,
+            _ if markers_hidden(plot) => T::Radar,
+            _ => T::RadarMarkers,
+        },
+        "scatterChart" => match plot.child_val("scatterStyle") {
+            Some("smoothMarker") if markers_hidden(plot) => T::XyScatterSmoothNoMarkers,
+            Some("smoothMarker") => T::XyScatterSmooth,
+            Some("smooth") => T::XyScatterSmoothNoMarkers,
+            Some("line") => T::XyScatterLinesNoMarkers,
+            Some("marker") => T::XyScatter,
+            _ if lines_hidden(plot) => T::XyScatter,
+            _ if markers_hidden(plot) => T::XyScatterLinesNoMarkers,
+            _ => T::XyScatterLines,
+        },
+        "bubbleChart" => {
+            let three_d = plot.child_bool("bubble3D").unwrap_or(false)
+                || plot
+                    .children_named("ser")
+                    .any(|s| s.child_bool("bubble3D").unwrap_or(false));
+            if three_d {
+                T::BubbleThreeDEffect
+            } else {
+                T::Bubble
+            }
+        }
+        "stockChart" if plot.children_named("ser").count() >= 4 => T::StockOHLC,
+        "stockChart" => T::StockHLC,
+        "surfaceChart" if plot.child_bool("wireframe").unwrap_or(false) => T::SurfaceTopWireframe,
+        "surfaceChart" => T::SurfaceTop,
+        "surface3DChart" if plot.child_bool("wireframe").unwrap_or(false) => T::SurfaceWireframe,
+        "surface3DChart" => T::Surface,
+        _ => return None,
+    };
+    Some(chart_type)
+}
+
+/// The chart type reported for the chart as a whole.
+fn overall_chart_type(plots: &[Plot]) -> Option<XlChartType> {
+    let first = plots.first()?.chart_type;
+    let has = |pred: fn(XlChartType) -> bool| plots.iter().any(|p| pred(p.chart_type));
+    if has(XlChartType::is_stock_type) && has(XlChartType::is_bar_or_column) {
+        // Volume-high-low-close: the volume bars share the plot area.
+        let stock = plots.iter().find(|p| p.chart_type.is_stock_type())?;
+        return Some(if stock.chart_type == XlChartType::StockOHLC {
+            XlChartType::StockVOHLC
+        } else {
+            XlChartType::StockVHLC
+        });
+    }
+    if plots.len() > 1 && has(XlChartType::is_column_type) && has(XlChartType::is_line_type) {
+        return Some(XlChartType::ColumnLineCombo);
+    }
+    Some(first)
+}
+
+// ---------------------------------------------------------------------------
+// Plots and series
+// ---------------------------------------------------------------------------
+
+fn parse_series(xml: &[u8], ser: &XmlNode, chart_type: XlChartType) -> PptxResult<Series> {
+    let index = ser.child_parsed("idx").unwrap_or(0);
+    let mut series = Series::new(&series_name(ser), index, chart_type);
+
+    if chart_type.is_category_type() {
+        series.set_values(ser.child("val").map(numbers).unwrap_or_default());
+    } else {
+        series.set_values(ser.child("yVal").map(numbers).unwrap_or_default());
+        series.set_x_values(ser.child("xVal").map(numbers).unwrap_or_default());
+        series.set_bubble_sizes(ser.child("bubbleSize").map(numbers).unwrap_or_default());
+    }
+
+    if let Some(format) = series_format(sp_pr_of(xml, ser)?) {
+        series.set_format(format);
+    }
+    if let Some(node) = ser.child("marker") {
+        series.set_marker(marker(xml, node)?);
+    }
+    if let Some(node) = ser.child("dLbls") {
+        series.set_data_labels(data_labels(node));
+    }
+    series.set_smooth(ser.child_bool("smooth").unwrap_or(false));
+    series.set_invert_if_negative(ser.child_bool("invertIfNegative").unwrap_or(false));
+
+    for d_pt in ser.children_named("dPt") {
+        let mut point = Point::new(d_pt.child_parsed("idx").unwrap_or(0));
+        if let Some(format) = series_format(sp_pr_of(xml, d_pt)?) {
+            point.set_format(format);
+        }
+        series.add_point(point);
+    }
+    Ok(series)
+}
+
+fn parse_plot(xml: &[u8], node: &XmlNode, chart_type: XlChartType) -> PptxResult<Plot> {
+    let mut plot = Plot::new(chart_type);
+    for ser in node.children_named("ser") {
+        plot.series.add(parse_series(xml, ser, chart_type)?);
+    }
+    if chart_type.is_category_type() {
+        plot.categories = node
+            .children_named("ser")
+            .find_map(|s| s.child("cat"))
+            .map(labels);
+    }
+
+    let props = &mut plot.plot_properties;
+    props.set_gap_width(node.child_parsed("gapWidth"));
+    props.set_overlap(node.child_parsed("overlap"));
+    props.set_vary_by_categories(node.child_bool("varyColors"));
+    props.set_bubble_scale(node.child_parsed("bubbleScale"));
+
+    if let Some(d_lbls) = node.child("dLbls") {
+        plot.set_data_labels(data_labels(d_lbls));
+    }
+    Ok(plot)
+}
+
+// ---------------------------------------------------------------------------
+// Axes and legend
+// ---------------------------------------------------------------------------
+
+/// Settings shared by all axis kinds, read once and applied to either model.
+struct AxisSettings {
+    visible: bool,
+    title: Option<AxisTitle>,
+    major_tick_mark: Option<XlTickMark>,
+    minor_tick_mark: Option<XlTickMark>,
+    tick_label_position: Option<XlTickLabelPosition>,
+    major_gridlines: Option<Option<ChartFormat>>,
+    minor_gridlines: Option<Option<ChartFormat>>,
+    crosses: Option<XlAxisCrosses>,
+    crosses_at: Option<f64>,
+    number_format: Option<(String, bool)>,
+    reverse_order: bool,
+    label_offset: Option<u32>,
+    format: Option<ChartFormat>,
+}
+
+impl AxisSettings {
+    fn read(xml: &[u8], ax: &XmlNode) -> PptxResult<Self> {
+        let gridlines = |name| -> PptxResult<Option<Option<ChartFormat>>> {
+            ax.child(name).map(|g| sp_pr_of(xml, g)).transpose()
+        };
+        let title = match ax.child("title") {
+            Some(t) => {
+                let mut title = AxisTitle::new();
+                if let Some(tf) = title_text(xml, t)? {
+                    title.set_text_frame(tf);
+                }
+                title.format = sp_pr_of(xml, t)?;
+                Some(title)
+            }
+            None => None,
+        };
+        Ok(Self {
+            visible: !ax.child_bool("delete").unwrap_or(false),
+            title,
+            major_tick_mark: ax
+                .child_val("majorTickMark")
+                .and_then(XlTickMark::from_xml_str),
+            minor_tick_mark: ax
+                .child_val("minorTickMark")
+                .and_then(XlTickMark::from_xml_str),
+            tick_label_position: ax
+                .child_val("tickLblPos")
+                .and_then(XlTickLabelPosition::from_xml_str),
+            major_gridlines: gridlines("majorGridlines")?,
+            minor_gridlines: gridlines("minorGridlines")?,
+            crosses: ax
+                .child_val("crosses")
+                .and_then(XlAxisCrosses::from_xml_str),
+            crosses_at: ax.child_parsed("crossesAt"),
+            number_format: ax.child("numFmt").map(|n| {
+                (
+                    n.attr("formatCode").unwrap_or("General").to_string(),
+                    n.attr("sourceLinked") == Some("1"),
+                )
+            }),
+            reverse_order: ax
+                .descend(&["scaling", "orientation"])
+                .and_then(|o| o.attr("val"))
+                == Some("maxMin"),
+            label_offset: ax.child_parsed("lblOffset"),
+            format: sp_pr_of(xml, ax)?,
+        })
+    }
+
+    fn tick_labels(&self) -> Option<TickLabels> {
+        if self.number_format.is_none() && self.label_offset.is_none() {
+            return None;
+        }
+        let mut labels = TickLabels::new();
+        if let Some((code, linked)) = &self.number_format {
+            labels.number_format = Some(code.clone());
+            labels.number_format_is_linked = *linked;
+        }
+        labels.offset = self.label_offset;
+        Some(labels)
+    }
+}
+
+/// Apply settings that both axis models expose through identically named
+/// methods.
+macro_rules! apply_axis_settings {
+    ($axis:expr, $s:expr) => {{
+        let axis = $axis;
+        let s = $s;
+        axis.set_visible(s.visible);
+        if let Some(title) = s.title.clone() {
+            axis.set_axis_title(title);
+        }
+        if let Some(v) = s.major_tick_mark {
+            axis.set_major_tick_mark(v);
+        }
+        if let Some(v) = s.minor_tick_mark {
+            axis.set_minor_tick_mark(v);
+        }
+        if let Some(v) = s.tick_label_position {
+            axis.set_tick_label_position(v);
+        }
+        axis.set_has_major_gridlines(s.major_gridlines.is_some());
+        if let Some(Some(format)) = s.major_gridlines.clone() {
+            *axis.major_gridline_format_mut() = format;
+        }
+        axis.set_has_minor_gridlines(s.minor_gridlines.is_some());
+        if let Some(Some(format)) = s.minor_gridlines.clone() {
+            *axis.minor_gridline_format_mut() = format;
+        }
+        if let Some(v) = s.crosses {
+            axis.set_crosses(v);
+        }
+        if s.crosses_at.is_some() {
+            axis.set_crosses(XlAxisCrosses::Custom);
+        }
+        if let Some((code, _)) = &s.number_format {
+            axis.set_number_format(code);
+        }
+        axis.set_reverse_order(s.reverse_order);
+        if let Some(labels) = s.tick_labels() {
+            axis.set_tick_labels(labels);
+        }
+        if let Some(format) = s.format.clone() {
+            axis.set_format(format);
+        }
+    }};
+}
+
+fn apply_category_axis(axis: &mut CategoryAxis, s: &AxisSettings) {
+    apply_axis_settings!(axis, s);
+}
+
+fn apply_value_axis(axis: &mut ValueAxis, ax: &XmlNode, s: &AxisSettings) {
+    apply_axis_settings!(&mut *axis, s);
+    if let Some((_, linked)) = &s.number_format {
+        axis.set_number_format_is_linked(*linked);
+    }
+    axis.set_crosses_at(s.crosses_at);
+    let scaling = ax.child("scaling");
+    let scale = |name| scaling.and_then(|sc| sc.child_parsed::<f64>(name));
+    axis.set_minimum_scale(scale("min"));
+    axis.set_maximum_scale(scale("max"));
+    axis.set_major_unit(ax.child_parsed("majorUnit"));
+    axis.set_minor_unit(ax.child_parsed("minorUnit"));
+}
+
+/// Whether a value axis runs horizontally (the X axis of XY charts).
+fn is_horizontal(ax: &XmlNode) -> bool {
+    matches!(ax.child_val("axPos"), Some("b" | "t"))
+}
+
+fn apply_axes(xml: &[u8], chart: &mut Chart, plot_area: &XmlNode) -> PptxResult<()> {
+    let cat_ax = plot_area
+        .children
+        .iter()
+        .find(|c| matches!(c.name.as_str(), "catAx" | "dateAx"));
+    let val_axes: Vec<&XmlNode> = plot_area.children_named("valAx").collect();
+    // XY and bubble charts have two value axes; the horizontal one plays
+    // the role of the category axis.
+    let (x_ax, y_ax) = match cat_ax {
+        Some(ax) => (Some(ax), val_axes.first().copied()),
+        None if val_axes.len() > 1 => {
+            let x = val_axes.iter().copied().find(|a| is_horizontal(a));
+            let y = val_axes.iter().copied().find(|a| !is_horizontal(a));
+            (x, y)
+        }
+        None => (None, val_axes.first().copied()),
+    };
+
+    if let (Some(ax), Some(axis)) = (x_ax, chart.category_axis_mut()) {
+        apply_category_axis(axis, &AxisSettings::read(xml, ax)?);
+    }
+    if let (Some(ax), Some(axis)) = (y_ax, chart.value_axis_mut()) {
+        let settings = AxisSettings::read(xml, ax)?;
+        apply_value_axis(axis, ax, &settings);
+    }
+    Ok(())
+}
+
+fn parse_legend(legend_node: &XmlNode) -> Legend {
+    let mut legend = Legend::new();
+    if let Some(pos) = legend_node
+        .child_val("legendPos")
+        .and_then(XlLegendPosition::from_xml_str)
+    {
+        legend.set_position(pos);
+    }
+    let overlay = legend_node.child_bool("overlay").unwrap_or(false);
+    legend.set_overlay(overlay);
+    legend.set_include_in_layout(!overlay);
+    for entry_node in legend_node.children_named("legendEntry") {
+        let mut entry = LegendEntry::new(entry_node.child_parsed("idx").unwrap_or(0));
+        entry.set_deleted(entry_node.child_bool("delete").unwrap_or(false));
+        legend.add_legend_entry(entry);
+    }
+    let manual_x = legend_node
+        .descend(&["layout", "manualLayout", "x"])
+        .and_then(|x| x.attr("val"))
+        .and_then(|v| v.parse().ok());
+    legend.set_horz_offset(manual_x);
+    legend
+}
+
+/// The chart style index from `c:style`, which Office writes either
+/// directly or in the fallback branch of an `mc:AlternateContent` block
+/// (the `c14:style` choice uses a different numbering).
+fn chart_style(chart_space: &XmlNode) -> Option<u32> {
+    chart_space.child_parsed("style").or_else(|| {
+        chart_space
+            .children_named("AlternateContent")
+            .filter_map(|ac| ac.child("Fallback"))
+            .find_map(|fallback| fallback.child_parsed::<u32>("style"))
+    })
+}
+
+impl Chart {
+    /// Parse a chart part (`/ppt/charts/chartN.xml`) into a `Chart`.
+    ///
+    /// The chart type of every plot is derived from its element and
+    /// attributes (bar direction, grouping, markers, explosion, ...). Series
+    /// names, values and categories are read from the caches stored in the
+    /// part; the embedded workbook is not consulted. Axis settings, legend,
+    /// titles, data labels and fill/line formatting are read where present.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed, has no `<c:plotArea>`, or
+    /// contains no plot of a known chart type.
+    pub fn from_xml(xml: &[u8]) -> PptxResult<Self> {
+        let chart_space = XmlNode::parse(xml)?;
+        let chart_node = chart_space.child("chart").unwrap_or(&chart_space);
+        let plot_area = chart_node
+            .child("plotArea")
+            .ok_or_else(|| PptxError::InvalidXml("chart has no <c:plotArea>".to_string()))?;
+
+        let mut plots = Vec::new();
+        for node in &plot_area.children {
+            if let Some(chart_type) = plot_chart_type(node) {
+                plots.push(parse_plot(xml, node, chart_type)?);
+            }
+        }
+        let chart_type = overall_chart_type(&plots)
+            .ok_or_else(|| PptxError::InvalidXml("chart has no supported plot".to_string()))?;
+
+        let mut chart = Self::new(chart_type);
+        *chart.plots_mut() = plots;
+        apply_axes(xml, &mut chart, plot_area)?;
+
+        let auto_title_deleted = chart_node.child_bool("autoTitleDeleted").unwrap_or(false);
+        if let Some(title_node) = chart_node.child("title") {
+            let mut title = ChartTitle::new();
+            if let Some(tf) = title_text(xml, title_node)? {
+                title.set_text_frame(tf);
+            }
+            title.format = sp_pr_of(xml, title_node)?;
+            chart.set_chart_title(title);
+        } else if !auto_title_deleted && chart.series().len() == 1 {
+            // A single-series chart without an explicit title shows the
+            // series name as an automatic title.
+            chart.set_has_title(true);
+        }
+
+        if let Some(legend_node) = chart_node.child("legend") {
+            chart.set_has_legend(true);
+            if let Some(legend) = chart.legend_mut() {
+                *legend = parse_legend(legend_node);
+            }
+        }
+
+        chart.set_chart_style(chart_style(&chart_space));
+        if let Some(format) = sp_pr_of(xml, &chart_space)? {
+            chart.set_chart_format(format);
+        }
+        Ok(chart)
+    }
+}
+
+#[cfg(test)]
+#[path = "parse_tests.rs"]
+mod tests;
@@ -0,0 +1,201 @@
+use super::*;
+use crate::chart::data::{BubbleChartData, CategoryChartData, XyChartData};
+use crate::chart::xmlwriter::ChartXmlWriter;
+use crate::dml::color::ColorFormat;
+use crate::dml::fill::FillFormat;
+
+fn category_data() -> CategoryChartData {
+    let mut data = CategoryChartData::new();
+    data.add_category("Q1");
+    data.add_category("Q2 & Q3");
+    data.add_series("Sales", &[100.0, 150.0]);
+    data.add_series_with_options("Costs", &[Some(80.0), None]);
+    data
+}
+
+fn parse_written(chart_type: XlChartType) -> Chart {
+    let xml = ChartXmlWriter::write_category(&category_data(), chart_type).unwrap();
+    Chart::from_xml(xml.as_bytes()).unwrap()
+}
+
+#[test]
+fn test_from_xml_detects_written_chart_types() {
+    let types = [
+        XlChartType::ColumnClustered,
+        XlChartType::BarStacked,
+        XlChartType::ColumnStacked100,
+        XlChartType::Line,
+        XlChartType::LineMarkers,
+        XlChartType::LineMarkersStacked,
+        XlChartType::Pie,
+        XlChartType::PieExploded,
+        XlChartType::Doughnut,
+        XlChartType::Area,
+        XlChartType::AreaStacked100,
+        XlChartType::Radar,
+        XlChartType::RadarFilled,
+        XlChartType::ColumnClustered3D,
+    ];
+    for chart_type in types {
+        assert_eq!(parse_written(chart_type).chart_type(), chart_type);
+    }
+}
+
+#[test]
+fn test_from_xml_series_from_caches() {
+    let chart = parse_written(XlChartType::ColumnClustered);
+    assert_eq!(chart.plots().len(), 1);
+    let plot = &chart.plots()[0];
+    assert_eq!(
+        plot.categories.as_deref(),
+        Some(&["Q1".to_string(), "Q2 & Q3".to_string()][..])
+    );
+    assert_eq!(plot.series.len(), 2);
+    let sales = plot.series.get(0).unwrap();
+    assert_eq!(sales.name(), "Sales");
+    assert_eq!(sales.values(), &[Some(100.0), Some(150.0)]);
+    // Missing points keep their slot
+    let costs = plot.series.get(1).unwrap();
+    assert_eq!(costs.index(), 1);
+    assert_eq!(costs.values(), &[Some(80.0), None]);
+}
+
+#[test]
+fn test_from_xml_xy_and_bubble() {
+    let mut xy = XyChartData::new();
+    let s = xy.add_series("Points");
+    s.add_data_point(1.0, 2.5);
+    s.add_data_point(3.0, 4.5);
+    let xml = ChartXmlWriter::write_xy(&xy, XlChartType::XyScatter).unwrap();
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::XyScatter);
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.x_values(), &[Some(1.0), Some(3.0)]);
+    assert_eq!(series.values(), &[Some(2.5), Some(4.5)]);
+    assert!(chart.plots()[0].categories.is_none());
+
+    let xml = ChartXmlWriter::write_xy(&xy, XlChartType::XyScatterSmoothNoMarkers).unwrap();
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::XyScatterSmoothNoMarkers);
+
+    let mut bubble = BubbleChartData::new();
+    bubble.add_series("B").add_data_point(1.0, 2.0, 10.0);
+    let xml = ChartXmlWriter::write_bubble(&bubble, XlChartType::Bubble).unwrap();
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::Bubble);
+    assert_eq!(chart.series().get(0).unwrap().bubble_sizes(), &[Some(10.0)]);
+}
+
+const OFFICE_CHART: &str = r##"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" xmlns:c14="http://schemas.microsoft.com/office/drawing/2007/8/2/chart">
+<mc:AlternateContent><mc:Choice Requires="c14"><c14:style val="102"/></mc:Choice><mc:Fallback><c:style val="2"/></mc:Fallback></mc:AlternateContent>
+<c:chart>
+<c:title><c:tx><c:rich><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>Revenue</a:t></a:r></a:p></c:rich></c:tx><c:overlay val="0"/></c:title>
+<c:autoTitleDeleted val="0"/>
+<c:plotArea><c:layout/>
+<c:barChart><c:barDir val="bar"/><c:grouping val="clustered"/><c:varyColors val="0"/>
+<c:ser><c:idx val="0"/><c:order val="0"/><c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>2024</c:v></c:pt></c:strCache></c:strRef></c:tx>
+<c:spPr><a:solidFill><a:srgbClr val="4472C4"/></a:solidFill></c:spPr><c:invertIfNegative val="0"/>
+<c:dPt><c:idx val="1"/><c:invertIfNegative val="0"/><c:bubble3D val="0"/><c:spPr><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></c:spPr></c:dPt>
+<c:dLbls><c:numFmt formatCode="0.0%" sourceLinked="0"/><c:dLblPos val="outEnd"/><c:showLegendKey val="0"/><c:showVal val="1"/><c:showCatName val="0"/><c:showSerName val="0"/><c:showPercent val="0"/><c:showBubbleSize val="0"/></c:dLbls>
+<c:cat><c:strRef><c:f>Sheet1!$A$2:$A$3</c:f><c:strCache><c:ptCount val="2"/><c:pt idx="0"><c:v>East</c:v></c:pt><c:pt idx="1"><c:v>West</c:v></c:pt></c:strCache></c:strRef></c:cat>
+<c:val><c:numRef><c:f>Sheet1!$B$2:$B$3</c:f><c:numCache><c:formatCode>General</c:formatCode><c:ptCount val="2"/><c:pt idx="0"><c:v>1.5</c:v></c:pt><c:pt idx="1"><c:v>2</c:v></c:pt></c:numCache></c:numRef></c:val>
+</c:ser>
+<c:gapWidth val="80"/><c:overlap val="-10"/><c:axId val="1"/><c:axId val="2"/></c:barChart>
+<c:catAx><c:axId val="1"/><c:scaling><c:orientation val="maxMin"/></c:scaling><c:delete val="0"/><c:axPos val="l"/><c:numFmt formatCode="General" sourceLinked="1"/><c:majorTickMark val="none"/><c:minorTickMark val="none"/><c:tickLblPos val="low"/><c:crossAx val="2"/><c:crosses val="autoZero"/><c:auto val="1"/><c:lblAlgn val="ctr"/><c:lblOffset val="100"/></c:catAx>
+<c:valAx><c:axId val="2"/><c:scaling><c:orientation val="minMax"/><c:max val="5"/><c:min val="0"/></c:scaling><c:delete val="1"/><c:axPos val="b"/>
+<c:title><c:tx><c:rich><a:bodyPr/><a:p><a:r><a:t>Millions</a:t></a:r></a:p></c:rich></c:tx></c:title>
+<c:numFmt formatCode="#,##0" sourceLinked="0"/><c:majorTickMark val="out"/><c:minorTickMark val="none"/><c:tickLblPos val="nextTo"/><c:crossAx val="1"/><c:crosses val="max"/><c:crossBetween val="between"/><c:majorUnit val="0.5"/></c:valAx>
+</c:plotArea>
+<c:legend><c:legendPos val="t"/><c:legendEntry><c:idx val="0"/><c:delete val="1"/></c:legendEntry><c:overlay val="1"/></c:legend>
+<c:plotVisOnly val="1"/>
+</c:chart>
+<c:spPr><a:noFill/><a:ln><a:noFill/></a:ln></c:spPr>
+</c:chartSpace>"##;
+
+#[test]
+fn test_from_xml_office_chart_series_formatting() {
+    let chart = Chart::from_xml(OFFICE_CHART.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::BarClustered);
+    assert_eq!(chart.chart_style(), Some(2));
+    assert_eq!(chart.title(), Some("Revenue"));
+
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.name(), "2024");
+    assert_eq!(series.values(), &[Some(1.5), Some(2.0)]);
+    assert_eq!(
+        series.format().unwrap().fill,
+        Some(FillFormat::solid(ColorFormat::rgb(0x44, 0x72, 0xC4)))
+    );
+    assert_eq!(series.points().len(), 1);
+    assert_eq!(series.points()[0].index(), 1);
+    assert!(series.points()[0].format().unwrap().fill.is_some());
+
+    let labels = series.data_labels().unwrap();
+    assert!(labels.show_value());
+    assert!(!labels.show_category_name());
+    assert_eq!(labels.number_format(), Some("0.0%"));
+    assert!(!labels.number_format_is_linked());
+    assert_eq!(labels.position(), Some(XlDataLabelPosition::OutsideEnd));
+
+    let props = chart.plot_properties();
+    assert_eq!(props.gap_width(), Some(80));
+    assert_eq!(props.overlap(), Some(-10));
+    assert_eq!(props.vary_by_categories(), Some(false));
+}
+
+#[test]
+fn test_from_xml_office_chart_axes_and_legend() {
+    let chart = Chart::from_xml(OFFICE_CHART.as_bytes()).unwrap();
+
+    let cat = chart.category_axis().unwrap();
+    assert!(cat.visible());
+    assert!(cat.reverse_order());
+    assert_eq!(cat.major_tick_mark(), XlTickMark::None);
+    assert_eq!(cat.tick_label_position(), XlTickLabelPosition::Low);
+    assert!(!cat.has_major_gridlines());
+
+    let val = chart.value_axis().unwrap();
+    assert!(!val.visible());
+    assert_eq!(val.title(), Some("Millions"));
+    assert_eq!(val.maximum_scale(), Some(5.0));
+    assert_eq!(val.minimum_scale(), Some(0.0));
+    assert_eq!(val.major_unit(), Some(0.5));
+    assert_eq!(val.crosses(), XlAxisCrosses::Maximum);
+    assert_eq!(val.number_format(), "#,##0");
+    assert!(!val.number_format_is_linked());
+    assert!(!val.has_major_gridlines());
+
+    assert!(chart.has_legend());
+    let legend = chart.legend().unwrap();
+    assert_eq!(legend.position(), XlLegendPosition::Top);
+    assert!(legend.overlay());
+    assert_eq!(legend.legend_entries().len(), 1);
+    assert!(legend.legend_entries()[0].is_deleted());
+
+    let format = chart.chart_format().unwrap();
+    assert_eq!(format.fill, Some(FillFormat::NoFill));
+}
+
+#[test]
+fn test_from_xml_combo_chart() {
+    let xml = OFFICE_CHART.replace(
+        "</c:barChart>",
+        r#"</c:barChart><c:lineChart><c:grouping val="standard"/><c:ser><c:idx val="1"/><c:order val="1"/><c:marker><c:symbol val="none"/></c:marker><c:val><c:numLit><c:ptCount val="2"/><c:pt idx="0"><c:v>3</c:v></c:pt><c:pt idx="1"><c:v>4</c:v></c:pt></c:numLit></c:val></c:ser><c:axId val="1"/><c:axId val="2"/></c:lineChart>"#,
+    ).replace(r#"<c:barDir val="bar"/>"#, r#"<c:barDir val="col"/>"#);
+    let chart = Chart::from_xml(xml.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::ColumnLineCombo);
+    assert_eq!(chart.plots().len(), 2);
+    assert_eq!(chart.plots()[1].chart_type, XlChartType::Line);
+    let line = chart.plots()[1].series.get(0).unwrap();
+    assert_eq!(line.values(), &[Some(3.0), Some(4.0)]);
+    assert_eq!(line.marker().unwrap().style(), XlMarkerStyle::None);
+}
+
+#[test]
+fn test_from_xml_rejects_non_chart() {
+    assert!(Chart::from_xml(b"<c:chartSpace/>").is_err());
+    assert!(
+        Chart::from_xml(b"<c:chartSpace><c:chart><c:plotArea/></c:chart></c:chartSpace>").is_err()
+    );
+}
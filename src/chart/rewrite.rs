@@ -0,0 +1,442 @@
+//! Replacing the data of an existing chart part.
+//!
+//! Only the series data is rewritten: series names, the category and value
+//! caches and the worksheet formulas that point into the embedded workbook.
+//! Everything else in the part — series formatting, markers, data labels,
+//! axes — is kept as it is. Series are matched to the new data by position;
+//! surplus series are removed and missing ones are cloned from the last
+//! existing series, the same way python-pptx's `replace_data` behaves.
+
+use std::fmt::Write;
+use std::ops::Range;
+
+use crate::error::{PptxError, PptxResult};
+use crate::xml_util::xml_escape;
+
+use super::data::{
+    BubbleChartData, CategoryChartData, ChartData, ComboChartData, ComboSeriesType,
+    DateAxisChartData, XyChartData,
+};
+use super::edit::apply_edits;
+use super::parse::XmlNode;
+use super::xlsx::col_letter;
+
+/// Days between the 1900 and 1904 date systems.
+const DATE_1904_OFFSET: f64 = 1462.0;
+
+/// The new content of one `c:ser` element.
+struct SeriesSpec {
+    name: String,
+    /// Worksheet cell holding the name, or `None` to store it as a literal.
+    name_ref: Option<String>,
+    /// Data references in schema order, keyed by element local name.
+    data: Vec<(&'static str, DataRef)>,
+}
+
+/// A `numRef`, `strRef` or `multiLvlStrRef` data reference.
+#[derive(Clone)]
+enum DataRef {
+    Numbers {
+        formula: String,
+        format: String,
+        values: Vec<Option<f64>>,
+    },
+    Labels {
+        formula: String,
+        labels: Vec<String>,
+    },
+    MultiLevel {
+        formula: String,
+        levels: Vec<Vec<String>>,
+    },
+}
+
+/// `Sheet1!$X$2:$X$N` for `count` values in worksheet column `col`.
+fn column_range(col: usize, count: usize) -> String {
+    let c = col_letter(col);
+    format!("Sheet1!${c}$2:${c}${}", count + 1)
+}
+
+/// `Sheet1!$X$1`, the header cell of worksheet column `col`.
+fn header_cell(col: usize) -> String {
+    format!("Sheet1!${}$1", col_letter(col))
+}
+
+fn category_labels(data: &CategoryChartData) -> DataRef {
+    let count = data.categories().len();
+    match data.hierarchical_categories() {
+        Some(levels) if levels.len() > 1 => DataRef::MultiLevel {
+            formula: column_range(0, count),
+            levels: levels.clone(),
+        },
+        _ => DataRef::Labels {
+            formula: column_range(0, count),
+            labels: data.categories().to_vec(),
+        },
+    }
+}
+
+fn category_specs(data: &CategoryChartData) -> Vec<SeriesSpec> {
+    let categories = category_labels(data);
+    data.series()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| SeriesSpec {
+            name: s.name().to_string(),
+            name_ref: Some(header_cell(i + 1)),
+            data: vec![
+                ("cat", categories.clone()),
+                (
+                    "val",
+                    DataRef::Numbers {
+                        formula: column_range(i + 1, s.values().len()),
+                        format: s
+                            .number_format()
+                            .unwrap_or(data.number_format())
+                            .to_string(),
+                        values: s.values().to_vec(),
+                    },
+                ),
+            ],
+        })
+        .collect()
+}
+
+/// Combo series in document order: the bar plot is written before the line
+/// plot, while worksheet columns follow the order the series were added.
+fn combo_specs(data: &ComboChartData) -> Vec<SeriesSpec> {
+    let mut specs: Vec<(ComboSeriesType, SeriesSpec)> = data
+        .series()
+        .iter()
+        .zip(category_specs(&data.to_category_chart_data()))
+        .map(|(s, spec)| (s.combo_type, spec))
+        .collect();
+    specs.sort_by_key(|(t, _)| *t != ComboSeriesType::Bar);
+    specs.into_iter().map(|(_, spec)| spec).collect()
+}
+
+fn date_axis_specs(data: &DateAxisChartData, date_1904: bool) -> Vec<SeriesSpec> {
+    let serials: Option<Vec<f64>> = data.date_serials().into_iter().collect();
+    let count = data.dates().len();
+    let categories = match serials {
+        Some(serials) => DataRef::Numbers {
+            formula: column_range(0, count),
+            format: data.date_format().to_string(),
+            values: serials
+                .into_iter()
+                .map(|d| Some(if date_1904 { d - DATE_1904_OFFSET } else { d }))
+                .collect(),
+        },
+        None => DataRef::Labels {
+            formula: column_range(0, count),
+            labels: data.dates().to_vec(),
+        },
+    };
+    let mut specs = category_specs(&data.to_category_chart_data());
+    for spec in &mut specs {
+        spec.data[0].1 = categories.clone();
+    }
+    specs
+}
+
+fn numbers(col: usize, format: &str, values: &[f64]) -> DataRef {
+    DataRef::Numbers {
+        formula: column_range(col, values.len()),
+        format: format.to_string(),
+        values: values.iter().copied().map(Some).collect(),
+    }
+}
+
+fn xy_specs(data: &XyChartData) -> Vec<SeriesSpec> {
+    data.series()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let format = s.number_format().unwrap_or(data.number_format());
+            SeriesSpec {
+                name: s.name().to_string(),
+                name_ref: None,
+                data: vec![
+                    ("xVal", numbers(2 * i, format, &s.x_values())),
+                    ("yVal", numbers(2 * i + 1, format, &s.y_values())),
+                ],
+            }
+        })
+        .collect()
+}
+
+fn bubble_specs(data: &BubbleChartData) -> Vec<SeriesSpec> {
+    data.series()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let format = s.number_format().unwrap_or(data.number_format());
+            SeriesSpec {
+                name: s.name().to_string(),
+                name_ref: None,
+                data: vec![
+                    ("xVal", numbers(3 * i, format, &s.x_values())),
+                    ("yVal", numbers(3 * i + 1, format, &s.y_values())),
+                    ("bubbleSize", numbers(3 * i + 2, format, &s.bubble_sizes())),
+                ],
+            }
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// XML generation
+// ---------------------------------------------------------------------------
+
+fn points_to(w: &mut String, p: &str, labels: &[String]) {
+    for (idx, label) in labels.iter().enumerate() {
+        write_str!(
+            w,
+            "<{p}pt idx=\"{idx}\"><{p}v>{}</{p}v></{p}pt>",
+            xml_escape(label)
+        );
+    }
+}
+
+/// Write `<c:{tag}>` holding the data reference.
+fn data_ref_to(w: &mut String, p: &str, tag: &str, data: &DataRef) {
+    write_str!(w, "<{p}{tag}>");
+    match data {
+        DataRef::Numbers {
+            formula,
+            format,
+            values,
+        } => {
+            write_str!(
+                w,
+                "<{p}numRef><{p}f>{formula}</{p}f><{p}numCache>\
+                 <{p}formatCode>{}</{p}formatCode><{p}ptCount val=\"{}\"/>",
+                xml_escape(format),
+                values.len()
+            );
+            for (idx, value) in values.iter().enumerate() {
+                if let Some(v) = value {
+                    write_str!(w, "<{p}pt idx=\"{idx}\"><{p}v>{v}</{p}v></{p}pt>");
+                }
+            }
+            write_str!(w, "</{p}numCache></{p}numRef>");
+        }
+        DataRef::Labels { formula, labels } => {
+            write_str!(
+                w,
+                "<{p}strRef><{p}f>{formula}</{p}f><{p}strCache><{p}ptCount val=\"{}\"/>",
+                labels.len()
+            );
+            points_to(w, p, labels);
+            write_str!(w, "</{p}strCache></{p}strRef>");
+        }
+        DataRef::MultiLevel { formula, levels } => {
+            write_str!(
+                w,
+                "<{p}multiLvlStrRef><{p}f>{formula}</{p}f><{p}multiLvlStrCache>\
+                 <{p}ptCount val=\"{}\"/>",
+                levels.first().map_or(0, Vec::len)
+            );
+            for level in levels {
+                write_str!(w, "<{p}lvl>");
+                points_to(w, p, level);
+                write_str!(w, "</{p}lvl>");
+            }
+            write_str!(w, "</{p}multiLvlStrCache></{p}multiLvlStrRef>");
+        }
+    }
+    write_str!(w, "</{p}{tag}>");
+}
+
+fn tx_xml(p: &str, spec: &SeriesSpec) -> String {
+    let name = xml_escape(&spec.name);
+    spec.name_ref.as_ref().map_or_else(
+        || format!("<{p}tx><{p}v>{name}</{p}v></{p}tx>"),
+        |cell| {
+            format!(
+                "<{p}tx><{p}strRef><{p}f>{cell}</{p}f><{p}strCache><{p}ptCount val=\"1\"/>\
+                 <{p}pt idx=\"0\"><{p}v>{name}</{p}v></{p}pt></{p}strCache></{p}strRef></{p}tx>"
+            )
+        },
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Rewriting
+// ---------------------------------------------------------------------------
+
+/// The namespace prefix (including `:`) of the element starting at `start`.
+fn prefix_at(xml: &str, start: usize, local: &str) -> String {
+    let tag = &xml[start + 1..];
+    let qname_len = tag
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(tag.len());
+    tag[..qname_len]
+        .strip_suffix(local)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The rewritten XML of `ser`, optionally renumbered to `index`.
+fn rewrite_ser(xml: &str, ser: &XmlNode, spec: &SeriesSpec, index: Option<usize>) -> String {
+    let p = prefix_at(xml, ser.range.start, "ser");
+    let mut edits = Vec::new();
+
+    if let Some(index) = index {
+        for name in ["idx", "order"] {
+            if let Some(node) = ser.child(name) {
+                edits.push((node.range.clone(), format!("<{p}{name} val=\"{index}\"/>")));
+            }
+        }
+    }
+
+    let tx = tx_xml(&p, spec);
+    if let Some(node) = ser.child("tx") {
+        edits.push((node.range.clone(), tx));
+    } else if let Some(anchor) = ser.child("order").or_else(|| ser.child("idx")) {
+        edits.push((anchor.range.end..anchor.range.end, tx));
+    }
+
+    // Elements that may follow the data references in a series
+    let trailing = ["smooth", "shape", "invertIfNegative", "bubble3D", "extLst"];
+    for (i, (tag, data)) in spec.data.iter().enumerate() {
+        let mut content = String::new();
+        data_ref_to(&mut content, &p, tag, data);
+        if let Some(node) = ser.child(tag) {
+            edits.push((node.range.clone(), content));
+            continue;
+        }
+        let before = spec.data[..i].iter().rev().find_map(|(t, _)| ser.child(t));
+        let after = spec.data[i + 1..].iter().find_map(|(t, _)| ser.child(t));
+        let at = before
+            .map(|n| n.range.end)
+            .or_else(|| after.map(|n| n.range.start));
+        let at = at.unwrap_or_else(|| {
+            ser.children
+                .iter()
+                .find(|c| trailing.contains(&c.name.as_str()))
+                .map_or_else(
+                    || ser.children.last().map_or(ser.range.start, |c| c.range.end),
+                    |c| c.range.start,
+                )
+        });
+        edits.push((at..at, content));
+    }
+
+    apply_edits(xml, ser.range.clone(), edits)
+}
+
+fn series_specs(data: &ChartData, date_1904: bool) -> Vec<SeriesSpec> {
+    match data {
+        ChartData::Category(d) => category_specs(d),
+        ChartData::Xy(d) => xy_specs(d),
+        ChartData::Bubble(d) => bubble_specs(d),
+        ChartData::Combo(d) => combo_specs(d),
+        ChartData::DateAxis(d) => date_axis_specs(d, date_1904),
+    }
+}
+
+/// Check that `data` can be plotted by the plot elements of a chart.
+fn check_plot_kinds(plots: &[&XmlNode], data: &ChartData) -> PptxResult<()> {
+    let (expected, fits): (&str, fn(&str) -> bool) = match data {
+        ChartData::Xy(_) => ("a scatter chart", |n| n == "scatterChart"),
+        ChartData::Bubble(_) => ("a bubble chart", |n| n == "bubbleChart"),
+        _ => ("a category chart", |n| {
+            n != "scatterChart" && n != "bubbleChart"
+        }),
+    };
+    if plots.iter().all(|plot| fits(&plot.name)) {
+        Ok(())
+    } else {
+        Err(PptxError::InvalidValue {
+            field: "chart_data",
+            value: plots
+                .iter()
+                .map(|plot| plot.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            expected,
+        })
+    }
+}
+
+/// Replace the series data of a chart part with `data`.
+///
+/// Returns the updated chart XML.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed, has no plot with series, or
+/// the kind of data does not match the chart (e.g. XY data for a bar chart).
+pub(crate) fn replace_series_data(chart_xml: &[u8], data: &ChartData) -> PptxResult<Vec<u8>> {
+    let xml = std::str::from_utf8(chart_xml)?;
+    let chart_space = XmlNode::parse(chart_xml)?;
+    let chart_node = chart_space.child("chart").unwrap_or(&chart_space);
+    let plot_area = chart_node
+        .child("plotArea")
+        .ok_or_else(|| PptxError::InvalidXml("chart has no <c:plotArea>".to_string()))?;
+    let plots: Vec<&XmlNode> = plot_area
+        .children
+        .iter()
+        .filter(|c| c.name.ends_with("Chart"))
+        .collect();
+    check_plot_kinds(&plots, data)?;
+
+    let sers: Vec<(usize, &XmlNode)> = plots
+        .iter()
+        .enumerate()
+        .flat_map(|(i, plot)| plot.children_named("ser").map(move |s| (i, s)))
+        .collect();
+    let Some(&(last_plot, last_ser)) = sers.last() else {
+        return Err(PptxError::InvalidXml("chart has no series".to_string()));
+    };
+
+    let date_1904 = chart_space
+        .child("date1904")
+        .is_some_and(|n| matches!(n.attr("val"), Some("1" | "true") | None));
+    let specs = series_specs(data, date_1904);
+    if specs.is_empty() {
+        return Err(PptxError::InvalidValue {
+            field: "chart_data",
+            value: "no series".to_string(),
+            expected: "at least one series",
+        });
+    }
+
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for (&(_, ser), spec) in sers.iter().zip(&specs) {
+        edits.push((ser.range.clone(), rewrite_ser(xml, ser, spec, None)));
+    }
+
+    if specs.len() > sers.len() {
+        // New series copy the formatting of the last one
+        let mut next_index = sers
+            .iter()
+            .filter_map(|(_, s)| s.child("idx")?.attr("val")?.parse::<usize>().ok())
+            .max()
+            .map_or(0, |max| max + 1);
+        let mut clones = String::new();
+        for spec in &specs[sers.len()..] {
+            clones.push_str(&rewrite_ser(xml, last_ser, spec, Some(next_index)));
+            next_index += 1;
+        }
+        let at = last_ser.range.end;
+        edits.push((at..at, clones));
+    } else {
+        for &(_, ser) in &sers[specs.len()..] {
+            edits.push((ser.range.clone(), String::new()));
+        }
+        // Drop plots left without series
+        let last_kept_plot = sers[specs.len() - 1].0;
+        for plot in &plots[last_kept_plot + 1..=last_plot] {
+            edits.retain(|(r, _)| !(plot.range.start <= r.start && r.end <= plot.range.end));
+            edits.push((plot.range.clone(), String::new()));
+        }
+    }
+
+    Ok(apply_edits(xml, 0..xml.len(), edits).into_bytes())
+}
+
+#[cfg(test)]
+#[path = "rewrite_tests.rs"]
+mod tests;
@@ -0,0 +1,214 @@
+use super::*;
+use crate::chart::data::{BubbleChartData, CategoryChartData, ComboChartData, XyChartData};
+use crate::chart::xmlwriter::ChartXmlWriter;
+use crate::chart::Chart;
+use crate::enums::chart::XlChartType;
+
+fn category_data(series: &[(&str, &[f64])]) -> CategoryChartData {
+    let mut data = CategoryChartData::new();
+    data.add_category("East");
+    data.add_category("West");
+    data.add_category("North");
+    for (name, values) in series {
+        data.add_series(name, values);
+    }
+    data
+}
+
+/// A two-series column chart whose second series has a red fill.
+fn formatted_column_chart() -> String {
+    let mut data = CategoryChartData::new();
+    data.add_category("Q1");
+    data.add_category("Q2");
+    data.add_series("Sales", &[1.0, 2.0]);
+    data.add_series("Costs", &[3.0, 4.0]);
+    let xml = ChartXmlWriter::write_category(&data, XlChartType::ColumnClustered).unwrap();
+    let second_cat = xml.rfind("<c:cat>").unwrap();
+    let mut xml = xml;
+    xml.insert_str(
+        second_cat,
+        "<c:spPr><a:solidFill><a:srgbClr val=\"FF0000\"/></a:solidFill></c:spPr>",
+    );
+    xml
+}
+
+fn replace(xml: &str, data: impl Into<ChartData>) -> String {
+    String::from_utf8(replace_series_data(xml.as_bytes(), &data.into()).unwrap()).unwrap()
+}
+
+#[test]
+fn test_replace_category_data_updates_caches_and_formulas() {
+    let xml = formatted_column_chart();
+    let data = category_data(&[
+        ("Revenue", &[10.0, 20.0, 30.0]),
+        ("Profit", &[1.0, 2.0, 3.0]),
+    ]);
+    let updated = replace(&xml, data);
+
+    let chart = Chart::from_xml(updated.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::ColumnClustered);
+    let plot = &chart.plots()[0];
+    assert_eq!(
+        plot.categories.as_deref(),
+        Some(&["East".to_string(), "West".to_string(), "North".to_string()][..])
+    );
+    assert_eq!(plot.series.get(0).unwrap().name(), "Revenue");
+    assert_eq!(
+        plot.series.get(1).unwrap().values(),
+        &[Some(1.0), Some(2.0), Some(3.0)]
+    );
+
+    assert!(updated.contains("<c:f>Sheet1!$A$2:$A$4</c:f>"));
+    assert!(updated.contains("<c:f>Sheet1!$C$1</c:f>"));
+    assert!(updated.contains("<c:f>Sheet1!$C$2:$C$4</c:f>"));
+    // Formatting of the second series survives
+    assert_eq!(updated.matches("FF0000").count(), 1);
+}
+
+#[test]
+fn test_replace_category_data_clones_last_series_for_new_ones() {
+    let xml = formatted_column_chart();
+    let data = category_data(&[
+        ("A", &[1.0, 2.0, 3.0]),
+        ("B", &[4.0, 5.0, 6.0]),
+        ("C", &[7.0, 8.0, 9.0]),
+    ]);
+    let updated = replace(&xml, data);
+
+    assert_eq!(updated.matches("<c:ser>").count(), 3);
+    assert_eq!(updated.matches("FF0000").count(), 2);
+    assert!(updated.contains("<c:idx val=\"2\"/>"));
+    assert!(updated.contains("<c:order val=\"2\"/>"));
+    assert!(updated.contains("<c:f>Sheet1!$D$2:$D$4</c:f>"));
+    let chart = Chart::from_xml(updated.as_bytes()).unwrap();
+    assert_eq!(chart.series().get(2).unwrap().name(), "C");
+}
+
+#[test]
+fn test_replace_category_data_removes_surplus_series() {
+    let xml = formatted_column_chart();
+    let updated = replace(&xml, category_data(&[("Only", &[1.0, 2.0, 3.0])]));
+
+    assert_eq!(updated.matches("<c:ser>").count(), 1);
+    assert!(!updated.contains("FF0000"));
+    assert!(updated.contains("<c:barChart>"));
+    Chart::from_xml(updated.as_bytes()).unwrap();
+}
+
+#[test]
+fn test_replace_keeps_missing_points_out_of_cache() {
+    let xml = formatted_column_chart();
+    let mut data = CategoryChartData::new();
+    data.add_category("A");
+    data.add_category("B");
+    data.add_series_with_options("Gaps", &[None, Some(5.0)]);
+    let updated = replace(&xml, data);
+
+    assert!(updated.contains("<c:ptCount val=\"2\"/><c:pt idx=\"1\"><c:v>5</c:v>"));
+    let chart = Chart::from_xml(updated.as_bytes()).unwrap();
+    assert_eq!(chart.series().get(0).unwrap().values(), &[None, Some(5.0)]);
+}
+
+#[test]
+fn test_replace_xy_data() {
+    let mut data = XyChartData::new();
+    data.add_series("First").add_data_point(1.0, 2.0);
+    let xml = ChartXmlWriter::write_xy(&data, XlChartType::XyScatterLines).unwrap();
+
+    let mut data = XyChartData::new();
+    let s = data.add_series("One");
+    s.add_data_point(1.0, 10.0);
+    s.add_data_point(2.0, 20.0);
+    let s = data.add_series("Two");
+    s.add_data_point(3.0, 30.0);
+    let updated = replace(&xml, data);
+
+    assert!(updated.contains("<c:xVal><c:numRef><c:f>Sheet1!$C$2:$C$2</c:f>"));
+    assert!(updated.contains("<c:yVal><c:numRef><c:f>Sheet1!$D$2:$D$2</c:f>"));
+    assert!(updated.contains("<c:tx><c:v>Two</c:v></c:tx>"));
+    let chart = Chart::from_xml(updated.as_bytes()).unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::XyScatterLines);
+    let one = chart.series().get(0).unwrap();
+    assert_eq!(one.name(), "One");
+    assert_eq!(one.x_values(), &[Some(1.0), Some(2.0)]);
+    assert_eq!(one.values(), &[Some(10.0), Some(20.0)]);
+}
+
+#[test]
+fn test_replace_bubble_data() {
+    let mut data = BubbleChartData::new();
+    data.add_series("B").add_data_point(1.0, 2.0, 3.0);
+    let xml = ChartXmlWriter::write_bubble(&data, XlChartType::Bubble).unwrap();
+
+    let mut data = BubbleChartData::new();
+    let s = data.add_series("Sizes");
+    s.add_data_point(4.0, 5.0, 6.0);
+    s.add_data_point(7.0, 8.0, 9.0);
+    let updated = replace(&xml, data);
+
+    assert!(updated.contains("<c:bubbleSize><c:numRef><c:f>Sheet1!$C$2:$C$3</c:f>"));
+    let chart = Chart::from_xml(updated.as_bytes()).unwrap();
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.bubble_sizes(), &[Some(6.0), Some(9.0)]);
+    assert_eq!(series.x_values(), &[Some(4.0), Some(7.0)]);
+}
+
+#[test]
+fn test_replace_date_axis_data_uses_serials() {
+    let xml = ChartXmlWriter::write_category(
+        &category_data(&[("S", &[1.0, 2.0, 3.0])]),
+        XlChartType::Line,
+    )
+    .unwrap();
+    let mut data = DateAxisChartData::new();
+    data.add_date("2024-01-01");
+    data.add_date("2024-01-02");
+    data.add_series("Daily", &[5.0, 6.0]);
+
+    let updated = replace(&xml, data.clone());
+    assert!(updated.contains(
+        "<c:cat><c:numRef><c:f>Sheet1!$A$2:$A$3</c:f><c:numCache>\
+         <c:formatCode>yyyy-mm-dd</c:formatCode>"
+    ));
+    assert!(updated.contains("<c:v>45292</c:v>"));
+
+    // Charts in the 1904 date system count from a later day zero
+    let xml_1904 = xml.replacen("<c:date1904 val=\"0\"/>", "<c:date1904 val=\"1\"/>", 1);
+    let updated = replace(&xml_1904, data);
+    assert!(updated.contains("<c:v>43830</c:v>"));
+}
+
+#[test]
+fn test_replace_combo_data_follows_plot_order() {
+    let mut data = ComboChartData::new();
+    data.add_category("A");
+    data.add_series("Bars", ComboSeriesType::Bar, &[1.0]);
+    data.add_series("Line", ComboSeriesType::Line, &[2.0]);
+    let xml = ChartXmlWriter::write_combo(&data).unwrap();
+
+    let mut data = ComboChartData::new();
+    data.add_category("A");
+    data.add_series("New line", ComboSeriesType::Line, &[20.0]);
+    data.add_series("New bars", ComboSeriesType::Bar, &[10.0]);
+    let updated = replace(&xml, data);
+
+    let chart = Chart::from_xml(updated.as_bytes()).unwrap();
+    let bars = chart.plots()[0].series.get(0).unwrap();
+    assert_eq!(bars.name(), "New bars");
+    assert_eq!(bars.values(), &[Some(10.0)]);
+    // Worksheet columns follow the order the series were added
+    assert!(updated.contains("<c:f>Sheet1!$C$1</c:f>"));
+    assert_eq!(chart.plots()[1].series.get(0).unwrap().name(), "New line");
+}
+
+#[test]
+fn test_replace_rejects_mismatched_data() {
+    let xml = formatted_column_chart();
+    let mut data = XyChartData::new();
+    data.add_series("XY").add_data_point(1.0, 2.0);
+    let err = replace_series_data(xml.as_bytes(), &data.into());
+    assert!(matches!(err, Err(PptxError::InvalidValue { .. })));
+
+    let err = replace_series_data(xml.as_bytes(), &CategoryChartData::new().into());
+    assert!(matches!(err, Err(PptxError::InvalidValue { .. })));
+}
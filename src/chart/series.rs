@@ -154,6 +154,8 @@ pub struct Series {
     invert_if_negative: bool,
     format: Option<SeriesFormat>,
     values: Vec<Option<f64>>,
+    x_values: Vec<Option<f64>>,
+    bubble_sizes: Vec<Option<f64>>,
     points: Vec<Point>,
 }
 
@@ -171,6 +173,8 @@ impl Series {
             invert_if_negative: false,
             format: None,
             values: Vec::new(),
+            x_values: Vec::new(),
+            bubble_sizes: Vec::new(),
             points: Vec::new(),
         }
     }
@@ -264,6 +268,28 @@ impl Series {
         self.values = values;
     }
 
+    /// Read-access to the X values (XY and bubble series only).
+    #[must_use]
+    pub fn x_values(&self) -> &[Option<f64>] {
+        &self.x_values
+    }
+
+    /// Set the X values.
+    pub fn set_x_values(&mut self, values: Vec<Option<f64>>) {
+        self.x_values = values;
+    }
+
+    /// Read-access to the bubble sizes (bubble series only).
+    #[must_use]
+    pub fn bubble_sizes(&self) -> &[Option<f64>] {
+        &self.bubble_sizes
+    }
+
+    /// Set the bubble sizes.
+    pub fn set_bubble_sizes(&mut self, sizes: Vec<Option<f64>>) {
+        self.bubble_sizes = sizes;
+    }
+
     /// Per-data-point access.
     #[must_use]
     pub fn points(&self) -> &[Point] {
@@ -7,9 +7,12 @@
 #[path = "xlsx_parts.rs"]
 mod xlsx_parts;
 
-use crate::chart::data::{BubbleChartData, CategoryChartData, XyChartData};
+use crate::chart::data::{
+    BubbleChartData, CategoryChartData, ChartData, DateAxisChartData, XyChartData,
+};
 use crate::error::PptxResult;
 
+pub(crate) use xlsx_parts::col_letter;
 use xlsx_parts::{build_xlsx, shared_string_index, CellValue};
 
 /// Generate a minimal `.xlsx` file containing the data from a `CategoryChartData`.
@@ -68,6 +71,47 @@ pub fn generate_category_xlsx(data: &CategoryChartData) -> PptxResult<Vec<u8>> {
     build_xlsx(&rows, &shared_strings)
 }
 
+/// Generate a minimal `.xlsx` file containing the data from a `DateAxisChartData`.
+///
+/// The layout matches [`generate_category_xlsx`], except that dates are
+/// stored as serial day numbers where they can be parsed, so the workbook
+/// column holds real dates.
+///
+/// # Errors
+/// Returns an error if ZIP assembly fails.
+pub fn generate_date_axis_xlsx(data: &DateAxisChartData) -> PptxResult<Vec<u8>> {
+    let series = data.series();
+
+    let mut shared_strings: Vec<String> = series.iter().map(|s| s.name().to_string()).collect();
+    let mut rows: Vec<Vec<CellValue>> = Vec::new();
+
+    // Header row
+    let mut header = vec![CellValue::Empty];
+    header.extend((0..series.len()).map(CellValue::SharedString));
+    rows.push(header);
+
+    // Data rows
+    for (date_idx, (date, serial)) in data.dates().iter().zip(data.date_serials()).enumerate() {
+        let first = if let Some(serial) = serial {
+            CellValue::Number(serial)
+        } else {
+            shared_strings.push(date.clone());
+            CellValue::SharedString(shared_strings.len() - 1)
+        };
+        let mut row = vec![first];
+        for s in series {
+            if let Some(Some(v)) = s.values().get(date_idx) {
+                row.push(CellValue::Number(*v));
+            } else {
+                row.push(CellValue::Empty);
+            }
+        }
+        rows.push(row);
+    }
+
+    build_xlsx(&rows, &shared_strings)
+}
+
 /// Generate a minimal `.xlsx` file containing the data from an `XyChartData`.
 ///
 /// The worksheet layout is:
@@ -172,6 +216,23 @@ pub fn generate_bubble_xlsx(data: &BubbleChartData) -> PptxResult<Vec<u8>> {
     build_xlsx(&rows, &shared_strings)
 }
 
+/// Generate the embedded workbook for any kind of chart data.
+///
+/// Combo data is laid out like category data, with the series in the order
+/// they were added.
+///
+/// # Errors
+/// Returns an error if ZIP assembly fails.
+pub fn generate_xlsx(data: &ChartData) -> PptxResult<Vec<u8>> {
+    match data {
+        ChartData::Category(d) => generate_category_xlsx(d),
+        ChartData::Xy(d) => generate_xy_xlsx(d),
+        ChartData::Bubble(d) => generate_bubble_xlsx(d),
+        ChartData::Combo(d) => generate_category_xlsx(&d.to_category_chart_data()),
+        ChartData::DateAxis(d) => generate_date_axis_xlsx(d),
+    }
+}
+
 #[cfg(test)]
 #[path = "xlsx_tests.rs"]
 mod tests;
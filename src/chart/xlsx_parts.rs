@@ -24,7 +24,7 @@ pub(super) fn shared_string_index(shared_strings: &[String], value: &str) -> Opt
 }
 
 /// Convert a zero-based column index to an Excel column letter (0->"A", 25->"Z", 26->"AA").
-pub(super) fn col_letter(col: usize) -> String {
+pub(crate) fn col_letter(col: usize) -> String {
     let mut result = String::new();
     let mut n = col;
     loop {
@@ -256,3 +256,35 @@ fn test_generate_category_xlsx_missing_values() {
     assert!(sheet_xml.contains("<v>1</v>"));
     assert!(sheet_xml.contains("<v>3</v>"));
 }
+
+#[test]
+fn test_generate_date_axis_xlsx_stores_dates_as_serials() {
+    use crate::chart::data::DateAxisChartData;
+
+    let mut data = DateAxisChartData::new();
+    data.add_date("2024-01-01");
+    data.add_date("sometime");
+    data.add_series("Revenue", &[5.0, 6.0]);
+
+    let xlsx_bytes = generate_date_axis_xlsx(&data).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(&xlsx_bytes)).unwrap();
+
+    let mut sheet_xml = String::new();
+    archive
+        .by_name("xl/worksheets/sheet1.xml")
+        .unwrap()
+        .read_to_string(&mut sheet_xml)
+        .unwrap();
+    assert!(sheet_xml.contains("<v>45292</v>"));
+    assert!(sheet_xml.contains("<v>5</v>"));
+
+    let mut ss_xml = String::new();
+    archive
+        .by_name("xl/sharedStrings.xml")
+        .unwrap()
+        .read_to_string(&mut ss_xml)
+        .unwrap();
+    assert!(ss_xml.contains("<t>Revenue</t>"));
+    assert!(ss_xml.contains("<t>sometime</t>"));
+    assert!(!ss_xml.contains("2024-01-01"));
+}
@@ -144,4 +144,34 @@ mod tests {
         assert!(xml.contains("<c:v>H1</c:v>"));
         assert!(xml.contains("<c:v>H2</c:v>"));
     }
+
+    #[test]
+    fn test_date_axis_chart_xml() {
+        use crate::chart::data::DateAxisChartData;
+
+        let mut data = DateAxisChartData::new();
+        data.add_date("2024-01-01");
+        data.add_date("2024-01-08");
+        data.add_series("Weekly", &[3.0, 4.0]);
+
+        let xml = ChartXmlWriter::write_date_axis(&data, XlChartType::Line).unwrap();
+        assert!(xml.contains("<c:dateAx>"));
+        assert!(!xml.contains("<c:catAx>"));
+        assert!(xml.contains("<c:baseTimeUnit val=\"days\"/>"));
+        assert!(xml.contains("<c:numFmt formatCode=\"yyyy-mm-dd\" sourceLinked=\"1\"/>"));
+        assert!(!xml.contains("<c:lblAlgn"));
+        assert!(xml.contains("<c:v>45299</c:v>"));
+    }
+
+    #[test]
+    fn test_write_dispatches_on_data_kind() {
+        use crate::chart::data::{ChartData, XyChartData};
+
+        let mut data = XyChartData::new();
+        data.add_series("S").add_data_point(1.0, 2.0);
+        let data = ChartData::Xy(data);
+        let xml = ChartXmlWriter::write(&data, XlChartType::XyScatter).unwrap();
+        assert!(xml.contains("<c:scatterChart>"));
+        assert!(ChartXmlWriter::write(&data, XlChartType::ColumnClustered).is_err());
+    }
 }
@@ -0,0 +1,83 @@
+//! Date-axis chart XML writer.
+
+use crate::enums::chart::XlChartType;
+use crate::error::{PptxError, PptxResult};
+use crate::xml_util::xml_escape;
+
+use super::super::data::{ChartData, DateAxisChartData};
+use super::super::edit::{apply_edits, set_child, AXIS_ORDER};
+use super::super::parse::XmlNode;
+use super::super::rewrite::replace_series_data;
+use super::ChartXmlWriter;
+
+impl ChartXmlWriter {
+    /// Generate chart XML for a chart with a date category axis.
+    ///
+    /// The chart is laid out like a category chart of the same type, except
+    /// that dates which can be parsed are cached as serial day numbers and
+    /// the category axis is written as a `<c:dateAx>` using the data's date
+    /// format.
+    ///
+    /// # Errors
+    /// Returns an error if the chart type is unsupported.
+    pub fn write_date_axis(
+        data: &DateAxisChartData,
+        chart_type: XlChartType,
+    ) -> PptxResult<String> {
+        let xml = Self::write_category(&data.to_category_chart_data(), chart_type)?;
+        let xml = replace_series_data(xml.as_bytes(), &ChartData::DateAxis(data.clone()))?;
+        let xml = String::from_utf8(xml)?;
+
+        let chart_space = XmlNode::parse(xml.as_bytes())?;
+        let Some(cat_ax) = chart_space
+            .descend(&["chart", "plotArea"])
+            .and_then(|plot_area| plot_area.child("catAx"))
+        else {
+            // Pie and doughnut charts have no axes
+            return Ok(xml);
+        };
+
+        let start_tag = "<c:catAx";
+        let end_tag = "</c:catAx>";
+        if !xml[cat_ax.range.clone()].ends_with(end_tag) {
+            return Err(PptxError::InvalidXml(
+                "unexpected <c:catAx> in generated chart".to_string(),
+            ));
+        }
+        let mut edits = vec![
+            (
+                cat_ax.range.start..cat_ax.range.start + start_tag.len(),
+                "<c:dateAx".to_string(),
+            ),
+            (
+                cat_ax.range.end - end_tag.len()..cat_ax.range.end,
+                "</c:dateAx>".to_string(),
+            ),
+        ];
+        // Elements allowed on a catAx only
+        for name in ["lblAlgn", "noMultiLvlLbl"] {
+            set_child(&mut edits, &xml, cat_ax, AXIS_ORDER, name, None);
+        }
+        let num_fmt = format!(
+            "<c:numFmt formatCode=\"{}\" sourceLinked=\"1\"/>",
+            xml_escape(data.date_format())
+        );
+        set_child(
+            &mut edits,
+            &xml,
+            cat_ax,
+            AXIS_ORDER,
+            "numFmt",
+            Some(num_fmt),
+        );
+        set_child(
+            &mut edits,
+            &xml,
+            cat_ax,
+            AXIS_ORDER,
+            "baseTimeUnit",
+            Some("<c:baseTimeUnit val=\"days\"/>".to_string()),
+        );
+        Ok(apply_edits(&xml, 0..xml.len(), edits))
+    }
+}
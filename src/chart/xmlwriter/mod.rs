@@ -4,29 +4,23 @@
 //! chart data objects. Each chart type has its own XML structure following
 //! the OOXML `DrawingML` Chart specification.
 
-/// Write to a String (infallible - `fmt::Write` for `String` never fails).
-macro_rules! write_str {
-    ($dst:expr, $($arg:tt)*) => {
-        write!($dst, $($arg)*).unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"))
-    };
-}
-
 mod category_area_radar;
 mod category_bar_line;
 mod category_pie;
 mod category_tests;
 mod category_tests_3d;
 mod combo;
+mod date_axis;
 mod helpers;
 mod stock;
 mod surface;
 mod xy_bubble;
 
 use crate::enums::chart::XlChartType;
-use crate::error::PptxResult;
+use crate::error::{PptxError, PptxResult};
 use crate::xml_util::write_xml_escaped;
 
-use super::data::CategoryChartData;
+use super::data::{CategoryChartData, ChartData};
 
 /// Generates chart XML from chart data.
 pub struct ChartXmlWriter;
@@ -36,13 +30,37 @@ fn xml_escape_to(w: &mut String, s: &str) {
 }
 
 impl ChartXmlWriter {
+    /// Generate chart XML for any kind of chart data.
+    ///
+    /// `chart_type` selects the chart drawn for category and date-axis data
+    /// and the style of XY and bubble charts. It is ignored for combo data,
+    /// whose series carry their own bar or line type.
+    ///
+    /// # Errors
+    /// Returns an error if the chart type is unsupported or does not fit the
+    /// data (e.g. XY data with a bar chart type).
+    pub fn write(data: &ChartData, chart_type: XlChartType) -> PptxResult<String> {
+        match data {
+            ChartData::Category(d) => Self::write_category(d, chart_type),
+            ChartData::Xy(d) if chart_type.is_xy_type() => Self::write_xy(d, chart_type),
+            ChartData::Bubble(d) if chart_type.is_bubble_type() => {
+                Self::write_bubble(d, chart_type)
+            }
+            ChartData::Combo(d) => Self::write_combo(d),
+            ChartData::DateAxis(d) => Self::write_date_axis(d, chart_type),
+            ChartData::Xy(_) | ChartData::Bubble(_) => Err(PptxError::InvalidXml(format!(
+                "chart type {chart_type:?} cannot plot XY or bubble data"
+            ))),
+        }
+    }
+
     /// Generate chart XML for a category chart.
     ///
     /// # Errors
     /// Returns an error if the chart type is unsupported (e.g. combo or unknown type).
     pub fn write_category(data: &CategoryChartData, chart_type: XlChartType) -> PptxResult<String> {
         match chart_type {
-            ct if ct.is_combo_type() => Err(crate::error::PptxError::InvalidXml(
+            ct if ct.is_combo_type() => Err(PptxError::InvalidXml(
                 "combo charts require ComboChartData; use ComboChartData::to_xml() instead"
                     .to_string(),
             )),
@@ -54,7 +72,7 @@ impl ChartXmlWriter {
             ct if ct.is_doughnut_type() => Ok(Self::write_doughnut_chart(data, chart_type)),
             ct if ct.is_area_type() => Ok(Self::write_area_chart(data, chart_type)),
             ct if ct.is_radar_type() => Ok(Self::write_radar_chart(data, chart_type)),
-            _ => Err(crate::error::PptxError::InvalidXml(format!(
+            _ => Err(PptxError::InvalidXml(format!(
                 "unsupported category chart type: {chart_type:?}"
             ))),
         }
@@ -0,0 +1,375 @@
+//! Legacy comments: `<p:cmLst>` parts and the `<p:cmAuthorLst>` of
+//! `ppt/commentAuthors.xml`.
+//!
+//! `PowerPoint` 2013 to 2016 thread replies through a `<p15:threadingInfo>`
+//! extension naming the parent comment; such replies are read into the
+//! `replies` of their parent.
+
+use std::collections::HashMap;
+
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::Emu;
+use crate::xml_util::splice::{find_element, find_start_tag, get_attr, set_attr, splice};
+use crate::xml_util::{append_text, attr_value, local_name, xml_escape};
+
+use super::{append_child, attr_text, Comment};
+
+/// A new, empty `ppt/commentAuthors.xml`.
+pub(crate) const EMPTY_COMMENT_AUTHOR_LIST: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:cmAuthorLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"/>"#;
+
+/// An entry of the legacy comment author list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommentAuthor {
+    pub id: u32,
+    pub name: String,
+    pub initials: String,
+}
+
+/// Parse the `<p:cmAuthorLst>` of `ppt/commentAuthors.xml`.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(crate) fn parse_comment_authors(xml: &[u8]) -> PptxResult<Vec<CommentAuthor>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut authors = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e) | Event::Empty(ref e))
+                if local_name(e.name().as_ref()) == b"cmAuthor" =>
+            {
+                authors.push(CommentAuthor {
+                    id: attr_value(e, b"id")?
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or_default(),
+                    name: attr_text(e, b"name")?,
+                    initials: attr_text(e, b"initials")?,
+                });
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::Xml(e)),
+            _ => {}
+        }
+    }
+    Ok(authors)
+}
+
+/// The ID of a legacy comment, from its author and index.
+fn legacy_comment_id(author_id: &str, idx: &str) -> String {
+    format!("{author_id}-{idx}")
+}
+
+/// Parse a legacy `<p:cmLst>` comments part, nesting threaded replies
+/// under the comment they answer.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(crate) fn parse_legacy_comments(
+    xml: &[u8],
+    authors: &[CommentAuthor],
+) -> PptxResult<Vec<Comment>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+
+    // Each comment with the ID of the comment it replies to
+    let mut comments: Vec<(Comment, Option<String>)> = Vec::new();
+    let mut in_text = false;
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) if local_name(e.name().as_ref()) == b"text" => {
+                in_text = true;
+            }
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => match local_name(e.name().as_ref()) {
+                b"cm" => {
+                    let author_id = attr_value(e, b"authorId")?.unwrap_or_default();
+                    let idx = attr_value(e, b"idx")?.unwrap_or_default();
+                    let author_id: u32 = author_id.parse().unwrap_or_default();
+                    let author = authors.iter().find(|a| a.id == author_id);
+                    let mut comment =
+                        Comment::new(author.map_or("", |a| a.name.as_str()), "", Emu(0), Emu(0))
+                            .with_date(&attr_text(e, b"dt")?)
+                            .with_author_id(author_id);
+                    if let Some(author) = author {
+                        comment.author_initials.clone_from(&author.initials);
+                    }
+                    comment.id = Some(legacy_comment_id(&author_id.to_string(), &idx));
+                    comments.push((comment, None));
+                }
+                b"pos" => {
+                    if let Some((comment, _)) = comments.last_mut() {
+                        let coord = |key| -> PptxResult<Emu> {
+                            Ok(Emu(attr_value(e, key)?
+                                .and_then(|v| v.parse().ok())
+                                .unwrap_or_default()))
+                        };
+                        comment.position = (coord(b"x")?, coord(b"y")?);
+                    }
+                }
+                b"parentCm" => {
+                    if let Some((_, parent)) = comments.last_mut() {
+                        *parent = Some(legacy_comment_id(
+                            &attr_value(e, b"authorId")?.unwrap_or_default(),
+                            &attr_value(e, b"idx")?.unwrap_or_default(),
+                        ));
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if local_name(e.name().as_ref()) == b"text" => {
+                in_text = false;
+            }
+            Ok(ref event @ (Event::Text(_) | Event::GeneralRef(_))) if in_text => {
+                if let Some((comment, _)) = comments.last_mut() {
+                    append_text(event, &mut comment.text)?;
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::Xml(e)),
+            _ => {}
+        }
+    }
+
+    let mut threads: Vec<Comment> = Vec::new();
+    for (comment, parent) in comments {
+        let parent = parent.and_then(|id| {
+            threads
+                .iter_mut()
+                .find(|c| c.id.as_deref() == Some(id.as_str()))
+        });
+        match parent {
+            Some(parent) => parent.replies.push(comment),
+            None => threads.push(comment),
+        }
+    }
+    Ok(threads)
+}
+
+/// Remove the legacy comment `id` (`"{authorId}-{idx}"`), and any replies
+/// threaded to it, from a `<p:cmLst>` part.
+///
+/// Returns `None` if the part has no such comment.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8.
+pub(crate) fn remove_legacy_comment(xml: &[u8], id: &str) -> PptxResult<Option<Vec<u8>>> {
+    let Some((author_id, idx)) = id.split_once('-') else {
+        return Ok(None);
+    };
+    let is_comment = |tag: &str| {
+        get_attr(tag, "authorId") == Some(author_id) && get_attr(tag, "idx") == Some(idx)
+    };
+
+    let mut xml = std::str::from_utf8(xml)?.to_string();
+    let mut found = false;
+    let mut from = 0;
+    while let Some(cm) = find_element(&xml, "p:cm", from) {
+        let tag = find_start_tag(&xml, "p:cm", cm.start).unwrap_or(cm.clone());
+        let target = is_comment(&xml[tag]);
+        let reply = find_start_tag(&xml, "p15:parentCm", cm.start)
+            .filter(|parent| parent.end <= cm.end)
+            .is_some_and(|parent| is_comment(&xml[parent]));
+        if target || reply {
+            found |= target;
+            xml = splice(&xml, cm.clone(), "");
+            from = cm.start;
+        } else {
+            from = cm.end;
+        }
+    }
+    Ok(found.then(|| xml.into_bytes()))
+}
+
+/// Move the comments of a `<p:cmLst>` part copied from another
+/// presentation onto the authors of `authors_xml`.
+///
+/// Each comment's author is looked up by name among `source_authors`, the
+/// authors of the presentation it was copied from, and added to
+/// `authors_xml` if missing. Comments are renumbered after the author's
+/// last comment, and replies follow their parent. Returns the new author
+/// list and comments XML.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or the author list has no
+/// `<p:cmAuthorLst>`.
+pub(crate) fn merge_comment_authors(
+    authors_xml: &[u8],
+    source_authors: &[CommentAuthor],
+    comments_xml: &[u8],
+) -> PptxResult<(Vec<u8>, Vec<u8>)> {
+    let mut authors = std::str::from_utf8(authors_xml)?.to_string();
+    let mut comments = std::str::from_utf8(comments_xml)?.to_string();
+
+    // (author ID, idx) in the source -> the same in the destination
+    let mut ids: HashMap<(String, String), (String, String)> = HashMap::new();
+    let mut from = 0;
+    while let Some(tag) = find_start_tag(&comments, "p:cm", from) {
+        let author_id = get_attr(&comments[tag.clone()], "authorId").unwrap_or("0");
+        let idx = get_attr(&comments[tag.clone()], "idx").unwrap_or("0");
+        let (name, initials) = source_authors
+            .iter()
+            .find(|a| a.id.to_string() == author_id)
+            .map_or(("", ""), |a| (a.name.as_str(), a.initials.as_str()));
+        let new_author_id = or_add_comment_author(&mut authors, name, initials)?;
+        let new_idx = next_comment_idx(&mut authors, &new_author_id);
+        ids.insert(
+            (author_id.to_string(), idx.to_string()),
+            (new_author_id.clone(), new_idx.clone()),
+        );
+
+        let mut cm = set_attr(&comments[tag.clone()], "authorId", Some(&new_author_id));
+        cm = set_attr(&cm, "idx", Some(&new_idx));
+        from = tag.start + cm.len();
+        comments = splice(&comments, tag, &cm);
+    }
+
+    let mut from = 0;
+    while let Some(tag) = find_start_tag(&comments, "p15:parentCm", from) {
+        let parent = &comments[tag.clone()];
+        let key = (
+            get_attr(parent, "authorId").unwrap_or("0").to_string(),
+            get_attr(parent, "idx").unwrap_or("0").to_string(),
+        );
+        let mut parent = parent.to_string();
+        if let Some((author_id, idx)) = ids.get(&key) {
+            parent = set_attr(&parent, "authorId", Some(author_id));
+            parent = set_attr(&parent, "idx", Some(idx));
+        }
+        from = tag.start + parent.len();
+        comments = splice(&comments, tag, &parent);
+    }
+    Ok((authors.into_bytes(), comments.into_bytes()))
+}
+
+/// The ID of the author named `name` in `authors`, adding the author if
+/// there is none.
+fn or_add_comment_author(authors: &mut String, name: &str, initials: &str) -> PptxResult<String> {
+    let mut max_id = None;
+    let mut from = 0;
+    while let Some(tag) = find_start_tag(authors, "p:cmAuthor", from) {
+        let author = &authors[tag.clone()];
+        let id = get_attr(author, "id").unwrap_or("0");
+        let author_name = get_attr(author, "name").unwrap_or_default();
+        if unescape(author_name).is_ok_and(|n| n == name) {
+            return Ok(id.to_string());
+        }
+        max_id = max_id.max(id.parse::<u32>().ok());
+        from = tag.end;
+    }
+
+    let id = max_id.map_or(0, |id| id + 1);
+    let author = format!(
+        r#"<p:cmAuthor id="{id}" name="{}" initials="{}" lastIdx="0" clrIdx="{id}"/>"#,
+        xml_escape(name),
+        xml_escape(initials)
+    );
+    let list = find_element(authors, "p:cmAuthorLst", 0).ok_or_else(|| {
+        PptxError::InvalidXml("comment authors XML has no <p:cmAuthorLst>".to_string())
+    })?;
+    *authors = append_child(authors, list, "p:cmAuthorLst", &author);
+    Ok(id.to_string())
+}
+
+/// Claim the next comment index of the author `author_id` in `authors`.
+fn next_comment_idx(authors: &mut String, author_id: &str) -> String {
+    let mut from = 0;
+    while let Some(tag) = find_start_tag(authors, "p:cmAuthor", from) {
+        if get_attr(&authors[tag.clone()], "id") == Some(author_id) {
+            let last = get_attr(&authors[tag.clone()], "lastIdx")
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(0);
+            let idx = (last + 1).to_string();
+            let author = set_attr(&authors[tag.clone()], "lastIdx", Some(&idx));
+            *authors = splice(authors, tag, &author);
+            return idx;
+        }
+        from = tag.end;
+    }
+    "1".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHORS: &str = r#"<p:cmAuthorLst xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cmAuthor id="0" name="Jane Doe" initials="JD" lastIdx="2" clrIdx="0"/><p:cmAuthor id="1" name="Bob &amp; Co" initials="B" lastIdx="1" clrIdx="1"/></p:cmAuthorLst>"#;
+
+    const COMMENTS: &str = r#"<p:cmLst xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" xmlns:p15="http://schemas.microsoft.com/office/powerpoint/2012/main"><p:cm authorId="0" dt="2024-01-15T10:30:00.000" idx="1"><p:pos x="10" y="20"/><p:text>Check &lt;this&gt; figure</p:text></p:cm><p:cm authorId="1" dt="2024-01-16T09:00:00.000" idx="1"><p:pos x="10" y="20"/><p:text>Done</p:text><p:extLst><p:ext uri="{C676402C-5697-4E1C-873F-D02D1690AC5C}"><p15:threadingInfo timeZoneBias="0"><p15:parentCm authorId="0" idx="1"/></p15:threadingInfo></p:ext></p:extLst></p:cm><p:cm authorId="0" dt="2024-01-17T08:00:00.000" idx="2"><p:pos x="0" y="0"/><p:text>Typo</p:text></p:cm></p:cmLst>"#;
+
+    #[test]
+    fn test_parse_comment_authors() {
+        let authors = parse_comment_authors(AUTHORS.as_bytes()).unwrap();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[1].id, 1);
+        assert_eq!(authors[1].name, "Bob & Co");
+        assert_eq!(authors[1].initials, "B");
+    }
+
+    #[test]
+    fn test_parse_legacy_comments() {
+        let authors = parse_comment_authors(AUTHORS.as_bytes()).unwrap();
+        let comments = parse_legacy_comments(COMMENTS.as_bytes(), &authors).unwrap();
+        assert_eq!(comments.len(), 2);
+
+        let first = &comments[0];
+        assert_eq!(first.id.as_deref(), Some("0-1"));
+        assert_eq!(first.author, "Jane Doe");
+        assert_eq!(first.author_initials, "JD");
+        assert_eq!(first.text, "Check <this> figure");
+        assert_eq!(first.date, "2024-01-15T10:30:00.000");
+        assert_eq!(first.position, (Emu(10), Emu(20)));
+
+        // The threaded reply is nested under its parent
+        assert_eq!(first.replies.len(), 1);
+        assert_eq!(first.replies[0].author, "Bob & Co");
+        assert_eq!(first.replies[0].author_id, 1);
+        assert_eq!(first.replies[0].text, "Done");
+        assert_eq!(comments[1].id.as_deref(), Some("0-2"));
+    }
+
+    #[test]
+    fn test_remove_legacy_comment_with_replies() {
+        let xml = remove_legacy_comment(COMMENTS.as_bytes(), "0-1")
+            .unwrap()
+            .unwrap();
+        let comments = parse_legacy_comments(&xml, &[]).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "Typo");
+
+        assert!(remove_legacy_comment(COMMENTS.as_bytes(), "3-1")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_merge_comment_authors() {
+        let source = parse_comment_authors(AUTHORS.as_bytes()).unwrap();
+        let dest = r#"<p:cmAuthorLst xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cmAuthor id="0" name="Bob &amp; Co" initials="B" lastIdx="4" clrIdx="0"/></p:cmAuthorLst>"#;
+        let (authors, comments) =
+            merge_comment_authors(dest.as_bytes(), &source, COMMENTS.as_bytes()).unwrap();
+
+        // Jane Doe is added; Bob is matched by name and keeps counting
+        let authors = parse_comment_authors(&authors).unwrap();
+        assert_eq!(authors.len(), 2);
+        assert_eq!((authors[1].id, authors[1].name.as_str()), (1, "Jane Doe"));
+        let text = std::str::from_utf8(&comments).unwrap();
+        assert!(text.contains(r#"<p:cm authorId="1" dt="2024-01-15T10:30:00.000" idx="1">"#));
+        assert!(text.contains(r#"<p:cm authorId="0" dt="2024-01-16T09:00:00.000" idx="5">"#));
+        assert!(text.contains(r#"<p:cm authorId="1" dt="2024-01-17T08:00:00.000" idx="2">"#));
+        assert!(text.contains(r#"<p15:parentCm authorId="1" idx="1"/>"#));
+
+        let comments = parse_legacy_comments(text.as_bytes(), &authors).unwrap();
+        assert_eq!(comments[0].author, "Jane Doe");
+        assert_eq!(comments[0].replies[0].author, "Bob & Co");
+    }
+}
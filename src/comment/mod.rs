@@ -0,0 +1,204 @@
+//! Slide comments for `PowerPoint` presentations.
+//!
+//! Two formats are in use. Legacy comments are stored in
+//! `ppt/comments/commentN.xml` parts, with their authors listed in
+//! `ppt/commentAuthors.xml`. Modern threaded comments are stored in
+//! `ppt/comments/modernComment_*.xml` parts, with their authors in
+//! `ppt/authors.xml`; they add replies, a resolved state and anchoring to a
+//! shape. Both kinds of part are linked to their slide via relationships.
+
+mod legacy;
+mod modern;
+
+pub(crate) use legacy::{
+    merge_comment_authors, parse_comment_authors, parse_legacy_comments, remove_legacy_comment,
+    EMPTY_COMMENT_AUTHOR_LIST,
+};
+pub(crate) use modern::{
+    add_author, comment_xml, insert_comment, insert_reply, merge_authors, parse_authors,
+    parse_modern_comments, remove_modern_comment, reply_xml, set_comment_rel, set_comment_status,
+    set_slide_moniker, shape_creation_id, slide_creation_id, unique_guid, Anchor,
+    EMPTY_AUTHOR_LIST, EMPTY_COMMENT_LIST,
+};
+
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use quick_xml::escape::unescape;
+use quick_xml::events::BytesStart;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::{Emu, ShapeId};
+use crate::xml_util::attr_value;
+use crate::xml_util::splice::splice;
+
+/// A comment on a slide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// The author of the comment.
+    pub author: String,
+    /// The author's initials.
+    pub author_initials: String,
+    /// The comment text.
+    pub text: String,
+    /// The date/time string (ISO 8601 format).
+    pub date: String,
+    /// The anchor position on the slide (x, y) in EMU.
+    pub position: (Emu, Emu),
+    /// Author index (0-based, for `authorId` attribute).
+    pub author_id: u32,
+    /// The comment's ID, once it is on a slide: a GUID for threaded
+    /// comments, or `"{authorId}-{idx}"` for legacy ones.
+    pub id: Option<String>,
+    /// Replies to the comment, oldest first.
+    pub replies: Vec<Comment>,
+    /// Whether the comment thread has been resolved.
+    pub resolved: bool,
+    /// The shape the comment is anchored to; `None` for the slide itself.
+    pub shape_id: Option<ShapeId>,
+}
+
+impl Comment {
+    /// Create a new comment.
+    #[must_use]
+    pub fn new(author: &str, text: &str, x: Emu, y: Emu) -> Self {
+        // Generate initials from author name
+        let initials: String = author
+            .split_whitespace()
+            .filter_map(|w| w.chars().next())
+            .collect();
+
+        Self {
+            author: author.to_string(),
+            author_initials: initials,
+            text: text.to_string(),
+            date: String::new(),
+            position: (x, y),
+            author_id: 0,
+            id: None,
+            replies: Vec::new(),
+            resolved: false,
+            shape_id: None,
+        }
+    }
+
+    /// Builder method: attach a date/time to this comment.
+    #[must_use]
+    pub fn with_date(mut self, date: &str) -> Self {
+        self.date = date.to_string();
+        self
+    }
+
+    /// Builder method: set the author ID for this comment.
+    #[must_use]
+    pub const fn with_author_id(mut self, id: u32) -> Self {
+        self.author_id = id;
+        self
+    }
+
+    /// Builder method: anchor this comment to a shape on the slide.
+    #[must_use]
+    pub const fn with_shape_id(mut self, shape_id: ShapeId) -> Self {
+        self.shape_id = Some(shape_id);
+        self
+    }
+}
+
+/// The unescaped value of attribute `key`, or an empty string.
+fn attr_text(e: &BytesStart<'_>, key: &[u8]) -> PptxResult<String> {
+    let Some(value) = attr_value(e, key)? else {
+        return Ok(String::new());
+    };
+    Ok(unescape(&value)
+        .map_err(|e| PptxError::InvalidXml(e.to_string()))?
+        .into_owned())
+}
+
+/// Append `child` to the list element `qname` at `list`, expanding it
+/// if it is empty (`<qname/>`).
+fn append_child(xml: &str, list: Range<usize>, qname: &str, child: &str) -> String {
+    if xml[..list.end].ends_with("/>") {
+        let open = xml[list.start..list.end - 2].trim_end();
+        let replacement = format!("{open}>{child}</{qname}>");
+        splice(xml, list, &replacement)
+    } else {
+        let at = list.end - qname.len() - 3;
+        splice(xml, at..at, child)
+    }
+}
+
+/// The current UTC time in the form `PowerPoint` writes comment dates
+/// (`2024-01-15T10:30:00.000`).
+pub(crate) fn current_timestamp() -> String {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = elapsed.as_secs();
+    let days = i64::try_from(secs / 86_400).unwrap_or_default();
+    let time = secs % 86_400;
+
+    // Civil date from days since 1970-01-01, after Howard Hinnant's
+    // `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_new() {
+        let c = Comment::new("John Doe", "Nice slide!", Emu(100), Emu(200));
+        assert_eq!(c.author, "John Doe");
+        assert_eq!(c.author_initials, "JD");
+        assert_eq!(c.text, "Nice slide!");
+        assert_eq!(c.position, (Emu(100), Emu(200)));
+    }
+
+    #[test]
+    fn test_comment_with_date() {
+        let c =
+            Comment::new("Alice", "Review this", Emu(0), Emu(0)).with_date("2024-01-15T10:30:00Z");
+        assert_eq!(c.date, "2024-01-15T10:30:00Z");
+    }
+
+    #[test]
+    fn test_comment_initials_single_name() {
+        let c = Comment::new("Alice", "Test", Emu(0), Emu(0));
+        assert_eq!(c.author_initials, "A");
+    }
+
+    #[test]
+    fn test_comment_with_shape_id() {
+        let c = Comment::new("Alice", "Test", Emu(0), Emu(0)).with_shape_id(ShapeId(4));
+        assert_eq!(c.shape_id, Some(ShapeId(4)));
+        assert!(c.id.is_none());
+        assert!(c.replies.is_empty());
+        assert!(!c.resolved);
+    }
+
+    #[test]
+    fn test_current_timestamp_format() {
+        let now = current_timestamp();
+        assert_eq!(now.len(), 23);
+        assert_eq!(&now[4..5], "-");
+        assert_eq!(&now[10..11], "T");
+        assert_eq!(&now[19..20], ".");
+        assert!(now.as_str() > "2024");
+    }
+}
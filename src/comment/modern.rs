@@ -0,0 +1,695 @@
+//! Modern threaded comments: `<p188:cmLst>` parts and the
+//! `<p188:authorLst>` of `ppt/authors.xml`.
+//!
+//! Edits splice the serialized XML, so formatting, mentions and task
+//! details `PowerPoint` stores on existing comments are kept.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::{Emu, ShapeId, SlideId};
+use crate::xml_util::splice::{
+    find_element, find_element_in, find_start_tag, get_attr, inner_range, set_attr, splice,
+};
+use crate::xml_util::{append_text, attr_value, hashed_guid, local_name, xml_escape};
+
+use super::{append_child, attr_text, Comment};
+
+const P188_NS: &str = "http://schemas.microsoft.com/office/powerpoint/2018/8/main";
+const PC_NS: &str = "http://schemas.microsoft.com/office/powerpoint/2013/main/command";
+const AC_NS: &str = "http://schemas.microsoft.com/office/drawing/2013/main/command";
+
+/// URI of the slide extension pointing at the slide's modern comments part.
+const COMMENT_REL_EXT_URI: &str = "{6950BFC3-D8DA-4A85-94F7-54DA5524770B}";
+
+/// A new, empty `ppt/authors.xml`.
+pub(crate) const EMPTY_AUTHOR_LIST: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p188:authorLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p188="http://schemas.microsoft.com/office/powerpoint/2018/8/main"/>"#;
+
+/// A new, empty modern comments part.
+pub(crate) const EMPTY_COMMENT_LIST: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p188:cmLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p188="http://schemas.microsoft.com/office/powerpoint/2018/8/main"/>"#;
+
+/// An entry of `ppt/authors.xml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Author {
+    pub id: String,
+    pub name: String,
+    pub initials: String,
+}
+
+/// Where a new comment is anchored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Anchor {
+    /// The slide's `p14:creationId` (`"0"` if it has none).
+    pub slide_creation_id: String,
+    pub slide_id: SlideId,
+    /// The anchor shape and its `a16:creationId`, if any.
+    pub shape: Option<(ShapeId, Option<String>)>,
+}
+
+/// Parse the `<p188:authorLst>` of `ppt/authors.xml`.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(crate) fn parse_authors(xml: &[u8]) -> PptxResult<Vec<Author>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut authors = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e) | Event::Empty(ref e))
+                if local_name(e.name().as_ref()) == b"author" =>
+            {
+                authors.push(Author {
+                    id: attr_text(e, b"id")?,
+                    name: attr_text(e, b"name")?,
+                    initials: attr_text(e, b"initials")?,
+                });
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::Xml(e)),
+            _ => {}
+        }
+    }
+    Ok(authors)
+}
+
+/// Add an author to `ppt/authors.xml`, returning the new XML and the
+/// author's GUID.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or has no `<p188:authorLst>`.
+pub(crate) fn add_author(xml: &[u8], name: &str, initials: &str) -> PptxResult<(Vec<u8>, String)> {
+    let xml = std::str::from_utf8(xml)?;
+    let id = unique_guid(xml, &format!("author:{name}"));
+    let author = format!(
+        r#"<p188:author id="{id}" name="{name}" initials="{}" userId="{name}" providerId="None"/>"#,
+        xml_escape(initials),
+        name = xml_escape(name),
+    );
+    let list = find_element(xml, "p188:authorLst", 0)
+        .ok_or_else(|| PptxError::InvalidXml("authors XML has no <p188:authorLst>".to_string()))?;
+    Ok((
+        append_child(xml, list, "p188:authorLst", &author).into_bytes(),
+        id,
+    ))
+}
+
+/// Move the comments of a modern comments part copied from another
+/// presentation onto the authors of `authors_xml`.
+///
+/// An author whose ID `authors_xml` lists is kept. Otherwise the author is
+/// looked up in `source_authors_xml`, the author list of the presentation
+/// the comments were copied from, and matched by name or copied over.
+/// Returns the new author list and comments XML.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed or the author list has no
+/// `<p188:authorLst>`.
+pub(crate) fn merge_authors(
+    authors_xml: &[u8],
+    source_authors_xml: &[u8],
+    comments_xml: &[u8],
+) -> PptxResult<(Vec<u8>, Vec<u8>)> {
+    let mut authors = std::str::from_utf8(authors_xml)?.to_string();
+    let source_xml = std::str::from_utf8(source_authors_xml)?;
+    let mut comments = std::str::from_utf8(comments_xml)?.to_string();
+
+    // Source author ID -> destination author ID
+    let mut ids: HashMap<String, String> = HashMap::new();
+    for qname in ["p188:cm", "p188:reply"] {
+        let mut from = 0;
+        while let Some(tag) = find_start_tag(&comments, qname, from) {
+            let mut element = comments[tag.clone()].to_string();
+            if let Some(author_id) = get_attr(&element, "authorId").map(str::to_string) {
+                let new_id = match ids.get(&author_id) {
+                    Some(id) => id.clone(),
+                    None => {
+                        let id = or_add_source_author(&mut authors, source_xml, &author_id)?;
+                        ids.insert(author_id, id.clone());
+                        id
+                    }
+                };
+                element = set_attr(&element, "authorId", Some(&new_id));
+            }
+            from = tag.start + element.len();
+            comments = splice(&comments, tag, &element);
+        }
+    }
+    Ok((authors.into_bytes(), comments.into_bytes()))
+}
+
+/// The ID in `authors` of the author `id` of `source_xml`, adding the
+/// author if `authors` has no author of that ID or name.
+fn or_add_source_author(authors: &mut String, source_xml: &str, id: &str) -> PptxResult<String> {
+    let existing = parse_authors(authors.as_bytes())?;
+    if existing.iter().any(|a| a.id == id) {
+        return Ok(id.to_string());
+    }
+    let Some(source) = parse_authors(source_xml.as_bytes())?
+        .into_iter()
+        .find(|a| a.id == id)
+    else {
+        return Ok(id.to_string());
+    };
+    if let Some(author) = existing.iter().find(|a| a.name == source.name) {
+        return Ok(author.id.clone());
+    }
+
+    let list = find_element(authors, "p188:authorLst", 0)
+        .ok_or_else(|| PptxError::InvalidXml("authors XML has no <p188:authorLst>".to_string()))?;
+    match find_by_id(source_xml, "p188:author", id) {
+        Some(element) => {
+            *authors = append_child(authors, list, "p188:authorLst", &source_xml[element]);
+            Ok(id.to_string())
+        }
+        None => {
+            let (xml, id) = add_author(authors.as_bytes(), &source.name, &source.initials)?;
+            *authors = String::from_utf8(xml)?;
+            Ok(id)
+        }
+    }
+}
+
+/// A GUID that does not occur in `xml`, hashed from `seed`.
+pub(crate) fn unique_guid(xml: &str, seed: &str) -> String {
+    (0..)
+        .map(|n| hashed_guid(&format!("{n}:{seed}")))
+        .find(|id| !xml.contains(id.as_str()))
+        .unwrap_or_default()
+}
+
+/// Parse a modern `<p188:cmLst>` comments part.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(crate) fn parse_modern_comments(xml: &[u8], authors: &[Author]) -> PptxResult<Vec<Comment>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+
+    let mut comments: Vec<Comment> = Vec::new();
+    let mut reply: Option<Comment> = None;
+    let mut paragraphs = 0usize;
+    let mut in_t = false;
+    loop {
+        buf.clear();
+        let event = match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(event) => event,
+            Err(e) => return Err(PptxError::Xml(e)),
+        };
+        // The comment or reply being read
+        let current = match reply.as_mut() {
+            Some(reply) => Some(reply),
+            None => comments.last_mut(),
+        };
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => match local_name(e.name().as_ref()) {
+                name @ (b"cm" | b"reply") => {
+                    let author_id = attr_text(e, b"authorId")?;
+                    let index = authors.iter().position(|a| a.id == author_id);
+                    let author = index.map(|i| &authors[i]);
+                    let mut comment =
+                        Comment::new(author.map_or("", |a| a.name.as_str()), "", Emu(0), Emu(0))
+                            .with_date(&attr_text(e, b"created")?)
+                            .with_author_id(
+                                index
+                                    .and_then(|i| u32::try_from(i).ok())
+                                    .unwrap_or_default(),
+                            );
+                    if let Some(author) = author {
+                        comment.author_initials.clone_from(&author.initials);
+                    }
+                    comment.id = Some(attr_text(e, b"id")?);
+                    comment.resolved = attr_value(e, b"status")?.as_deref() == Some("resolved");
+                    paragraphs = 0;
+                    if name == b"cm" {
+                        comments.push(comment);
+                    } else {
+                        reply = Some(comment);
+                    }
+                }
+                b"pos" => {
+                    if let Some(comment) = current {
+                        let coord = |key| -> PptxResult<Emu> {
+                            Ok(Emu(attr_value(e, key)?
+                                .and_then(|v| v.parse().ok())
+                                .unwrap_or_default()))
+                        };
+                        comment.position = (coord(b"x")?, coord(b"y")?);
+                    }
+                }
+                b"spMk" => {
+                    if let Some(comment) = current {
+                        comment.shape_id = attr_value(e, b"id")?
+                            .and_then(|v| v.parse().ok())
+                            .map(ShapeId);
+                    }
+                }
+                b"p" => {
+                    if let Some(comment) = current {
+                        if paragraphs > 0 {
+                            comment.text.push('\n');
+                        }
+                        paragraphs += 1;
+                    }
+                }
+                b"br" => {
+                    if let Some(comment) = current {
+                        comment.text.push('\n');
+                    }
+                }
+                b"t" => in_t = matches!(event, Event::Start(_)),
+                _ => {}
+            },
+            Event::End(ref e) => match local_name(e.name().as_ref()) {
+                b"t" => in_t = false,
+                b"reply" => {
+                    if let (Some(done), Some(comment)) = (reply.take(), comments.last_mut()) {
+                        comment.replies.push(done);
+                    }
+                    paragraphs = 0;
+                }
+                _ => {}
+            },
+            Event::Text(_) | Event::GeneralRef(_) if in_t => {
+                if let Some(comment) = current {
+                    append_text(&event, &mut comment.text)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(comments)
+}
+
+/// The `<p188:txBody>` of a comment or reply, one paragraph per line.
+fn text_body_xml(text: &str) -> String {
+    let mut xml = String::from("<p188:txBody><a:bodyPr/><a:lstStyle/>");
+    for line in text.split('\n') {
+        if line.is_empty() {
+            xml.push_str("<a:p/>");
+        } else {
+            write!(xml, "<a:p><a:r><a:t>{}</a:t></a:r></a:p>", xml_escape(line))
+                .unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"));
+        }
+    }
+    xml.push_str("</p188:txBody>");
+    xml
+}
+
+/// A `<p188:cm>` element for a new comment.
+pub(crate) fn comment_xml(
+    id: &str,
+    author_id: &str,
+    created: &str,
+    comment: &Comment,
+    anchor: &Anchor,
+) -> String {
+    let slide = format!(
+        r#"<pc:docMk/><pc:sldMk cId="{}" sldId="{}"/>"#,
+        xml_escape(&anchor.slide_creation_id),
+        anchor.slide_id
+    );
+    let moniker = match &anchor.shape {
+        None => format!(r#"<pc:sldMkLst xmlns:pc="{PC_NS}">{slide}</pc:sldMkLst>"#),
+        Some((shape_id, creation_id)) => {
+            let creation_id = creation_id
+                .as_deref()
+                .map(|c| format!(r#" creationId="{}""#, xml_escape(c)))
+                .unwrap_or_default();
+            format!(
+                r#"<ac:deMkLst xmlns:ac="{AC_NS}" xmlns:pc="{PC_NS}">{slide}<ac:spMk id="{shape_id}"{creation_id}/></ac:deMkLst>"#
+            )
+        }
+    };
+    format!(
+        r#"<p188:cm id="{}" authorId="{}" created="{}">{moniker}<p188:pos x="{}" y="{}"/>{}</p188:cm>"#,
+        xml_escape(id),
+        xml_escape(author_id),
+        xml_escape(created),
+        comment.position.0,
+        comment.position.1,
+        text_body_xml(&comment.text)
+    )
+}
+
+/// A `<p188:reply>` element for a new reply.
+pub(crate) fn reply_xml(id: &str, author_id: &str, created: &str, text: &str) -> String {
+    format!(
+        r#"<p188:reply id="{}" authorId="{}" created="{}">{}</p188:reply>"#,
+        xml_escape(id),
+        xml_escape(author_id),
+        xml_escape(created),
+        text_body_xml(text)
+    )
+}
+
+/// Append `comment` (a `<p188:cm>` element) to a `<p188:cmLst>` part.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or has no `<p188:cmLst>`.
+pub(crate) fn insert_comment(xml: &[u8], comment: &str) -> PptxResult<Vec<u8>> {
+    let xml = std::str::from_utf8(xml)?;
+    let list = find_element(xml, "p188:cmLst", 0)
+        .ok_or_else(|| PptxError::InvalidXml("comments XML has no <p188:cmLst>".to_string()))?;
+    Ok(append_child(xml, list, "p188:cmLst", comment).into_bytes())
+}
+
+/// Append `reply` (a `<p188:reply>` element) to the replies of comment
+/// `comment_id`.
+///
+/// Returns `None` if the part has no such comment.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8.
+pub(crate) fn insert_reply(
+    xml: &[u8],
+    comment_id: &str,
+    reply: &str,
+) -> PptxResult<Option<Vec<u8>>> {
+    let xml = std::str::from_utf8(xml)?;
+    let Some(cm) = find_by_id(xml, "p188:cm", comment_id) else {
+        return Ok(None);
+    };
+    let updated = match find_element_in(xml, "p188:replyLst", cm.clone()) {
+        Some(list) => append_child(xml, list, "p188:replyLst", reply),
+        None => {
+            // The reply list goes just before the comment's own text
+            let at = find_start_tag(xml, "p188:txBody", cm.start)
+                .filter(|tag| tag.end <= cm.end)
+                .map_or(cm.end - "</p188:cm>".len(), |tag| tag.start);
+            splice(
+                xml,
+                at..at,
+                &format!("<p188:replyLst>{reply}</p188:replyLst>"),
+            )
+        }
+    };
+    Ok(Some(updated.into_bytes()))
+}
+
+/// Mark comment `comment_id` resolved, or active again.
+///
+/// Returns `None` if the part has no such comment.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8.
+pub(crate) fn set_comment_status(
+    xml: &[u8],
+    comment_id: &str,
+    resolved: bool,
+) -> PptxResult<Option<Vec<u8>>> {
+    let xml = std::str::from_utf8(xml)?;
+    let Some(cm) = find_by_id(xml, "p188:cm", comment_id) else {
+        return Ok(None);
+    };
+    let Some(tag) = find_start_tag(xml, "p188:cm", cm.start) else {
+        return Ok(None);
+    };
+    let status = resolved.then_some("resolved");
+    let updated = set_attr(&xml[tag.clone()], "status", status);
+    Ok(Some(splice(xml, tag, &updated).into_bytes()))
+}
+
+/// Remove comment or reply `id` from a `<p188:cmLst>` part; removing a
+/// comment removes its replies.
+///
+/// Returns `None` if the part has no such comment or reply.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8.
+pub(crate) fn remove_modern_comment(xml: &[u8], id: &str) -> PptxResult<Option<Vec<u8>>> {
+    let xml = std::str::from_utf8(xml)?;
+    if let Some(cm) = find_by_id(xml, "p188:cm", id) {
+        return Ok(Some(splice(xml, cm, "").into_bytes()));
+    }
+    let Some(reply) = find_by_id(xml, "p188:reply", id) else {
+        return Ok(None);
+    };
+    // A last reply takes its reply list with it
+    let mut from = 0;
+    let mut range = reply.clone();
+    while let Some(list) = find_element(xml, "p188:replyLst", from) {
+        if list.start < reply.start && reply.end <= list.end {
+            if xml[inner_range(xml, list.clone())].trim() == &xml[reply] {
+                range = list;
+            }
+            break;
+        }
+        from = list.end;
+    }
+    Ok(Some(splice(xml, range, "").into_bytes()))
+}
+
+/// Point the slide at its modern comments part through relationship
+/// `r_id`, or with `None` remove that link.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or has no `</p:sld>`.
+pub(crate) fn set_comment_rel(slide_xml: &[u8], r_id: Option<&str>) -> PptxResult<Vec<u8>> {
+    let mut xml = std::str::from_utf8(slide_xml)?.to_string();
+
+    // Drop the current link, and the slide's extension list if that leaves
+    // it empty
+    if let Some(ext_lst) = slide_ext_lst(&xml) {
+        let inner = inner_range(&xml, ext_lst.clone());
+        let mut from = inner.start;
+        while let Some(ext) = find_element_in(&xml, "p:ext", from..inner.end) {
+            let tag = find_start_tag(&xml, "p:ext", ext.start).unwrap_or(ext.clone());
+            if get_attr(&xml[tag], "uri") == Some(COMMENT_REL_EXT_URI) {
+                xml = splice(&xml, ext, "");
+                break;
+            }
+            from = ext.end;
+        }
+        if let Some(ext_lst) = slide_ext_lst(&xml) {
+            if xml[inner_range(&xml, ext_lst.clone())].trim().is_empty() {
+                xml = splice(&xml, ext_lst, "");
+            }
+        }
+    }
+    let Some(r_id) = r_id else {
+        return Ok(xml.into_bytes());
+    };
+
+    let ext = format!(
+        r#"<p:ext uri="{COMMENT_REL_EXT_URI}"><p188:commentRel xmlns:p188="{P188_NS}" r:id="{}"/></p:ext>"#,
+        xml_escape(r_id)
+    );
+    match slide_ext_lst(&xml) {
+        Some(ext_lst) => xml = append_child(&xml, ext_lst, "p:extLst", &ext),
+        None => {
+            let at = xml.rfind("</p:sld>").ok_or_else(|| {
+                PptxError::InvalidXml("slide XML does not contain </p:sld>".to_string())
+            })?;
+            xml.insert_str(at, &format!("<p:extLst>{ext}</p:extLst>"));
+        }
+    }
+    Ok(xml.into_bytes())
+}
+
+/// Anchor every comment of a modern comments part to the slide with ID
+/// `slide_id` and creation ID `slide_creation_id`, as when the comments are
+/// copied along with their slide.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8.
+pub(crate) fn set_slide_moniker(
+    xml: &[u8],
+    slide_id: SlideId,
+    slide_creation_id: &str,
+) -> PptxResult<Vec<u8>> {
+    let mut xml = std::str::from_utf8(xml)?.to_string();
+    let slide_id = slide_id.to_string();
+    let creation_id = xml_escape(slide_creation_id);
+    let mut from = 0;
+    while let Some(tag) = find_start_tag(&xml, "pc:sldMk", from) {
+        let mut moniker = set_attr(&xml[tag.clone()], "cId", Some(&creation_id));
+        moniker = set_attr(&moniker, "sldId", Some(&slide_id));
+        from = tag.start + moniker.len();
+        xml = splice(&xml, tag, &moniker);
+    }
+    Ok(xml.into_bytes())
+}
+
+/// The `p14:creationId` of a slide.
+pub(crate) fn slide_creation_id(slide_xml: &str) -> Option<String> {
+    let tag = find_start_tag(slide_xml, "p14:creationId", 0)?;
+    get_attr(&slide_xml[tag], "val").map(str::to_string)
+}
+
+/// The `a16:creationId` of a shape, from its XML.
+pub(crate) fn shape_creation_id(shape_xml: &str) -> Option<String> {
+    let tag = find_start_tag(shape_xml, "a16:creationId", 0)?;
+    get_attr(&shape_xml[tag], "id").map(str::to_string)
+}
+
+/// The `<p:extLst>` that is the last child of `<p:sld>`.
+fn slide_ext_lst(xml: &str) -> Option<Range<usize>> {
+    let end = xml.rfind("</p:sld>")?;
+    let last = xml[..end].trim_end().len();
+    let mut from = 0;
+    while let Some(ext_lst) = find_element(xml, "p:extLst", from) {
+        if ext_lst.end == last {
+            return Some(ext_lst);
+        }
+        from = ext_lst.end;
+    }
+    None
+}
+
+/// The element `qname` whose `id` attribute is `id`.
+fn find_by_id(xml: &str, qname: &str, id: &str) -> Option<Range<usize>> {
+    let id = xml_escape(id);
+    let mut from = 0;
+    while let Some(element) = find_element(xml, qname, from) {
+        let tag = find_start_tag(xml, qname, element.start)?;
+        if get_attr(&xml[tag], "id") == Some(id.as_ref()) {
+            return Some(element);
+        }
+        from = element.end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHORS: &str = r#"<p188:authorLst xmlns:p188="http://schemas.microsoft.com/office/powerpoint/2018/8/main"><p188:author id="{A1}" name="Jane Doe" initials="JD" userId="Jane Doe" providerId="None"/><p188:author id="{B2}" name="Bob" initials="B" userId="Bob" providerId="None"/></p188:authorLst>"#;
+
+    const COMMENTS: &str = r#"<p188:cmLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p188="http://schemas.microsoft.com/office/powerpoint/2018/8/main"><p188:cm id="{C1}" authorId="{A1}" created="2024-01-15T10:30:00.000" status="resolved"><ac:deMkLst xmlns:ac="http://schemas.microsoft.com/office/drawing/2013/main/command"><pc:docMk xmlns:pc="http://schemas.microsoft.com/office/powerpoint/2013/main/command"/><pc:sldMk xmlns:pc="http://schemas.microsoft.com/office/powerpoint/2013/main/command" cId="0" sldId="256"/><ac:spMk id="3" creationId="{S3}"/></ac:deMkLst><p188:pos x="100" y="200"/><p188:replyLst><p188:reply id="{R1}" authorId="{B2}" created="2024-01-16T09:00:00.000"><p188:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:t>Fixed</a:t></a:r></a:p></p188:txBody></p188:reply></p188:replyLst><p188:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:t>Numbers &amp; </a:t></a:r><a:r><a:rPr b="1"/><a:t>units</a:t></a:r></a:p><a:p><a:r><a:t>please</a:t></a:r></a:p></p188:txBody></p188:cm></p188:cmLst>"#;
+
+    #[test]
+    fn test_parse_modern_comments() {
+        let authors = parse_authors(AUTHORS.as_bytes()).unwrap();
+        let comments = parse_modern_comments(COMMENTS.as_bytes(), &authors).unwrap();
+        assert_eq!(comments.len(), 1);
+
+        let comment = &comments[0];
+        assert_eq!(comment.id.as_deref(), Some("{C1}"));
+        assert_eq!(comment.author, "Jane Doe");
+        assert_eq!(comment.author_initials, "JD");
+        assert_eq!(comment.text, "Numbers & units\nplease");
+        assert_eq!(comment.position, (Emu(100), Emu(200)));
+        assert_eq!(comment.shape_id, Some(ShapeId(3)));
+        assert!(comment.resolved);
+
+        assert_eq!(comment.replies.len(), 1);
+        let reply = &comment.replies[0];
+        assert_eq!(reply.id.as_deref(), Some("{R1}"));
+        assert_eq!(reply.author, "Bob");
+        assert_eq!(reply.author_id, 1);
+        assert_eq!(reply.text, "Fixed");
+        assert_eq!(reply.date, "2024-01-16T09:00:00.000");
+    }
+
+    #[test]
+    fn test_add_author() {
+        let (xml, id) = add_author(EMPTY_AUTHOR_LIST.as_bytes(), "Ann <QA>", "AQ").unwrap();
+        let (xml, other) = add_author(&xml, "Ann <QA>", "AQ").unwrap();
+        assert_ne!(id, other);
+        let authors = parse_authors(&xml).unwrap();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].id, id);
+        assert_eq!(authors[0].name, "Ann <QA>");
+    }
+
+    #[test]
+    fn test_reply_resolve_and_remove() {
+        let comment = Comment::new("Jane Doe", "Line one\n\nLine <3>", Emu(5), Emu(6));
+        let anchor = Anchor {
+            slide_creation_id: "0".to_string(),
+            slide_id: SlideId(257),
+            shape: None,
+        };
+        let cm = comment_xml("{N1}", "{A1}", "2024-02-01T00:00:00.000", &comment, &anchor);
+        assert!(cm.contains(r#"<pc:sldMk cId="0" sldId="257"/>"#));
+        let xml = insert_comment(EMPTY_COMMENT_LIST.as_bytes(), &cm).unwrap();
+
+        let reply = reply_xml("{N2}", "{B2}", "2024-02-02T00:00:00.000", "Ok");
+        let xml = insert_reply(&xml, "{N1}", &reply).unwrap().unwrap();
+        assert!(insert_reply(&xml, "{N9}", &reply).unwrap().is_none());
+        let xml = set_comment_status(&xml, "{N1}", true).unwrap().unwrap();
+
+        let authors = parse_authors(AUTHORS.as_bytes()).unwrap();
+        let comments = parse_modern_comments(&xml, &authors).unwrap();
+        assert_eq!(comments[0].text, "Line one\n\nLine <3>");
+        assert_eq!(comments[0].replies[0].text, "Ok");
+        assert!(comments[0].resolved);
+
+        // Removing the only reply drops the reply list
+        let xml = remove_modern_comment(&xml, "{N2}").unwrap().unwrap();
+        assert!(!std::str::from_utf8(&xml).unwrap().contains("replyLst"));
+        let xml = set_comment_status(&xml, "{N1}", false).unwrap().unwrap();
+        assert!(!std::str::from_utf8(&xml).unwrap().contains("status="));
+        let xml = remove_modern_comment(&xml, "{N1}").unwrap().unwrap();
+        assert!(parse_modern_comments(&xml, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_slide_moniker() {
+        let xml = set_slide_moniker(COMMENTS.as_bytes(), SlideId(300), "1234").unwrap();
+        let text = std::str::from_utf8(&xml).unwrap();
+        assert!(text.contains(r#"main/command" cId="1234" sldId="300"/><ac:spMk id="3""#));
+        assert!(!text.contains(r#"sldId="256""#));
+    }
+
+    #[test]
+    fn test_set_comment_rel_keeps_other_extensions() {
+        let slide = r#"<p:sld xmlns:p="p" xmlns:r="r"><p:cSld><p:spTree/></p:cSld><p:extLst><p:ext uri="{BB962C8B-B14F-4D97-AF65-F5344CB8AC3E}"><p14:creationId xmlns:p14="p14" val="42"/></p:ext></p:extLst></p:sld>"#;
+        let xml = set_comment_rel(slide.as_bytes(), Some("rId3")).unwrap();
+        let text = std::str::from_utf8(&xml).unwrap();
+        assert!(text.contains(r#"<p14:creationId xmlns:p14="p14" val="42"/></p:ext><p:ext uri="{6950BFC3-D8DA-4A85-94F7-54DA5524770B}">"#));
+        assert!(text.contains(r#"r:id="rId3"/></p:ext></p:extLst></p:sld>"#));
+        assert_eq!(slide_creation_id(text).as_deref(), Some("42"));
+
+        let xml = set_comment_rel(&xml, None).unwrap();
+        assert_eq!(std::str::from_utf8(&xml).unwrap(), slide);
+
+        let bare = r#"<p:sld><p:cSld/></p:sld>"#;
+        let xml = set_comment_rel(bare.as_bytes(), Some("rId2")).unwrap();
+        let xml = set_comment_rel(&xml, None).unwrap();
+        assert_eq!(std::str::from_utf8(&xml).unwrap(), bare);
+    }
+
+    #[test]
+    fn test_merge_authors() {
+        let dest = r#"<p188:authorLst xmlns:p188="http://schemas.microsoft.com/office/powerpoint/2018/8/main"><p188:author id="{X9}" name="Bob" initials="B" userId="Bob" providerId="None"/></p188:authorLst>"#;
+        let (authors, comments) =
+            merge_authors(dest.as_bytes(), AUTHORS.as_bytes(), COMMENTS.as_bytes()).unwrap();
+
+        // Jane Doe is copied over; Bob is matched by name
+        let authors = parse_authors(&authors).unwrap();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(
+            (authors[1].id.as_str(), authors[1].name.as_str()),
+            ("{A1}", "Jane Doe")
+        );
+        let text = std::str::from_utf8(&comments).unwrap();
+        assert!(text.contains(r#"<p188:cm id="{C1}" authorId="{A1}""#));
+        assert!(text.contains(r#"<p188:reply id="{R1}" authorId="{X9}""#));
+
+        let comments = parse_modern_comments(text.as_bytes(), &authors).unwrap();
+        assert_eq!(comments[0].author, "Jane Doe");
+        assert_eq!(comments[0].replies[0].author, "Bob");
+    }
+}
@@ -0,0 +1,140 @@
+//! Custom document properties (`docProps/custom.xml`).
+//!
+//! Custom properties are named, typed values an application or user
+//! attaches to a document, such as a classification label.
+
+mod xml;
+
+/// The value of a custom document property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomPropertyValue {
+    /// Text (`vt:lpwstr`).
+    String(String),
+    /// An integer (`vt:i4`, or `vt:i8` beyond the 32-bit range).
+    Int(i64),
+    /// A floating-point number (`vt:r8`).
+    Float(f64),
+    /// A yes/no flag (`vt:bool`).
+    Bool(bool),
+    /// A date and time (`vt:filetime`) in W3C format, e.g.
+    /// `"2024-01-15T10:30:00Z"`.
+    Date(String),
+}
+
+impl From<&str> for CustomPropertyValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for CustomPropertyValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<i32> for CustomPropertyValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<i64> for CustomPropertyValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for CustomPropertyValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for CustomPropertyValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// Custom document properties stored in `docProps/custom.xml`.
+///
+/// Property names are matched without regard to ASCII case, as Office
+/// does; properties keep the order they were added in.
+///
+/// # Examples
+///
+/// ```
+/// use pptx::custom_properties::{CustomProperties, CustomPropertyValue};
+///
+/// let mut props = CustomProperties::new();
+/// props.set("Classification", "Confidential");
+/// props.set("Revision", 3);
+/// assert_eq!(
+///     props.get("classification"),
+///     Some(&CustomPropertyValue::String("Confidential".to_string()))
+/// );
+/// assert_eq!(props.get("Revision"), Some(&CustomPropertyValue::Int(3)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CustomProperties {
+    pub(crate) properties: Vec<(String, CustomPropertyValue)>,
+}
+
+impl CustomProperties {
+    /// Create a new empty `CustomProperties`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of the property `name`, if set.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&CustomPropertyValue> {
+        self.position(name).map(|i| &self.properties[i].1)
+    }
+
+    /// Sets the property `name`, replacing any value it already has.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<CustomPropertyValue>) {
+        let name = name.into();
+        let value = value.into();
+        match self.position(&name) {
+            Some(i) => self.properties[i] = (name, value),
+            None => self.properties.push((name, value)),
+        }
+    }
+
+    /// Removes the property `name`, returning its value if it was set.
+    pub fn remove(&mut self, name: &str) -> Option<CustomPropertyValue> {
+        self.position(name).map(|i| self.properties.remove(i).1)
+    }
+
+    /// Iterate over the properties as `(name, value)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &CustomPropertyValue)> {
+        self.properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Returns the number of properties.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Returns `true` if there are no properties.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// The index of the property `name`.
+    fn position(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|(n, _)| n.eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests;
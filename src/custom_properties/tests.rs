@@ -0,0 +1,78 @@
+use super::*;
+
+const CUSTOM_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/custom-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes"><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Classification"><vt:lpwstr>Internal &amp; Restricted</vt:lpwstr></property><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="3" name="Pages"><vt:i4>12</vt:i4></property><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="4" name="Score"><vt:r8>0.75</vt:r8></property><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="5" name="Reviewed"><vt:bool>true</vt:bool></property><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="6" name="Expires"><vt:filetime>2025-01-01T00:00:00Z</vt:filetime></property><property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="7" name="Owner"><vt:bstr>Ops</vt:bstr></property></Properties>"#;
+
+#[test]
+fn test_set_get_remove() {
+    let mut props = CustomProperties::new();
+    assert!(props.is_empty());
+    props.set("Classification", "Public");
+    props.set("Pages", 3);
+    props.set("classification", "Confidential");
+    assert_eq!(props.len(), 2);
+    assert_eq!(
+        props.get("CLASSIFICATION"),
+        Some(&CustomPropertyValue::String("Confidential".to_string()))
+    );
+    assert_eq!(props.remove("pages"), Some(CustomPropertyValue::Int(3)));
+    assert_eq!(props.remove("pages"), None);
+    assert_eq!(
+        props.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["classification"]
+    );
+}
+
+#[test]
+fn test_from_xml() {
+    let props = CustomProperties::from_xml(CUSTOM_XML.as_bytes()).unwrap();
+    assert_eq!(props.len(), 6);
+    assert_eq!(
+        props.get("Classification"),
+        Some(&CustomPropertyValue::String(
+            "Internal & Restricted".to_string()
+        ))
+    );
+    assert_eq!(props.get("Pages"), Some(&CustomPropertyValue::Int(12)));
+    assert_eq!(props.get("Score"), Some(&CustomPropertyValue::Float(0.75)));
+    assert_eq!(
+        props.get("Reviewed"),
+        Some(&CustomPropertyValue::Bool(true))
+    );
+    assert_eq!(
+        props.get("Expires"),
+        Some(&CustomPropertyValue::Date(
+            "2025-01-01T00:00:00Z".to_string()
+        ))
+    );
+    // Other variant types read as text
+    assert_eq!(
+        props.get("Owner"),
+        Some(&CustomPropertyValue::String("Ops".to_string()))
+    );
+}
+
+#[test]
+fn test_round_trip_xml() {
+    let mut props = CustomProperties::new();
+    props.set("Label", "A <b> & c");
+    props.set("Small", -7);
+    props.set("Large", 5_000_000_000_i64);
+    props.set("Ratio", 2.5);
+    props.set("Final", false);
+    props.set(
+        "Due",
+        CustomPropertyValue::Date("2024-06-30T12:00:00Z".to_string()),
+    );
+
+    let xml = props.to_xml().unwrap();
+    let xml_str = std::str::from_utf8(&xml).unwrap();
+    assert!(xml_str.contains(
+        r#"<property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="2" name="Label"><vt:lpwstr>A &lt;b&gt; &amp; c</vt:lpwstr></property>"#
+    ));
+    assert!(xml_str.contains("<vt:i4>-7</vt:i4>"));
+    assert!(xml_str.contains("<vt:i8>5000000000</vt:i8>"));
+    assert!(xml_str.contains(r#"pid="7" name="Due"><vt:filetime>"#));
+
+    assert_eq!(CustomProperties::from_xml(&xml).unwrap(), props);
+}
@@ -0,0 +1,142 @@
+//! XML parsing and serialization for custom properties.
+
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Reader;
+use quick_xml::Writer;
+
+use crate::error::{PptxError, PptxResult};
+use crate::oxml::ns::{NS_CUSTOM_PROPERTIES, NS_VT};
+use crate::xml_util::{append_text, attr_value, local_name, local_name_owned};
+
+use super::{CustomProperties, CustomPropertyValue};
+
+/// The format ID Office gives user-defined properties.
+const FMTID_USER_DEFINED: &str = "{D5CDD505-2E9C-101B-9397-08002B2CF9AE}";
+
+/// The property ID of the first custom property; lower IDs are reserved.
+const FIRST_PID: usize = 2;
+
+impl CustomProperties {
+    /// Parse `CustomProperties` from the `docProps/custom.xml` content.
+    ///
+    /// Values of variant types without a `CustomPropertyValue` counterpart
+    /// are read as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed or cannot be decoded.
+    pub fn from_xml(xml: &[u8]) -> PptxResult<Self> {
+        let mut props = Self::new();
+        let mut reader = Reader::from_reader(xml);
+
+        let mut buf = Vec::new();
+        // The name of the open property and the variant type of its value
+        let mut name: Option<String> = None;
+        let mut value_type: Option<String> = None;
+        let mut text = String::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) if local_name(e.name().as_ref()) == b"property" => {
+                    let value = attr_value(e, b"name")?.unwrap_or_default();
+                    name = Some(
+                        unescape(&value)
+                            .map_err(|e| PptxError::InvalidXml(e.to_string()))?
+                            .into_owned(),
+                    );
+                }
+                Ok(Event::Start(ref e)) if name.is_some() && value_type.is_none() => {
+                    value_type = Some(local_name_owned(e.name().as_ref()));
+                    text.clear();
+                }
+                Ok(ref event @ (Event::Text(_) | Event::GeneralRef(_))) if value_type.is_some() => {
+                    append_text(event, &mut text)?;
+                }
+                Ok(Event::End(ref e)) => {
+                    if local_name(e.name().as_ref()) == b"property" {
+                        name = None;
+                    } else if let (Some(name), Some(vt)) = (&name, value_type.take()) {
+                        props
+                            .properties
+                            .push((name.clone(), parse_value(&vt, &text)));
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(PptxError::Xml(e)),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(props)
+    }
+
+    /// Serialize to XML bytes suitable for `docProps/custom.xml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if XML serialization fails.
+    pub fn to_xml(&self) -> PptxResult<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+
+        writer.write_event(Event::Decl(BytesDecl::new(
+            "1.0",
+            Some("UTF-8"),
+            Some("yes"),
+        )))?;
+
+        let mut root = BytesStart::new("Properties");
+        root.push_attribute(("xmlns", NS_CUSTOM_PROPERTIES));
+        root.push_attribute(("xmlns:vt", NS_VT));
+        writer.write_event(Event::Start(root))?;
+
+        for (i, (name, value)) in self.properties.iter().enumerate() {
+            let pid = (FIRST_PID + i).to_string();
+            let mut property = BytesStart::new("property");
+            property.push_attribute(("fmtid", FMTID_USER_DEFINED));
+            property.push_attribute(("pid", pid.as_str()));
+            property.push_attribute(("name", name.as_str()));
+            writer.write_event(Event::Start(property))?;
+
+            let (tag, text) = match value {
+                CustomPropertyValue::String(s) => ("vt:lpwstr", s.clone()),
+                CustomPropertyValue::Int(n) if i32::try_from(*n).is_ok() => {
+                    ("vt:i4", n.to_string())
+                }
+                CustomPropertyValue::Int(n) => ("vt:i8", n.to_string()),
+                CustomPropertyValue::Float(x) => ("vt:r8", x.to_string()),
+                CustomPropertyValue::Bool(b) => ("vt:bool", b.to_string()),
+                CustomPropertyValue::Date(d) => ("vt:filetime", d.clone()),
+            };
+            writer.write_event(Event::Start(BytesStart::new(tag)))?;
+            writer.write_event(Event::Text(BytesText::new(&text)))?;
+            writer.write_event(Event::End(BytesEnd::new(tag)))?;
+
+            writer.write_event(Event::End(BytesEnd::new("property")))?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("Properties")))?;
+
+        Ok(writer.into_inner())
+    }
+}
+
+/// Read the text of a `vt:*` element of the local name `vt`.
+fn parse_value(vt: &str, text: &str) -> CustomPropertyValue {
+    let trimmed = text.trim();
+    let parsed = match vt {
+        "i1" | "i2" | "i4" | "i8" | "int" | "ui1" | "ui2" | "ui4" | "ui8" | "uint" => {
+            trimmed.parse().ok().map(CustomPropertyValue::Int)
+        }
+        "r4" | "r8" | "decimal" => trimmed.parse().ok().map(CustomPropertyValue::Float),
+        "bool" => match trimmed {
+            "true" | "1" => Some(CustomPropertyValue::Bool(true)),
+            "false" | "0" => Some(CustomPropertyValue::Bool(false)),
+            _ => None,
+        },
+        "filetime" | "date" => Some(CustomPropertyValue::Date(trimmed.to_string())),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| CustomPropertyValue::String(text.to_string()))
+}
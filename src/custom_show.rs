@@ -0,0 +1,231 @@
+//! Custom shows: named, ordered subsets of the slides of a presentation.
+//!
+//! Custom shows are stored in the `<p:custShowLst>` of the presentation XML.
+//! Each `<p:custShow>` has a name and a numeric ID, and lists its slides as
+//! `<p:sld r:id="..."/>` references to the presentation's slide
+//! relationships.
+
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::SlideId;
+use crate::xml_util::splice::{find_element, find_start_tag, get_attr, splice};
+use crate::xml_util::{attr_value, local_name, xml_escape};
+
+/// Elements that follow `<p:custShowLst>` in the presentation XML.
+const FOLLOWING_ELEMENTS: &[&str] = &[
+    "p:photoAlbum",
+    "p:custDataLst",
+    "p:kinsoku",
+    "p:defaultTextStyle",
+    "p:modifyVerifier",
+    "p:extLst",
+];
+
+/// A custom show in a presentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomShow {
+    /// The display name of the custom show.
+    pub name: String,
+    /// The ID by which show settings refer to the custom show.
+    pub id: u32,
+    /// The slides of the show, in show order; a slide may appear more than
+    /// once.
+    pub slide_ids: Vec<SlideId>,
+}
+
+/// A custom show as stored in `<p:custShow>`, with its slides by
+/// relationship ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CustomShowEntry {
+    pub name: String,
+    pub id: u32,
+    pub slide_r_ids: Vec<String>,
+}
+
+/// Parse the `<p:custShowLst>` of a presentation XML.
+///
+/// Returns an empty list if the presentation has no custom shows.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(crate) fn parse_custom_shows(presentation_xml: &[u8]) -> PptxResult<Vec<CustomShowEntry>> {
+    let mut reader = Reader::from_reader(presentation_xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut shows: Vec<CustomShowEntry> = Vec::new();
+    let mut in_show = false;
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => match local_name(e.name().as_ref()) {
+                b"custShow" => {
+                    let name = attr_value(e, b"name")?.unwrap_or_default();
+                    shows.push(CustomShowEntry {
+                        name: unescape(&name)
+                            .map_err(|e| PptxError::InvalidXml(e.to_string()))?
+                            .into_owned(),
+                        id: attr_value(e, b"id")?
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or_default(),
+                        slide_r_ids: Vec::new(),
+                    });
+                    in_show = true;
+                }
+                b"sld" if in_show => {
+                    if let (Some(show), Some(r_id)) = (shows.last_mut(), attr_value(e, b"r:id")?) {
+                        show.slide_r_ids.push(r_id.into_owned());
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if local_name(e.name().as_ref()) == b"custShow" => {
+                in_show = false;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::Xml(e)),
+            _ => {}
+        }
+    }
+    Ok(shows)
+}
+
+/// Replace the `<p:custShowLst>` of a presentation XML with `shows`; an
+/// empty list removes it.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or has no `</p:presentation>`.
+pub(crate) fn write_custom_shows(
+    presentation_xml: &[u8],
+    shows: &[CustomShowEntry],
+) -> PptxResult<Vec<u8>> {
+    let mut xml = std::str::from_utf8(presentation_xml)?.to_string();
+    if let Some(list) = find_element(&xml, "p:custShowLst", 0) {
+        xml = splice(&xml, list, "");
+    }
+    if shows.is_empty() {
+        return Ok(xml.into_bytes());
+    }
+
+    let mut list = String::from("<p:custShowLst>");
+    for show in shows {
+        list.push_str(&format!(
+            r#"<p:custShow name="{}" id="{}"><p:sldLst>"#,
+            xml_escape(&show.name),
+            show.id
+        ));
+        for r_id in &show.slide_r_ids {
+            list.push_str(&format!(r#"<p:sld r:id="{}"/>"#, xml_escape(r_id)));
+        }
+        list.push_str("</p:sldLst></p:custShow>");
+    }
+    list.push_str("</p:custShowLst>");
+
+    let at = FOLLOWING_ELEMENTS
+        .iter()
+        .filter_map(|qname| find_start_tag(&xml, qname, 0).map(|tag| tag.start))
+        .min()
+        .or_else(|| xml.rfind("</p:presentation>"))
+        .ok_or_else(|| {
+            PptxError::InvalidXml("presentation XML does not contain </p:presentation>".to_string())
+        })?;
+    xml.insert_str(at, &list);
+    Ok(xml.into_bytes())
+}
+
+/// Remove the slide with relationship ID `r_id` from every custom show of a
+/// presentation XML.
+///
+/// Returns `None` if no custom show includes the slide.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8.
+pub(crate) fn remove_slide_from_custom_shows(
+    presentation_xml: &[u8],
+    r_id: &str,
+) -> PptxResult<Option<Vec<u8>>> {
+    let mut xml = std::str::from_utf8(presentation_xml)?.to_string();
+    let Some(list) = find_element(&xml, "p:custShowLst", 0) else {
+        return Ok(None);
+    };
+    let mut found = false;
+    let mut from = list.start;
+    let mut end = list.end;
+    while let Some(tag) = find_start_tag(&xml, "p:sld", from).filter(|tag| tag.end <= end) {
+        if get_attr(&xml[tag.clone()], "r:id") == Some(r_id) {
+            xml = splice(&xml, tag.clone(), "");
+            found = true;
+            end -= tag.len();
+            from = tag.start;
+        } else {
+            from = tag.end;
+        }
+    }
+    Ok(found.then(|| xml.into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRESENTATION: &str = r#"<p:presentation xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:sldIdLst><p:sldId id="256" r:id="rId2"/><p:sldId id="257" r:id="rId3"/></p:sldIdLst><p:sldSz cx="9144000" cy="6858000"/><p:notesSz cx="6858000" cy="9144000"/><p:defaultTextStyle/></p:presentation>"#;
+
+    fn show(name: &str, id: u32, slide_r_ids: &[&str]) -> CustomShowEntry {
+        CustomShowEntry {
+            name: name.to_string(),
+            id,
+            slide_r_ids: slide_r_ids.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_write_and_parse_custom_shows() {
+        let shows = [
+            show("Kiosk & booth", 0, &["rId3", "rId2"]),
+            show("Short", 1, &["rId3"]),
+        ];
+        let xml = write_custom_shows(PRESENTATION.as_bytes(), &shows).unwrap();
+        let text = std::str::from_utf8(&xml).unwrap();
+        assert!(text.contains(
+            r#"cy="9144000"/><p:custShowLst><p:custShow name="Kiosk &amp; booth" id="0"><p:sldLst><p:sld r:id="rId3"/><p:sld r:id="rId2"/></p:sldLst></p:custShow>"#
+        ));
+        assert!(text.contains("</p:custShowLst><p:defaultTextStyle/>"));
+        assert_eq!(parse_custom_shows(&xml).unwrap(), shows);
+
+        // Writing again replaces the list; an empty list removes it
+        let xml = write_custom_shows(&xml, &shows[1..]).unwrap();
+        assert_eq!(parse_custom_shows(&xml).unwrap(), shows[1..]);
+        let xml = write_custom_shows(&xml, &[]).unwrap();
+        assert_eq!(std::str::from_utf8(&xml).unwrap(), PRESENTATION);
+    }
+
+    #[test]
+    fn test_remove_slide_from_custom_shows() {
+        let shows = [
+            show("A", 0, &["rId2", "rId3", "rId2"]),
+            show("B", 1, &["rId3"]),
+        ];
+        let xml = write_custom_shows(PRESENTATION.as_bytes(), &shows).unwrap();
+        let xml = remove_slide_from_custom_shows(&xml, "rId2")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            parse_custom_shows(&xml).unwrap(),
+            [show("A", 0, &["rId3"]), show("B", 1, &["rId3"])]
+        );
+        assert!(remove_slide_from_custom_shows(&xml, "rId2")
+            .unwrap()
+            .is_none());
+        assert!(
+            remove_slide_from_custom_shows(PRESENTATION.as_bytes(), "rId2")
+                .unwrap()
+                .is_none()
+        );
+    }
+}
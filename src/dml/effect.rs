@@ -61,10 +61,10 @@ impl ShadowFormat {
     }
 }
 
-impl WriteXml for ShadowFormat {
-    fn write_xml<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
-        w.write_str("<a:effectLst>")?;
-
+impl ShadowFormat {
+    /// Write the shadow element alone, for an `<a:effectLst>` written by the
+    /// caller.
+    pub(crate) fn write_effect<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
         let tag = match self.shadow_type {
             ShadowType::Outer | ShadowType::Perspective => "a:outerShdw",
             ShadowType::Inner => "a:innerShdw",
@@ -118,12 +118,45 @@ impl WriteXml for ShadowFormat {
 
         w.write_str("</")?;
         w.write_str(tag)?;
-        w.write_char('>')?;
+        w.write_char('>')
+    }
+}
 
+impl WriteXml for ShadowFormat {
+    fn write_xml<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
+        w.write_str("<a:effectLst>")?;
+        self.write_effect(w)?;
         w.write_str("</a:effectLst>")
     }
 }
 
+/// Glow effect formatting: a blurred halo of color around the outline.
+///
+/// Corresponds to the `<a:glow>` element of an `<a:effectLst>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlowFormat {
+    /// Glow color.
+    pub color: ColorFormat,
+    /// Radius of the glow in EMU.
+    pub radius: Emu,
+}
+
+impl GlowFormat {
+    /// Create a glow with the given color and radius.
+    #[must_use]
+    pub const fn new(color: ColorFormat, radius: Emu) -> Self {
+        Self { color, radius }
+    }
+}
+
+impl WriteXml for GlowFormat {
+    fn write_xml<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
+        write!(w, r#"<a:glow rad="{}">"#, self.radius)?;
+        self.color.write_xml(w)?;
+        w.write_str("</a:glow>")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,4 +196,13 @@ mod tests {
         let xml = shadow.to_xml_string();
         assert!(xml.contains(r#"<a:alpha val="50000"/>"#));
     }
+
+    #[test]
+    fn test_glow_xml() {
+        let glow = GlowFormat::new(ColorFormat::rgb(255, 192, 0), Emu(63_500));
+        assert_eq!(
+            glow.to_xml_string(),
+            r#"<a:glow rad="63500"><a:srgbClr val="FFC000"/></a:glow>"#
+        );
+    }
 }
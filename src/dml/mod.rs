@@ -7,7 +7,7 @@ pub mod fill;
 pub mod line;
 
 pub use color::{ColorFormat, HslColor, PresetColor, SystemColor, ThemeColor};
-pub use effect::ShadowFormat;
+pub use effect::{GlowFormat, ShadowFormat};
 pub use effect3d::{Bevel, Camera, LightRig, Rotation3D, Scene3D, Shape3D};
 pub use fill::{FillFormat, GradientFill, GradientStop, PatternFill, PictureFill, SolidFill};
 pub use line::{LineCap, LineFormat, LineJoin};
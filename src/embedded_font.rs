@@ -73,6 +73,35 @@ impl EmbeddedFont {
 /// The content type for embedded font data parts.
 pub const FONT_DATA_CONTENT_TYPE: &str = "application/x-fontdata";
 
+/// The TrueType or OpenType program in font data: the data itself, or the
+/// font inside an Embedded OpenType (`.fntdata`) wrapper.
+pub(crate) fn font_program(data: &[u8]) -> Option<Vec<u8>> {
+    if ttf_parser::Face::parse(data, 0).is_ok() {
+        return Some(data.to_vec());
+    }
+    // EOT header: sizes and flags up front, magic number 0x504C at 34
+    if data.len() < 36 || data[34..36] != [0x4C, 0x50] {
+        return None;
+    }
+    let u32_at =
+        |pos: usize| u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+    let (eot_size, font_size, flags) = (u32_at(0) as usize, u32_at(4) as usize, u32_at(12));
+    // MicroType Express compression is not supported
+    if flags & 0x4 != 0 {
+        return None;
+    }
+    let start = eot_size.checked_sub(font_size)?;
+    let mut program = data.get(start..start + font_size)?.to_vec();
+    if flags & 0x1000_0000 != 0 {
+        for b in &mut program {
+            *b ^= 0x50;
+        }
+    }
+    ttf_parser::Face::parse(&program, 0)
+        .is_ok()
+        .then_some(program)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,6 +258,388 @@ impl MsoTextUnderlineType {
     }
 }
 
+// ---------------------------------------------------------------------------
+// MSO_TAB_STOP_TYPE
+// ---------------------------------------------------------------------------
+
+/// Specifies how text is aligned at a tab stop.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsoTabStopType {
+    /// Text starts at the tab stop.
+    Left,
+    /// Text is centered on the tab stop.
+    Center,
+    /// Text ends at the tab stop.
+    Right,
+    /// The decimal point of a number lines up with the tab stop.
+    Decimal,
+}
+
+impl MsoTabStopType {
+    /// Return the XML attribute value for this tab stop type.
+    #[must_use]
+    pub const fn to_xml_str(self) -> &'static str {
+        match self {
+            Self::Left => "l",
+            Self::Center => "ctr",
+            Self::Right => "r",
+            Self::Decimal => "dec",
+        }
+    }
+
+    /// Parse an XML tab stop type attribute value.
+    #[must_use]
+    pub fn from_xml_str(s: &str) -> Option<Self> {
+        match s {
+            "l" => Some(Self::Left),
+            "ctr" => Some(Self::Center),
+            "r" => Some(Self::Right),
+            "dec" => Some(Self::Decimal),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MSO_BASELINE_ALIGNMENT
+// ---------------------------------------------------------------------------
+
+/// Specifies how runs of different sizes in a line are aligned vertically
+/// (the `fontAlgn` attribute).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsoBaselineAlignment {
+    /// Alignment is chosen from the script of the text.
+    Auto,
+    /// Runs are aligned to the top of the line.
+    Top,
+    /// Runs are centered in the line.
+    Center,
+    /// Runs share a baseline.
+    Baseline,
+    /// Runs are aligned to the bottom of the line.
+    Bottom,
+}
+
+impl MsoBaselineAlignment {
+    /// Return the XML attribute value for this baseline alignment.
+    #[must_use]
+    pub const fn to_xml_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Top => "t",
+            Self::Center => "ctr",
+            Self::Baseline => "base",
+            Self::Bottom => "b",
+        }
+    }
+
+    /// Parse an XML baseline alignment attribute value.
+    #[must_use]
+    pub fn from_xml_str(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(Self::Auto),
+            "t" => Some(Self::Top),
+            "ctr" => Some(Self::Center),
+            "base" => Some(Self::Baseline),
+            "b" => Some(Self::Bottom),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MSO_TEXT_CAPS
+// ---------------------------------------------------------------------------
+
+/// Specifies how lowercase letters of a run are capitalized.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsoTextCaps {
+    /// Letters are shown as typed.
+    None,
+    /// Lowercase letters are shown as smaller capitals.
+    Small,
+    /// Lowercase letters are shown as capitals.
+    All,
+}
+
+impl MsoTextCaps {
+    /// Return the XML attribute value for this capitalization.
+    #[must_use]
+    pub const fn to_xml_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Small => "small",
+            Self::All => "all",
+        }
+    }
+
+    /// Parse an XML capitalization attribute value.
+    #[must_use]
+    pub fn from_xml_str(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(Self::None),
+            "small" => Some(Self::Small),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MSO_TEXT_ORIENTATION
+// ---------------------------------------------------------------------------
+
+/// Specifies the direction text flows in a text frame (the `vert`
+/// attribute).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsoTextOrientation {
+    /// Text runs left to right.
+    Horizontal,
+    /// Lines are rotated 90 degrees clockwise.
+    Vertical,
+    /// Lines are rotated 270 degrees clockwise.
+    Vertical270,
+    /// Letters are stacked top to bottom.
+    WordArtVertical,
+    /// East Asian vertical text: CJK characters upright, Latin text rotated.
+    EastAsianVertical,
+    /// Mongolian vertical text, with lines flowing left to right.
+    MongolianVertical,
+    /// Letters are stacked top to bottom, with lines flowing left to right.
+    WordArtVerticalRtl,
+}
+
+impl MsoTextOrientation {
+    /// Return the XML attribute value for this orientation.
+    #[must_use]
+    pub const fn to_xml_str(self) -> &'static str {
+        match self {
+            Self::Horizontal => "horz",
+            Self::Vertical => "vert",
+            Self::Vertical270 => "vert270",
+            Self::WordArtVertical => "wordArtVert",
+            Self::EastAsianVertical => "eaVert",
+            Self::MongolianVertical => "mongolianVert",
+            Self::WordArtVerticalRtl => "wordArtVertRtl",
+        }
+    }
+
+    /// Parse an XML orientation attribute value.
+    #[must_use]
+    pub fn from_xml_str(s: &str) -> Option<Self> {
+        match s {
+            "horz" => Some(Self::Horizontal),
+            "vert" => Some(Self::Vertical),
+            "vert270" => Some(Self::Vertical270),
+            "wordArtVert" => Some(Self::WordArtVertical),
+            "eaVert" => Some(Self::EastAsianVertical),
+            "mongolianVert" => Some(Self::MongolianVertical),
+            "wordArtVertRtl" => Some(Self::WordArtVerticalRtl),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MSO_AUTO_NUMBER_SCHEME
+// ---------------------------------------------------------------------------
+
+/// Specifies the numbering scheme of an auto-numbered bullet (the `type`
+/// attribute of `<a:buAutoNum>`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MsoAutoNumberScheme {
+    /// Lowercase letters in parentheses: (a), (b), (c).
+    AlphaLcParenBoth,
+    /// Uppercase letters in parentheses: (A), (B), (C).
+    AlphaUcParenBoth,
+    /// Lowercase letters with a closing parenthesis: a), b), c).
+    AlphaLcParenR,
+    /// Uppercase letters with a closing parenthesis: A), B), C).
+    AlphaUcParenR,
+    /// Lowercase letters with a period: a., b., c..
+    AlphaLcPeriod,
+    /// Uppercase letters with a period: A., B., C..
+    AlphaUcPeriod,
+    /// Numbers in parentheses: (1), (2), (3).
+    ArabicParenBoth,
+    /// Numbers with a closing parenthesis: 1), 2), 3).
+    ArabicParenR,
+    /// Numbers with a period: 1., 2., 3..
+    ArabicPeriod,
+    /// Numbers alone: 1, 2, 3.
+    ArabicPlain,
+    /// Lowercase Roman numerals in parentheses: (i), (ii), (iii).
+    RomanLcParenBoth,
+    /// Uppercase Roman numerals in parentheses: (I), (II), (III).
+    RomanUcParenBoth,
+    /// Lowercase Roman numerals with a closing parenthesis: i), ii), iii).
+    RomanLcParenR,
+    /// Uppercase Roman numerals with a closing parenthesis: I), II), III).
+    RomanUcParenR,
+    /// Lowercase Roman numerals with a period: i., ii., iii..
+    RomanLcPeriod,
+    /// Uppercase Roman numerals with a period: I., II., III..
+    RomanUcPeriod,
+    /// Double-byte circled numbers: ①, ②, ③.
+    CircleNumDbPlain,
+    /// White numbers in black circles: ❶, ❷, ❸.
+    CircleNumWdBlackPlain,
+    /// Black numbers in white circles: ①, ②, ③.
+    CircleNumWdWhitePlain,
+    /// Double-byte numbers with a double-byte period: １．, ２．.
+    ArabicDbPeriod,
+    /// Double-byte numbers alone: １, ２.
+    ArabicDbPlain,
+    /// Simplified Chinese numbers with a double-byte period: 一．, 二．.
+    Ea1ChsPeriod,
+    /// Simplified Chinese numbers alone: 一, 二.
+    Ea1ChsPlain,
+    /// Traditional Chinese numbers with a double-byte period: 一．, 二．.
+    Ea1ChtPeriod,
+    /// Traditional Chinese numbers alone: 一, 二.
+    Ea1ChtPlain,
+    /// Japanese/Chinese numbers with a double-byte period: 一．, 二．.
+    Ea1JpnChsDbPeriod,
+    /// Japanese/Korean numbers alone: 一, 二.
+    Ea1JpnKorPlain,
+    /// Japanese/Korean numbers with a double-byte period: 一．, 二．.
+    Ea1JpnKorPeriod,
+    /// Arabic alphabet with a minus sign.
+    Arabic1Minus,
+    /// Arabic abjad numerals with a minus sign.
+    Arabic2Minus,
+    /// Hebrew numerals with a minus sign.
+    Hebrew2Minus,
+    /// Thai letters with a period.
+    ThaiAlphaPeriod,
+    /// Thai letters with a closing parenthesis.
+    ThaiAlphaParenR,
+    /// Thai letters in parentheses.
+    ThaiAlphaParenBoth,
+    /// Thai numbers with a period.
+    ThaiNumPeriod,
+    /// Thai numbers with a closing parenthesis.
+    ThaiNumParenR,
+    /// Thai numbers in parentheses.
+    ThaiNumParenBoth,
+    /// Hindi consonants with a period.
+    HindiAlphaPeriod,
+    /// Hindi numbers with a period.
+    HindiNumPeriod,
+    /// Hindi numbers with a closing parenthesis.
+    HindiNumParenR,
+    /// Hindi vowels with a period.
+    HindiAlpha1Period,
+}
+
+impl MsoAutoNumberScheme {
+    /// Every numbering scheme, in schema order.
+    pub const ALL: [Self; 41] = [
+        Self::AlphaLcParenBoth,
+        Self::AlphaUcParenBoth,
+        Self::AlphaLcParenR,
+        Self::AlphaUcParenR,
+        Self::AlphaLcPeriod,
+        Self::AlphaUcPeriod,
+        Self::ArabicParenBoth,
+        Self::ArabicParenR,
+        Self::ArabicPeriod,
+        Self::ArabicPlain,
+        Self::RomanLcParenBoth,
+        Self::RomanUcParenBoth,
+        Self::RomanLcParenR,
+        Self::RomanUcParenR,
+        Self::RomanLcPeriod,
+        Self::RomanUcPeriod,
+        Self::CircleNumDbPlain,
+        Self::CircleNumWdBlackPlain,
+        Self::CircleNumWdWhitePlain,
+        Self::ArabicDbPeriod,
+        Self::ArabicDbPlain,
+        Self::Ea1ChsPeriod,
+        Self::Ea1ChsPlain,
+        Self::Ea1ChtPeriod,
+        Self::Ea1ChtPlain,
+        Self::Ea1JpnChsDbPeriod,
+        Self::Ea1JpnKorPlain,
+        Self::Ea1JpnKorPeriod,
+        Self::Arabic1Minus,
+        Self::Arabic2Minus,
+        Self::Hebrew2Minus,
+        Self::ThaiAlphaPeriod,
+        Self::ThaiAlphaParenR,
+        Self::ThaiAlphaParenBoth,
+        Self::ThaiNumPeriod,
+        Self::ThaiNumParenR,
+        Self::ThaiNumParenBoth,
+        Self::HindiAlphaPeriod,
+        Self::HindiNumPeriod,
+        Self::HindiNumParenR,
+        Self::HindiAlpha1Period,
+    ];
+
+    /// Return the XML attribute value for this numbering scheme.
+    #[must_use]
+    pub const fn to_xml_str(self) -> &'static str {
+        match self {
+            Self::AlphaLcParenBoth => "alphaLcParenBoth",
+            Self::AlphaUcParenBoth => "alphaUcParenBoth",
+            Self::AlphaLcParenR => "alphaLcParenR",
+            Self::AlphaUcParenR => "alphaUcParenR",
+            Self::AlphaLcPeriod => "alphaLcPeriod",
+            Self::AlphaUcPeriod => "alphaUcPeriod",
+            Self::ArabicParenBoth => "arabicParenBoth",
+            Self::ArabicParenR => "arabicParenR",
+            Self::ArabicPeriod => "arabicPeriod",
+            Self::ArabicPlain => "arabicPlain",
+            Self::RomanLcParenBoth => "romanLcParenBoth",
+            Self::RomanUcParenBoth => "romanUcParenBoth",
+            Self::RomanLcParenR => "romanLcParenR",
+            Self::RomanUcParenR => "romanUcParenR",
+            Self::RomanLcPeriod => "romanLcPeriod",
+            Self::RomanUcPeriod => "romanUcPeriod",
+            Self::CircleNumDbPlain => "circleNumDbPlain",
+            Self::CircleNumWdBlackPlain => "circleNumWdBlackPlain",
+            Self::CircleNumWdWhitePlain => "circleNumWdWhitePlain",
+            Self::ArabicDbPeriod => "arabicDbPeriod",
+            Self::ArabicDbPlain => "arabicDbPlain",
+            Self::Ea1ChsPeriod => "ea1ChsPeriod",
+            Self::Ea1ChsPlain => "ea1ChsPlain",
+            Self::Ea1ChtPeriod => "ea1ChtPeriod",
+            Self::Ea1ChtPlain => "ea1ChtPlain",
+            Self::Ea1JpnChsDbPeriod => "ea1JpnChsDbPeriod",
+            Self::Ea1JpnKorPlain => "ea1JpnKorPlain",
+            Self::Ea1JpnKorPeriod => "ea1JpnKorPeriod",
+            Self::Arabic1Minus => "arabic1Minus",
+            Self::Arabic2Minus => "arabic2Minus",
+            Self::Hebrew2Minus => "hebrew2Minus",
+            Self::ThaiAlphaPeriod => "thaiAlphaPeriod",
+            Self::ThaiAlphaParenR => "thaiAlphaParenR",
+            Self::ThaiAlphaParenBoth => "thaiAlphaParenBoth",
+            Self::ThaiNumPeriod => "thaiNumPeriod",
+            Self::ThaiNumParenR => "thaiNumParenR",
+            Self::ThaiNumParenBoth => "thaiNumParenBoth",
+            Self::HindiAlphaPeriod => "hindiAlphaPeriod",
+            Self::HindiNumPeriod => "hindiNumPeriod",
+            Self::HindiNumParenR => "hindiNumParenR",
+            Self::HindiAlpha1Period => "hindiAlpha1Period",
+        }
+    }
+
+    /// Parse an XML numbering scheme attribute value.
+    #[must_use]
+    pub fn from_xml_str(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.to_xml_str() == s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,4 +732,63 @@ mod tests {
     fn test_unknown_alignment() {
         assert_eq!(PpParagraphAlignment::from_xml_str("unknown"), None);
     }
+
+    #[test]
+    fn test_tab_stop_and_baseline_roundtrip() {
+        for t in [
+            MsoTabStopType::Left,
+            MsoTabStopType::Center,
+            MsoTabStopType::Right,
+            MsoTabStopType::Decimal,
+        ] {
+            assert_eq!(MsoTabStopType::from_xml_str(t.to_xml_str()), Some(t));
+        }
+        for a in [
+            MsoBaselineAlignment::Auto,
+            MsoBaselineAlignment::Top,
+            MsoBaselineAlignment::Center,
+            MsoBaselineAlignment::Baseline,
+            MsoBaselineAlignment::Bottom,
+        ] {
+            assert_eq!(MsoBaselineAlignment::from_xml_str(a.to_xml_str()), Some(a));
+        }
+        assert_eq!(MsoTabStopType::from_xml_str("unknown"), None);
+    }
+
+    #[test]
+    fn test_text_orientation_roundtrip() {
+        for o in [
+            MsoTextOrientation::Horizontal,
+            MsoTextOrientation::Vertical,
+            MsoTextOrientation::Vertical270,
+            MsoTextOrientation::WordArtVertical,
+            MsoTextOrientation::EastAsianVertical,
+            MsoTextOrientation::MongolianVertical,
+            MsoTextOrientation::WordArtVerticalRtl,
+        ] {
+            assert_eq!(MsoTextOrientation::from_xml_str(o.to_xml_str()), Some(o));
+        }
+    }
+
+    #[test]
+    fn test_auto_number_scheme_roundtrip() {
+        for scheme in MsoAutoNumberScheme::ALL {
+            assert_eq!(
+                MsoAutoNumberScheme::from_xml_str(scheme.to_xml_str()),
+                Some(scheme)
+            );
+        }
+        assert_eq!(
+            MsoAutoNumberScheme::from_xml_str("circleNumDbPlain"),
+            Some(MsoAutoNumberScheme::CircleNumDbPlain)
+        );
+        assert_eq!(MsoAutoNumberScheme::from_xml_str("arabic"), None);
+    }
+
+    #[test]
+    fn test_caps_roundtrip() {
+        for c in [MsoTextCaps::None, MsoTextCaps::Small, MsoTextCaps::All] {
+            assert_eq!(MsoTextCaps::from_xml_str(c.to_xml_str()), Some(c));
+        }
+    }
 }
@@ -0,0 +1,908 @@
+//! Charts drawn as inline SVG from the chart model.
+//!
+//! Bar and column charts (clustered, stacked and 100% stacked), line and
+//! area charts, pie and doughnut charts and scatter charts are drawn with
+//! their axes, gridlines, title and legend. Other chart types are drawn as
+//! line charts.
+
+use std::fmt::{self, Write};
+
+use super::utils::html_escape;
+use crate::chart::plot::{grouping_for, ChartGrouping};
+use crate::chart::{Chart, Plot, Series};
+use crate::dml::fill::FillFormat;
+use crate::enums::chart::{XlChartType, XlLegendPosition};
+use crate::export::scene::Colors;
+use crate::export::svg::writer::{hex, num, pt_to_px};
+use crate::text::font::RgbColor;
+
+/// Size of titles, in points.
+const TITLE_SIZE: f64 = 14.0;
+/// Size of axis and legend labels, in points.
+const LABEL_SIZE: f64 = 9.0;
+const LABEL_COLOR: &str = "#595959";
+const GRIDLINE_COLOR: &str = "#D9D9D9";
+/// Space around the chart and between its parts, in points.
+const PAD: f64 = 6.0;
+
+/// A box in points.
+#[derive(Debug, Clone, Copy)]
+struct Area {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+/// A value axis scale: its ends and the step between labels.
+#[derive(Debug, Clone, Copy)]
+struct Scale {
+    min: f64,
+    max: f64,
+    step: f64,
+    percent: bool,
+}
+
+impl Scale {
+    /// A scale covering `low..high`, with round steps unless the axis
+    /// sets its own.
+    fn new(low: f64, high: f64, min: Option<f64>, max: Option<f64>, step: Option<f64>) -> Self {
+        let low = min.unwrap_or_else(|| low.min(0.0));
+        let high = max.unwrap_or(high).max(low + f64::EPSILON);
+        let step = step
+            .filter(|s| *s > 0.0)
+            .unwrap_or_else(|| nice_step(high - low));
+        let min = min.unwrap_or_else(|| (low / step).floor() * step);
+        let max = max.unwrap_or_else(|| (high / step).ceil() * step);
+        Self {
+            min,
+            max: if max > min { max } else { min + step },
+            step,
+            percent: false,
+        }
+    }
+
+    /// The fraction of the axis length at `value`.
+    fn at(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min)
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        let mut ticks = Vec::new();
+        let mut value = self.min;
+        // Bounded so that a tiny step on a huge range cannot run away
+        while value <= self.max + self.step * 1e-6 && ticks.len() < 100 {
+            ticks.push(value);
+            value += self.step;
+        }
+        ticks
+    }
+
+    fn label(&self, value: f64) -> String {
+        if self.percent {
+            format!("{}%", num(value * 100.0))
+        } else {
+            num(value)
+        }
+    }
+}
+
+/// A step of 1, 2 or 5 times a power of ten giving about five intervals.
+fn nice_step(range: f64) -> f64 {
+    if range <= 0.0 || !range.is_finite() {
+        return 1.0;
+    }
+    let rough = range / 5.0;
+    let power = 10f64.powf(rough.log10().floor());
+    let step = match rough / power {
+        r if r <= 1.0 => 1.0,
+        r if r <= 2.0 => 2.0,
+        r if r <= 5.0 => 5.0,
+        _ => 10.0,
+    };
+    step * power
+}
+
+/// Write `chart` as an `<svg>` of `width` × `height` points.
+pub(super) fn write_chart(
+    w: &mut String,
+    chart: &Chart,
+    (width, height): (f64, f64),
+    colors: &Colors,
+) -> fmt::Result {
+    write!(
+        w,
+        "<svg class=\"chart\" xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" font-family=\"Calibri, Arial, sans-serif\">",
+        num(pt_to_px(width)),
+        num(pt_to_px(height)),
+        num(width),
+        num(height),
+    )?;
+    let mut area = Area {
+        x: PAD,
+        y: PAD,
+        w: (width - 2.0 * PAD).max(1.0),
+        h: (height - 2.0 * PAD).max(1.0),
+    };
+    if let Some(title) = chart.title().filter(|t| !t.is_empty()) {
+        write_label(
+            w,
+            width / 2.0,
+            area.y + TITLE_SIZE,
+            TITLE_SIZE,
+            "middle",
+            title,
+        )?;
+        area.y += TITLE_SIZE + PAD;
+        area.h -= TITLE_SIZE + PAD;
+    }
+    let painter = Painter { colors };
+    if chart.has_legend() {
+        let position = chart
+            .legend()
+            .map_or(XlLegendPosition::Right, |l| l.position());
+        let entries = painter.legend_entries(chart);
+        area = write_legend(w, &entries, position, area)?;
+    }
+    if let Some(plot) = chart.plots().first() {
+        let kind = plot.chart_type;
+        if kind.is_pie_type() || kind.is_doughnut_type() {
+            painter.write_pie(w, plot, area)?;
+        } else if kind.is_xy_type() || kind.is_bubble_type() {
+            painter.write_scatter(w, chart, area)?;
+        } else {
+            painter.write_category_chart(w, chart, area)?;
+        }
+    }
+    w.write_str("</svg>")
+}
+
+fn write_label(w: &mut String, x: f64, y: f64, size: f64, anchor: &str, text: &str) -> fmt::Result {
+    write!(
+        w,
+        "<text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{LABEL_COLOR}\" text-anchor=\"{anchor}\">{}</text>",
+        num(x),
+        num(y),
+        num(size),
+        html_escape(text)
+    )
+}
+
+/// Rough width of `text` at `size`, for laying out labels.
+#[allow(clippy::cast_precision_loss)]
+fn text_width(text: &str, size: f64) -> f64 {
+    text.chars().count() as f64 * size * 0.55
+}
+
+/// Write the legend at `position` within `area`; returns what is left of
+/// the area for the plot.
+fn write_legend(
+    w: &mut String,
+    entries: &[(String, RgbColor)],
+    position: XlLegendPosition,
+    mut area: Area,
+) -> Result<Area, fmt::Error> {
+    if entries.is_empty() {
+        return Ok(area);
+    }
+    let swatch = LABEL_SIZE * 0.8;
+    let line = LABEL_SIZE * 1.6;
+    let entry_width = |text: &str| swatch + 4.0 + text_width(text, LABEL_SIZE) + 2.0 * PAD;
+    let entry = |w: &mut String, x: f64, y: f64, (text, color): &(String, RgbColor)| {
+        write!(
+            w,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            num(x),
+            num(y - swatch),
+            num(swatch),
+            num(swatch),
+            hex(*color)
+        )?;
+        write_label(w, x + swatch + 4.0, y, LABEL_SIZE, "start", text)
+    };
+    match position {
+        XlLegendPosition::Top | XlLegendPosition::Bottom => {
+            let total: f64 = entries.iter().map(|(t, _)| entry_width(t)).sum();
+            let mut x = area.x + ((area.w - total) / 2.0).max(0.0);
+            let y = if position == XlLegendPosition::Top {
+                area.y + LABEL_SIZE
+            } else {
+                area.y + area.h - LABEL_SIZE * 0.4
+            };
+            for e in entries {
+                entry(w, x + PAD, y, e)?;
+                x += entry_width(&e.0);
+            }
+            if position == XlLegendPosition::Top {
+                area.y += line + PAD;
+            }
+            area.h -= line + PAD;
+        }
+        XlLegendPosition::Left | XlLegendPosition::Right | XlLegendPosition::Corner => {
+            let widest = entries
+                .iter()
+                .map(|(t, _)| entry_width(t))
+                .fold(0.0, f64::max)
+                .min(area.w / 3.0);
+            #[allow(clippy::cast_precision_loss)]
+            let total = entries.len() as f64 * line;
+            let x = if position == XlLegendPosition::Left {
+                area.x
+            } else {
+                area.x + area.w - widest
+            };
+            let mut y = area.y + ((area.h - total) / 2.0).max(0.0) + LABEL_SIZE;
+            for e in entries {
+                entry(w, x + PAD, y, e)?;
+                y += line;
+            }
+            if position == XlLegendPosition::Left {
+                area.x += widest;
+            }
+            area.w -= widest;
+        }
+    }
+    Ok(area)
+}
+
+/// Picks the colors of series and points.
+struct Painter<'a> {
+    colors: &'a Colors,
+}
+
+impl Painter<'_> {
+    /// The `n`th accent color of the theme, repeating after six.
+    fn accent(&self, n: usize) -> RgbColor {
+        self.colors.scheme(&format!("accent{}", n % 6 + 1))
+    }
+
+    /// The series' own solid fill or line color, else an accent color.
+    fn series_color(&self, series: &Series, n: usize) -> RgbColor {
+        let format = series.format();
+        let fill = format
+            .and_then(|f| f.fill.as_ref())
+            .and_then(|fill| match fill {
+                FillFormat::Solid(solid) => Some(self.colors.resolve(&solid.color)),
+                _ => None,
+            });
+        let line = format
+            .and_then(|f| f.line.as_ref())
+            .and_then(|line| line.color.as_ref())
+            .map(|color| self.colors.resolve(color));
+        fill.or(line).unwrap_or_else(|| self.accent(n))
+    }
+
+    fn legend_entries(&self, chart: &Chart) -> Vec<(String, RgbColor)> {
+        let Some(first) = chart.plots().first() else {
+            return Vec::new();
+        };
+        if first.chart_type.is_pie_type() || first.chart_type.is_doughnut_type() {
+            let count = first.series.get(0).map_or(0, |s| s.values().len());
+            return (0..count)
+                .map(|i| (category(first, i), self.accent(i)))
+                .collect();
+        }
+        chart
+            .plots()
+            .iter()
+            .flat_map(|plot| plot.series.iter())
+            .enumerate()
+            .map(|(n, series)| (series.name().to_string(), self.series_color(series, n)))
+            .collect()
+    }
+
+    fn write_pie(&self, w: &mut String, plot: &Plot, area: Area) -> fmt::Result {
+        let Some(series) = plot.series.get(0) else {
+            return Ok(());
+        };
+        let values: Vec<f64> = series
+            .values()
+            .iter()
+            .map(|v| v.unwrap_or(0.0).max(0.0))
+            .collect();
+        let total: f64 = values.iter().sum();
+        if total <= 0.0 {
+            return Ok(());
+        }
+        let r = (area.w.min(area.h) / 2.0 - PAD).max(1.0);
+        let (cx, cy) = (area.x + area.w / 2.0, area.y + area.h / 2.0);
+        let hole = if plot.chart_type.is_doughnut_type() {
+            r * 0.5
+        } else {
+            0.0
+        };
+        let point = |angle: f64, radius: f64| {
+            // Slices start at 12 o'clock and go clockwise
+            let (sin, cos) = (angle - std::f64::consts::FRAC_PI_2).sin_cos();
+            (cx + radius * cos, cy + radius * sin)
+        };
+        let mut start = 0.0;
+        for (i, value) in values.iter().enumerate() {
+            let sweep = value / total * std::f64::consts::TAU;
+            let color = hex(self.accent(i));
+            if sweep >= std::f64::consts::TAU - 1e-9 {
+                write!(
+                    w,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{color}\"/>",
+                    num(cx),
+                    num(cy),
+                    num(r)
+                )?;
+            } else if sweep > 0.0 {
+                let large = u8::from(sweep > std::f64::consts::PI);
+                let (x1, y1) = point(start, r);
+                let (x2, y2) = point(start + sweep, r);
+                let (x3, y3) = point(start + sweep, hole);
+                let (x4, y4) = point(start, hole);
+                write!(
+                    w,
+                    "<path d=\"M{} {}A{} {} 0 {large} 1 {} {}L{} {}",
+                    num(x1),
+                    num(y1),
+                    num(r),
+                    num(r),
+                    num(x2),
+                    num(y2),
+                    num(x3),
+                    num(y3)
+                )?;
+                if hole > 0.0 {
+                    write!(
+                        w,
+                        "A{} {} 0 {large} 0 {} {}",
+                        num(hole),
+                        num(hole),
+                        num(x4),
+                        num(y4)
+                    )?;
+                }
+                write!(w, "Z\" fill=\"{color}\" stroke=\"#FFFFFF\"/>")?;
+            }
+            start += sweep;
+        }
+        if hole > 0.0 {
+            // The hole of a full ring
+            if values.iter().filter(|v| **v > 0.0).count() == 1 {
+                write!(
+                    w,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"#FFFFFF\"/>",
+                    num(cx),
+                    num(cy),
+                    num(hole)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_scatter(&self, w: &mut String, chart: &Chart, area: Area) -> fmt::Result {
+        let series: Vec<&Series> = chart.plots().iter().flat_map(|p| p.series.iter()).collect();
+        let points = |s: &Series| -> Vec<(f64, f64)> {
+            s.values()
+                .iter()
+                .enumerate()
+                .filter_map(|(i, y)| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let x = s
+                        .x_values()
+                        .get(i)
+                        .copied()
+                        .flatten()
+                        .unwrap_or(i as f64 + 1.0);
+                    Some((x, (*y)?))
+                })
+                .collect()
+        };
+        let all: Vec<(f64, f64)> = series.iter().flat_map(|s| points(s)).collect();
+        let (x_low, x_high) = bounds(all.iter().map(|p| p.0));
+        let (y_low, y_high) = bounds(all.iter().map(|p| p.1));
+        let x_scale = Scale::new(x_low, x_high, None, None, None);
+        let y_scale = value_scale(chart, y_low, y_high);
+        let plot = write_value_axes(w, area, &y_scale, Some(&x_scale), chart)?;
+
+        let lines = chart
+            .plots()
+            .first()
+            .is_some_and(|p| p.chart_type != XlChartType::XyScatter);
+        for (n, s) in series.iter().enumerate() {
+            let color = hex(self.series_color(s, n));
+            let coords: Vec<(f64, f64)> = points(s)
+                .into_iter()
+                .map(|(x, y)| {
+                    (
+                        plot.x + x_scale.at(x) * plot.w,
+                        plot.y + plot.h - y_scale.at(y) * plot.h,
+                    )
+                })
+                .collect();
+            if lines && coords.len() > 1 {
+                write_polyline(w, &coords, &color)?;
+            }
+            for (x, y) in coords {
+                write!(
+                    w,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"2.5\" fill=\"{color}\"/>",
+                    num(x),
+                    num(y)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Bar, column, line and area charts, sharing the category axis.
+    fn write_category_chart(&self, w: &mut String, chart: &Chart, area: Area) -> fmt::Result {
+        let plots = chart.plots();
+        let count = plots
+            .iter()
+            .flat_map(|p| p.series.iter())
+            .map(|s| s.values().len())
+            .chain(
+                plots
+                    .iter()
+                    .map(|p| p.categories.as_ref().map_or(0, Vec::len)),
+            )
+            .max()
+            .unwrap_or(0);
+        if count == 0 {
+            return Ok(());
+        }
+        let stacks: Vec<Stacked> = plots.iter().map(|p| Stacked::new(p, count)).collect();
+        let (low, high) = bounds(stacks.iter().flat_map(Stacked::extent));
+        let mut scale = value_scale(chart, low, high);
+        let percent = plots
+            .iter()
+            .all(|p| grouping_for(p.chart_type) == Some(ChartGrouping::PercentStacked));
+        if percent {
+            scale = Scale {
+                min: 0.0,
+                max: 1.0,
+                step: 0.2,
+                percent: true,
+            };
+        }
+        let horizontal = plots[0].chart_type.is_bar_type();
+        let plot_area = if horizontal {
+            write_bar_axes(w, area, &scale, plots, count, chart)?
+        } else {
+            write_value_axes(w, area, &scale, None, chart)?
+        };
+        let axis = Axis {
+            area: plot_area,
+            scale,
+            count,
+            horizontal,
+        };
+
+        let mut n = 0;
+        for (plot, stack) in plots.iter().zip(&stacks) {
+            let colors: Vec<String> = plot
+                .series
+                .iter()
+                .map(|s| {
+                    n += 1;
+                    hex(self.series_color(s, n - 1))
+                })
+                .collect();
+            if plot.chart_type.is_bar_or_column() {
+                write_bars(w, plot, stack, &axis, &colors)?;
+            } else if plot.chart_type.is_area_type() {
+                write_areas(w, stack, &axis, &colors)?;
+            } else {
+                for (s, color) in stack.tops.iter().zip(&colors) {
+                    let coords: Vec<(f64, f64)> = s
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, v)| Some(axis.point(i, (*v)?)))
+                        .collect();
+                    write_polyline(w, &coords, color)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the values of one plot are drawn: each series' bottom and top,
+/// accumulated for stacked groupings.
+struct Stacked {
+    stacked: bool,
+    bottoms: Vec<Vec<f64>>,
+    tops: Vec<Vec<Option<f64>>>,
+}
+
+impl Stacked {
+    fn new(plot: &Plot, count: usize) -> Self {
+        let grouping = grouping_for(plot.chart_type);
+        let stacked = matches!(
+            grouping,
+            Some(ChartGrouping::Stacked | ChartGrouping::PercentStacked)
+        );
+        let values = |s: &Series, i: usize| s.values().get(i).copied().flatten();
+        let totals: Vec<f64> = (0..count)
+            .map(|i| {
+                plot.series
+                    .iter()
+                    .filter_map(|s| values(s, i))
+                    .map(f64::abs)
+                    .sum()
+            })
+            .collect();
+        let mut positive = vec![0.0; count];
+        let mut negative = vec![0.0; count];
+        let mut bottoms = Vec::new();
+        let mut tops = Vec::new();
+        for s in &plot.series {
+            let mut bottom = vec![0.0; count];
+            let mut top = vec![None; count];
+            for i in 0..count {
+                let Some(mut value) = values(s, i) else {
+                    continue;
+                };
+                if grouping == Some(ChartGrouping::PercentStacked) && totals[i] > 0.0 {
+                    value /= totals[i];
+                }
+                if stacked {
+                    let base = if value < 0.0 {
+                        &mut negative[i]
+                    } else {
+                        &mut positive[i]
+                    };
+                    bottom[i] = *base;
+                    *base += value;
+                    top[i] = Some(*base);
+                } else {
+                    top[i] = Some(value);
+                }
+            }
+            bottoms.push(bottom);
+            tops.push(top);
+        }
+        Self {
+            stacked,
+            bottoms,
+            tops,
+        }
+    }
+
+    /// The values the axis has to cover.
+    fn extent(&self) -> impl Iterator<Item = f64> + '_ {
+        self.tops.iter().flatten().flatten().copied()
+    }
+}
+
+/// The category axis and value scale of a plot area.
+struct Axis {
+    area: Area,
+    scale: Scale,
+    count: usize,
+    horizontal: bool,
+}
+
+impl Axis {
+    /// Width of one category's band along the category axis.
+    #[allow(clippy::cast_precision_loss)]
+    fn band(&self) -> f64 {
+        let length = if self.horizontal {
+            self.area.h
+        } else {
+            self.area.w
+        };
+        length / self.count as f64
+    }
+
+    /// Position of the value axis at `value`.
+    fn value_pos(&self, value: f64) -> f64 {
+        let at = self.scale.at(value);
+        if self.horizontal {
+            self.area.x + at * self.area.w
+        } else {
+            self.area.y + self.area.h - at * self.area.h
+        }
+    }
+
+    /// Start of category `i`'s band.
+    #[allow(clippy::cast_precision_loss)]
+    fn band_start(&self, i: usize) -> f64 {
+        let origin = if self.horizontal {
+            self.area.y
+        } else {
+            self.area.x
+        };
+        origin + i as f64 * self.band()
+    }
+
+    /// The point of `value` at the middle of category `i`.
+    fn point(&self, i: usize, value: f64) -> (f64, f64) {
+        let along = self.band_start(i) + self.band() / 2.0;
+        let across = self.value_pos(value);
+        if self.horizontal {
+            (across, along)
+        } else {
+            (along, across)
+        }
+    }
+}
+
+fn write_bars(
+    w: &mut String,
+    plot: &Plot,
+    stack: &Stacked,
+    axis: &Axis,
+    colors: &[String],
+) -> fmt::Result {
+    let gap = f64::from(plot.plot_properties.gap_width().unwrap_or(150)) / 100.0;
+    let group = axis.band() / (1.0 + gap);
+    #[allow(clippy::cast_precision_loss)]
+    let width = if stack.stacked {
+        group
+    } else {
+        group / stack.tops.len().max(1) as f64
+    };
+    for (n, (tops, bottoms)) in stack.tops.iter().zip(&stack.bottoms).enumerate() {
+        for (i, top) in tops.iter().enumerate() {
+            let Some(top) = top else { continue };
+            let slot = if stack.stacked { 0.0 } else { n as f64 };
+            #[allow(clippy::cast_precision_loss)]
+            let along = axis.band_start(i) + (axis.band() - group) / 2.0 + slot * width;
+            let a = axis.value_pos(bottoms[i]);
+            let b = axis.value_pos(*top);
+            let (x, y, w_, h) = if axis.horizontal {
+                (a.min(b), along, (b - a).abs(), width)
+            } else {
+                (along, a.min(b), width, (b - a).abs())
+            };
+            write!(
+                w,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                num(x),
+                num(y),
+                num(w_),
+                num(h),
+                colors[n]
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn write_areas(w: &mut String, stack: &Stacked, axis: &Axis, colors: &[String]) -> fmt::Result {
+    // Front series are drawn last, so draw from the back
+    for (n, (tops, bottoms)) in stack.tops.iter().zip(&stack.bottoms).enumerate().rev() {
+        let mut d = String::new();
+        for (i, top) in tops.iter().enumerate() {
+            let (x, y) = axis.point(i, top.unwrap_or(bottoms[i]));
+            let _ = write!(d, "{}{} {}", if i == 0 { 'M' } else { 'L' }, num(x), num(y));
+        }
+        for i in (0..tops.len()).rev() {
+            let (x, y) = axis.point(i, bottoms[i]);
+            let _ = write!(d, "L{} {}", num(x), num(y));
+        }
+        write!(w, "<path d=\"{d}Z\" fill=\"{}\"/>", colors[n])?;
+    }
+    Ok(())
+}
+
+fn write_polyline(w: &mut String, coords: &[(f64, f64)], color: &str) -> fmt::Result {
+    let points: Vec<String> = coords
+        .iter()
+        .map(|(x, y)| format!("{},{}", num(*x), num(*y)))
+        .collect();
+    write!(
+        w,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\" \
+         stroke-linejoin=\"round\"/>",
+        points.join(" ")
+    )
+}
+
+/// The value scale for values from `low` to `high`, with the limits set on
+/// the value axis.
+fn value_scale(chart: &Chart, low: f64, high: f64) -> Scale {
+    let axis = chart.value_axis();
+    Scale::new(
+        low,
+        high,
+        axis.and_then(|a| a.minimum_scale()),
+        axis.and_then(|a| a.maximum_scale()),
+        axis.and_then(|a| a.major_unit()),
+    )
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values
+        .filter(|v| v.is_finite())
+        .fold(None, |acc: Option<(f64, f64)>, v| {
+            Some(acc.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v))))
+        })
+        .unwrap_or((0.0, 1.0))
+}
+
+fn category(plot: &Plot, i: usize) -> String {
+    plot.categories
+        .as_ref()
+        .and_then(|c| c.get(i).cloned())
+        .unwrap_or_else(|| (i + 1).to_string())
+}
+
+fn gridlines(chart: &Chart) -> bool {
+    chart.value_axis().is_none_or(|a| a.has_major_gridlines())
+}
+
+/// Draw a vertical value axis at the left of `area` with gridlines, and a
+/// category axis (or the `x` value axis of scatter charts) along the
+/// bottom; returns the plot area inside them.
+fn write_value_axes(
+    w: &mut String,
+    area: Area,
+    scale: &Scale,
+    x_scale: Option<&Scale>,
+    chart: &Chart,
+) -> Result<Area, fmt::Error> {
+    let ticks = scale.ticks();
+    let label_w = ticks
+        .iter()
+        .map(|v| text_width(&scale.label(*v), LABEL_SIZE))
+        .fold(0.0, f64::max);
+    let plot = Area {
+        x: area.x + label_w + PAD,
+        y: area.y + LABEL_SIZE / 2.0,
+        w: (area.w - label_w - PAD).max(1.0),
+        h: (area.h - LABEL_SIZE * 2.0 - LABEL_SIZE / 2.0).max(1.0),
+    };
+    for value in &ticks {
+        let y = plot.y + plot.h - scale.at(*value) * plot.h;
+        if gridlines(chart) {
+            write_line(w, (plot.x, y), (plot.x + plot.w, y), GRIDLINE_COLOR)?;
+        }
+        write_label(
+            w,
+            plot.x - PAD / 2.0,
+            y + LABEL_SIZE / 3.0,
+            LABEL_SIZE,
+            "end",
+            &scale.label(*value),
+        )?;
+    }
+    let label_y = plot.y + plot.h + LABEL_SIZE * 1.5;
+    if let Some(x_scale) = x_scale {
+        for value in x_scale.ticks() {
+            let x = plot.x + x_scale.at(value) * plot.w;
+            write_label(w, x, label_y, LABEL_SIZE, "middle", &x_scale.label(value))?;
+        }
+    } else if let Some(first) = chart.plots().first() {
+        let count = first
+            .series
+            .iter()
+            .map(|s| s.values().len())
+            .max()
+            .unwrap_or(0);
+        let count = count.max(first.categories.as_ref().map_or(0, Vec::len));
+        #[allow(clippy::cast_precision_loss)]
+        let band = plot.w / count.max(1) as f64;
+        for i in 0..count {
+            #[allow(clippy::cast_precision_loss)]
+            let x = plot.x + (i as f64 + 0.5) * band;
+            write_label(w, x, label_y, LABEL_SIZE, "middle", &category(first, i))?;
+        }
+    }
+    let zero = plot.y + plot.h - scale.at(0.0_f64.clamp(scale.min, scale.max)) * plot.h;
+    write_line(w, (plot.x, zero), (plot.x + plot.w, zero), "#BFBFBF")?;
+    Ok(plot)
+}
+
+/// Axes of a horizontal bar chart: categories down the left, values
+/// along the bottom.
+fn write_bar_axes(
+    w: &mut String,
+    area: Area,
+    scale: &Scale,
+    plots: &[Plot],
+    count: usize,
+    chart: &Chart,
+) -> Result<Area, fmt::Error> {
+    let labels: Vec<String> = (0..count).map(|i| category(&plots[0], i)).collect();
+    let label_w = labels
+        .iter()
+        .map(|l| text_width(l, LABEL_SIZE))
+        .fold(0.0, f64::max)
+        .min(area.w / 3.0);
+    let plot = Area {
+        x: area.x + label_w + PAD,
+        y: area.y,
+        w: (area.w - label_w - PAD * 2.0).max(1.0),
+        h: (area.h - LABEL_SIZE * 2.0).max(1.0),
+    };
+    for value in scale.ticks() {
+        let x = plot.x + scale.at(value) * plot.w;
+        if gridlines(chart) {
+            write_line(w, (x, plot.y), (x, plot.y + plot.h), GRIDLINE_COLOR)?;
+        }
+        write_label(
+            w,
+            x,
+            plot.y + plot.h + LABEL_SIZE * 1.5,
+            LABEL_SIZE,
+            "middle",
+            &scale.label(value),
+        )?;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let band = plot.h / count.max(1) as f64;
+    for (i, label) in labels.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = plot.y + (i as f64 + 0.5) * band + LABEL_SIZE / 3.0;
+        write_label(w, plot.x - PAD / 2.0, y, LABEL_SIZE, "end", label)?;
+    }
+    let zero = plot.x + scale.at(0.0_f64.clamp(scale.min, scale.max)) * plot.w;
+    write_line(w, (zero, plot.y), (zero, plot.y + plot.h), "#BFBFBF")?;
+    Ok(plot)
+}
+
+fn write_line(w: &mut String, from: (f64, f64), to: (f64, f64), color: &str) -> fmt::Result {
+    write!(
+        w,
+        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{color}\" stroke-width=\"0.75\"/>",
+        num(from.0),
+        num(from.1),
+        num(to.0),
+        num(to.1)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::data::CategoryChartData;
+
+    fn chart(chart_type: XlChartType) -> Chart {
+        let mut data = CategoryChartData::new();
+        data.add_category("North");
+        data.add_category("South");
+        data.add_series("Sales", &[3.0, 5.0]);
+        data.add_series("Costs", &[1.0, 2.0]);
+        let xml = crate::chart::ChartXmlWriter::write_category(&data, chart_type).unwrap();
+        Chart::from_xml(xml.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn picks_round_steps() {
+        assert!((nice_step(10.0) - 2.0).abs() < 1e-9);
+        assert!((nice_step(0.7) - 0.2).abs() < 1e-9);
+        let scale = Scale::new(0.5, 9.2, None, None, None);
+        assert_eq!((scale.min, scale.max, scale.step), (0.0, 10.0, 2.0));
+    }
+
+    #[test]
+    fn draws_clustered_columns() {
+        let mut svg = String::new();
+        write_chart(
+            &mut svg,
+            &chart(XlChartType::ColumnClustered),
+            (300.0, 200.0),
+            &Colors::default(),
+        )
+        .unwrap();
+        assert!(svg.starts_with("<svg class=\"chart\""));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(">North</text>"));
+    }
+
+    #[test]
+    fn stacks_percentages() {
+        let stack = Stacked::new(&chart(XlChartType::ColumnStacked100).plots()[0], 2);
+        assert_eq!(stack.tops[1][0], Some(1.0));
+        assert!((stack.bottoms[1][0] - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn draws_pie_slices_with_legend() {
+        let mut chart = chart(XlChartType::Pie);
+        chart.set_has_legend(true);
+        let mut svg = String::new();
+        write_chart(&mut svg, &chart, (300.0, 200.0), &Colors::default()).unwrap();
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert!(svg.contains(">South</text>"));
+    }
+}
@@ -1,57 +1,317 @@
 //! PPTX to HTML export.
 //!
-//! Produces a self-contained HTML string with inline CSS. Each slide becomes
-//! a `<div class="slide">` element.
+//! Produces HTML with inline CSS and SVG. Each slide becomes a
+//! `<div class="slide">` element holding one absolutely positioned element
+//! per shape, with what shapes inherit from their layout and master
+//! resolved: plain rectangles are styled with CSS, other geometry, lines
+//! and pictures are inline SVG, tables are `<table>` markup and charts are
+//! drawn as SVG from the chart model. Speaker notes follow their slide.
+//!
+//! [`HtmlExportOptions`] choose between embedded and external images and
+//! between one document and one document per slide.
 
+mod chart;
 mod render;
-mod utils;
+pub(crate) mod utils;
+
+use std::collections::HashMap;
 
 use crate::error::{PptxError, PptxResult};
+use crate::export::scene::{build_shapes, SceneImage, SlideShapes};
+use crate::media::Image;
 use crate::presentation::Presentation;
-use crate::shapes::ShapeTree;
+use crate::slide::SlideRef;
 use crate::units::Emu;
-use utils::emu_to_px;
+use render::SlideWriter;
+use utils::{base64_encode, emu_to_px, html_escape};
+
+/// How pictures are included in exported HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlImages {
+    /// Embedded in the HTML as base64 `data:` URIs.
+    #[default]
+    Embedded,
+    /// Written as separate files under `media/` and referenced by path.
+    External,
+}
+
+/// How exported HTML is split into documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlLayout {
+    /// All slides in one document, `index.html`.
+    #[default]
+    SingleFile,
+    /// One document per slide, `slide1.html`, `slide2.html`, …, and an
+    /// `index.html` linking to them.
+    PerSlide,
+}
+
+/// Options for [`HtmlExporter`].
+///
+/// # Examples
+///
+/// ```
+/// use pptx::export::html::{HtmlExportOptions, HtmlImages, HtmlLayout};
+///
+/// let options = HtmlExportOptions::new()
+///     .with_images(HtmlImages::External)
+///     .with_layout(HtmlLayout::PerSlide)
+///     .with_notes(false);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlExportOptions {
+    /// How pictures are included.
+    pub images: HtmlImages,
+    /// How slides are split into documents.
+    pub layout: HtmlLayout,
+    /// Whether speaker notes are written after each slide.
+    pub include_notes: bool,
+}
+
+impl HtmlExportOptions {
+    /// Create options for one document with embedded images and notes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            images: HtmlImages::Embedded,
+            layout: HtmlLayout::SingleFile,
+            include_notes: true,
+        }
+    }
+
+    /// Set how pictures are included.
+    #[must_use]
+    pub const fn with_images(mut self, images: HtmlImages) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// Set how slides are split into documents.
+    #[must_use]
+    pub const fn with_layout(mut self, layout: HtmlLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set whether speaker notes are written.
+    #[must_use]
+    pub const fn with_notes(mut self, include_notes: bool) -> Self {
+        self.include_notes = include_notes;
+        self
+    }
+}
+
+impl Default for HtmlExportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A file produced by [`HtmlExporter::export_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlFile {
+    /// Path relative to the output directory, with `/` separators.
+    pub path: String,
+    pub data: Vec<u8>,
+}
 
-/// Converts a `Presentation` into a self-contained HTML document.
+/// Converts a `Presentation` into HTML.
 pub struct HtmlExporter<'a> {
     prs: &'a Presentation,
+    options: HtmlExportOptions,
 }
 
 impl<'a> HtmlExporter<'a> {
     /// Create a new exporter for the given presentation.
     #[must_use]
     pub const fn new(prs: &'a Presentation) -> Self {
-        Self { prs }
+        Self::with_options(prs, HtmlExportOptions::new())
     }
 
-    /// Export the presentation as a complete HTML document string.
+    /// Create an exporter with the given options.
+    #[must_use]
+    pub const fn with_options(prs: &'a Presentation, options: HtmlExportOptions) -> Self {
+        Self { prs, options }
+    }
+
+    /// Export the presentation as a complete HTML document string, with
+    /// all slides regardless of the layout option. With external images
+    /// the document refers to files only [`export_files`](Self::export_files)
+    /// produces.
     ///
     /// # Errors
     ///
     /// Returns an error if slide XML cannot be read or parsed.
-    #[allow(clippy::similar_names)]
     pub fn export(&self) -> PptxResult<String> {
         let slides = self.prs.slides()?;
+        let mut media = Media::new(self.options.images);
+        self.document(&slides, 0, &mut media)
+    }
+
+    /// Export the presentation as the files of its HTML layout: the
+    /// documents and, with external images, the image files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if slide XML cannot be read or parsed.
+    pub fn export_files(&self) -> PptxResult<Vec<HtmlFile>> {
+        let slides = self.prs.slides()?;
+        let mut media = Media::new(self.options.images);
+        let mut files = Vec::new();
+        match self.options.layout {
+            HtmlLayout::SingleFile => files.push(HtmlFile {
+                path: "index.html".to_string(),
+                data: self.document(&slides, 0, &mut media)?.into_bytes(),
+            }),
+            HtmlLayout::PerSlide => {
+                files.push(HtmlFile {
+                    path: "index.html".to_string(),
+                    data: self.index(&slides)?.into_bytes(),
+                });
+                for (idx, slide_ref) in slides.iter().enumerate() {
+                    let mut html =
+                        self.document(std::slice::from_ref(slide_ref), idx, &mut media)?;
+                    let nav = nav(idx, slides.len());
+                    html = html.replacen("<body>\n", &format!("<body>\n{nav}"), 1);
+                    files.push(HtmlFile {
+                        path: slide_file(idx),
+                        data: html.into_bytes(),
+                    });
+                }
+            }
+        }
+        files.extend(media.files);
+        Ok(files)
+    }
+
+    fn slide_size_px(&self) -> PptxResult<(f64, f64)> {
         let slide_size = self.prs.slide_size()?.unwrap_or((9_144_000, 6_858_000));
-        let slide_w_px = emu_to_px(Emu(slide_size.0));
-        let slide_h_px = emu_to_px(Emu(slide_size.1));
+        Ok((emu_to_px(Emu(slide_size.0)), emu_to_px(Emu(slide_size.1))))
+    }
 
+    /// A document with `slides`, numbered from `first`.
+    #[allow(clippy::similar_names)]
+    fn document(&self, slides: &[SlideRef], first: usize, media: &mut Media) -> PptxResult<String> {
+        let (slide_w_px, slide_h_px) = self.slide_size_px()?;
         let mut html = String::with_capacity(4096);
+        render::write_doc_header(&mut html, slide_w_px, slide_h_px).map_err(fmt_error)?;
+
+        let mut next_id = 0;
+        for (offset, slide_ref) in slides.iter().enumerate() {
+            let shapes = build_shapes(self.prs, slide_ref)?;
+            let images = media.sources(&shapes);
+            let notes = if self.options.include_notes {
+                self.prs.notes_slide(slide_ref)?
+            } else {
+                None
+            };
+            SlideWriter::new(&mut next_id, &images, &shapes.colors)
+                .write_slide(
+                    &mut html,
+                    first + offset,
+                    &shapes,
+                    notes.as_ref().and_then(|n| n.notes_text_frame()),
+                )
+                .map_err(fmt_error)?;
+        }
 
-        render::write_doc_header(&mut html, slide_w_px, slide_h_px)
-            .map_err(|e| PptxError::InvalidXml(e.to_string()))?;
+        render::write_doc_footer(&mut html).map_err(fmt_error)?;
+        Ok(html)
+    }
 
+    /// The index of a per-slide export.
+    fn index(&self, slides: &[SlideRef]) -> PptxResult<String> {
+        let (slide_w_px, slide_h_px) = self.slide_size_px()?;
+        let mut html = String::new();
+        render::write_doc_header(&mut html, slide_w_px, slide_h_px).map_err(fmt_error)?;
+        html.push_str("<nav><ol>\n");
         for (idx, slide_ref) in slides.iter().enumerate() {
-            let slide_xml = self.prs.slide_xml(slide_ref)?;
-            let tree = ShapeTree::from_slide_xml(slide_xml)?;
-            let slide_name = self.prs.slide_name(slide_ref)?.unwrap_or_default();
-            render::write_slide(&mut html, idx, &slide_name, &tree, slide_w_px, slide_h_px)
-                .map_err(|e| PptxError::InvalidXml(e.to_string()))?;
+            let name = self
+                .prs
+                .slide_name(slide_ref)?
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("Slide {}", idx + 1));
+            html.push_str(&format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                slide_file(idx),
+                html_escape(&name)
+            ));
         }
+        html.push_str("</ol></nav>\n");
+        render::write_doc_footer(&mut html).map_err(fmt_error)?;
+        Ok(html)
+    }
+}
 
-        render::write_doc_footer(&mut html).map_err(|e| PptxError::InvalidXml(e.to_string()))?;
+fn fmt_error(e: std::fmt::Error) -> PptxError {
+    PptxError::InvalidXml(e.to_string())
+}
 
-        Ok(html)
+fn slide_file(idx: usize) -> String {
+    format!("slide{}.html", idx + 1)
+}
+
+/// Links to the neighbors of slide `idx` and the index.
+fn nav(idx: usize, count: usize) -> String {
+    let mut links = Vec::new();
+    if idx > 0 {
+        links.push(format!("<a href=\"{}\">Previous</a>", slide_file(idx - 1)));
+    }
+    links.push("<a href=\"index.html\">Index</a>".to_string());
+    if idx + 1 < count {
+        links.push(format!("<a href=\"{}\">Next</a>", slide_file(idx + 1)));
+    }
+    format!("<nav>{}</nav>\n", links.join(" | "))
+}
+
+/// Where the images of exported slides come from.
+struct Media {
+    images: HtmlImages,
+    /// Image files written so far.
+    files: Vec<HtmlFile>,
+    /// Paths of the image files by SHA-1 of their data.
+    paths: HashMap<String, String>,
+}
+
+impl Media {
+    fn new(images: HtmlImages) -> Self {
+        Self {
+            images,
+            files: Vec::new(),
+            paths: HashMap::new(),
+        }
+    }
+
+    /// The `src` of each image of `slide`.
+    fn sources(&mut self, slide: &SlideShapes) -> Vec<String> {
+        slide
+            .images
+            .iter()
+            .map(|image| self.source(image))
+            .collect()
+    }
+
+    fn source(&mut self, image: &SceneImage) -> String {
+        match self.images {
+            HtmlImages::Embedded => format!(
+                "data:{};base64,{}",
+                image.content_type,
+                base64_encode(&image.data)
+            ),
+            HtmlImages::External => {
+                let image = Image::from_bytes(image.data.clone(), &image.content_type);
+                if let Some(path) = self.paths.get(image.sha1()) {
+                    return path.clone();
+                }
+                let path = format!("media/image{}.{}", self.files.len() + 1, image.ext());
+                self.paths.insert(image.sha1().to_string(), path.clone());
+                self.files.push(HtmlFile {
+                    path: path.clone(),
+                    data: image.blob().to_vec(),
+                });
+                path
+            }
+        }
     }
 }
 
@@ -67,11 +327,11 @@ pub fn export_to_html(prs: &Presentation) -> PptxResult<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::opc::constants::relationship_type as RT;
     use crate::presentation::Presentation;
     use crate::shapes::ShapeTree;
     use crate::text::font::RgbColor;
     use crate::units::Emu;
-    use utils::{base64_encode, html_escape};
 
     #[test]
     fn html_escape_special_chars() {
@@ -184,6 +444,201 @@ mod tests {
         assert!((px - 96.0).abs() < 0.01);
     }
 
+    fn append_shapes(prs: &mut Presentation, slide_ref: &SlideRef, shapes: &str) {
+        let xml = String::from_utf8(prs.slide_xml(slide_ref).unwrap().to_vec()).unwrap();
+        *prs.slide_xml_mut(slide_ref).unwrap() = xml
+            .replacen("</p:spTree>", &format!("{shapes}</p:spTree>"), 1)
+            .into_bytes();
+    }
+
+    fn blank_slide(prs: &mut Presentation) -> SlideRef {
+        let layouts = prs.slide_layouts().unwrap();
+        prs.add_slide(&layouts[6]).unwrap()
+    }
+
+    fn sp(id: u32, name: &str, xfrm: &str, geometry: &str, fill: &str) -> String {
+        format!(
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="{name}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr>{xfrm}<a:prstGeom prst="{geometry}"><a:avLst/></a:prstGeom><a:solidFill><a:srgbClr val="{fill}"/></a:solidFill></p:spPr></p:sp>"#
+        )
+    }
+
+    #[test]
+    fn export_draws_geometry_with_css_and_svg() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let shapes = [
+            sp(
+                2,
+                "Box",
+                r#"<a:xfrm><a:off x="0" y="0"/><a:ext cx="952500" cy="952500"/></a:xfrm>"#,
+                "rect",
+                "FF0000",
+            ),
+            sp(
+                3,
+                "Oval",
+                r#"<a:xfrm rot="1800000"><a:off x="952500" y="0"/><a:ext cx="952500" cy="476250"/></a:xfrm>"#,
+                "ellipse",
+                "00FF00",
+            ),
+        ]
+        .concat();
+        append_shapes(&mut prs, &slide_ref, &shapes);
+
+        let html = HtmlExporter::new(&prs).export().unwrap();
+        // 952500 EMU is 100 px
+        assert!(html.contains(
+            "title=\"Box\" style=\"left:0px;top:0px;width:100px;height:100px;background:#FF0000;"
+        ));
+        assert!(html.contains("title=\"Oval\" style=\"left:100px;top:0px;width:100px;height:50px;transform:rotate(30deg);\"><svg class=\"geometry\""));
+        assert!(html.contains("fill=\"#00FF00\""));
+    }
+
+    #[test]
+    fn export_writes_tables_with_spans_and_borders() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let xml = prs.slide_xml(&slide_ref).unwrap().to_vec();
+        let updated =
+            ShapeTree::add_table(&xml, 2, 2, Emu(0), Emu(0), Emu(1_905_000), Emu(952_500)).unwrap();
+        let xml = String::from_utf8(updated).unwrap();
+        // Merge the first row and put text in it
+        let xml = xml
+            .replacen("<a:tc>", r#"<a:tc gridSpan="2">"#, 1)
+            .replacen("<a:tc>", r#"<a:tc hMerge="1">"#, 1)
+            .replacen(
+                "<a:endParaRPr",
+                r#"<a:r><a:t>Merged</a:t></a:r><a:endParaRPr"#,
+                1,
+            );
+        *prs.slide_xml_mut(&slide_ref).unwrap() = xml.into_bytes();
+
+        let html = HtmlExporter::new(&prs).export().unwrap();
+        assert!(html.contains("<table class=\"pptx-table\" style=\"width:150pt;height:75pt;\">"));
+        assert!(html.contains("<col style=\"width:75pt;\">"));
+        assert!(html.contains("<td colspan=\"2\" style=\"background:"));
+        assert!(html.contains("border-left:1pt solid #FFFFFF;"));
+        assert!(html.contains(">Merged</span>"));
+        assert_eq!(html.matches("<td").count(), 3);
+    }
+
+    #[test]
+    fn export_draws_charts_as_svg() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let mut data = crate::chart::data::CategoryChartData::new();
+        data.add_category("Q1");
+        data.add_category("Q2");
+        data.add_series("Revenue", &[4.0, 6.0]);
+        prs.add_chart_to_slide(
+            &slide_ref,
+            &data,
+            crate::enums::chart::XlChartType::ColumnClustered,
+            Emu(0),
+            Emu(0),
+            Emu(4_572_000),
+            Emu(2_743_200),
+        )
+        .unwrap();
+
+        let html = HtmlExporter::new(&prs).export().unwrap();
+        assert!(html.contains("<svg class=\"chart\""));
+        assert!(html.contains(">Q2</text>"));
+    }
+
+    #[test]
+    fn export_writes_speaker_notes_unless_disabled() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let notes = prs.notes_slide_or_create(&slide_ref).unwrap();
+        let part = prs.package_mut().part_mut(&notes.partname).unwrap();
+        part.blob = String::from_utf8(part.blob.clone())
+            .unwrap()
+            .replacen(
+                "<a:endParaRPr",
+                "<a:r><a:t>Say hello</a:t></a:r><a:endParaRPr",
+                1,
+            )
+            .into_bytes();
+
+        let html = HtmlExporter::new(&prs).export().unwrap();
+        assert!(html.contains(
+            "<aside class=\"notes\"><p style=\"margin:0 0 2px 0;\">Say hello</p></aside>"
+        ));
+
+        let options = HtmlExportOptions::new().with_notes(false);
+        let html = HtmlExporter::with_options(&prs, options).export().unwrap();
+        assert!(!html.contains("Say hello"));
+    }
+
+    #[test]
+    fn export_files_per_slide_with_external_images() {
+        let mut prs = Presentation::new().unwrap();
+        let first = blank_slide(&mut prs);
+        blank_slide(&mut prs);
+        let png = crate::render::SlideImage {
+            width: 1,
+            height: 1,
+            pixels: vec![0, 0, 255, 255],
+        }
+        .to_png();
+        let image = Image::from_bytes(png.clone(), "image/png");
+        let (image_partname, _) = prs.package_mut().or_add_image_part(&image).unwrap();
+        let slide_part = prs.package_mut().part_mut(&first.partname).unwrap();
+        let target = image_partname.relative_ref(slide_part.partname.base_uri());
+        let r_id = slide_part.rels.or_add(RT::IMAGE, &target, false);
+        let pic = format!(
+            r#"<p:pic><p:nvPicPr><p:cNvPr id="2" name="Picture"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="{r_id}"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="952500" cy="952500"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic>"#
+        );
+        append_shapes(&mut prs, &first, &pic);
+
+        let options = HtmlExportOptions::new()
+            .with_images(HtmlImages::External)
+            .with_layout(HtmlLayout::PerSlide);
+        let files = prs.export_html_files(options).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "index.html",
+                "slide1.html",
+                "slide2.html",
+                "media/image1.png"
+            ]
+        );
+        assert_eq!(files[3].data, png);
+        let index = String::from_utf8(files[0].data.clone()).unwrap();
+        assert!(index.contains("<a href=\"slide2.html\">"));
+        let slide1 = String::from_utf8(files[1].data.clone()).unwrap();
+        assert!(slide1.contains("<image href=\"media/image1.png\""));
+        assert!(slide1.contains("<a href=\"slide2.html\">Next</a>"));
+        assert!(!slide1.contains("data:image"));
+
+        // Embedded images go into the document itself
+        let html = HtmlExporter::new(&prs).export().unwrap();
+        assert!(html.contains("<image href=\"data:image/png;base64,"));
+    }
+
+    #[test]
+    fn export_resolves_placeholder_formatting() {
+        let mut prs = Presentation::new().unwrap();
+        let layouts = prs.slide_layouts().unwrap();
+        let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+        append_shapes(
+            &mut prs,
+            &slide_ref,
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Title 1"/><p:cNvSpPr/><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr><p:spPr/><p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:t>Quarterly review</a:t></a:r></a:p></p:txBody></p:sp>"#,
+        );
+
+        let html = HtmlExporter::new(&prs).export().unwrap();
+        // The title takes its position from the layout and its size from
+        // the master's title style
+        let title = html.find(">Quarterly review</span>").unwrap();
+        let shape = html[..title].rfind("<div class=\"shape\"").unwrap();
+        assert!(!html[shape..title].contains("width:0px"));
+        assert!(html[shape..title].contains("font-size:44pt;"));
+    }
+
     #[test]
     fn export_presentation_method() {
         let prs = Presentation::new().unwrap();
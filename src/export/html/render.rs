@@ -2,8 +2,15 @@
 
 use std::fmt::{self, Write};
 
-use super::utils::{base64_encode, emu_to_px, html_escape};
-use crate::shapes::{Shape, ShapeTree};
+use super::chart::write_chart;
+use super::utils::html_escape;
+use crate::enums::text::{MsoVerticalAnchor, PpParagraphAlignment};
+use crate::export::scene::{
+    Colors, Item, Matrix, Paint, Path, RunStyle, Shadow, ShapeContent, ShapeView, SlideShapes,
+    Stroke, TableView, TextDefaults, TextStyler,
+};
+use crate::export::svg::writer::{hex, num, pt_to_px, SvgWriter};
+use crate::text::{Paragraph, TextFrame};
 
 pub(super) fn write_doc_header(w: &mut String, slide_w: f64, slide_h: f64) -> fmt::Result {
     w.write_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n")?;
@@ -24,13 +31,24 @@ pub(super) fn write_doc_header(w: &mut String, slide_w: f64, slide_h: f64) -> fm
         ".slide-number { position: absolute; bottom: 8px; right: 12px; \
          font-size: 12px; color: #999; }\n",
     )?;
-    w.write_str(".shape { position: absolute; box-sizing: border-box; overflow: hidden; }\n")?;
-    w.write_str("table.pptx-table { border-collapse: collapse; width: 100%; height: 100%; }\n")?;
+    w.write_str(".slide > svg.background { position: absolute; left: 0; top: 0; }\n")?;
+    w.write_str(".shape { position: absolute; box-sizing: border-box; }\n")?;
+    w.write_str(".shape > svg { position: absolute; left: 0; top: 0; overflow: visible; }\n")?;
+    w.write_str(".group { position: absolute; left: 0; top: 0; transform-origin: 0 0; }\n")?;
     w.write_str(
-        "table.pptx-table td, table.pptx-table th { border: 1px solid #bbb; padding: 4px 6px; \
-         vertical-align: top; font-size: 14px; }\n",
+        ".text { position: absolute; left: 0; top: 0; width: 100%; height: 100%; \
+         box-sizing: border-box; display: flex; flex-direction: column; \
+         overflow-wrap: break-word; }\n",
     )?;
-    w.write_str("img.shape-img { width: 100%; height: 100%; object-fit: contain; }\n")?;
+    w.write_str(".text p, table.pptx-table p { margin: 0; }\n")?;
+    w.write_str(".bullet { display: inline-block; text-indent: 0; }\n")?;
+    w.write_str("table.pptx-table { border-collapse: collapse; table-layout: fixed; }\n")?;
+    w.write_str("table.pptx-table td { overflow-wrap: break-word; }\n")?;
+    writeln!(
+        w,
+        ".notes {{ width: {slide_w:.0}px; margin: 0 auto 20px; font-size: 14px; color: #333; }}"
+    )?;
+    w.write_str("nav { text-align: center; }\n")?;
     w.write_str("</style>\n")?;
     w.write_str("</head>\n<body>\n")
 }
@@ -39,96 +57,489 @@ pub(super) fn write_doc_footer(w: &mut String) -> fmt::Result {
     w.write_str("</body>\n</html>\n")
 }
 
-pub(super) fn write_slide(
-    w: &mut String,
-    index: usize,
-    _name: &str,
-    tree: &ShapeTree,
-    slide_w: f64,
-    slide_h: f64,
-) -> fmt::Result {
-    writeln!(
-        w,
-        "<div class=\"slide\" style=\"width:{slide_w:.0}px;height:{slide_h:.0}px;\">"
-    )?;
+/// Writes a slide of an HTML document.
+pub(super) struct SlideWriter<'a> {
+    /// Counter for IDs of SVG definitions, unique within the document.
+    next_id: &'a mut usize,
+    /// `src` of each image of the slide.
+    images: &'a [String],
+    colors: &'a Colors,
+}
 
-    for shape in tree.iter() {
-        write_shape(w, shape)?;
+impl<'a> SlideWriter<'a> {
+    pub(super) fn new(next_id: &'a mut usize, images: &'a [String], colors: &'a Colors) -> Self {
+        Self {
+            next_id,
+            images,
+            colors,
+        }
     }
 
-    // Slide number
-    writeln!(w, "<div class=\"slide-number\">{}</div>", index + 1)?;
-    w.write_str("</div>\n")
-}
+    /// Write slide `index` and, if given, its speaker notes.
+    pub(super) fn write_slide(
+        &mut self,
+        w: &mut String,
+        index: usize,
+        slide: &SlideShapes,
+        notes: Option<&TextFrame>,
+    ) -> fmt::Result {
+        write!(
+            w,
+            "<div class=\"slide\" id=\"slide-{}\" style=\"width:{}px;height:{}px;",
+            index + 1,
+            num(pt_to_px(slide.width)),
+            num(pt_to_px(slide.height)),
+        )?;
+        let solid = match slide.background.as_slice() {
+            [Item::Fill(_, Paint::Solid(color))] => Some(*color),
+            _ => None,
+        };
+        if let Some(color) = solid {
+            write!(w, "background:{};", hex(color))?;
+        }
+        w.write_str("\">\n")?;
+        if solid.is_none() {
+            SvgWriter::new(self.next_id, self.images).write_svg(
+                w,
+                "background",
+                (slide.width, slide.height),
+                &slide.background,
+            )?;
+            w.write_char('\n')?;
+        }
 
-fn write_shape(w: &mut String, shape: &Shape) -> fmt::Result {
-    let left = emu_to_px(shape.left());
-    let top = emu_to_px(shape.top());
-    let width = emu_to_px(shape.width());
-    let height = emu_to_px(shape.height());
+        for shape in &slide.shapes {
+            self.write_shape(w, shape)?;
+        }
 
-    write!(
-        w,
-        "<div class=\"shape\" title=\"{}\" style=\"left:{left:.1}px;top:{top:.1}px;\
-         width:{width:.1}px;height:{height:.1}px;\">",
-        html_escape(shape.name()),
-    )?;
+        // Slide number
+        writeln!(w, "<div class=\"slide-number\">{}</div>", index + 1)?;
+        w.write_str("</div>\n")?;
 
-    match shape {
-        Shape::AutoShape(auto) => {
-            if let Some(ref tf) = auto.text_frame {
-                write_text_frame(w, tf)?;
+        if let Some(notes) = notes.filter(|tf| !tf.text().trim().is_empty()) {
+            w.write_str("<aside class=\"notes\">")?;
+            for para in notes.paragraphs() {
+                write_paragraph(w, para)?;
             }
+            w.write_str("</aside>\n")?;
         }
-        Shape::Picture(pic) => {
-            write_picture(w, pic)?;
+        Ok(())
+    }
+
+    fn write_shape(&mut self, w: &mut String, shape: &ShapeView) -> fmt::Result {
+        let (width, height) = (shape.width, shape.height);
+        write!(
+            w,
+            "<div class=\"shape\" title=\"{}\" style=\"left:{}px;top:{}px;width:{}px;height:{}px;",
+            html_escape(&shape.name),
+            num(pt_to_px(shape.x)),
+            num(pt_to_px(shape.y)),
+            num(pt_to_px(width)),
+            num(pt_to_px(height)),
+        )?;
+        if shape.rotation != 0.0 {
+            write!(w, "transform:rotate({}deg);", num(shape.rotation))?;
         }
-        Shape::GraphicFrame(gf) => {
-            if gf.has_table {
-                // We cannot access table data from GraphicFrame directly
-                // (it only stores has_table flag). Show a placeholder.
+        let css = css_box(shape);
+        if let Some(css) = &css {
+            w.write_str(css)?;
+            if let Some(shadow) = &shape.shadow {
+                write_box_shadow(w, shadow)?;
+            }
+        }
+        w.write_str("\">")?;
+
+        if css.is_none() && !shape.graphics.is_empty() {
+            let mut items = shape.graphics.clone();
+            if shape.flip_h || shape.flip_v {
+                let flip = Matrix::rotate_about(
+                    0.0,
+                    width / 2.0,
+                    height / 2.0,
+                    shape.flip_h,
+                    shape.flip_v,
+                );
+                items = vec![Item::Group {
+                    transform: flip,
+                    clip: None,
+                    items,
+                }];
+            }
+            let mut svg = String::new();
+            SvgWriter::new(self.next_id, self.images).write_svg(
+                &mut svg,
+                "geometry",
+                (width, height),
+                &items,
+            )?;
+            match shape.shadow.as_ref().filter(|s| !s.inner) {
+                Some(shadow) => {
+                    // The shadow follows the outline, so it goes on the SVG
+                    let style = format!(
+                        " style=\"filter:drop-shadow({}pt {}pt {}pt {})\">",
+                        num(shadow.dx),
+                        num(shadow.dy),
+                        num(shadow.blur / 2.0),
+                        rgba(shadow)
+                    );
+                    w.push_str(&svg.replacen('>', &style, 1));
+                }
+                None => w.push_str(&svg),
+            }
+        }
+
+        match &shape.content {
+            ShapeContent::None => {}
+            ShapeContent::Text(text) => {
                 write!(
                     w,
-                    "<div style=\"padding:8px;font-style:italic;color:#888;\">\
-                     [Table: {}]</div>",
-                    html_escape(&gf.name)
+                    "<div class=\"text\" style=\"padding:{};justify-content:{};",
+                    padding(text.insets),
+                    flex_anchor(text.defaults.anchor),
                 )?;
+                if !text.frame.word_wrap {
+                    w.write_str("white-space:nowrap;")?;
+                }
+                w.write_str("\">")?;
+                self.write_paragraphs(w, &text.frame, &text.defaults)?;
+                w.write_str("</div>")?;
+            }
+            ShapeContent::Table(table) => self.write_table(w, table)?,
+            ShapeContent::Chart(chart) => write_chart(w, chart, (width, height), self.colors)?,
+            ShapeContent::Group { transform, shapes } => {
+                let [a, b, c, d, e, f] = transform.0;
+                writeln!(
+                    w,
+                    "<div class=\"group\" style=\"transform:matrix({},{},{},{},{},{});\">",
+                    num(a),
+                    num(b),
+                    num(c),
+                    num(d),
+                    num(pt_to_px(e)),
+                    num(pt_to_px(f)),
+                )?;
+                for child in shapes {
+                    self.write_shape(w, child)?;
+                }
+                w.write_str("</div>")?;
             }
         }
-        Shape::GroupShape(group) => {
-            for child in &group.shapes {
-                write_shape(w, child)?;
+
+        w.write_str("</div>\n")
+    }
+
+    fn write_paragraphs(
+        &self,
+        w: &mut String,
+        tf: &TextFrame,
+        defaults: &TextDefaults,
+    ) -> fmt::Result {
+        let styler = TextStyler::new(tf, defaults, self.colors);
+        for (para, label) in tf.paragraphs().iter().zip(tf.bullet_labels()) {
+            write_styled_paragraph(w, &styler, para, label.as_deref())?;
+        }
+        Ok(())
+    }
+
+    fn write_table(&self, w: &mut String, view: &TableView) -> fmt::Result {
+        write!(
+            w,
+            "<table class=\"pptx-table\" style=\"width:{}pt;height:{}pt;\"><colgroup>",
+            num(view.columns.iter().sum()),
+            num(view.rows.iter().sum()),
+        )?;
+        for width in &view.columns {
+            write!(w, "<col style=\"width:{}pt;\">", num(*width))?;
+        }
+        w.write_str("</colgroup>")?;
+        for (ri, row) in view.table.rows().iter().enumerate() {
+            write!(
+                w,
+                "<tr style=\"height:{}pt;\">",
+                num(view.rows.get(ri).copied().unwrap_or(0.0))
+            )?;
+            // Cells covered by a merge are rendered by their origin cell.
+            for (ci, cell) in row.cells.iter().enumerate() {
+                if cell.is_spanned() {
+                    continue;
+                }
+                let Some(look) = view.cells.get(ri).and_then(|r| r.get(ci)) else {
+                    continue;
+                };
+                w.write_str("<td")?;
+                if cell.grid_span > 1 {
+                    write!(w, " colspan=\"{}\"", cell.grid_span)?;
+                }
+                if cell.row_span > 1 {
+                    write!(w, " rowspan=\"{}\"", cell.row_span)?;
+                }
+                w.write_str(" style=\"")?;
+                if let Some(fill) = &look.fill {
+                    let span = |sizes: &[f64], from: usize, count: u32| -> f64 {
+                        sizes.iter().skip(from).take(count.max(1) as usize).sum()
+                    };
+                    let size = (
+                        span(&view.columns, ci, cell.grid_span),
+                        span(&view.rows, ri, cell.row_span),
+                    );
+                    write_css_background(w, fill, size)?;
+                }
+                for (side, border) in ["left", "right", "top", "bottom"].iter().zip(&look.borders) {
+                    write_border(w, side, border.as_ref())?;
+                }
+                write!(
+                    w,
+                    "padding:{};vertical-align:{};\">",
+                    padding(look.insets),
+                    match look.text.anchor {
+                        MsoVerticalAnchor::Middle => "middle",
+                        MsoVerticalAnchor::Bottom => "bottom",
+                        _ => "top",
+                    }
+                )?;
+                self.write_paragraphs(w, &cell.text_frame, &look.text)?;
+                w.write_str("</td>")?;
+            }
+            w.write_str("</tr>")?;
+        }
+        w.write_str("</table>")
+    }
+}
+
+/// CSS drawing a shape's graphics when they are a plain rectangle with a
+/// solid or gradient fill and a solid outline; `None` when they need SVG.
+fn css_box(shape: &ShapeView) -> Option<String> {
+    let rect = Path::rect(0.0, 0.0, shape.width, shape.height);
+    let (fill, stroke) = match shape.graphics.as_slice() {
+        [] => return Some(String::new()),
+        [Item::Fill(path, paint)] => (Some((path, paint)), None),
+        [Item::Stroke(path, stroke)] => (None, Some((path, stroke))),
+        [Item::Fill(fill, paint), Item::Stroke(path, stroke)] => {
+            (Some((fill, paint)), Some((path, stroke)))
+        }
+        _ => return None,
+    };
+    let mut css = String::new();
+    if let Some((path, paint)) = fill {
+        if *path != rect || matches!(paint, Paint::Pattern { .. }) {
+            return None;
+        }
+        let paint = match paint {
+            Paint::LinearGradient { start, end, stops } if shape.flip_h || shape.flip_v => {
+                let flip = |(x, y): (f64, f64)| {
+                    (
+                        if shape.flip_h { shape.width - x } else { x },
+                        if shape.flip_v { shape.height - y } else { y },
+                    )
+                };
+                Paint::LinearGradient {
+                    start: flip(*start),
+                    end: flip(*end),
+                    stops: stops.clone(),
+                }
             }
+            _ => paint.clone(),
+        };
+        write_css_background(&mut css, &paint, (shape.width, shape.height)).ok()?;
+    }
+    if let Some((path, stroke)) = stroke {
+        if *path != rect || !stroke.dash.is_empty() {
+            return None;
         }
-        Shape::Connector(_) | Shape::OleObject(_) => {
-            // Connectors and OLE objects are not visually rendered in HTML
+        // Outlines are centered on the edge, as in PowerPoint
+        let _ = write!(
+            css,
+            "outline:{}pt solid {};outline-offset:{}pt;",
+            num(stroke.width),
+            hex(stroke.color),
+            num(-stroke.width / 2.0)
+        );
+    }
+    Some(css)
+}
+
+/// CSS painting a box of `width` × `height` points with `paint`. Patterns
+/// are approximated by their background color.
+fn write_css_background(w: &mut String, paint: &Paint, (width, height): (f64, f64)) -> fmt::Result {
+    match paint {
+        Paint::Solid(color) => write!(w, "background:{};", hex(*color)),
+        Paint::LinearGradient { start, end, stops } => {
+            // CSS gradients run through the center at an angle from "to
+            // top", over a length that reaches the corners; project the
+            // stops onto that line
+            let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+            let angle = dx.atan2(-dy);
+            let (sin, cos) = angle.sin_cos();
+            let length = (width * sin).abs() + (height * cos).abs();
+            let at = |t: f64| {
+                let (x, y) = (start.0 + t * dx, start.1 + t * dy);
+                ((x - width / 2.0) * sin - (y - height / 2.0) * cos) / length.max(f64::EPSILON)
+                    + 0.5
+            };
+            write!(
+                w,
+                "background:linear-gradient({}deg",
+                num(angle.to_degrees())
+            )?;
+            for (position, color) in stops {
+                write!(w, ",{} {}%", hex(*color), num(at(*position) * 100.0))?;
+            }
+            w.write_str(");")
         }
+        Paint::Pattern { background, .. } => write!(w, "background:{};", hex(*background)),
     }
+}
+
+fn write_border(w: &mut String, side: &str, stroke: Option<&Stroke>) -> fmt::Result {
+    match stroke {
+        Some(stroke) => write!(
+            w,
+            "border-{side}:{}pt {} {};",
+            num(stroke.width),
+            if stroke.dash.is_empty() {
+                "solid"
+            } else {
+                "dashed"
+            },
+            hex(stroke.color)
+        ),
+        None => write!(w, "border-{side}:none;"),
+    }
+}
+
+fn write_box_shadow(w: &mut String, shadow: &Shadow) -> fmt::Result {
+    write!(
+        w,
+        "box-shadow:{}{}pt {}pt {}pt {};",
+        if shadow.inner { "inset " } else { "" },
+        num(shadow.dx),
+        num(shadow.dy),
+        num(shadow.blur),
+        rgba(shadow)
+    )
+}
+
+fn rgba(shadow: &Shadow) -> String {
+    let c = shadow.color;
+    format!("rgba({},{},{},{})", c.r, c.g, c.b, num(shadow.opacity))
+}
+
+/// CSS padding for left, top, right and bottom insets in points.
+fn padding([left, top, right, bottom]: [f64; 4]) -> String {
+    format!(
+        "{}pt {}pt {}pt {}pt",
+        num(top),
+        num(right),
+        num(bottom),
+        num(left)
+    )
+}
 
-    w.write_str("</div>\n")
+const fn flex_anchor(anchor: MsoVerticalAnchor) -> &'static str {
+    match anchor {
+        MsoVerticalAnchor::Middle => "center",
+        MsoVerticalAnchor::Bottom => "flex-end",
+        _ => "flex-start",
+    }
 }
 
-fn write_text_frame(w: &mut String, tf: &crate::text::TextFrame) -> fmt::Result {
-    w.write_str("<div style=\"padding:4px;\">")?;
-    for para in tf.paragraphs() {
-        write_paragraph(w, para)?;
+const fn text_align(alignment: PpParagraphAlignment) -> &'static str {
+    match alignment {
+        PpParagraphAlignment::Left => "left",
+        PpParagraphAlignment::Center => "center",
+        PpParagraphAlignment::Right => "right",
+        PpParagraphAlignment::Justify
+        | PpParagraphAlignment::JustifyLow
+        | PpParagraphAlignment::Distribute
+        | PpParagraphAlignment::ThaiDistribute => "justify",
     }
-    w.write_str("</div>")
 }
 
-fn write_paragraph(w: &mut String, para: &crate::text::Paragraph) -> fmt::Result {
+/// A paragraph with the formatting it inherits resolved by `styler`.
+fn write_styled_paragraph(
+    w: &mut String,
+    styler: &TextStyler<'_>,
+    para: &Paragraph,
+    label: Option<&str>,
+) -> fmt::Result {
+    let style = styler.paragraph(para, label);
+    write!(
+        w,
+        "<p style=\"margin:{}pt 0 {}pt {}pt;text-indent:{}pt;text-align:{};\
+         line-height:{};font-size:{}pt;\">",
+        num(style.space_before),
+        num(style.space_after),
+        num(style.margin),
+        num(style.indent),
+        text_align(style.alignment),
+        num(style.line_spacing * 1.2),
+        num(style.base.size),
+    )?;
+    if let Some((text, run)) = &style.bullet {
+        w.write_str("<span class=\"bullet\" style=\"")?;
+        write_run_css(w, run)?;
+        if style.indent < 0.0 {
+            // Hanging bullets fill the indent
+            write!(w, "min-width:{}pt;", num(-style.indent))?;
+        } else {
+            w.write_str("margin-right:0.5em;")?;
+        }
+        write!(w, "\">{}</span>", html_escape(text))?;
+    }
+    if para.runs().iter().all(|r| r.text().is_empty()) {
+        w.write_str("&nbsp;")?;
+    }
+    for run in para.runs() {
+        if run.is_line_break {
+            w.write_str("<br>")?;
+            continue;
+        }
+        if run.text().is_empty() {
+            continue;
+        }
+        let link = run.hyperlink.as_ref().and_then(|h| h.address.as_deref());
+        if let Some(href) = link {
+            write!(w, "<a href=\"{}\">", html_escape(href))?;
+        }
+        w.write_str("<span style=\"")?;
+        write_run_css(w, &styler.run(para, run.font()))?;
+        write!(w, "\">{}</span>", html_escape(run.text()))?;
+        if link.is_some() {
+            w.write_str("</a>")?;
+        }
+    }
+    w.write_str("</p>")
+}
+
+fn write_run_css(w: &mut String, run: &RunStyle) -> fmt::Result {
+    if let Some(font) = &run.font {
+        write!(w, "font-family:'{}',sans-serif;", html_escape(font))?;
+    }
+    write!(w, "font-size:{}pt;color:{};", num(run.size), hex(run.color))?;
+    if run.bold {
+        w.write_str("font-weight:bold;")?;
+    }
+    if run.italic {
+        w.write_str("font-style:italic;")?;
+    }
+    match (run.underline, run.strike) {
+        (true, true) => w.write_str("text-decoration:underline line-through;")?,
+        (true, false) => w.write_str("text-decoration:underline;")?,
+        (false, true) => w.write_str("text-decoration:line-through;")?,
+        (false, false) => {}
+    }
+    if run.shift != 0.0 {
+        write!(w, "position:relative;top:{}pt;", num(run.shift))?;
+    }
+    Ok(())
+}
+
+/// A paragraph with only the formatting set on its runs, as used for
+/// speaker notes.
+fn write_paragraph(w: &mut String, para: &Paragraph) -> fmt::Result {
     w.write_str("<p style=\"margin:0 0 2px 0;")?;
     if let Some(align) = para.alignment {
-        let align_str = match align {
-            crate::enums::text::PpParagraphAlignment::Left => "left",
-            crate::enums::text::PpParagraphAlignment::Center => "center",
-            crate::enums::text::PpParagraphAlignment::Right => "right",
-            crate::enums::text::PpParagraphAlignment::Justify
-            | crate::enums::text::PpParagraphAlignment::JustifyLow
-            | crate::enums::text::PpParagraphAlignment::Distribute
-            | crate::enums::text::PpParagraphAlignment::ThaiDistribute => "justify",
-        };
-        write!(w, "text-align:{align_str};")?;
+        write!(w, "text-align:{};", text_align(align))?;
     }
     if para.level > 0 {
         write!(w, "margin-left:{}px;", u32::from(para.level) * 24)?;
@@ -205,24 +616,3 @@ fn write_font_css(w: &mut String, font: &crate::text::font::Font) -> fmt::Result
     }
     Ok(())
 }
-
-fn write_picture(w: &mut String, pic: &crate::shapes::Picture) -> fmt::Result {
-    if let (Some(data), Some(ct)) = (&pic.image_data, &pic.image_content_type) {
-        let b64 = base64_encode(data);
-        write!(
-            w,
-            "<img class=\"shape-img\" src=\"data:{ct};base64,{b64}\" alt=\"{}\">",
-            html_escape(pic.description.as_deref().unwrap_or(&pic.name)),
-        )?;
-    } else {
-        // No cached image data available; show placeholder
-        write!(
-            w,
-            "<div style=\"width:100%;height:100%;background:#eee;display:flex;\
-             align-items:center;justify-content:center;font-size:12px;color:#888;\">\
-             [Image: {}]</div>",
-            html_escape(&pic.name),
-        )?;
-    }
-    Ok(())
-}
@@ -0,0 +1,676 @@
+//! Markdown outline export and import.
+//!
+//! [`MarkdownExporter`] writes the text of a presentation as a Markdown
+//! outline, handy for reviewing decks in diffs. The same format can be read
+//! back with [`Presentation::add_slides_from_markdown`]:
+//!
+//! - slides are separated by `---` lines, and a `#` or `##` heading starts
+//!   a new slide with that title;
+//! - list items (`-`, `*`, `+` or `1.`) become bullets, two spaces of
+//!   indentation per level;
+//! - other lines are paragraphs without a bullet;
+//! - pipe tables become tables, with the header as the first row;
+//! - `>` quotes hold the speaker notes;
+//! - `**bold**`, `*italic*` and `<br>` line breaks are kept within text.
+//!
+//! Pictures are written as `![description](file)`, but not imported, since
+//! the outline only names their files.
+//!
+//! ```
+//! use pptx::Presentation;
+//!
+//! let prs = Presentation::from_markdown("# Agenda\n\n- Results\n  - By region\n")?;
+//! let markdown = prs.export_markdown()?;
+//! assert_eq!(markdown, "# Agenda\n\n- Results\n  - By region\n");
+//! # Ok::<(), pptx::PptxError>(())
+//! ```
+
+use std::fmt::Write as _;
+
+use crate::enums::shapes::PpPlaceholderType;
+use crate::enums::text::MsoAutoNumberScheme;
+use crate::error::{PartNotFoundExt, PptxResult};
+use crate::opc::part::Part;
+use crate::presentation::Presentation;
+use crate::shapes::placeholder::PlaceholderFormat;
+use crate::shapes::Shape;
+use crate::slide::SlideRef;
+use crate::table::Table;
+use crate::text::{BulletFormat, Paragraph, TextFrame};
+
+/// Separates slides in an outline.
+const SLIDE_BREAK: &str = "---";
+
+/// Numbering used for imported numbered lists.
+const NUMBERING: MsoAutoNumberScheme = MsoAutoNumberScheme::ArabicPeriod;
+
+/// Converts the text of a `Presentation` into a Markdown outline.
+pub struct MarkdownExporter<'a> {
+    prs: &'a Presentation,
+}
+
+impl<'a> MarkdownExporter<'a> {
+    /// Create a new exporter for the given presentation.
+    #[must_use]
+    pub const fn new(prs: &'a Presentation) -> Self {
+        Self { prs }
+    }
+
+    /// Export the presentation as a Markdown outline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if slide or notes XML cannot be read or parsed.
+    pub fn export(&self) -> PptxResult<String> {
+        let slides = self
+            .prs
+            .slides()?
+            .iter()
+            .map(|slide| self.slide(slide))
+            .collect::<PptxResult<Vec<_>>>()?;
+        Ok(slides.join(&format!("\n{SLIDE_BREAK}\n\n")))
+    }
+
+    fn slide(&self, slide: &SlideRef) -> PptxResult<String> {
+        let tree = self.prs.slide_shapes(slide)?;
+        let part = self
+            .prs
+            .package()
+            .part(&slide.partname)
+            .or_part_not_found(slide.partname.as_str())?;
+
+        let mut blocks = Vec::new();
+        let title = tree.title();
+        if let Some(tf) = title
+            .and_then(Shape::as_autoshape)
+            .and_then(|s| s.text_frame())
+        {
+            let text: Vec<String> = tf
+                .paragraphs()
+                .iter()
+                .map(inline)
+                .filter(|text| !text.trim().is_empty())
+                .collect();
+            if !text.is_empty() {
+                blocks.push(format!("# {}", text.join(" ")));
+            }
+        }
+        for shape in &tree.shapes {
+            if title.is_some_and(|title| std::ptr::eq(title, shape)) {
+                continue;
+            }
+            shape_blocks(shape, part, &mut blocks);
+        }
+        if let Some(notes) = self.prs.notes_slide_text(slide)? {
+            let notes = notes.trim();
+            if !notes.is_empty() {
+                let quoted: Vec<String> = notes
+                    .lines()
+                    .map(|line| format!("> {line}").trim_end().to_string())
+                    .collect();
+                blocks.push(quoted.join("\n"));
+            }
+        }
+
+        let mut markdown = blocks.join("\n\n");
+        markdown.push('\n');
+        Ok(markdown)
+    }
+}
+
+/// Standalone function to export a presentation to a Markdown outline.
+///
+/// # Errors
+///
+/// Returns an error if slide or notes XML cannot be read or parsed.
+pub fn export_to_markdown(prs: &Presentation) -> PptxResult<String> {
+    MarkdownExporter::new(prs).export()
+}
+
+/// Append the blocks of `shape` to `blocks`.
+fn shape_blocks(shape: &Shape, part: &Part, blocks: &mut Vec<String>) {
+    match shape {
+        Shape::AutoShape(s) => {
+            let Some(tf) = &s.text_frame else { return };
+            let placeholder = s.placeholder.as_ref();
+            if placeholder.is_some_and(is_page_furniture) {
+                return;
+            }
+            let bulleted = placeholder.is_some_and(is_body);
+            text_blocks(tf, bulleted, blocks);
+        }
+        Shape::GraphicFrame(g) => {
+            if let Some(table) = &g.table {
+                blocks.push(pipe_table(table));
+            }
+        }
+        Shape::Picture(p) => {
+            let file = p
+                .image_r_id
+                .as_deref()
+                .and_then(|r_id| part.rels.get(r_id))
+                .and_then(|rel| rel.target_partname(part.partname.base_uri()).ok())
+                .map(|partname| partname.filename().to_string())
+                .unwrap_or_default();
+            let alt = p.description.as_deref().unwrap_or_default();
+            blocks.push(format!("![{}]({file})", escape_inline(alt)));
+        }
+        Shape::GroupShape(g) => {
+            for child in &g.shapes {
+                shape_blocks(child, part, blocks);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Paragraphs of a text frame: runs of list items as one block, other
+/// paragraphs as blocks of their own.
+fn text_blocks(tf: &TextFrame, bulleted: bool, blocks: &mut Vec<String>) {
+    let mut list: Vec<String> = Vec::new();
+    for para in tf.paragraphs() {
+        let text = inline(para);
+        if text.trim().is_empty() {
+            continue;
+        }
+        let marker = match &para.bullet {
+            Some(BulletFormat::AutoNumbered { .. }) => Some("1."),
+            Some(BulletFormat::None) => None,
+            Some(_) => Some("-"),
+            None => bulleted.then_some("-"),
+        };
+        match marker {
+            Some(marker) => {
+                let indent = "  ".repeat(usize::from(para.level));
+                list.push(format!("{indent}{marker} {}", text.trim()));
+            }
+            None => {
+                if !list.is_empty() {
+                    blocks.push(list.join("\n"));
+                    list.clear();
+                }
+                blocks.push(escape_block_start(text.trim()));
+            }
+        }
+    }
+    if !list.is_empty() {
+        blocks.push(list.join("\n"));
+    }
+}
+
+fn pipe_table(table: &Table) -> String {
+    let row_line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+    let mut lines = Vec::new();
+    for (i, row) in table.rows.iter().enumerate() {
+        let cells = row
+            .cells
+            .iter()
+            .map(|cell| {
+                if cell.h_merge || cell.v_merge {
+                    return String::new();
+                }
+                let text: Vec<String> = cell
+                    .text_frame
+                    .paragraphs()
+                    .iter()
+                    .map(|para| inline(para).trim().to_string())
+                    .filter(|text| !text.is_empty())
+                    .collect();
+                text.join("<br>").replace('|', "\\|")
+            })
+            .collect();
+        lines.push(row_line(cells));
+        if i == 0 {
+            lines.push(row_line(vec!["---".to_string(); row.cells.len()]));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Placeholders repeated on every slide, whose text is not content.
+const fn is_page_furniture(placeholder: &PlaceholderFormat) -> bool {
+    matches!(
+        placeholder.ph_type,
+        Some(
+            PpPlaceholderType::Date
+                | PpPlaceholderType::Footer
+                | PpPlaceholderType::Header
+                | PpPlaceholderType::SlideNumber
+        )
+    )
+}
+
+/// Placeholders whose paragraphs are bulleted unless they say otherwise.
+pub(crate) const fn is_body(placeholder: &PlaceholderFormat) -> bool {
+    matches!(
+        placeholder.ph_type,
+        None | Some(
+            PpPlaceholderType::Body
+                | PpPlaceholderType::Object
+                | PpPlaceholderType::VerticalBody
+                | PpPlaceholderType::VerticalObject
+        )
+    )
+}
+
+/// The text of a paragraph with Markdown emphasis for bold and italic runs.
+fn inline(para: &Paragraph) -> String {
+    // Runs of the same emphasis are merged so markers do not touch
+    let mut spans: Vec<(&str, String)> = Vec::new();
+    for run in para.runs() {
+        if run.is_line_break {
+            spans.push(("<br>", String::new()));
+            continue;
+        }
+        let font = run.font();
+        let marker = match (font.bold == Some(true), font.italic == Some(true)) {
+            (true, true) => "***",
+            (true, false) => "**",
+            (false, true) => "*",
+            (false, false) => "",
+        };
+        match spans.last_mut() {
+            Some((last, text)) if *last == marker => text.push_str(run.text()),
+            _ => spans.push((marker, run.text().to_string())),
+        }
+    }
+
+    let mut out = String::new();
+    for (marker, text) in spans {
+        if marker == "<br>" {
+            out.push_str(marker);
+            continue;
+        }
+        let text = escape_inline(&text);
+        let trimmed = text.trim();
+        if marker.is_empty() || trimmed.is_empty() {
+            out.push_str(&text);
+            continue;
+        }
+        let lead = &text[..text.len() - text.trim_start().len()];
+        let trail = &text[text.trim_end().len()..];
+        let _ = write!(out, "{lead}{marker}{trimmed}{marker}{trail}");
+    }
+    out
+}
+
+fn escape_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '<' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Escape what would make a paragraph read as a heading, list, quote or
+/// table.
+fn escape_block_start(text: &str) -> String {
+    if text.starts_with(['#', '>', '-', '+', '|', '!']) {
+        return format!("\\{text}");
+    }
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    text.to_string()
+}
+
+/// A slide read from an outline.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct OutlineSlide {
+    pub title: Option<Paragraph>,
+    /// Body paragraphs, with their levels and bullets.
+    pub body: Vec<Paragraph>,
+    /// Tables as rows of cells.
+    pub tables: Vec<Vec<Vec<Paragraph>>>,
+    /// Lines of the speaker notes.
+    pub notes: Vec<String>,
+}
+
+impl OutlineSlide {
+    fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.body.is_empty()
+            && self.tables.is_empty()
+            && self.notes.is_empty()
+    }
+}
+
+/// What the previous line of an outline was.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Last {
+    Blank,
+    Text,
+    Table,
+    Other,
+}
+
+/// Read the slides of a Markdown outline.
+pub(crate) fn parse_outline(markdown: &str) -> Vec<OutlineSlide> {
+    let mut slides = Vec::new();
+    let mut slide = OutlineSlide::default();
+    // Body paragraphs are kept as Markdown until continuation lines have
+    // been joined to them
+    let mut body: Vec<(u8, Option<BulletFormat>, String)> = Vec::new();
+    let mut last = Last::Blank;
+
+    let finish = |slide: &mut OutlineSlide,
+                  body: &mut Vec<(u8, Option<BulletFormat>, String)>,
+                  slides: &mut Vec<OutlineSlide>| {
+        slide.body = body
+            .drain(..)
+            .map(|(level, bullet, text)| {
+                let mut para = parse_inline(&text);
+                para.level = level;
+                para.bullet = bullet;
+                para
+            })
+            .collect();
+        let done = std::mem::take(slide);
+        if !done.is_empty() {
+            slides.push(done);
+        }
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            last = Last::Blank;
+            continue;
+        }
+        if trimmed.len() >= 3 && trimmed.chars().all(|c| c == '-') {
+            finish(&mut slide, &mut body, &mut slides);
+            last = Last::Other;
+            continue;
+        }
+        if let Some(title) = heading(trimmed) {
+            if slide.title.is_some() || !body.is_empty() || !slide.tables.is_empty() {
+                finish(&mut slide, &mut body, &mut slides);
+            }
+            slide.title = Some(parse_inline(title));
+            last = Last::Other;
+            continue;
+        }
+        if let Some(quote) = trimmed.strip_prefix('>') {
+            slide
+                .notes
+                .push(quote.strip_prefix(' ').unwrap_or(quote).to_string());
+            last = Last::Other;
+            continue;
+        }
+        if trimmed.starts_with('|') {
+            let cells = table_cells(trimmed);
+            if !cells.iter().all(|cell| is_delimiter_cell(cell)) {
+                let row = cells.iter().map(|cell| parse_inline(cell)).collect();
+                match slide.tables.last_mut() {
+                    Some(table) if last == Last::Table => table.push(row),
+                    _ => slide.tables.push(vec![row]),
+                }
+            }
+            last = Last::Table;
+            continue;
+        }
+        if trimmed.starts_with("![") && trimmed.ends_with(')') {
+            // Pictures are only named by the outline
+            last = Last::Other;
+            continue;
+        }
+        if let Some((bullet, text)) = list_item(trimmed) {
+            let indent: usize = line
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .map(|c| if c == '\t' { 2 } else { 1 })
+                .sum();
+            // usize→u8: clamped to the nine outline levels
+            #[allow(clippy::cast_possible_truncation)]
+            let level = (indent / 2).min(8) as u8;
+            body.push((level, bullet, text.to_string()));
+            last = Last::Text;
+            continue;
+        }
+        match body.last_mut() {
+            Some((_, _, text)) if last == Last::Text => {
+                text.push(' ');
+                text.push_str(trimmed);
+            }
+            _ => body.push((0, Some(BulletFormat::None), trimmed.to_string())),
+        }
+        last = Last::Text;
+    }
+    finish(&mut slide, &mut body, &mut slides);
+    slides
+}
+
+/// The text of a `#` or `##` heading.
+fn heading(line: &str) -> Option<&str> {
+    let text = line
+        .strip_prefix("## ")
+        .or_else(|| line.strip_prefix("# "))?;
+    Some(text.trim().trim_end_matches('#').trim_end())
+}
+
+/// The bullet and text of a list item.
+fn list_item(line: &str) -> Option<(Option<BulletFormat>, &str)> {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(text) = line.strip_prefix(marker) {
+            return Some((None, text.trim()));
+        }
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return None;
+    }
+    let text = line[digits..]
+        .strip_prefix(". ")
+        .or_else(|| line[digits..].strip_prefix(") "))?;
+    Some((Some(BulletFormat::auto_numbered(NUMBERING)), text.trim()))
+}
+
+/// Cells of a pipe table row, split on unescaped pipes.
+fn table_cells(line: &str) -> Vec<String> {
+    let inner = line.strip_prefix('|').unwrap_or(line);
+    let inner = if inner.ends_with('|') && !inner.ends_with("\\|") {
+        &inner[..inner.len() - 1]
+    } else {
+        inner
+    };
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // Escaped pipes are unescaped here, other escapes are left for
+            // the cell text
+            '\\' => match chars.next() {
+                Some('|') => cell.push('|'),
+                Some(next) => {
+                    cell.push('\\');
+                    cell.push(next);
+                }
+                None => cell.push('\\'),
+            },
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+fn is_delimiter_cell(cell: &str) -> bool {
+    let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+    !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+}
+
+/// A paragraph from Markdown text with emphasis, escapes and `<br>` breaks.
+fn parse_inline(text: &str) -> Paragraph {
+    let mut para = Paragraph::new();
+    let mut current = String::new();
+    let (mut bold, mut italic) = (false, false);
+
+    let flush = |para: &mut Paragraph, current: &mut String, bold: bool, italic: bool| {
+        if current.is_empty() {
+            return;
+        }
+        let run = para.add_run();
+        run.set_text(current);
+        if bold {
+            run.font_mut().bold = Some(true);
+        }
+        if italic {
+            run.font_mut().italic = Some(true);
+        }
+        current.clear();
+    };
+
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            let mut chars = rest[1..].chars();
+            match chars.next() {
+                Some(next) => {
+                    current.push(next);
+                    rest = chars.as_str();
+                }
+                None => {
+                    current.push('\\');
+                    rest = "";
+                }
+            }
+            continue;
+        }
+        if rest.starts_with("<br>") {
+            flush(&mut para, &mut current, bold, italic);
+            para.add_line_break();
+            rest = &rest[4..];
+            continue;
+        }
+        if c == '*' {
+            let stars = rest.len() - rest.trim_start_matches('*').len();
+            let marker = &rest[..stars.min(3)];
+            let after = &rest[marker.len()..];
+            let (closes_bold, closes_italic) = (marker.len() >= 2, marker.len() % 2 == 1);
+            // A marker opens emphasis only if it is closed later on
+            let opening = (closes_bold && !bold) || (closes_italic && !italic);
+            if !opening || after.contains(marker) {
+                flush(&mut para, &mut current, bold, italic);
+                bold ^= closes_bold;
+                italic ^= closes_italic;
+                rest = after;
+                continue;
+            }
+        }
+        current.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut para, &mut current, bold, italic);
+    para
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(para: &Paragraph) -> Vec<(String, bool, bool)> {
+        para.runs()
+            .iter()
+            .map(|run| {
+                (
+                    run.text().to_string(),
+                    run.font().bold == Some(true),
+                    run.font().italic == Some(true),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_emphasis_and_escapes() {
+        let para = parse_inline(r"Plain **bold** *it* ***both*** 2 \* 3 a*b");
+        assert_eq!(
+            runs(&para),
+            [
+                ("Plain ".to_string(), false, false),
+                ("bold".to_string(), true, false),
+                (" ".to_string(), false, false),
+                ("it".to_string(), false, true),
+                (" ".to_string(), false, false),
+                ("both".to_string(), true, true),
+                (" 2 * 3 a*b".to_string(), false, false),
+            ]
+        );
+        assert_eq!(inline(&para), r"Plain **bold** *it* ***both*** 2 \* 3 a\*b");
+    }
+
+    #[test]
+    fn parses_slides_lists_tables_and_notes() {
+        let markdown = "\
+# First
+
+- One
+  - One and a half
+    continued
+1. Numbered
+
+Plain text
+
+| Name | Value |
+| :--- | ---: |
+| a \\| b | 2 |
+
+> Say this
+>
+> And this
+---
+No title here
+
+## Second
+";
+        let slides = parse_outline(markdown);
+        assert_eq!(slides.len(), 3);
+
+        let first = &slides[0];
+        assert_eq!(first.title.as_ref().unwrap().text(), "First");
+        let body: Vec<(String, u8, Option<BulletFormat>)> = first
+            .body
+            .iter()
+            .map(|p| (p.text(), p.level, p.bullet.clone()))
+            .collect();
+        assert_eq!(
+            body,
+            [
+                ("One".to_string(), 0, None),
+                ("One and a half continued".to_string(), 1, None),
+                (
+                    "Numbered".to_string(),
+                    0,
+                    Some(BulletFormat::auto_numbered(NUMBERING))
+                ),
+                ("Plain text".to_string(), 0, Some(BulletFormat::None)),
+            ]
+        );
+        let table: Vec<Vec<String>> = first.tables[0]
+            .iter()
+            .map(|row| row.iter().map(Paragraph::text).collect())
+            .collect();
+        assert_eq!(table, [["Name", "Value"], ["a | b", "2"]]);
+        assert_eq!(first.notes, ["Say this", "", "And this"]);
+
+        assert!(slides[1].title.is_none());
+        assert_eq!(slides[1].body[0].text(), "No title here");
+        assert_eq!(slides[2].title.as_ref().unwrap().text(), "Second");
+    }
+
+    #[test]
+    fn escapes_paragraphs_that_look_like_markup() {
+        assert_eq!(escape_block_start("# not a heading"), r"\# not a heading");
+        assert_eq!(escape_block_start("2024. A year"), r"2024\. A year");
+        assert_eq!(escape_block_start("Fine"), "Fine");
+        let slides = parse_outline("\\# not a heading\n\n2024\\. A year\n");
+        let texts: Vec<String> = slides[0].body.iter().map(Paragraph::text).collect();
+        assert_eq!(texts, ["# not a heading", "2024. A year"]);
+    }
+}
@@ -1,5 +1,12 @@
 //! Export functionality for converting presentations to other formats.
 
 pub mod html;
+pub mod markdown;
+pub mod pdf;
+pub(crate) mod scene;
+pub mod svg;
 
-pub use html::HtmlExporter;
+pub use html::{HtmlExportOptions, HtmlExporter};
+pub use markdown::MarkdownExporter;
+pub use pdf::PdfExporter;
+pub use svg::SvgExporter;
@@ -0,0 +1,490 @@
+//! Page content streams: scene items as PDF drawing operators.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use crate::export::scene::{
+    FontFace, FontFamily, Item, Paint, Path, PathCmd, PatternKind, Scene, Stroke, TextItem,
+};
+use crate::text::font::RgbColor;
+
+use super::font::EmbeddedFonts;
+use super::writer::num;
+
+/// Horizontal shear of synthesized italics (about 12°).
+const ITALIC_SHEAR: f64 = 0.21;
+
+/// PostScript names of the standard faces, indexed by [`font_index`].
+pub(super) const FONT_NAMES: [&str; 12] = [
+    "Helvetica",
+    "Helvetica-Bold",
+    "Helvetica-Oblique",
+    "Helvetica-BoldOblique",
+    "Times-Roman",
+    "Times-Bold",
+    "Times-Italic",
+    "Times-BoldItalic",
+    "Courier",
+    "Courier-Bold",
+    "Courier-Oblique",
+    "Courier-BoldOblique",
+];
+
+/// Index of a face in [`FONT_NAMES`].
+pub(super) fn font_index(face: FontFace) -> usize {
+    let family = match face.family {
+        FontFamily::Sans => 0,
+        FontFamily::Serif => 4,
+        FontFamily::Mono => 8,
+    };
+    family + usize::from(face.bold) + 2 * usize::from(face.italic)
+}
+
+/// A tiling pattern used on a page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct PatternUse {
+    pub kind: PatternKind,
+    pub foreground: RgbColor,
+    pub background: RgbColor,
+}
+
+/// The content stream of a page and the resources it uses.
+#[derive(Debug, Default)]
+pub(super) struct PageContent {
+    pub ops: String,
+    /// Standard faces used, by [`font_index`]; named `/F<index>`.
+    pub fonts: BTreeSet<usize>,
+    /// Embedded fonts used, by position; named `/E<position>`.
+    pub embedded_fonts: BTreeSet<usize>,
+    /// Scene images drawn; named `/Im<index>`.
+    pub images: BTreeSet<usize>,
+    /// Shading dictionaries; named `/Sh<position>`.
+    pub shadings: Vec<String>,
+    /// Tiling patterns; named `/P<position>`.
+    pub patterns: Vec<PatternUse>,
+}
+
+/// Draw `scene` on a page of its size, with text in `fonts` where they have
+/// its typeface. `embeddable[i]` tells whether scene image `i` can be
+/// embedded; the others are drawn as gray boxes.
+pub(super) fn page_content(
+    scene: &Scene,
+    embeddable: &[bool],
+    fonts: &mut EmbeddedFonts,
+) -> PageContent {
+    let mut content = PageContent::default();
+    // Scene coordinates have y growing downwards from the top-left corner
+    let _ = writeln!(content.ops, "1 0 0 -1 0 {} cm", num(scene.height));
+    for item in &scene.items {
+        content.item(item, embeddable, fonts);
+    }
+    content
+}
+
+fn rgb(color: RgbColor) -> String {
+    format!(
+        "{} {} {}",
+        num(f64::from(color.r) / 255.0),
+        num(f64::from(color.g) / 255.0),
+        num(f64::from(color.b) / 255.0)
+    )
+}
+
+fn path_ops(ops: &mut String, path: &Path) {
+    for cmd in &path.0 {
+        let _ = match *cmd {
+            PathCmd::MoveTo(x, y) => writeln!(ops, "{} {} m", num(x), num(y)),
+            PathCmd::LineTo(x, y) => writeln!(ops, "{} {} l", num(x), num(y)),
+            PathCmd::CubicTo(x1, y1, x2, y2, x, y) => writeln!(
+                ops,
+                "{} {} {} {} {} {} c",
+                num(x1),
+                num(y1),
+                num(x2),
+                num(y2),
+                num(x),
+                num(y)
+            ),
+            PathCmd::Close => writeln!(ops, "h"),
+        };
+    }
+}
+
+/// Map a character to its `WinAnsiEncoding` code, if it has one.
+pub(super) fn win_ansi(c: char) -> Option<u8> {
+    let code = match c {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => return u8::try_from(u32::from(c)).ok(),
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// `text` as a PDF literal string in `WinAnsiEncoding`; characters outside
+/// the encoding become `?`.
+fn literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('(');
+    for c in text.chars() {
+        match win_ansi(c).unwrap_or(b'?') {
+            b'(' => out.push_str("\\("),
+            b')' => out.push_str("\\)"),
+            b'\\' => out.push_str("\\\\"),
+            b @ 0x20..=0x7E => out.push(char::from(b)),
+            b => {
+                let _ = write!(out, "\\{b:03o}");
+            }
+        }
+    }
+    out.push(')');
+    out
+}
+
+impl PageContent {
+    fn item(&mut self, item: &Item, embeddable: &[bool], fonts: &mut EmbeddedFonts) {
+        match item {
+            Item::Fill(path, paint) => self.fill(path, paint),
+            Item::Stroke(path, stroke) => self.stroke(path, stroke),
+            Item::Text(text) => self.text(text, fonts),
+            Item::Image {
+                index,
+                x,
+                y,
+                width,
+                height,
+            } => {
+                if embeddable.get(*index).copied().unwrap_or(false) {
+                    self.images.insert(*index);
+                    let _ = writeln!(
+                        self.ops,
+                        "q {} 0 0 {} {} {} cm /Im{index} Do Q",
+                        num(*width),
+                        num(-*height),
+                        num(*x),
+                        num(*y + *height)
+                    );
+                } else {
+                    let placeholder = Path::rect(*x, *y, *width, *height);
+                    self.fill(&placeholder, &Paint::Solid(RgbColor::new(204, 204, 204)));
+                }
+            }
+            Item::Group {
+                transform,
+                clip,
+                items,
+            } => {
+                self.ops.push_str("q\n");
+                if !transform.is_identity() {
+                    let [a, b, c, d, e, f] = transform.0;
+                    let _ = writeln!(
+                        self.ops,
+                        "{} {} {} {} {} {} cm",
+                        num(a),
+                        num(b),
+                        num(c),
+                        num(d),
+                        num(e),
+                        num(f)
+                    );
+                }
+                if let Some(clip) = clip {
+                    path_ops(&mut self.ops, clip);
+                    self.ops.push_str("W n\n");
+                }
+                for item in items {
+                    self.item(item, embeddable, fonts);
+                }
+                self.ops.push_str("Q\n");
+            }
+        }
+    }
+
+    fn fill(&mut self, path: &Path, paint: &Paint) {
+        match paint {
+            Paint::Solid(color) => {
+                let _ = writeln!(self.ops, "{} rg", rgb(*color));
+                path_ops(&mut self.ops, path);
+                self.ops.push_str("f\n");
+            }
+            Paint::LinearGradient { start, end, stops } => {
+                let name = self.shadings.len();
+                self.shadings.push(shading(*start, *end, stops));
+                self.ops.push_str("q\n");
+                path_ops(&mut self.ops, path);
+                let _ = writeln!(self.ops, "W n /Sh{name} sh Q");
+            }
+            Paint::Pattern {
+                kind,
+                foreground,
+                background,
+            } => {
+                let pattern = PatternUse {
+                    kind: *kind,
+                    foreground: *foreground,
+                    background: *background,
+                };
+                let name = match self.patterns.iter().position(|p| *p == pattern) {
+                    Some(name) => name,
+                    None => {
+                        self.patterns.push(pattern);
+                        self.patterns.len() - 1
+                    }
+                };
+                let _ = writeln!(self.ops, "/Pattern cs /P{name} scn");
+                path_ops(&mut self.ops, path);
+                self.ops.push_str("f\n");
+            }
+        }
+    }
+
+    fn stroke(&mut self, path: &Path, stroke: &Stroke) {
+        let dash: Vec<String> = stroke.dash.iter().map(|d| num(*d)).collect();
+        let _ = writeln!(
+            self.ops,
+            "q {} RG {} w [{}] 0 d {} J {} j",
+            rgb(stroke.color),
+            num(stroke.width),
+            dash.join(" "),
+            u8::from(stroke.round_cap),
+            u8::from(stroke.round_join)
+        );
+        path_ops(&mut self.ops, path);
+        self.ops.push_str("S Q\n");
+    }
+
+    fn text(&mut self, text: &TextItem, fonts: &mut EmbeddedFonts) {
+        if let Some(encoded) = fonts.encode(text) {
+            self.embedded_fonts.insert(encoded.font);
+            // Styles the face lacks are synthesized: bold by stroking the
+            // glyph outlines too, italic by shearing them
+            let bold = if encoded.fake_bold {
+                format!("2 Tr {} w {} RG ", num(text.size * 0.03), rgb(text.color))
+            } else {
+                String::new()
+            };
+            let shear = if encoded.fake_italic {
+                ITALIC_SHEAR
+            } else {
+                0.0
+            };
+            let _ = writeln!(
+                self.ops,
+                "q BT /E{} {} Tf {} rg {bold}1 0 {} -1 {} {} Tm {} Tj ET Q",
+                encoded.font,
+                num(text.size),
+                rgb(text.color),
+                num(shear),
+                num(text.x),
+                num(text.y),
+                encoded.glyphs
+            );
+            return;
+        }
+
+        let font = font_index(text.face);
+        self.fonts.insert(font);
+        // The text matrix flips glyphs back upright
+        let _ = writeln!(
+            self.ops,
+            "BT /F{font} {} Tf {} rg 1 0 0 -1 {} {} Tm {} Tj ET",
+            num(text.size),
+            rgb(text.color),
+            num(text.x),
+            num(text.y),
+            literal(&text.text)
+        );
+    }
+}
+
+/// An axial shading dictionary for gradient `stops`.
+fn shading(start: (f64, f64), end: (f64, f64), stops: &[(f64, RgbColor)]) -> String {
+    let mut stops = stops.to_vec();
+    if let Some(&(pos, color)) = stops.first() {
+        if pos > 0.0 {
+            stops.insert(0, (0.0, color));
+        }
+    }
+    if let Some(&(pos, color)) = stops.last() {
+        if pos < 1.0 {
+            stops.push((1.0, color));
+        }
+    }
+    let segment = |a: RgbColor, b: RgbColor| {
+        format!(
+            "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+            rgb(a),
+            rgb(b)
+        )
+    };
+    let function = if stops.len() <= 2 {
+        let first = stops.first().map_or(RgbColor::new(0, 0, 0), |s| s.1);
+        let last = stops.last().map_or(first, |s| s.1);
+        segment(first, last)
+    } else {
+        let functions: Vec<String> = stops.windows(2).map(|w| segment(w[0].1, w[1].1)).collect();
+        let bounds: Vec<String> = stops[1..stops.len() - 1].iter().map(|s| num(s.0)).collect();
+        let encode = vec!["0 1"; functions.len()].join(" ");
+        format!(
+            "<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{}] /Encode [{encode}] >>",
+            functions.join(" "),
+            bounds.join(" ")
+        )
+    };
+    format!(
+        "<< /ShadingType 2 /ColorSpace /DeviceRGB /Coords [{} {} {} {}] /Function {function} /Extend [true true] >>",
+        num(start.0),
+        num(start.1),
+        num(end.0),
+        num(end.1)
+    )
+}
+
+/// Drawing operators of one 8×8 cell of a tiling pattern.
+pub(super) fn pattern_cell(pattern: &PatternUse) -> String {
+    let mut ops = format!("{} rg 0 0 8 8 re f\n", rgb(pattern.background));
+    let line = |ops: &mut String, width: f64, lines: &[(f64, f64, f64, f64)]| {
+        let _ = writeln!(ops, "{} RG {} w 2 J", rgb(pattern.foreground), num(width));
+        for (x1, y1, x2, y2) in lines {
+            let _ = writeln!(
+                ops,
+                "{} {} m {} {} l S",
+                num(*x1),
+                num(*y1),
+                num(*x2),
+                num(*y2)
+            );
+        }
+    };
+    // Diagonals continue across cells, so the corners get the ends of the
+    // neighboring cells' lines
+    let down = [
+        (0.0, 0.0, 8.0, 8.0),
+        (-1.0, 7.0, 1.0, 9.0),
+        (7.0, -1.0, 9.0, 1.0),
+    ];
+    let up = [
+        (0.0, 8.0, 8.0, 0.0),
+        (-1.0, 1.0, 1.0, -1.0),
+        (7.0, 9.0, 9.0, 7.0),
+    ];
+    match pattern.kind {
+        PatternKind::Dots(coverage) => {
+            // Four square dots per cell covering the given fraction
+            let side = 4.0 * coverage.clamp(0.0, 1.0).sqrt();
+            let offset = (4.0 - side) / 2.0;
+            let _ = write!(ops, "{} rg", rgb(pattern.foreground));
+            for (x, y) in [(0.0, 0.0), (4.0, 0.0), (0.0, 4.0), (4.0, 4.0)] {
+                let _ = write!(
+                    ops,
+                    " {} {} {} {} re",
+                    num(x + offset),
+                    num(y + offset),
+                    num(side),
+                    num(side)
+                );
+            }
+            ops.push_str(" f\n");
+        }
+        PatternKind::Horizontal(w) => line(&mut ops, w, &[(0.0, 4.0, 8.0, 4.0)]),
+        PatternKind::Vertical(w) => line(&mut ops, w, &[(4.0, 0.0, 4.0, 8.0)]),
+        PatternKind::DownwardDiagonal(w) => line(&mut ops, w, &down),
+        PatternKind::UpwardDiagonal(w) => line(&mut ops, w, &up),
+        PatternKind::Cross(w) => line(&mut ops, w, &[(0.0, 4.0, 8.0, 4.0), (4.0, 0.0, 4.0, 8.0)]),
+        PatternKind::DiagonalCross(w) => {
+            line(&mut ops, w, &down);
+            line(&mut ops, w, &up);
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::layout::FontSet;
+
+    #[test]
+    fn encodes_text_as_win_ansi() {
+        assert_eq!(literal("a(b)\\"), "(a\\(b\\)\\\\)");
+        assert_eq!(literal("•é€"), "(\\225\\351\\200)");
+        assert_eq!(literal("日"), "(?)");
+    }
+
+    #[test]
+    fn picks_standard_faces() {
+        let face = FontFace {
+            family: FontFamily::Serif,
+            bold: true,
+            italic: true,
+        };
+        assert_eq!(FONT_NAMES[font_index(face)], "Times-BoldItalic");
+    }
+
+    #[test]
+    fn stitches_gradients_with_several_stops() {
+        let red = RgbColor::new(255, 0, 0);
+        let blue = RgbColor::new(0, 0, 255);
+        let two = shading((0.0, 0.0), (10.0, 0.0), &[(0.0, red), (1.0, blue)]);
+        assert!(two.contains("/FunctionType 2"));
+        let three = shading(
+            (0.0, 0.0),
+            (10.0, 0.0),
+            &[(0.2, red), (0.5, blue), (1.0, red)],
+        );
+        assert!(three.contains("/FunctionType 3"));
+        assert!(three.contains("/Bounds [0.2 0.5]"));
+    }
+
+    #[test]
+    fn draws_scene_items() {
+        let scene = Scene {
+            width: 100.0,
+            height: 50.0,
+            items: vec![
+                Item::Fill(
+                    Path::rect(0.0, 0.0, 10.0, 10.0),
+                    Paint::Solid(RgbColor::new(255, 0, 0)),
+                ),
+                Item::Image {
+                    index: 0,
+                    x: 5.0,
+                    y: 5.0,
+                    width: 20.0,
+                    height: 10.0,
+                },
+            ],
+            images: Vec::new(),
+        };
+        let content = page_content(&scene, &[true], &mut EmbeddedFonts::new(FontSet::new()));
+        assert!(content.ops.starts_with("1 0 0 -1 0 50 cm\n"));
+        assert!(content.ops.contains("1 0 0 rg\n0 0 m\n10 0 l\n"));
+        assert!(content.ops.contains("q 20 0 0 -10 5 15 cm /Im0 Do Q"));
+        assert!(content.images.contains(&0));
+    }
+}
@@ -0,0 +1,395 @@
+//! TrueType fonts embedded as CID-keyed fonts.
+//!
+//! Each face is written as a `Type0` font with `Identity-H` encoding over a
+//! `CIDFontType2` font, so text is shown as two-byte glyph ids and any
+//! script keeps its glyphs. A `ToUnicode` CMap maps the glyphs back to
+//! their characters for searching and copying. Faces with PostScript (CFF)
+//! outlines cannot be embedded this way and are not used.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use ttf_parser::{name_id, Face};
+
+use crate::export::scene::TextItem;
+use crate::text::layout::FontSet;
+
+use super::content::win_ansi;
+use super::writer::{num, PdfWriter};
+
+/// Most entries of one `bfchar` block of a CMap.
+const BFCHAR_BLOCK: usize = 100;
+
+/// The text of a run as glyphs of an embedded font.
+pub(super) struct EncodedText {
+    /// Position of the font among the fonts used; named `/E<position>`.
+    pub font: usize,
+    /// Two-byte glyph ids as a hex string.
+    pub glyphs: String,
+    /// Whether the face lacks the weight asked for.
+    pub fake_bold: bool,
+    /// Whether the face lacks the slant asked for.
+    pub fake_italic: bool,
+}
+
+/// A face of the font set drawn in the document.
+struct UsedFont {
+    /// Position of the face in the font set.
+    face: usize,
+    /// The character each glyph drawn stands for, by glyph id.
+    glyphs: BTreeMap<u16, char>,
+}
+
+/// The faces of a font set that text is drawn with.
+pub(super) struct EmbeddedFonts {
+    set: FontSet,
+    used: Vec<UsedFont>,
+    /// Position in `used` by position in the set; `None` for faces that
+    /// cannot be embedded.
+    by_face: HashMap<usize, Option<usize>>,
+}
+
+impl EmbeddedFonts {
+    pub(super) fn new(set: FontSet) -> Self {
+        Self {
+            set,
+            used: Vec::new(),
+            by_face: HashMap::new(),
+        }
+    }
+
+    /// The number of fonts used so far.
+    pub(super) fn len(&self) -> usize {
+        self.used.len()
+    }
+
+    /// `text` as glyphs of the closest face of its typeface. Text whose
+    /// typeface is not in the set is drawn with a face that has glyphs for
+    /// all of it, unless the standard fonts can show it; `None` when the
+    /// standard fonts are used.
+    pub(super) fn encode(&mut self, text: &TextItem) -> Option<EncodedText> {
+        let (bold, italic) = (text.face.bold, text.face.italic);
+        let font = text
+            .font
+            .as_deref()
+            .and_then(|name| self.set.closest(name, bold, italic))
+            .and_then(|face| self.use_face(face))
+            .or_else(|| {
+                if text.text.chars().all(|c| win_ansi(c).is_some()) {
+                    None
+                } else {
+                    self.covering(&text.text, bold, italic)
+                }
+            })?;
+
+        let used = &mut self.used[font];
+        let (data, index) = self.set.program(used.face)?;
+        let face = Face::parse(data, index).ok()?;
+        let mut glyphs = String::with_capacity(text.text.len() * 4 + 2);
+        glyphs.push('<');
+        for c in text.text.chars() {
+            let glyph = face.glyph_index(c).map_or(0, |g| g.0);
+            used.glyphs.entry(glyph).or_insert(c);
+            let _ = write!(glyphs, "{glyph:04X}");
+        }
+        glyphs.push('>');
+        let (has_bold, has_italic) = self.set.style(used.face);
+        Some(EncodedText {
+            font,
+            glyphs,
+            fake_bold: bold && !has_bold,
+            fake_italic: italic && !has_italic,
+        })
+    }
+
+    /// Position in `used` of the face at `face` in the set, adding it on
+    /// first use; `None` when it cannot be embedded.
+    fn use_face(&mut self, face: usize) -> Option<usize> {
+        if let Some(font) = self.by_face.get(&face) {
+            return *font;
+        }
+        let embeddable = self
+            .parse(face)
+            .is_some_and(|parsed| parsed.tables().glyf.is_some());
+        let font = embeddable.then(|| {
+            self.used.push(UsedFont {
+                face,
+                glyphs: BTreeMap::new(),
+            });
+            self.used.len() - 1
+        });
+        self.by_face.insert(face, font);
+        font
+    }
+
+    /// A font with glyphs for every character of `text`, preferring faces
+    /// of the style asked for, then fonts already used.
+    fn covering(&mut self, text: &str, bold: bool, italic: bool) -> Option<usize> {
+        // Of each style, faces already used come first, so that other font
+        // files are only read when needed
+        let mut faces: Vec<usize> = (0..self.set.len())
+            .filter(|face| self.by_face.get(face) != Some(&None))
+            .collect();
+        faces.sort_by_key(|face| {
+            let (has_bold, has_italic) = self.set.style(*face);
+            let mismatch = u8::from(has_italic != italic) * 2 + u8::from(has_bold != bold);
+            (mismatch, !self.by_face.contains_key(face))
+        });
+        for face in faces {
+            let covers = self
+                .parse(face)
+                .is_some_and(|parsed| text.chars().all(|c| parsed.glyph_index(c).is_some()));
+            if covers {
+                if let Some(font) = self.use_face(face) {
+                    return Some(font);
+                }
+            }
+        }
+        None
+    }
+
+    fn parse(&self, face: usize) -> Option<Face<'_>> {
+        let (data, index) = self.set.program(face)?;
+        Face::parse(data, index).ok()
+    }
+
+    /// Write font `font` as object `id`, with its descendant font, font
+    /// descriptor, font program and `ToUnicode` CMap.
+    pub(super) fn write(&self, writer: &mut PdfWriter, font: usize, id: u32) {
+        let Some(used) = self.used.get(font) else {
+            return;
+        };
+        // The face was parsed when it was first used
+        let Some((data, index)) = self.set.program(used.face) else {
+            return;
+        };
+        let Ok(face) = Face::parse(data, index) else {
+            return;
+        };
+        let scale = 1000.0 / f64::from(face.units_per_em());
+        let units = |v: i16| num(f64::from(v) * scale);
+        let name = postscript_name(&face, font);
+
+        let program = standalone(data, &face, index);
+        let file = writer.add_compressed_stream(&format!("/Length1 {}", program.len()), &program);
+        let bbox = face.global_bounding_box();
+        // Symbolic, plus fixed pitch and italic
+        let flags = 4 | u8::from(face.is_monospaced()) | (u8::from(face.is_italic()) << 6);
+        let descriptor = writer.add_object(&format!(
+            "<< /Type /FontDescriptor /FontName /{name} /Flags {flags} /FontBBox [{} {} {} {}] \
+             /ItalicAngle {} /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {file} 0 R >>",
+            units(bbox.x_min),
+            units(bbox.y_min),
+            units(bbox.x_max),
+            units(bbox.y_max),
+            num(f64::from(face.italic_angle())),
+            units(face.ascender()),
+            units(face.descender()),
+            units(face.capital_height().unwrap_or_else(|| face.ascender())),
+        ));
+
+        let mut widths = String::new();
+        for &glyph in used.glyphs.keys() {
+            let advance = face
+                .glyph_hor_advance(ttf_parser::GlyphId(glyph))
+                .unwrap_or(0);
+            let _ = write!(widths, " {glyph} [{}]", num(f64::from(advance) * scale));
+        }
+        let descendant = writer.add_object(&format!(
+            "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{name} \
+             /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+             /FontDescriptor {descriptor} 0 R /CIDToGIDMap /Identity /W [{}] >>",
+            widths.trim_start()
+        ));
+        let to_unicode = writer.add_compressed_stream("", to_unicode(&used.glyphs).as_bytes());
+        writer.object(
+            id,
+            &format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{name} /Encoding /Identity-H \
+                 /DescendantFonts [{descendant} 0 R] /ToUnicode {to_unicode} 0 R >>"
+            ),
+        );
+    }
+}
+
+/// The PostScript name of a face, reduced to characters that need no
+/// escaping in a PDF name; `Font<font>` when it has none.
+fn postscript_name(face: &Face<'_>, font: usize) -> String {
+    let name: String = face
+        .names()
+        .into_iter()
+        .filter(|name| name.name_id == name_id::POST_SCRIPT_NAME)
+        .find_map(|name| name.to_string())
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if name.is_empty() {
+        format!("Font{font}")
+    } else {
+        name
+    }
+}
+
+/// The program of face `index` as a font file of its own: a face of a
+/// font collection is copied out of it with its tables.
+fn standalone(data: &[u8], face: &Face<'_>, index: u32) -> Vec<u8> {
+    if !data.starts_with(b"ttcf") {
+        return data.to_vec();
+    }
+    let start = 12 + 4 * index as usize;
+    let version = data
+        .get(start..start + 4)
+        .and_then(|offset| {
+            let offset = u32::from_be_bytes(offset.try_into().ok()?) as usize;
+            data.get(offset..offset + 4)
+        })
+        .unwrap_or(&[0, 1, 0, 0]);
+
+    let records: Vec<_> = face
+        .raw_face()
+        .table_records
+        .into_iter()
+        .filter_map(|record| {
+            let offset = record.offset as usize;
+            let table = data.get(offset..offset + record.length as usize)?;
+            Some((record.tag.to_bytes(), record.check_sum, table))
+        })
+        .collect();
+    let count = u16::try_from(records.len()).unwrap_or(u16::MAX);
+    let selector = count.checked_ilog2().unwrap_or(0);
+    let search_range = 16u16 << selector;
+
+    let mut font = version.to_vec();
+    for value in [
+        count,
+        search_range,
+        u16::try_from(selector).unwrap_or(0),
+        count.saturating_mul(16).saturating_sub(search_range),
+    ] {
+        font.extend_from_slice(&value.to_be_bytes());
+    }
+    let mut body = Vec::new();
+    let header = 12 + 16 * records.len();
+    for (tag, check_sum, table) in records {
+        let offset = u32::try_from(header + body.len()).unwrap_or(u32::MAX);
+        let length = u32::try_from(table.len()).unwrap_or(u32::MAX);
+        font.extend_from_slice(&tag);
+        for value in [check_sum, offset, length] {
+            font.extend_from_slice(&value.to_be_bytes());
+        }
+        body.extend_from_slice(table);
+        body.resize(body.len().next_multiple_of(4), 0);
+    }
+    font.extend(body);
+    font
+}
+
+/// A `ToUnicode` CMap mapping the glyphs drawn to their characters.
+fn to_unicode(glyphs: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    // Glyph 0 stands for every character the font lacks
+    let mapped: Vec<(&u16, &char)> = glyphs.iter().filter(|(glyph, _)| **glyph != 0).collect();
+    for block in mapped.chunks(BFCHAR_BLOCK) {
+        let _ = writeln!(cmap, "{} beginbfchar", block.len());
+        for (glyph, c) in block {
+            let _ = write!(cmap, "<{glyph:04X}> <");
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                let _ = write!(cmap, "{unit:04X}");
+            }
+            cmap.push_str(">\n");
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    cmap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_font::EmbeddedFont;
+    use crate::export::scene::{FontFace, FontFamily};
+    use crate::render::sample_font;
+    use crate::text::font::RgbColor;
+
+    fn sample_fonts() -> EmbeddedFonts {
+        let set = FontSet::new()
+            .with_font(&EmbeddedFont::from_bytes(
+                sample_font(),
+                "Sample",
+                false,
+                false,
+            ))
+            .unwrap();
+        EmbeddedFonts::new(set)
+    }
+
+    fn item(font: Option<&str>, text: &str) -> TextItem {
+        TextItem {
+            x: 0.0,
+            y: 0.0,
+            face: FontFace {
+                family: FontFamily::Sans,
+                bold: true,
+                italic: false,
+            },
+            font: font.map(str::to_string),
+            size: 10.0,
+            color: RgbColor::new(0, 0, 0),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn encodes_text_as_glyph_ids() {
+        let mut fonts = sample_fonts();
+        let encoded = fonts.encode(&item(Some("sample"), "AZ?")).unwrap();
+        assert_eq!(encoded.font, 0);
+        assert_eq!(encoded.glyphs, "<0001001A0000>");
+        assert!(encoded.fake_bold && !encoded.fake_italic);
+        assert_eq!(fonts.len(), 1);
+
+        // Text the standard fonts can show keeps them when its typeface
+        // is missing; other text uses a face that has its glyphs
+        assert!(fonts.encode(&item(Some("Calibri"), "AB")).is_none());
+        assert!(fonts.encode(&item(Some("Calibri"), "日本")).is_none());
+    }
+
+    #[test]
+    fn maps_glyphs_back_to_characters() {
+        let glyphs = BTreeMap::from([(0, '?'), (1, 'A'), (7, '𝄞')]);
+        let cmap = to_unicode(&glyphs);
+        assert!(cmap.contains("2 beginbfchar\n<0001> <0041>\n<0007> <D834DD1E>\nendbfchar\n"));
+        assert!(!cmap.contains("<0000> <003F>"));
+    }
+
+    #[test]
+    fn copies_faces_out_of_collections() {
+        let font = sample_font();
+        // A collection of the sample font, its tables moved past the header
+        let mut collection = b"ttcf\0\x01\0\0\0\0\0\x01\0\0\0\x10".to_vec();
+        let mut directory = font[..12].to_vec();
+        for record in font[12..12 + 7 * 16].chunks(16) {
+            let offset = u32::from_be_bytes(record[8..12].try_into().unwrap()) + 16;
+            directory.extend_from_slice(&record[..8]);
+            directory.extend_from_slice(&offset.to_be_bytes());
+            directory.extend_from_slice(&record[12..]);
+        }
+        collection.extend(directory);
+        collection.extend_from_slice(&font[12 + 7 * 16..]);
+
+        let face = Face::parse(&collection, 0).unwrap();
+        let copy = standalone(&collection, &face, 0);
+        assert_eq!(copy, font);
+        assert_eq!(
+            Face::parse(&copy, 0).unwrap().glyph_index('Z').unwrap().0,
+            26
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! Embedding JPEG and PNG images as PDF image XObjects.
+//!
+//! JPEG data is embedded as-is (`/DCTDecode`). PNG data without an alpha
+//! channel is embedded as-is too, since PDF's `/FlateDecode` with PNG
+//! predictors reads the PNG scanlines directly; PNGs with alpha are
+//! decoded so the alpha channel can become a soft mask.
+
+use std::fmt::Write as _;
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+use super::writer::deflate;
+
+/// An image ready to be written as an XObject.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PdfImage {
+    /// Dictionary entries other than `/Length` and `/SMask`.
+    pub dict: String,
+    pub data: Vec<u8>,
+    /// A soft mask (alpha channel), as dictionary entries and data.
+    pub mask: Option<(String, Vec<u8>)>,
+}
+
+/// Convert an image to a PDF XObject, or `None` for formats that cannot
+/// be embedded.
+pub(super) fn prepare(data: &[u8], content_type: &str) -> Option<PdfImage> {
+    if data.starts_with(&[0xFF, 0xD8]) || content_type == "image/jpeg" {
+        jpeg(data)
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png(data)
+    } else {
+        None
+    }
+}
+
+fn jpeg(data: &[u8]) -> Option<PdfImage> {
+    // Walk the markers up to the frame header, which has the size and
+    // number of components
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        let len = usize::from(u16::from_be_bytes([data[pos + 2], data[pos + 3]]));
+        let is_frame = matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+        if is_frame {
+            let header = data.get(pos + 4..pos + 10)?;
+            let height = u16::from_be_bytes([header[1], header[2]]);
+            let width = u16::from_be_bytes([header[3], header[4]]);
+            let color_space = match header[5] {
+                1 => "/DeviceGray",
+                3 => "/DeviceRGB",
+                // Adobe CMYK JPEGs store inverted values
+                4 => "/DeviceCMYK /Decode [1 0 1 0 1 0 1 0]",
+                _ => return None,
+            };
+            return Some(PdfImage {
+                dict: format!(
+                    "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+                     /ColorSpace {color_space} /BitsPerComponent 8 /Filter /DCTDecode"
+                ),
+                data: data.to_vec(),
+                mask: None,
+            });
+        }
+        pos += 2 + len;
+    }
+    None
+}
+
+/// The parts of a PNG file needed to embed it.
+struct Png<'a> {
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: u8,
+    interlaced: bool,
+    palette: &'a [u8],
+    /// Concatenated `IDAT` data (zlib stream of filtered scanlines).
+    idat: Vec<u8>,
+}
+
+fn parse_png(data: &[u8]) -> Option<Png<'_>> {
+    let mut png = Png {
+        width: 0,
+        height: 0,
+        bit_depth: 0,
+        color_type: 0,
+        interlaced: false,
+        palette: &[],
+        idat: Vec::new(),
+    };
+    let mut pos = 8;
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let body = data.get(pos + 8..pos + 8 + len)?;
+        match kind {
+            b"IHDR" if len >= 13 => {
+                png.width = u32::from_be_bytes(body[0..4].try_into().ok()?);
+                png.height = u32::from_be_bytes(body[4..8].try_into().ok()?);
+                png.bit_depth = body[8];
+                png.color_type = body[9];
+                png.interlaced = body[12] != 0;
+            }
+            b"PLTE" => png.palette = body,
+            b"IDAT" => png.idat.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        // Chunk length, type, body and CRC
+        pos += 12 + len;
+    }
+    (png.width > 0 && png.height > 0 && !png.idat.is_empty()).then_some(png)
+}
+
+fn png(data: &[u8]) -> Option<PdfImage> {
+    let png = parse_png(data)?;
+    if png.interlaced {
+        return None;
+    }
+    let (width, height, bits) = (png.width, png.height, png.bit_depth);
+    let (color_space, colors) = match png.color_type {
+        0 | 4 => ("/DeviceGray".to_string(), 1),
+        2 | 6 => ("/DeviceRGB".to_string(), 3),
+        3 => {
+            let entries = png.palette.len() / 3;
+            if entries == 0 {
+                return None;
+            }
+            let mut hex = String::with_capacity(entries * 6);
+            for b in &png.palette[..entries * 3] {
+                let _ = write!(hex, "{b:02X}");
+            }
+            (format!("[/Indexed /DeviceRGB {} <{hex}>]", entries - 1), 1)
+        }
+        _ => return None,
+    };
+    let image_dict = |bits: u8| {
+        format!(
+            "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+             /ColorSpace {color_space} /BitsPerComponent {bits}"
+        )
+    };
+
+    if matches!(png.color_type, 0 | 2 | 3) {
+        return Some(PdfImage {
+            dict: format!(
+                "{} /Filter /FlateDecode /DecodeParms << /Predictor 15 /Colors {colors} \
+                 /BitsPerComponent {bits} /Columns {width} >>",
+                image_dict(bits)
+            ),
+            data: png.idat,
+            mask: None,
+        });
+    }
+
+    // Gray or RGB with alpha: split the alpha channel off into a soft mask
+    if bits != 8 {
+        return None;
+    }
+    let channels = colors + 1;
+    let mut raw = Vec::new();
+    ZlibDecoder::new(png.idat.as_slice())
+        .read_to_end(&mut raw)
+        .ok()?;
+    let pixels = unfilter(&raw, width as usize * channels, height as usize, channels)?;
+    let mut color = Vec::with_capacity(pixels.len() / channels * colors);
+    let mut alpha = Vec::with_capacity(pixels.len() / channels);
+    for pixel in pixels.chunks_exact(channels) {
+        color.extend_from_slice(&pixel[..colors]);
+        alpha.push(pixel[colors]);
+    }
+    Some(PdfImage {
+        dict: format!("{} /Filter /FlateDecode", image_dict(8)),
+        data: deflate(&color),
+        mask: Some((
+            format!(
+                "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+                 /ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /FlateDecode"
+            ),
+            deflate(&alpha),
+        )),
+    })
+}
+
+/// Undo PNG scanline filtering of 8-bit samples, `bpp` bytes per pixel.
+fn unfilter(raw: &[u8], stride: usize, height: usize, bpp: usize) -> Option<Vec<u8>> {
+    let mut out = vec![0u8; stride * height];
+    for y in 0..height {
+        let line = raw.get(y * (stride + 1)..(y + 1) * (stride + 1))?;
+        let (filter, src) = (line[0], &line[1..]);
+        let (done, rest) = out.split_at_mut(y * stride);
+        let prev = if y == 0 {
+            None
+        } else {
+            Some(&done[(y - 1) * stride..])
+        };
+        let cur = &mut rest[..stride];
+        for x in 0..stride {
+            let a = if x >= bpp { cur[x - bpp] } else { 0 };
+            let b = prev.map_or(0, |p| p[x]);
+            let c = if x >= bpp {
+                prev.map_or(0, |p| p[x - bpp])
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((u16::from(a) + u16::from(b)) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return None,
+            };
+            cur[x] = src[x].wrapping_add(predicted);
+        }
+    }
+    Some(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let pa = (p - i16::from(a)).abs();
+    let pb = (p - i16::from(b)).abs();
+    let pc = (p - i16::from(c)).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(kind: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut out = u32::try_from(body.len()).unwrap().to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(body);
+        // The CRC is not checked
+        out.extend_from_slice(&[0; 4]);
+        out
+    }
+
+    fn png_file(color_type: u8, scanlines: &[u8]) -> Vec<u8> {
+        let mut file = b"\x89PNG\r\n\x1a\n".to_vec();
+        file.extend(chunk(
+            b"IHDR",
+            &[0, 0, 0, 2, 0, 0, 0, 1, 8, color_type, 0, 0, 0],
+        ));
+        file.extend(chunk(b"IDAT", &deflate(scanlines)));
+        file.extend(chunk(b"IEND", &[]));
+        file
+    }
+
+    #[test]
+    fn reads_jpeg_frame_header() {
+        let data = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // SOI, APP0
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x20, 0x00, 0x40, 0x03, // SOF0 32×64
+        ];
+        let image = prepare(&data, "image/jpeg").unwrap();
+        assert!(image.dict.contains("/Width 64 /Height 32"));
+        assert!(image.dict.contains("/DeviceRGB"));
+        assert!(image.dict.contains("/DCTDecode"));
+    }
+
+    #[test]
+    fn passes_rgb_png_through() {
+        let file = png_file(2, &[0, 255, 0, 0, 0, 255, 0]);
+        let image = prepare(&file, "image/png").unwrap();
+        assert!(image.dict.contains("/Predictor 15 /Colors 3"));
+        assert!(image.mask.is_none());
+    }
+
+    #[test]
+    fn splits_alpha_into_a_mask() {
+        // Two RGBA pixels; the second scanline byte uses the Sub filter
+        let file = png_file(6, &[1, 10, 20, 30, 40, 1, 1, 1, 1]);
+        let image = prepare(&file, "image/png").unwrap();
+        let (_, mask) = image.mask.unwrap();
+        let mut alpha = Vec::new();
+        ZlibDecoder::new(mask.as_slice())
+            .read_to_end(&mut alpha)
+            .unwrap();
+        assert_eq!(alpha, vec![40, 41]);
+        let mut color = Vec::new();
+        ZlibDecoder::new(image.data.as_slice())
+            .read_to_end(&mut color)
+            .unwrap();
+        assert_eq!(color, vec![10, 20, 30, 11, 21, 31]);
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        assert!(prepare(b"GIF89a", "image/gif").is_none());
+    }
+}
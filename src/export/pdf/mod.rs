@@ -0,0 +1,374 @@
+//! PPTX to PDF export.
+//!
+//! Produces a vector PDF with one page per slide, drawn from the same
+//! slide layout used by the other renderers. Text is drawn with the
+//! TrueType fonts given to the exporter or embedded in the presentation,
+//! which are embedded in the PDF with the glyphs of any script. Text whose
+//! font is not available is set in the standard PDF typefaces (Helvetica,
+//! Times and Courier), or, when they cannot show it, with another font
+//! that has its glyphs.
+
+mod content;
+mod font;
+mod image;
+mod writer;
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use sha1::{Digest, Sha1};
+
+use crate::error::PptxResult;
+use crate::export::scene::{build_scene, Scene};
+use crate::presentation::Presentation;
+use crate::text::layout::FontSet;
+
+use content::{page_content, pattern_cell, FONT_NAMES};
+use font::EmbeddedFonts;
+use writer::{num, PdfWriter};
+
+/// Converts a `Presentation` into a PDF document.
+pub struct PdfExporter<'a> {
+    prs: &'a Presentation,
+    fonts: FontSet,
+    use_embedded_fonts: bool,
+}
+
+impl<'a> PdfExporter<'a> {
+    /// Create a new exporter for the given presentation.
+    #[must_use]
+    pub const fn new(prs: &'a Presentation) -> Self {
+        Self {
+            prs,
+            fonts: FontSet::new(),
+            use_embedded_fonts: true,
+        }
+    }
+
+    /// Set the fonts to draw text with, matched by typeface name. They take
+    /// precedence over fonts embedded in the presentation.
+    #[must_use]
+    pub fn with_fonts(mut self, fonts: FontSet) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    /// Set whether fonts embedded in the presentation are used.
+    #[must_use]
+    pub const fn with_embedded_fonts(mut self, use_embedded_fonts: bool) -> Self {
+        self.use_embedded_fonts = use_embedded_fonts;
+        self
+    }
+
+    /// Export the presentation as the bytes of a PDF file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if slide XML cannot be read or parsed, or the
+    /// embedded fonts cannot be read.
+    pub fn export(&self) -> PptxResult<Vec<u8>> {
+        let mut fonts = self.fonts.clone();
+        if self.use_embedded_fonts {
+            for font in self.prs.embedded_fonts()? {
+                // Compressed fonts cannot be used; their text falls back
+                // to the standard typefaces
+                let _ = fonts.add_font(&font);
+            }
+        }
+        let mut doc = Document::new(fonts);
+        for slide in self.prs.slides()? {
+            let scene = build_scene(self.prs, &slide)?;
+            doc.add_page(&scene);
+        }
+        Ok(doc.finish())
+    }
+}
+
+/// Standalone function to export a presentation to PDF.
+///
+/// # Errors
+///
+/// Returns an error if slide XML cannot be read or parsed.
+pub fn export_to_pdf(prs: &Presentation) -> PptxResult<Vec<u8>> {
+    PdfExporter::new(prs).export()
+}
+
+/// A PDF document being assembled page by page.
+struct Document {
+    writer: PdfWriter,
+    pages_id: u32,
+    pages: Vec<u32>,
+    /// Font objects by standard face index.
+    fonts: HashMap<usize, u32>,
+    embedded_fonts: EmbeddedFonts,
+    /// Objects reserved for the embedded fonts, by position; written once
+    /// all their glyphs are known.
+    embedded_font_ids: Vec<u32>,
+    /// Image objects by SHA-1 of the image data, shared between pages;
+    /// `None` for images that cannot be embedded.
+    images: HashMap<[u8; 20], Option<u32>>,
+}
+
+impl Document {
+    fn new(fonts: FontSet) -> Self {
+        let mut writer = PdfWriter::new();
+        let pages_id = writer.reserve();
+        Self {
+            writer,
+            pages_id,
+            pages: Vec::new(),
+            fonts: HashMap::new(),
+            embedded_fonts: EmbeddedFonts::new(fonts),
+            embedded_font_ids: Vec::new(),
+            images: HashMap::new(),
+        }
+    }
+
+    /// The image object for `data`, writing it on first use.
+    fn image(&mut self, data: &[u8], content_type: &str) -> Option<u32> {
+        let key: [u8; 20] = Sha1::digest(data).into();
+        if let Some(id) = self.images.get(&key) {
+            return *id;
+        }
+        let id = image::prepare(data, content_type).map(|image| {
+            let mut dict = image.dict;
+            if let Some((mask_dict, mask_data)) = image.mask {
+                let mask = self.writer.add_stream(&mask_dict, &mask_data);
+                let _ = write!(dict, " /SMask {mask} 0 R");
+            }
+            self.writer.add_stream(&dict, &image.data)
+        });
+        self.images.insert(key, id);
+        id
+    }
+
+    fn font(&mut self, index: usize) -> u32 {
+        if let Some(id) = self.fonts.get(&index) {
+            return *id;
+        }
+        let id = self.writer.add_object(&format!(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+            FONT_NAMES[index]
+        ));
+        self.fonts.insert(index, id);
+        id
+    }
+
+    fn add_page(&mut self, scene: &Scene) {
+        let image_ids: Vec<Option<u32>> = scene
+            .images
+            .iter()
+            .map(|image| self.image(&image.data, &image.content_type))
+            .collect();
+        let embeddable: Vec<bool> = image_ids.iter().map(Option::is_some).collect();
+        let content = page_content(scene, &embeddable, &mut self.embedded_fonts);
+        while self.embedded_font_ids.len() < self.embedded_fonts.len() {
+            let id = self.writer.reserve();
+            self.embedded_font_ids.push(id);
+        }
+
+        let mut resources = String::from("<<");
+        if !content.fonts.is_empty() || !content.embedded_fonts.is_empty() {
+            resources.push_str(" /Font <<");
+            for &index in &content.fonts {
+                let id = self.font(index);
+                let _ = write!(resources, " /F{index} {id} 0 R");
+            }
+            for &font in &content.embedded_fonts {
+                let id = self.embedded_font_ids[font];
+                let _ = write!(resources, " /E{font} {id} 0 R");
+            }
+            resources.push_str(" >>");
+        }
+        if !content.images.is_empty() {
+            resources.push_str(" /XObject <<");
+            for &index in &content.images {
+                if let Some(Some(id)) = image_ids.get(index) {
+                    let _ = write!(resources, " /Im{index} {id} 0 R");
+                }
+            }
+            resources.push_str(" >>");
+        }
+        if !content.shadings.is_empty() {
+            resources.push_str(" /Shading <<");
+            for (i, shading) in content.shadings.iter().enumerate() {
+                let _ = write!(resources, " /Sh{i} {shading}");
+            }
+            resources.push_str(" >>");
+        }
+        if !content.patterns.is_empty() {
+            resources.push_str(" /Pattern <<");
+            for (i, pattern) in content.patterns.iter().enumerate() {
+                // Pattern space is the page's default space; flip it like
+                // the page content so cells line up with the scene
+                let dict = format!(
+                    "/Type /Pattern /PatternType 1 /PaintType 1 /TilingType 1 \
+                     /BBox [0 0 8 8] /XStep 8 /YStep 8 /Resources << >> /Matrix [1 0 0 -1 0 {}]",
+                    num(scene.height)
+                );
+                let id = self
+                    .writer
+                    .add_compressed_stream(&dict, pattern_cell(pattern).as_bytes());
+                let _ = write!(resources, " /P{i} {id} 0 R");
+            }
+            resources.push_str(" >>");
+        }
+        resources.push_str(" >>");
+
+        let contents = self
+            .writer
+            .add_compressed_stream("", content.ops.as_bytes());
+        let page = self.writer.add_object(&format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources {resources} /Contents {contents} 0 R >>",
+            self.pages_id,
+            num(scene.width),
+            num(scene.height)
+        ));
+        self.pages.push(page);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        for (font, &id) in self.embedded_font_ids.iter().enumerate() {
+            self.embedded_fonts.write(&mut self.writer, font, id);
+        }
+        let kids: Vec<String> = self.pages.iter().map(|id| format!("{id} 0 R")).collect();
+        self.writer.object(
+            self.pages_id,
+            &format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                self.pages.len()
+            ),
+        );
+        let root = self.writer.add_object(&format!(
+            "<< /Type /Catalog /Pages {} 0 R >>",
+            self.pages_id
+        ));
+        self.writer.finish(root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dml::color::ColorFormat;
+    use crate::dml::fill::FillFormat;
+    use crate::embedded_font::EmbeddedFont;
+    use crate::render::sample_font;
+    use crate::shapes::ShapeTree;
+    use crate::text::font::RgbColor;
+    use crate::units::Emu;
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack
+            .windows(needle.len())
+            .filter(|w| *w == needle)
+            .count()
+    }
+
+    #[test]
+    fn export_empty_presentation() {
+        let prs = Presentation::new().unwrap();
+        let pdf = PdfExporter::new(&prs).export().unwrap();
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert_eq!(count(&pdf, b"/Count 0"), 1);
+    }
+
+    #[test]
+    fn export_one_page_per_slide() {
+        let mut prs = Presentation::new().unwrap();
+        let layouts = prs.slide_layouts().unwrap();
+        let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+        prs.add_slide(&layouts[1]).unwrap();
+
+        let slide_xml = prs.slide_xml(&slide_ref).unwrap().to_vec();
+        let updated = ShapeTree::add_textbox(
+            &slide_xml,
+            Emu(914_400),
+            Emu(914_400),
+            Emu(3_657_600),
+            Emu(457_200),
+        )
+        .unwrap();
+        *prs.slide_xml_mut(&slide_ref).unwrap() = updated;
+        let mut tree = prs.slide_shapes(&slide_ref).unwrap();
+        let textbox = tree.shapes.last_mut().unwrap().as_autoshape_mut().unwrap();
+        textbox.text_frame.as_mut().unwrap().set_text("Hello PDF");
+        textbox.fill = Some(FillFormat::solid(ColorFormat::Rgb(RgbColor::new(
+            255, 0, 0,
+        ))));
+        prs.update_slide_shapes(&slide_ref, &tree).unwrap();
+
+        let pdf = export_to_pdf(&prs).unwrap();
+        assert_eq!(count(&pdf, b"/Type /Page "), 2);
+        assert_eq!(count(&pdf, b"/Count 2"), 1);
+        assert_eq!(count(&pdf, b"/BaseFont /Helvetica "), 1);
+        assert!(pdf.ends_with(b"%%EOF\n"));
+    }
+
+    #[test]
+    fn export_embeds_fonts_as_cid_fonts() {
+        let mut prs = Presentation::new().unwrap();
+        let layouts = prs.slide_layouts().unwrap();
+        let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+        let slide_xml = prs.slide_xml(&slide_ref).unwrap().to_vec();
+        let updated = ShapeTree::add_textbox(
+            &slide_xml,
+            Emu(914_400),
+            Emu(914_400),
+            Emu(3_657_600),
+            Emu(457_200),
+        )
+        .unwrap();
+        *prs.slide_xml_mut(&slide_ref).unwrap() = updated;
+        let mut tree = prs.slide_shapes(&slide_ref).unwrap();
+        let textbox = tree.shapes.last_mut().unwrap().as_autoshape_mut().unwrap();
+        let tf = textbox.text_frame.as_mut().unwrap();
+        tf.set_text("HELLO");
+        tf.paragraphs_mut()[0].runs_mut()[0].font_mut().name = Some("Sample".to_string());
+        prs.update_slide_shapes(&slide_ref, &tree).unwrap();
+
+        let fonts = FontSet::new()
+            .with_font(&EmbeddedFont::from_bytes(
+                sample_font(),
+                "Sample",
+                false,
+                false,
+            ))
+            .unwrap();
+        let pdf = PdfExporter::new(&prs).with_fonts(fonts).export().unwrap();
+        assert_eq!(count(&pdf, b"/Subtype /Type0 "), 1);
+        assert_eq!(count(&pdf, b"/Encoding /Identity-H "), 1);
+        assert_eq!(count(&pdf, b"/Subtype /CIDFontType2 "), 1);
+        assert_eq!(
+            count(
+                &pdf,
+                b"/CIDToGIDMap /Identity /W [5 [600] 8 [600] 12 [600] 15 [600]]"
+            ),
+            1
+        );
+        assert_eq!(count(&pdf, b"/FontFile2 "), 1);
+        assert_eq!(count(&pdf, b"/ToUnicode "), 1);
+        assert_eq!(count(&pdf, b"/Length1 "), 1);
+        assert_eq!(count(&pdf, b"/E0 "), 1);
+        assert_eq!(count(&pdf, b"/BaseFont /Helvetica"), 0);
+
+        // Without the font the standard typefaces are used
+        let pdf = export_to_pdf(&prs).unwrap();
+        assert_eq!(count(&pdf, b"/Subtype /Type0 "), 0);
+        assert_eq!(count(&pdf, b"/BaseFont /Helvetica "), 1);
+    }
+
+    #[test]
+    fn export_shares_images_between_pages() {
+        let mut doc = Document::new(FontSet::new());
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x01, 0x00, 0x01, 0x01,
+        ];
+        let first = doc.image(&jpeg, "image/jpeg");
+        let second = doc.image(&jpeg, "image/jpeg");
+        assert!(first.is_some());
+        assert_eq!(first, second);
+        assert_eq!(doc.image(b"not an image", "image/gif"), None);
+    }
+}
@@ -0,0 +1,149 @@
+//! Low-level PDF file structure: numbered objects, streams and the
+//! cross-reference table.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+/// Writes PDF objects in order and records their offsets for the
+/// cross-reference table.
+pub(super) struct PdfWriter {
+    buf: Vec<u8>,
+    /// Byte offset of each object, indexed by object number - 1; `None`
+    /// for objects reserved but not written yet.
+    offsets: Vec<Option<usize>>,
+}
+
+impl PdfWriter {
+    pub(super) fn new() -> Self {
+        let mut buf = b"%PDF-1.4\n".to_vec();
+        // A comment with high bytes marks the file as binary
+        buf.extend_from_slice(b"%\xE2\xE3\xCF\xD3\n");
+        Self {
+            buf,
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Allocate an object number to be written later.
+    pub(super) fn reserve(&mut self) -> u32 {
+        self.offsets.push(None);
+        u32::try_from(self.offsets.len()).unwrap_or(u32::MAX)
+    }
+
+    fn begin(&mut self, id: u32) {
+        if let Some(slot) = self.offsets.get_mut(id as usize - 1) {
+            *slot = Some(self.buf.len());
+        }
+        self.buf
+            .extend_from_slice(format!("{id} 0 obj\n").as_bytes());
+    }
+
+    /// Write object `id` with the given body.
+    pub(super) fn object(&mut self, id: u32, body: &str) {
+        self.begin(id);
+        self.buf.extend_from_slice(body.as_bytes());
+        self.buf.extend_from_slice(b"\nendobj\n");
+    }
+
+    /// Write a new object, returning its number.
+    pub(super) fn add_object(&mut self, body: &str) -> u32 {
+        let id = self.reserve();
+        self.object(id, body);
+        id
+    }
+
+    /// Write stream object `id`. `dict` holds the entries of the stream
+    /// dictionary other than `/Length`.
+    pub(super) fn stream(&mut self, id: u32, dict: &str, data: &[u8]) {
+        self.begin(id);
+        self.buf
+            .extend_from_slice(format!("<< {dict} /Length {} >>\nstream\n", data.len()).as_bytes());
+        self.buf.extend_from_slice(data);
+        self.buf.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    /// Write a new stream object, returning its number.
+    pub(super) fn add_stream(&mut self, dict: &str, data: &[u8]) -> u32 {
+        let id = self.reserve();
+        self.stream(id, dict, data);
+        id
+    }
+
+    /// Write a new stream, Flate-compressed, returning its number.
+    pub(super) fn add_compressed_stream(&mut self, dict: &str, data: &[u8]) -> u32 {
+        let dict = format!("{dict} /Filter /FlateDecode");
+        self.add_stream(dict.trim_start(), &deflate(data))
+    }
+
+    /// Write the cross-reference table and trailer for catalog `root`.
+    pub(super) fn finish(mut self, root: u32) -> Vec<u8> {
+        let xref = self.buf.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            // Objects never written are listed as free
+            match offset {
+                Some(offset) => {
+                    let _ = writeln!(table, "{offset:010} 00000 n ");
+                }
+                None => table.push_str("0000000000 65535 f \n"),
+            }
+        }
+        let _ = write!(
+            table,
+            "trailer\n<< /Size {} /Root {root} 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            self.offsets.len() + 1
+        );
+        self.buf.extend_from_slice(table.as_bytes());
+        self.buf
+    }
+}
+
+/// Compress `data` with zlib, as `/FlateDecode` expects.
+pub(super) fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    // Writing to a Vec cannot fail
+    let _ = encoder.write_all(data);
+    encoder.finish().unwrap_or_default()
+}
+
+/// Format a number for a content stream or dictionary: at most three
+/// decimals, without trailing zeros.
+pub(super) fn num(value: f64) -> String {
+    if !value.is_finite() {
+        return "0".to_string();
+    }
+    let s = format!("{value:.3}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    match s {
+        "-0" | "" => "0".to_string(),
+        _ => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers_compactly() {
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(0.5), "0.5");
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(2.12345), "2.123");
+        assert_eq!(num(f64::NAN), "0");
+    }
+
+    #[test]
+    fn writes_xref_offsets() {
+        let mut writer = PdfWriter::new();
+        let root = writer.add_object("<< /Type /Catalog >>");
+        let pdf = writer.finish(root);
+        let offset = pdf.windows(7).position(|w| w == b"1 0 obj").unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains(&format!("{offset:010} 00000 n ")));
+        assert!(text.ends_with("%%EOF\n"));
+    }
+}
@@ -0,0 +1,1206 @@
+//! Building the scene of a slide from its shapes and from the layout and
+//! master behind it.
+
+use std::collections::HashMap;
+
+use crate::dml::color::ColorFormat;
+use crate::dml::fill::{FillFormat, GradientFill};
+use crate::dml::line::{LineCap, LineFormat, LineJoin};
+use crate::enums::dml::MsoLineDashStyle;
+use crate::enums::dml_pattern::MsoPatternType;
+use crate::enums::shapes::PpPlaceholderType;
+use crate::enums::text::MsoVerticalAnchor;
+use crate::error::{PartNotFoundExt, PptxResult};
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::part::Part;
+use crate::presentation::Presentation;
+use crate::shapes::autoshape::AutoShape;
+use crate::shapes::connector::Connector;
+use crate::shapes::graphfrm::GraphicFrame;
+use crate::shapes::group::GroupShape;
+use crate::shapes::ole::OleObject;
+use crate::shapes::parser::parse_fill_from_xml;
+use crate::shapes::picture::Picture;
+use crate::shapes::placeholder::PlaceholderFormat;
+use crate::shapes::{Shape, ShapeTree};
+use crate::slide::SlideRef;
+use crate::table::Table;
+use crate::text::font::RgbColor;
+use crate::text::layout::{frame_insets, insets, merge, ListStyle, TextDefaults, ThemeFonts};
+use crate::text::TextFrame;
+use crate::theme::colors::{tint, Colors, WHITE};
+use crate::units::{Emu, ShapeId};
+use crate::xml_util::splice::{find_element, find_element_in, find_start_tag, get_attr};
+
+use super::geometry::{self, Geometry};
+use super::style::parse_list_style;
+use super::text::{layout_text, TextBox};
+use super::{
+    emu_to_pt, Item, Matrix, Paint, Path, PatternKind, Scene, SceneImage, Stroke, EMU_PER_PT,
+};
+
+/// Slide size used when the presentation does not declare one (10" × 7.5").
+const DEFAULT_SLIDE_SIZE: (i64, i64) = (9_144_000, 6_858_000);
+
+/// Line width used when a line sets none, in points.
+const DEFAULT_LINE_WIDTH: f64 = 0.75;
+
+/// The slide size of `prs` in points.
+///
+/// # Errors
+///
+/// Returns an error if the presentation part cannot be read.
+pub(crate) fn page_size(prs: &Presentation) -> PptxResult<(f64, f64)> {
+    let (width, height) = prs.slide_size()?.unwrap_or(DEFAULT_SLIDE_SIZE);
+    Ok((emu_to_pt(width), emu_to_pt(height)))
+}
+
+/// Lay out `slide` of `prs` for drawing.
+///
+/// # Errors
+///
+/// Returns an error if the slide, its layout or its master cannot be read.
+pub(crate) fn build_scene(prs: &Presentation, slide: &SlideRef) -> PptxResult<Scene> {
+    let (width, height) = page_size(prs)?;
+    let parts = SlideParts::new(prs, slide)?;
+    let mut builder = Builder::new(prs, &parts)?;
+
+    let mut items = builder.background(&parts.all(), width, height)?;
+    for (ctx, slide) in parts.drawn() {
+        items.extend(builder.part_shapes(ctx, slide)?);
+    }
+    Ok(Scene {
+        width,
+        height,
+        items,
+        images: builder.images,
+    })
+}
+
+/// A slide with the layout and master behind it.
+pub(super) struct SlideParts<'a> {
+    pub slide: PartCtx<'a>,
+    pub layout: Option<PartCtx<'a>>,
+    pub master: Option<PartCtx<'a>>,
+}
+
+impl<'a> SlideParts<'a> {
+    pub(super) fn new(prs: &'a Presentation, slide: &SlideRef) -> PptxResult<Self> {
+        let slide_part = prs
+            .package()
+            .part(&slide.partname)
+            .or_part_not_found(slide.partname.as_str())?;
+        let layout = related(prs, slide_part, RT::SLIDE_LAYOUT)
+            .map(PartCtx::new)
+            .transpose()?;
+        let master = layout
+            .as_ref()
+            .and_then(|l| related(prs, l.part, RT::SLIDE_MASTER))
+            .map(PartCtx::new)
+            .transpose()?;
+        Ok(Self {
+            slide: PartCtx::new(slide_part)?,
+            layout,
+            master,
+        })
+    }
+
+    /// The slide, layout and master, in that order.
+    pub(super) fn all(&self) -> Vec<&PartCtx<'a>> {
+        [
+            Some(&self.slide),
+            self.layout.as_ref(),
+            self.master.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The parts whose shapes are drawn, back to front, each with whether
+    /// it is the slide itself.
+    pub(super) fn drawn(&self) -> Vec<(&PartCtx<'a>, bool)> {
+        let mut parts = Vec::new();
+        if shows_master_shapes(&self.slide) {
+            if let Some(master) = &self.master {
+                if self.layout.as_ref().is_none_or(shows_master_shapes) {
+                    parts.push((master, false));
+                }
+            }
+            if let Some(layout) = &self.layout {
+                parts.push((layout, false));
+            }
+        }
+        parts.push((&self.slide, true));
+        parts
+    }
+}
+
+/// A slide, layout or master part with its shapes.
+pub(super) struct PartCtx<'a> {
+    pub part: &'a Part,
+    pub xml: &'a str,
+    pub tree: ShapeTree,
+}
+
+impl<'a> PartCtx<'a> {
+    fn new(part: &'a Part) -> PptxResult<Self> {
+        Ok(Self {
+            part,
+            xml: std::str::from_utf8(&part.blob)?,
+            tree: ShapeTree::from_slide_xml(&part.blob)?,
+        })
+    }
+
+    pub(super) fn shape_xml(&self, shape_id: ShapeId) -> PptxResult<String> {
+        ShapeTree::shape_xml(self.xml.as_bytes(), shape_id)
+    }
+}
+
+/// The first part `part` relates to with `reltype`.
+fn related<'p>(prs: &'p Presentation, part: &Part, reltype: &str) -> Option<&'p Part> {
+    let rel = part.rels.all_by_reltype(reltype).into_iter().next()?;
+    let partname = rel.target_partname(part.partname.base_uri()).ok()?;
+    prs.package().part(&partname)
+}
+
+/// Whether a slide or layout shows the shapes of the parts behind it
+/// (`showMasterSp`).
+fn shows_master_shapes(ctx: &PartCtx<'_>) -> bool {
+    ["p:sld", "p:sldLayout"]
+        .into_iter()
+        .find_map(|root| find_start_tag(ctx.xml, root, 0))
+        .and_then(|tag| get_attr(&ctx.xml[tag], "showMasterSp"))
+        != Some("0")
+}
+
+/// Text styles of a slide master (`<p:txStyles>`).
+#[derive(Debug, Default)]
+struct MasterStyles {
+    title: ListStyle,
+    body: ListStyle,
+    other: ListStyle,
+}
+
+impl MasterStyles {
+    fn parse(xml: &str, colors: &Colors) -> PptxResult<Self> {
+        let style = |name: &str| match find_element(xml, name, 0) {
+            Some(range) => parse_list_style(&xml[range], colors),
+            None => Ok(ListStyle::default()),
+        };
+        Ok(Self {
+            title: style("p:titleStyle")?,
+            body: style("p:bodyStyle")?,
+            other: style("p:otherStyle")?,
+        })
+    }
+}
+
+/// Placeholder kinds that inherit from each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlaceholderKind {
+    Title,
+    Body,
+    Date,
+    Footer,
+    SlideNumber,
+    Other,
+}
+
+fn placeholder_kind(ph_type: Option<PpPlaceholderType>) -> PlaceholderKind {
+    match ph_type {
+        Some(
+            PpPlaceholderType::Title
+            | PpPlaceholderType::CenterTitle
+            | PpPlaceholderType::VerticalTitle,
+        ) => PlaceholderKind::Title,
+        Some(PpPlaceholderType::Date) => PlaceholderKind::Date,
+        Some(PpPlaceholderType::Footer) => PlaceholderKind::Footer,
+        Some(PpPlaceholderType::SlideNumber) => PlaceholderKind::SlideNumber,
+        Some(PpPlaceholderType::Header) => PlaceholderKind::Other,
+        _ => PlaceholderKind::Body,
+    }
+}
+
+/// The placeholder of `ctx` that `ph` inherits from: the one with the same
+/// index (when `by_idx`) or else the first of the same kind.
+fn find_placeholder<'c>(
+    ctx: &'c PartCtx<'_>,
+    ph: &PlaceholderFormat,
+    by_idx: bool,
+) -> Option<&'c Shape> {
+    let kind = placeholder_kind(ph.ph_type);
+    let same_kind = |p: &PlaceholderFormat| placeholder_kind(p.ph_type) == kind;
+    let mut placeholders = ctx
+        .tree
+        .shapes
+        .iter()
+        .filter_map(|s| s.placeholder().map(|p| (s, p)));
+    let by_index = by_idx
+        .then(|| {
+            ctx.tree.shapes.iter().find(|s| {
+                s.placeholder()
+                    .is_some_and(|p| p.idx == ph.idx && same_kind(p))
+            })
+        })
+        .flatten();
+    by_index.or_else(|| placeholders.find(|(_, p)| same_kind(p)).map(|(s, _)| s))
+}
+
+/// What a placeholder inherits from the layout and master.
+#[derive(Debug, Default)]
+struct Inherited {
+    /// Position and size, in points.
+    bounds: Option<Bounds>,
+    levels: ListStyle,
+    anchor: Option<MsoVerticalAnchor>,
+}
+
+/// A box in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Bounds {
+    pub x: f64,
+    pub y: f64,
+    pub w: f64,
+    pub h: f64,
+}
+
+impl Bounds {
+    pub(super) fn new(left: Emu, top: Emu, width: Emu, height: Emu) -> Self {
+        Self {
+            x: emu_to_pt(left.0),
+            y: emu_to_pt(top.0),
+            w: emu_to_pt(width.0),
+            h: emu_to_pt(height.0),
+        }
+    }
+
+    fn of(shape: &Shape) -> Self {
+        Self::new(shape.left(), shape.top(), shape.width(), shape.height())
+    }
+
+    /// The same size at the origin.
+    const fn local(self) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            ..self
+        }
+    }
+
+    /// Moves the box from the origin to its place, then rotates and flips
+    /// it about its center.
+    fn transform(self, rotation: f64, flip_h: bool, flip_v: bool) -> Matrix {
+        Matrix::translate(self.x, self.y).then(Matrix::rotate_about(
+            rotation,
+            self.x + self.w / 2.0,
+            self.y + self.h / 2.0,
+            flip_h,
+            flip_v,
+        ))
+    }
+}
+
+/// Horizontal and vertical flips set on the first `<a:xfrm>` of a shape.
+pub(super) fn flips(shape_xml: &str) -> (bool, bool) {
+    let Some(tag) = find_start_tag(shape_xml, "a:xfrm", 0) else {
+        return (false, false);
+    };
+    let flag = |name| matches!(get_attr(&shape_xml[tag.clone()], name), Some("1" | "true"));
+    (flag("flipH"), flag("flipV"))
+}
+
+fn preset_name(shape_xml: &str) -> Option<&str> {
+    find_start_tag(shape_xml, "a:prstGeom", 0).and_then(|tag| get_attr(&shape_xml[tag], "prst"))
+}
+
+/// The outline of a shape: its custom geometry, else its preset geometry,
+/// else a rectangle.
+fn shape_geometry(shape_xml: &str, bounds: Bounds) -> PptxResult<Geometry> {
+    if let Some(custom) = geometry::custom(shape_xml, bounds.w, bounds.h)? {
+        return Ok(custom);
+    }
+    Ok(geometry::preset(
+        preset_name(shape_xml).unwrap_or("rect"),
+        bounds.w,
+        bounds.h,
+        &geometry::adjustments(shape_xml),
+    ))
+}
+
+/// Vertical anchor set on the `<a:bodyPr>` of a shape.
+fn body_anchor(shape_xml: &str) -> Option<MsoVerticalAnchor> {
+    let tag = find_start_tag(shape_xml, "a:bodyPr", 0)?;
+    match get_attr(&shape_xml[tag], "anchor")? {
+        "t" => Some(MsoVerticalAnchor::Top),
+        "ctr" => Some(MsoVerticalAnchor::Middle),
+        "b" => Some(MsoVerticalAnchor::Bottom),
+        _ => None,
+    }
+}
+
+/// Wrap items in a group when the transform does anything.
+fn transformed(items: Vec<Item>, transform: Matrix) -> Vec<Item> {
+    if transform.is_identity() || items.is_empty() {
+        items
+    } else {
+        vec![Item::Group {
+            transform,
+            clip: None,
+            items,
+        }]
+    }
+}
+
+fn has_text(tf: &TextFrame) -> bool {
+    tf.paragraphs().iter().any(|p| !p.text().trim().is_empty())
+}
+
+/// A shape drawn in its own box, before it is placed on the slide.
+pub(super) struct Drawn {
+    pub bounds: Bounds,
+    pub rotation: f64,
+    pub flip_h: bool,
+    pub flip_v: bool,
+    /// Fills, outlines and pictures, from `(0, 0)` to the box size.
+    pub graphics: Vec<Item>,
+    /// How the shape's text is formatted where it sets nothing itself,
+    /// and the insets of its text frame.
+    pub text: Option<(TextDefaults, [f64; 4])>,
+}
+
+impl Drawn {
+    /// A box with nothing drawn in it.
+    pub(super) const fn empty(bounds: Bounds, rotation: f64) -> Self {
+        Self {
+            bounds,
+            rotation,
+            flip_h: false,
+            flip_v: false,
+            graphics: Vec::new(),
+            text: None,
+        }
+    }
+
+    /// The shape placed on the slide, with `tf` laid out inside it.
+    fn place(self, tf: Option<&TextFrame>, colors: &Colors) -> Vec<Item> {
+        let b = self.bounds;
+        let mut items = transformed(
+            self.graphics,
+            b.transform(self.rotation, self.flip_h, self.flip_v),
+        );
+        if let (Some(tf), Some((defaults, insets))) = (tf, &self.text) {
+            let area = TextBox {
+                x: 0.0,
+                y: 0.0,
+                width: b.w,
+                height: b.h,
+                insets: *insets,
+            };
+            // Text turns with the shape but is not mirrored
+            items.extend(transformed(
+                layout_text(tf, area, defaults, colors),
+                b.transform(self.rotation, false, false),
+            ));
+        }
+        items
+    }
+}
+
+/// How a table cell is drawn.
+pub(super) struct CellLook {
+    /// Background from the table style, used when the cell sets no fill.
+    pub style_fill: Option<RgbColor>,
+    /// Left, right, top and bottom borders.
+    pub borders: [Option<Stroke>; 4],
+    pub text: TextDefaults,
+    pub insets: [f64; 4],
+}
+
+/// Whether a table is drawn with its table style: without any cell fill it
+/// relies on the style for its look.
+pub(super) fn uses_table_style(table: &Table) -> bool {
+    !table.iter_cells().any(|c| c.fill.is_some())
+}
+
+/// The transform from a group's child space (`chOff`/`chExt`), in which
+/// its children are positioned, onto the group's own box.
+pub(super) fn child_space(group_xml: &str, bounds: Bounds) -> Matrix {
+    let xfrm = find_element(group_xml, "a:xfrm", 0).unwrap_or(0..0);
+    let child_attr = |tag: &str, name: &str| {
+        find_element_in(group_xml, tag, xfrm.clone())
+            .and_then(|range| get_attr(&group_xml[range], name).and_then(|v| v.parse::<f64>().ok()))
+            .map(|emu| emu / EMU_PER_PT)
+    };
+    let ch_x = child_attr("a:chOff", "x").unwrap_or(bounds.x);
+    let ch_y = child_attr("a:chOff", "y").unwrap_or(bounds.y);
+    let ch_w = child_attr("a:chExt", "cx").filter(|w| *w > 0.0);
+    let ch_h = child_attr("a:chExt", "cy").filter(|h| *h > 0.0);
+    let sx = ch_w.map_or(1.0, |w| bounds.w / w);
+    let sy = ch_h.map_or(1.0, |h| bounds.h / h);
+    Matrix([sx, 0.0, 0.0, sy, bounds.x - ch_x * sx, bounds.y - ch_y * sy])
+}
+
+/// Colors referenced by a shape's `<p:style>`, used where the shape sets no
+/// fill, line or font color of its own.
+#[derive(Debug, Default)]
+struct StyleRefs {
+    fill: Option<RgbColor>,
+    line: Option<RgbColor>,
+    font: Option<RgbColor>,
+}
+
+pub(super) struct Builder<'a, 'c> {
+    pub prs: &'a Presentation,
+    pub colors: Colors,
+    styles: MasterStyles,
+    pub theme_fonts: ThemeFonts,
+    layout: Option<&'c PartCtx<'a>>,
+    master: Option<&'c PartCtx<'a>>,
+    pub images: Vec<SceneImage>,
+    /// Index in `images` by image partname.
+    image_parts: HashMap<String, usize>,
+}
+
+impl<'a, 'c> Builder<'a, 'c> {
+    /// A builder for the shapes of `parts`, with the theme and text styles
+    /// of its master.
+    pub(super) fn new(prs: &'a Presentation, parts: &'c SlideParts<'a>) -> PptxResult<Self> {
+        let colors = Colors::new(prs.theme_colors()?.unwrap_or_default());
+        let styles = match &parts.master {
+            Some(m) => MasterStyles::parse(m.xml, &colors)?,
+            None => MasterStyles::default(),
+        };
+        let theme_fonts = parts
+            .master
+            .as_ref()
+            .and_then(|m| related(prs, m.part, RT::THEME))
+            .and_then(|theme| std::str::from_utf8(&theme.blob).ok())
+            .map(ThemeFonts::parse)
+            .unwrap_or_default();
+        Ok(Self {
+            prs,
+            colors,
+            styles,
+            theme_fonts,
+            layout: parts.layout.as_ref(),
+            master: parts.master.as_ref(),
+            images: Vec::new(),
+            image_parts: HashMap::new(),
+        })
+    }
+}
+
+impl Builder<'_, '_> {
+    /// The background of the first part that defines one, white otherwise.
+    pub(super) fn background(
+        &mut self,
+        parts: &[&PartCtx<'_>],
+        width: f64,
+        height: f64,
+    ) -> PptxResult<Vec<Item>> {
+        let page = Bounds {
+            x: 0.0,
+            y: 0.0,
+            w: width,
+            h: height,
+        };
+        let rect = Path::rect(0.0, 0.0, width, height);
+        let white = || vec![Item::Fill(rect.clone(), Paint::Solid(WHITE))];
+        for ctx in parts {
+            let Some(bg) = find_element(ctx.xml, "p:bg", 0) else {
+                continue;
+            };
+            let bg_xml = &ctx.xml[bg];
+            if let Some(pr) = find_element(bg_xml, "p:bgPr", 0) {
+                if let Some(fill) = parse_fill_from_xml(bg_xml[pr].as_bytes())? {
+                    // Picture backgrounds may be transparent in places
+                    let mut items = match fill {
+                        FillFormat::Solid(_) | FillFormat::Gradient(_) => Vec::new(),
+                        _ => white(),
+                    };
+                    items.extend(self.fill(ctx.part, &fill, &rect, page)?);
+                    return Ok(items);
+                }
+            } else if let Some(color) = self.colors.resolve_xml(bg_xml)? {
+                return Ok(vec![Item::Fill(rect, Paint::Solid(color))]);
+            }
+            break;
+        }
+        Ok(white())
+    }
+
+    /// The shapes of a part; placeholders only on slides, where they hold
+    /// content.
+    fn part_shapes(&mut self, ctx: &PartCtx<'_>, slide: bool) -> PptxResult<Vec<Item>> {
+        let mut items = Vec::new();
+        for shape in &ctx.tree.shapes {
+            if slide || !shape.is_placeholder() {
+                items.extend(self.shape(ctx, shape, slide)?);
+            }
+        }
+        Ok(items)
+    }
+
+    fn shape(&mut self, ctx: &PartCtx<'_>, shape: &Shape, slide: bool) -> PptxResult<Vec<Item>> {
+        match shape {
+            Shape::AutoShape(s) => self.autoshape(ctx, s, slide),
+            Shape::Picture(p) => self.picture(ctx, p),
+            Shape::GraphicFrame(g) => match &g.table {
+                Some(table) => self.table(ctx, g, table),
+                None => Ok(Vec::new()),
+            },
+            Shape::GroupShape(g) => self.group(ctx, g, slide),
+            Shape::Connector(c) => self.connector(ctx, c),
+            Shape::OleObject(o) => {
+                let drawn = self.draw_ole_object(ctx, o)?;
+                Ok(drawn.place(None, &self.colors))
+            }
+        }
+    }
+
+    fn style_refs(&self, shape_xml: &str) -> PptxResult<StyleRefs> {
+        let Some(style) = find_element(shape_xml, "p:style", 0) else {
+            return Ok(StyleRefs::default());
+        };
+        let color = |name: &str| -> PptxResult<Option<RgbColor>> {
+            let Some(range) = find_element_in(shape_xml, name, style.clone()) else {
+                return Ok(None);
+            };
+            let element = &shape_xml[range];
+            if get_attr(element, "idx") == Some("0") {
+                return Ok(None);
+            }
+            self.colors.resolve_xml(element)
+        };
+        Ok(StyleRefs {
+            fill: color("a:fillRef")?,
+            line: color("a:lnRef")?,
+            font: color("a:fontRef")?,
+        })
+    }
+
+    /// What a placeholder on the slide inherits from the layout and master.
+    fn inherited(&self, ph: &PlaceholderFormat) -> PptxResult<Inherited> {
+        let mut inherited = Inherited {
+            levels: match placeholder_kind(ph.ph_type) {
+                PlaceholderKind::Title => self.styles.title.clone(),
+                PlaceholderKind::Body => self.styles.body.clone(),
+                _ => self.styles.other.clone(),
+            },
+            ..Inherited::default()
+        };
+        let from_master = self
+            .master
+            .and_then(|m| find_placeholder(m, ph, false).map(|s| (m, s)));
+        let from_layout = self
+            .layout
+            .and_then(|l| find_placeholder(l, ph, true).map(|s| (l, s)));
+        for (ctx, shape) in [from_master, from_layout].into_iter().flatten() {
+            if shape.width().0 > 0 || shape.height().0 > 0 {
+                inherited.bounds = Some(Bounds::of(shape));
+            }
+            let xml = ctx.shape_xml(shape.shape_id())?;
+            if let Some(list) = find_element(&xml, "a:lstStyle", 0) {
+                let levels = parse_list_style(&xml[list], &self.colors)?;
+                inherited.levels = merge(&inherited.levels, &levels);
+            }
+            if let Some(anchor) = body_anchor(&xml) {
+                inherited.anchor = Some(anchor);
+            }
+        }
+        Ok(inherited)
+    }
+
+    fn autoshape(
+        &mut self,
+        ctx: &PartCtx<'_>,
+        s: &AutoShape,
+        slide: bool,
+    ) -> PptxResult<Vec<Item>> {
+        let drawn = self.draw_autoshape(ctx, s, slide)?;
+        Ok(drawn.place(s.text_frame.as_ref(), &self.colors))
+    }
+
+    /// The fill and outline of an autoshape, and how its text is formatted
+    /// where it sets nothing itself.
+    pub(super) fn draw_autoshape(
+        &mut self,
+        ctx: &PartCtx<'_>,
+        s: &AutoShape,
+        slide: bool,
+    ) -> PptxResult<Drawn> {
+        let xml = ctx.shape_xml(s.shape_id)?;
+        let mut inherited = match (&s.placeholder, slide) {
+            (Some(ph), true) => self.inherited(ph)?,
+            _ => Inherited {
+                levels: self.styles.other.clone(),
+                ..Inherited::default()
+            },
+        };
+        let own = Bounds::new(s.left, s.top, s.width, s.height);
+        let bounds = if own.w == 0.0 && own.h == 0.0 {
+            inherited.bounds.unwrap_or(own)
+        } else {
+            own
+        };
+        let refs = self.style_refs(&xml)?;
+        let (flip_h, flip_v) = flips(&xml);
+        let geometry = shape_geometry(&xml, bounds)?;
+        let local = bounds.local();
+
+        let mut graphics = Vec::new();
+        let fill = s
+            .fill
+            .clone()
+            .or_else(|| refs.fill.map(|c| FillFormat::solid(ColorFormat::Rgb(c))));
+        if let (Some(fill), true) = (&fill, geometry.closed) {
+            graphics.extend(self.fill(ctx.part, fill, &geometry.path, local)?);
+        }
+        if let Some(stroke) = self.stroke(s.line.as_ref(), refs.line) {
+            graphics.push(Item::Stroke(geometry.path, stroke));
+        }
+
+        let mut text = None;
+        if let Some(tf) = s.text_frame.as_ref().filter(|tf| has_text(tf)) {
+            if let Some(list) = find_element(&xml, "a:lstStyle", 0) {
+                let levels = parse_list_style(&xml[list], &self.colors)?;
+                inherited.levels = merge(&inherited.levels, &levels);
+            }
+            if refs.font.is_some() {
+                // The shape style's font color wins over the master's
+                for level in &mut inherited.levels {
+                    level.color = None;
+                }
+            }
+            let defaults = TextDefaults {
+                color: refs.font.unwrap_or_else(|| self.colors.scheme("tx1")),
+                anchor: inherited.anchor.unwrap_or(MsoVerticalAnchor::Top),
+                levels: inherited.levels,
+                theme_fonts: self.theme_fonts.clone(),
+                ..TextDefaults::default()
+            };
+            text = Some((defaults, frame_insets(tf)));
+        }
+        Ok(Drawn {
+            bounds,
+            rotation: s.rotation,
+            flip_h,
+            flip_v,
+            graphics,
+            text,
+        })
+    }
+
+    fn picture(&mut self, ctx: &PartCtx<'_>, p: &Picture) -> PptxResult<Vec<Item>> {
+        let drawn = self.draw_picture(ctx, p)?;
+        Ok(drawn.place(None, &self.colors))
+    }
+
+    /// The image of a picture, cropped and clipped to its outline.
+    pub(super) fn draw_picture(&mut self, ctx: &PartCtx<'_>, p: &Picture) -> PptxResult<Drawn> {
+        let xml = ctx.shape_xml(p.shape_id)?;
+        let bounds = Bounds::new(p.left, p.top, p.width, p.height);
+        let geometry = shape_geometry(&xml, bounds)?;
+        let index = match &p.image_r_id {
+            Some(r_id) => self.image(ctx.part, r_id)?,
+            None => None,
+        };
+        let index = index.or_else(|| {
+            p.image_data.as_ref().map(|data| {
+                self.images.push(SceneImage {
+                    data: data.clone(),
+                    content_type: p.image_content_type.clone().unwrap_or_default(),
+                });
+                self.images.len() - 1
+            })
+        });
+
+        let mut graphics = Vec::new();
+        if let Some(index) = index {
+            // The whole image is placed so that its uncropped part fills the
+            // shape, and clipped to the shape outline
+            let visible_w = (1.0 - p.crop_left - p.crop_right).max(0.01);
+            let visible_h = (1.0 - p.crop_top - p.crop_bottom).max(0.01);
+            let width = bounds.w / visible_w;
+            let height = bounds.h / visible_h;
+            graphics.push(Item::Group {
+                transform: Matrix::IDENTITY,
+                clip: Some(geometry.path.clone()),
+                items: vec![Item::Image {
+                    index,
+                    x: -p.crop_left * width,
+                    y: -p.crop_top * height,
+                    width,
+                    height,
+                }],
+            });
+        }
+        if let Some(stroke) = self.stroke(p.line.as_ref(), None) {
+            graphics.push(Item::Stroke(geometry.path, stroke));
+        }
+        let (flip_h, flip_v) = flips(&xml);
+        Ok(Drawn {
+            bounds,
+            rotation: p.rotation,
+            flip_h,
+            flip_v,
+            graphics,
+            text: None,
+        })
+    }
+
+    fn connector(&mut self, ctx: &PartCtx<'_>, c: &Connector) -> PptxResult<Vec<Item>> {
+        let drawn = self.draw_connector(ctx, c)?;
+        Ok(drawn.place(None, &self.colors))
+    }
+
+    /// The line of a connector.
+    pub(super) fn draw_connector(&self, ctx: &PartCtx<'_>, c: &Connector) -> PptxResult<Drawn> {
+        let xml = ctx.shape_xml(c.shape_id)?;
+        let refs = self.style_refs(&xml)?;
+        let bounds = Bounds::new(c.left, c.top, c.width, c.height);
+        let mut graphics = Vec::new();
+        if let Some(stroke) = self.stroke(c.line.as_ref(), refs.line) {
+            let geometry = geometry::preset(
+                preset_name(&xml).unwrap_or("line"),
+                bounds.w,
+                bounds.h,
+                &geometry::adjustments(&xml),
+            );
+            graphics.push(Item::Stroke(geometry.path, stroke));
+        }
+        Ok(Drawn {
+            bounds,
+            rotation: c.rotation,
+            flip_h: c.flip_h,
+            flip_v: c.flip_v,
+            graphics,
+            text: None,
+        })
+    }
+
+    /// The preview image of an OLE object, stretched over its box.
+    pub(super) fn draw_ole_object(
+        &mut self,
+        ctx: &PartCtx<'_>,
+        o: &OleObject,
+    ) -> PptxResult<Drawn> {
+        let bounds = Bounds::new(o.left, o.top, o.width, o.height);
+        let mut drawn = Drawn::empty(bounds, o.rotation);
+        if let Some(r_id) = &o.icon_r_id {
+            if let Some(index) = self.image(ctx.part, r_id.as_str())? {
+                drawn.graphics.push(Item::Image {
+                    index,
+                    x: 0.0,
+                    y: 0.0,
+                    width: bounds.w,
+                    height: bounds.h,
+                });
+            }
+        }
+        Ok(drawn)
+    }
+
+    fn group(&mut self, ctx: &PartCtx<'_>, g: &GroupShape, slide: bool) -> PptxResult<Vec<Item>> {
+        let xml = ctx.shape_xml(g.shape_id)?;
+        let bounds = Bounds::new(g.left, g.top, g.width, g.height);
+        let (flip_h, flip_v) = flips(&xml);
+        let transform = child_space(&xml, bounds).then(Matrix::rotate_about(
+            g.rotation,
+            bounds.x + bounds.w / 2.0,
+            bounds.y + bounds.h / 2.0,
+            flip_h,
+            flip_v,
+        ));
+
+        let mut items = Vec::new();
+        for shape in &g.shapes {
+            items.extend(self.shape(ctx, shape, slide)?);
+        }
+        Ok(transformed(items, transform))
+    }
+
+    fn table(
+        &mut self,
+        ctx: &PartCtx<'_>,
+        g: &GraphicFrame,
+        table: &Table,
+    ) -> PptxResult<Vec<Item>> {
+        let bounds = Bounds::new(g.left, g.top, g.width, g.height);
+        let edges = |sizes: &mut dyn Iterator<Item = Emu>| {
+            let mut edges = vec![0.0];
+            let mut at = 0.0;
+            for size in sizes {
+                at += emu_to_pt(size.0);
+                edges.push(at);
+            }
+            edges
+        };
+        let xs = edges(&mut table.columns.iter().map(|c| c.width));
+        let ys = edges(&mut table.rows.iter().map(|r| r.height));
+        let styled = uses_table_style(table);
+
+        let mut items = Vec::new();
+        for (ri, row) in table.rows.iter().enumerate() {
+            for (ci, cell) in row.cells.iter().enumerate() {
+                if cell.is_spanned() || ci + 1 >= xs.len() {
+                    continue;
+                }
+                let col_end = (ci + cell.grid_span.max(1) as usize).min(xs.len() - 1);
+                let row_end = (ri + cell.row_span.max(1) as usize).min(ys.len() - 1);
+                let b = Bounds {
+                    x: xs[ci],
+                    y: ys[ri],
+                    w: xs[col_end] - xs[ci],
+                    h: ys[row_end] - ys[ri],
+                };
+                let path = Path::rect(b.x, b.y, b.w, b.h);
+                let look = self.cell_look(table, ri, cell, styled);
+                match (&cell.fill, look.style_fill) {
+                    (Some(fill), _) => items.extend(self.fill(ctx.part, fill, &path, b)?),
+                    (None, Some(color)) => items.push(Item::Fill(path, Paint::Solid(color))),
+                    (None, None) => {}
+                }
+                let sides = [
+                    ((b.x, b.y), (b.x, b.y + b.h)),
+                    ((b.x + b.w, b.y), (b.x + b.w, b.y + b.h)),
+                    ((b.x, b.y), (b.x + b.w, b.y)),
+                    ((b.x, b.y + b.h), (b.x + b.w, b.y + b.h)),
+                ];
+                for (border, (from, to)) in look.borders.into_iter().zip(sides) {
+                    if let Some(stroke) = border {
+                        let mut line = Path::default();
+                        line.move_to(from.0, from.1);
+                        line.line_to(to.0, to.1);
+                        items.push(Item::Stroke(line, stroke));
+                    }
+                }
+
+                if has_text(&cell.text_frame) {
+                    let area = TextBox {
+                        x: b.x,
+                        y: b.y,
+                        width: b.w,
+                        height: b.h,
+                        insets: look.insets,
+                    };
+                    items.extend(layout_text(
+                        &cell.text_frame,
+                        area,
+                        &look.text,
+                        &self.colors,
+                    ));
+                }
+            }
+        }
+        Ok(transformed(
+            items,
+            bounds.transform(g.rotation, false, false),
+        ))
+    }
+
+    /// How the cell of row `ri` is drawn. `styled` tables have no cell
+    /// fills of their own and are drawn like the default medium table
+    /// style.
+    pub(super) fn cell_look(
+        &self,
+        table: &Table,
+        ri: usize,
+        cell: &crate::table::Cell,
+        styled: bool,
+    ) -> CellLook {
+        let accent = self.colors.scheme("accent1");
+        let header = styled && table.first_row && ri == 0;
+        let style_fill = styled.then(|| {
+            let body_row = ri.saturating_sub(usize::from(table.first_row));
+            if header {
+                accent
+            } else if table.horz_banding && body_row % 2 == 0 {
+                tint(accent, 0.4)
+            } else {
+                tint(accent, 0.2)
+            }
+        });
+        let border = |border: &Option<crate::table::CellBorder>| {
+            let (color, width) = match border {
+                Some(border) => (
+                    self.colors.resolve(border.color.as_ref()?),
+                    emu_to_pt(border.width.0),
+                ),
+                None if styled => (WHITE, 1.0),
+                None => return None,
+            };
+            (width > 0.0).then(|| Stroke {
+                color,
+                width,
+                dash: Vec::new(),
+                round_cap: false,
+                round_join: false,
+            })
+        };
+        let borders = &cell.borders;
+        CellLook {
+            style_fill,
+            borders: [
+                border(&borders.left),
+                border(&borders.right),
+                border(&borders.top),
+                border(&borders.bottom),
+            ],
+            text: TextDefaults {
+                color: if header {
+                    WHITE
+                } else {
+                    self.colors.scheme("tx1")
+                },
+                bold: header,
+                anchor: cell.vertical_anchor.unwrap_or(MsoVerticalAnchor::Top),
+                theme_fonts: self.theme_fonts.clone(),
+                ..TextDefaults::default()
+            },
+            insets: insets([
+                cell.margin_left,
+                cell.margin_top,
+                cell.margin_right,
+                cell.margin_bottom,
+            ]),
+        }
+    }
+
+    /// Items painting `path` with `fill`; `bounds` is the box gradients and
+    /// pictures are fitted to.
+    fn fill(
+        &mut self,
+        part: &Part,
+        fill: &FillFormat,
+        path: &Path,
+        bounds: Bounds,
+    ) -> PptxResult<Vec<Item>> {
+        if let FillFormat::Picture(picture) = fill {
+            let Some(index) = self.image(part, picture.image_r_id.as_str())? else {
+                return Ok(Vec::new());
+            };
+            return Ok(vec![Item::Group {
+                transform: Matrix::IDENTITY,
+                clip: Some(path.clone()),
+                items: vec![Item::Image {
+                    index,
+                    x: bounds.x,
+                    y: bounds.y,
+                    width: bounds.w,
+                    height: bounds.h,
+                }],
+            }]);
+        }
+        Ok(self
+            .paint(fill, bounds)
+            .map(|paint| Item::Fill(path.clone(), paint))
+            .into_iter()
+            .collect())
+    }
+
+    /// How `fill` paints an area fitted to `bounds`; `None` when nothing is
+    /// painted and for picture fills.
+    pub(super) fn paint(&self, fill: &FillFormat, bounds: Bounds) -> Option<Paint> {
+        Some(match fill {
+            FillFormat::NoFill | FillFormat::Background | FillFormat::Picture(_) => return None,
+            FillFormat::Solid(solid) => Paint::Solid(self.colors.resolve(&solid.color)),
+            FillFormat::Gradient(gradient) => self.gradient(gradient, bounds),
+            FillFormat::Pattern(pattern) => Paint::Pattern {
+                kind: pattern_kind(pattern.preset),
+                foreground: pattern
+                    .fore_color
+                    .as_ref()
+                    .map_or(RgbColor::new(0, 0, 0), |c| self.colors.resolve(c)),
+                background: pattern
+                    .back_color
+                    .as_ref()
+                    .map_or(WHITE, |c| self.colors.resolve(c)),
+            },
+        })
+    }
+
+    /// A linear gradient across `bounds` at the fill's angle.
+    fn gradient(&self, gradient: &GradientFill, bounds: Bounds) -> Paint {
+        let mut stops: Vec<(f64, RgbColor)> = gradient
+            .stops
+            .iter()
+            .map(|s| (s.position, self.colors.resolve(&s.color)))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let [(_, color)] = stops.as_slice() {
+            return Paint::Solid(*color);
+        }
+        // The API angle is counter-clockwise; drawing is clockwise
+        let clockwise = gradient
+            .angle
+            .map_or(0.0, |a| if a == 0.0 { 0.0 } else { 360.0 - a });
+        let (sin, cos) = clockwise.to_radians().sin_cos();
+        let half = (bounds.w * cos.abs() + bounds.h * sin.abs()) / 2.0;
+        let (cx, cy) = (bounds.x + bounds.w / 2.0, bounds.y + bounds.h / 2.0);
+        Paint::LinearGradient {
+            start: (cx - cos * half, cy - sin * half),
+            end: (cx + cos * half, cy + sin * half),
+            stops,
+        }
+    }
+
+    /// How to draw a line, or `None` when it is not drawn. `default` is the
+    /// color from the shape style, used when the line sets none.
+    fn stroke(&self, line: Option<&LineFormat>, default: Option<RgbColor>) -> Option<Stroke> {
+        let color = match line {
+            Some(l) if matches!(l.fill, Some(FillFormat::NoFill)) => return None,
+            Some(l) => l
+                .color
+                .as_ref()
+                .or(match &l.fill {
+                    Some(FillFormat::Solid(solid)) => Some(&solid.color),
+                    _ => None,
+                })
+                .map(|c| self.colors.resolve(c))
+                .or(default)?,
+            None => default?,
+        };
+        let width = line
+            .and_then(|l| l.width)
+            .map_or(DEFAULT_LINE_WIDTH, |w| emu_to_pt(w.0));
+        let unit: &[f64] = match line.and_then(|l| l.dash_style) {
+            None | Some(MsoLineDashStyle::Solid) => &[],
+            Some(MsoLineDashStyle::Dash) => &[4.0, 3.0],
+            Some(MsoLineDashStyle::DashDot) => &[4.0, 3.0, 1.0, 3.0],
+            Some(MsoLineDashStyle::DashDotDot) => &[8.0, 3.0, 1.0, 3.0, 1.0, 3.0],
+            Some(MsoLineDashStyle::LongDash) => &[8.0, 3.0],
+            Some(MsoLineDashStyle::LongDashDot) => &[8.0, 3.0, 1.0, 3.0],
+            Some(MsoLineDashStyle::RoundDot | MsoLineDashStyle::SquareDot) => &[1.0, 1.0],
+        };
+        Some(Stroke {
+            color,
+            width,
+            dash: unit.iter().map(|d| d * width).collect(),
+            round_cap: matches!(line.and_then(|l| l.cap), Some(LineCap::Round)),
+            round_join: matches!(line.and_then(|l| l.join), Some(LineJoin::Round)),
+        })
+    }
+
+    /// The index in the scene of the image `r_id` of `part` points at.
+    fn image(&mut self, part: &Part, r_id: &str) -> PptxResult<Option<usize>> {
+        let Some(rel) = part.rels.get(r_id).filter(|rel| !rel.is_external) else {
+            return Ok(None);
+        };
+        let partname = rel.target_partname(part.partname.base_uri())?;
+        if let Some(&index) = self.image_parts.get(partname.as_str()) {
+            return Ok(Some(index));
+        }
+        let Some(image) = self.prs.package().part(&partname) else {
+            return Ok(None);
+        };
+        self.images.push(SceneImage {
+            data: image.blob.clone(),
+            content_type: image.content_type.clone(),
+        });
+        let index = self.images.len() - 1;
+        self.image_parts.insert(partname.into_string(), index);
+        Ok(Some(index))
+    }
+}
+
+/// The drawn pattern closest to a preset.
+fn pattern_kind(preset: Option<MsoPatternType>) -> PatternKind {
+    use MsoPatternType as P;
+    let Some(preset) = preset else {
+        return PatternKind::Dots(0.5);
+    };
+    match preset {
+        P::Percent5 => PatternKind::Dots(0.05),
+        P::Percent10 => PatternKind::Dots(0.1),
+        P::Percent20 => PatternKind::Dots(0.2),
+        P::Percent25 => PatternKind::Dots(0.25),
+        P::Percent30 => PatternKind::Dots(0.3),
+        P::Percent40 => PatternKind::Dots(0.4),
+        P::Percent50 => PatternKind::Dots(0.5),
+        P::Percent60 => PatternKind::Dots(0.6),
+        P::Percent70 => PatternKind::Dots(0.7),
+        P::Percent75 => PatternKind::Dots(0.75),
+        P::Percent80 => PatternKind::Dots(0.8),
+        P::Percent90 => PatternKind::Dots(0.9),
+        P::Horizontal | P::DashedHorizontal => PatternKind::Horizontal(1.0),
+        P::LightHorizontal | P::NarrowHorizontal => PatternKind::Horizontal(0.5),
+        P::DarkHorizontal => PatternKind::Horizontal(2.0),
+        P::Vertical | P::DashedVertical => PatternKind::Vertical(1.0),
+        P::LightVertical | P::NarrowVertical => PatternKind::Vertical(0.5),
+        P::DarkVertical => PatternKind::Vertical(2.0),
+        P::DownwardDiagonal | P::DashedDownwardDiagonal => PatternKind::DownwardDiagonal(1.0),
+        P::LightDownwardDiagonal => PatternKind::DownwardDiagonal(0.5),
+        P::DarkDownwardDiagonal => PatternKind::DownwardDiagonal(2.0),
+        P::WideDownwardDiagonal => PatternKind::DownwardDiagonal(3.0),
+        P::UpwardDiagonal | P::DashedUpwardDiagonal => PatternKind::UpwardDiagonal(1.0),
+        P::LightUpwardDiagonal => PatternKind::UpwardDiagonal(0.5),
+        P::DarkUpwardDiagonal => PatternKind::UpwardDiagonal(2.0),
+        P::WideUpwardDiagonal => PatternKind::UpwardDiagonal(3.0),
+        P::Cross | P::SmallGrid | P::LargeGrid | P::DottedGrid | P::Plaid => {
+            PatternKind::Cross(1.0)
+        }
+        P::DiagonalCross | P::OutlinedDiamond | P::Trellis | P::DottedDiamond => {
+            PatternKind::DiagonalCross(1.0)
+        }
+        _ => PatternKind::Dots(0.5),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dml::fill::GradientStop;
+    use crate::enums::dml::MsoThemeColorIndex;
+
+    #[test]
+    fn gradient_runs_across_the_box() {
+        let builder_colors = Colors::default();
+        let gradient = GradientFill {
+            stops: vec![
+                GradientStop::new(0.0, ColorFormat::Rgb(RgbColor::new(255, 0, 0))).unwrap(),
+                GradientStop::new(1.0, ColorFormat::theme(MsoThemeColorIndex::Accent1)).unwrap(),
+            ],
+            angle: Some(270.0),
+        };
+        let prs = Presentation::new().unwrap();
+        let builder = Builder {
+            prs: &prs,
+            colors: builder_colors,
+            styles: MasterStyles::default(),
+            theme_fonts: ThemeFonts::default(),
+            layout: None,
+            master: None,
+            images: Vec::new(),
+            image_parts: HashMap::new(),
+        };
+        let bounds = Bounds {
+            x: 0.0,
+            y: 0.0,
+            w: 100.0,
+            h: 50.0,
+        };
+        // 270° counter-clockwise is 90° clockwise: top to bottom
+        let Paint::LinearGradient { start, end, stops } = builder.gradient(&gradient, bounds)
+        else {
+            panic!("expected a gradient");
+        };
+        assert!((start.0 - 50.0).abs() < 1e-9 && start.1.abs() < 1e-9);
+        assert!((end.0 - 50.0).abs() < 1e-9 && (end.1 - 50.0).abs() < 1e-9);
+        assert_eq!(stops[1].1, RgbColor::new(79, 129, 189));
+    }
+
+    #[test]
+    fn maps_placeholder_kinds() {
+        assert_eq!(
+            placeholder_kind(Some(PpPlaceholderType::CenterTitle)),
+            PlaceholderKind::Title
+        );
+        assert_eq!(
+            placeholder_kind(Some(PpPlaceholderType::Subtitle)),
+            PlaceholderKind::Body
+        );
+        assert_eq!(placeholder_kind(None), PlaceholderKind::Body);
+    }
+}
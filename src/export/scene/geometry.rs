@@ -0,0 +1,381 @@
+//! Outlines of preset and custom shape geometries.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PptxError, PptxResult};
+use crate::xml_util::splice::{find_element, find_start_tag, get_attr};
+use crate::xml_util::{attr_value, local_name_str};
+
+use super::Path;
+
+/// The outline of a shape, in its own box from `(0, 0)` to `(w, h)`.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Geometry {
+    pub path: Path,
+    /// Whether the outline encloses an area that can be filled.
+    pub closed: bool,
+}
+
+impl Geometry {
+    const fn closed(path: Path) -> Self {
+        Self { path, closed: true }
+    }
+
+    const fn open(path: Path) -> Self {
+        Self {
+            path,
+            closed: false,
+        }
+    }
+}
+
+/// Adjustment values of a preset geometry (`<a:avLst>`), as fractions.
+pub(super) fn adjustments(shape_xml: &str) -> Vec<f64> {
+    let Some(list) = find_element(shape_xml, "a:avLst", 0) else {
+        return Vec::new();
+    };
+    let mut values = Vec::new();
+    let mut pos = list.start;
+    while let Some(gd) = find_start_tag(shape_xml, "a:gd", pos).filter(|t| t.end <= list.end) {
+        let value = get_attr(&shape_xml[gd.clone()], "fmla")
+            .and_then(|f| f.strip_prefix("val "))
+            .and_then(|v| v.trim().parse::<f64>().ok());
+        values.push(value.map_or(f64::NAN, |v| v / 100_000.0));
+        pos = gd.end;
+    }
+    values
+}
+
+/// Polygon through `points`, given as fractions of the box.
+fn polygon(w: f64, h: f64, points: &[(f64, f64)]) -> Path {
+    let mut path = Path::default();
+    for (i, &(x, y)) in points.iter().enumerate() {
+        if i == 0 {
+            path.move_to(x * w, y * h);
+        } else {
+            path.line_to(x * w, y * h);
+        }
+    }
+    path.close();
+    path
+}
+
+/// Polygon through `points` in box coordinates.
+fn polygon_abs(points: &[(f64, f64)]) -> Path {
+    polygon(1.0, 1.0, points)
+}
+
+fn round_rect(w: f64, h: f64, r: f64) -> Path {
+    let r = r.clamp(0.0, w.min(h) / 2.0);
+    let mut path = Path::default();
+    path.move_to(r, 0.0);
+    path.line_to(w - r, 0.0);
+    path.arc_to(r, r, 270.0, 90.0);
+    path.line_to(w, h - r);
+    path.arc_to(r, r, 0.0, 90.0);
+    path.line_to(r, h);
+    path.arc_to(r, r, 90.0, 90.0);
+    path.line_to(0.0, r);
+    path.arc_to(r, r, 180.0, 90.0);
+    path.close();
+    path
+}
+
+/// A right-pointing block arrow in a `w` × `h` box, returned as points.
+fn arrow_points(w: f64, h: f64, shaft: f64, head: f64) -> Vec<(f64, f64)> {
+    let y1 = h * (1.0 - shaft) / 2.0;
+    let y2 = h - y1;
+    let hx = (w - head).max(0.0);
+    vec![
+        (0.0, y1),
+        (hx, y1),
+        (hx, 0.0),
+        (w, h / 2.0),
+        (hx, h),
+        (hx, y2),
+        (0.0, y2),
+    ]
+}
+
+fn star(w: f64, h: f64, points: u32, inner: f64) -> Path {
+    let steps = points * 2;
+    let coords: Vec<_> = (0..steps)
+        .map(|i| {
+            let angle = (-90.0 + 180.0 * f64::from(i) / f64::from(points)).to_radians();
+            let r = if i % 2 == 0 { 1.0 } else { inner };
+            (
+                w / 2.0 * (1.0 + r * angle.cos()),
+                h / 2.0 * (1.0 + r * angle.sin()),
+            )
+        })
+        .collect();
+    polygon_abs(&coords)
+}
+
+/// The outline of preset geometry `name` (e.g. `"roundRect"`) in a
+/// `w` × `h` box. Presets without a dedicated outline are drawn as
+/// rectangles.
+pub(super) fn preset(name: &str, w: f64, h: f64, adj: &[f64]) -> Geometry {
+    let ss = w.min(h);
+    let a = |i: usize, default: f64| {
+        adj.get(i)
+            .copied()
+            .filter(|v| v.is_finite())
+            .unwrap_or(default)
+    };
+    let path = match name {
+        "line" | "straightConnector1" | "curvedConnector2" | "curvedConnector3"
+        | "curvedConnector4" | "curvedConnector5" | "bentConnector2" => {
+            let mut path = Path::default();
+            path.move_to(0.0, 0.0);
+            path.line_to(w, h);
+            return Geometry::open(path);
+        }
+        "bentConnector3" | "bentConnector4" | "bentConnector5" => {
+            let x = w * a(0, 0.5);
+            let mut path = Path::default();
+            path.move_to(0.0, 0.0);
+            path.line_to(x, 0.0);
+            path.line_to(x, h);
+            path.line_to(w, h);
+            return Geometry::open(path);
+        }
+        "roundRect" | "flowChartAlternateProcess" => round_rect(w, h, ss * a(0, 0.166_67)),
+        "flowChartTerminator" => round_rect(w, h, h / 2.0),
+        "ellipse" | "flowChartConnector" => Path::ellipse(0.0, 0.0, w, h),
+        "triangle" | "flowChartExtract" => {
+            polygon(w, h, &[(a(0, 0.5), 0.0), (1.0, 1.0), (0.0, 1.0)])
+        }
+        "rtTriangle" => polygon(w, h, &[(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)]),
+        "diamond" | "flowChartDecision" => {
+            polygon(w, h, &[(0.5, 0.0), (1.0, 0.5), (0.5, 1.0), (0.0, 0.5)])
+        }
+        "parallelogram" | "flowChartInputOutput" => {
+            let x = ss * a(0, 0.25);
+            polygon_abs(&[(x, 0.0), (w, 0.0), (w - x, h), (0.0, h)])
+        }
+        "trapezoid" => {
+            let x = ss * a(0, 0.25);
+            polygon_abs(&[(0.0, h), (x, 0.0), (w - x, 0.0), (w, h)])
+        }
+        "homePlate" => {
+            let x = ss * a(0, 0.5);
+            polygon_abs(&[(0.0, 0.0), (w - x, 0.0), (w, h / 2.0), (w - x, h), (0.0, h)])
+        }
+        "pentagon" => polygon(
+            w,
+            h,
+            &[
+                (0.5, 0.0),
+                (1.0, 0.382),
+                (0.809, 1.0),
+                (0.191, 1.0),
+                (0.0, 0.382),
+            ],
+        ),
+        "hexagon" => {
+            let x = ss * a(0, 0.25);
+            polygon_abs(&[
+                (x, 0.0),
+                (w - x, 0.0),
+                (w, h / 2.0),
+                (w - x, h),
+                (x, h),
+                (0.0, h / 2.0),
+            ])
+        }
+        "octagon" => {
+            let x = ss * a(0, 0.292_89);
+            polygon_abs(&[
+                (x, 0.0),
+                (w - x, 0.0),
+                (w, x),
+                (w, h - x),
+                (w - x, h),
+                (x, h),
+                (0.0, h - x),
+                (0.0, x),
+            ])
+        }
+        "plus" => {
+            let x = ss * a(0, 0.25);
+            polygon_abs(&[
+                (x, 0.0),
+                (w - x, 0.0),
+                (w - x, x),
+                (w, x),
+                (w, h - x),
+                (w - x, h - x),
+                (w - x, h),
+                (x, h),
+                (x, h - x),
+                (0.0, h - x),
+                (0.0, x),
+                (x, x),
+            ])
+        }
+        "star4" => star(w, h, 4, 0.25),
+        "star5" => star(w, h, 5, 0.382),
+        "star6" => star(w, h, 6, 0.577),
+        "star8" => star(w, h, 8, 0.75),
+        "chevron" => {
+            let x = ss * a(0, 0.5);
+            polygon_abs(&[
+                (0.0, 0.0),
+                (w - x, 0.0),
+                (w, h / 2.0),
+                (w - x, h),
+                (0.0, h),
+                (x, h / 2.0),
+            ])
+        }
+        "rightArrow" => polygon_abs(&arrow_points(w, h, a(0, 0.5), ss * a(1, 0.5))),
+        "leftArrow" => {
+            let points = arrow_points(w, h, a(0, 0.5), ss * a(1, 0.5));
+            polygon_abs(&points.iter().map(|&(x, y)| (w - x, y)).collect::<Vec<_>>())
+        }
+        "downArrow" => {
+            let points = arrow_points(h, w, a(0, 0.5), ss * a(1, 0.5));
+            polygon_abs(&points.iter().map(|&(x, y)| (y, x)).collect::<Vec<_>>())
+        }
+        "upArrow" => {
+            let points = arrow_points(h, w, a(0, 0.5), ss * a(1, 0.5));
+            polygon_abs(&points.iter().map(|&(x, y)| (y, h - x)).collect::<Vec<_>>())
+        }
+        _ => Path::rect(0.0, 0.0, w, h),
+    };
+    Geometry::closed(path)
+}
+
+/// Parse a coordinate of a custom geometry path. Guide references are
+/// not evaluated and read as zero.
+fn coordinate(e: &quick_xml::events::BytesStart<'_>, key: &[u8]) -> PptxResult<f64> {
+    Ok(attr_value(e, key)?
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0))
+}
+
+/// The outline of the `<a:custGeom>` in `shape_xml`, scaled to a `w` × `h`
+/// box, or `None` if the shape has no custom geometry.
+///
+/// # Errors
+///
+/// Returns an error if the geometry XML is malformed.
+pub(super) fn custom(shape_xml: &str, w: f64, h: f64) -> PptxResult<Option<Geometry>> {
+    let Some(range) = find_element(shape_xml, "a:custGeom", 0) else {
+        return Ok(None);
+    };
+    let mut reader = Reader::from_str(&shape_xml[range]);
+    let mut path = Path::default();
+    let mut closed = false;
+    let (mut sx, mut sy) = (1.0, 1.0);
+    // Command being read and the points collected for it
+    let mut command = String::new();
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    loop {
+        match reader.read_event().map_err(PptxError::Xml)? {
+            Event::Start(ref e) | Event::Empty(ref e) => match local_name_str(e.name().as_ref()) {
+                "path" => {
+                    let pw = coordinate(e, b"w")?;
+                    let ph = coordinate(e, b"h")?;
+                    sx = if pw > 0.0 { w / pw } else { 1.0 };
+                    sy = if ph > 0.0 { h / ph } else { 1.0 };
+                    if attr_value(e, b"fill")?.as_deref() != Some("none") {
+                        closed = true;
+                    }
+                }
+                "pt" => {
+                    points.push((coordinate(e, b"x")? * sx, coordinate(e, b"y")? * sy));
+                }
+                "arcTo" => {
+                    let angle = |v: f64| v / 60_000.0;
+                    path.arc_to(
+                        coordinate(e, b"wR")? * sx,
+                        coordinate(e, b"hR")? * sy,
+                        angle(coordinate(e, b"stAng")?),
+                        angle(coordinate(e, b"swAng")?),
+                    );
+                }
+                "close" => path.close(),
+                name @ ("moveTo" | "lnTo" | "cubicBezTo" | "quadBezTo") => {
+                    command = name.to_string();
+                    points.clear();
+                }
+                _ => {}
+            },
+            Event::End(ref e) => {
+                if local_name_str(e.name().as_ref()) == command {
+                    add_segment(&mut path, &command, &points);
+                    command.clear();
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if path.0.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Geometry { path, closed }))
+}
+
+fn add_segment(path: &mut Path, command: &str, points: &[(f64, f64)]) {
+    match (command, points) {
+        ("moveTo", [(x, y), ..]) => path.move_to(*x, *y),
+        ("lnTo", [(x, y), ..]) => path.line_to(*x, *y),
+        ("cubicBezTo", [(x1, y1), (x2, y2), (x, y), ..]) => {
+            path.cubic_to(*x1, *y1, *x2, *y2, *x, *y);
+        }
+        ("quadBezTo", [(qx, qy), (x, y), ..]) => {
+            let (x0, y0) = path.current_point().unwrap_or((*qx, *qy));
+            path.cubic_to(
+                x0 + 2.0 / 3.0 * (qx - x0),
+                y0 + 2.0 / 3.0 * (qy - y0),
+                x + 2.0 / 3.0 * (qx - x),
+                y + 2.0 / 3.0 * (qy - y),
+                *x,
+                *y,
+            );
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::scene::PathCmd;
+
+    #[test]
+    fn preset_rect_fallback_and_line() {
+        let rect = preset("cloud", 10.0, 5.0, &[]);
+        assert!(rect.closed);
+        assert_eq!(rect.path, Path::rect(0.0, 0.0, 10.0, 5.0));
+        let line = preset("line", 10.0, 5.0, &[]);
+        assert!(!line.closed);
+        assert_eq!(line.path.current_point(), Some((10.0, 5.0)));
+    }
+
+    #[test]
+    fn reads_adjustments() {
+        let xml = r#"<p:sp><a:prstGeom prst="roundRect"><a:avLst><a:gd name="adj" fmla="val 50000"/></a:avLst></a:prstGeom></p:sp>"#;
+        assert_eq!(adjustments(xml), vec![0.5]);
+    }
+
+    #[test]
+    fn custom_geometry_is_scaled() {
+        let xml = r#"<p:sp><p:spPr><a:custGeom><a:pathLst><a:path w="100" h="100"><a:moveTo><a:pt x="0" y="0"/></a:moveTo><a:lnTo><a:pt x="100" y="0"/></a:lnTo><a:lnTo><a:pt x="50" y="100"/></a:lnTo><a:close/></a:path></a:pathLst></a:custGeom></p:spPr></p:sp>"#;
+        let geometry = custom(xml, 20.0, 10.0).unwrap().unwrap();
+        assert!(geometry.closed);
+        assert_eq!(
+            geometry.path.0,
+            vec![
+                PathCmd::MoveTo(0.0, 0.0),
+                PathCmd::LineTo(20.0, 0.0),
+                PathCmd::LineTo(10.0, 10.0),
+                PathCmd::Close,
+            ]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Device-independent drawing of a slide.
+//!
+//! [`build_scene`] lays a slide out as a list of simple drawing items —
+//! filled and stroked paths, positioned text and images — in points, with
+//! the origin at the top-left corner and y growing downwards. Export
+//! backends only have to serialize these items.
+
+mod build;
+mod geometry;
+mod shapes;
+mod style;
+mod text;
+
+pub(crate) use crate::text::layout::{FontFace, FontFamily, RunStyle, TextDefaults, TextStyler};
+pub(crate) use crate::theme::Colors;
+pub(crate) use build::{build_scene, page_size};
+pub(crate) use shapes::{
+    build_shapes, slide_text_boxes, Shadow, ShapeContent, ShapeView, SlideShapes, TableView,
+};
+
+use crate::text::font::RgbColor;
+
+/// EMU per point.
+pub(crate) const EMU_PER_PT: f64 = 12_700.0;
+
+/// Convert EMU to points.
+pub(crate) fn emu_to_pt(emu: i64) -> f64 {
+    #[allow(clippy::cast_precision_loss)] // i64→f64: slide coordinates are far below 2^52
+    let emu = emu as f64;
+    emu / EMU_PER_PT
+}
+
+/// An affine transform `[a b c d e f]`, mapping `(x, y)` to
+/// `(a·x + c·y + e, b·x + d·y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Matrix(pub [f64; 6]);
+
+impl Matrix {
+    pub(crate) const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    pub(crate) const fn translate(x: f64, y: f64) -> Self {
+        Self([1.0, 0.0, 0.0, 1.0, x, y])
+    }
+
+    /// Rotation by `degrees` clockwise (y points down) about `(cx, cy)`,
+    /// with optional horizontal and vertical flips about the same point.
+    pub(crate) fn rotate_about(degrees: f64, cx: f64, cy: f64, flip_h: bool, flip_v: bool) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let sx = if flip_h { -1.0 } else { 1.0 };
+        let sy = if flip_v { -1.0 } else { 1.0 };
+        let (a, b, c, d) = (cos * sx, sin * sx, -sin * sy, cos * sy);
+        Self([a, b, c, d, cx - a * cx - c * cy, cy - b * cx - d * cy])
+    }
+
+    /// `self` followed by `then`.
+    pub(crate) fn then(self, then: Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = then.0;
+        Self([
+            a * a2 + b * c2,
+            a * b2 + b * d2,
+            c * a2 + d * c2,
+            c * b2 + d * d2,
+            e * a2 + f * c2 + e2,
+            e * b2 + f * d2 + f2,
+        ])
+    }
+
+    pub(crate) fn is_identity(self) -> bool {
+        self == Self::IDENTITY
+    }
+}
+
+/// One step of a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PathCmd {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    CubicTo(f64, f64, f64, f64, f64, f64),
+    Close,
+}
+
+/// A path made of straight and cubic Bézier segments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Path(pub Vec<PathCmd>);
+
+impl Path {
+    pub(crate) fn rect(x: f64, y: f64, w: f64, h: f64) -> Self {
+        Self(vec![
+            PathCmd::MoveTo(x, y),
+            PathCmd::LineTo(x + w, y),
+            PathCmd::LineTo(x + w, y + h),
+            PathCmd::LineTo(x, y + h),
+            PathCmd::Close,
+        ])
+    }
+
+    pub(crate) fn move_to(&mut self, x: f64, y: f64) {
+        self.0.push(PathCmd::MoveTo(x, y));
+    }
+
+    pub(crate) fn line_to(&mut self, x: f64, y: f64) {
+        self.0.push(PathCmd::LineTo(x, y));
+    }
+
+    pub(crate) fn cubic_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) {
+        self.0.push(PathCmd::CubicTo(x1, y1, x2, y2, x, y));
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.0.push(PathCmd::Close);
+    }
+
+    /// Append an elliptical arc from the current point, of radii `rx`/`ry`,
+    /// from angle `start` sweeping `sweep` degrees (clockwise when
+    /// positive), as in DrawingML `arcTo`.
+    pub(crate) fn arc_to(&mut self, rx: f64, ry: f64, start: f64, sweep: f64) {
+        let Some((x0, y0)) = self.current_point() else {
+            return;
+        };
+        let (s, c) = start.to_radians().sin_cos();
+        let (cx, cy) = (x0 - rx * c, y0 - ry * s);
+        // Split into pieces of at most 90° so the Bézier stays close
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let pieces = (sweep.abs() / 90.0).ceil().max(1.0) as usize;
+        #[allow(clippy::cast_precision_loss)]
+        let step = sweep.to_radians() / pieces as f64;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let mut angle = start.to_radians();
+        for _ in 0..pieces {
+            let (s1, c1) = angle.sin_cos();
+            let (s2, c2) = (angle + step).sin_cos();
+            self.cubic_to(
+                cx + rx * (c1 - k * s1),
+                cy + ry * (s1 + k * c1),
+                cx + rx * (c2 + k * s2),
+                cy + ry * (s2 - k * c2),
+                cx + rx * c2,
+                cy + ry * s2,
+            );
+            angle += step;
+        }
+    }
+
+    /// The end point of the last segment.
+    pub(crate) fn current_point(&self) -> Option<(f64, f64)> {
+        let mut start = None;
+        let mut current = None;
+        for cmd in &self.0 {
+            match *cmd {
+                PathCmd::MoveTo(x, y) => {
+                    start = Some((x, y));
+                    current = start;
+                }
+                PathCmd::LineTo(x, y) | PathCmd::CubicTo(_, _, _, _, x, y) => {
+                    current = Some((x, y));
+                }
+                PathCmd::Close => current = start,
+            }
+        }
+        current
+    }
+
+    /// An ellipse inscribed in the given box.
+    pub(crate) fn ellipse(x: f64, y: f64, w: f64, h: f64) -> Self {
+        let mut path = Self::default();
+        path.move_to(x + w, y + h / 2.0);
+        path.arc_to(w / 2.0, h / 2.0, 0.0, 360.0);
+        path.close();
+        path
+    }
+}
+
+/// A pattern drawn by [`Paint::Pattern`], on an 8×8 point cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PatternKind {
+    /// Dots covering roughly the given fraction of the cell.
+    Dots(f64),
+    /// Lines of the given width, in points.
+    Horizontal(f64),
+    Vertical(f64),
+    /// Lines going down to the right.
+    DownwardDiagonal(f64),
+    /// Lines going up to the right.
+    UpwardDiagonal(f64),
+    Cross(f64),
+    DiagonalCross(f64),
+}
+
+/// How an area is painted.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Paint {
+    Solid(RgbColor),
+    /// A linear gradient along the line from `start` to `end`, with
+    /// `(position, color)` stops in increasing order.
+    LinearGradient {
+        start: (f64, f64),
+        end: (f64, f64),
+        stops: Vec<(f64, RgbColor)>,
+    },
+    Pattern {
+        kind: PatternKind,
+        foreground: RgbColor,
+        background: RgbColor,
+    },
+}
+
+/// How a path outline is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Stroke {
+    pub color: RgbColor,
+    pub width: f64,
+    /// Alternating dash and gap lengths, in points; empty for solid.
+    pub dash: Vec<f64>,
+    pub round_cap: bool,
+    pub round_join: bool,
+}
+
+/// A run of text on one line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TextItem {
+    /// Left end of the baseline.
+    pub x: f64,
+    pub y: f64,
+    pub face: FontFace,
+    /// The typeface asked for, with theme fonts resolved; `face` is the
+    /// standard face the text was measured with.
+    pub font: Option<String>,
+    /// Font size in points.
+    pub size: f64,
+    pub color: RgbColor,
+    pub text: String,
+}
+
+/// An image used by a scene, as stored in the package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SceneImage {
+    pub data: Vec<u8>,
+    pub content_type: String,
+}
+
+/// A drawing item.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Item {
+    Fill(Path, Paint),
+    Stroke(Path, Stroke),
+    Text(TextItem),
+    /// Image `index` of [`Scene::images`] stretched over a box.
+    Image {
+        index: usize,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    /// Items drawn with a transform and, optionally, clipped to a path
+    /// (given in the group's own coordinates).
+    Group {
+        transform: Matrix,
+        clip: Option<Path>,
+        items: Vec<Item>,
+    },
+}
+
+/// A slide laid out for drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Scene {
+    /// Page width in points.
+    pub width: f64,
+    /// Page height in points.
+    pub height: f64,
+    pub items: Vec<Item>,
+    pub images: Vec<SceneImage>,
+}
@@ -0,0 +1,370 @@
+//! The shapes of a slide one by one, with what they inherit resolved.
+//!
+//! [`build_shapes`] is the counterpart of [`build_scene`](super::build_scene)
+//! for exporters that keep each shape as an element of its own: fills,
+//! outlines and pictures are drawing items in the shape's box, while text,
+//! tables and charts are left for the exporter to write out.
+
+use crate::chart::Chart;
+use crate::dml::effect::{ShadowFormat, ShadowType};
+use crate::error::PptxResult;
+use crate::presentation::Presentation;
+use crate::shapes::graphfrm::GraphicFrame;
+use crate::shapes::Shape;
+use crate::slide::SlideRef;
+use crate::table::Table;
+use crate::text::font::RgbColor;
+use crate::text::layout::TextDefaults;
+use crate::text::TextFrame;
+use crate::theme::colors::{Colors, BLACK};
+use crate::units::ShapeId;
+
+use super::build::{
+    child_space, flips, uses_table_style, Bounds, Builder, Drawn, PartCtx, SlideParts,
+};
+use super::{emu_to_pt, page_size, Item, Matrix, Paint, SceneImage, Stroke};
+
+/// The shapes of a slide, back to front.
+#[derive(Debug, Clone)]
+pub(crate) struct SlideShapes {
+    /// Page width in points.
+    pub width: f64,
+    /// Page height in points.
+    pub height: f64,
+    /// The slide background, covering the page.
+    pub background: Vec<Item>,
+    /// Shapes of the master and layout shown behind the slide, then the
+    /// slide's own shapes.
+    pub shapes: Vec<ShapeView>,
+    pub images: Vec<SceneImage>,
+    /// Theme colors, for resolving colors of text and charts.
+    pub colors: Colors,
+}
+
+/// A shape with its own box, placed in the slide or in its group.
+#[derive(Debug, Clone)]
+pub(crate) struct ShapeView {
+    pub name: String,
+    /// Position and size of the box, in points.
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Clockwise rotation about the center, in degrees.
+    pub rotation: f64,
+    /// Whether `graphics` are mirrored; text is not.
+    pub flip_h: bool,
+    pub flip_v: bool,
+    /// Fills, outlines and pictures, from `(0, 0)` to the box size.
+    pub graphics: Vec<Item>,
+    pub shadow: Option<Shadow>,
+    pub content: ShapeContent,
+}
+
+/// A shadow cast by a shape, in points.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Shadow {
+    pub color: RgbColor,
+    /// Opacity from 0.0 to 1.0.
+    pub opacity: f64,
+    pub blur: f64,
+    /// Offset of the shadow from the shape.
+    pub dx: f64,
+    pub dy: f64,
+    /// Whether the shadow falls inside the shape.
+    pub inner: bool,
+}
+
+/// What a shape holds besides its graphics.
+#[derive(Debug, Clone)]
+pub(crate) enum ShapeContent {
+    None,
+    Text(Box<TextView>),
+    Table(Box<TableView>),
+    Chart(Box<Chart>),
+    /// Child shapes, positioned in the group's child space, which
+    /// `transform` maps onto the group's box.
+    Group {
+        transform: Matrix,
+        shapes: Vec<ShapeView>,
+    },
+}
+
+/// A text frame with the formatting its text inherits.
+#[derive(Debug, Clone)]
+pub(crate) struct TextView {
+    pub frame: TextFrame,
+    pub defaults: TextDefaults,
+    /// Left, top, right and bottom insets, in points.
+    pub insets: [f64; 4],
+}
+
+/// A table with the look of each cell resolved.
+#[derive(Debug, Clone)]
+pub(crate) struct TableView {
+    pub table: Table,
+    /// Column widths in points.
+    pub columns: Vec<f64>,
+    /// Row heights in points.
+    pub rows: Vec<f64>,
+    /// The look of each cell, row by row like the table's cells.
+    pub cells: Vec<Vec<CellView>>,
+}
+
+/// How a table cell is drawn.
+#[derive(Debug, Clone)]
+pub(crate) struct CellView {
+    pub fill: Option<Paint>,
+    /// Left, right, top and bottom borders.
+    pub borders: [Option<Stroke>; 4],
+    pub text: TextDefaults,
+    /// Left, top, right and bottom insets, in points.
+    pub insets: [f64; 4],
+}
+
+/// Resolve the shapes of `slide` of `prs`.
+///
+/// # Errors
+///
+/// Returns an error if the slide, its layout or its master cannot be read.
+pub(crate) fn build_shapes(prs: &Presentation, slide: &SlideRef) -> PptxResult<SlideShapes> {
+    let (width, height) = page_size(prs)?;
+    let parts = SlideParts::new(prs, slide)?;
+    let mut builder = Builder::new(prs, &parts)?;
+
+    let background = builder.background(&parts.all(), width, height)?;
+    let mut shapes = Vec::new();
+    for (ctx, slide) in parts.drawn() {
+        for shape in &ctx.tree.shapes {
+            if slide || !shape.is_placeholder() {
+                shapes.push(builder.view(ctx, shape, slide)?);
+            }
+        }
+    }
+    Ok(SlideShapes {
+        width,
+        height,
+        background,
+        shapes,
+        images: builder.images,
+        colors: builder.colors,
+    })
+}
+
+/// The box of a text frame on a slide and what its text inherits.
+#[derive(Debug, Clone)]
+pub(crate) struct TextBoxView {
+    pub shape_id: ShapeId,
+    /// Position and size of the box, in points; group members are placed
+    /// in their group's child space.
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub defaults: TextDefaults,
+}
+
+/// The text frames of the shapes on `slide` itself, including those in
+/// groups, with the boxes and formatting they inherit.
+///
+/// # Errors
+///
+/// Returns an error if the slide, its layout or its master cannot be read.
+pub(crate) fn slide_text_boxes(
+    prs: &Presentation,
+    slide: &SlideRef,
+) -> PptxResult<Vec<TextBoxView>> {
+    let parts = SlideParts::new(prs, slide)?;
+    let mut builder = Builder::new(prs, &parts)?;
+    let mut boxes = Vec::new();
+    for (ctx, slide) in parts.drawn() {
+        if slide {
+            for shape in &ctx.tree.shapes {
+                builder.text_boxes(ctx, shape, &mut boxes)?;
+            }
+        }
+    }
+    Ok(boxes)
+}
+
+impl Builder<'_, '_> {
+    fn text_boxes(
+        &mut self,
+        ctx: &PartCtx<'_>,
+        shape: &Shape,
+        boxes: &mut Vec<TextBoxView>,
+    ) -> PptxResult<()> {
+        match shape {
+            Shape::AutoShape(s) => {
+                let drawn = self.draw_autoshape(ctx, s, true)?;
+                if let Some((defaults, _)) = drawn.text {
+                    boxes.push(TextBoxView {
+                        shape_id: s.shape_id,
+                        x: drawn.bounds.x,
+                        y: drawn.bounds.y,
+                        width: drawn.bounds.w,
+                        height: drawn.bounds.h,
+                        defaults,
+                    });
+                }
+            }
+            Shape::GroupShape(g) => {
+                for child in &g.shapes {
+                    self.text_boxes(ctx, child, boxes)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn view(&mut self, ctx: &PartCtx<'_>, shape: &Shape, slide: bool) -> PptxResult<ShapeView> {
+        let (drawn, content) = match shape {
+            Shape::AutoShape(s) => {
+                let mut drawn = self.draw_autoshape(ctx, s, slide)?;
+                let content = match (drawn.text.take(), &s.text_frame) {
+                    (Some((defaults, insets)), Some(frame)) => {
+                        ShapeContent::Text(Box::new(TextView {
+                            frame: frame.clone(),
+                            defaults,
+                            insets,
+                        }))
+                    }
+                    _ => ShapeContent::None,
+                };
+                (drawn, content)
+            }
+            Shape::Picture(p) => (self.draw_picture(ctx, p)?, ShapeContent::None),
+            Shape::Connector(c) => (self.draw_connector(ctx, c)?, ShapeContent::None),
+            Shape::OleObject(o) => (self.draw_ole_object(ctx, o)?, ShapeContent::None),
+            Shape::GraphicFrame(g) => {
+                let content = self.frame_content(ctx, g)?;
+                (
+                    Drawn::empty(Bounds::new(g.left, g.top, g.width, g.height), g.rotation),
+                    content,
+                )
+            }
+            Shape::GroupShape(g) => {
+                let xml = ctx.shape_xml(g.shape_id)?;
+                let bounds = Bounds::new(g.left, g.top, g.width, g.height);
+                let (flip_h, flip_v) = flips(&xml);
+                let transform = child_space(&xml, bounds)
+                    .then(Matrix::translate(-bounds.x, -bounds.y))
+                    .then(Matrix::rotate_about(
+                        0.0,
+                        bounds.w / 2.0,
+                        bounds.h / 2.0,
+                        flip_h,
+                        flip_v,
+                    ));
+                let mut shapes = Vec::new();
+                for child in &g.shapes {
+                    shapes.push(self.view(ctx, child, slide)?);
+                }
+                (
+                    Drawn::empty(bounds, g.rotation),
+                    ShapeContent::Group { transform, shapes },
+                )
+            }
+        };
+        let shadow = match shape {
+            Shape::AutoShape(s) => s.shadow.as_ref(),
+            Shape::Picture(p) => p.shadow.as_ref(),
+            _ => None,
+        };
+        Ok(ShapeView {
+            name: shape.name().to_string(),
+            x: drawn.bounds.x,
+            y: drawn.bounds.y,
+            width: drawn.bounds.w,
+            height: drawn.bounds.h,
+            rotation: drawn.rotation,
+            flip_h: drawn.flip_h,
+            flip_v: drawn.flip_v,
+            graphics: drawn.graphics,
+            shadow: shadow.map(|s| self.shadow(s)),
+            content,
+        })
+    }
+
+    /// The table or chart a graphic frame holds.
+    fn frame_content(&self, ctx: &PartCtx<'_>, g: &GraphicFrame) -> PptxResult<ShapeContent> {
+        if let Some(table) = &g.table {
+            return Ok(ShapeContent::Table(Box::new(self.table_view(table))));
+        }
+        let Some(r_id) = g.chart_r_id.as_deref() else {
+            return Ok(ShapeContent::None);
+        };
+        let Some(rel) = ctx.part.rels.get(r_id).filter(|rel| !rel.is_external) else {
+            return Ok(ShapeContent::None);
+        };
+        let partname = rel.target_partname(ctx.part.partname.base_uri())?;
+        match self.prs.package().part(&partname) {
+            Some(part) => Ok(ShapeContent::Chart(Box::new(Chart::from_xml(&part.blob)?))),
+            None => Ok(ShapeContent::None),
+        }
+    }
+
+    fn table_view(&self, table: &Table) -> TableView {
+        let columns: Vec<f64> = table.columns.iter().map(|c| emu_to_pt(c.width.0)).collect();
+        let rows: Vec<f64> = table.rows.iter().map(|r| emu_to_pt(r.height.0)).collect();
+        let span = |sizes: &[f64], from: usize, count: u32| -> f64 {
+            sizes.iter().skip(from).take(count.max(1) as usize).sum()
+        };
+        let styled = uses_table_style(table);
+        let cells = table
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(ri, row)| {
+                row.cells
+                    .iter()
+                    .enumerate()
+                    .map(|(ci, cell)| {
+                        let look = self.cell_look(table, ri, cell, styled);
+                        let fill = match &cell.fill {
+                            Some(fill) => {
+                                let bounds = Bounds {
+                                    x: 0.0,
+                                    y: 0.0,
+                                    w: span(&columns, ci, cell.grid_span),
+                                    h: span(&rows, ri, cell.row_span),
+                                };
+                                self.paint(fill, bounds)
+                            }
+                            None => look.style_fill.map(Paint::Solid),
+                        };
+                        CellView {
+                            fill,
+                            borders: look.borders,
+                            text: look.text,
+                            insets: look.insets,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        TableView {
+            table: table.clone(),
+            columns,
+            rows,
+            cells,
+        }
+    }
+
+    fn shadow(&self, shadow: &ShadowFormat) -> Shadow {
+        let distance = shadow.distance.map_or(0.0, |d| emu_to_pt(d.0));
+        let (sin, cos) = shadow.direction.unwrap_or(0.0).to_radians().sin_cos();
+        Shadow {
+            color: shadow
+                .color
+                .as_ref()
+                .map_or(BLACK, |c| self.colors.resolve(c)),
+            opacity: shadow.opacity.unwrap_or(1.0),
+            blur: shadow.blur_radius.map_or(0.0, |b| emu_to_pt(b.0)),
+            dx: distance * cos,
+            dy: distance * sin,
+            inner: shadow.shadow_type == ShadowType::Inner,
+        }
+    }
+}
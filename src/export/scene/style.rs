@@ -0,0 +1,125 @@
+//! Parsing paragraph styles inherited from masters, layouts and list styles.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::enums::text::PpParagraphAlignment;
+use crate::error::{PptxError, PptxResult};
+use crate::shapes::parser::parse_color_from_xml;
+use crate::text::layout::ListStyle;
+use crate::theme::Colors;
+use crate::xml_util::splice::{find_element, find_start_tag, get_attr};
+use crate::xml_util::{attr_value, local_name_str, read_inner_xml};
+
+fn emu_attr_pt(e: &quick_xml::events::BytesStart<'_>, key: &[u8]) -> PptxResult<Option<f64>> {
+    Ok(attr_value(e, key)?
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|emu| emu / super::EMU_PER_PT))
+}
+
+/// Parse the level styles of a list style element such as `<a:lstStyle>`
+/// or a master's `<p:titleStyle>`.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(super) fn parse_list_style(xml: &str, colors: &Colors) -> PptxResult<ListStyle> {
+    let mut levels = ListStyle::default();
+    let mut reader = Reader::from_str(xml);
+    let mut level: Option<usize> = None;
+    loop {
+        let event = reader.read_event().map_err(PptxError::Xml)?;
+        let (e, is_empty) = match event {
+            Event::Start(ref e) => (e.clone(), false),
+            Event::Empty(ref e) => (e.clone(), true),
+            Event::End(ref e) => {
+                if local_name_str(e.name().as_ref()).ends_with("pPr") {
+                    level = None;
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        let qname = e.name();
+        let local = local_name_str(qname.as_ref());
+        if let Some(n) = local
+            .strip_prefix("lvl")
+            .and_then(|rest| rest.strip_suffix("pPr"))
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|n| (1..=9).contains(n))
+        {
+            let style = &mut levels[n - 1];
+            style.alignment = attr_value(&e, b"algn")?.and_then(|a| alignment(&a));
+            style.margin_left = emu_attr_pt(&e, b"marL")?;
+            style.indent = emu_attr_pt(&e, b"indent")?;
+            level = (!is_empty).then_some(n - 1);
+            continue;
+        }
+        let Some(index) = level else {
+            continue;
+        };
+        let style = &mut levels[index];
+        match local {
+            "buNone" => style.bullet = Some(None),
+            "buChar" => {
+                style.bullet = attr_value(&e, b"char")?
+                    .and_then(|c| c.chars().next())
+                    .map(Some);
+            }
+            "defRPr" => {
+                style.size = attr_value(&e, b"sz")?
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .map(|s| s / 100.0);
+                style.bold = attr_value(&e, b"b")?.map(|b| b == "1" || b == "true");
+                if !is_empty {
+                    let inner = read_inner_xml(&mut reader, "defRPr")
+                        .map_err(|e| PptxError::InvalidXml(format!("defRPr: {e}")))?;
+                    let inner_str = std::str::from_utf8(&inner)?;
+                    if let Some(fill) = find_element(inner_str, "a:solidFill", 0) {
+                        let color = parse_color_from_xml(&inner[fill])?;
+                        style.color = color.map(|c| colors.resolve(&c));
+                    }
+                    style.font = find_start_tag(inner_str, "a:latin", 0)
+                        .and_then(|tag| get_attr(&inner_str[tag], "typeface"))
+                        .map(str::to_string);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(levels)
+}
+
+fn alignment(value: &str) -> Option<PpParagraphAlignment> {
+    match value {
+        "l" => Some(PpParagraphAlignment::Left),
+        "ctr" => Some(PpParagraphAlignment::Center),
+        "r" => Some(PpParagraphAlignment::Right),
+        "just" => Some(PpParagraphAlignment::Justify),
+        "dist" => Some(PpParagraphAlignment::Distribute),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::font::RgbColor;
+
+    #[test]
+    fn parses_levels() {
+        let xml = r#"<p:bodyStyle><a:lvl1pPr marL="342900" indent="-342900" algn="l"><a:buChar char="•"/><a:defRPr sz="3200"><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill><a:latin typeface="+mn-lt"/></a:defRPr></a:lvl1pPr><a:lvl2pPr algn="ctr"><a:buNone/><a:defRPr sz="2800" b="1"/></a:lvl2pPr></p:bodyStyle>"#;
+        let levels = parse_list_style(xml, &Colors::default()).unwrap();
+        assert_eq!(levels[0].size, Some(32.0));
+        assert_eq!(levels[0].bullet, Some(Some('•')));
+        assert_eq!(levels[0].margin_left, Some(27.0));
+        assert_eq!(levels[0].indent, Some(-27.0));
+        assert_eq!(levels[0].color, Some(RgbColor::new(255, 0, 0)));
+        assert_eq!(levels[0].font.as_deref(), Some("+mn-lt"));
+        assert_eq!(levels[1].alignment, Some(PpParagraphAlignment::Center));
+        assert_eq!(levels[1].bullet, Some(None));
+        assert_eq!(levels[1].bold, Some(true));
+        assert_eq!(levels[2], ListStyle::default()[2]);
+    }
+}
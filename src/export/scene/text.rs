@@ -0,0 +1,216 @@
+//! Placement of text frames on a slide.
+
+use crate::enums::text::{MsoVerticalAnchor, PpParagraphAlignment};
+use crate::text::layout::{break_lines, Line, Piece, TextDefaults};
+use crate::text::TextFrame;
+use crate::theme::Colors;
+
+use super::{Item, Paint, Path, TextItem};
+
+/// A box to lay text out in, in points, with its insets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct TextBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Left, top, right and bottom insets.
+    pub insets: [f64; 4],
+}
+
+/// Lay out the paragraphs of `tf` in `area`, returning text items and the
+/// rectangles of underlines and strike-throughs.
+pub(super) fn layout_text(
+    tf: &TextFrame,
+    area: TextBox,
+    defaults: &TextDefaults,
+    colors: &Colors,
+) -> Vec<Item> {
+    let [left, top, right, bottom] = area.insets;
+    let inner_width = (area.width - left - right).max(0.0);
+    let lines = break_lines(tf, inner_width, defaults, colors, None);
+
+    let total: f64 = lines.iter().map(Line::height).sum();
+    let inner_height = area.height - top - bottom;
+    let anchor = tf.vertical_anchor.unwrap_or(defaults.anchor);
+    let mut y = area.y
+        + top
+        + match anchor {
+            MsoVerticalAnchor::Top => 0.0,
+            MsoVerticalAnchor::Middle => (inner_height - total) / 2.0,
+            MsoVerticalAnchor::Bottom => inner_height - total,
+        };
+
+    let mut items = Vec::new();
+    for line in &lines {
+        y += line.before;
+        let baseline = y + line.size * (1.2 * line.spacing - 0.25);
+        let room = inner_width - line.indent;
+        let offset = match line.alignment {
+            PpParagraphAlignment::Center => (room - line.width(None)) / 2.0,
+            PpParagraphAlignment::Right => room - line.width(None),
+            _ => 0.0,
+        };
+        let mut x = area.x + left + line.indent + offset;
+        if let Some((bullet, indent)) = &line.bullet {
+            emit(
+                &mut items,
+                bullet,
+                area.x + left + indent + offset,
+                baseline,
+            );
+        }
+        for piece in &line.pieces {
+            let width = piece.width(None);
+            emit(&mut items, piece, x, baseline);
+            let rule = |offset: f64| {
+                let thickness = (piece.size * 0.05).max(0.5);
+                Item::Fill(
+                    Path::rect(x, baseline + piece.shift + offset, width, thickness),
+                    Paint::Solid(piece.color),
+                )
+            };
+            if piece.underline {
+                items.push(rule(piece.size * 0.1));
+            }
+            if piece.strike {
+                items.push(rule(-piece.size * 0.3));
+            }
+            x += width;
+        }
+        y += line.size * 1.2 * line.spacing + line.after;
+    }
+    items
+}
+
+fn emit(items: &mut Vec<Item>, piece: &Piece, x: f64, baseline: f64) {
+    let text = piece.text.trim_end();
+    if text.is_empty() {
+        return;
+    }
+    items.push(Item::Text(TextItem {
+        x,
+        y: baseline + piece.shift,
+        face: piece.face,
+        font: piece.font.clone(),
+        size: piece.size,
+        color: piece.color,
+        text: text.to_string(),
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::bullet::BulletFormat;
+    use crate::units::Emu;
+
+    fn texts(items: &[Item]) -> Vec<(String, f64, f64)> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Text(t) => Some((t.text.clone(), t.x, t.y)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn wraps_lines_at_the_box_width() {
+        let mut tf = TextFrame::new();
+        tf.set_text("aaaa bbbb");
+        let area = TextBox {
+            x: 0.0,
+            y: 0.0,
+            width: 30.0,
+            height: 100.0,
+            insets: [0.0; 4],
+        };
+        let defaults = TextDefaults {
+            size: 10.0,
+            ..TextDefaults::default()
+        };
+        let items = layout_text(&tf, area, &defaults, &Colors::default());
+        let lines = texts(&items);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].0, "aaaa");
+        assert_eq!(lines[1].0, "bbbb");
+        assert!((lines[1].2 - lines[0].2 - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn centers_text_both_ways() {
+        let mut tf = TextFrame::new();
+        tf.set_text("ab");
+        tf.paragraphs_mut()[0].alignment = Some(PpParagraphAlignment::Center);
+        tf.vertical_anchor = Some(MsoVerticalAnchor::Middle);
+        let area = TextBox {
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 100.0,
+            insets: [0.0; 4],
+        };
+        let defaults = TextDefaults {
+            size: 10.0,
+            ..TextDefaults::default()
+        };
+        let lines = texts(&layout_text(&tf, area, &defaults, &Colors::default()));
+        // "ab" is 11.12pt wide in Helvetica at 10pt
+        assert!((lines[0].1 - (100.0 - 11.12) / 2.0).abs() < 1e-9);
+        assert!((lines[0].2 - (44.0 + 9.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hangs_first_line_from_paragraph_indents() {
+        let mut tf = TextFrame::new();
+        tf.set_text("aaaa bbbb");
+        let para = &mut tf.paragraphs_mut()[0];
+        para.set_hanging_indent(Emu(254_000), Emu(127_000));
+        para.line_spacing_pts = Some(24.0);
+        let area = TextBox {
+            x: 0.0,
+            y: 0.0,
+            width: 50.0,
+            height: 100.0,
+            insets: [0.0; 4],
+        };
+        let defaults = TextDefaults {
+            size: 10.0,
+            ..TextDefaults::default()
+        };
+        let lines = texts(&layout_text(&tf, area, &defaults, &Colors::default()));
+        // The first line hangs 10pt left of the 20pt margin
+        assert_eq!(lines.len(), 2);
+        assert!((lines[0].1 - 10.0).abs() < 1e-9);
+        assert!((lines[1].1 - 20.0).abs() < 1e-9);
+        assert!((lines[1].2 - lines[0].2 - 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn numbers_auto_numbered_paragraphs() {
+        let mut tf = TextFrame::new();
+        tf.set_text("one\ntwo");
+        for para in tf.paragraphs_mut() {
+            para.set_bullet(BulletFormat::auto_numbered(
+                crate::enums::text::MsoAutoNumberScheme::RomanLcPeriod,
+            ));
+            para.set_hanging_indent(Emu(254_000), Emu(254_000));
+        }
+        let area = TextBox {
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 100.0,
+            insets: [0.0; 4],
+        };
+        let lines = texts(&layout_text(
+            &tf,
+            area,
+            &TextDefaults::default(),
+            &Colors::default(),
+        ));
+        let labels: Vec<_> = lines.iter().map(|(text, ..)| text.as_str()).collect();
+        assert_eq!(labels, ["i.", "one", "ii.", "two"]);
+    }
+}
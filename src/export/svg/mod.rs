@@ -0,0 +1,195 @@
+//! PPTX to SVG export.
+//!
+//! Produces one standalone SVG document per slide, drawn from the same
+//! slide layout as the PDF export: shape geometry becomes paths, gradient
+//! and pattern fills become `<defs>`, each line of text is a `<text>`
+//! element with a `<tspan>` per run, pictures are embedded as data URIs
+//! clipped to their crop and outline, and group shapes are `<g>` elements
+//! carrying the group's transform.
+
+pub(crate) mod writer;
+
+use crate::error::{PptxError, PptxResult};
+use crate::export::html::utils::base64_encode;
+use crate::export::scene::{build_scene, Scene};
+use crate::presentation::Presentation;
+use crate::slide::SlideRef;
+
+use writer::SvgWriter;
+
+/// Converts the slides of a `Presentation` into SVG documents.
+pub struct SvgExporter<'a> {
+    prs: &'a Presentation,
+}
+
+impl<'a> SvgExporter<'a> {
+    /// Create a new exporter for the given presentation.
+    #[must_use]
+    pub const fn new(prs: &'a Presentation) -> Self {
+        Self { prs }
+    }
+
+    /// Export every slide as an SVG document, in slide order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if slide XML cannot be read or parsed.
+    pub fn export(&self) -> PptxResult<Vec<String>> {
+        self.prs
+            .slides()?
+            .iter()
+            .map(|slide| self.export_slide(slide))
+            .collect()
+    }
+
+    /// Export one slide as an SVG document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide, its layout or its master cannot be
+    /// read or parsed.
+    pub fn export_slide(&self, slide: &SlideRef) -> PptxResult<String> {
+        let scene = build_scene(self.prs, slide)?;
+        document(&scene).map_err(|e| PptxError::InvalidXml(e.to_string()))
+    }
+}
+
+/// Standalone function to export the slides of a presentation to SVG.
+///
+/// # Errors
+///
+/// Returns an error if slide XML cannot be read or parsed.
+pub fn export_to_svg(prs: &Presentation) -> PptxResult<Vec<String>> {
+    SvgExporter::new(prs).export()
+}
+
+fn document(scene: &Scene) -> Result<String, std::fmt::Error> {
+    let images: Vec<String> = scene
+        .images
+        .iter()
+        .map(|image| {
+            format!(
+                "data:{};base64,{}",
+                image.content_type,
+                base64_encode(&image.data)
+            )
+        })
+        .collect();
+    let mut next_id = 0;
+    let mut svg = String::new();
+    SvgWriter::new(&mut next_id, &images).write_document(
+        &mut svg,
+        (scene.width, scene.height),
+        &scene.items,
+    )?;
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::Image;
+    use crate::opc::constants::relationship_type as RT;
+
+    fn blank_slide(prs: &mut Presentation) -> SlideRef {
+        let layouts = prs.slide_layouts().unwrap();
+        prs.add_slide(&layouts[6]).unwrap()
+    }
+
+    fn append_shapes(prs: &mut Presentation, slide_ref: &SlideRef, shapes: &str) {
+        let xml = String::from_utf8(prs.slide_xml(slide_ref).unwrap().to_vec()).unwrap();
+        *prs.slide_xml_mut(slide_ref).unwrap() = xml
+            .replacen("</p:spTree>", &format!("{shapes}</p:spTree>"), 1)
+            .into_bytes();
+    }
+
+    fn sp(id: u32, xfrm: &str, fill: &str, body: &str) -> String {
+        format!(
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="Shape {id}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr>{xfrm}<a:prstGeom prst="ellipse"><a:avLst/></a:prstGeom>{fill}</p:spPr>{body}</p:sp>"#
+        )
+    }
+
+    const XFRM: &str = r#"<a:xfrm><a:off x="0" y="0"/><a:ext cx="1905000" cy="952500"/></a:xfrm>"#;
+
+    #[test]
+    fn export_one_document_per_slide() {
+        let mut prs = Presentation::new().unwrap();
+        blank_slide(&mut prs);
+        blank_slide(&mut prs);
+
+        let slides = export_to_svg(&prs).unwrap();
+        assert_eq!(slides.len(), 2);
+        for svg in &slides {
+            assert!(svg.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\""));
+            assert!(svg.contains("width=\"960\" height=\"720\" viewBox=\"0 0 720 540\">"));
+            assert!(svg.ends_with("</svg>\n"));
+        }
+        assert_eq!(prs.export_svg().unwrap(), slides);
+    }
+
+    #[test]
+    fn export_text_as_tspans_with_font_attributes() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let body = r#"<p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US" sz="2400" b="1"><a:solidFill><a:srgbClr val="112233"/></a:solidFill><a:latin typeface="Georgia"/></a:rPr><a:t>Big </a:t></a:r><a:r><a:rPr lang="en-US" sz="2400" i="1"/><a:t>and bold</a:t></a:r></a:p></p:txBody>"#;
+        append_shapes(&mut prs, &slide_ref, &sp(2, XFRM, "", body));
+
+        let svg = SvgExporter::new(&prs).export_slide(&slide_ref).unwrap();
+        assert_eq!(svg.matches("<text xml:space=\"preserve\">").count(), 1);
+        assert_eq!(svg.matches("<tspan").count(), 2);
+        assert!(svg.contains(
+            "font-family=\"'Georgia', serif\" font-size=\"24\" fill=\"#112233\" font-weight=\"bold\">Big</tspan>"
+        ));
+        assert!(svg.contains("font-style=\"italic\">and bold</tspan></text>"));
+    }
+
+    #[test]
+    fn export_fills_as_defs() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let gradient = r#"<a:gradFill><a:gsLst><a:gs pos="0"><a:srgbClr val="FF0000"/></a:gs><a:gs pos="100000"><a:srgbClr val="0000FF"/></a:gs></a:gsLst><a:lin ang="0"/></a:gradFill>"#;
+        let pattern = r#"<a:pattFill prst="cross"><a:fgClr><a:srgbClr val="000000"/></a:fgClr><a:bgClr><a:srgbClr val="FFFFFF"/></a:bgClr></a:pattFill>"#;
+        let shapes = [sp(2, XFRM, gradient, ""), sp(3, XFRM, pattern, "")].concat();
+        append_shapes(&mut prs, &slide_ref, &shapes);
+
+        let svg = SvgExporter::new(&prs).export_slide(&slide_ref).unwrap();
+        assert!(svg.contains("<defs><linearGradient id=\"grad1\" gradientUnits=\"userSpaceOnUse\""));
+        assert!(svg.contains("<stop offset=\"1\" stop-color=\"#0000FF\"/>"));
+        assert!(svg.contains("<pattern id=\"pattern2\" width=\"8\" height=\"8\""));
+        assert!(svg.contains("fill=\"url(#grad1)\""));
+        assert!(svg.contains("fill=\"url(#pattern2)\""));
+        // The ellipse is drawn from Bézier curves
+        assert!(svg.contains("<path d=\"M150 37.5C"));
+    }
+
+    #[test]
+    fn export_cropped_pictures_and_groups() {
+        let mut prs = Presentation::new().unwrap();
+        let slide_ref = blank_slide(&mut prs);
+        let png = crate::render::SlideImage {
+            width: 1,
+            height: 1,
+            pixels: vec![0, 0, 255, 255],
+        }
+        .to_png();
+        let image = Image::from_bytes(png, "image/png");
+        let (image_partname, _) = prs.package_mut().or_add_image_part(&image).unwrap();
+        let slide_part = prs.package_mut().part_mut(&slide_ref.partname).unwrap();
+        let target = image_partname.relative_ref(slide_part.partname.base_uri());
+        let r_id = slide_part.rels.or_add(RT::IMAGE, &target, false);
+        let group = format!(
+            r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="2" name="Group"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm><a:off x="952500" y="952500"/><a:ext cx="952500" cy="952500"/><a:chOff x="0" y="0"/><a:chExt cx="1905000" cy="1905000"/></a:xfrm></p:grpSpPr><p:pic><p:nvPicPr><p:cNvPr id="3" name="Picture"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="{r_id}"/><a:srcRect l="50000"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="952500" cy="952500"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic></p:grpSp>"#
+        );
+        append_shapes(&mut prs, &slide_ref, &group);
+
+        let svg = SvgExporter::new(&prs).export_slide(&slide_ref).unwrap();
+        // The group halves its child space and moves it to (75, 75)
+        assert!(svg.contains("<g transform=\"matrix(0.5 0 0 0.5 75 75)\">"));
+        assert!(
+            svg.contains("<clipPath id=\"clip1\"><path d=\"M0 0L75 0L75 75L0 75Z\"/></clipPath>")
+        );
+        // Half the image is cropped away, so it is drawn twice as wide
+        assert!(svg.contains("<image href=\"data:image/png;base64,iVBORw0KGgo"));
+        assert!(svg.contains("x=\"-75\" y=\"0\" width=\"150\" height=\"75\""));
+    }
+}
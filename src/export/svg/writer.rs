@@ -0,0 +1,494 @@
+//! SVG elements for drawing items: shape geometry, fills, text and pictures.
+
+use std::fmt::{self, Write};
+
+use crate::export::scene::{
+    FontFamily, Item, Matrix, Paint, Path, PathCmd, PatternKind, Stroke, TextItem,
+};
+use crate::text::font::RgbColor;
+use crate::xml_util::{write_xml_escaped, xml_escape};
+
+/// Writes drawing items as SVG elements, collecting the gradients,
+/// patterns and clip paths they use as `<defs>`.
+pub(crate) struct SvgWriter<'a> {
+    body: String,
+    defs: String,
+    /// Counter for element IDs, shared by the whole document so that IDs
+    /// stay unique across inline SVGs.
+    next_id: &'a mut usize,
+    /// `src` of each image of the slide.
+    images: &'a [String],
+}
+
+impl<'a> SvgWriter<'a> {
+    pub(crate) fn new(next_id: &'a mut usize, images: &'a [String]) -> Self {
+        Self {
+            body: String::new(),
+            defs: String::new(),
+            next_id,
+            images,
+        }
+    }
+
+    /// Write an `<svg>` element showing `items` in a box of `width` ×
+    /// `height` points, stretched to the box it is placed in.
+    pub(crate) fn write_svg(
+        mut self,
+        w: &mut String,
+        class: &str,
+        size: (f64, f64),
+        items: &[Item],
+    ) -> fmt::Result {
+        self.items(items)?;
+        write!(w, "<svg class=\"{class}\" ")?;
+        self.finish(w, size, " preserveAspectRatio=\"none\"")
+    }
+
+    /// Write a standalone SVG document showing `items` on a page of
+    /// `width` × `height` points.
+    pub(crate) fn write_document(
+        mut self,
+        w: &mut String,
+        size: (f64, f64),
+        items: &[Item],
+    ) -> fmt::Result {
+        self.items(items)?;
+        w.write_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg ")?;
+        self.finish(w, size, "")?;
+        w.write_char('\n')
+    }
+
+    /// The rest of the `<svg>` element, once the items have been written.
+    fn finish(self, w: &mut String, (width, height): (f64, f64), attrs: &str) -> fmt::Result {
+        write!(
+            w,
+            "xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\"{attrs}>",
+            num(pt_to_px(width)),
+            num(pt_to_px(height)),
+            num(width),
+            num(height),
+        )?;
+        if !self.defs.is_empty() {
+            write!(w, "<defs>{}</defs>", self.defs)?;
+        }
+        w.push_str(&self.body);
+        w.write_str("</svg>")
+    }
+
+    fn id(&mut self, prefix: &str) -> String {
+        *self.next_id += 1;
+        format!("{prefix}{}", self.next_id)
+    }
+
+    fn items(&mut self, items: &[Item]) -> fmt::Result {
+        let mut i = 0;
+        while i < items.len() {
+            if let Item::Text(first) = &items[i] {
+                let end = line_end(items, i, first);
+                self.line(&items[i..end])?;
+                i = end;
+            } else {
+                self.item(&items[i])?;
+                i += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// One `<text>` element for the runs of a line, followed by the
+    /// underlines and strike-throughs drawn between them.
+    fn line(&mut self, items: &[Item]) -> fmt::Result {
+        self.body.write_str("<text xml:space=\"preserve\">")?;
+        for item in items {
+            if let Item::Text(text) = item {
+                write_tspan(&mut self.body, text)?;
+            }
+        }
+        self.body.write_str("</text>\n")?;
+        for item in items {
+            if !matches!(item, Item::Text(_)) {
+                self.item(item)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn item(&mut self, item: &Item) -> fmt::Result {
+        match item {
+            Item::Fill(path, paint) => {
+                let fill = self.paint(paint)?;
+                writeln!(
+                    self.body,
+                    "<path d=\"{}\" fill=\"{fill}\"/>",
+                    path_data(path)
+                )
+            }
+            Item::Stroke(path, stroke) => {
+                write!(self.body, "<path d=\"{}\" fill=\"none\"", path_data(path))?;
+                write_stroke(&mut self.body, stroke)?;
+                self.body.write_str("/>\n")
+            }
+            Item::Text(_) => self.line(std::slice::from_ref(item)),
+            Item::Image {
+                index,
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let Some(src) = self.images.get(*index) else {
+                    return Ok(());
+                };
+                writeln!(
+                    self.body,
+                    "<image href=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                     preserveAspectRatio=\"none\"/>",
+                    xml_escape(src),
+                    num(*x),
+                    num(*y),
+                    num(*width),
+                    num(*height),
+                )
+            }
+            Item::Group {
+                transform,
+                clip,
+                items,
+            } => {
+                self.body.write_str("<g")?;
+                if !transform.is_identity() {
+                    write!(self.body, " transform=\"{}\"", matrix(*transform))?;
+                }
+                if let Some(clip) = clip {
+                    let id = self.id("clip");
+                    write!(
+                        self.defs,
+                        "<clipPath id=\"{id}\"><path d=\"{}\"/></clipPath>",
+                        path_data(clip)
+                    )?;
+                    write!(self.body, " clip-path=\"url(#{id})\"")?;
+                }
+                self.body.write_str(">\n")?;
+                self.items(items)?;
+                self.body.write_str("</g>\n")
+            }
+        }
+    }
+
+    /// The `fill` value for `paint`, adding the definitions it needs.
+    fn paint(&mut self, paint: &Paint) -> Result<String, fmt::Error> {
+        match paint {
+            Paint::Solid(color) => Ok(hex(*color)),
+            Paint::LinearGradient { start, end, stops } => {
+                let id = self.id("grad");
+                write!(
+                    self.defs,
+                    "<linearGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" \
+                     x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">",
+                    num(start.0),
+                    num(start.1),
+                    num(end.0),
+                    num(end.1),
+                )?;
+                for (offset, color) in stops {
+                    write!(
+                        self.defs,
+                        "<stop offset=\"{}\" stop-color=\"{}\"/>",
+                        num(*offset),
+                        hex(*color)
+                    )?;
+                }
+                self.defs.write_str("</linearGradient>")?;
+                Ok(format!("url(#{id})"))
+            }
+            Paint::Pattern {
+                kind,
+                foreground,
+                background,
+            } => {
+                let id = self.id("pattern");
+                write!(
+                    self.defs,
+                    "<pattern id=\"{id}\" width=\"8\" height=\"8\" patternUnits=\"userSpaceOnUse\">\
+                     <rect width=\"8\" height=\"8\" fill=\"{}\"/>",
+                    hex(*background)
+                )?;
+                write_pattern_cell(&mut self.defs, *kind, *foreground)?;
+                self.defs.write_str("</pattern>")?;
+                Ok(format!("url(#{id})"))
+            }
+        }
+    }
+}
+
+/// The foreground of an 8×8 pattern cell, drawn like the PDF export.
+fn write_pattern_cell(w: &mut String, kind: PatternKind, color: RgbColor) -> fmt::Result {
+    let color = hex(color);
+    let lines = |w: &mut String, width: f64, lines: &[(f64, f64, f64, f64)]| {
+        write!(
+            w,
+            "<path stroke=\"{color}\" stroke-width=\"{}\" stroke-linecap=\"square\" d=\"",
+            num(width)
+        )?;
+        for (x1, y1, x2, y2) in lines {
+            write!(w, "M{} {}L{} {}", num(*x1), num(*y1), num(*x2), num(*y2))?;
+        }
+        w.write_str("\"/>")
+    };
+    // Diagonals continue across cells, so the corners get the ends of the
+    // neighboring cells' lines
+    let down = [
+        (0.0, 0.0, 8.0, 8.0),
+        (-1.0, 7.0, 1.0, 9.0),
+        (7.0, -1.0, 9.0, 1.0),
+    ];
+    let up = [
+        (0.0, 8.0, 8.0, 0.0),
+        (-1.0, 1.0, 1.0, -1.0),
+        (7.0, 9.0, 9.0, 7.0),
+    ];
+    match kind {
+        PatternKind::Dots(coverage) => {
+            let side = 4.0 * coverage.clamp(0.0, 1.0).sqrt();
+            let offset = (4.0 - side) / 2.0;
+            for (x, y) in [(0.0, 0.0), (4.0, 0.0), (0.0, 4.0), (4.0, 4.0)] {
+                write!(
+                    w,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{color}\"/>",
+                    num(x + offset),
+                    num(y + offset),
+                    num(side),
+                    num(side)
+                )?;
+            }
+            Ok(())
+        }
+        PatternKind::Horizontal(width) => lines(w, width, &[(0.0, 4.0, 8.0, 4.0)]),
+        PatternKind::Vertical(width) => lines(w, width, &[(4.0, 0.0, 4.0, 8.0)]),
+        PatternKind::DownwardDiagonal(width) => lines(w, width, &down),
+        PatternKind::UpwardDiagonal(width) => lines(w, width, &up),
+        PatternKind::Cross(width) => lines(w, width, &[(0.0, 4.0, 8.0, 4.0), (4.0, 0.0, 4.0, 8.0)]),
+        PatternKind::DiagonalCross(width) => {
+            lines(w, width, &down)?;
+            lines(w, width, &up)
+        }
+    }
+}
+
+fn write_stroke(w: &mut String, stroke: &Stroke) -> fmt::Result {
+    write!(
+        w,
+        " stroke=\"{}\" stroke-width=\"{}\"",
+        hex(stroke.color),
+        num(stroke.width)
+    )?;
+    if !stroke.dash.is_empty() {
+        let dash: Vec<String> = stroke.dash.iter().map(|d| num(*d)).collect();
+        write!(w, " stroke-dasharray=\"{}\"", dash.join(" "))?;
+    }
+    if stroke.round_cap {
+        w.write_str(" stroke-linecap=\"round\"")?;
+    }
+    if stroke.round_join {
+        w.write_str(" stroke-linejoin=\"round\"")?;
+    }
+    Ok(())
+}
+
+/// The end of the line of text starting at `items[start]`: the text items
+/// that follow on the same baseline, with the rules drawn between them.
+fn line_end(items: &[Item], start: usize, first: &TextItem) -> usize {
+    let mut end = start + 1;
+    for (i, item) in items.iter().enumerate().skip(start + 1) {
+        match item {
+            // Superscripts and subscripts sit off the baseline by less than
+            // half the font size
+            Item::Text(text) if (text.y - first.y).abs() < first.size.max(text.size) / 2.0 => {
+                end = i + 1;
+            }
+            Item::Fill(..) => {}
+            _ => break,
+        }
+    }
+    end
+}
+
+fn write_tspan(w: &mut String, text: &TextItem) -> fmt::Result {
+    let generic = match text.face.family {
+        FontFamily::Sans => "sans-serif",
+        FontFamily::Serif => "serif",
+        FontFamily::Mono => "monospace",
+    };
+    write!(
+        w,
+        "<tspan x=\"{}\" y=\"{}\" font-family=\"",
+        num(text.x),
+        num(text.y)
+    )?;
+    if let Some(font) = &text.font {
+        write!(w, "'{}', ", xml_escape(font))?;
+    }
+    write!(
+        w,
+        "{generic}\" font-size=\"{}\" fill=\"{}\"",
+        num(text.size),
+        hex(text.color)
+    )?;
+    if text.face.bold {
+        w.write_str(" font-weight=\"bold\"")?;
+    }
+    if text.face.italic {
+        w.write_str(" font-style=\"italic\"")?;
+    }
+    w.write_char('>')?;
+    write_xml_escaped(w, &text.text)?;
+    w.write_str("</tspan>")
+}
+
+/// SVG path data for `path`.
+pub(crate) fn path_data(path: &Path) -> String {
+    let mut d = String::new();
+    for cmd in &path.0 {
+        let _ = match *cmd {
+            PathCmd::MoveTo(x, y) => write!(d, "M{} {}", num(x), num(y)),
+            PathCmd::LineTo(x, y) => write!(d, "L{} {}", num(x), num(y)),
+            PathCmd::CubicTo(x1, y1, x2, y2, x, y) => write!(
+                d,
+                "C{} {} {} {} {} {}",
+                num(x1),
+                num(y1),
+                num(x2),
+                num(y2),
+                num(x),
+                num(y)
+            ),
+            PathCmd::Close => write!(d, "Z"),
+        };
+    }
+    d
+}
+
+pub(crate) fn matrix(m: Matrix) -> String {
+    let values: Vec<String> = m.0.iter().map(|v| num(*v)).collect();
+    format!("matrix({})", values.join(" "))
+}
+
+pub(crate) fn hex(color: RgbColor) -> String {
+    format!("#{}", color.to_hex())
+}
+
+/// Points to CSS pixels.
+pub(crate) fn pt_to_px(pt: f64) -> f64 {
+    pt * 4.0 / 3.0
+}
+
+/// A number with at most three decimals and no trailing zeros.
+pub(crate) fn num(value: f64) -> String {
+    let rounded = (value * 1000.0).round() / 1000.0;
+    // Avoid "-0"
+    let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+    let mut s = format!("{rounded:.3}");
+    while s.ends_with('0') {
+        s.pop();
+    }
+    if s.ends_with('.') {
+        s.pop();
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers_compactly() {
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(1.25), "1.25");
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(2.0 / 3.0), "0.667");
+    }
+
+    #[test]
+    fn writes_gradients_and_clips_as_defs() {
+        let mut next_id = 0;
+        let images = ["media/image1.png".to_string()];
+        let items = [
+            Item::Fill(
+                Path::rect(0.0, 0.0, 10.0, 5.0),
+                Paint::LinearGradient {
+                    start: (0.0, 0.0),
+                    end: (10.0, 0.0),
+                    stops: vec![
+                        (0.0, RgbColor::new(255, 0, 0)),
+                        (1.0, RgbColor::new(0, 0, 255)),
+                    ],
+                },
+            ),
+            Item::Group {
+                transform: Matrix::IDENTITY,
+                clip: Some(Path::rect(0.0, 0.0, 10.0, 5.0)),
+                items: vec![Item::Image {
+                    index: 0,
+                    x: 0.0,
+                    y: 0.0,
+                    width: 10.0,
+                    height: 5.0,
+                }],
+            },
+        ];
+        let mut svg = String::new();
+        SvgWriter::new(&mut next_id, &images)
+            .write_svg(&mut svg, "geometry", (10.0, 5.0), &items)
+            .unwrap();
+        assert!(svg.contains("width=\"13.333\" height=\"6.667\" viewBox=\"0 0 10 5\""));
+        assert!(svg.contains("<linearGradient id=\"grad1\""));
+        assert!(svg.contains("<stop offset=\"1\" stop-color=\"#0000FF\"/>"));
+        assert!(svg.contains("fill=\"url(#grad1)\""));
+        assert!(svg.contains("<clipPath id=\"clip2\">"));
+        assert!(svg.contains("<image href=\"media/image1.png\""));
+        assert_eq!(next_id, 2);
+    }
+
+    #[test]
+    fn writes_a_line_of_runs_as_one_text_element() {
+        let run = |x: f64, y: f64, text: &str| {
+            Item::Text(TextItem {
+                x,
+                y,
+                face: crate::export::scene::FontFace {
+                    family: FontFamily::Mono,
+                    bold: false,
+                    italic: true,
+                },
+                font: Some("Courier New".to_string()),
+                size: 10.0,
+                color: RgbColor::new(0, 0, 0),
+                text: text.to_string(),
+            })
+        };
+        let underline = Item::Fill(
+            Path::rect(0.0, 11.0, 20.0, 0.5),
+            Paint::Solid(RgbColor::new(0, 0, 0)),
+        );
+        let items = [
+            run(0.0, 10.0, "a<b"),
+            underline,
+            run(20.0, 10.0, "c"),
+            run(0.0, 22.0, "next"),
+        ];
+        let mut next_id = 0;
+        let mut svg = String::new();
+        SvgWriter::new(&mut next_id, &[])
+            .write_document(&mut svg, (100.0, 50.0), &items)
+            .unwrap();
+        assert_eq!(svg.matches("<text").count(), 2);
+        assert!(svg.contains(
+            "<text xml:space=\"preserve\"><tspan x=\"0\" y=\"10\" font-family=\"'Courier New', monospace\" \
+             font-size=\"10\" fill=\"#000000\" font-style=\"italic\">a&lt;b</tspan><tspan x=\"20\""
+        ));
+        // The underline follows the line it belongs to
+        assert!(svg.contains("c</tspan></text>\n<path d=\"M0 11L20 11L20 11.5L0 11.5Z\""));
+    }
+}
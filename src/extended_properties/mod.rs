@@ -0,0 +1,326 @@
+//! Extended (application) document properties (`docProps/app.xml`).
+//!
+//! These record the producing application, the company and manager, and
+//! document statistics such as slide counts and the titles of the parts of
+//! the document.
+
+mod xml;
+
+/// The heading-pair group listing the slide titles of a presentation.
+pub(crate) const SLIDE_TITLES: &str = "Slide Titles";
+
+/// Extended document properties stored in `docProps/app.xml`.
+///
+/// The slide, notes and hidden-slide counts and the "Slide Titles" entries
+/// of the heading pairs and titles of parts are kept in step with the
+/// slides of a presentation when it is saved. Elements that are not
+/// modeled, such as `DocSecurity`, `Characters` or `HLinks`, are kept and
+/// written back unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use pptx::extended_properties::ExtendedProperties;
+///
+/// let mut props = ExtendedProperties::new();
+/// props.set_company("Contoso");
+/// props.set_manager("Jane Doe");
+/// assert_eq!(props.company(), "Contoso");
+/// assert_eq!(props.manager(), "Jane Doe");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtendedProperties {
+    pub(crate) template: String,
+    pub(crate) total_time: Option<u32>,
+    pub(crate) words: Option<u32>,
+    pub(crate) application: String,
+    pub(crate) presentation_format: String,
+    pub(crate) paragraphs: Option<u32>,
+    pub(crate) slides: Option<u32>,
+    pub(crate) notes: Option<u32>,
+    pub(crate) hidden_slides: Option<u32>,
+    pub(crate) multimedia_clips: Option<u32>,
+    pub(crate) scale_crop: Option<bool>,
+    pub(crate) heading_pairs: Vec<(String, u32)>,
+    pub(crate) titles_of_parts: Vec<String>,
+    pub(crate) manager: String,
+    pub(crate) company: String,
+    pub(crate) links_up_to_date: Option<bool>,
+    pub(crate) shared_doc: Option<bool>,
+    pub(crate) hyperlink_base: String,
+    pub(crate) hyperlinks_changed: Option<bool>,
+    pub(crate) app_version: String,
+    /// Top-level elements that are not modeled, such as `DocSecurity` or
+    /// vendor extensions, as XML; written back unchanged.
+    pub(crate) other_elements: Vec<String>,
+    /// Namespace declarations of the root element other than the default
+    /// and `vt` ones, which `other_elements` may use.
+    pub(crate) namespaces: Vec<(String, String)>,
+}
+
+impl ExtendedProperties {
+    /// Create a new empty `ExtendedProperties`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // --- Getters ---
+
+    /// Returns the name of the template the document is based on.
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns the total editing time in minutes, if recorded.
+    #[must_use]
+    pub const fn total_time(&self) -> Option<u32> {
+        self.total_time
+    }
+
+    /// Returns the word count, if recorded.
+    #[must_use]
+    pub const fn words(&self) -> Option<u32> {
+        self.words
+    }
+
+    /// Returns the name of the application that produced the document.
+    #[must_use]
+    pub fn application(&self) -> &str {
+        &self.application
+    }
+
+    /// Returns the intended presentation format, e.g. "Widescreen".
+    #[must_use]
+    pub fn presentation_format(&self) -> &str {
+        &self.presentation_format
+    }
+
+    /// Returns the paragraph count, if recorded.
+    #[must_use]
+    pub const fn paragraphs(&self) -> Option<u32> {
+        self.paragraphs
+    }
+
+    /// Returns the number of slides, if recorded.
+    #[must_use]
+    pub const fn slides(&self) -> Option<u32> {
+        self.slides
+    }
+
+    /// Returns the number of slides with notes, if recorded.
+    #[must_use]
+    pub const fn notes(&self) -> Option<u32> {
+        self.notes
+    }
+
+    /// Returns the number of hidden slides, if recorded.
+    #[must_use]
+    pub const fn hidden_slides(&self) -> Option<u32> {
+        self.hidden_slides
+    }
+
+    /// Returns the number of sound or video clips, if recorded.
+    #[must_use]
+    pub const fn multimedia_clips(&self) -> Option<u32> {
+        self.multimedia_clips
+    }
+
+    /// Returns the document thumbnail display mode: `true` to scale the
+    /// thumbnail, `false` to crop it.
+    #[must_use]
+    pub fn scale_crop(&self) -> bool {
+        self.scale_crop.unwrap_or(false)
+    }
+
+    /// Returns the heading pairs: the name of each group of document parts
+    /// with the number of entries of `titles_of_parts` it covers.
+    #[must_use]
+    pub fn heading_pairs(&self) -> &[(String, u32)] {
+        &self.heading_pairs
+    }
+
+    /// Returns the titles of the document parts, grouped as described by
+    /// `heading_pairs`.
+    #[must_use]
+    pub fn titles_of_parts(&self) -> &[String] {
+        &self.titles_of_parts
+    }
+
+    /// Returns the name of the document's manager.
+    #[must_use]
+    pub fn manager(&self) -> &str {
+        &self.manager
+    }
+
+    /// Returns the name of the company the document belongs to.
+    #[must_use]
+    pub fn company(&self) -> &str {
+        &self.company
+    }
+
+    /// Returns whether the hyperlinks of the document are up to date.
+    #[must_use]
+    pub fn links_up_to_date(&self) -> bool {
+        self.links_up_to_date.unwrap_or(false)
+    }
+
+    /// Returns whether the document is shared between several producers.
+    #[must_use]
+    pub fn shared_doc(&self) -> bool {
+        self.shared_doc.unwrap_or(false)
+    }
+
+    /// Returns the base URL relative hyperlinks are resolved against.
+    #[must_use]
+    pub fn hyperlink_base(&self) -> &str {
+        &self.hyperlink_base
+    }
+
+    /// Returns whether hyperlinks were changed outside the document.
+    #[must_use]
+    pub fn hyperlinks_changed(&self) -> bool {
+        self.hyperlinks_changed.unwrap_or(false)
+    }
+
+    /// Returns the version of the application that produced the document.
+    #[must_use]
+    pub fn app_version(&self) -> &str {
+        &self.app_version
+    }
+
+    // --- Setters ---
+
+    /// Sets the name of the template the document is based on.
+    pub fn set_template(&mut self, value: impl Into<String>) {
+        self.template = value.into();
+    }
+
+    /// Sets the total editing time in minutes.
+    pub fn set_total_time(&mut self, value: Option<u32>) {
+        self.total_time = value;
+    }
+
+    /// Sets the word count.
+    pub fn set_words(&mut self, value: Option<u32>) {
+        self.words = value;
+    }
+
+    /// Sets the name of the application that produced the document.
+    pub fn set_application(&mut self, value: impl Into<String>) {
+        self.application = value.into();
+    }
+
+    /// Sets the intended presentation format.
+    pub fn set_presentation_format(&mut self, value: impl Into<String>) {
+        self.presentation_format = value.into();
+    }
+
+    /// Sets the paragraph count.
+    pub fn set_paragraphs(&mut self, value: Option<u32>) {
+        self.paragraphs = value;
+    }
+
+    /// Sets the number of sound or video clips.
+    pub fn set_multimedia_clips(&mut self, value: Option<u32>) {
+        self.multimedia_clips = value;
+    }
+
+    /// Sets the document thumbnail display mode.
+    pub fn set_scale_crop(&mut self, value: bool) {
+        self.scale_crop = Some(value);
+    }
+
+    /// Sets the name of the document's manager.
+    pub fn set_manager(&mut self, value: impl Into<String>) {
+        self.manager = value.into();
+    }
+
+    /// Sets the name of the company the document belongs to.
+    pub fn set_company(&mut self, value: impl Into<String>) {
+        self.company = value.into();
+    }
+
+    /// Sets whether the hyperlinks of the document are up to date.
+    pub fn set_links_up_to_date(&mut self, value: bool) {
+        self.links_up_to_date = Some(value);
+    }
+
+    /// Sets whether the document is shared between several producers.
+    pub fn set_shared_doc(&mut self, value: bool) {
+        self.shared_doc = Some(value);
+    }
+
+    /// Sets the base URL relative hyperlinks are resolved against.
+    pub fn set_hyperlink_base(&mut self, value: impl Into<String>) {
+        self.hyperlink_base = value.into();
+    }
+
+    /// Sets whether hyperlinks were changed outside the document.
+    pub fn set_hyperlinks_changed(&mut self, value: bool) {
+        self.hyperlinks_changed = Some(value);
+    }
+
+    /// Sets the version of the application that produced the document.
+    pub fn set_app_version(&mut self, value: impl Into<String>) {
+        self.app_version = value.into();
+    }
+
+    // --- Presentation statistics ---
+
+    /// Record the slide, notes and hidden-slide counts.
+    pub(crate) fn set_slide_counts(&mut self, slides: u32, notes: u32, hidden_slides: u32) {
+        self.slides = Some(slides);
+        self.notes = Some(notes);
+        self.hidden_slides = Some(hidden_slides);
+    }
+
+    /// The titles of the "Slide Titles" group.
+    pub(crate) fn slide_titles(&self) -> &[String] {
+        let (start, group) = self.slide_titles_group();
+        let start = start.min(self.titles_of_parts.len());
+        let end = group.map_or(start, |i| {
+            (start + self.heading_pairs[i].1 as usize).min(self.titles_of_parts.len())
+        });
+        &self.titles_of_parts[start..end]
+    }
+
+    /// Replace the titles of the "Slide Titles" group, keeping the other
+    /// groups of the heading pairs and their titles.
+    pub(crate) fn set_slide_titles(&mut self, titles: Vec<String>) {
+        let (start, group) = self.slide_titles_group();
+        let count = u32::try_from(titles.len()).unwrap_or(u32::MAX);
+        let start = start.min(self.titles_of_parts.len());
+        match group {
+            Some(i) => {
+                let end =
+                    (start + self.heading_pairs[i].1 as usize).min(self.titles_of_parts.len());
+                self.titles_of_parts.splice(start..end, titles);
+                self.heading_pairs[i].1 = count;
+            }
+            None if titles.is_empty() => {}
+            None => {
+                self.titles_of_parts.truncate(start);
+                self.titles_of_parts.extend(titles);
+                self.heading_pairs.push((SLIDE_TITLES.to_string(), count));
+            }
+        }
+    }
+
+    /// The position in `titles_of_parts` of the first slide title, and the
+    /// heading pair of the "Slide Titles" group if there is one.
+    fn slide_titles_group(&self) -> (usize, Option<usize>) {
+        let mut start = 0;
+        for (i, (name, count)) in self.heading_pairs.iter().enumerate() {
+            if name == SLIDE_TITLES {
+                return (start, Some(i));
+            }
+            start += *count as usize;
+        }
+        (start, None)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use super::*;
+
+const APP_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes"><TotalTime>12</TotalTime><Words>40</Words><Application>Microsoft Office PowerPoint</Application><PresentationFormat>Widescreen</PresentationFormat><Paragraphs>6</Paragraphs><Slides>2</Slides><Notes>1</Notes><HiddenSlides>0</HiddenSlides><MMClips>0</MMClips><ScaleCrop>false</ScaleCrop><HeadingPairs><vt:vector size="4" baseType="variant"><vt:variant><vt:lpstr>Theme</vt:lpstr></vt:variant><vt:variant><vt:i4>1</vt:i4></vt:variant><vt:variant><vt:lpstr>Slide Titles</vt:lpstr></vt:variant><vt:variant><vt:i4>2</vt:i4></vt:variant></vt:vector></HeadingPairs><TitlesOfParts><vt:vector size="3" baseType="lpstr"><vt:lpstr>Office Theme</vt:lpstr><vt:lpstr>Intro</vt:lpstr><vt:lpstr>Q&amp;A</vt:lpstr></vt:vector></TitlesOfParts><Manager>Jane Doe</Manager><Company>Contoso &amp; Co</Company><LinksUpToDate>false</LinksUpToDate><SharedDoc>false</SharedDoc><HyperlinksChanged>false</HyperlinksChanged><AppVersion>16.0000</AppVersion></Properties>"#;
+
+#[test]
+fn test_new_extended_properties() {
+    let props = ExtendedProperties::new();
+    assert_eq!(props.application(), "");
+    assert_eq!(props.slides(), None);
+    assert!(props.heading_pairs().is_empty());
+    assert!(!props.scale_crop());
+}
+
+#[test]
+fn test_from_xml() {
+    let props = ExtendedProperties::from_xml(APP_XML.as_bytes()).unwrap();
+    assert_eq!(props.total_time(), Some(12));
+    assert_eq!(props.words(), Some(40));
+    assert_eq!(props.application(), "Microsoft Office PowerPoint");
+    assert_eq!(props.presentation_format(), "Widescreen");
+    assert_eq!(props.paragraphs(), Some(6));
+    assert_eq!(props.slides(), Some(2));
+    assert_eq!(props.notes(), Some(1));
+    assert_eq!(props.hidden_slides(), Some(0));
+    assert_eq!(props.multimedia_clips(), Some(0));
+    assert_eq!(
+        props.heading_pairs(),
+        [("Theme".to_string(), 1), ("Slide Titles".to_string(), 2)]
+    );
+    assert_eq!(props.titles_of_parts(), ["Office Theme", "Intro", "Q&A"]);
+    assert_eq!(props.manager(), "Jane Doe");
+    assert_eq!(props.company(), "Contoso & Co");
+    assert_eq!(props.app_version(), "16.0000");
+}
+
+#[test]
+fn test_from_xml_rejects_bad_count() {
+    let xml = r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties"><Slides>many</Slides></Properties>"#;
+    assert!(ExtendedProperties::from_xml(xml.as_bytes()).is_err());
+}
+
+#[test]
+fn test_round_trip_xml() {
+    let mut props = ExtendedProperties::from_xml(APP_XML.as_bytes()).unwrap();
+    props.set_company("Fabrikam <EU>");
+    props.set_manager("");
+    props.set_hyperlink_base("https://example.com/");
+    props.set_scale_crop(true);
+
+    let xml = props.to_xml().unwrap();
+    let xml_str = std::str::from_utf8(&xml).unwrap();
+    assert!(xml_str.contains("<Company>Fabrikam &lt;EU&gt;</Company>"));
+    assert!(!xml_str.contains("<Manager>"));
+    assert!(xml_str.contains(r#"<vt:vector size="4" baseType="variant">"#));
+
+    let props2 = ExtendedProperties::from_xml(&xml).unwrap();
+    assert_eq!(props2, props);
+}
+
+/// An `app.xml` with elements the properties do not model.
+const APP_XML_WITH_OTHERS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes" xmlns:x="urn:example:vendor"><Application>Microsoft Office PowerPoint</Application><DocSecurity>2</DocSecurity><Lines>7</Lines><Pages>3</Pages><Characters>120</Characters><CharactersWithSpaces>140</CharactersWithSpaces><Slides>2</Slides><HLinks><vt:vector size="1" baseType="variant"><vt:variant><vt:lpwstr>https://example.com/</vt:lpwstr></vt:variant></vt:vector></HLinks><DigSig><vt:blob>AAAA</vt:blob></DigSig><x:Flag/></Properties>"#;
+
+#[test]
+fn test_round_trip_keeps_unmodeled_elements() {
+    let mut props = ExtendedProperties::from_xml(APP_XML_WITH_OTHERS.as_bytes()).unwrap();
+    assert_eq!(props.slides(), Some(2));
+    props.set_company("Contoso");
+
+    let xml = props.to_xml().unwrap();
+    let xml_str = std::str::from_utf8(&xml).unwrap();
+    assert!(xml_str.contains(r#" xmlns:x="urn:example:vendor">"#));
+    for element in [
+        "<DocSecurity>2</DocSecurity>",
+        "<Lines>7</Lines>",
+        "<Pages>3</Pages>",
+        "<Characters>120</Characters>",
+        "<CharactersWithSpaces>140</CharactersWithSpaces>",
+        r#"<HLinks><vt:vector size="1" baseType="variant"><vt:variant><vt:lpwstr>https://example.com/</vt:lpwstr></vt:variant></vt:vector></HLinks>"#,
+        "<DigSig><vt:blob>AAAA</vt:blob></DigSig>",
+        "<x:Flag/>",
+    ] {
+        assert_eq!(xml_str.matches(element).count(), 1, "{element}");
+    }
+    assert!(xml_str.contains("<Company>Contoso</Company>"));
+    assert_eq!(ExtendedProperties::from_xml(&xml).unwrap(), props);
+}
+
+#[test]
+fn test_set_slide_titles_keeps_other_groups() {
+    let mut props = ExtendedProperties::from_xml(APP_XML.as_bytes()).unwrap();
+    props.set_slide_titles(vec!["One".into(), "Two".into(), "Three".into()]);
+    assert_eq!(
+        props.heading_pairs(),
+        [("Theme".to_string(), 1), ("Slide Titles".to_string(), 3)]
+    );
+    assert_eq!(
+        props.titles_of_parts(),
+        ["Office Theme", "One", "Two", "Three"]
+    );
+
+    // A group is added when there is none
+    let mut props = ExtendedProperties::new();
+    props.set_slide_titles(Vec::new());
+    assert!(props.heading_pairs().is_empty());
+    props.set_slide_titles(vec!["Only".into()]);
+    assert_eq!(props.heading_pairs(), [("Slide Titles".to_string(), 1)]);
+    assert_eq!(props.titles_of_parts(), ["Only"]);
+}
@@ -0,0 +1,296 @@
+//! XML parsing and serialization for extended properties.
+
+use std::ops::Range;
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Reader;
+use quick_xml::Writer;
+
+use crate::error::{PptxError, PptxResult};
+use crate::oxml::ns::{NS_EP, NS_VT};
+use crate::xml_util::{append_text, local_name_owned, offset};
+
+use super::ExtendedProperties;
+
+/// Top-level elements read into fields; others are kept verbatim.
+const MODELED: &[&str] = &[
+    "Template",
+    "TotalTime",
+    "Words",
+    "Application",
+    "PresentationFormat",
+    "Paragraphs",
+    "Slides",
+    "Notes",
+    "HiddenSlides",
+    "MMClips",
+    "ScaleCrop",
+    "HeadingPairs",
+    "TitlesOfParts",
+    "Manager",
+    "Company",
+    "LinksUpToDate",
+    "SharedDoc",
+    "HyperlinkBase",
+    "HyperlinksChanged",
+    "AppVersion",
+];
+
+impl ExtendedProperties {
+    /// Parse `ExtendedProperties` from the `docProps/app.xml` content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed or cannot be decoded.
+    pub fn from_xml(xml: &[u8]) -> PptxResult<Self> {
+        let mut props = Self::new();
+        let mut reader = Reader::from_reader(xml);
+
+        let mut buf = Vec::new();
+        // Local names of the open elements, from the root
+        let mut path: Vec<String> = Vec::new();
+        let mut text = String::new();
+        // Start of the top-level element being skipped, if it is not modeled
+        let mut other_start = None;
+
+        loop {
+            let start = offset(&reader);
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let name = local_name_owned(e.name().as_ref());
+                    if path.is_empty() {
+                        props.read_namespaces(e)?;
+                    } else if path.len() == 1 && !MODELED.contains(&name.as_str()) {
+                        other_start = Some(start);
+                    }
+                    path.push(name);
+                    text.clear();
+                }
+                Ok(Event::Empty(ref e)) => {
+                    let name = local_name_owned(e.name().as_ref());
+                    if path.len() == 1 && !MODELED.contains(&name.as_str()) {
+                        props.push_other(xml, start..offset(&reader))?;
+                    }
+                }
+                Ok(ref event @ (Event::Text(_) | Event::GeneralRef(_))) => {
+                    append_text(event, &mut text)?;
+                }
+                Ok(Event::End(_)) => {
+                    if let Some(name) = path.pop() {
+                        props.read_element(&path, &name, text.trim())?;
+                    }
+                    if path.len() == 1 {
+                        if let Some(start) = other_start.take() {
+                            props.push_other(xml, start..offset(&reader))?;
+                        }
+                    }
+                    text.clear();
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(PptxError::Xml(e)),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(props)
+    }
+
+    /// Keep the namespace declarations of the root element that are not
+    /// written anyway.
+    fn read_namespaces(&mut self, root: &BytesStart<'_>) -> PptxResult<()> {
+        for attr in root.attributes() {
+            let attr = attr.map_err(|e| PptxError::InvalidXml(e.to_string()))?;
+            let key = std::str::from_utf8(attr.key.as_ref())?;
+            if key.starts_with("xmlns:") && key != "xmlns:vt" {
+                let value = std::str::from_utf8(&attr.value)?;
+                self.namespaces.push((key.to_string(), value.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep the top-level element at `range` of `xml` verbatim.
+    fn push_other(&mut self, xml: &[u8], range: Range<usize>) -> PptxResult<()> {
+        let element = xml.get(range).unwrap_or_default();
+        self.other_elements
+            .push(std::str::from_utf8(element)?.trim_start().to_string());
+        Ok(())
+    }
+
+    /// Store the text of the element `name`, whose ancestors are `path`.
+    fn read_element(&mut self, path: &[String], name: &str, text: &str) -> PptxResult<()> {
+        let vector = path.get(1).map(String::as_str);
+        if path.len() > 1 {
+            match (vector, name) {
+                (Some("HeadingPairs"), "lpstr" | "lpwstr") => {
+                    self.heading_pairs.push((text.to_string(), 0));
+                }
+                (Some("HeadingPairs"), "i4") => {
+                    if let Some(pair) = self.heading_pairs.last_mut() {
+                        pair.1 = parse_count("i4", text)?;
+                    }
+                }
+                (Some("TitlesOfParts"), "lpstr" | "lpwstr") => {
+                    self.titles_of_parts.push(text.to_string());
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+        if path.is_empty() {
+            return Ok(());
+        }
+        match name {
+            "Template" => self.template = text.to_string(),
+            "TotalTime" => self.total_time = Some(parse_count("TotalTime", text)?),
+            "Words" => self.words = Some(parse_count("Words", text)?),
+            "Application" => self.application = text.to_string(),
+            "PresentationFormat" => self.presentation_format = text.to_string(),
+            "Paragraphs" => self.paragraphs = Some(parse_count("Paragraphs", text)?),
+            "Slides" => self.slides = Some(parse_count("Slides", text)?),
+            "Notes" => self.notes = Some(parse_count("Notes", text)?),
+            "HiddenSlides" => self.hidden_slides = Some(parse_count("HiddenSlides", text)?),
+            "MMClips" => self.multimedia_clips = Some(parse_count("MMClips", text)?),
+            "ScaleCrop" => self.scale_crop = Some(parse_bool(text)),
+            "Manager" => self.manager = text.to_string(),
+            "Company" => self.company = text.to_string(),
+            "LinksUpToDate" => self.links_up_to_date = Some(parse_bool(text)),
+            "SharedDoc" => self.shared_doc = Some(parse_bool(text)),
+            "HyperlinkBase" => self.hyperlink_base = text.to_string(),
+            "HyperlinksChanged" => self.hyperlinks_changed = Some(parse_bool(text)),
+            "AppVersion" => self.app_version = text.to_string(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Serialize to XML bytes suitable for `docProps/app.xml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if XML serialization fails.
+    pub fn to_xml(&self) -> PptxResult<Vec<u8>> {
+        let mut writer = Writer::new(Vec::new());
+
+        writer.write_event(Event::Decl(BytesDecl::new(
+            "1.0",
+            Some("UTF-8"),
+            Some("yes"),
+        )))?;
+
+        let mut root = BytesStart::new("Properties");
+        root.push_attribute(("xmlns", NS_EP));
+        root.push_attribute(("xmlns:vt", NS_VT));
+        for (name, uri) in &self.namespaces {
+            root.push_attribute((name.as_str(), uri.as_str()));
+        }
+        writer.write_event(Event::Start(root))?;
+
+        write_text(&mut writer, "Template", &self.template)?;
+        write_count(&mut writer, "TotalTime", self.total_time)?;
+        write_count(&mut writer, "Words", self.words)?;
+        write_text(&mut writer, "Application", &self.application)?;
+        write_text(&mut writer, "PresentationFormat", &self.presentation_format)?;
+        write_count(&mut writer, "Paragraphs", self.paragraphs)?;
+        write_count(&mut writer, "Slides", self.slides)?;
+        write_count(&mut writer, "Notes", self.notes)?;
+        write_count(&mut writer, "HiddenSlides", self.hidden_slides)?;
+        write_count(&mut writer, "MMClips", self.multimedia_clips)?;
+        write_bool(&mut writer, "ScaleCrop", self.scale_crop)?;
+
+        if !self.heading_pairs.is_empty() {
+            writer.write_event(Event::Start(BytesStart::new("HeadingPairs")))?;
+            let size = (self.heading_pairs.len() * 2).to_string();
+            let mut vector = BytesStart::new("vt:vector");
+            vector.push_attribute(("size", size.as_str()));
+            vector.push_attribute(("baseType", "variant"));
+            writer.write_event(Event::Start(vector))?;
+            for (name, count) in &self.heading_pairs {
+                writer.write_event(Event::Start(BytesStart::new("vt:variant")))?;
+                write_simple_element(&mut writer, "vt:lpstr", name)?;
+                writer.write_event(Event::End(BytesEnd::new("vt:variant")))?;
+                writer.write_event(Event::Start(BytesStart::new("vt:variant")))?;
+                write_simple_element(&mut writer, "vt:i4", &count.to_string())?;
+                writer.write_event(Event::End(BytesEnd::new("vt:variant")))?;
+            }
+            writer.write_event(Event::End(BytesEnd::new("vt:vector")))?;
+            writer.write_event(Event::End(BytesEnd::new("HeadingPairs")))?;
+        }
+
+        if !self.titles_of_parts.is_empty() {
+            writer.write_event(Event::Start(BytesStart::new("TitlesOfParts")))?;
+            let size = self.titles_of_parts.len().to_string();
+            let mut vector = BytesStart::new("vt:vector");
+            vector.push_attribute(("size", size.as_str()));
+            vector.push_attribute(("baseType", "lpstr"));
+            writer.write_event(Event::Start(vector))?;
+            for title in &self.titles_of_parts {
+                write_simple_element(&mut writer, "vt:lpstr", title)?;
+            }
+            writer.write_event(Event::End(BytesEnd::new("vt:vector")))?;
+            writer.write_event(Event::End(BytesEnd::new("TitlesOfParts")))?;
+        }
+
+        write_text(&mut writer, "Manager", &self.manager)?;
+        write_text(&mut writer, "Company", &self.company)?;
+        write_bool(&mut writer, "LinksUpToDate", self.links_up_to_date)?;
+        write_bool(&mut writer, "SharedDoc", self.shared_doc)?;
+        write_text(&mut writer, "HyperlinkBase", &self.hyperlink_base)?;
+        write_bool(&mut writer, "HyperlinksChanged", self.hyperlinks_changed)?;
+        write_text(&mut writer, "AppVersion", &self.app_version)?;
+        for element in &self.other_elements {
+            writer.get_mut().extend_from_slice(element.as_bytes());
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("Properties")))?;
+
+        Ok(writer.into_inner())
+    }
+}
+
+/// Parse the count held by the element `field`.
+fn parse_count(field: &'static str, text: &str) -> PptxResult<u32> {
+    text.parse().map_err(|_| PptxError::InvalidValue {
+        field,
+        value: text.to_string(),
+        expected: "a non-negative integer",
+    })
+}
+
+/// Parse an `xsd:boolean`.
+fn parse_bool(text: &str) -> bool {
+    matches!(text, "true" | "1")
+}
+
+/// Write a simple element like `<tag>text</tag>`.
+fn write_simple_element(writer: &mut Writer<Vec<u8>>, tag: &str, text: &str) -> PptxResult<()> {
+    writer.write_event(Event::Start(BytesStart::new(tag)))?;
+    writer.write_event(Event::Text(BytesText::new(text)))?;
+    writer.write_event(Event::End(BytesEnd::new(tag)))?;
+    Ok(())
+}
+
+/// Write a text element, unless the text is empty.
+fn write_text(writer: &mut Writer<Vec<u8>>, tag: &str, text: &str) -> PptxResult<()> {
+    if text.is_empty() {
+        return Ok(());
+    }
+    write_simple_element(writer, tag, text)
+}
+
+/// Write a count element, if the count is set.
+fn write_count(writer: &mut Writer<Vec<u8>>, tag: &str, count: Option<u32>) -> PptxResult<()> {
+    match count {
+        Some(count) => write_simple_element(writer, tag, &count.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Write a boolean element, if the flag is set.
+fn write_bool(writer: &mut Writer<Vec<u8>>, tag: &str, flag: Option<bool>) -> PptxResult<()> {
+    match flag {
+        Some(flag) => write_simple_element(writer, tag, if flag { "true" } else { "false" }),
+        None => Ok(()),
+    }
+}
@@ -22,23 +22,29 @@ pub mod animation;
 pub mod chart;
 pub mod comment;
 pub mod core_properties;
+pub mod custom_properties;
+pub mod custom_show;
 pub mod dml;
 pub mod embedded_font;
 pub mod enums;
 pub mod error;
 pub mod export;
+pub mod extended_properties;
 pub mod media;
 pub mod opc;
 pub(crate) mod oxml;
 pub mod presentation;
 pub mod print_settings;
+pub mod render;
 pub mod repair;
 pub mod section;
 pub mod shapes;
+pub mod show_settings;
 pub mod signature;
 pub mod slide;
 pub mod smartart;
 pub mod table;
+pub mod template;
 pub mod text;
 pub mod theme;
 pub mod transition;
@@ -47,7 +53,7 @@ pub mod units;
 // --- Convenience re-exports for the most-used public types ---
 
 pub use error::{PackageError, PartNotFoundExt, PptxError, PptxResult, SlideError};
-pub use presentation::Presentation;
+pub use presentation::{ImportFormatting, ImportOptions, Presentation, TextMatch, TextPart};
 pub use units::{
     Centipoints, Cm, ConnectionPointIndex, DurationMs, Emu, Inches, Mm, PlaceholderIndex, Pt,
     ShapeId, SlideId, Twips,
@@ -62,7 +68,7 @@ pub use shapes::{
 
 // Text
 pub use text::font::RgbColor;
-pub use text::{BulletFormat, Font, Paragraph, Run, TextFrame};
+pub use text::{BulletFormat, Font, Paragraph, ReplaceOptions, Run, TextFrame};
 
 // Table
 pub use table::{Cell, CellBorder, Column, Row, Table};
@@ -76,17 +82,19 @@ pub use dml::{
 // Chart
 pub use chart::{
     AxisTitle, BubbleChartData, Categories, Category, CategoryAxis, CategoryChartData,
-    CategoryLevel, Chart, ChartFormat, ChartTitle, ChartXmlWriter, ComboChartData, ComboSeriesData,
-    ComboSeriesType, DataLabel, DataLabels, DateAxis, DateAxisChartData, Legend, LegendEntry,
-    Marker, MarkerFormat, Plot, PlotProperties, Point, Series, SeriesCollection, SeriesFormat,
-    TickLabels, ValueAxis, XyChartData,
+    CategoryLevel, Chart, ChartData, ChartFormat, ChartTitle, ChartXmlWriter, ComboChartData,
+    ComboSeriesData, ComboSeriesType, DataLabel, DataLabels, DateAxis, DateAxisChartData, Legend,
+    LegendEntry, Marker, MarkerFormat, Plot, PlotProperties, Point, Series, SeriesCollection,
+    SeriesFormat, TickLabels, ValueAxis, XyChartData,
 };
 
 // Media
 pub use media::{Audio, Image, Video};
 
-// Core Properties
+// Document Properties
 pub use core_properties::CoreProperties;
+pub use custom_properties::{CustomProperties, CustomPropertyValue};
+pub use extended_properties::ExtendedProperties;
 
 // Actions & Hyperlinks
 pub use shapes::action::{ActionSetting, Hyperlink};
@@ -95,7 +103,7 @@ pub use shapes::action::{ActionSetting, Hyperlink};
 pub use shapes::freeform::FreeformBuilder;
 
 // Effects
-pub use dml::effect::{ShadowFormat, ShadowType};
+pub use dml::effect::{GlowFormat, ShadowFormat, ShadowType};
 
 // 3D Effects
 pub use dml::effect3d::{Bevel, Camera, LightRig, Rotation3D, Scene3D, Shape3D};
@@ -143,6 +151,10 @@ pub use embedded_font::EmbeddedFont;
 // Print Settings
 pub use print_settings::PrintSettings;
 
+// Slide Show Settings & Custom Shows
+pub use custom_show::CustomShow;
+pub use show_settings::{ShowSettings, ShowType, SlideRange};
+
 // Repair & Validation
 pub use repair::{
     IssueCategory, PptxRepairer, PptxValidator, RepairReport, Severity, ValidationIssue,
@@ -4,6 +4,10 @@ pub mod content_type {
     pub const OPC_RELATIONSHIPS: &str = "application/vnd.openxmlformats-package.relationships+xml";
     pub const OPC_CORE_PROPERTIES: &str =
         "application/vnd.openxmlformats-package.core-properties+xml";
+    pub const OFC_EXTENDED_PROPERTIES: &str =
+        "application/vnd.openxmlformats-officedocument.extended-properties+xml";
+    pub const OFC_CUSTOM_PROPERTIES: &str =
+        "application/vnd.openxmlformats-officedocument.custom-properties+xml";
 
     // Presentation ML
     pub const PML_PRESENTATION_MAIN: &str =
@@ -18,10 +22,18 @@ pub mod content_type {
         "application/vnd.openxmlformats-officedocument.presentationml.notesMaster+xml";
     pub const PML_NOTES_SLIDE: &str =
         "application/vnd.openxmlformats-officedocument.presentationml.notesSlide+xml";
+    pub const PML_PRES_PROPS: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.presProps+xml";
     pub const PML_PRINTER_SETTINGS: &str =
         "application/vnd.openxmlformats-officedocument.presentationml.printerSettings";
     pub const PML_COMMENTS: &str =
         "application/vnd.openxmlformats-officedocument.presentationml.comments+xml";
+    pub const PML_COMMENT_AUTHORS: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.commentAuthors+xml";
+
+    // Modern (threaded) comments
+    pub const PPT_COMMENTS: &str = "application/vnd.ms-powerpoint.comments+xml";
+    pub const PPT_AUTHORS: &str = "application/vnd.ms-powerpoint.authors+xml";
 
     // DrawingML
     pub const DML_CHART: &str = "application/vnd.openxmlformats-officedocument.drawingml.chart+xml";
@@ -86,14 +98,38 @@ pub mod relationship_type {
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
     pub const CHART: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart";
+    pub const CHART_USER_SHAPES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chartUserShapes";
+    pub const CHART_STYLE: &str =
+        "http://schemas.microsoft.com/office/2011/relationships/chartStyle";
+    pub const CHART_COLOR_STYLE: &str =
+        "http://schemas.microsoft.com/office/2011/relationships/chartColorStyle";
+    pub const PRES_PROPS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/presProps";
+    pub const COMMENTS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
+    pub const COMMENT_AUTHORS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/commentAuthors";
+    pub const MODERN_COMMENTS: &str =
+        "http://schemas.microsoft.com/office/2018/10/relationships/comments";
+    pub const AUTHORS: &str = "http://schemas.microsoft.com/office/2018/10/relationships/authors";
     pub const FONT: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/font";
     pub const VIDEO: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/video";
+    pub const AUDIO: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio";
+    pub const MEDIA: &str = "http://schemas.microsoft.com/office/2007/relationships/media";
     pub const CORE_PROPERTIES: &str =
         "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties";
+    pub const EXTENDED_PROPERTIES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties";
+    pub const CUSTOM_PROPERTIES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/custom-properties";
     pub const PACKAGE: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/package";
+    pub const THUMBNAIL: &str =
+        "http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail";
     pub const VBA_PROJECT: &str =
         "http://schemas.microsoft.com/office/2006/relationships/vbaProject";
 }
@@ -101,7 +101,17 @@ impl OpcPackage {
     ///
     /// Returns an error if XML serialization or ZIP writing fails.
     pub fn to_bytes(&self) -> PptxResult<Vec<u8>> {
-        zip_io::write_to_bytes(self)
+        zip_io::write_to_bytes(self, &[])
+    }
+
+    /// Serialize the package, writing the given blobs in place of the
+    /// stored content of the named parts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if XML serialization or ZIP writing fails.
+    pub(crate) fn to_bytes_with(&self, blobs: &[(PackURI, Vec<u8>)]) -> PptxResult<Vec<u8>> {
+        zip_io::write_to_bytes(self, blobs)
     }
 
     /// Get a part by its partname.
@@ -202,6 +212,39 @@ impl OpcPackage {
         self.next_partname(&template)
     }
 
+    /// Whether any part (or the package itself) has an internal relationship
+    /// targeting `partname`.
+    pub(crate) fn is_referenced(&self, partname: &PackURI) -> bool {
+        let targets = |rels: &Relationships| {
+            rels.iter().any(|r| {
+                !r.is_external
+                    && r.target_partname(rels.base_uri())
+                        .is_ok_and(|t| t == *partname)
+            })
+        };
+        targets(&self.pkg_rels) || self.parts.values().any(|p| targets(&p.rels))
+    }
+
+    /// Remove `partname` if nothing references it any more, then do the
+    /// same for every part it pointed to (e.g. a chart's embedded workbook).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a relationship target of a removed part cannot be resolved.
+    pub(crate) fn remove_part_if_unreferenced(&mut self, partname: &PackURI) -> PptxResult<()> {
+        if self.is_referenced(partname) {
+            return Ok(());
+        }
+        let Some(part) = self.remove_part(partname) else {
+            return Ok(());
+        };
+        for rel in part.rels.iter().filter(|r| !r.is_external) {
+            let target = rel.target_partname(part.rels.base_uri())?;
+            self.remove_part_if_unreferenced(&target)?;
+        }
+        Ok(())
+    }
+
     /// Build a `ContentTypeMap` from the current parts in the package.
     pub(super) fn build_content_type_map(&self) -> ContentTypeMap {
         ContentTypeMap::from_parts(
@@ -30,7 +30,13 @@ pub(super) fn read_from_bytes(data: &[u8]) -> PptxResult<OpcPackage> {
 }
 
 /// Serialize the package to in-memory bytes (a valid .pptx file).
-pub(super) fn write_to_bytes(pkg: &OpcPackage) -> PptxResult<Vec<u8>> {
+///
+/// Parts named in `blobs` are written with the given content in place of
+/// their stored blob.
+pub(super) fn write_to_bytes(
+    pkg: &OpcPackage,
+    blobs: &[(PackURI, Vec<u8>)],
+) -> PptxResult<Vec<u8>> {
     let buf = Vec::new();
     let cursor = Cursor::new(buf);
     let mut zip = ZipWriter::new(cursor);
@@ -52,8 +58,12 @@ pub(super) fn write_to_bytes(pkg: &OpcPackage) -> PptxResult<Vec<u8>> {
     // Write each part and its rels
     for part in pkg.parts.values() {
         let membername = part.partname.membername();
+        let blob = blobs
+            .iter()
+            .find(|(partname, _)| *partname == part.partname)
+            .map_or(&part.blob, |(_, blob)| blob);
         zip.start_file(membername, options)?;
-        zip.write_all(&part.blob)?;
+        zip.write_all(blob)?;
 
         // Write part-level rels if any
         if !part.rels.is_empty() {
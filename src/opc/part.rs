@@ -22,6 +22,8 @@ pub enum PartType {
     Theme,
     Presentation,
     CoreProperties,
+    ExtendedProperties,
+    CustomProperties,
     CustomXml,
     Comments,
     Font,
@@ -41,8 +43,10 @@ pub fn part_type_from_content_type(ct: &str) -> PartType {
         CT::DML_CHART => PartType::Chart,
         CT::PML_PRESENTATION_MAIN => PartType::Presentation,
         CT::OPC_CORE_PROPERTIES => PartType::CoreProperties,
+        CT::OFC_EXTENDED_PROPERTIES => PartType::ExtendedProperties,
+        CT::OFC_CUSTOM_PROPERTIES => PartType::CustomProperties,
         CT::OFC_THEME => PartType::Theme,
-        CT::PML_COMMENTS => PartType::Comments,
+        CT::PML_COMMENTS | CT::PPT_COMMENTS => PartType::Comments,
         CT::X_FONTDATA | CT::X_FONT_TTF => PartType::Font,
         _ if ct.starts_with("image/") => PartType::Image,
         _ if ct.starts_with("video/") => PartType::Video,
@@ -201,6 +205,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_part_type_extended_and_custom_properties() {
+        assert_eq!(
+            part_type_from_content_type(CT::OFC_EXTENDED_PROPERTIES),
+            PartType::ExtendedProperties
+        );
+        assert_eq!(
+            part_type_from_content_type(CT::OFC_CUSTOM_PROPERTIES),
+            PartType::CustomProperties
+        );
+    }
+
     #[test]
     fn test_part_type_font() {
         assert_eq!(part_type_from_content_type(CT::X_FONTDATA), PartType::Font);
@@ -213,6 +229,10 @@ mod tests {
             part_type_from_content_type(CT::PML_COMMENTS),
             PartType::Comments
         );
+        assert_eq!(
+            part_type_from_content_type(CT::PPT_COMMENTS),
+            PartType::Comments
+        );
     }
 
     #[test]
@@ -37,6 +37,7 @@ pub const NSMAP: &[(&str, &str)] = &[
     ("p14", NS_P14),
     ("dgm", NS_DGM),
     ("mc", NS_MC),
+    ("vt", NS_VT),
 ];
 
 // DrawingML main namespace
@@ -55,6 +56,11 @@ pub const NS_DCMITYPE: &str = "http://purl.org/dc/dcmitype/";
 pub const NS_DCTERMS: &str = "http://purl.org/dc/terms/";
 // Extended properties namespace
 pub const NS_EP: &str = "http://schemas.openxmlformats.org/officeDocument/2006/extended-properties";
+// Custom properties namespace
+pub const NS_CUSTOM_PROPERTIES: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/custom-properties";
+// Document property variant types namespace
+pub const NS_VT: &str = "http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes";
 // Image relationship namespace
 pub const NS_I: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
 // Office math namespace
@@ -0,0 +1,94 @@
+//! Fitting slide text to its shapes with font metrics.
+
+use crate::enums::text::MsoAutoSize;
+use crate::error::PptxResult;
+use crate::export::scene::slide_text_boxes;
+use crate::shapes::autoshape::AutoShape;
+use crate::shapes::Shape;
+use crate::slide::SlideRef;
+use crate::text::layout::{fit_text, measure_text, FontSet};
+use crate::units::{pt_to_emu, Emu, ShapeId};
+
+use super::Presentation;
+
+impl Presentation {
+    /// Apply the autofit setting of every text frame on a slide, measuring
+    /// text with `fonts` and the formatting it inherits from the layout and
+    /// master:
+    ///
+    /// - "Shrink text on overflow" (`TextToFitShape`) frames get the font
+    ///   scale and line spacing reduction `PowerPoint` would apply (see
+    ///   [`TextFrame::shrink_to_fit`](crate::text::TextFrame::shrink_to_fit)).
+    /// - "Resize shape to fit text" (`ShapeToFitText`) shapes get the
+    ///   height of their text, and the width of their longest line when
+    ///   words do not wrap.
+    ///
+    /// Frames without autofit are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide, its layout or its master cannot be
+    /// read or the slide cannot be updated.
+    pub fn autofit_slide(&mut self, slide: &SlideRef, fonts: &FontSet) -> PptxResult<()> {
+        let boxes = slide_text_boxes(self, slide)?;
+        let mut tree = self.slide_shapes(slide)?;
+        let mut changed = false;
+        for b in &boxes {
+            let Some(shape) = find_autoshape(&mut tree.shapes, b.shape_id) else {
+                continue;
+            };
+            let Some(tf) = shape.text_frame.as_mut() else {
+                continue;
+            };
+            let width = pt_to_emu(b.width);
+            match tf.auto_size {
+                MsoAutoSize::TextToFitShape => {
+                    fit_text(tf, width, pt_to_emu(b.height), &b.defaults, fonts);
+                }
+                MsoAutoSize::ShapeToFitText => {
+                    let extent = measure_text(tf, width, &b.defaults, fonts);
+                    if !tf.word_wrap {
+                        shape.width = extent.width;
+                    } else if shape.width == Emu(0) {
+                        // A placeholder inheriting its box gets one of its own
+                        shape.width = width;
+                    }
+                    if shape.left == Emu(0) && shape.top == Emu(0) {
+                        shape.left = pt_to_emu(b.x);
+                        shape.top = pt_to_emu(b.y);
+                    }
+                    shape.height = extent.height;
+                }
+                MsoAutoSize::None => continue,
+            }
+            changed = true;
+        }
+        if changed {
+            self.update_slide_shapes(slide, &tree)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the autofit setting of every text frame on every slide.
+    ///
+    /// See [`autofit_slide`](Self::autofit_slide).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a slide cannot be read or updated.
+    pub fn autofit_text(&mut self, fonts: &FontSet) -> PptxResult<()> {
+        for slide in self.slides()? {
+            self.autofit_slide(&slide, fonts)?;
+        }
+        Ok(())
+    }
+}
+
+/// The autoshape with id `shape_id`, looking into groups.
+fn find_autoshape(shapes: &mut [Shape], shape_id: ShapeId) -> Option<&mut AutoShape> {
+    shapes.iter_mut().find_map(|shape| match shape {
+        Shape::AutoShape(s) if s.shape_id == shape_id => Some(s.as_mut()),
+        Shape::GroupShape(g) => find_autoshape(&mut g.shapes, shape_id),
+        _ => None,
+    })
+}
@@ -0,0 +1,134 @@
+//! Chart access on a [`Presentation`].
+
+use crate::chart::{replace_series_data, Chart, ChartData};
+use crate::error::{PackageError, PartNotFoundExt, PptxError, PptxResult};
+use crate::opc::constants::{content_type as CT, relationship_type as RT};
+use crate::opc::pack_uri::PackURI;
+use crate::opc::part::Part;
+use crate::shapes::graphfrm::GraphicFrame;
+use crate::shapes::Shape;
+use crate::slide::SlideRef;
+use crate::units::ShapeId;
+
+use super::Presentation;
+
+impl Presentation {
+    /// Parse the chart hosted by a graphic frame on a slide.
+    ///
+    /// The frame is usually taken from [`slide_shapes`](Self::slide_shapes);
+    /// its chart relationship is resolved against the slide and the chart
+    /// part is read with [`Chart::from_xml`]. Returns `Ok(None)` if the
+    /// frame holds no chart or its relationship does not resolve to a part.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or the chart XML
+    /// cannot be parsed.
+    pub fn chart_for(
+        &self,
+        slide_ref: &SlideRef,
+        frame: &GraphicFrame,
+    ) -> PptxResult<Option<Chart>> {
+        let Some(r_id) = frame.chart_r_id.as_deref() else {
+            return Ok(None);
+        };
+        let slide_part = self
+            .package
+            .part(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        let Some(rel) = slide_part.rels.get(r_id) else {
+            return Ok(None);
+        };
+        let chart_partname = rel.target_partname(slide_part.partname.base_uri())?;
+        self.package
+            .part(&chart_partname)
+            .map(|part| Chart::from_xml(&part.blob))
+            .transpose()
+    }
+
+    /// Replace the data of the chart in the graphic frame `shape_id`.
+    ///
+    /// The chart part keeps its formatting: only series names, the cached
+    /// categories and values and their worksheet formulas are rewritten.
+    /// Series are matched by position; extra series are removed and new
+    /// ones copy the formatting of the last existing series. The embedded
+    /// workbook is regenerated from `chart_data` (or added if the chart had
+    /// none).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide has no chart with this shape ID, the
+    /// chart part cannot be found, or the kind of data does not match the
+    /// chart (XY data needs a scatter chart, bubble data a bubble chart).
+    pub fn replace_chart_data(
+        &mut self,
+        slide_ref: &SlideRef,
+        shape_id: ShapeId,
+        chart_data: &ChartData,
+    ) -> PptxResult<()> {
+        let tree = self.slide_shapes(slide_ref)?;
+        let r_id =
+            find_chart_r_id(&tree.shapes, shape_id).ok_or_else(|| PptxError::InvalidValue {
+                field: "shape_id",
+                value: shape_id.to_string(),
+                expected: "the ID of a chart graphic frame on the slide",
+            })?;
+        let chart_partname = self.related_partname(&slide_ref.partname, &r_id)?;
+
+        let chart_part = self
+            .package
+            .part_mut(&chart_partname)
+            .or_part_not_found(chart_partname.as_str())?;
+        chart_part.blob = replace_series_data(&chart_part.blob, chart_data)?;
+        let workbook_r_id = chart_part
+            .rels
+            .all_by_reltype(RT::PACKAGE)
+            .into_iter()
+            .find(|rel| !rel.is_external)
+            .map(|rel| rel.r_id.to_string());
+
+        let xlsx_bytes = crate::chart::xlsx::generate_xlsx(chart_data)?;
+        match workbook_r_id {
+            Some(r_id) => {
+                let xlsx_partname = self.related_partname(&chart_partname, &r_id)?;
+                self.package
+                    .part_mut(&xlsx_partname)
+                    .or_part_not_found(xlsx_partname.as_str())?
+                    .blob = xlsx_bytes;
+            }
+            None => {
+                let xlsx_partname = self.package.next_partname("/ppt/charts/chart{}.xlsx")?;
+                let target_ref = xlsx_partname.relative_ref(chart_partname.base_uri());
+                self.package
+                    .put_part(Part::new(xlsx_partname, CT::SML_SHEET, xlsx_bytes));
+                self.package
+                    .part_mut(&chart_partname)
+                    .or_part_not_found(chart_partname.as_str())?
+                    .rels
+                    .add_relationship(RT::PACKAGE, &target_ref, false);
+            }
+        }
+        Ok(())
+    }
+
+    /// The partname targeted by relationship `r_id` of part `source`.
+    fn related_partname(&self, source: &PackURI, r_id: &str) -> PptxResult<PackURI> {
+        let part = self
+            .package
+            .part(source)
+            .or_part_not_found(source.as_str())?;
+        let rel = part.rels.get(r_id).ok_or_else(|| {
+            PptxError::Package(PackageError::RelationshipNotFound(r_id.to_string()))
+        })?;
+        rel.target_partname(part.partname.base_uri())
+    }
+}
+
+/// The chart relationship ID of graphic frame `shape_id`, searching groups.
+fn find_chart_r_id(shapes: &[Shape], shape_id: ShapeId) -> Option<String> {
+    shapes.iter().find_map(|shape| match shape {
+        Shape::GraphicFrame(frame) if frame.shape_id == shape_id => frame.chart_r_id.clone(),
+        Shape::GroupShape(group) => find_chart_r_id(&group.shapes, shape_id),
+        _ => None,
+    })
+}
@@ -0,0 +1,515 @@
+//! Slide comment operations on a [`Presentation`].
+
+use crate::comment::{
+    add_author, comment_xml, current_timestamp, insert_comment, insert_reply, merge_authors,
+    merge_comment_authors, parse_authors, parse_comment_authors, parse_legacy_comments,
+    parse_modern_comments, remove_legacy_comment, remove_modern_comment, reply_xml,
+    set_comment_rel, set_comment_status, set_slide_moniker, shape_creation_id, slide_creation_id,
+    unique_guid, Anchor, Comment, EMPTY_AUTHOR_LIST, EMPTY_COMMENT_AUTHOR_LIST, EMPTY_COMMENT_LIST,
+};
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::opc::constants::{content_type as CT, relationship_type as RT};
+use crate::opc::pack_uri::PackURI;
+use crate::opc::part::Part;
+use crate::shapes::shapetree::ShapeTree;
+use crate::slide::{parse_slide_ids, SlideRef};
+use crate::units::SlideId;
+
+use super::Presentation;
+
+impl Presentation {
+    /// Get the comments on a slide.
+    ///
+    /// Both legacy comments and modern threaded comments are read, legacy
+    /// ones first. Replies are nested in the `replies` of the comment they
+    /// answer. Returns an empty vector if the slide has no comments.
+    /// # Errors
+    ///
+    /// Returns an error if the slide or a comments part cannot be read.
+    pub fn slide_comments(&self, slide_ref: &SlideRef) -> PptxResult<Vec<Comment>> {
+        let slide_part = self
+            .package
+            .part(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        let mut comments = Vec::new();
+
+        let legacy = slide_part.rels.all_by_reltype(RT::COMMENTS);
+        if !legacy.is_empty() {
+            let authors = match self.presentation_related_part(RT::COMMENT_AUTHORS)? {
+                Some(part) => parse_comment_authors(&part.blob)?,
+                None => Vec::new(),
+            };
+            for rel in legacy {
+                let partname = rel.target_partname(slide_part.partname.base_uri())?;
+                let part = self
+                    .package
+                    .part(&partname)
+                    .or_part_not_found(partname.as_str())?;
+                comments.extend(parse_legacy_comments(&part.blob, &authors)?);
+            }
+        }
+
+        let modern = slide_part.rels.all_by_reltype(RT::MODERN_COMMENTS);
+        if !modern.is_empty() {
+            let authors = match self.presentation_related_part(RT::AUTHORS)? {
+                Some(part) => parse_authors(&part.blob)?,
+                None => Vec::new(),
+            };
+            for rel in modern {
+                let partname = rel.target_partname(slide_part.partname.base_uri())?;
+                let part = self
+                    .package
+                    .part(&partname)
+                    .or_part_not_found(partname.as_str())?;
+                comments.extend(parse_modern_comments(&part.blob, &authors)?);
+            }
+        }
+        Ok(comments)
+    }
+
+    /// Add a threaded comment to a slide, returning its ID.
+    ///
+    /// The comment is written in the modern threaded format. Its author is
+    /// looked up by name in the presentation's author list, and added if
+    /// missing. An empty `date` stands for the current time. With a
+    /// `shape_id` the comment is anchored to that shape, otherwise to the
+    /// slide. The comment's `replies` and `resolved` state are added too,
+    /// so a comment read from one slide can be copied to another.
+    /// # Errors
+    ///
+    /// Returns an error if `shape_id` names no shape on the slide, or if the
+    /// slide or comment parts cannot be updated.
+    pub fn add_comment(&mut self, slide_ref: &SlideRef, comment: &Comment) -> PptxResult<String> {
+        let slide_xml = std::str::from_utf8(self.slide_xml(slide_ref)?)?;
+        let shape = match comment.shape_id {
+            Some(shape_id) => {
+                let shape_xml = ShapeTree::shape_xml(slide_xml.as_bytes(), shape_id)?;
+                Some((shape_id, shape_creation_id(&shape_xml)))
+            }
+            None => None,
+        };
+        let slide_id = parse_slide_ids(&self.presentation_part()?.blob)?
+            .into_iter()
+            .find(|(r_id, _)| r_id == slide_ref.r_id.as_str())
+            .map(|(_, id)| id)
+            .ok_or_else(|| {
+                PptxError::InvalidXml(format!(
+                    "slide {} is not in the slide list",
+                    slide_ref.partname
+                ))
+            })?;
+        let anchor = Anchor {
+            slide_creation_id: slide_creation_id(slide_xml).unwrap_or_else(|| "0".to_string()),
+            slide_id,
+            shape,
+        };
+
+        let author_id = self.comment_author_id(comment)?;
+        let partname = self.or_add_modern_comments_part(slide_ref, &anchor)?;
+        let part = self
+            .package
+            .part_mut(&partname)
+            .or_part_not_found(partname.as_str())?;
+        let created = comment_date(comment);
+        let id = unique_guid(
+            std::str::from_utf8(&part.blob)?,
+            &format!("comment:{created}:{}", comment.text),
+        );
+        part.blob = insert_comment(
+            &part.blob,
+            &comment_xml(&id, &author_id, &created, comment, &anchor),
+        )?;
+
+        for reply in &comment.replies {
+            self.reply_to_comment(slide_ref, &id, reply)?;
+        }
+        if comment.resolved {
+            self.resolve_comment(slide_ref, &id, true)?;
+        }
+        Ok(id)
+    }
+
+    /// Reply to the threaded comment `comment_id` on a slide, returning the
+    /// reply's ID.
+    ///
+    /// The reply's author, text and date are used as in
+    /// [`add_comment`](Self::add_comment); its position, anchor and own
+    /// replies are ignored.
+    /// # Errors
+    ///
+    /// Returns an error if the slide has no threaded comment with this ID,
+    /// or if the comment parts cannot be updated.
+    pub fn reply_to_comment(
+        &mut self,
+        slide_ref: &SlideRef,
+        comment_id: &str,
+        reply: &Comment,
+    ) -> PptxResult<String> {
+        let partname = self
+            .modern_comments_partname(slide_ref)?
+            .ok_or_else(|| no_threaded_comment(comment_id))?;
+        let part = self
+            .package
+            .part(&partname)
+            .or_part_not_found(partname.as_str())?;
+        if !parse_modern_comments(&part.blob, &[])?
+            .iter()
+            .any(|c| c.id.as_deref() == Some(comment_id))
+        {
+            return Err(no_threaded_comment(comment_id));
+        }
+        let author_id = self.comment_author_id(reply)?;
+        let part = self
+            .package
+            .part_mut(&partname)
+            .or_part_not_found(partname.as_str())?;
+        let created = comment_date(reply);
+        let id = unique_guid(
+            std::str::from_utf8(&part.blob)?,
+            &format!("reply:{created}:{}", reply.text),
+        );
+        let xml = reply_xml(&id, &author_id, &created, &reply.text);
+        part.blob = insert_reply(&part.blob, comment_id, &xml)?
+            .ok_or_else(|| no_threaded_comment(comment_id))?;
+        Ok(id)
+    }
+
+    /// Mark the threaded comment `comment_id` on a slide as resolved, or
+    /// with `resolved` false reopen it.
+    /// # Errors
+    ///
+    /// Returns an error if the slide has no threaded comment with this ID,
+    /// or if its comments part cannot be updated.
+    pub fn resolve_comment(
+        &mut self,
+        slide_ref: &SlideRef,
+        comment_id: &str,
+        resolved: bool,
+    ) -> PptxResult<()> {
+        let partname = self
+            .modern_comments_partname(slide_ref)?
+            .ok_or_else(|| no_threaded_comment(comment_id))?;
+        let part = self
+            .package
+            .part_mut(&partname)
+            .or_part_not_found(partname.as_str())?;
+        part.blob = set_comment_status(&part.blob, comment_id, resolved)?
+            .ok_or_else(|| no_threaded_comment(comment_id))?;
+        Ok(())
+    }
+
+    /// Delete the comment or reply `comment_id` from a slide.
+    ///
+    /// Deleting a comment deletes its replies. A comments part left empty
+    /// is removed from the package.
+    /// # Errors
+    ///
+    /// Returns an error if the slide has no comment or reply with this ID,
+    /// or if the slide or its comment parts cannot be updated.
+    pub fn delete_comment(&mut self, slide_ref: &SlideRef, comment_id: &str) -> PptxResult<()> {
+        let slide_part = self
+            .package
+            .part(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        let base_uri = slide_part.partname.base_uri();
+        let mut candidates = Vec::new();
+        for reltype in [RT::MODERN_COMMENTS, RT::COMMENTS] {
+            for rel in slide_part.rels.all_by_reltype(reltype) {
+                let partname = rel.target_partname(base_uri)?;
+                candidates.push((reltype, rel.r_id.to_string(), partname));
+            }
+        }
+
+        for (reltype, r_id, partname) in candidates {
+            let part = self
+                .package
+                .part_mut(&partname)
+                .or_part_not_found(partname.as_str())?;
+            let modern = reltype == RT::MODERN_COMMENTS;
+            let removed = if modern {
+                remove_modern_comment(&part.blob, comment_id)?
+            } else {
+                remove_legacy_comment(&part.blob, comment_id)?
+            };
+            let Some(blob) = removed else {
+                continue;
+            };
+            let now_empty = if modern {
+                parse_modern_comments(&blob, &[])?.is_empty()
+            } else {
+                parse_legacy_comments(&blob, &[])?.is_empty()
+            };
+            part.blob = blob;
+
+            if now_empty {
+                self.package.remove_part(&partname);
+                let slide_part = self
+                    .package
+                    .part_mut(&slide_ref.partname)
+                    .or_part_not_found(slide_ref.partname.as_str())?;
+                slide_part.rels.remove(&r_id);
+                if modern {
+                    slide_part.blob = set_comment_rel(&slide_part.blob, None)?;
+                }
+            }
+            return Ok(());
+        }
+        Err(PptxError::InvalidValue {
+            field: "comment_id",
+            value: comment_id.to_string(),
+            expected: "the ID of a comment on the slide",
+        })
+    }
+
+    /// The GUID of the comment author of `comment` in `ppt/authors.xml`,
+    /// adding the author (and the part) if needed.
+    fn comment_author_id(&mut self, comment: &Comment) -> PptxResult<String> {
+        let partname = self.or_add_authors_part(RT::AUTHORS)?;
+        let part = self
+            .package
+            .part_mut(&partname)
+            .or_part_not_found(partname.as_str())?;
+        if let Some(author) = parse_authors(&part.blob)?
+            .into_iter()
+            .find(|a| a.name == comment.author)
+        {
+            return Ok(author.id);
+        }
+        let (blob, id) = add_author(&part.blob, &comment.author, &comment.author_initials)?;
+        part.blob = blob;
+        Ok(id)
+    }
+
+    /// The partname of the comment author list of type `reltype`
+    /// (`ppt/authors.xml` or the legacy `ppt/commentAuthors.xml`), adding
+    /// an empty list if needed.
+    fn or_add_authors_part(&mut self, reltype: &'static str) -> PptxResult<PackURI> {
+        if let Some(part) = self.presentation_related_part(reltype)? {
+            return Ok(part.partname.clone());
+        }
+        let (partname, content_type, empty) = if reltype == RT::COMMENT_AUTHORS {
+            (
+                "/ppt/commentAuthors.xml",
+                CT::PML_COMMENT_AUTHORS,
+                EMPTY_COMMENT_AUTHOR_LIST,
+            )
+        } else {
+            ("/ppt/authors.xml", CT::PPT_AUTHORS, EMPTY_AUTHOR_LIST)
+        };
+        let partname = PackURI::new(partname)?;
+        let pres_partname = self.presentation_partname()?;
+        let target_ref = partname.relative_ref(pres_partname.base_uri());
+        self.package.put_part(Part::new(
+            partname.clone(),
+            content_type,
+            empty.as_bytes().to_vec(),
+        ));
+        let pres_part = self
+            .package
+            .part_mut(&pres_partname)
+            .or_part_not_found(pres_partname.as_str())?;
+        pres_part.rels.or_add(reltype, &target_ref, false);
+        Ok(partname)
+    }
+
+    /// Point the comments of a slide copied from `source` at authors of
+    /// this presentation, adding the source's authors that are missing.
+    pub(super) fn import_comment_authors(
+        &mut self,
+        source: &Presentation,
+        slide_ref: &SlideRef,
+    ) -> PptxResult<()> {
+        let slide_part = self
+            .package
+            .part(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        let legacy = slide_part
+            .rels
+            .all_by_reltype(RT::COMMENTS)
+            .first()
+            .map(|rel| rel.target_partname(slide_part.partname.base_uri()))
+            .transpose()?;
+
+        if let Some(comments_partname) = legacy {
+            let source_authors = match source.presentation_related_part(RT::COMMENT_AUTHORS)? {
+                Some(part) => parse_comment_authors(&part.blob)?,
+                None => Vec::new(),
+            };
+            let authors_partname = self.or_add_authors_part(RT::COMMENT_AUTHORS)?;
+            self.merge_comment_author_parts(&authors_partname, &comments_partname, |a, c| {
+                merge_comment_authors(a, &source_authors, c)
+            })?;
+        }
+
+        if let Some(comments_partname) = self.modern_comments_partname(slide_ref)? {
+            let source_authors = source
+                .presentation_related_part(RT::AUTHORS)?
+                .map_or_else(|| EMPTY_AUTHOR_LIST.as_bytes(), |part| &part.blob);
+            let authors_partname = self.or_add_authors_part(RT::AUTHORS)?;
+            self.merge_comment_author_parts(&authors_partname, &comments_partname, |a, c| {
+                merge_authors(a, source_authors, c)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Replace an author list and a comments part with the result of
+    /// `merge` on their XML.
+    fn merge_comment_author_parts(
+        &mut self,
+        authors_partname: &PackURI,
+        comments_partname: &PackURI,
+        merge: impl FnOnce(&[u8], &[u8]) -> PptxResult<(Vec<u8>, Vec<u8>)>,
+    ) -> PptxResult<()> {
+        let authors = &self
+            .package
+            .part(authors_partname)
+            .or_part_not_found(authors_partname.as_str())?
+            .blob;
+        let comments = &self
+            .package
+            .part(comments_partname)
+            .or_part_not_found(comments_partname.as_str())?
+            .blob;
+        let (authors, comments) = merge(authors, comments)?;
+        self.package
+            .part_mut(authors_partname)
+            .or_part_not_found(authors_partname.as_str())?
+            .blob = authors;
+        self.package
+            .part_mut(comments_partname)
+            .or_part_not_found(comments_partname.as_str())?
+            .blob = comments;
+        Ok(())
+    }
+
+    /// The modern comments part of a slide, if it has one.
+    fn modern_comments_partname(&self, slide_ref: &SlideRef) -> PptxResult<Option<PackURI>> {
+        let slide_part = self
+            .package
+            .part(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        slide_part
+            .rels
+            .all_by_reltype(RT::MODERN_COMMENTS)
+            .first()
+            .map(|rel| rel.target_partname(slide_part.partname.base_uri()))
+            .transpose()
+    }
+
+    /// The modern comments part of a slide, created for the slide
+    /// identified by `anchor` if it has none yet.
+    fn or_add_modern_comments_part(
+        &mut self,
+        slide_ref: &SlideRef,
+        anchor: &Anchor,
+    ) -> PptxResult<PackURI> {
+        if let Some(partname) = self.modern_comments_partname(slide_ref)? {
+            return Ok(partname);
+        }
+
+        let partname =
+            self.new_modern_comments_partname(anchor.slide_id, &anchor.slide_creation_id)?;
+        let target_ref = partname.relative_ref(slide_ref.partname.base_uri());
+        self.package.put_part(Part::new(
+            partname.clone(),
+            CT::PPT_COMMENTS,
+            EMPTY_COMMENT_LIST.as_bytes().to_vec(),
+        ));
+        let slide_part = self
+            .package
+            .part_mut(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        let r_id = slide_part
+            .rels
+            .or_add(RT::MODERN_COMMENTS, &target_ref, false);
+        slide_part.blob = set_comment_rel(&slide_part.blob, Some(&r_id))?;
+        Ok(partname)
+    }
+
+    /// A free partname for the modern comments part of the slide
+    /// `slide_id`.
+    ///
+    /// The part is named after the slide's ID and creation ID, as
+    /// `PowerPoint` does; if another part already has that name, the next
+    /// free numbered name is used instead.
+    fn new_modern_comments_partname(
+        &self,
+        slide_id: SlideId,
+        slide_creation_id: &str,
+    ) -> PptxResult<PackURI> {
+        let creation_id: u32 = slide_creation_id
+            .parse()
+            .map_err(|_| PptxError::InvalidValue {
+                field: "p14:creationId",
+                value: slide_creation_id.to_string(),
+                expected: "an unsigned 32-bit integer",
+            })?;
+        let partname = PackURI::new(format!(
+            "/ppt/comments/modernComment_{:X}_{creation_id:08X}.xml",
+            slide_id.0
+        ))?;
+        if self.package.part(&partname).is_none() {
+            return Ok(partname);
+        }
+        self.package
+            .next_partname("/ppt/comments/modernComment{}.xml")
+    }
+
+    /// Point the modern comments of a copied slide at the copy.
+    ///
+    /// The comments are anchored to the slide's own ID and creation ID, and
+    /// the part is renamed after them.
+    pub(super) fn reanchor_modern_comments(&mut self, slide_ref: &SlideRef) -> PptxResult<()> {
+        let Some(old_partname) = self.modern_comments_partname(slide_ref)? else {
+            return Ok(());
+        };
+        let slide_id = self.slide_id(slide_ref)?;
+        let creation_id = slide_creation_id(std::str::from_utf8(self.slide_xml(slide_ref)?)?)
+            .unwrap_or_else(|| "0".to_string());
+
+        let mut part = self
+            .package
+            .remove_part(&old_partname)
+            .or_part_not_found(old_partname.as_str())?;
+        let partname = match self.new_modern_comments_partname(slide_id, &creation_id) {
+            Ok(partname) => partname,
+            Err(e) => {
+                self.package.put_part(part);
+                return Err(e);
+            }
+        };
+        part.blob = set_slide_moniker(&part.blob, slide_id, &creation_id)?;
+        part.partname = partname.clone();
+        self.package.put_part(part);
+
+        let target_ref = partname.relative_ref(slide_ref.partname.base_uri());
+        let slide_part = self
+            .package
+            .part_mut(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        for rel in &mut slide_part.rels.rels {
+            if rel.rel_type == RT::MODERN_COMMENTS {
+                rel.target_ref.clone_from(&target_ref);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The date to write for `comment`: its own, or the current time.
+fn comment_date(comment: &Comment) -> String {
+    if comment.date.is_empty() {
+        current_timestamp()
+    } else {
+        comment.date.clone()
+    }
+}
+
+fn no_threaded_comment(comment_id: &str) -> PptxError {
+    PptxError::InvalidValue {
+        field: "comment_id",
+        value: comment_id.to_string(),
+        expected: "the ID of a threaded comment on the slide",
+    }
+}
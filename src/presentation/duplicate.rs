@@ -0,0 +1,133 @@
+//! Slide duplication on a [`Presentation`].
+
+use std::collections::HashMap;
+
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
+use crate::opc::package::OpcPackage;
+use crate::opc::part::Part;
+use crate::opc::relationship::{Relationship, Relationships};
+use crate::slide::SlideRef;
+
+use super::Presentation;
+
+/// Relationship types whose targets belong to the source part alone.
+///
+/// When a part is copied these targets are copied along with it; all other
+/// targets (layouts, images, media, the notes master, ...) are shared by
+/// the original and the copy.
+const OWNED_RELATIONSHIPS: &[&str] = &[
+    RT::NOTES_SLIDE,
+    RT::CHART,
+    RT::CHART_USER_SHAPES,
+    RT::CHART_STYLE,
+    RT::CHART_COLOR_STYLE,
+    RT::COMMENTS,
+    RT::MODERN_COMMENTS,
+    RT::PACKAGE,
+];
+
+impl Presentation {
+    /// Duplicate a slide and insert the copy at `index`.
+    ///
+    /// With `index` of `None` the copy is placed directly after the
+    /// original. The slide part and its notes slide are copied, as are
+    /// parts that must not be shared between slides: charts together with
+    /// their embedded workbooks, and comments. Layout, images and media
+    /// are shared with the original slide.
+    ///
+    /// Returns a `SlideRef` for the new slide.
+    /// # Errors
+    ///
+    /// Returns an error if the slide is not in the presentation, if `index`
+    /// is greater than the slide count, or if a related part is missing.
+    pub fn duplicate_slide(
+        &mut self,
+        slide_ref: &SlideRef,
+        index: Option<usize>,
+    ) -> PptxResult<SlideRef> {
+        let source_index = self.slide_index(slide_ref)?;
+        let count = self.slide_count()?;
+        let index = index.unwrap_or(source_index + 1);
+        if index > count {
+            return Err(PptxError::InvalidXml(format!(
+                "slide index {index} out of range (presentation has {count} slides)"
+            )));
+        }
+
+        let mut copies = HashMap::new();
+        let partname = copy_part(&mut self.package, &slide_ref.partname, &mut copies)?;
+        let new_ref = self.append_slide_part(partname)?;
+        self.reanchor_modern_comments(&new_ref)?;
+        self.move_slide(count, index)?;
+        Ok(new_ref)
+    }
+}
+
+/// `/ppt/charts/chart3.xml` -> `/ppt/charts/chart{}.xml`.
+pub(super) fn partname_template(partname: &PackURI) -> String {
+    let name = partname.as_str();
+    let stem_end = name
+        .rfind('.')
+        .filter(|&i| i > name.rfind('/').unwrap_or(0))
+        .unwrap_or(name.len());
+    let stem = name[..stem_end].trim_end_matches(|c: char| c.is_ascii_digit());
+    format!("{stem}{{}}{}", &name[stem_end..])
+}
+
+/// Copy `source` to a new partname in the same folder.
+///
+/// Targets of [`OWNED_RELATIONSHIPS`] are copied recursively; targets found
+/// in `copies` (source partname -> copy) are re-pointed to their copy, so a
+/// notes slide copied along with a slide refers back to the new slide.
+/// Relationship IDs are kept, so the copied XML needs no changes.
+fn copy_part(
+    package: &mut OpcPackage,
+    source: &PackURI,
+    copies: &mut HashMap<PackURI, PackURI>,
+) -> PptxResult<PackURI> {
+    let part = package
+        .part(source)
+        .or_part_not_found(source.as_str())?
+        .clone();
+    let partname = package.next_partname(&partname_template(source))?;
+    copies.insert(source.clone(), partname.clone());
+    // Reserve the partname so parts copied below do not claim it
+    package.put_part(Part::new(
+        partname.clone(),
+        part.content_type.clone(),
+        Vec::new(),
+    ));
+
+    let mut rels = Relationships::new(partname.base_uri());
+    for rel in part.rels.iter() {
+        let target_ref = if rel.is_external {
+            rel.target_ref.clone()
+        } else {
+            let target = rel.target_partname(part.rels.base_uri())?;
+            let target = match copies.get(&target) {
+                Some(copy) => copy.clone(),
+                None if OWNED_RELATIONSHIPS.contains(&rel.rel_type.as_ref()) => {
+                    copy_part(package, &target, copies)?
+                }
+                None => target,
+            };
+            target.relative_ref(partname.base_uri())
+        };
+        rels.add(Relationship::new(
+            rel.r_id.clone(),
+            rel.rel_type.clone(),
+            target_ref,
+            rel.is_external,
+        ));
+    }
+
+    package.put_part(Part::with_rels(
+        partname.clone(),
+        part.content_type,
+        part.blob,
+        rels,
+    ));
+    Ok(partname)
+}
@@ -1,5 +1,6 @@
 use crate::error::PptxResult;
-use crate::export::HtmlExporter;
+use crate::export::html::{HtmlExportOptions, HtmlFile};
+use crate::export::{HtmlExporter, MarkdownExporter, PdfExporter, SvgExporter};
 
 use super::Presentation;
 
@@ -8,13 +9,65 @@ impl Presentation {
     /// Export the presentation as a self-contained HTML string.
     ///
     /// Each slide becomes a `<div class="slide">` element with shapes rendered
-    /// using absolute CSS positioning. Text formatting (bold, italic, color,
-    /// size) is preserved. Images with cached data are embedded as base64
-    /// data URIs.
+    /// using absolute CSS positioning, including what they inherit from
+    /// their layout and master. Shape geometry and charts are inline SVG,
+    /// tables are `<table>` markup, and speaker notes follow each slide.
+    /// Images are embedded as base64 data URIs.
     /// # Errors
     ///
     /// Returns an error if the presentation cannot be exported.
     pub fn export_html(&self) -> PptxResult<String> {
         HtmlExporter::new(self).export()
     }
+
+    /// Export the presentation as HTML files laid out as `options` ask:
+    /// one document or one per slide, with images embedded or as separate
+    /// files under `media/`. Paths are relative to the output directory.
+    /// # Errors
+    ///
+    /// Returns an error if the presentation cannot be exported.
+    pub fn export_html_files(&self, options: HtmlExportOptions) -> PptxResult<Vec<HtmlFile>> {
+        HtmlExporter::with_options(self, options).export_files()
+    }
+
+    /// Export the text of the presentation as a Markdown outline.
+    ///
+    /// Slide titles become headings, body text bullet lists indented by
+    /// level, tables pipe tables, pictures images with their description as
+    /// alt text, and speaker notes quotes; slides are separated by `---`.
+    /// The outline can be read back with
+    /// [`add_slides_from_markdown`](Self::add_slides_from_markdown).
+    /// # Errors
+    ///
+    /// Returns an error if the presentation cannot be exported.
+    pub fn export_markdown(&self) -> PptxResult<String> {
+        MarkdownExporter::new(self).export()
+    }
+
+    /// Export the presentation as a PDF document, one page per slide.
+    ///
+    /// Shapes are drawn as vector graphics with their geometry, fills and
+    /// outlines; text is wrapped inside its shape and drawn with the fonts
+    /// embedded in the presentation, or else set in the standard PDF
+    /// typefaces closest to the fonts used. Pictures are embedded as JPEG
+    /// or PNG images. Use [`PdfExporter`] to supply more fonts.
+    /// # Errors
+    ///
+    /// Returns an error if the presentation cannot be exported.
+    pub fn export_pdf(&self) -> PptxResult<Vec<u8>> {
+        PdfExporter::new(self).export()
+    }
+
+    /// Export each slide as a standalone SVG document, in slide order.
+    ///
+    /// Shapes are drawn from their geometry with gradient and pattern
+    /// fills as SVG definitions, text is set as `<text>` and `<tspan>`
+    /// elements with its font attributes, and pictures are embedded as data
+    /// URIs cropped with clip paths.
+    /// # Errors
+    ///
+    /// Returns an error if the presentation cannot be exported.
+    pub fn export_svg(&self) -> PptxResult<Vec<String>> {
+        SvgExporter::new(self).export()
+    }
 }
@@ -0,0 +1,482 @@
+//! Importing slides from another [`Presentation`].
+
+use std::collections::HashMap;
+
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::media::{Image, Video};
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
+use crate::opc::package::OpcPackage;
+use crate::opc::part::Part;
+use crate::opc::relationship::{Relationship, Relationships};
+use crate::slide::{get_layout_by_name, parse_layout_name, SlideLayoutRef, SlideRef};
+use crate::xml_util::splice::{find_element, splice};
+use crate::xml_util::xml_escape;
+
+use super::duplicate::partname_template;
+use super::Presentation;
+
+/// Relationship types that are re-pointed but never copied on import.
+///
+/// Their targets are mapped to a destination part chosen up front (the
+/// layout, master and notes master) or, for links to other slides of the
+/// source deck, dropped.
+const LINKED_RELATIONSHIPS: &[&str] = &[
+    RT::SLIDE,
+    RT::SLIDE_LAYOUT,
+    RT::SLIDE_MASTER,
+    RT::NOTES_MASTER,
+];
+
+/// Lowest ID of a `<p:sldMasterId>` or `<p:sldLayoutId>`.
+const MIN_MASTER_ID: u32 = 2_147_483_648;
+
+/// How an imported slide is formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportFormatting {
+    /// Use the destination layout with the same name as the source layout,
+    /// so the slide takes on the destination theme.
+    #[default]
+    UseDestinationTheme,
+    /// Bring the source layout, slide master and theme along, so the slide
+    /// looks as it did in the source deck.
+    KeepSourceFormatting,
+}
+
+/// Options for [`Presentation::import_slide`].
+///
+/// # Examples
+///
+/// ```
+/// use pptx::presentation::{ImportFormatting, ImportOptions};
+///
+/// let options = ImportOptions::new()
+///     .with_formatting(ImportFormatting::KeepSourceFormatting)
+///     .with_index(0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ImportOptions {
+    /// How the imported slide is formatted.
+    pub formatting: ImportFormatting,
+    /// 0-based position of the imported slide; `None` appends it.
+    pub index: Option<usize>,
+}
+
+impl ImportOptions {
+    /// Create options that append the slide using the destination theme.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            formatting: ImportFormatting::UseDestinationTheme,
+            index: None,
+        }
+    }
+
+    /// Set how the imported slide is formatted.
+    #[must_use]
+    pub const fn with_formatting(mut self, formatting: ImportFormatting) -> Self {
+        self.formatting = formatting;
+        self
+    }
+
+    /// Set the 0-based position of the imported slide.
+    #[must_use]
+    pub const fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+}
+
+impl Presentation {
+    /// Copy a slide from another presentation into this one.
+    ///
+    /// The slide is copied together with its notes slide, charts and their
+    /// embedded workbooks, and any other parts it owns. Images and media are
+    /// added with SHA1 deduplication, so importing several slides that share
+    /// a picture stores it once. Links to other slides of the source deck
+    /// are dropped. Comments keep their authors, who are added to the
+    /// author lists of this presentation as needed.
+    ///
+    /// With [`ImportFormatting::UseDestinationTheme`] the slide is attached
+    /// to the destination layout of the same name, or to the first layout
+    /// if there is none. With [`ImportFormatting::KeepSourceFormatting`]
+    /// the source layout is brought along with its slide master and theme;
+    /// a master or layout imported earlier is reused.
+    ///
+    /// Returns a `SlideRef` for the new slide.
+    /// # Errors
+    ///
+    /// Returns an error if `index` is greater than the slide count, if the
+    /// destination has no layouts, or if a part of the source slide is
+    /// missing.
+    pub fn import_slide(
+        &mut self,
+        source: &Presentation,
+        slide_ref: &SlideRef,
+        options: ImportOptions,
+    ) -> PptxResult<SlideRef> {
+        let count = self.slide_count()?;
+        let index = options.index.unwrap_or(count);
+        if index > count {
+            return Err(PptxError::InvalidXml(format!(
+                "slide index {index} out of range (presentation has {count} slides)"
+            )));
+        }
+
+        let slide_part = source
+            .package
+            .part(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        let base_uri = slide_part.partname.base_uri();
+        let source_layout = slide_part
+            .rels
+            .by_reltype(RT::SLIDE_LAYOUT)?
+            .target_partname(base_uri)?;
+
+        let mut map = HashMap::new();
+        let layout = match options.formatting {
+            ImportFormatting::UseDestinationTheme => {
+                self.matching_layout(source, &source_layout)?.partname
+            }
+            ImportFormatting::KeepSourceFormatting => {
+                self.import_layout(source, &source_layout, &mut map)?
+            }
+        };
+        map.insert(source_layout, layout);
+
+        if let Some(notes_rel) = slide_part.rels.all_by_reltype(RT::NOTES_SLIDE).first() {
+            let notes_partname = notes_rel.target_partname(base_uri)?;
+            let notes_part = source
+                .package
+                .part(&notes_partname)
+                .or_part_not_found(notes_partname.as_str())?;
+            if let Some(rel) = notes_part.rels.all_by_reltype(RT::NOTES_MASTER).first() {
+                let source_master = rel.target_partname(notes_part.partname.base_uri())?;
+                map.insert(source_master, self.notes_master_or_create()?.partname);
+            }
+        }
+
+        let partname = import_part(
+            &mut self.package,
+            &source.package,
+            &slide_ref.partname,
+            &mut map,
+        )?;
+        let new_ref = self.append_slide_part(partname)?;
+        self.import_comment_authors(source, &new_ref)?;
+        self.reanchor_modern_comments(&new_ref)?;
+        self.move_slide(count, index)?;
+        Ok(new_ref)
+    }
+
+    /// The destination layout named like `source_layout`, else the first.
+    fn matching_layout(
+        &self,
+        source: &Presentation,
+        source_layout: &PackURI,
+    ) -> PptxResult<SlideLayoutRef> {
+        let layout_part = source
+            .package
+            .part(source_layout)
+            .or_part_not_found(source_layout.as_str())?;
+        let name = parse_layout_name(&layout_part.blob)?;
+        let layouts = self.slide_layouts()?;
+        get_layout_by_name(&layouts, &name)
+            .or_else(|| layouts.first())
+            .cloned()
+            .ok_or_else(|| PptxError::InvalidXml("presentation has no slide layouts".to_string()))
+    }
+
+    /// Bring `source_layout`, its slide master and theme into this package.
+    ///
+    /// Reuses a master whose XML (ignoring its layout list) and theme match
+    /// the source master, and a layout of that master whose XML matches.
+    fn import_layout(
+        &mut self,
+        source: &Presentation,
+        source_layout: &PackURI,
+        map: &mut HashMap<PackURI, PackURI>,
+    ) -> PptxResult<PackURI> {
+        let layout_part = source
+            .package
+            .part(source_layout)
+            .or_part_not_found(source_layout.as_str())?;
+        let source_master = layout_part
+            .rels
+            .by_reltype(RT::SLIDE_MASTER)?
+            .target_partname(layout_part.partname.base_uri())?;
+        let master_part = source
+            .package
+            .part(&source_master)
+            .or_part_not_found(source_master.as_str())?;
+
+        let source_key = master_key(&source.package, master_part)?;
+        let mut existing_master = None;
+        for master in self.slide_masters()? {
+            let part = self
+                .package
+                .part(&master.partname)
+                .or_part_not_found(master.partname.as_str())?;
+            if master_key(&self.package, part)? == source_key {
+                existing_master = Some(master.partname);
+                break;
+            }
+        }
+
+        let master = if let Some(master) = existing_master {
+            // An identical layout of the existing master is reused
+            let master_str = master.as_str();
+            let reused = self.slide_layouts()?.into_iter().find(|l| {
+                l.slide_master_part_name.as_deref() == Some(master_str)
+                    && self
+                        .package
+                        .part(&l.partname)
+                        .is_some_and(|p| p.blob == layout_part.blob)
+            });
+            if let Some(layout) = reused {
+                return Ok(layout.partname);
+            }
+            master
+        } else {
+            let master = import_part(&mut self.package, &source.package, &source_master, map)?;
+            let part = self
+                .package
+                .part_mut(&master)
+                .or_part_not_found(master.as_str())?;
+            part.blob = without_layout_ids(&part.blob)?.into_bytes();
+            self.register_slide_master(&master)?;
+            master
+        };
+        map.insert(source_master, master.clone());
+
+        let layout = import_part(&mut self.package, &source.package, source_layout, map)?;
+        let id = self.next_master_or_layout_id()?;
+        let master_part = self
+            .package
+            .part_mut(&master)
+            .or_part_not_found(master.as_str())?;
+        let r_id = master_part.rels.add_relationship(
+            RT::SLIDE_LAYOUT,
+            layout.relative_ref(master.base_uri()),
+            false,
+        );
+        master_part.blob = add_layout_id(&master_part.blob, id, &r_id)?;
+        Ok(layout)
+    }
+
+    /// Add a `<p:sldMasterId>` and relationship for an imported master.
+    fn register_slide_master(&mut self, master: &PackURI) -> PptxResult<()> {
+        let id = self.next_master_or_layout_id()?;
+        let pres_partname = self.presentation_partname()?;
+        let target_ref = master.relative_ref(pres_partname.base_uri());
+        let pres_part = self
+            .package
+            .part_mut(&pres_partname)
+            .or_part_not_found(pres_partname.as_str())?;
+        let r_id = pres_part
+            .rels
+            .add_relationship(RT::SLIDE_MASTER, target_ref, false);
+        let xml = std::str::from_utf8(&pres_part.blob)?;
+        let entry = format!(r#"<p:sldMasterId id="{id}" r:id="{r_id}"/>"#);
+        let updated = insert_into_list(xml, "p:sldMasterIdLst", &entry).ok_or_else(|| {
+            PptxError::InvalidXml("no sldMasterIdLst found in presentation XML".to_string())
+        })?;
+        pres_part.blob = updated.into_bytes();
+        Ok(())
+    }
+
+    /// Masters and layouts share one ID space, starting at 2^31.
+    fn next_master_or_layout_id(&self) -> PptxResult<u32> {
+        let mut max = max_id(
+            std::str::from_utf8(&self.presentation_part()?.blob)?,
+            "<p:sldMasterId ",
+        );
+        for master in self.slide_masters()? {
+            let part = self
+                .package
+                .part(&master.partname)
+                .or_part_not_found(master.partname.as_str())?;
+            max = max.max(max_id(std::str::from_utf8(&part.blob)?, "<p:sldLayoutId "));
+        }
+        Ok(max.map_or(MIN_MASTER_ID, |m| m.max(MIN_MASTER_ID - 1) + 1))
+    }
+}
+
+/// Copy `source` from the `src` package into `dest`, with the parts it
+/// relates to.
+///
+/// Targets in `map` (source partname -> destination partname) are
+/// re-pointed; images and media are added with deduplication; targets of
+/// [`LINKED_RELATIONSHIPS`] not in `map` are dropped along with the
+/// elements that refer to them; everything else is copied recursively.
+fn import_part(
+    dest: &mut OpcPackage,
+    src: &OpcPackage,
+    source: &PackURI,
+    map: &mut HashMap<PackURI, PackURI>,
+) -> PptxResult<PackURI> {
+    let part = src.part(source).or_part_not_found(source.as_str())?;
+    let partname = dest.next_partname(&partname_template(source))?;
+    map.insert(source.clone(), partname.clone());
+    // Reserve the partname so parts imported below do not claim it
+    dest.put_part(Part::new(
+        partname.clone(),
+        part.content_type.clone(),
+        Vec::new(),
+    ));
+
+    let mut rels = Relationships::new(partname.base_uri());
+    let mut dropped = Vec::new();
+    for rel in part.rels.iter() {
+        let target_ref = if rel.is_external {
+            rel.target_ref.clone()
+        } else {
+            let target = rel.target_partname(part.rels.base_uri())?;
+            let rel_type = rel.rel_type.as_ref();
+            let target = if let Some(mapped) = map.get(&target) {
+                mapped.clone()
+            } else if LINKED_RELATIONSHIPS.contains(&rel_type) {
+                dropped.push(rel.r_id.to_string());
+                continue;
+            } else if rel_type == RT::IMAGE {
+                let media = src.part(&target).or_part_not_found(target.as_str())?;
+                let image = Image::from_bytes(media.blob.clone(), &media.content_type);
+                dest.or_add_image_part(&image)?.0
+            } else if [RT::VIDEO, RT::AUDIO, RT::MEDIA].contains(&rel_type) {
+                let media = src.part(&target).or_part_not_found(target.as_str())?;
+                let video = Video::from_bytes(media.blob.clone(), &media.content_type);
+                dest.or_add_media_part(&video)?.0
+            } else {
+                import_part(dest, src, &target, map)?
+            };
+            target.relative_ref(partname.base_uri())
+        };
+        rels.add(Relationship::new(
+            rel.r_id.clone(),
+            rel.rel_type.clone(),
+            target_ref,
+            rel.is_external,
+        ));
+    }
+
+    let mut blob = part.blob.clone();
+    if !dropped.is_empty() {
+        let mut xml = String::from_utf8(blob)?;
+        for r_id in dropped {
+            xml = without_references(&xml, &r_id);
+        }
+        blob = xml.into_bytes();
+    }
+    dest.put_part(Part::with_rels(
+        partname.clone(),
+        part.content_type.clone(),
+        blob,
+        rels,
+    ));
+    Ok(partname)
+}
+
+/// `xml` without the elements whose `r:id` is `r_id`, such as an
+/// `<a:hlinkClick>` jumping to a slide that was left behind.
+fn without_references(xml: &str, r_id: &str) -> String {
+    let needle = format!(r#" r:id="{}""#, xml_escape(r_id));
+    let mut xml = xml.to_string();
+    let mut from = 0;
+    while let Some(pos) = xml[from..].find(&needle).map(|i| from + i) {
+        // Only attributes count, not text that happens to match
+        let element = xml[..pos]
+            .rfind('<')
+            .filter(|&start| !xml[start..pos].contains('>'))
+            .and_then(|start| {
+                let qname_end = xml[start + 1..pos]
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .map_or(pos, |i| start + 1 + i);
+                find_element(&xml, &xml[start + 1..qname_end], start)
+            });
+        match element {
+            Some(element) => {
+                from = element.start;
+                xml = splice(&xml, element, "");
+            }
+            None => from = pos + needle.len(),
+        }
+    }
+    xml
+}
+
+/// What identifies a slide master for reuse: its XML without the layout
+/// list, and its theme.
+fn master_key(package: &OpcPackage, master: &Part) -> PptxResult<(String, Option<Vec<u8>>)> {
+    let theme = match master.rels.all_by_reltype(RT::THEME).first() {
+        Some(rel) => {
+            let partname = rel.target_partname(master.partname.base_uri())?;
+            package.part(&partname).map(|p| p.blob.clone())
+        }
+        None => None,
+    };
+    Ok((without_layout_ids(&master.blob)?, theme))
+}
+
+/// Slide master XML with an empty `<p:sldLayoutIdLst>`.
+fn without_layout_ids(master_xml: &[u8]) -> PptxResult<String> {
+    let xml = std::str::from_utf8(master_xml)?;
+    let Some(start) = xml.find("<p:sldLayoutIdLst") else {
+        return Ok(xml.to_string());
+    };
+    let end = match xml[start..].find("</p:sldLayoutIdLst>") {
+        Some(i) => start + i + "</p:sldLayoutIdLst>".len(),
+        None => xml[start..].find("/>").map_or(xml.len(), |i| start + i + 2),
+    };
+    Ok(format!(
+        "{}<p:sldLayoutIdLst></p:sldLayoutIdLst>{}",
+        &xml[..start],
+        &xml[end..]
+    ))
+}
+
+/// Append a `<p:sldLayoutId>` to a slide master's layout list.
+fn add_layout_id(master_xml: &[u8], id: u32, r_id: &str) -> PptxResult<Vec<u8>> {
+    let xml = std::str::from_utf8(master_xml)?;
+    let entry = format!(r#"<p:sldLayoutId id="{id}" r:id="{r_id}"/>"#);
+    if let Some(updated) = insert_into_list(xml, "p:sldLayoutIdLst", &entry) {
+        return Ok(updated.into_bytes());
+    }
+    // No list yet: it follows <p:clrMap>
+    let clr_map = xml
+        .find("<p:clrMap ")
+        .and_then(|start| xml[start..].find("/>").map(|i| start + i + 2))
+        .ok_or_else(|| PptxError::InvalidXml("no clrMap found in slide master".to_string()))?;
+    Ok(format!(
+        "{}<p:sldLayoutIdLst>{entry}</p:sldLayoutIdLst>{}",
+        &xml[..clr_map],
+        &xml[clr_map..]
+    )
+    .into_bytes())
+}
+
+/// Insert `entry` at the end of the list element `tag`, if present.
+fn insert_into_list(xml: &str, tag: &str, entry: &str) -> Option<String> {
+    let close = format!("</{tag}>");
+    if let Some(pos) = xml.find(&close) {
+        return Some(format!("{}{entry}{}", &xml[..pos], &xml[pos..]));
+    }
+    let empty = format!("<{tag}/>");
+    xml.find(&empty).map(|pos| {
+        format!(
+            "{}<{tag}>{entry}{close}{}",
+            &xml[..pos],
+            &xml[pos + empty.len()..]
+        )
+    })
+}
+
+/// The largest `id` attribute of elements starting with `tag_start`.
+fn max_id(xml: &str, tag_start: &str) -> Option<u32> {
+    xml.match_indices(tag_start)
+        .filter_map(|(pos, _)| {
+            let tag = &xml[pos..pos + xml[pos..].find('>')?];
+            let value = tag.split(" id=\"").nth(1)?;
+            value[..value.find('"')?].parse().ok()
+        })
+        .max()
+}
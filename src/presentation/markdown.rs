@@ -0,0 +1,252 @@
+//! Building slides from a Markdown outline.
+
+use crate::enums::shapes::PpPlaceholderType;
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::export::markdown::{is_body, parse_outline, OutlineSlide};
+use crate::shapes::placeholder::PlaceholderFormat;
+use crate::shapes::{Shape, ShapeTree};
+use crate::slide::{get_layout_by_name, SlideLayoutRef, SlideRef};
+use crate::text::{Paragraph, TextFrame};
+use crate::units::Emu;
+
+use super::Presentation;
+
+/// Layout the outline's slides are created with.
+const OUTLINE_LAYOUT: &str = "Title and Content";
+
+/// A placeholder of the outline layout.
+struct LayoutPlaceholder {
+    format: PlaceholderFormat,
+    name: String,
+    /// Left, top, width and height, from the layout or its master.
+    bounds: (Emu, Emu, Emu, Emu),
+}
+
+impl Presentation {
+    /// Create a presentation from a Markdown outline.
+    ///
+    /// See [`add_slides_from_markdown`](Self::add_slides_from_markdown) for
+    /// the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default template cannot be loaded or the
+    /// slides cannot be created.
+    pub fn from_markdown(markdown: &str) -> PptxResult<Self> {
+        let mut prs = Self::new()?;
+        prs.add_slides_from_markdown(markdown)?;
+        Ok(prs)
+    }
+
+    /// Append slides built from a Markdown outline, using the "Title and
+    /// Content" layout.
+    ///
+    /// A `#` or `##` heading or a `---` line starts a new slide; headings
+    /// become titles, list items bulleted body paragraphs (two spaces of
+    /// indentation per level), other lines body paragraphs without a
+    /// bullet, pipe tables tables, and `>` quotes speaker notes. This is the
+    /// format written by [`export_markdown`](Self::export_markdown); see
+    /// [`crate::export::markdown`] for details.
+    ///
+    /// Returns the new slides in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the presentation has no "Title and Content"
+    /// layout or the slides cannot be created.
+    pub fn add_slides_from_markdown(&mut self, markdown: &str) -> PptxResult<Vec<SlideRef>> {
+        let layouts = self.slide_layouts()?;
+        let layout = get_layout_by_name(&layouts, OUTLINE_LAYOUT)
+            .ok_or_else(|| PptxError::InvalidValue {
+                field: "layout",
+                value: OUTLINE_LAYOUT.to_string(),
+                expected: "a slide layout of the presentation",
+            })?
+            .clone();
+        let (title, body) = self.outline_placeholders(&layout)?;
+
+        let mut slides = Vec::new();
+        for outline in parse_outline(markdown) {
+            let slide = self.add_slide(&layout)?;
+            self.fill_outline_slide(&slide, &outline, title.as_ref(), body.as_ref())?;
+            slides.push(slide);
+        }
+        Ok(slides)
+    }
+
+    /// The title and body placeholders of `layout`.
+    fn outline_placeholders(
+        &self,
+        layout: &SlideLayoutRef,
+    ) -> PptxResult<(Option<LayoutPlaceholder>, Option<LayoutPlaceholder>)> {
+        let layout_tree = ShapeTree::from_slide_xml(
+            &self
+                .package
+                .part(&layout.partname)
+                .or_part_not_found(layout.partname.as_str())?
+                .blob,
+        )?;
+        let master_tree = match self.slide_master_for_layout(layout)? {
+            Some(master) => Some(ShapeTree::from_slide_xml(
+                &self
+                    .package
+                    .part(&master.partname)
+                    .or_part_not_found(master.partname.as_str())?
+                    .blob,
+            )?),
+            None => None,
+        };
+
+        let find = |wanted: fn(&PlaceholderFormat) -> bool, master_type: PpPlaceholderType| {
+            let shape = layout_tree
+                .shapes
+                .iter()
+                .find(|s| s.placeholder().is_some_and(wanted))?;
+            let mut bounds = (shape.left(), shape.top(), shape.width(), shape.height());
+            if bounds.2 == Emu(0) || bounds.3 == Emu(0) {
+                // The layout placeholder inherits its box from the master
+                if let Some(inherited) = master_tree.as_ref().and_then(|tree| {
+                    tree.shapes.iter().find(|s| {
+                        s.placeholder()
+                            .is_some_and(|ph| ph.placeholder_type() == Some(master_type))
+                    })
+                }) {
+                    bounds = (
+                        inherited.left(),
+                        inherited.top(),
+                        inherited.width(),
+                        inherited.height(),
+                    );
+                }
+            }
+            let name = shape.name().trim_end_matches(|c: char| c.is_ascii_digit());
+            Some(LayoutPlaceholder {
+                format: shape.placeholder()?.clone(),
+                name: name.trim_end().to_string(),
+                bounds,
+            })
+        };
+        Ok((
+            find(PlaceholderFormat::is_title, PpPlaceholderType::Title),
+            find(|ph| is_body(ph) && ph.idx.0 > 0, PpPlaceholderType::Body),
+        ))
+    }
+
+    fn fill_outline_slide(
+        &mut self,
+        slide: &SlideRef,
+        outline: &OutlineSlide,
+        title: Option<&LayoutPlaceholder>,
+        body: Option<&LayoutPlaceholder>,
+    ) -> PptxResult<()> {
+        let mut xml = self.slide_xml(slide)?.to_vec();
+        if let (Some(text), Some(title)) = (&outline.title, title) {
+            xml = ShapeTree::add_placeholder(
+                &xml,
+                &title.format,
+                &title.name,
+                None,
+                &placeholder_text(std::slice::from_ref(text)),
+            )?;
+        }
+
+        // Body text and tables share the body area, one band each
+        let (left, top, width, height) = match body {
+            Some(body) => body.bounds,
+            None => {
+                let (w, h) = self.slide_size()?.unwrap_or((9_144_000, 6_858_000));
+                (Emu(w / 10), Emu(h / 4), Emu(w * 8 / 10), Emu(h * 2 / 3))
+            }
+        };
+        let text_bands = usize::from(!outline.body.is_empty());
+        let bands = i64::try_from(text_bands + outline.tables.len())
+            .unwrap_or(1)
+            .max(1);
+        let band = |i: usize| {
+            let i = i64::try_from(i).unwrap_or(0);
+            (
+                left,
+                Emu(top.0 + height.0 * i / bands),
+                width,
+                Emu(height.0 / bands),
+            )
+        };
+        if let (false, Some(body)) = (outline.body.is_empty(), body) {
+            let bounds = (!outline.tables.is_empty()).then(|| band(0));
+            xml = ShapeTree::add_placeholder(
+                &xml,
+                &body.format,
+                &body.name,
+                bounds,
+                &placeholder_text(&outline.body),
+            )?;
+        }
+        for (i, table) in outline.tables.iter().enumerate() {
+            let rows = u32::try_from(table.len()).unwrap_or(u32::MAX);
+            let cols = table.iter().map(Vec::len).max().unwrap_or(1);
+            let cols = u32::try_from(cols).unwrap_or(u32::MAX);
+            let (left, top, width, height) = band(text_bands + i);
+            xml = ShapeTree::add_table(&xml, rows, cols, left, top, width, height)?;
+        }
+        *self.slide_xml_mut(slide)? = xml;
+
+        if !outline.tables.is_empty() {
+            let mut tree = self.slide_shapes(slide)?;
+            let frames = tree
+                .shapes
+                .iter_mut()
+                .filter_map(|shape| match shape {
+                    Shape::GraphicFrame(g) => g.table_mut(),
+                    _ => None,
+                })
+                .rev()
+                .take(outline.tables.len())
+                .collect::<Vec<_>>();
+            for (table, rows) in frames.into_iter().rev().zip(&outline.tables) {
+                for (row, cells) in table.rows.iter_mut().zip(rows) {
+                    for (cell, text) in row.cells.iter_mut().zip(cells) {
+                        cell.text_frame.paragraphs = vec![text.clone()];
+                    }
+                }
+            }
+            self.update_slide_shapes(slide, &tree)?;
+        }
+
+        if !outline.notes.is_empty() {
+            let notes = self.notes_slide_or_create(slide)?;
+            let part = self
+                .package
+                .part_mut(&notes.partname)
+                .or_part_not_found(notes.partname.as_str())?;
+            let mut tree = ShapeTree::from_slide_xml(&part.blob)?;
+            let frame = tree.shapes.iter_mut().find_map(|shape| match shape {
+                Shape::AutoShape(s)
+                    if s.placeholder
+                        .as_ref()
+                        .is_some_and(|ph| ph.ph_type == Some(PpPlaceholderType::Body)) =>
+                {
+                    s.text_frame.as_mut()
+                }
+                _ => None,
+            });
+            if let Some(frame) = frame {
+                frame.set_text(&outline.notes.join("\n"));
+                part.blob = tree.apply_to_slide_xml(&part.blob)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A text frame for a placeholder, leaving body properties to the layout.
+fn placeholder_text(paragraphs: &[Paragraph]) -> TextFrame {
+    TextFrame {
+        paragraphs: paragraphs.to_vec(),
+        margin_left: None,
+        margin_right: None,
+        margin_top: None,
+        margin_bottom: None,
+        inherit_word_wrap: true,
+        ..TextFrame::default()
+    }
+}
@@ -1,7 +1,8 @@
 //! Media operations (images, charts, videos) on a [`Presentation`].
 
-use crate::chart::data::CategoryChartData;
+use crate::chart::data::{CategoryChartData, ChartData};
 use crate::chart::xmlwriter::ChartXmlWriter;
+use crate::chart::Chart;
 use crate::enums::chart::XlChartType;
 use crate::error::{PartNotFoundExt, PptxResult};
 use crate::media::{Image, Video};
@@ -26,6 +27,27 @@ impl Presentation {
         Ok(partname.into_string())
     }
 
+    /// Add an image for picture bullets on a slide and return the
+    /// relationship ID to use in [`BulletFormat::Picture`].
+    ///
+    /// The image part is deduplicated like [`add_image`](Self::add_image),
+    /// and an existing image relationship from the slide is reused.
+    ///
+    /// [`BulletFormat::Picture`]: crate::text::BulletFormat::Picture
+    /// # Errors
+    ///
+    /// Returns an error if the image part cannot be created or the slide
+    /// part is not found.
+    pub fn add_bullet_image(&mut self, slide_ref: &SlideRef, image: &Image) -> PptxResult<String> {
+        let (partname, _ct) = self.package.or_add_image_part(image)?;
+        let target_ref = partname.relative_ref(slide_ref.partname.base_uri());
+        let slide_part = self
+            .package
+            .part_mut(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+        Ok(slide_part.rels.or_add(RT::IMAGE, &target_ref, false))
+    }
+
     /// Add a chart to a slide.
     ///
     /// Generates chart XML from the given `CategoryChartData` and chart type,
@@ -74,14 +96,82 @@ impl Presentation {
         width: Emu,
         height: Emu,
     ) -> PptxResult<()> {
-        // 1. Generate chart XML
-        let chart_xml_str = ChartXmlWriter::write_category(chart_data, chart_type)?;
+        let chart_data = ChartData::Category(chart_data.clone());
+        self.add_chart(
+            slide_ref,
+            &chart_data,
+            chart_type,
+            None,
+            left,
+            top,
+            width,
+            height,
+        )
+    }
+
+    /// Add a chart of any data kind to a slide.
+    ///
+    /// Generates chart XML for `chart_data` — category, XY, bubble, combo or
+    /// date-axis data — together with its embedded workbook, and inserts a
+    /// `<p:graphicFrame>` referencing the chart into the slide. When `style`
+    /// is given, its title, legend, axes, formatting and chart style are
+    /// applied to the generated chart (see [`Chart::apply_to_chart_xml`]);
+    /// its data is ignored.
+    ///
+    /// ```no_run
+    /// use pptx::presentation::Presentation;
+    /// use pptx::chart::{Chart, ChartData, XyChartData};
+    /// use pptx::enums::chart::XlChartType;
+    /// use pptx::units::{Emu, Inches};
+    ///
+    /// let mut prs = Presentation::new().unwrap();
+    /// let layouts = prs.slide_layouts().unwrap();
+    /// let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+    ///
+    /// let mut data = XyChartData::new();
+    /// let series = data.add_series("Samples");
+    /// series.add_data_point(1.0, 2.5);
+    /// series.add_data_point(2.0, 4.0);
+    ///
+    /// let mut style = Chart::new(XlChartType::XyScatter);
+    /// style.set_title("Measurements");
+    ///
+    /// let size: Emu = Inches(4.0).into();
+    /// prs.add_chart(
+    ///     &slide_ref,
+    ///     &ChartData::Xy(data),
+    ///     XlChartType::XyScatter,
+    ///     Some(&style),
+    ///     Emu(0), Emu(0), size, size,
+    /// ).unwrap();
+    /// ```
+    /// # Errors
+    ///
+    /// Returns an error if `chart_type` does not fit the kind of data, or
+    /// if the chart cannot be created or inserted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_chart(
+        &mut self,
+        slide_ref: &SlideRef,
+        chart_data: &ChartData,
+        chart_type: XlChartType,
+        style: Option<&Chart>,
+        left: Emu,
+        top: Emu,
+        width: Emu,
+        height: Emu,
+    ) -> PptxResult<()> {
+        // 1. Generate chart XML and apply the styling model
+        let mut chart_xml = ChartXmlWriter::write(chart_data, chart_type)?.into_bytes();
+        if let Some(style) = style {
+            chart_xml = style.apply_to_chart_xml(&chart_xml)?;
+        }
 
         // 2. Create the chart part
         let chart_partname = self.package.next_partname("/ppt/charts/chart{}.xml")?;
 
         // 3. Generate and add the embedded xlsx part
-        let xlsx_bytes = crate::chart::xlsx::generate_category_xlsx(chart_data)?;
+        let xlsx_bytes = crate::chart::xlsx::generate_xlsx(chart_data)?;
         let xlsx_partname = self.package.next_partname("/ppt/charts/chart{}.xlsx")?;
 
         // Pre-compute relative refs before consuming partnames
@@ -89,7 +179,7 @@ impl Presentation {
         let chart_target_ref = chart_partname.relative_ref(slide_ref.partname.base_uri());
         let chart_partname_clone = chart_partname.clone();
 
-        let chart_part = Part::new(chart_partname, CT::DML_CHART, chart_xml_str.into_bytes());
+        let chart_part = Part::new(chart_partname, CT::DML_CHART, chart_xml);
         self.package.put_part(chart_part);
 
         let xlsx_part = Part::new(xlsx_partname, CT::SML_SHEET, xlsx_bytes);
@@ -1,22 +1,38 @@
+mod autofit;
+mod charts;
+mod comments;
+mod duplicate;
 mod export;
 mod fonts;
+mod import;
 mod layouts;
+mod markdown;
 mod media;
 mod notes;
 mod print;
+mod properties;
+mod render;
+mod search;
+mod sections;
+mod shapes;
 mod signature;
 mod slide_props;
+mod slide_show;
 mod slides;
+mod template;
 mod vba;
 
 #[cfg(test)]
 mod tests;
 
+pub use import::{ImportFormatting, ImportOptions};
+pub use search::{TextMatch, TextPart};
+
 use std::io::{Read, Write};
 use std::path::Path;
 
 use crate::core_properties::CoreProperties;
-use crate::error::{PptxError, PptxResult};
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
 use crate::opc::constants::{content_type as CT, relationship_type as RT};
 use crate::opc::pack_uri::PackURI;
 use crate::opc::package::OpcPackage;
@@ -112,16 +128,22 @@ impl Presentation {
     ///
     /// Returns an error if serialization or file I/O fails.
     pub fn save(&self, path: impl AsRef<Path>) -> PptxResult<()> {
-        self.package.save(path)
+        let bytes = self.to_bytes()?;
+        std::fs::write(path, bytes)?;
+        Ok(())
     }
 
     /// Serialize the presentation to in-memory bytes.
     ///
+    /// The slide counts and slide titles of `docProps/app.xml` are brought
+    /// up to date in the output.
+    ///
     /// # Errors
     ///
     /// Returns an error if serialization fails.
     pub fn to_bytes(&self) -> PptxResult<Vec<u8>> {
-        self.package.to_bytes()
+        let blobs: Vec<_> = self.synced_extended_properties()?.into_iter().collect();
+        self.package.to_bytes_with(&blobs)
     }
 
     /// Write the presentation to anything implementing `Write`.
@@ -197,6 +219,21 @@ impl Presentation {
         self.package.part_by_reltype(RT::OFFICE_DOCUMENT)
     }
 
+    /// The part a relationship of type `reltype` of the presentation part
+    /// points to, if there is one.
+    pub(crate) fn presentation_related_part(&self, reltype: &str) -> PptxResult<Option<&Part>> {
+        let pres_part = self.presentation_part()?;
+        let Some(rel) = pres_part.rels.all_by_reltype(reltype).first().copied() else {
+            return Ok(None);
+        };
+        let partname = rel.target_partname(pres_part.partname.base_uri())?;
+        Ok(Some(
+            self.package
+                .part(&partname)
+                .or_part_not_found(partname.as_str())?,
+        ))
+    }
+
     /// Get the partname of the presentation part.
     pub(crate) fn presentation_partname(&self) -> PptxResult<PackURI> {
         let rel = self.package.pkg_rels.by_reltype(RT::OFFICE_DOCUMENT)?;
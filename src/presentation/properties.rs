@@ -0,0 +1,169 @@
+//! Extended and custom document properties of a [`Presentation`].
+
+use crate::custom_properties::CustomProperties;
+use crate::error::PptxResult;
+use crate::extended_properties::ExtendedProperties;
+use crate::opc::constants::{content_type as CT, relationship_type as RT};
+use crate::opc::pack_uri::PackURI;
+use crate::opc::part::Part;
+use crate::shapes::Shape;
+
+use super::Presentation;
+
+/// The title `PowerPoint` lists for a slide without title text.
+const UNTITLED_SLIDE: &str = "PowerPoint Presentation";
+
+impl Presentation {
+    /// Get the extended properties of the presentation.
+    ///
+    /// Reads from `docProps/app.xml` if it exists, otherwise starts from
+    /// empty properties. The slide counts and slide titles reflect the
+    /// current slides.
+    /// # Errors
+    ///
+    /// Returns an error if the XML cannot be parsed.
+    pub fn extended_properties(&self) -> PptxResult<ExtendedProperties> {
+        let mut props = match self.package_property_part(RT::EXTENDED_PROPERTIES)? {
+            Some(part) => ExtendedProperties::from_xml(&part.blob)?,
+            None => ExtendedProperties::new(),
+        };
+        self.update_slide_statistics(&mut props)?;
+        Ok(props)
+    }
+
+    /// Set the extended properties of the presentation.
+    ///
+    /// Writes to `docProps/app.xml`, creating the part and relationship if
+    /// needed. The slide counts and slide titles are taken from the slides
+    /// rather than from `props`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if XML serialization fails.
+    pub fn set_extended_properties(&mut self, props: &ExtendedProperties) -> PptxResult<()> {
+        let mut props = props.clone();
+        self.update_slide_statistics(&mut props)?;
+
+        let partname = PackURI::new("/docProps/app.xml")?;
+        let part = Part::new(partname, CT::OFC_EXTENDED_PROPERTIES, props.to_xml()?);
+        self.package.put_part(part);
+        self.package
+            .pkg_rels
+            .or_add(RT::EXTENDED_PROPERTIES, "docProps/app.xml", false);
+        Ok(())
+    }
+
+    /// Get the custom properties of the presentation.
+    ///
+    /// Reads from `docProps/custom.xml` if it exists, otherwise returns
+    /// empty properties.
+    /// # Errors
+    ///
+    /// Returns an error if the XML cannot be parsed.
+    pub fn custom_properties(&self) -> PptxResult<CustomProperties> {
+        match self.package_property_part(RT::CUSTOM_PROPERTIES)? {
+            Some(part) => CustomProperties::from_xml(&part.blob),
+            None => Ok(CustomProperties::new()),
+        }
+    }
+
+    /// Set the custom properties of the presentation.
+    ///
+    /// Writes to `docProps/custom.xml`, creating the part and relationship
+    /// if needed. Setting empty properties removes the part.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if XML serialization fails.
+    pub fn set_custom_properties(&mut self, props: &CustomProperties) -> PptxResult<()> {
+        if props.is_empty() {
+            if let Some(part) = self.package_property_part(RT::CUSTOM_PROPERTIES)? {
+                let partname = part.partname.clone();
+                self.package.remove_part(&partname);
+            }
+            let r_ids: Vec<String> = self
+                .package
+                .pkg_rels
+                .all_by_reltype(RT::CUSTOM_PROPERTIES)
+                .iter()
+                .map(|rel| rel.r_id.to_string())
+                .collect();
+            for r_id in r_ids {
+                self.package.pkg_rels.remove(&r_id);
+            }
+            return Ok(());
+        }
+
+        let partname = PackURI::new("/docProps/custom.xml")?;
+        let part = Part::new(partname, CT::OFC_CUSTOM_PROPERTIES, props.to_xml()?);
+        self.package.put_part(part);
+        self.package
+            .pkg_rels
+            .or_add(RT::CUSTOM_PROPERTIES, "docProps/custom.xml", false);
+        Ok(())
+    }
+
+    /// The content of `docProps/app.xml` with its slide statistics brought
+    /// up to date, to be written in place of the stored part.
+    ///
+    /// Returns `None` if the presentation has no readable extended
+    /// properties part.
+    pub(crate) fn synced_extended_properties(&self) -> PptxResult<Option<(PackURI, Vec<u8>)>> {
+        let Some(part) = self.package_property_part(RT::EXTENDED_PROPERTIES)? else {
+            return Ok(None);
+        };
+        let Ok(mut props) = ExtendedProperties::from_xml(&part.blob) else {
+            return Ok(None);
+        };
+        self.update_slide_statistics(&mut props)?;
+        Ok(Some((part.partname.clone(), props.to_xml()?)))
+    }
+
+    /// The part targeted by the package relationship of type `reltype`.
+    fn package_property_part(&self, reltype: &str) -> PptxResult<Option<&Part>> {
+        let Ok(rel) = self.package.pkg_rels.by_reltype(reltype) else {
+            return Ok(None);
+        };
+        let partname = rel.target_partname(self.package.pkg_rels.base_uri())?;
+        Ok(self.package.part(&partname))
+    }
+
+    /// Record the slide, notes and hidden-slide counts and the slide titles.
+    ///
+    /// A slide whose XML cannot be read keeps the title recorded for it, and
+    /// counts as shown, so that the statistics never stop a save.
+    fn update_slide_statistics(&self, props: &mut ExtendedProperties) -> PptxResult<()> {
+        let slides = self.slides()?;
+        let mut notes = 0;
+        let mut hidden = 0;
+        let mut titles = Vec::with_capacity(slides.len());
+        for (i, slide) in slides.iter().enumerate() {
+            if self.has_notes_slide(slide) {
+                notes += 1;
+            }
+            if self.is_hidden(slide).unwrap_or(false) {
+                hidden += 1;
+            }
+            let title = match self.slide_shapes(slide) {
+                Ok(shapes) => match shapes.title() {
+                    Some(Shape::AutoShape(shape)) => shape
+                        .text_frame
+                        .as_ref()
+                        .map(|tf| tf.text().split_whitespace().collect::<Vec<_>>().join(" "))
+                        .unwrap_or_default(),
+                    _ => String::new(),
+                },
+                Err(_) => props.slide_titles().get(i).cloned().unwrap_or_default(),
+            };
+            titles.push(if title.is_empty() {
+                UNTITLED_SLIDE.to_string()
+            } else {
+                title
+            });
+        }
+        let count = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        props.set_slide_counts(count(slides.len()), count(notes), count(hidden));
+        props.set_slide_titles(titles);
+        Ok(())
+    }
+}
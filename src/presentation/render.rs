@@ -0,0 +1,74 @@
+//! Slide rendering and package thumbnails on a [`Presentation`].
+
+use crate::error::PptxResult;
+use crate::opc::constants::{content_type as CT, relationship_type as RT};
+use crate::opc::pack_uri::PackURI;
+use crate::opc::part::Part;
+use crate::render::{RenderOptions, Renderer, SlideImage};
+use crate::slide::SlideRef;
+
+use super::Presentation;
+
+/// Where a regenerated package thumbnail is stored.
+const THUMBNAIL_PARTNAME: &str = "/docProps/thumbnail.jpeg";
+
+/// JPEG quality of package thumbnails.
+const THUMBNAIL_QUALITY: u8 = 85;
+
+impl Presentation {
+    /// Draw a slide into an RGBA bitmap.
+    ///
+    /// The bitmap is `options.width` pixels wide and, unless a height is
+    /// given, as tall as the slide's aspect ratio makes it. Use
+    /// [`SlideImage::to_png`] to get a PNG file.
+    /// # Errors
+    ///
+    /// Returns an error if the size is invalid or the slide cannot be read.
+    pub fn render_slide(
+        &self,
+        slide: &SlideRef,
+        options: &RenderOptions,
+    ) -> PptxResult<SlideImage> {
+        Renderer::new(self, options)?.render(slide)
+    }
+
+    /// Regenerate the package thumbnail from the first slide.
+    ///
+    /// The first slide (or a blank page when there are no slides) is drawn
+    /// with `options` and stored as `docProps/thumbnail.jpeg`, replacing
+    /// any previous thumbnail. `RenderOptions::default()` gives the usual
+    /// 256-pixel-wide thumbnail. Returns the drawn bitmap.
+    /// # Errors
+    ///
+    /// Returns an error if the size is invalid or the slide cannot be read.
+    pub fn thumbnail(&mut self, options: &RenderOptions) -> PptxResult<SlideImage> {
+        let renderer = Renderer::new(self, options)?;
+        let image = match self.slides()?.first() {
+            Some(slide) => renderer.render(slide)?,
+            None => renderer.render_blank()?,
+        };
+        let jpeg = image.to_jpeg(THUMBNAIL_QUALITY)?;
+
+        // Replace the old thumbnail, which may be in another format
+        let old: Vec<(String, PackURI)> = self
+            .package
+            .pkg_rels
+            .all_by_reltype(RT::THUMBNAIL)
+            .into_iter()
+            .filter_map(|rel| {
+                let partname = rel.target_partname(self.package.pkg_rels.base_uri()).ok()?;
+                Some((rel.r_id.to_string(), partname))
+            })
+            .collect();
+        for (r_id, partname) in old {
+            self.package.pkg_rels.remove(&r_id);
+            self.package.remove_part(&partname);
+        }
+        let partname = PackURI::new(THUMBNAIL_PARTNAME)?;
+        self.package.put_part(Part::new(partname, CT::JPEG, jpeg));
+        self.package
+            .pkg_rels
+            .or_add(RT::THUMBNAIL, "docProps/thumbnail.jpeg", false);
+        Ok(image)
+    }
+}
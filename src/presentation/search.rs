@@ -0,0 +1,226 @@
+//! Text search and replace on a [`Presentation`].
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
+use crate::slide::SlideRef;
+use crate::text::search::{find_in_xml, replace_in_xml, ReplaceOptions};
+use crate::units::ShapeId;
+use crate::xml_util::{attr_value, local_name_str};
+
+use super::Presentation;
+
+/// The kind of part a [`TextMatch`] was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPart {
+    /// The slide itself, including placeholders, groups and tables.
+    Slide,
+    /// The notes slide of the slide.
+    Notes,
+    /// A chart on the slide.
+    Chart,
+}
+
+/// Where a piece of text was found.
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+    /// The slide the text belongs to.
+    pub slide: SlideRef,
+    /// The part of the slide the text was found in.
+    pub part: TextPart,
+    /// ID of the shape holding the text. For charts this is the graphic
+    /// frame showing the chart.
+    pub shape_id: Option<ShapeId>,
+    /// Index of the paragraph within its shape (within the part, for text
+    /// outside shapes such as chart titles).
+    pub paragraph: usize,
+    /// Indices of the `<a:r>` runs of the paragraph the match spans.
+    pub runs: Range<usize>,
+    /// The matched text.
+    pub text: String,
+}
+
+/// A part to search, with the slide it belongs to.
+struct SearchPart {
+    slide: SlideRef,
+    kind: TextPart,
+    partname: PackURI,
+    /// Shape ID to report for every match, used for charts.
+    shape_id: Option<ShapeId>,
+}
+
+impl Presentation {
+    /// Find every occurrence of `pattern` in the text of the slides.
+    ///
+    /// Slides, their notes slides and chart titles are searched, in slide
+    /// order. Text split over several runs is matched as a whole, but not
+    /// across line breaks, fields or paragraphs. `options` set whether case
+    /// must match and whether matches must be whole words, as for
+    /// [`replace_text`](Self::replace_text).
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is empty or a part cannot be read.
+    pub fn find_text(&self, pattern: &str, options: ReplaceOptions) -> PptxResult<Vec<TextMatch>> {
+        let mut found = Vec::new();
+        for part in self.search_parts()? {
+            let xml = self.part_xml(&part.partname)?;
+            for m in find_in_xml(xml, pattern, options)? {
+                found.push(TextMatch {
+                    slide: part.slide.clone(),
+                    part: part.kind,
+                    shape_id: part.shape_id.or(m.shape_id),
+                    paragraph: m.paragraph,
+                    runs: m.runs,
+                    text: m.text,
+                });
+            }
+        }
+        Ok(found)
+    }
+
+    /// Replace every occurrence of `pattern` with `replacement`.
+    ///
+    /// Searches the same text as [`find_text`](Self::find_text). The
+    /// replacement takes the formatting of the run the match starts in;
+    /// runs left empty are removed.
+    ///
+    /// Returns the number of replacements made.
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is empty or a part cannot be read.
+    pub fn replace_text(
+        &mut self,
+        pattern: &str,
+        replacement: &str,
+        options: ReplaceOptions,
+    ) -> PptxResult<usize> {
+        let mut done = HashSet::new();
+        let parts = self.search_parts()?;
+        let parts: Vec<_> = parts
+            .into_iter()
+            .filter(|part| done.insert(part.partname.clone()))
+            .collect();
+        self.replace_in_parts(&parts, pattern, replacement, options)
+    }
+
+    /// Replace every occurrence of `pattern` in the text of one slide, its
+    /// notes and its charts. Returns the number of replacements made.
+    pub(super) fn replace_text_on_slide(
+        &mut self,
+        slide: &SlideRef,
+        pattern: &str,
+        replacement: &str,
+        options: ReplaceOptions,
+    ) -> PptxResult<usize> {
+        let parts = self.slide_search_parts(slide)?;
+        self.replace_in_parts(&parts, pattern, replacement, options)
+    }
+
+    fn replace_in_parts(
+        &mut self,
+        parts: &[SearchPart],
+        pattern: &str,
+        replacement: &str,
+        options: ReplaceOptions,
+    ) -> PptxResult<usize> {
+        let mut count = 0;
+        for part in parts {
+            let (xml, replaced) = replace_in_xml(
+                self.part_xml(&part.partname)?,
+                pattern,
+                replacement,
+                options,
+            )?;
+            if replaced > 0 {
+                self.package
+                    .part_mut(&part.partname)
+                    .or_part_not_found(part.partname.as_str())?
+                    .blob = xml.into_bytes();
+                count += replaced;
+            }
+        }
+        Ok(count)
+    }
+
+    fn part_xml(&self, partname: &PackURI) -> PptxResult<&str> {
+        let part = self
+            .package
+            .part(partname)
+            .or_part_not_found(partname.as_str())?;
+        Ok(std::str::from_utf8(&part.blob)?)
+    }
+
+    /// The parts holding the text of each slide.
+    fn search_parts(&self) -> PptxResult<Vec<SearchPart>> {
+        let mut parts = Vec::new();
+        for slide in self.slides()? {
+            parts.extend(self.slide_search_parts(&slide)?);
+        }
+        Ok(parts)
+    }
+
+    /// The slide part, notes slide and charts of `slide`.
+    fn slide_search_parts(&self, slide: &SlideRef) -> PptxResult<Vec<SearchPart>> {
+        let slide_part = self
+            .package
+            .part(&slide.partname)
+            .or_part_not_found(slide.partname.as_str())?;
+        let base_uri = slide_part.partname.base_uri();
+        let mut parts = vec![SearchPart {
+            slide: slide.clone(),
+            kind: TextPart::Slide,
+            partname: slide.partname.clone(),
+            shape_id: None,
+        }];
+        for rel in slide_part.rels.all_by_reltype(RT::NOTES_SLIDE) {
+            parts.push(SearchPart {
+                slide: slide.clone(),
+                kind: TextPart::Notes,
+                partname: rel.target_partname(base_uri)?,
+                shape_id: None,
+            });
+        }
+        let frames = chart_frames(std::str::from_utf8(&slide_part.blob)?)?;
+        for rel in slide_part.rels.all_by_reltype(RT::CHART) {
+            parts.push(SearchPart {
+                slide: slide.clone(),
+                kind: TextPart::Chart,
+                partname: rel.target_partname(base_uri)?,
+                shape_id: frames.get(rel.r_id.as_str()).copied(),
+            });
+        }
+        Ok(parts)
+    }
+}
+
+/// Map the rId of each `<c:chart>` in a slide to its graphic frame's ID.
+fn chart_frames(slide_xml: &str) -> PptxResult<HashMap<String, ShapeId>> {
+    let mut reader = Reader::from_str(slide_xml);
+    let mut frames = HashMap::new();
+    // The graphic frame's cNvPr precedes its graphic
+    let mut last_id = None;
+    loop {
+        match reader.read_event().map_err(PptxError::Xml)? {
+            Event::Start(ref e) | Event::Empty(ref e) => match local_name_str(e.name().as_ref()) {
+                "cNvPr" => {
+                    last_id = attr_value(e, b"id")?.and_then(|v| v.parse().ok());
+                }
+                "chart" => {
+                    if let (Some(r_id), Some(id)) = (attr_value(e, b"r:id")?, last_id) {
+                        frames.insert(r_id.into_owned(), ShapeId(id));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(frames)
+}
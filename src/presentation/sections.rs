@@ -0,0 +1,259 @@
+//! Section operations on a [`Presentation`].
+
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::section::{parse_sections, place_moved_slide, write_sections, Section, SectionEntry};
+use crate::slide::{parse_slide_ids, reorder_slide_in_presentation_xml};
+use crate::units::SlideId;
+
+use super::Presentation;
+
+/// Name `PowerPoint` gives the section of slides before the first named one.
+const DEFAULT_SECTION_NAME: &str = "Default Section";
+
+impl Presentation {
+    /// Get the sections of the presentation, in order.
+    ///
+    /// Reads the `<p14:sectionLst>` extension of presentation.xml. Returns
+    /// an empty list if the presentation has no sections.
+    /// # Errors
+    ///
+    /// Returns an error if the presentation XML cannot be parsed.
+    pub fn sections(&self) -> PptxResult<Vec<Section>> {
+        let slide_ids = self.slide_id_order()?;
+        Ok(self
+            .section_entries()?
+            .into_iter()
+            .map(|entry| Section {
+                name: entry.name,
+                slide_indices: entry
+                    .slide_ids
+                    .iter()
+                    .filter_map(|id| slide_ids.iter().position(|s| s == id))
+                    .collect(),
+                id: Some(entry.id),
+            })
+            .collect())
+    }
+
+    /// Replace the sections of the presentation.
+    ///
+    /// `PowerPoint` expects every slide to be in exactly one section, with
+    /// sections following the slide order. An empty list removes the
+    /// sections.
+    /// # Errors
+    ///
+    /// Returns an error if a slide index is out of range or in more than
+    /// one section, or if the presentation XML cannot be updated.
+    pub fn set_sections(&mut self, sections: &[Section]) -> PptxResult<()> {
+        let slide_ids = self.slide_id_order()?;
+        let mut seen = vec![false; slide_ids.len()];
+        let mut entries: Vec<SectionEntry> = Vec::with_capacity(sections.len());
+        for section in sections {
+            let mut ids = Vec::with_capacity(section.slide_indices.len());
+            for &index in &section.slide_indices {
+                match seen.get_mut(index) {
+                    Some(seen @ false) => *seen = true,
+                    _ => {
+                        return Err(PptxError::InvalidValue {
+                            field: "slide_indices",
+                            value: index.to_string(),
+                            expected: "slide indices in range and in one section only",
+                        })
+                    }
+                }
+                ids.push(slide_ids[index]);
+            }
+            let entry = match &section.id {
+                Some(id) => SectionEntry {
+                    name: section.name.clone(),
+                    id: id.clone(),
+                    slide_ids: ids,
+                },
+                None => SectionEntry::new(&section.name, ids, &entries),
+            };
+            entries.push(entry);
+        }
+        self.write_section_entries(&entries)
+    }
+
+    /// Start a new section at the slide at `first_slide_index`.
+    ///
+    /// The new section takes that slide and the slides after it in its
+    /// section. If the presentation has no sections yet, the slides before
+    /// it go into a "Default Section". A `first_slide_index` equal to the
+    /// slide count adds an empty section at the end.
+    ///
+    /// Returns the index of the new section.
+    /// # Errors
+    ///
+    /// Returns an error if `first_slide_index` is greater than the slide
+    /// count, or if the presentation XML cannot be updated.
+    pub fn add_section(&mut self, name: &str, first_slide_index: usize) -> PptxResult<usize> {
+        let slide_ids = self.slide_id_order()?;
+        if first_slide_index > slide_ids.len() {
+            return Err(PptxError::InvalidValue {
+                field: "first_slide_index",
+                value: first_slide_index.to_string(),
+                expected: "a slide index no greater than the slide count",
+            });
+        }
+        let mut entries = self.section_entries()?;
+        if entries.is_empty() && first_slide_index > 0 {
+            let before = slide_ids[..first_slide_index].to_vec();
+            entries.push(SectionEntry::new(DEFAULT_SECTION_NAME, before, &[]));
+        }
+
+        // The section holding the first slide, and where in it the new one
+        // splits off
+        let split = slide_ids.get(first_slide_index).and_then(|first| {
+            entries
+                .iter()
+                .enumerate()
+                .find_map(|(i, s)| Some((i, s.slide_ids.iter().position(|id| id == first)?)))
+        });
+        let (index, slides) = match split {
+            Some((i, at)) => (i + 1, entries[i].slide_ids.split_off(at)),
+            // A new first section, or an empty one at the end
+            None => (entries.len(), slide_ids[first_slide_index..].to_vec()),
+        };
+        let entry = SectionEntry::new(name, slides, &entries);
+        entries.insert(index, entry);
+        self.write_section_entries(&entries)?;
+        Ok(index)
+    }
+
+    /// Rename the section at `index`.
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range, or if the presentation
+    /// XML cannot be updated.
+    pub fn rename_section(&mut self, index: usize, name: &str) -> PptxResult<()> {
+        let mut entries = self.section_entries()?;
+        section_at(&mut entries, index)?.name = name.to_string();
+        self.write_section_entries(&entries)
+    }
+
+    /// Move the section at `from_index`, with its slides, to `to_index`.
+    ///
+    /// The slides are reordered so they stay grouped by section.
+    /// # Errors
+    ///
+    /// Returns an error if either index is out of range, or if the
+    /// presentation XML cannot be updated.
+    pub fn move_section(&mut self, from_index: usize, to_index: usize) -> PptxResult<()> {
+        let mut entries = self.section_entries()?;
+        let section = section_at(&mut entries, from_index)?.clone();
+        section_at(&mut entries, to_index)?;
+        entries.remove(from_index);
+        entries.insert(to_index, section);
+
+        // Slides in section order; any slide outside the sections stays last
+        let mut order = self.slide_id_order()?;
+        let mut wanted: Vec<SlideId> = entries
+            .iter()
+            .flat_map(|s| s.slide_ids.iter().copied())
+            .filter(|id| order.contains(id))
+            .collect();
+        let outside: Vec<SlideId> = order
+            .iter()
+            .filter(|id| !wanted.contains(id))
+            .copied()
+            .collect();
+        wanted.extend(outside);
+
+        let pres_partname = self.presentation_partname()?;
+        let pres_part = self
+            .package
+            .part_mut(&pres_partname)
+            .or_part_not_found(pres_partname.as_str())?;
+        for (to, id) in wanted.iter().enumerate() {
+            let Some(from) = order.iter().position(|s| s == id) else {
+                continue;
+            };
+            if from != to {
+                pres_part.blob = reorder_slide_in_presentation_xml(&pres_part.blob, from, to)?;
+                let moved = order.remove(from);
+                order.insert(to, moved);
+            }
+        }
+        self.write_section_entries(&entries)
+    }
+
+    /// Delete the section at `index`.
+    ///
+    /// With `with_slides`, its slides are deleted too; otherwise they join
+    /// the previous section (or the next one, for the first section).
+    /// Deleting the only section without its slides removes the section
+    /// list.
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range, or if a slide or the
+    /// presentation XML cannot be updated.
+    pub fn delete_section(&mut self, index: usize, with_slides: bool) -> PptxResult<()> {
+        let mut entries = self.section_entries()?;
+        let section = section_at(&mut entries, index)?.clone();
+        if with_slides {
+            let slides = self.slides()?;
+            let order = self.slide_id_order()?;
+            for id in &section.slide_ids {
+                if let Some(pos) = order.iter().position(|s| s == id) {
+                    self.delete_slide(&slides[pos])?;
+                }
+            }
+            // Deleting the slides emptied the section
+            entries = self.section_entries()?;
+            entries.remove(index);
+            return self.write_section_entries(&entries);
+        }
+
+        entries.remove(index);
+        if let Some(previous) = index.checked_sub(1).and_then(|i| entries.get_mut(i)) {
+            previous.slide_ids.extend(section.slide_ids);
+        } else if let Some(next) = entries.get_mut(index) {
+            next.slide_ids.splice(0..0, section.slide_ids);
+        }
+        self.write_section_entries(&entries)
+    }
+
+    /// The slide IDs of the presentation, in slide order.
+    pub(super) fn slide_id_order(&self) -> PptxResult<Vec<SlideId>> {
+        let pres_part = self.presentation_part()?;
+        Ok(parse_slide_ids(&pres_part.blob)?
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect())
+    }
+
+    pub(super) fn section_entries(&self) -> PptxResult<Vec<SectionEntry>> {
+        parse_sections(&self.presentation_part()?.blob)
+    }
+
+    pub(super) fn write_section_entries(&mut self, entries: &[SectionEntry]) -> PptxResult<()> {
+        let pres_partname = self.presentation_partname()?;
+        let pres_part = self
+            .package
+            .part_mut(&pres_partname)
+            .or_part_not_found(pres_partname.as_str())?;
+        pres_part.blob = write_sections(&pres_part.blob, entries)?;
+        Ok(())
+    }
+
+    /// Keep the sections in step after the slide `moved` changed position.
+    pub(super) fn place_moved_slide_in_sections(&mut self, moved: SlideId) -> PptxResult<()> {
+        let mut entries = self.section_entries()?;
+        if entries.is_empty() {
+            return Ok(());
+        }
+        place_moved_slide(&mut entries, &self.slide_id_order()?, moved);
+        self.write_section_entries(&entries)
+    }
+}
+
+fn section_at(entries: &mut [SectionEntry], index: usize) -> PptxResult<&mut SectionEntry> {
+    let len = entries.len();
+    entries.get_mut(index).ok_or_else(|| {
+        PptxError::InvalidXml(format!(
+            "section index {index} out of range (presentation has {len} sections)"
+        ))
+    })
+}
@@ -0,0 +1,157 @@
+use crate::error::{PartNotFoundExt, PptxResult};
+use crate::shapes::{Shape, ShapeTree};
+use crate::slide::SlideRef;
+use crate::units::ShapeId;
+use crate::xml_util::relationship_ids;
+
+use super::Presentation;
+
+/// Shape tree access methods for `Presentation`.
+impl Presentation {
+    /// Parse the shape tree of a slide.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or its XML cannot be parsed.
+    pub fn slide_shapes(&self, slide_ref: &SlideRef) -> PptxResult<ShapeTree> {
+        ShapeTree::from_slide_xml(self.slide_xml(slide_ref)?)
+    }
+
+    /// Write an edited shape tree back into a slide.
+    ///
+    /// The tree is usually obtained from [`slide_shapes`](Self::slide_shapes)
+    /// and then modified. Untouched shapes and any markup the crate does not
+    /// model (extension lists, alternate content) are preserved; see
+    /// [`ShapeTree::apply_to_slide_xml`] for the exact rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or its XML cannot be updated.
+    pub fn update_slide_shapes(
+        &mut self,
+        slide_ref: &SlideRef,
+        tree: &ShapeTree,
+    ) -> PptxResult<()> {
+        let updated = tree.apply_to_slide_xml(self.slide_xml(slide_ref)?)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        Ok(())
+    }
+
+    /// Remove a shape from a slide.
+    ///
+    /// Shapes nested in groups can be removed as well. Relationships the
+    /// shape used (pictures, charts, media, hyperlinks) are dropped from the
+    /// slide once nothing else on it refers to them, and parts left without
+    /// any referrer are removed from the package together with their own
+    /// dependents, such as a chart's embedded workbook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found, its XML cannot be
+    /// parsed, or it has no shape with this ID.
+    pub fn remove_shape(&mut self, slide_ref: &SlideRef, shape_id: ShapeId) -> PptxResult<()> {
+        let slide_xml = self.slide_xml(slide_ref)?;
+        let removed = ShapeTree::shape_xml(slide_xml, shape_id)?;
+        let updated = ShapeTree::remove_shape(slide_xml, shape_id)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        self.release_relationships(slide_ref, removed.as_bytes())
+    }
+
+    /// Replace a shape on a slide with `shape`, keeping its z-order position.
+    ///
+    /// Relationships used only by the replaced shape are cleaned up as in
+    /// [`remove_shape`](Self::remove_shape). Any relationship `shape` refers
+    /// to must already exist on the slide.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found, it has no shape with
+    /// this ID, or `shape` is a graphic frame.
+    pub fn replace_shape(
+        &mut self,
+        slide_ref: &SlideRef,
+        shape_id: ShapeId,
+        shape: &Shape,
+    ) -> PptxResult<()> {
+        let slide_xml = self.slide_xml(slide_ref)?;
+        let removed = ShapeTree::shape_xml(slide_xml, shape_id)?;
+        let updated = ShapeTree::replace_shape(slide_xml, shape_id, shape)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        self.release_relationships(slide_ref, removed.as_bytes())
+    }
+
+    /// Bring a shape to the front of its slide (or group).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or has no shape with this ID.
+    pub fn bring_to_front(&mut self, slide_ref: &SlideRef, shape_id: ShapeId) -> PptxResult<()> {
+        let updated = ShapeTree::bring_to_front(self.slide_xml(slide_ref)?, shape_id)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        Ok(())
+    }
+
+    /// Send a shape to the back of its slide (or group).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or has no shape with this ID.
+    pub fn send_to_back(&mut self, slide_ref: &SlideRef, shape_id: ShapeId) -> PptxResult<()> {
+        let updated = ShapeTree::send_to_back(self.slide_xml(slide_ref)?, shape_id)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        Ok(())
+    }
+
+    /// Move a shape one step towards the front.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or has no shape with this ID.
+    pub fn move_forward(&mut self, slide_ref: &SlideRef, shape_id: ShapeId) -> PptxResult<()> {
+        let updated = ShapeTree::move_forward(self.slide_xml(slide_ref)?, shape_id)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        Ok(())
+    }
+
+    /// Move a shape one step towards the back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found or has no shape with this ID.
+    pub fn move_backward(&mut self, slide_ref: &SlideRef, shape_id: ShapeId) -> PptxResult<()> {
+        let updated = ShapeTree::move_backward(self.slide_xml(slide_ref)?, shape_id)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        Ok(())
+    }
+
+    /// Drop the slide relationships referenced from `removed_xml` that the
+    /// slide no longer uses, along with any part left unreferenced.
+    pub(super) fn release_relationships(
+        &mut self,
+        slide_ref: &SlideRef,
+        removed_xml: &[u8],
+    ) -> PptxResult<()> {
+        let candidates = relationship_ids(removed_xml)?;
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        let still_used = relationship_ids(self.slide_xml(slide_ref)?)?;
+        let part = self
+            .package
+            .part_mut(&slide_ref.partname)
+            .or_part_not_found(slide_ref.partname.as_str())?;
+
+        let mut released = Vec::new();
+        for r_id in candidates.iter().filter(|id| !still_used.contains(id)) {
+            if let Some(rel) = part.rels.remove(r_id) {
+                if !rel.is_external {
+                    released.push(rel.target_partname(part.rels.base_uri())?);
+                }
+            }
+        }
+        for partname in released {
+            self.package.remove_part_if_unreferenced(&partname)?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use crate::dml::fill::GradientFill;
 use crate::error::{PartNotFoundExt, PptxError, PptxResult};
 use crate::opc::constants::relationship_type as RT;
-use crate::slide::{SlideLayoutRef, SlideRef};
+use crate::slide::{
+    is_hidden_slide, parse_slide_properties, write_slide_properties, SlideLayoutRef,
+    SlideProperties, SlideRef,
+};
 use crate::xml_util::WriteXml;
 
 use super::{remove_xml_element, Presentation};
@@ -55,6 +58,93 @@ impl Presentation {
         }))
     }
 
+    /// Get the properties of a slide: its ID, name and notes flag, whether
+    /// it is hidden or shows the master shapes, and how it advances.
+    /// # Errors
+    ///
+    /// Returns an error if the slide is not in the presentation or its XML
+    /// cannot be parsed.
+    pub fn slide_properties(&self, slide_ref: &SlideRef) -> PptxResult<SlideProperties> {
+        let slide_id = self.slide_id(slide_ref)?;
+        parse_slide_properties(
+            self.slide_xml(slide_ref)?,
+            slide_id,
+            self.has_notes_slide(slide_ref),
+        )
+    }
+
+    /// Set the properties of a slide.
+    ///
+    /// Writes the name, hidden and master shapes flags and the advance
+    /// settings. The slide ID and notes flag are read-only and ignored.
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be updated.
+    pub fn set_slide_properties(
+        &mut self,
+        slide_ref: &SlideRef,
+        props: &SlideProperties,
+    ) -> PptxResult<()> {
+        let updated = write_slide_properties(self.slide_xml(slide_ref)?, props)?;
+        *self.slide_xml_mut(slide_ref)? = updated;
+        Ok(())
+    }
+
+    /// Whether a slide is hidden, i.e. skipped in slide shows.
+    /// # Errors
+    ///
+    /// Returns an error if the slide part is not found.
+    pub fn is_hidden(&self, slide_ref: &SlideRef) -> PptxResult<bool> {
+        Ok(is_hidden_slide(std::str::from_utf8(
+            self.slide_xml(slide_ref)?,
+        )?))
+    }
+
+    /// Hide a slide from slide shows, or show it again.
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be updated.
+    pub fn set_hidden(&mut self, slide_ref: &SlideRef, hidden: bool) -> PptxResult<()> {
+        let mut props = self.slide_properties(slide_ref)?;
+        props.hidden = hidden;
+        self.set_slide_properties(slide_ref, &props)
+    }
+
+    /// Whether a slide shows the shapes of its slide master and layout.
+    /// # Errors
+    ///
+    /// Returns an error if the slide is not in the presentation or its XML
+    /// cannot be parsed.
+    pub fn show_master_shapes(&self, slide_ref: &SlideRef) -> PptxResult<bool> {
+        Ok(self.slide_properties(slide_ref)?.show_master_shapes)
+    }
+
+    /// Set whether a slide shows the shapes of its slide master and layout.
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be updated.
+    pub fn set_show_master_shapes(&mut self, slide_ref: &SlideRef, show: bool) -> PptxResult<()> {
+        let mut props = self.slide_properties(slide_ref)?;
+        props.show_master_shapes = show;
+        self.set_slide_properties(slide_ref, &props)
+    }
+
+    /// Set the auto-advance time of every slide, in milliseconds; `None`
+    /// turns auto-advance off.
+    ///
+    /// Slides keep their transitions and click setting.
+    /// # Errors
+    ///
+    /// Returns an error if a slide XML cannot be updated.
+    pub fn set_advance_after_all(&mut self, advance_after: Option<u32>) -> PptxResult<()> {
+        for slide in self.slides()? {
+            let mut props = self.slide_properties(&slide)?;
+            props.advance_after = advance_after;
+            self.set_slide_properties(&slide, &props)?;
+        }
+        Ok(())
+    }
+
     /// Set a slide transition on a given slide.
     ///
     /// Inserts a `<p:transition>` element into the slide XML.
@@ -127,23 +217,6 @@ impl Presentation {
         Ok(())
     }
 
-    /// Get comments for a slide.
-    ///
-    /// Returns an empty vector if the slide has no comments part.
-    /// # Errors
-    ///
-    /// Returns an error if comments cannot be read.
-    #[allow(clippy::missing_const_for_fn)]
-    pub fn slide_comments(
-        &self,
-        _slide_ref: &SlideRef,
-    ) -> PptxResult<Vec<crate::comment::Comment>> {
-        // Comments parsing from existing XML is a read operation.
-        // For now, return empty; full parsing would require reading
-        // the comments part and the comment authors part.
-        Ok(Vec::new())
-    }
-
     /// Set a gradient background on a slide.
     ///
     /// Replaces any existing background with a gradient fill derived from
@@ -0,0 +1,305 @@
+//! Slide show settings and custom shows of a [`Presentation`].
+
+use crate::custom_show::{parse_custom_shows, write_custom_shows, CustomShow, CustomShowEntry};
+use crate::error::{PartNotFoundExt, PptxError, PptxResult};
+use crate::opc::constants::{content_type as CT, relationship_type as RT};
+use crate::opc::pack_uri::PackURI;
+use crate::opc::part::Part;
+use crate::show_settings::{ShowSettings, SlideRange, LASER_COLOR_EXT_URI};
+use crate::slide::{parse_slide_ids, SlideRef};
+use crate::units::SlideId;
+use crate::xml_util::splice::{find_element, find_element_in, get_attr, inner_range, splice};
+use crate::xml_util::WriteXml;
+
+use super::Presentation;
+
+/// A presProps part with no properties set.
+const EMPTY_PRES_PROPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:presentationPr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"></p:presentationPr>"#;
+
+/// Elements that follow `<p:showPr>` in presProps.xml.
+const AFTER_SHOW_PR: &[&str] = &["p:clrMru", "p:extLst"];
+
+impl Presentation {
+    /// Get the slide show settings of the presentation.
+    ///
+    /// Reads the `<p:showPr>` element of presProps.xml. Returns default
+    /// settings if there is none.
+    /// # Errors
+    ///
+    /// Returns an error if the presentation properties cannot be parsed.
+    pub fn show_settings(&self) -> PptxResult<ShowSettings> {
+        let Some(part) = self.presentation_related_part(RT::PRES_PROPS)? else {
+            return Ok(ShowSettings::new());
+        };
+        let xml = std::str::from_utf8(&part.blob)?;
+        match find_element(xml, "p:showPr", 0) {
+            Some(show_pr) => ShowSettings::from_xml(xml[show_pr].as_bytes()),
+            None => Ok(ShowSettings::new()),
+        }
+    }
+
+    /// Set the slide show settings of the presentation.
+    ///
+    /// Writes the `<p:showPr>` element of presProps.xml, creating the part
+    /// if needed. Extensions of the current settings other than the laser
+    /// color are kept.
+    /// # Errors
+    ///
+    /// Returns an error if the slide range is out of range or names a
+    /// custom show the presentation does not have, or if the presentation
+    /// properties cannot be updated.
+    pub fn set_show_settings(&mut self, settings: &ShowSettings) -> PptxResult<()> {
+        match settings.slide_range {
+            SlideRange::All => {}
+            SlideRange::Range { start, end } => {
+                if start > end || end >= self.slide_count()? {
+                    return Err(PptxError::InvalidValue {
+                        field: "slide_range",
+                        value: format!("{start}..={end}"),
+                        expected: "an ascending range of slide indices in range",
+                    });
+                }
+            }
+            SlideRange::CustomShow(id) => {
+                self.custom_show_entry(id)?;
+            }
+        }
+
+        let partname = self.or_add_pres_props_part()?;
+        let part = self
+            .package
+            .part_mut(&partname)
+            .or_part_not_found(partname.as_str())?;
+        let mut xml = std::str::from_utf8(&part.blob)?.to_string();
+
+        // Keep the extensions PowerPoint adds, such as media controls
+        let mut show_pr = settings.to_xml_string();
+        let mut extensions = String::new();
+        if let Some(old) = find_element(&xml, "p:showPr", 0) {
+            if let Some(ext_lst) = find_element_in(&xml, "p:extLst", old.clone()) {
+                let inner = inner_range(&xml, ext_lst);
+                let mut from = inner.start;
+                while let Some(ext) = find_element_in(&xml, "p:ext", from..inner.end) {
+                    let start_tag =
+                        &xml[ext.start..=ext.start + xml[ext.clone()].find('>').unwrap_or(0)];
+                    if get_attr(start_tag, "uri") != Some(LASER_COLOR_EXT_URI) {
+                        extensions.push_str(&xml[ext.clone()]);
+                    }
+                    from = ext.end;
+                }
+            }
+            xml = splice(&xml, old, "");
+        }
+        if !extensions.is_empty() {
+            let at = show_pr.len() - "</p:showPr>".len();
+            match show_pr.find("</p:extLst>") {
+                Some(end) => show_pr.insert_str(end, &extensions),
+                None => show_pr.insert_str(at, &format!("<p:extLst>{extensions}</p:extLst>")),
+            }
+        }
+
+        // `<p:showPr>` comes after the web and print settings, before the
+        // recent colors and extensions
+        if xml.trim_end().ends_with("/>") {
+            if let Some(root) = find_element(&xml, "p:presentationPr", 0) {
+                let open = xml[root.clone()].trim_end_matches("/>").to_string();
+                xml = splice(&xml, root, &format!("{open}></p:presentationPr>"));
+            }
+        }
+        let at = AFTER_SHOW_PR
+            .iter()
+            .filter_map(|qname| find_element(&xml, qname, 0).map(|r| r.start))
+            .min()
+            .or_else(|| xml.rfind("</p:presentationPr>"))
+            .ok_or_else(|| {
+                PptxError::InvalidXml(
+                    "presentation properties do not contain </p:presentationPr>".to_string(),
+                )
+            })?;
+        xml.insert_str(at, &show_pr);
+        part.blob = xml.into_bytes();
+        Ok(())
+    }
+
+    /// Get the custom shows of the presentation, in order.
+    ///
+    /// Slides a custom show references that are no longer in the
+    /// presentation are left out.
+    /// # Errors
+    ///
+    /// Returns an error if the presentation XML cannot be parsed.
+    pub fn custom_shows(&self) -> PptxResult<Vec<CustomShow>> {
+        let pres_part = self.presentation_part()?;
+        let slide_ids = parse_slide_ids(&pres_part.blob)?;
+        Ok(parse_custom_shows(&pres_part.blob)?
+            .into_iter()
+            .map(|entry| CustomShow {
+                name: entry.name,
+                id: entry.id,
+                slide_ids: entry
+                    .slide_r_ids
+                    .iter()
+                    .filter_map(|r_id| {
+                        slide_ids
+                            .iter()
+                            .find(|(slide_r_id, _)| slide_r_id == r_id)
+                            .map(|(_, id)| *id)
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Add a custom show of the given slides, in the given order.
+    ///
+    /// Returns the ID of the new custom show.
+    /// # Errors
+    ///
+    /// Returns an error if a slide ID is not in the presentation, or if the
+    /// presentation XML cannot be updated.
+    pub fn add_custom_show(&mut self, name: &str, slide_ids: &[SlideId]) -> PptxResult<u32> {
+        let slide_r_ids = self.slide_r_ids(slide_ids)?;
+        let mut entries = self.custom_show_entries()?;
+        let id = entries.iter().map(|e| e.id + 1).max().unwrap_or(0);
+        entries.push(CustomShowEntry {
+            name: name.to_string(),
+            id,
+            slide_r_ids,
+        });
+        self.write_custom_show_entries(&entries)?;
+        Ok(id)
+    }
+
+    /// Replace the slides of the custom show `id`.
+    /// # Errors
+    ///
+    /// Returns an error if there is no such custom show, a slide ID is not
+    /// in the presentation, or the presentation XML cannot be updated.
+    pub fn set_custom_show_slides(&mut self, id: u32, slide_ids: &[SlideId]) -> PptxResult<()> {
+        self.custom_show_entry(id)?;
+        let slide_r_ids = self.slide_r_ids(slide_ids)?;
+        let mut entries = self.custom_show_entries()?;
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.slide_r_ids = slide_r_ids;
+        }
+        self.write_custom_show_entries(&entries)
+    }
+
+    /// Rename the custom show `id`.
+    /// # Errors
+    ///
+    /// Returns an error if there is no such custom show or the
+    /// presentation XML cannot be updated.
+    pub fn rename_custom_show(&mut self, id: u32, name: &str) -> PptxResult<()> {
+        self.custom_show_entry(id)?;
+        let mut entries = self.custom_show_entries()?;
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.name = name.to_string();
+        }
+        self.write_custom_show_entries(&entries)
+    }
+
+    /// Delete the custom show `id`.
+    ///
+    /// Show settings that run the custom show go back to all slides.
+    /// # Errors
+    ///
+    /// Returns an error if there is no such custom show or the
+    /// presentation cannot be updated.
+    pub fn delete_custom_show(&mut self, id: u32) -> PptxResult<()> {
+        self.custom_show_entry(id)?;
+        let mut entries = self.custom_show_entries()?;
+        entries.retain(|e| e.id != id);
+        self.write_custom_show_entries(&entries)?;
+
+        let mut settings = self.show_settings()?;
+        if settings.slide_range == SlideRange::CustomShow(id) {
+            settings.slide_range = SlideRange::All;
+            self.set_show_settings(&settings)?;
+        }
+        Ok(())
+    }
+
+    /// Get the slide ID of a slide, as custom shows refer to it.
+    /// # Errors
+    ///
+    /// Returns an error if the slide is not in the presentation.
+    pub fn slide_id(&self, slide_ref: &SlideRef) -> PptxResult<SlideId> {
+        parse_slide_ids(&self.presentation_part()?.blob)?
+            .into_iter()
+            .find(|(r_id, _)| r_id == slide_ref.r_id.as_str())
+            .map(|(_, id)| id)
+            .ok_or_else(|| PptxError::InvalidValue {
+                field: "slide_ref",
+                value: slide_ref.partname.to_string(),
+                expected: "a slide of this presentation",
+            })
+    }
+
+    /// The custom show `id`, as stored.
+    fn custom_show_entry(&self, id: u32) -> PptxResult<CustomShowEntry> {
+        self.custom_show_entries()?
+            .into_iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| PptxError::InvalidValue {
+                field: "custom show",
+                value: id.to_string(),
+                expected: "the ID of a custom show of this presentation",
+            })
+    }
+
+    fn custom_show_entries(&self) -> PptxResult<Vec<CustomShowEntry>> {
+        parse_custom_shows(&self.presentation_part()?.blob)
+    }
+
+    fn write_custom_show_entries(&mut self, entries: &[CustomShowEntry]) -> PptxResult<()> {
+        let pres_partname = self.presentation_partname()?;
+        let pres_part = self
+            .package
+            .part_mut(&pres_partname)
+            .or_part_not_found(pres_partname.as_str())?;
+        pres_part.blob = write_custom_shows(&pres_part.blob, entries)?;
+        Ok(())
+    }
+
+    /// The relationship IDs of the slides `slide_ids`.
+    fn slide_r_ids(&self, slide_ids: &[SlideId]) -> PptxResult<Vec<String>> {
+        let slides = parse_slide_ids(&self.presentation_part()?.blob)?;
+        slide_ids
+            .iter()
+            .map(|slide_id| {
+                slides
+                    .iter()
+                    .find(|(_, id)| id == slide_id)
+                    .map(|(r_id, _)| r_id.clone())
+                    .ok_or_else(|| PptxError::InvalidValue {
+                        field: "slide_ids",
+                        value: slide_id.0.to_string(),
+                        expected: "the ID of a slide of this presentation",
+                    })
+            })
+            .collect()
+    }
+
+    /// The partname of presProps.xml, adding the part if needed.
+    fn or_add_pres_props_part(&mut self) -> PptxResult<PackURI> {
+        if let Some(part) = self.presentation_related_part(RT::PRES_PROPS)? {
+            return Ok(part.partname.clone());
+        }
+        let pres_partname = self.presentation_partname()?;
+        let partname = PackURI::new("/ppt/presProps.xml")?;
+        let target_ref = partname.relative_ref(pres_partname.base_uri());
+        self.package.put_part(Part::new(
+            partname.clone(),
+            CT::PML_PRES_PROPS,
+            EMPTY_PRES_PROPS.as_bytes().to_vec(),
+        ));
+        let pres_part = self
+            .package
+            .part_mut(&pres_partname)
+            .or_part_not_found(pres_partname.as_str())?;
+        pres_part.rels.or_add(RT::PRES_PROPS, &target_ref, false);
+        Ok(partname)
+    }
+}
@@ -1,6 +1,8 @@
+use crate::custom_show::remove_slide_from_custom_shows;
 use crate::error::{PackageError, PartNotFoundExt, PptxError, PptxResult};
 use crate::opc::constants::content_type as CT;
 use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
 use crate::opc::part::Part;
 use crate::slide::{
     add_slide_id_to_presentation_xml, new_slide_xml, next_slide_id, parse_slide_ids,
@@ -88,7 +90,8 @@ impl Presentation {
     /// Move a slide from one position to another.
     ///
     /// Both `from_index` and `to_index` are 0-based. This reorders the
-    /// `<p:sldIdLst>` entries in the presentation XML.
+    /// `<p:sldIdLst>` entries in the presentation XML. If the presentation
+    /// has sections, the slide joins the section of the slide before it.
     /// # Errors
     ///
     /// Returns an error if the indices are out of range.
@@ -101,8 +104,14 @@ impl Presentation {
             .package
             .part_mut(&pres_partname)
             .or_part_not_found(pres_partname.as_str())?;
+        let moved = parse_slide_ids(&pres_part.blob)?
+            .get(from_index)
+            .map(|(_, id)| *id);
         pres_part.blob = reorder_slide_in_presentation_xml(&pres_part.blob, from_index, to_index)?;
-        Ok(())
+        match moved {
+            Some(id) => self.place_moved_slide_in_sections(id),
+            None => Ok(()),
+        }
     }
 
     /// Add a new blank slide to the presentation using the given layout.
@@ -117,8 +126,6 @@ impl Presentation {
 
         // Pre-compute relative references before consuming the partname
         let layout_target_ref = layout.partname.relative_ref(slide_partname.base_uri());
-        let pres_partname = self.presentation_partname()?;
-        let slide_target_ref = slide_partname.relative_ref(pres_partname.base_uri());
 
         // Create the new slide part with a blank slide XML
         let slide_blob = new_slide_xml();
@@ -132,6 +139,16 @@ impl Presentation {
         // Add the slide part to the package
         self.package.put_part(slide_part);
 
+        self.append_slide_part(slide_partname)
+    }
+
+    /// Add an existing slide part to the end of the slide list.
+    ///
+    /// Creates the relationship from the presentation part and the matching
+    /// `<p:sldId>` entry, and adds the slide to the last section.
+    pub(super) fn append_slide_part(&mut self, slide_partname: PackURI) -> PptxResult<SlideRef> {
+        let pres_partname = self.presentation_partname()?;
+        let slide_target_ref = slide_partname.relative_ref(pres_partname.base_uri());
         let pres_part = self
             .package
             .part_mut(&pres_partname)
@@ -147,6 +164,13 @@ impl Presentation {
         let updated_xml = add_slide_id_to_presentation_xml(&pres_part.blob, &r_id_str, slide_id)?;
         pres_part.blob = updated_xml;
 
+        // A new slide joins the last section
+        let mut sections = self.section_entries()?;
+        if let Some(last) = sections.last_mut() {
+            last.slide_ids.push(slide_id);
+            self.write_section_entries(&sections)?;
+        }
+
         let r_id = RelationshipId::try_from(r_id_str.as_str())
             .map_err(|_| PptxError::InvalidXml(format!("invalid slide rId: {r_id_str}")))?;
 
@@ -229,7 +253,7 @@ impl Presentation {
     /// Delete a slide from the presentation.
     ///
     /// Removes the slide part, its relationship from the presentation part,
-    /// and its entry in `<p:sldIdLst>`.
+    /// and its entries in `<p:sldIdLst>` and its section.
     /// # Errors
     ///
     /// Returns an error if the slide cannot be removed.
@@ -248,9 +272,30 @@ impl Presentation {
         pres_part.rels.remove(slide_ref.r_id.as_str());
 
         // Remove the sldId entry from presentation XML
+        let slide_id = parse_slide_ids(&pres_part.blob)?
+            .into_iter()
+            .find(|(r_id, _)| r_id == slide_ref.r_id.as_str())
+            .map(|(_, id)| id);
         pres_part.blob =
             remove_slide_id_from_presentation_xml(&pres_part.blob, slide_ref.r_id.as_str())?;
 
+        // And from the custom shows it is in
+        if let Some(blob) =
+            remove_slide_from_custom_shows(&pres_part.blob, slide_ref.r_id.as_str())?
+        {
+            pres_part.blob = blob;
+        }
+
+        // And from its section
+        let mut sections = self.section_entries()?;
+        if let Some(section) = sections
+            .iter_mut()
+            .find(|s| slide_id.is_some_and(|id| s.slide_ids.contains(&id)))
+        {
+            section.slide_ids.retain(|id| Some(*id) != slide_id);
+            self.write_section_entries(&sections)?;
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,193 @@
+//! Template filling on a [`Presentation`].
+
+use crate::chart::ChartData;
+use crate::error::{PartNotFoundExt, PptxResult};
+use crate::media::Image;
+use crate::opc::constants::relationship_type as RT;
+use crate::shapes::{Shape, ShapeTree};
+use crate::slide::SlideRef;
+use crate::template::xml::{
+    has_slide_section, picture_in_place_of, repeat_table_rows, with_picture_image,
+};
+use crate::template::{section_end, section_start, token, TemplateData, TemplateValue};
+use crate::text::ReplaceOptions;
+use crate::units::ShapeId;
+
+use super::Presentation;
+
+impl Presentation {
+    /// Fill the template tokens of every slide from `data`.
+    ///
+    /// See the [`template`](crate::template) module for the token syntax.
+    /// A slide holding `{{#key}}` outside its tables is replaced by one
+    /// copy per item of the list `key` (and removed if the list is empty);
+    /// its notes and charts are copied and filled along with it.
+    ///
+    /// ```no_run
+    /// use pptx::presentation::Presentation;
+    /// use pptx::template::TemplateData;
+    ///
+    /// let mut prs = Presentation::open("report-template.pptx").unwrap();
+    /// let data = TemplateData::new()
+    ///     .with_text("customer_name", "Acme Corp")
+    ///     .with_list("rows", vec![TemplateData::new().with_text("item", "Widgets")]);
+    /// prs.fill_template(&data).unwrap();
+    /// prs.save("report.pptx").unwrap();
+    /// ```
+    /// # Errors
+    ///
+    /// Returns an error if a slide or one of its parts cannot be read or
+    /// updated, or chart data does not match the kind of its chart.
+    pub fn fill_template(&mut self, data: &TemplateData) -> PptxResult<()> {
+        for slide in self.slides()? {
+            let slide_xml = std::str::from_utf8(self.slide_xml(&slide)?)?;
+            let mut section = None;
+            for (key, items) in data.lists() {
+                if has_slide_section(slide_xml, key)? {
+                    section = Some((key, items));
+                    break;
+                }
+            }
+            let Some((key, items)) = section else {
+                self.fill_slide(&slide, data)?;
+                continue;
+            };
+
+            let index = self.slide_index(&slide)?;
+            for (offset, item) in items.iter().enumerate() {
+                let copy = self.duplicate_slide(&slide, Some(index + 1 + offset))?;
+                let options = ReplaceOptions::new();
+                self.replace_text_on_slide(&copy, &section_start(key), "", options)?;
+                self.replace_text_on_slide(&copy, &section_end(key), "", options)?;
+                self.fill_slide(&copy, &data.scoped(item))?;
+            }
+            self.remove_template_slide(&slide)?;
+        }
+        Ok(())
+    }
+
+    /// Fill the tokens of one slide, its notes and its charts.
+    fn fill_slide(&mut self, slide: &SlideRef, data: &TemplateData) -> PptxResult<()> {
+        let slide_xml = std::str::from_utf8(self.slide_xml(slide)?)?;
+        let updated = repeat_table_rows(slide_xml, data)?;
+        *self.slide_xml_mut(slide)? = updated.into_bytes();
+
+        for (key, value) in data.iter() {
+            match value {
+                TemplateValue::Image(image) => self.fill_pictures(slide, &token(key), image)?,
+                TemplateValue::Chart(chart_data) => {
+                    self.fill_charts(slide, &token(key), chart_data)?;
+                }
+                TemplateValue::Text(_) | TemplateValue::List(_) => {}
+            }
+        }
+
+        // Text tokens, on the slide and in its notes and charts
+        for (key, value) in data.texts() {
+            self.replace_text_on_slide(slide, &token(key), value, ReplaceOptions::new())?;
+        }
+        Ok(())
+    }
+
+    /// Put `image` in every picture slot named `token` on the slide.
+    fn fill_pictures(&mut self, slide: &SlideRef, token: &str, image: &Image) -> PptxResult<()> {
+        let mut slots = Vec::new();
+        picture_slots(&self.slide_shapes(slide)?.shapes, token, &mut slots);
+        if slots.is_empty() {
+            return Ok(());
+        }
+
+        let (image_partname, _) = self.package.or_add_image_part(image)?;
+        let slide_part = self
+            .package
+            .part_mut(&slide.partname)
+            .or_part_not_found(slide.partname.as_str())?;
+        let target = image_partname.relative_ref(slide_part.partname.base_uri());
+        let r_id = slide_part.rels.or_add(RT::IMAGE, &target, false);
+
+        for (shape_id, is_picture) in slots {
+            let old = ShapeTree::shape_xml(self.slide_xml(slide)?, shape_id)?;
+            let new = if is_picture {
+                with_picture_image(&old, &r_id)
+            } else {
+                picture_in_place_of(&old, &r_id)
+            };
+            let updated = ShapeTree::replace_shape_xml(self.slide_xml(slide)?, shape_id, &new)?;
+            *self.slide_xml_mut(slide)? = updated;
+            self.release_relationships(slide, old.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Replace the data of every chart named `token` on the slide.
+    fn fill_charts(
+        &mut self,
+        slide: &SlideRef,
+        token: &str,
+        chart_data: &ChartData,
+    ) -> PptxResult<()> {
+        let mut frames = Vec::new();
+        chart_slots(&self.slide_shapes(slide)?.shapes, token, &mut frames);
+        for shape_id in frames {
+            self.replace_chart_data(slide, shape_id, chart_data)?;
+        }
+        Ok(())
+    }
+
+    /// Delete a slide that was repeated, along with the notes slide and
+    /// charts no other slide uses.
+    fn remove_template_slide(&mut self, slide: &SlideRef) -> PptxResult<()> {
+        let part = self
+            .package
+            .part(&slide.partname)
+            .or_part_not_found(slide.partname.as_str())?;
+        let mut targets = Vec::new();
+        for rel in part.rels.iter().filter(|r| !r.is_external) {
+            targets.push(rel.target_partname(part.rels.base_uri())?);
+        }
+        self.delete_slide(slide)?;
+        for target in targets {
+            self.package.remove_part_if_unreferenced(&target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shapes that take an image for `token`: pictures named or described
+/// `token` (`true`), and other shapes named `token` or holding just that
+/// text (`false`).
+fn picture_slots(shapes: &[Shape], token: &str, slots: &mut Vec<(ShapeId, bool)>) {
+    for shape in shapes {
+        match shape {
+            Shape::Picture(pic)
+                if pic.name == token || pic.description.as_deref() == Some(token) =>
+            {
+                slots.push((pic.shape_id, true));
+            }
+            Shape::AutoShape(sp)
+                if sp.name == token
+                    || sp
+                        .text_frame
+                        .as_ref()
+                        .is_some_and(|tf| tf.text().trim() == token) =>
+            {
+                slots.push((sp.shape_id, false));
+            }
+            Shape::GroupShape(group) => picture_slots(&group.shapes, token, slots),
+            _ => {}
+        }
+    }
+}
+
+/// Chart frames named `token`.
+fn chart_slots(shapes: &[Shape], token: &str, frames: &mut Vec<ShapeId>) {
+    for shape in shapes {
+        match shape {
+            Shape::GraphicFrame(frame) if frame.chart_r_id.is_some() && frame.name == token => {
+                frames.push(frame.shape_id);
+            }
+            Shape::GroupShape(group) => chart_slots(&group.shapes, token, frames),
+            _ => {}
+        }
+    }
+}
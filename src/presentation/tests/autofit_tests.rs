@@ -0,0 +1,82 @@
+use crate::embedded_font::EmbeddedFont;
+use crate::enums::text::MsoAutoSize;
+use crate::presentation::Presentation;
+use crate::render::sample_font;
+use crate::slide::SlideRef;
+use crate::text::layout::FontSet;
+use crate::units::Emu;
+
+fn append_shapes(prs: &mut Presentation, slide_ref: &SlideRef, shapes: &str) {
+    let xml = String::from_utf8(prs.slide_xml(slide_ref).unwrap().to_vec()).unwrap();
+    *prs.slide_xml_mut(slide_ref).unwrap() = xml
+        .replacen("</p:spTree>", &format!("{shapes}</p:spTree>"), 1)
+        .into_bytes();
+}
+
+/// A text box of `lines` paragraphs of 10pt text in the sample font, with
+/// no insets.
+fn text_box(id: u32, autofit: &str, lines: usize) -> String {
+    let para = r#"<a:p><a:r><a:rPr lang="en-US" sz="1000"><a:latin typeface="Sample"/></a:rPr><a:t>AAAA AAAA</a:t></a:r></a:p>"#;
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="Text {id}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="12700" y="12700"/><a:ext cx="508000" cy="254000"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr><p:txBody><a:bodyPr wrap="square" lIns="0" tIns="0" rIns="0" bIns="0">{autofit}</a:bodyPr><a:lstStyle/>{}</p:txBody></p:sp>"#,
+        para.repeat(lines)
+    )
+}
+
+fn deck(shapes: &str) -> (Presentation, SlideRef) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+    append_shapes(&mut prs, &slide_ref, shapes);
+    (prs, slide_ref)
+}
+
+fn sample_fonts() -> FontSet {
+    FontSet::new()
+        .with_font(&EmbeddedFont::from_bytes(
+            sample_font(),
+            "Sample",
+            false,
+            false,
+        ))
+        .unwrap()
+}
+
+#[test]
+fn autofit_resizes_shapes_to_their_text() {
+    let (mut prs, slide_ref) = deck(&text_box(2, "<a:spAutoFit/>", 2));
+    prs.autofit_slide(&slide_ref, &sample_fonts()).unwrap();
+
+    // Each paragraph wraps into two 12pt lines in the 40pt wide box
+    let tree = prs.slide_shapes(&slide_ref).unwrap();
+    let shape = tree.shapes[0].as_autoshape().unwrap();
+    assert_eq!(shape.height, Emu(4 * 12 * 12_700));
+    assert_eq!(
+        (shape.left, shape.top, shape.width),
+        (Emu(12_700), Emu(12_700), Emu(508_000))
+    );
+}
+
+#[test]
+fn autofit_shrinks_overflowing_text() {
+    let shapes = [
+        text_box(2, "<a:normAutofit/>", 1),
+        text_box(3, "<a:normAutofit/>", 2),
+        text_box(4, "", 3),
+    ]
+    .concat();
+    let (mut prs, slide_ref) = deck(&shapes);
+    prs.autofit_text(&sample_fonts()).unwrap();
+
+    let xml = String::from_utf8(prs.slide_xml(&slide_ref).unwrap().to_vec()).unwrap();
+    // Two 12pt lines overflow the 20pt high box a little
+    assert!(xml.contains(r#"<a:normAutofit fontScale="92500" lnSpcReduction="10000"/>"#));
+    // Four lines do not fit until the words fit on one line at 70%
+    assert!(xml.contains(r#"<a:normAutofit fontScale="70000" lnSpcReduction="20000"/>"#));
+    let tree = prs.slide_shapes(&slide_ref).unwrap();
+    let untouched = tree.shapes[2].as_autoshape().unwrap();
+    assert_eq!(
+        untouched.text_frame.as_ref().unwrap().auto_size,
+        MsoAutoSize::None
+    );
+}
@@ -16,6 +16,25 @@ fn test_add_image_dedup() {
     assert_eq!(partname1, partname2);
 }
 
+#[test]
+fn test_add_bullet_image() {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let img = Image::from_bytes(vec![1, 2, 3, 4, 5], "image/png");
+    let r_id = prs.add_bullet_image(&slide_ref, &img).unwrap();
+    // The same image reuses the part and the relationship
+    assert_eq!(prs.add_bullet_image(&slide_ref, &img).unwrap(), r_id);
+
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    let rel = slide_part.rels.get(&r_id).unwrap();
+    assert_eq!(rel.rel_type, RT::IMAGE);
+    let partname = rel.target_partname(slide_part.rels.base_uri()).unwrap();
+    assert!(prs.package().part(&partname).is_some());
+    assert_eq!(slide_part.rels.all_by_reltype(RT::IMAGE).len(), 1);
+}
+
 #[test]
 fn test_add_chart_to_slide() {
     use crate::chart::data::CategoryChartData;
@@ -138,6 +157,272 @@ fn test_add_multiple_charts() {
     assert!(c2_xml.contains("<c:lineChart>"));
 }
 
+#[test]
+fn test_chart_for_parses_slide_chart() {
+    use crate::chart::data::CategoryChartData;
+    use crate::enums::chart::XlChartType;
+    use crate::shapes::Shape;
+    use crate::units::Inches;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("Q1");
+    data.add_category("Q2");
+    data.add_series("Sales", &[100.0, 150.0]);
+
+    let size: Emu = Inches(4.0).into();
+    prs.add_chart_to_slide(
+        &slide_ref,
+        &data,
+        XlChartType::BarClustered,
+        Emu(0),
+        Emu(0),
+        size,
+        size,
+    )
+    .unwrap();
+
+    // Reopen so the chart is read from the saved package
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let slide_ref = prs.slides().unwrap().remove(0);
+    let tree = prs.slide_shapes(&slide_ref).unwrap();
+    let Shape::GraphicFrame(frame) = &tree.shapes[0] else {
+        panic!("Expected GraphicFrame shape");
+    };
+
+    let chart = prs.chart_for(&slide_ref, frame).unwrap().unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::BarClustered);
+    assert_eq!(chart.series().len(), 1);
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.name(), "Sales");
+    assert_eq!(series.values(), &[Some(100.0), Some(150.0)]);
+}
+
+#[test]
+fn test_replace_chart_data_updates_chart_and_workbook() {
+    use crate::chart::data::{CategoryChartData, ChartData};
+    use crate::enums::chart::XlChartType;
+    use crate::shapes::Shape;
+    use crate::units::Inches;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("Q1");
+    data.add_series("Old", &[1.0]);
+    let size: Emu = Inches(4.0).into();
+    prs.add_chart_to_slide(
+        &slide_ref,
+        &data,
+        XlChartType::Line,
+        Emu(0),
+        Emu(0),
+        size,
+        size,
+    )
+    .unwrap();
+
+    let tree = prs.slide_shapes(&slide_ref).unwrap();
+    let Shape::GraphicFrame(frame) = &tree.shapes[0] else {
+        panic!("Expected GraphicFrame shape");
+    };
+    let shape_id = frame.shape_id;
+
+    let mut data = CategoryChartData::new();
+    data.add_category("Jan");
+    data.add_category("Feb");
+    data.add_series("Visitors", &[120.0, 340.0]);
+    prs.replace_chart_data(&slide_ref, shape_id, &ChartData::Category(data))
+        .unwrap();
+
+    let chart = prs.chart_for(&slide_ref, frame).unwrap().unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::Line);
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.name(), "Visitors");
+    assert_eq!(series.values(), &[Some(120.0), Some(340.0)]);
+
+    // The embedded workbook was regenerated in place
+    let chart_partname = PackURI::new("/ppt/charts/chart1.xml").unwrap();
+    let chart_part = prs.package().part(&chart_partname).unwrap();
+    let workbook_rels = chart_part.rels.all_by_reltype(RT::PACKAGE);
+    assert_eq!(workbook_rels.len(), 1);
+    let xlsx_partname = workbook_rels[0]
+        .target_partname(chart_partname.base_uri())
+        .unwrap();
+    let xlsx = &prs.package().part(&xlsx_partname).unwrap().blob;
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(xlsx)).unwrap();
+    let mut shared_strings = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name("xl/sharedStrings.xml").unwrap(),
+        &mut shared_strings,
+    )
+    .unwrap();
+    assert!(shared_strings.contains("Visitors"));
+    assert!(!shared_strings.contains("Old"));
+}
+
+#[test]
+fn test_replace_chart_data_rejects_non_chart_shape() {
+    use crate::chart::data::{CategoryChartData, ChartData};
+    use crate::units::ShapeId;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let data = ChartData::Category(CategoryChartData::new());
+    assert!(prs
+        .replace_chart_data(&slide_ref, ShapeId(42), &data)
+        .is_err());
+}
+
+#[test]
+fn test_add_chart_xy_with_style() {
+    use crate::chart::data::{ChartData, XyChartData};
+    use crate::chart::Chart;
+    use crate::enums::chart::{XlChartType, XlLegendPosition};
+    use crate::shapes::Shape;
+    use crate::units::Inches;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let mut data = XyChartData::new();
+    let series = data.add_series("Samples");
+    series.add_data_point(1.0, 2.5);
+    series.add_data_point(2.0, 4.0);
+
+    let mut style = Chart::new(XlChartType::XyScatter);
+    style.set_title("Measurements");
+    style.set_legend_position(XlLegendPosition::Right);
+    style
+        .value_axis_mut()
+        .unwrap()
+        .set_maximum_scale(Some(10.0));
+
+    let size: Emu = Inches(4.0).into();
+    prs.add_chart(
+        &slide_ref,
+        &ChartData::Xy(data),
+        XlChartType::XyScatter,
+        Some(&style),
+        Emu(0),
+        Emu(0),
+        size,
+        size,
+    )
+    .unwrap();
+
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let slide_ref = prs.slides().unwrap().remove(0);
+    let tree = prs.slide_shapes(&slide_ref).unwrap();
+    let Shape::GraphicFrame(frame) = &tree.shapes[0] else {
+        panic!("Expected GraphicFrame shape");
+    };
+    let chart = prs.chart_for(&slide_ref, frame).unwrap().unwrap();
+    assert_eq!(chart.chart_type(), XlChartType::XyScatter);
+    assert_eq!(chart.title(), Some("Measurements"));
+    assert_eq!(chart.legend().unwrap().position(), XlLegendPosition::Right);
+    assert_eq!(chart.value_axis().unwrap().maximum_scale(), Some(10.0));
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.x_values(), &[Some(1.0), Some(2.0)]);
+    assert_eq!(series.values(), &[Some(2.5), Some(4.0)]);
+}
+
+#[test]
+fn test_add_chart_combo_and_date_axis() {
+    use crate::chart::data::{ChartData, ComboChartData, ComboSeriesType, DateAxisChartData};
+    use crate::enums::chart::XlChartType;
+    use crate::units::Inches;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+    let size: Emu = Inches(4.0).into();
+
+    let mut combo = ComboChartData::new();
+    combo.add_category("Q1");
+    combo.add_category("Q2");
+    combo.add_series("Revenue", ComboSeriesType::Bar, &[10.0, 12.0]);
+    combo.add_series("Margin", ComboSeriesType::Line, &[0.2, 0.25]);
+    prs.add_chart(
+        &slide_ref,
+        &ChartData::Combo(combo),
+        XlChartType::ColumnClustered,
+        None,
+        Emu(0),
+        Emu(0),
+        size,
+        size,
+    )
+    .unwrap();
+
+    let mut dates = DateAxisChartData::new();
+    dates.add_date("2024-01-01");
+    dates.add_date("2024-01-02");
+    dates.add_series("Visits", &[5.0, 7.0]);
+    prs.add_chart(
+        &slide_ref,
+        &ChartData::DateAxis(dates),
+        XlChartType::Line,
+        None,
+        Emu(0),
+        Emu(0),
+        size,
+        size,
+    )
+    .unwrap();
+
+    let combo_xml = prs
+        .package()
+        .part(&PackURI::new("/ppt/charts/chart1.xml").unwrap())
+        .unwrap()
+        .blob
+        .clone();
+    let combo_xml = String::from_utf8(combo_xml).unwrap();
+    assert!(combo_xml.contains("<c:barChart>"));
+    assert!(combo_xml.contains("<c:lineChart>"));
+
+    let date_part = prs
+        .package()
+        .part(&PackURI::new("/ppt/charts/chart2.xml").unwrap())
+        .unwrap();
+    let date_xml = String::from_utf8(date_part.blob.clone()).unwrap();
+    assert!(date_xml.contains("<c:dateAx>"));
+    assert!(date_xml.contains("<c:v>45292</c:v>"));
+    assert_eq!(date_part.rels.all_by_reltype(RT::PACKAGE).len(), 1);
+}
+
+#[test]
+fn test_add_chart_rejects_mismatched_chart_type() {
+    use crate::chart::data::{ChartData, XyChartData};
+    use crate::enums::chart::XlChartType;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let mut data = XyChartData::new();
+    data.add_series("S").add_data_point(1.0, 2.0);
+    let result = prs.add_chart(
+        &slide_ref,
+        &ChartData::Xy(data),
+        XlChartType::Pie,
+        None,
+        Emu(0),
+        Emu(0),
+        Emu(100),
+        Emu(100),
+    );
+    assert!(result.is_err());
+}
+
 // --- Embedded font tests ---
 
 #[test]
@@ -0,0 +1,300 @@
+use crate::comment::Comment;
+use crate::opc::constants::{content_type as CT, relationship_type as RT};
+use crate::opc::pack_uri::PackURI;
+use crate::opc::part::Part;
+use crate::presentation::Presentation;
+use crate::shapes::shapetree::ShapeTree;
+use crate::slide::SlideRef;
+use crate::units::{Emu, ShapeId};
+
+fn presentation() -> (Presentation, SlideRef) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+    (prs, slide_ref)
+}
+
+fn slide_text(prs: &Presentation, slide_ref: &SlideRef) -> String {
+    String::from_utf8(prs.slide_xml(slide_ref).unwrap().to_vec()).unwrap()
+}
+
+#[test]
+fn test_no_comments_by_default() {
+    let (prs, slide_ref) = presentation();
+    assert!(prs.slide_comments(&slide_ref).unwrap().is_empty());
+}
+
+#[test]
+fn test_add_comment_round_trip() {
+    let (mut prs, slide_ref) = presentation();
+    let comment = Comment::new("Jane Doe", "Check the numbers", Emu(100), Emu(200))
+        .with_date("2024-01-15T10:30:00.000");
+    let id = prs.add_comment(&slide_ref, &comment).unwrap();
+    let slide_xml = prs.slide_xml_mut(&slide_ref).unwrap();
+    *slide_xml = ShapeTree::add_textbox(slide_xml, Emu(0), Emu(0), Emu(10), Emu(10)).unwrap();
+    let shape_id = prs.slide_shapes(&slide_ref).unwrap().shapes[0].shape_id();
+    let anchored = Comment::new("Bob", "Shorter title?", Emu(0), Emu(0))
+        .with_date("2024-01-15T11:00:00.000")
+        .with_shape_id(shape_id);
+    let other = prs.add_comment(&slide_ref, &anchored).unwrap();
+    assert_ne!(id, other);
+
+    // The slide links to its comments part, and the authors are listed once
+    assert!(slide_text(&prs, &slide_ref).contains("<p188:commentRel"));
+    prs.add_comment(
+        &slide_ref,
+        &Comment::new("Jane Doe", "Again", Emu(0), Emu(0)),
+    )
+    .unwrap();
+    let pres_part = prs.presentation_part().unwrap();
+    let rel = pres_part.rels.by_reltype(RT::AUTHORS).unwrap();
+    let authors = prs
+        .package()
+        .part(&rel.target_partname(pres_part.partname.base_uri()).unwrap())
+        .unwrap();
+    assert_eq!(authors.content_type, CT::PPT_AUTHORS);
+    assert_eq!(
+        std::str::from_utf8(&authors.blob)
+            .unwrap()
+            .matches("<p188:author ")
+            .count(),
+        2
+    );
+
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let slide_ref = &prs.slides().unwrap()[0];
+    let comments = prs.slide_comments(slide_ref).unwrap();
+    assert_eq!(comments.len(), 3);
+    assert_eq!(comments[0].id.as_deref(), Some(id.as_str()));
+    assert_eq!(comments[0].author, "Jane Doe");
+    assert_eq!(comments[0].author_initials, "JD");
+    assert_eq!(comments[0].text, "Check the numbers");
+    assert_eq!(comments[0].date, "2024-01-15T10:30:00.000");
+    assert_eq!(comments[0].position, (Emu(100), Emu(200)));
+    assert_eq!(comments[0].shape_id, None);
+    assert_eq!(comments[1].author, "Bob");
+    assert_eq!(comments[1].author_id, 1);
+    assert_eq!(comments[1].shape_id, Some(shape_id));
+    // Comments without a date get the current time
+    assert!(!comments[2].date.is_empty());
+}
+
+#[test]
+fn test_add_comment_to_missing_shape_fails() {
+    let (mut prs, slide_ref) = presentation();
+    let comment = Comment::new("Jane", "Where?", Emu(0), Emu(0)).with_shape_id(ShapeId(99));
+    assert!(prs.add_comment(&slide_ref, &comment).is_err());
+    assert!(prs.slide_comments(&slide_ref).unwrap().is_empty());
+}
+
+#[test]
+fn test_reply_and_resolve() {
+    let (mut prs, slide_ref) = presentation();
+    let id = prs
+        .add_comment(
+            &slide_ref,
+            &Comment::new("Jane", "Typo here", Emu(0), Emu(0)),
+        )
+        .unwrap();
+    let reply = prs
+        .reply_to_comment(
+            &slide_ref,
+            &id,
+            &Comment::new("Bob", "Fixed", Emu(0), Emu(0)),
+        )
+        .unwrap();
+    prs.reply_to_comment(
+        &slide_ref,
+        &id,
+        &Comment::new("Jane", "Thanks", Emu(0), Emu(0)),
+    )
+    .unwrap();
+    prs.resolve_comment(&slide_ref, &id, true).unwrap();
+
+    let comments = prs.slide_comments(&slide_ref).unwrap();
+    assert!(comments[0].resolved);
+    let replies: Vec<_> = comments[0]
+        .replies
+        .iter()
+        .map(|r| (r.author.as_str(), r.text.as_str()))
+        .collect();
+    assert_eq!(replies, [("Bob", "Fixed"), ("Jane", "Thanks")]);
+    assert_eq!(comments[0].replies[0].id.as_deref(), Some(reply.as_str()));
+
+    prs.resolve_comment(&slide_ref, &id, false).unwrap();
+    assert!(!prs.slide_comments(&slide_ref).unwrap()[0].resolved);
+
+    let nobody = Comment::new("Nobody", "?", Emu(0), Emu(0));
+    assert!(prs
+        .reply_to_comment(&slide_ref, "{missing}", &nobody)
+        .is_err());
+    assert!(prs.resolve_comment(&slide_ref, "{missing}", true).is_err());
+}
+
+#[test]
+fn test_delete_comment() {
+    let (mut prs, slide_ref) = presentation();
+    let first = prs
+        .add_comment(&slide_ref, &Comment::new("Jane", "One", Emu(0), Emu(0)))
+        .unwrap();
+    let second = prs
+        .add_comment(&slide_ref, &Comment::new("Jane", "Two", Emu(0), Emu(0)))
+        .unwrap();
+    let reply = prs
+        .reply_to_comment(
+            &slide_ref,
+            &second,
+            &Comment::new("Bob", "Re", Emu(0), Emu(0)),
+        )
+        .unwrap();
+
+    prs.delete_comment(&slide_ref, &reply).unwrap();
+    assert!(prs.slide_comments(&slide_ref).unwrap()[1]
+        .replies
+        .is_empty());
+    prs.delete_comment(&slide_ref, &first).unwrap();
+    let texts: Vec<_> = prs
+        .slide_comments(&slide_ref)
+        .unwrap()
+        .into_iter()
+        .map(|c| c.text)
+        .collect();
+    assert_eq!(texts, ["Two"]);
+    assert!(prs.delete_comment(&slide_ref, &first).is_err());
+
+    // Deleting the last comment removes the part and the slide's link to it
+    prs.delete_comment(&slide_ref, &second).unwrap();
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    assert!(slide_part
+        .rels
+        .all_by_reltype(RT::MODERN_COMMENTS)
+        .is_empty());
+    assert!(!slide_text(&prs, &slide_ref).contains("commentRel"));
+    assert!(!prs
+        .package()
+        .parts()
+        .any(|p| p.content_type == CT::PPT_COMMENTS));
+}
+
+#[test]
+fn test_legacy_comments() {
+    let (mut prs, slide_ref) = presentation();
+    let pres_partname = prs.presentation_partname().unwrap();
+    prs.package_mut().put_part(Part::new(
+        PackURI::new("/ppt/commentAuthors.xml").unwrap(),
+        "application/vnd.openxmlformats-officedocument.presentationml.commentAuthors+xml",
+        br#"<p:cmAuthorLst xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cmAuthor id="0" name="Jane Doe" initials="JD" lastIdx="2" clrIdx="0"/></p:cmAuthorLst>"#.to_vec(),
+    ));
+    prs.package_mut()
+        .part_mut(&pres_partname)
+        .unwrap()
+        .rels
+        .or_add(RT::COMMENT_AUTHORS, "commentAuthors.xml", false);
+    prs.package_mut().put_part(Part::new(
+        PackURI::new("/ppt/comments/comment1.xml").unwrap(),
+        CT::PML_COMMENTS,
+        br#"<p:cmLst xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cm authorId="0" dt="2020-05-01T12:00:00.000" idx="1"><p:pos x="10" y="10"/><p:text>Old style</p:text></p:cm><p:cm authorId="0" dt="2020-05-02T12:00:00.000" idx="2"><p:pos x="20" y="20"/><p:text>Second</p:text></p:cm></p:cmLst>"#.to_vec(),
+    ));
+    prs.package_mut()
+        .part_mut(&slide_ref.partname)
+        .unwrap()
+        .rels
+        .or_add(RT::COMMENTS, "../comments/comment1.xml", false);
+
+    // Legacy comments come first, threaded ones after them
+    prs.add_comment(
+        &slide_ref,
+        &Comment::new("Bob", "New style", Emu(0), Emu(0)),
+    )
+    .unwrap();
+    let comments = prs.slide_comments(&slide_ref).unwrap();
+    let summary: Vec<_> = comments
+        .iter()
+        .map(|c| {
+            (
+                c.id.clone().unwrap_or_default(),
+                c.author.clone(),
+                c.text.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary[0],
+        ("0-1".into(), "Jane Doe".into(), "Old style".into())
+    );
+    assert_eq!(summary[1].2, "Second");
+    assert_eq!(summary[2].2, "New style");
+
+    // Legacy comments have no threads to reply to
+    let reply = Comment::new("Bob", "Re", Emu(0), Emu(0));
+    assert!(prs.reply_to_comment(&slide_ref, "0-1", &reply).is_err());
+
+    prs.delete_comment(&slide_ref, "0-1").unwrap();
+    prs.delete_comment(&slide_ref, "0-2").unwrap();
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    assert!(slide_part.rels.all_by_reltype(RT::COMMENTS).is_empty());
+    assert_eq!(prs.slide_comments(&slide_ref).unwrap().len(), 1);
+}
+
+#[test]
+fn test_add_comment_rejects_invalid_creation_id() {
+    let (mut prs, slide_ref) = presentation();
+    let xml = slide_text(&prs, &slide_ref).replace(
+        "</p:sld>",
+        r#"<p:extLst><p:ext uri="{BB962C8B-B14F-4D97-AF65-F5344CB8AC3E}"><p14:creationId xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" val="abc"/></p:ext></p:extLst></p:sld>"#,
+    );
+    *prs.slide_xml_mut(&slide_ref).unwrap() = xml.into_bytes();
+    let comment = Comment::new("Jane", "Hi", Emu(0), Emu(0));
+    assert!(prs.add_comment(&slide_ref, &comment).is_err());
+}
+
+#[test]
+fn test_new_comment_part_does_not_replace_existing_part() {
+    let (mut prs, slide_ref) = presentation();
+    let slide_id = prs.slide_id(&slide_ref).unwrap();
+    let taken = PackURI::new(format!(
+        "/ppt/comments/modernComment_{:X}_00000000.xml",
+        slide_id.0
+    ))
+    .unwrap();
+    prs.package_mut().put_part(Part::new(
+        taken.clone(),
+        CT::PPT_COMMENTS,
+        b"other".to_vec(),
+    ));
+
+    prs.add_comment(&slide_ref, &Comment::new("Jane", "Hi", Emu(0), Emu(0)))
+        .unwrap();
+    assert_eq!(prs.package().part(&taken).unwrap().blob, b"other");
+    assert_eq!(prs.slide_comments(&slide_ref).unwrap().len(), 1);
+}
+
+#[test]
+fn test_duplicated_slide_gets_its_own_comments() {
+    let (mut prs, slide_ref) = presentation();
+    let id = prs
+        .add_comment(
+            &slide_ref,
+            &Comment::new("Jane", "Original", Emu(0), Emu(0)),
+        )
+        .unwrap();
+    let copy = prs.duplicate_slide(&slide_ref, None).unwrap();
+
+    // The copied comment is anchored to the copy, in a part named after it
+    let copy_id = prs.slide_id(&copy).unwrap();
+    let slide_part = prs.package().part(&copy.partname).unwrap();
+    let rel = slide_part.rels.all_by_reltype(RT::MODERN_COMMENTS)[0];
+    let partname = rel.target_partname(slide_part.partname.base_uri()).unwrap();
+    assert_eq!(
+        partname.as_str(),
+        format!("/ppt/comments/modernComment_{:X}_00000000.xml", copy_id.0)
+    );
+    let xml = String::from_utf8(prs.package().part(&partname).unwrap().blob.clone()).unwrap();
+    assert!(xml.contains(&format!(r#"<pc:sldMk cId="0" sldId="{copy_id}"/>"#)));
+    let original = prs.slide_id(&slide_ref).unwrap();
+    assert!(!xml.contains(&format!(r#"sldId="{original}""#)));
+
+    prs.delete_comment(&copy, &id).unwrap();
+    assert!(prs.slide_comments(&copy).unwrap().is_empty());
+    assert_eq!(prs.slide_comments(&slide_ref).unwrap().len(), 1);
+}
@@ -0,0 +1,236 @@
+use crate::chart::data::CategoryChartData;
+use crate::comment::Comment;
+use crate::enums::chart::XlChartType;
+use crate::media::Image;
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
+use crate::presentation::{ImportFormatting, ImportOptions, Presentation};
+use crate::slide::SlideRef;
+use crate::units::Emu;
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 1, 2, 3];
+
+fn add_picture_rel(prs: &mut Presentation, slide_ref: &SlideRef) {
+    let image = Image::from_bytes(PNG.to_vec(), "image/png");
+    let partname = PackURI::new(prs.add_image(&image).unwrap()).unwrap();
+    let target = partname.relative_ref(slide_ref.partname.base_uri());
+    prs.package_mut()
+        .part_mut(&slide_ref.partname)
+        .unwrap()
+        .rels
+        .add_relationship(RT::IMAGE, &target, false);
+}
+
+/// A deck whose only slide has a chart, a picture and notes.
+fn source_deck() -> (Presentation, SlideRef) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[1]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("A");
+    data.add_series("S", &[1.0]);
+    let (x, w) = (Emu(0), Emu(914_400));
+    prs.add_chart_to_slide(&slide_ref, &data, XlChartType::Pie, x, x, w, w)
+        .unwrap();
+    add_picture_rel(&mut prs, &slide_ref);
+    let notes = prs.notes_slide_or_create(&slide_ref).unwrap();
+    let notes_master = prs.notes_master_or_create().unwrap();
+    let target = notes_master
+        .partname
+        .relative_ref(notes.partname.base_uri());
+    prs.package_mut()
+        .part_mut(&notes.partname)
+        .unwrap()
+        .rels
+        .add_relationship(RT::NOTES_MASTER, &target, false);
+    (prs, slide_ref)
+}
+
+fn related(prs: &Presentation, partname: &PackURI, reltype: &str) -> Vec<PackURI> {
+    let part = prs.package().part(partname).unwrap();
+    part.rels
+        .all_by_reltype(reltype)
+        .iter()
+        .map(|r| r.target_partname(partname.base_uri()).unwrap())
+        .collect()
+}
+
+fn count_parts(prs: &Presentation, prefix: &str) -> usize {
+    prs.package()
+        .parts()
+        .filter(|p| p.partname.as_str().starts_with(prefix))
+        .count()
+}
+
+#[test]
+fn test_import_slide_with_destination_theme() {
+    let (source, source_slide) = source_deck();
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let existing = prs.add_slide(&layouts[0]).unwrap();
+    add_picture_rel(&mut prs, &existing);
+
+    let imported = prs
+        .import_slide(&source, &source_slide, ImportOptions::new())
+        .unwrap();
+    assert_eq!(prs.slide_count().unwrap(), 2);
+    assert_eq!(prs.slide_index(&imported).unwrap(), 1);
+
+    // Attached to the destination layout of the same name
+    assert_eq!(
+        related(&prs, &imported.partname, RT::SLIDE_LAYOUT),
+        vec![layouts[1].partname.clone()]
+    );
+    // The picture is deduplicated against the one already in the deck
+    assert_eq!(count_parts(&prs, "/ppt/media/"), 1);
+    // Chart and workbook are brought along
+    let charts = related(&prs, &imported.partname, RT::CHART);
+    assert_eq!(charts.len(), 1);
+    assert_eq!(related(&prs, &charts[0], RT::PACKAGE).len(), 1);
+    // Notes point back at the new slide and at this deck's notes master
+    let notes = related(&prs, &imported.partname, RT::NOTES_SLIDE);
+    assert_eq!(
+        related(&prs, &notes[0], RT::SLIDE),
+        vec![imported.partname.clone()]
+    );
+    let notes_master = prs.notes_master().unwrap().unwrap();
+    assert_eq!(
+        related(&prs, &notes[0], RT::NOTES_MASTER),
+        vec![notes_master.partname]
+    );
+    assert_eq!(count_parts(&prs, "/ppt/slideMasters/"), 1);
+
+    let reopened = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    assert_eq!(reopened.slide_count().unwrap(), 2);
+}
+
+#[test]
+fn test_import_slide_keeping_source_formatting() {
+    let (mut source, source_slide) = source_deck();
+    // Give the source deck a theme of its own
+    let theme = PackURI::new("/ppt/theme/theme1.xml").unwrap();
+    let theme_part = source.package_mut().part_mut(&theme).unwrap();
+    let xml = String::from_utf8(theme_part.blob.clone()).unwrap();
+    theme_part.blob = xml
+        .replacen("<a:srgbClr val=\"", "<a:srgbClr val=\"12", 1)
+        .into_bytes();
+
+    let mut prs = Presentation::new().unwrap();
+    let options = ImportOptions::new().with_formatting(ImportFormatting::KeepSourceFormatting);
+    let first = prs.import_slide(&source, &source_slide, options).unwrap();
+    let second = prs
+        .import_slide(&source, &source_slide, options.with_index(0))
+        .unwrap();
+    assert_eq!(prs.slide_index(&second).unwrap(), 0);
+
+    // One extra master with its theme, shared by both imports
+    assert_eq!(prs.slide_masters().unwrap().len(), 2);
+    let layout = related(&prs, &first.partname, RT::SLIDE_LAYOUT);
+    assert_eq!(layout, related(&prs, &second.partname, RT::SLIDE_LAYOUT));
+    let master = related(&prs, &layout[0], RT::SLIDE_MASTER);
+    let themes = related(&prs, &master[0], RT::THEME);
+    assert_ne!(themes[0], theme);
+    let imported_theme = &prs.package().part(&themes[0]).unwrap().blob;
+    assert_eq!(imported_theme, &source.package().part(&theme).unwrap().blob);
+
+    // The new master lists only the imported layout
+    let master_xml =
+        String::from_utf8(prs.package().part(&master[0]).unwrap().blob.clone()).unwrap();
+    assert_eq!(master_xml.matches("<p:sldLayoutId ").count(), 1);
+    assert!(prs
+        .slide_layouts()
+        .unwrap()
+        .iter()
+        .any(|l| l.partname == layout[0]));
+
+    let reopened = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    assert_eq!(reopened.slide_count().unwrap(), 2);
+    assert_eq!(reopened.slide_masters().unwrap().len(), 2);
+}
+
+#[test]
+fn test_import_slide_rejects_bad_index() {
+    let (source, source_slide) = source_deck();
+    let mut prs = Presentation::new().unwrap();
+    let result = prs.import_slide(&source, &source_slide, ImportOptions::new().with_index(1));
+    assert!(result.is_err());
+    assert_eq!(prs.slide_count().unwrap(), 0);
+}
+
+#[test]
+fn test_import_slide_drops_links_to_other_source_slides() {
+    let (mut source, source_slide) = source_deck();
+    let layouts = source.slide_layouts().unwrap();
+    let other = source.add_slide(&layouts[0]).unwrap();
+    let target = other
+        .partname
+        .relative_ref(source_slide.partname.base_uri());
+    let part = source
+        .package_mut()
+        .part_mut(&source_slide.partname)
+        .unwrap();
+    let r_id = part.rels.add_relationship(RT::SLIDE, &target, false);
+    let xml = String::from_utf8(part.blob.clone()).unwrap();
+    part.blob = xml
+        .replacen(
+            "<p:cNvPr id=\"1\" name=\"\"/>",
+            &format!(
+                "<p:cNvPr id=\"1\" name=\"\"><a:hlinkClick r:id=\"{r_id}\" \
+                 action=\"ppaction://hlinksldjump\"/><a:hlinkHover r:id=\"{r_id}\" \
+                 action=\"ppaction://hlinksldjump\"><a:snd r:embed=\"rId99\"/></a:hlinkHover>\
+                 </p:cNvPr>"
+            ),
+            1,
+        )
+        .into_bytes();
+
+    let mut prs = Presentation::new().unwrap();
+    let imported = prs
+        .import_slide(&source, &source_slide, ImportOptions::new())
+        .unwrap();
+    assert!(related(&prs, &imported.partname, RT::SLIDE).is_empty());
+    let xml = String::from_utf8(prs.slide_xml(&imported).unwrap().to_vec()).unwrap();
+    // The hyperlinks are removed, not left with an empty relationship ID
+    assert!(!xml.contains("hlinkClick"));
+    assert!(!xml.contains("hlinkHover"));
+    assert!(!xml.contains("r:id=\"\""));
+    assert!(xml.contains("<p:cNvPr id=\"1\" name=\"\"></p:cNvPr>"));
+    assert_eq!(prs.slide_count().unwrap(), 1);
+}
+
+#[test]
+fn test_import_slide_merges_comment_authors() {
+    let (mut source, source_slide) = source_deck();
+    let mut comment = Comment::new("Jane Doe", "Check the chart", Emu(0), Emu(0));
+    comment
+        .replies
+        .push(Comment::new("Bob", "Done", Emu(0), Emu(0)));
+    source.add_comment(&source_slide, &comment).unwrap();
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let existing = prs.add_slide(&layouts[0]).unwrap();
+    prs.add_comment(&existing, &Comment::new("Bob", "Hello", Emu(0), Emu(0)))
+        .unwrap();
+    let imported = prs
+        .import_slide(&source, &source_slide, ImportOptions::new())
+        .unwrap();
+
+    let comments = prs.slide_comments(&imported).unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].author, "Jane Doe");
+    assert_eq!(comments[0].replies[0].author, "Bob");
+    let authors = prs
+        .package()
+        .part(&PackURI::new("/ppt/authors.xml").unwrap())
+        .unwrap();
+    let authors = String::from_utf8(authors.blob.clone()).unwrap();
+    assert_eq!(authors.matches("<p188:author ").count(), 2);
+
+    // The comments are anchored to the imported slide
+    let slide_id = prs.slide_id(&imported).unwrap();
+    let partname = related(&prs, &imported.partname, RT::MODERN_COMMENTS)[0].clone();
+    let xml = String::from_utf8(prs.package().part(&partname).unwrap().blob.clone()).unwrap();
+    assert!(xml.contains(&format!(r#"sldId="{slide_id}""#)));
+}
@@ -0,0 +1,111 @@
+use crate::presentation::Presentation;
+use crate::shapes::Shape;
+
+const OUTLINE: &str = "\
+# Quarterly review
+
+- Revenue **up** 12%
+  - Europe *strongest*
+1. First step
+
+Closing remark
+
+| Region | Sales |
+| --- | --- |
+| EMEA | 10 |
+
+> Mention the new office
+
+---
+
+# Next steps
+
+- Hire
+";
+
+#[test]
+fn markdown_outline_round_trips() {
+    let prs = Presentation::from_markdown(OUTLINE).unwrap();
+    assert_eq!(prs.slides().unwrap().len(), 2);
+    assert_eq!(prs.export_markdown().unwrap(), OUTLINE);
+}
+
+#[test]
+fn markdown_outline_fills_placeholders_tables_and_notes() {
+    let prs = Presentation::from_markdown(OUTLINE).unwrap();
+    let slides = prs.slides().unwrap();
+
+    // Placeholders keep the layout's position
+    let xml = String::from_utf8(prs.slide_xml(&slides[0]).unwrap().to_vec()).unwrap();
+    assert_eq!(xml.matches("<p:spPr/>").count(), 1);
+    assert!(xml.contains("<p:ph type=\"title\"/>"));
+    assert!(xml.contains("<p:ph idx=\"1\"/>"));
+
+    let tree = prs.slide_shapes(&slides[0]).unwrap();
+    let body = tree
+        .shapes
+        .iter()
+        .find_map(|s| {
+            s.as_autoshape()
+                .filter(|s| s.placeholder.is_some() && s.name.starts_with("Content"))
+        })
+        .and_then(|s| s.text_frame.as_ref())
+        .unwrap();
+    let levels: Vec<u8> = body.paragraphs.iter().map(|p| p.level).collect();
+    assert_eq!(levels, [0, 1, 0, 0]);
+    assert_eq!(body.paragraphs[0].text(), "Revenue up 12%");
+
+    let table = tree
+        .shapes
+        .iter()
+        .find_map(|s| match s {
+            Shape::GraphicFrame(g) => g.table(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(table.rows[1].cells[0].text_frame.text(), "EMEA");
+
+    assert_eq!(
+        prs.notes_slide_text(&slides[0]).unwrap().as_deref(),
+        Some("Mention the new office")
+    );
+    assert_eq!(prs.notes_slide_text(&slides[1]).unwrap(), None);
+}
+
+#[test]
+fn markdown_placeholders_inherit_word_wrap() {
+    let prs = Presentation::from_markdown(OUTLINE).unwrap();
+    for slide in prs.slides().unwrap() {
+        let xml = String::from_utf8(prs.slide_xml(&slide).unwrap().to_vec()).unwrap();
+        let body_prs: Vec<&str> = xml
+            .match_indices("<a:bodyPr")
+            .map(|(at, _)| &xml[at..at + xml[at..].find('>').unwrap()])
+            .collect();
+        assert!(!body_prs.is_empty());
+        assert!(body_prs.iter().all(|body_pr| !body_pr.contains(" wrap=")));
+    }
+}
+
+#[test]
+fn markdown_export_skips_footers_and_describes_pictures() {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+    let xml = String::from_utf8(prs.slide_xml(&slide_ref).unwrap().to_vec()).unwrap();
+    let shapes = concat!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Footer"/><p:cNvSpPr/><p:nvPr><p:ph type="ftr" idx="11"/></p:nvPr></p:nvSpPr><p:spPr/><p:txBody><a:bodyPr/><a:p><a:r><a:t>Confidential</a:t></a:r></a:p></p:txBody></p:sp>"#,
+        r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="3" name="Group"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/>"#,
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="4" name="TextBox"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr/><p:txBody><a:bodyPr/><a:p><a:r><a:t># not a heading</a:t></a:r></a:p></p:txBody></p:sp>"#,
+        r#"<p:pic><p:nvPicPr><p:cNvPr id="5" name="Picture" descr="Team photo"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="rId9"/></p:blipFill><p:spPr/></p:pic>"#,
+        "</p:grpSp>",
+    );
+    *prs.slide_xml_mut(&slide_ref).unwrap() = xml
+        .replacen("</p:spTree>", &format!("{shapes}</p:spTree>"), 1)
+        .into_bytes();
+
+    let markdown = prs.export_markdown().unwrap();
+    assert!(!markdown.contains("Confidential"));
+    assert!(markdown.contains("\\# not a heading\n"));
+    assert!(markdown.contains("![Team photo]("));
+}
@@ -1,4 +1,15 @@
+mod autofit_tests;
 mod chart_font_vba_tests;
+mod comment_tests;
 mod creation_tests;
+mod import_tests;
+mod markdown_tests;
 mod notes_tests;
+mod properties_tests;
+mod render_tests;
+mod search_tests;
+mod section_tests;
+mod shape_edit_tests;
+mod slide_show_tests;
 mod slide_tests;
+mod template_tests;
@@ -0,0 +1,133 @@
+use crate::custom_properties::{CustomProperties, CustomPropertyValue};
+use crate::extended_properties::ExtendedProperties;
+use crate::opc::constants::relationship_type as RT;
+use crate::presentation::Presentation;
+
+/// A presentation with two slides, the first titled "Agenda" and with
+/// notes, the second hidden.
+fn presentation() -> Presentation {
+    let mut prs = Presentation::from_markdown("# Agenda\n\n---\n\nNo title\n").unwrap();
+    let slides = prs.slides().unwrap();
+    prs.notes_slide_or_create(&slides[0]).unwrap();
+    prs.set_hidden(&slides[1], true).unwrap();
+    prs
+}
+
+#[test]
+fn test_extended_properties_reflect_slides() {
+    let prs = presentation();
+    let props = prs.extended_properties().unwrap();
+    assert_eq!(props.application(), "Microsoft Macintosh PowerPoint");
+    assert_eq!(props.slides(), Some(2));
+    assert_eq!(props.notes(), Some(1));
+    assert_eq!(props.hidden_slides(), Some(1));
+    assert_eq!(
+        props.heading_pairs().last(),
+        Some(&("Slide Titles".to_string(), 2))
+    );
+    assert_eq!(
+        &props.titles_of_parts()[props.titles_of_parts().len() - 2..],
+        ["Agenda", "PowerPoint Presentation"]
+    );
+}
+
+#[test]
+fn test_extended_properties_synced_on_save() {
+    let mut prs = presentation();
+    let mut props = ExtendedProperties::new();
+    props.set_company("Contoso");
+    props.set_manager("Jane Doe");
+    prs.set_extended_properties(&props).unwrap();
+
+    // Slides added after the properties were set are counted on save
+    let layouts = prs.slide_layouts().unwrap();
+    prs.add_slide(&layouts[0]).unwrap();
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let part = prs
+        .package()
+        .part_by_reltype(RT::EXTENDED_PROPERTIES)
+        .unwrap();
+    let stored = ExtendedProperties::from_xml(&part.blob).unwrap();
+    assert_eq!(stored.company(), "Contoso");
+    assert_eq!(stored.manager(), "Jane Doe");
+    assert_eq!(stored.slides(), Some(3));
+    assert_eq!(stored.hidden_slides(), Some(1));
+    assert_eq!(stored.heading_pairs(), [("Slide Titles".to_string(), 3)]);
+}
+
+#[test]
+fn test_save_does_not_fail_on_unreadable_slide() {
+    let mut prs = presentation();
+    prs.set_extended_properties(&ExtendedProperties::new())
+        .unwrap();
+    let slides = prs.slides().unwrap();
+    *prs.slide_xml_mut(&slides[0]).unwrap() = b"<p:sld><p:cSld><p:spTree></p:sld>".to_vec();
+    assert!(prs.slide_shapes(&slides[0]).is_err());
+
+    // The slide keeps the title recorded for it
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let part = prs
+        .package()
+        .part_by_reltype(RT::EXTENDED_PROPERTIES)
+        .unwrap();
+    let stored = ExtendedProperties::from_xml(&part.blob).unwrap();
+    assert_eq!(stored.slides(), Some(2));
+    assert_eq!(
+        stored.titles_of_parts(),
+        ["Agenda", "PowerPoint Presentation"]
+    );
+}
+
+#[test]
+fn test_save_keeps_unmodeled_app_elements() {
+    let mut prs = presentation();
+    prs.package_mut()
+        .part_by_reltype_mut(RT::EXTENDED_PROPERTIES)
+        .unwrap()
+        .blob = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes" xmlns:x="urn:example:vendor"><DocSecurity>2</DocSecurity><CharactersWithSpaces>140</CharactersWithSpaces><Slides>7</Slides><HLinks><vt:vector size="1" baseType="variant"><vt:variant><vt:lpwstr>https://example.com/</vt:lpwstr></vt:variant></vt:vector></HLinks><DigSig><vt:blob>AAAA</vt:blob></DigSig><x:Flag/></Properties>"#
+        .to_vec();
+
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let part = prs
+        .package()
+        .part_by_reltype(RT::EXTENDED_PROPERTIES)
+        .unwrap();
+    let xml = std::str::from_utf8(&part.blob).unwrap();
+    assert!(xml.contains("<DocSecurity>2</DocSecurity>"));
+    assert!(xml.contains("<CharactersWithSpaces>140</CharactersWithSpaces>"));
+    assert!(xml.contains("<HLinks><vt:vector size=\"1\""));
+    assert!(xml.contains("<DigSig><vt:blob>AAAA</vt:blob></DigSig>"));
+    assert!(xml.contains(r#"xmlns:x="urn:example:vendor""#));
+    assert!(xml.contains("<x:Flag/>"));
+    assert!(xml.contains("<Slides>2</Slides><Notes>1</Notes><HiddenSlides>1</HiddenSlides>"));
+}
+
+#[test]
+fn test_custom_properties_roundtrip() {
+    let mut prs = Presentation::new().unwrap();
+    assert!(prs.custom_properties().unwrap().is_empty());
+
+    let mut props = CustomProperties::new();
+    props.set("Classification", "Confidential");
+    props.set("RetentionYears", 7);
+    props.set("Reviewed", true);
+    prs.set_custom_properties(&props).unwrap();
+
+    let mut prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let props2 = prs.custom_properties().unwrap();
+    assert_eq!(props2, props);
+    assert_eq!(
+        props2.get("classification"),
+        Some(&CustomPropertyValue::String("Confidential".to_string()))
+    );
+
+    // Clearing the properties removes the part
+    prs.set_custom_properties(&CustomProperties::new()).unwrap();
+    assert!(prs
+        .package()
+        .pkg_rels
+        .by_reltype(RT::CUSTOM_PROPERTIES)
+        .is_err());
+    assert!(prs.custom_properties().unwrap().is_empty());
+}
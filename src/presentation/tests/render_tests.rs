@@ -0,0 +1,141 @@
+use crate::embedded_font::EmbeddedFont;
+use crate::media::Image;
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
+use crate::presentation::Presentation;
+use crate::render::{RenderOptions, SlideImage};
+use crate::slide::SlideRef;
+
+fn append_shapes(prs: &mut Presentation, slide_ref: &SlideRef, shapes: &str) {
+    let xml = String::from_utf8(prs.slide_xml(slide_ref).unwrap().to_vec()).unwrap();
+    *prs.slide_xml_mut(slide_ref).unwrap() = xml
+        .replacen("</p:spTree>", &format!("{shapes}</p:spTree>"), 1)
+        .into_bytes();
+}
+
+fn xfrm(x: i64, y: i64, cx: i64, cy: i64) -> String {
+    format!(r#"<a:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm>"#)
+}
+
+/// A 4:3 slide with a red rectangle at the top left, a blue picture in
+/// the middle and black text below it.
+fn deck() -> (Presentation, SlideRef) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+
+    let blue = SlideImage {
+        width: 2,
+        height: 1,
+        pixels: [0, 0, 255, 255].repeat(2),
+    };
+    let image = Image::from_bytes(blue.to_png(), "image/png");
+    let (image_partname, _) = prs.package_mut().or_add_image_part(&image).unwrap();
+    let slide_part = prs.package_mut().part_mut(&slide_ref.partname).unwrap();
+    let target = image_partname.relative_ref(slide_part.partname.base_uri());
+    let r_id = slide_part.rels.or_add(RT::IMAGE, &target, false);
+
+    let shapes = format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Box"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr>{}<a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></p:spPr></p:sp><p:pic><p:nvPicPr><p:cNvPr id="3" name="Picture"/><p:cNvPicPr/><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="{r_id}"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr>{}<a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic><p:sp><p:nvSpPr><p:cNvPr id="4" name="Text"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr>{}</p:spPr><p:txBody><a:bodyPr/><a:p><a:r><a:rPr lang="en-US" sz="4000"><a:solidFill><a:srgbClr val="000000"/></a:solidFill></a:rPr><a:t>HELLO</a:t></a:r></a:p></p:txBody></p:sp>"#,
+        xfrm(0, 0, 2_743_200, 1_371_600),
+        xfrm(4_572_000, 2_743_200, 1_828_800, 914_400),
+        xfrm(0, 4_572_000, 9_144_000, 1_371_600),
+    );
+    append_shapes(&mut prs, &slide_ref, &shapes);
+    (prs, slide_ref)
+}
+
+/// The darkest channel value in a block of pixels.
+fn darkest(image: &SlideImage, x: std::ops::Range<u32>, y: std::ops::Range<u32>) -> u8 {
+    y.flat_map(|y| x.clone().map(move |x| (x, y)))
+        .filter_map(|(x, y)| image.pixel(x, y))
+        .flat_map(|p| p[..3].to_vec())
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn test_render_slide_draws_shapes_and_pictures() {
+    let (prs, slide_ref) = deck();
+    let image = prs
+        .render_slide(&slide_ref, &RenderOptions::new(400))
+        .unwrap();
+    assert_eq!((image.width, image.height), (400, 300));
+    assert_eq!(image.pixels.len(), 400 * 300 * 4);
+    // Red box over the top left 120×60 pixels, on a white background
+    assert_eq!(image.pixel(60, 30), Some([255, 0, 0, 255]));
+    assert_eq!(image.pixel(390, 10), Some([255, 255, 255, 255]));
+    // Blue picture from (200, 120) to (280, 160)
+    assert_eq!(image.pixel(240, 140), Some([0, 0, 255, 255]));
+
+    let png = image.to_png();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[test]
+fn test_render_slide_with_size() {
+    let (prs, slide_ref) = deck();
+    let image = prs
+        .render_slide(&slide_ref, &RenderOptions::new(100).with_height(100))
+        .unwrap();
+    assert_eq!((image.width, image.height), (100, 100));
+    // The box covers 30% of the width and 20% of the height
+    assert_eq!(image.pixel(25, 15), Some([255, 0, 0, 255]));
+    assert_eq!(image.pixel(35, 15), Some([255, 255, 255, 255]));
+}
+
+#[test]
+fn test_render_slide_text_with_supplied_font() {
+    let (prs, slide_ref) = deck();
+    // Text starts near y = 200 px; 40pt text is about 22 px tall
+    let text_area = |image: &SlideImage| darkest(image, 0..200, 200..230);
+
+    let greeked = prs
+        .render_slide(&slide_ref, &RenderOptions::new(400))
+        .unwrap();
+    let shade = text_area(&greeked);
+    assert!(shade > 100 && shade < 255, "bar shade {shade}");
+
+    let font = EmbeddedFont::from_bytes(crate::render::sample_font(), "Sample", false, false);
+    let drawn = prs
+        .render_slide(&slide_ref, &RenderOptions::new(400).with_font(font))
+        .unwrap();
+    assert!(text_area(&drawn) < 10);
+}
+
+#[test]
+fn test_render_slide_rejects_bad_sizes() {
+    let (prs, slide_ref) = deck();
+    assert!(prs
+        .render_slide(&slide_ref, &RenderOptions::new(0))
+        .is_err());
+    assert!(prs
+        .render_slide(&slide_ref, &RenderOptions::new(100_000))
+        .is_err());
+}
+
+#[test]
+fn test_thumbnail_replaces_package_thumbnail() {
+    let (mut prs, _) = deck();
+    let image = prs.thumbnail(&RenderOptions::default()).unwrap();
+    assert_eq!((image.width, image.height), (256, 192));
+    prs.thumbnail(&RenderOptions::default()).unwrap();
+
+    let bytes = prs.to_bytes().unwrap();
+    let reopened = Presentation::from_bytes(&bytes).unwrap();
+    let package = reopened.package();
+    assert_eq!(package.pkg_rels.all_by_reltype(RT::THUMBNAIL).len(), 1);
+    let part = package
+        .part(&PackURI::new("/docProps/thumbnail.jpeg").unwrap())
+        .unwrap();
+    assert_eq!(part.content_type, "image/jpeg");
+    assert!(part.blob.starts_with(&[0xFF, 0xD8]));
+}
+
+#[test]
+fn test_thumbnail_without_slides_is_blank() {
+    let mut prs = Presentation::new().unwrap();
+    let image = prs.thumbnail(&RenderOptions::new(40)).unwrap();
+    assert_eq!((image.width, image.height), (40, 30));
+    assert!(image.pixels.iter().all(|&b| b == 255));
+}
@@ -0,0 +1,124 @@
+use crate::chart::data::{CategoryChartData, ChartData};
+use crate::chart::Chart;
+use crate::enums::chart::XlChartType;
+use crate::presentation::{Presentation, TextPart};
+use crate::slide::SlideRef;
+use crate::text::ReplaceOptions;
+use crate::units::{Emu, ShapeId};
+
+fn set_part_xml(
+    prs: &mut Presentation,
+    partname: &crate::opc::pack_uri::PackURI,
+    from: &str,
+    to: &str,
+) {
+    let part = prs.package_mut().part_mut(partname).unwrap();
+    let xml = String::from_utf8(part.blob.clone()).unwrap();
+    assert!(xml.contains(from));
+    part.blob = xml.replacen(from, to, 1).into_bytes();
+}
+
+/// A slide with a split-run text box, notes and a titled chart.
+fn deck_with_text() -> (Presentation, SlideRef) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("Q1");
+    data.add_series("Sales", &[1.0]);
+    let mut style = Chart::new(XlChartType::ColumnClustered);
+    style.set_title("Acme revenue");
+    let size = Emu(914_400);
+    prs.add_chart(
+        &slide_ref,
+        &ChartData::Category(data),
+        XlChartType::ColumnClustered,
+        Some(&style),
+        Emu(0),
+        Emu(0),
+        size,
+        size,
+    )
+    .unwrap();
+
+    let text_box = r#"<p:sp><p:nvSpPr><p:cNvPr id="20" name="TextBox 19"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr/><p:txBody><a:bodyPr/><a:p><a:r><a:rPr lang="en-US"/><a:t>Welcome to Ac</a:t></a:r><a:r><a:rPr lang="en-US" b="1"/><a:t>me</a:t></a:r><a:r><a:rPr lang="en-US"/><a:t> Corp</a:t></a:r></a:p></p:txBody></p:sp></p:spTree>"#;
+    set_part_xml(
+        &mut prs,
+        &slide_ref.partname.clone(),
+        "</p:spTree>",
+        text_box,
+    );
+
+    let notes = prs.notes_slide_or_create(&slide_ref).unwrap();
+    set_part_xml(
+        &mut prs,
+        &notes.partname,
+        r#"<a:p><a:endParaRPr lang="en-US"/></a:p>"#,
+        r#"<a:p><a:r><a:rPr lang="en-US"/><a:t>Mention acme pricing</a:t></a:r></a:p>"#,
+    );
+    (prs, slide_ref)
+}
+
+#[test]
+fn test_find_text_in_slide_notes_and_chart() {
+    let (prs, slide_ref) = deck_with_text();
+    let found = prs.find_text("Acme", ReplaceOptions::new()).unwrap();
+    let locations: Vec<_> = found
+        .iter()
+        .map(|m| (m.part, m.shape_id, m.paragraph, m.runs.clone()))
+        .collect();
+    let frame_id = prs.slide_shapes(&slide_ref).unwrap().shapes[0].shape_id();
+    assert_eq!(
+        locations,
+        vec![
+            (TextPart::Slide, Some(ShapeId(20)), 0, 0..2),
+            (TextPart::Chart, Some(frame_id), 0, 0..1),
+        ]
+    );
+    assert!(found
+        .iter()
+        .all(|m| m.slide.partname == slide_ref.partname && m.text == "Acme"));
+    assert!(prs.find_text("", ReplaceOptions::new()).is_err());
+}
+
+#[test]
+fn test_find_text_with_options() {
+    let (prs, _) = deck_with_text();
+    let any_case = ReplaceOptions::new().with_match_case(false);
+    let found = prs.find_text("acme", any_case).unwrap();
+    assert_eq!(found.len(), 3);
+    assert_eq!(found[1].part, TextPart::Notes);
+    assert_eq!(found[1].text, "acme");
+
+    // The same matcher as replace_text: "Ac" is not a whole word
+    assert_eq!(
+        prs.find_text("Ac", any_case.with_whole_words(true))
+            .unwrap()
+            .len(),
+        0
+    );
+    assert_eq!(prs.find_text("Ac", any_case).unwrap().len(), 3);
+}
+
+#[test]
+fn test_replace_text_across_parts() {
+    let (mut prs, slide_ref) = deck_with_text();
+    let options = ReplaceOptions::new().with_match_case(false);
+    assert_eq!(prs.replace_text("acme", "Globex", options).unwrap(), 3);
+    assert!(prs
+        .find_text("Acme", ReplaceOptions::new())
+        .unwrap()
+        .is_empty());
+
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    let xml = String::from_utf8(prs.slide_xml(&slide_ref).unwrap().to_vec()).unwrap();
+    assert!(xml.contains("<a:t>Welcome to Globex</a:t>"));
+    assert!(!xml.contains(r#"b="1""#));
+    assert_eq!(
+        prs.find_text("Globex", ReplaceOptions::new())
+            .unwrap()
+            .len(),
+        3
+    );
+}
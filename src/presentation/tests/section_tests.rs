@@ -0,0 +1,211 @@
+use crate::presentation::Presentation;
+use crate::section::Section;
+use crate::slide::SlideRef;
+
+/// A presentation with `count` slides.
+fn presentation(count: usize) -> (Presentation, Vec<SlideRef>) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slides = (0..count)
+        .map(|_| prs.add_slide(&layouts[0]).unwrap())
+        .collect();
+    (prs, slides)
+}
+
+/// `(name, slide indices)` of each section.
+fn outline(prs: &Presentation) -> Vec<(String, Vec<usize>)> {
+    prs.sections()
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.name, s.slide_indices))
+        .collect()
+}
+
+fn section(name: &str, slides: &[usize]) -> (String, Vec<usize>) {
+    (name.to_string(), slides.to_vec())
+}
+
+#[test]
+fn test_no_sections_by_default() {
+    let (prs, _) = presentation(2);
+    assert!(prs.sections().unwrap().is_empty());
+}
+
+#[test]
+fn test_add_section_splits_slides() {
+    let (mut prs, _) = presentation(4);
+    assert_eq!(prs.add_section("Body", 1).unwrap(), 1);
+    assert_eq!(
+        outline(&prs),
+        [
+            section("Default Section", &[0]),
+            section("Body", &[1, 2, 3])
+        ]
+    );
+    assert_eq!(prs.add_section("End", 3).unwrap(), 2);
+    assert_eq!(prs.add_section("Appendix", 4).unwrap(), 3);
+    assert_eq!(
+        outline(&prs),
+        [
+            section("Default Section", &[0]),
+            section("Body", &[1, 2]),
+            section("End", &[3]),
+            section("Appendix", &[]),
+        ]
+    );
+    assert!(prs.add_section("Too far", 5).is_err());
+
+    // Each section has its own GUID, which survives a save
+    let sections = prs.sections().unwrap();
+    assert!(sections
+        .iter()
+        .all(|s| s.id.as_ref().is_some_and(|id| id.len() == 38)));
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    assert_eq!(prs.sections().unwrap(), sections);
+}
+
+#[test]
+fn test_set_sections() {
+    let (mut prs, _) = presentation(3);
+    prs.set_sections(&[
+        Section::with_slides("One", vec![0, 1]),
+        Section::new("Two", 2),
+    ])
+    .unwrap();
+    assert_eq!(
+        outline(&prs),
+        [section("One", &[0, 1]), section("Two", &[2])]
+    );
+    let xml = std::str::from_utf8(&prs.presentation_part().unwrap().blob).unwrap();
+    assert!(xml.contains(r#"<p14:section name="One" id="{"#));
+    assert!(
+        xml.contains(r#"<p14:sldIdLst><p14:sldId id="256"/><p14:sldId id="257"/></p14:sldIdLst>"#)
+    );
+
+    assert!(prs.set_sections(&[Section::new("Out", 3)]).is_err());
+    assert!(prs
+        .set_sections(&[Section::new("A", 0), Section::new("B", 0)])
+        .is_err());
+
+    prs.set_sections(&[]).unwrap();
+    assert!(prs.sections().unwrap().is_empty());
+    let xml = std::str::from_utf8(&prs.presentation_part().unwrap().blob).unwrap();
+    assert!(!xml.contains("extLst"));
+}
+
+#[test]
+fn test_rename_section() {
+    let (mut prs, _) = presentation(1);
+    prs.add_section("Draft", 0).unwrap();
+    let id = prs.sections().unwrap()[0].id.clone();
+    prs.rename_section(0, "Final <v2>").unwrap();
+    let sections = prs.sections().unwrap();
+    assert_eq!(sections[0].name, "Final <v2>");
+    assert_eq!(sections[0].id, id);
+    assert!(prs.rename_section(1, "Missing").is_err());
+}
+
+#[test]
+fn test_move_section_reorders_slides() {
+    let (mut prs, slides) = presentation(4);
+    prs.set_sections(&[
+        Section::with_slides("A", vec![0, 1]),
+        Section::with_slides("B", vec![2]),
+        Section::with_slides("C", vec![3]),
+    ])
+    .unwrap();
+    prs.move_section(0, 2).unwrap();
+    assert_eq!(
+        outline(&prs),
+        [
+            section("B", &[0]),
+            section("C", &[1]),
+            section("A", &[2, 3])
+        ]
+    );
+    let order: Vec<_> = prs
+        .slides()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.partname)
+        .collect();
+    let expected: Vec<_> = [2, 3, 0, 1]
+        .iter()
+        .map(|&i| slides[i].partname.clone())
+        .collect();
+    assert_eq!(order, expected);
+    assert!(prs.move_section(0, 3).is_err());
+}
+
+#[test]
+fn test_delete_section_keeping_slides() {
+    let (mut prs, _) = presentation(3);
+    prs.set_sections(&[
+        Section::new("A", 0),
+        Section::new("B", 1),
+        Section::new("C", 2),
+    ])
+    .unwrap();
+    // Slides join the previous section, or the next for the first one
+    prs.delete_section(1, false).unwrap();
+    assert_eq!(outline(&prs), [section("A", &[0, 1]), section("C", &[2])]);
+    prs.delete_section(0, false).unwrap();
+    assert_eq!(outline(&prs), [section("C", &[0, 1, 2])]);
+    prs.delete_section(0, false).unwrap();
+    assert!(prs.sections().unwrap().is_empty());
+    assert_eq!(prs.slide_count().unwrap(), 3);
+}
+
+#[test]
+fn test_delete_section_with_slides() {
+    let (mut prs, slides) = presentation(3);
+    prs.set_sections(&[Section::with_slides("A", vec![0, 1]), Section::new("B", 2)])
+        .unwrap();
+    prs.delete_section(0, true).unwrap();
+    assert_eq!(outline(&prs), [section("B", &[0])]);
+    assert_eq!(prs.slide_count().unwrap(), 1);
+    assert!(prs.package().part(&slides[0].partname).is_none());
+    assert!(prs.package().part(&slides[2].partname).is_some());
+}
+
+#[test]
+fn test_slide_changes_keep_sections_in_step() {
+    let (mut prs, slides) = presentation(3);
+    prs.set_sections(&[Section::with_slides("A", vec![0, 1]), Section::new("B", 2)])
+        .unwrap();
+
+    // New slides join the last section
+    let layouts = prs.slide_layouts().unwrap();
+    prs.add_slide(&layouts[0]).unwrap();
+    assert_eq!(
+        outline(&prs),
+        [section("A", &[0, 1]), section("B", &[2, 3])]
+    );
+
+    // A copy stays in the section of its original
+    prs.duplicate_slide(&slides[0], None).unwrap();
+    assert_eq!(
+        outline(&prs),
+        [section("A", &[0, 1, 2]), section("B", &[3, 4])]
+    );
+
+    // A moved slide joins the section of the slide before it
+    prs.move_slide(0, 4).unwrap();
+    assert_eq!(
+        outline(&prs),
+        [section("A", &[0, 1]), section("B", &[2, 3, 4])]
+    );
+    prs.move_slide(3, 0).unwrap();
+    assert_eq!(
+        outline(&prs),
+        [section("A", &[0, 1, 2]), section("B", &[3, 4])]
+    );
+
+    // Deleted slides leave their section
+    let first = prs.slides_get(0).unwrap();
+    prs.delete_slide(&first).unwrap();
+    assert_eq!(
+        outline(&prs),
+        [section("A", &[0, 1]), section("B", &[2, 3])]
+    );
+}
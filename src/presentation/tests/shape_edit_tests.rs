@@ -0,0 +1,138 @@
+use crate::chart::data::CategoryChartData;
+use crate::enums::chart::XlChartType;
+use crate::media::Image;
+use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
+use crate::presentation::Presentation;
+use crate::shapes::shapetree::ShapeTree;
+use crate::slide::SlideRef;
+use crate::units::{Emu, ShapeId};
+
+/// A slide holding a chart and two pictures that share one image part.
+fn slide_with_chart_and_pictures() -> (Presentation, SlideRef) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("A");
+    data.add_series("S", &[1.0]);
+    let (x, w) = (Emu(0), Emu(914_400));
+    prs.add_chart_to_slide(&slide_ref, &data, XlChartType::Pie, x, x, w, w)
+        .unwrap();
+
+    let img = Image::from_bytes(vec![0x89, b'P', b'N', b'G'], "image/png");
+    let image_partname = PackURI::new(prs.add_image(&img).unwrap()).unwrap();
+    let target = image_partname.relative_ref(slide_ref.partname.base_uri());
+    let slide_part = prs.package_mut().part_mut(&slide_ref.partname).unwrap();
+    let r_id = slide_part.rels.add_relationship(RT::IMAGE, &target, false);
+    for _ in 0..2 {
+        slide_part.blob = ShapeTree::add_picture(&slide_part.blob, &r_id, x, x, w, w).unwrap();
+    }
+    (prs, slide_ref)
+}
+
+fn shape_names(prs: &Presentation, slide_ref: &SlideRef) -> Vec<String> {
+    prs.slide_shapes(slide_ref)
+        .unwrap()
+        .iter()
+        .map(|s| s.name().to_string())
+        .collect()
+}
+
+#[test]
+fn test_remove_chart_drops_chart_and_workbook_parts() {
+    let (mut prs, slide_ref) = slide_with_chart_and_pictures();
+    let chart_id = prs.slide_shapes(&slide_ref).unwrap().shapes[0].shape_id();
+
+    prs.remove_shape(&slide_ref, chart_id).unwrap();
+
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    assert!(slide_part.rels.all_by_reltype(RT::CHART).is_empty());
+    assert!(prs
+        .package()
+        .part(&PackURI::new("/ppt/charts/chart1.xml").unwrap())
+        .is_none());
+    assert!(prs
+        .package()
+        .part(&PackURI::new("/ppt/charts/chart1.xlsx").unwrap())
+        .is_none());
+    // The package still saves and reopens cleanly
+    let bytes = prs.to_bytes().unwrap();
+    assert!(Presentation::from_bytes(&bytes).is_ok());
+}
+
+#[test]
+fn test_remove_picture_keeps_shared_image_until_last_user() {
+    let (mut prs, slide_ref) = slide_with_chart_and_pictures();
+    let image = PackURI::new("/ppt/media/image1.png").unwrap();
+    let ids: Vec<ShapeId> = prs
+        .slide_shapes(&slide_ref)
+        .unwrap()
+        .iter()
+        .map(crate::shapes::Shape::shape_id)
+        .collect();
+
+    prs.remove_shape(&slide_ref, ids[1]).unwrap();
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    assert_eq!(slide_part.rels.all_by_reltype(RT::IMAGE).len(), 1);
+    assert!(prs.package().part(&image).is_some());
+
+    prs.remove_shape(&slide_ref, ids[2]).unwrap();
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    assert!(slide_part.rels.all_by_reltype(RT::IMAGE).is_empty());
+    assert!(prs.package().part(&image).is_none());
+}
+
+#[test]
+fn test_replace_shape_releases_old_relationships() {
+    let (mut prs, slide_ref) = slide_with_chart_and_pictures();
+    let tree = prs.slide_shapes(&slide_ref).unwrap();
+    let chart_id = tree.shapes[0].shape_id();
+    let replacement =
+        crate::shapes::Shape::AutoShape(Box::new(crate::shapes::autoshape::AutoShape::new(
+            chart_id,
+            "Placeholder Box",
+            Emu(0),
+            Emu(0),
+            Emu(100),
+            Emu(100),
+        )));
+
+    prs.replace_shape(&slide_ref, chart_id, &replacement)
+        .unwrap();
+
+    assert_eq!(shape_names(&prs, &slide_ref)[0], "Placeholder Box");
+    let slide_part = prs.package().part(&slide_ref.partname).unwrap();
+    assert!(slide_part.rels.all_by_reltype(RT::CHART).is_empty());
+}
+
+#[test]
+fn test_z_order_operations() {
+    let (mut prs, slide_ref) = slide_with_chart_and_pictures();
+    let ids: Vec<ShapeId> = prs
+        .slide_shapes(&slide_ref)
+        .unwrap()
+        .iter()
+        .map(crate::shapes::Shape::shape_id)
+        .collect();
+    let original = shape_names(&prs, &slide_ref);
+
+    prs.bring_to_front(&slide_ref, ids[0]).unwrap();
+    assert_eq!(
+        shape_names(&prs, &slide_ref),
+        [original[1].as_str(), &original[2], &original[0]]
+    );
+    prs.move_backward(&slide_ref, ids[0]).unwrap();
+    assert_eq!(
+        shape_names(&prs, &slide_ref),
+        [original[1].as_str(), &original[0], &original[2]]
+    );
+    prs.send_to_back(&slide_ref, ids[0]).unwrap();
+    assert_eq!(shape_names(&prs, &slide_ref), original);
+    prs.move_forward(&slide_ref, ids[0]).unwrap();
+    assert_eq!(
+        shape_names(&prs, &slide_ref),
+        [original[1].as_str(), &original[0], &original[2]]
+    );
+}
@@ -0,0 +1,164 @@
+use crate::dml::color::ColorFormat;
+use crate::opc::constants::relationship_type as RT;
+use crate::presentation::Presentation;
+use crate::show_settings::{ShowSettings, ShowType, SlideRange, DEFAULT_KIOSK_RESTART_MS};
+use crate::units::SlideId;
+
+/// A presentation with `count` slides, and their slide IDs.
+fn presentation(count: usize) -> (Presentation, Vec<SlideId>) {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let ids = (0..count)
+        .map(|_| {
+            let slide = prs.add_slide(&layouts[0]).unwrap();
+            prs.slide_id(&slide).unwrap()
+        })
+        .collect();
+    (prs, ids)
+}
+
+fn pres_props(prs: &Presentation) -> String {
+    let part = prs
+        .presentation_related_part(RT::PRES_PROPS)
+        .unwrap()
+        .unwrap();
+    String::from_utf8(part.blob.clone()).unwrap()
+}
+
+#[test]
+fn test_default_show_settings() {
+    let (prs, _) = presentation(1);
+    assert_eq!(prs.show_settings().unwrap(), ShowSettings::new());
+}
+
+#[test]
+fn test_kiosk_settings_roundtrip() {
+    let (mut prs, _) = presentation(3);
+    let settings = ShowSettings::new()
+        .with_show_type(ShowType::Kiosk {
+            restart_ms: DEFAULT_KIOSK_RESTART_MS,
+        })
+        .with_loop_until_esc(true)
+        .with_show_without_narration(true)
+        .with_slide_range(SlideRange::Range { start: 1, end: 2 })
+        .with_pen_color(ColorFormat::rgb(0, 0x70, 0xC0))
+        .with_laser_color(ColorFormat::rgb(0xFF, 0, 0));
+    prs.set_show_settings(&settings).unwrap();
+
+    // The settings go before the existing extension list
+    let xml = pres_props(&prs);
+    assert!(xml.contains(r#"<p:showPr loop="1"><p:kiosk/><p:sldRg st="2" end="3"/>"#));
+    assert!(xml.contains("</p:showPr><p:extLst>"));
+
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    assert_eq!(prs.show_settings().unwrap(), settings);
+}
+
+#[test]
+fn test_set_show_settings_keeps_other_extensions() {
+    let (mut prs, _) = presentation(1);
+    let partname = prs
+        .presentation_related_part(RT::PRES_PROPS)
+        .unwrap()
+        .unwrap()
+        .partname
+        .clone();
+    let media_controls = r#"<p:ext uri="{EC167BDA-8182-4AB2-A6B2-F6E2D3EB6C5A}"><p14:showMediaCtrls xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" val="1"/></p:ext>"#;
+    let xml = pres_props(&prs).replace(
+        "<p:extLst>",
+        &format!(r#"<p:showPr showNarration="1"><p:present/><p:sldAll/><p:extLst>{media_controls}</p:extLst></p:showPr><p:extLst>"#),
+    );
+    prs.package_mut().part_mut(&partname).unwrap().blob = xml.into_bytes();
+
+    let settings = ShowSettings::new().with_laser_color(ColorFormat::rgb(0, 0xFF, 0));
+    prs.set_show_settings(&settings).unwrap();
+    prs.set_show_settings(&settings).unwrap();
+    let xml = pres_props(&prs);
+    assert_eq!(xml.matches("<p:showPr").count(), 1);
+    assert_eq!(xml.matches("showMediaCtrls").count(), 1);
+    assert_eq!(xml.matches("laserClr").count(), 2);
+    assert_eq!(prs.show_settings().unwrap(), settings);
+}
+
+#[test]
+fn test_set_show_settings_keeps_preset_pen_color() {
+    let (mut prs, _) = presentation(1);
+    let partname = prs
+        .presentation_related_part(RT::PRES_PROPS)
+        .unwrap()
+        .unwrap()
+        .partname
+        .clone();
+    let xml = pres_props(&prs).replace(
+        "<p:extLst>",
+        r#"<p:showPr showNarration="1"><p:present/><p:sldAll/><p:penClr><a:prstClr val="red"/></p:penClr></p:showPr><p:extLst>"#,
+    );
+    prs.package_mut().part_mut(&partname).unwrap().blob = xml.into_bytes();
+
+    let settings = prs.show_settings().unwrap().with_loop_until_esc(true);
+    prs.set_show_settings(&settings).unwrap();
+    assert!(pres_props(&prs).contains(r#"<p:penClr><a:prstClr val="red"/></p:penClr>"#));
+}
+
+#[test]
+fn test_set_show_settings_validates_range() {
+    let (mut prs, _) = presentation(2);
+    let range = |start, end| ShowSettings::new().with_slide_range(SlideRange::Range { start, end });
+    assert!(prs.set_show_settings(&range(0, 2)).is_err());
+    assert!(prs.set_show_settings(&range(1, 0)).is_err());
+    assert!(prs
+        .set_show_settings(&ShowSettings::new().with_slide_range(SlideRange::CustomShow(0)))
+        .is_err());
+    prs.set_show_settings(&range(0, 1)).unwrap();
+}
+
+#[test]
+fn test_custom_shows() {
+    let (mut prs, ids) = presentation(3);
+    assert!(prs.custom_shows().unwrap().is_empty());
+
+    let booth = prs.add_custom_show("Booth", &[ids[2], ids[0]]).unwrap();
+    let short = prs.add_custom_show("Short", &[ids[1]]).unwrap();
+    assert_eq!((booth, short), (0, 1));
+    assert!(prs.add_custom_show("Bad", &[SlideId(9999)]).is_err());
+
+    prs.rename_custom_show(short, "Teaser").unwrap();
+    prs.set_custom_show_slides(short, &[ids[1], ids[2]])
+        .unwrap();
+    let shows = prs.custom_shows().unwrap();
+    assert_eq!(shows.len(), 2);
+    assert_eq!(shows[0].name, "Booth");
+    assert_eq!(shows[0].slide_ids, [ids[2], ids[0]]);
+    assert_eq!(shows[1].name, "Teaser");
+    assert_eq!(shows[1].slide_ids, [ids[1], ids[2]]);
+
+    // A kiosk deck looping through the custom show survives a save
+    let settings = ShowSettings::new()
+        .with_show_type(ShowType::Kiosk {
+            restart_ms: DEFAULT_KIOSK_RESTART_MS,
+        })
+        .with_loop_until_esc(true)
+        .with_slide_range(SlideRange::CustomShow(booth));
+    prs.set_show_settings(&settings).unwrap();
+    let mut prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    assert_eq!(prs.custom_shows().unwrap(), shows);
+    assert_eq!(prs.show_settings().unwrap(), settings);
+
+    // Deleting the show sends the settings back to all slides
+    prs.delete_custom_show(booth).unwrap();
+    assert_eq!(prs.custom_shows().unwrap().len(), 1);
+    assert_eq!(prs.show_settings().unwrap().slide_range, SlideRange::All);
+    assert!(prs.delete_custom_show(booth).is_err());
+}
+
+#[test]
+fn test_deleted_slides_leave_custom_shows() {
+    let (mut prs, ids) = presentation(2);
+    prs.add_custom_show("All", &[ids[0], ids[1], ids[0]])
+        .unwrap();
+    let first = prs.slides_get(0).unwrap();
+    prs.delete_slide(&first).unwrap();
+    assert_eq!(prs.custom_shows().unwrap()[0].slide_ids, [ids[1]]);
+    let xml = std::str::from_utf8(&prs.presentation_part().unwrap().blob).unwrap();
+    assert_eq!(xml.matches("<p:sld ").count(), 1);
+}
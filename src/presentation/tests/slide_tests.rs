@@ -1,5 +1,7 @@
 use crate::opc::constants::relationship_type as RT;
+use crate::opc::pack_uri::PackURI;
 use crate::presentation::Presentation;
+use crate::transition::SlideTransition;
 
 #[test]
 fn test_add_slide() {
@@ -225,6 +227,94 @@ fn test_has_notes_slide_true() {
     assert!(prs.has_notes_slide(&slide_ref));
 }
 
+#[test]
+fn test_slide_properties_default() {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    let props = prs.slide_properties(&slide_ref).unwrap();
+    assert_eq!(props.slide_id, prs.slide_id(&slide_ref).unwrap());
+    assert_eq!(props.name, "");
+    assert!(!props.has_notes_slide);
+    assert!(!props.hidden);
+    assert!(props.show_master_shapes);
+    assert!(props.advance_on_click);
+    assert_eq!(props.advance_after, None);
+}
+
+#[test]
+fn test_set_slide_properties_roundtrip() {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+    prs.set_slide_transition(&slide_ref, &SlideTransition::fade())
+        .unwrap();
+
+    let mut props = prs.slide_properties(&slide_ref).unwrap();
+    props.name = "Backup".to_string();
+    props.hidden = true;
+    props.show_master_shapes = false;
+    props.advance_on_click = false;
+    props.advance_after = Some(8000);
+    prs.set_slide_properties(&slide_ref, &props).unwrap();
+
+    let bytes = prs.to_bytes().unwrap();
+    let prs = Presentation::from_bytes(&bytes).unwrap();
+    let slide_ref = prs.slides_get(0).unwrap();
+    assert_eq!(prs.slide_properties(&slide_ref).unwrap(), props);
+    assert_eq!(
+        prs.slide_name(&slide_ref).unwrap().as_deref(),
+        Some("Backup")
+    );
+
+    // The transition itself is kept
+    let xml = std::str::from_utf8(prs.slide_xml(&slide_ref).unwrap()).unwrap();
+    assert!(xml.contains("<p:fade"));
+}
+
+#[test]
+fn test_set_hidden_and_show_master_shapes() {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+
+    prs.set_hidden(&slide_ref, true).unwrap();
+    assert!(prs.is_hidden(&slide_ref).unwrap());
+    assert_eq!(prs.extended_properties().unwrap().hidden_slides(), Some(1));
+    prs.set_hidden(&slide_ref, false).unwrap();
+    assert!(!prs.is_hidden(&slide_ref).unwrap());
+
+    prs.set_show_master_shapes(&slide_ref, false).unwrap();
+    assert!(!prs.show_master_shapes(&slide_ref).unwrap());
+    let xml = std::str::from_utf8(prs.slide_xml(&slide_ref).unwrap()).unwrap();
+    assert!(xml.contains(r#"showMasterSp="0""#));
+    assert!(!xml.contains("show=\"0\""));
+}
+
+#[test]
+fn test_set_advance_after_all() {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let first = prs.add_slide(&layouts[0]).unwrap();
+    let second = prs.add_slide(&layouts[0]).unwrap();
+    prs.set_slide_transition(&second, &SlideTransition::fade().without_click_advance())
+        .unwrap();
+
+    prs.set_advance_after_all(Some(5000)).unwrap();
+    for slide in [&first, &second] {
+        assert_eq!(
+            prs.slide_properties(slide).unwrap().advance_after,
+            Some(5000)
+        );
+    }
+    assert!(!prs.slide_properties(&second).unwrap().advance_on_click);
+
+    prs.set_advance_after_all(None).unwrap();
+    assert_eq!(prs.slide_properties(&first).unwrap().advance_after, None);
+    assert_eq!(prs.slide_properties(&second).unwrap().advance_after, None);
+}
+
 #[test]
 fn test_slide_layout_for() {
     let mut prs = Presentation::new().unwrap();
@@ -291,3 +381,125 @@ fn test_remove_slide_layout_unused() {
         .iter()
         .any(|l| l.partname.as_str() == layout_to_remove.partname.as_str()));
 }
+
+#[test]
+fn test_update_slide_shapes_round_trip() {
+    use crate::shapes::ShapeTree;
+    use crate::units::Emu;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[0]).unwrap();
+    let xml = prs.slide_xml(&slide_ref).unwrap().to_vec();
+    let xml = ShapeTree::add_textbox(&xml, Emu(0), Emu(0), Emu(100), Emu(100)).unwrap();
+    *prs.slide_xml_mut(&slide_ref).unwrap() = xml;
+
+    let mut tree = prs.slide_shapes(&slide_ref).unwrap();
+    let textbox = tree.shapes[0].as_autoshape_mut().unwrap();
+    textbox.top = Emu(4242);
+    textbox.text_frame.as_mut().unwrap().set_text("Edited");
+    prs.update_slide_shapes(&slide_ref, &tree).unwrap();
+
+    let bytes = prs.to_bytes().unwrap();
+    let prs2 = Presentation::from_bytes(&bytes).unwrap();
+    let slide2 = prs2.slides().unwrap().remove(0);
+    let tree2 = prs2.slide_shapes(&slide2).unwrap();
+    assert_eq!(tree2.shapes[0].top(), Emu(4242));
+    let tf = tree2.shapes[0].as_autoshape().unwrap().text_frame.as_ref();
+    assert_eq!(tf.unwrap().text(), "Edited");
+}
+
+/// A slide with a chart, a picture and a notes slide, plus a second slide.
+fn deck_with_rich_slide() -> (Presentation, crate::slide::SlideRef) {
+    use crate::chart::data::CategoryChartData;
+    use crate::enums::chart::XlChartType;
+    use crate::media::Image;
+    use crate::units::Emu;
+
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let slide_ref = prs.add_slide(&layouts[6]).unwrap();
+    prs.add_slide(&layouts[0]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("A");
+    data.add_series("S", &[1.0]);
+    let (x, w) = (Emu(0), Emu(914_400));
+    prs.add_chart_to_slide(&slide_ref, &data, XlChartType::Pie, x, x, w, w)
+        .unwrap();
+
+    let img = Image::from_bytes(vec![0x89, b'P', b'N', b'G'], "image/png");
+    let image_partname = PackURI::new(prs.add_image(&img).unwrap()).unwrap();
+    let target = image_partname.relative_ref(slide_ref.partname.base_uri());
+    prs.package_mut()
+        .part_mut(&slide_ref.partname)
+        .unwrap()
+        .rels
+        .add_relationship(RT::IMAGE, &target, false);
+    prs.notes_slide_or_create(&slide_ref).unwrap();
+    (prs, slide_ref)
+}
+
+fn target_of(prs: &Presentation, slide_ref: &crate::slide::SlideRef, reltype: &str) -> String {
+    let part = prs.package().part(&slide_ref.partname).unwrap();
+    let rel = part.rels.all_by_reltype(reltype)[0];
+    rel.target_partname(part.partname.base_uri())
+        .unwrap()
+        .into_string()
+}
+
+#[test]
+fn test_duplicate_slide_copies_owned_parts_and_shares_the_rest() {
+    let (mut prs, slide_ref) = deck_with_rich_slide();
+    let copy = prs.duplicate_slide(&slide_ref, None).unwrap();
+
+    assert_eq!(copy.partname.as_str(), "/ppt/slides/slide3.xml");
+    assert_eq!(prs.slide_count().unwrap(), 3);
+    assert_eq!(prs.slide_index(&copy).unwrap(), 1);
+    assert_eq!(
+        prs.slide_xml(&copy).unwrap(),
+        prs.slide_xml(&slide_ref).unwrap()
+    );
+
+    // Chart, its workbook and the notes slide are copied
+    let chart = target_of(&prs, &copy, RT::CHART);
+    assert_eq!(chart, "/ppt/charts/chart2.xml");
+    let chart_part = prs.package().part(&PackURI::new(&chart).unwrap()).unwrap();
+    let workbook = chart_part.rels.all_by_reltype(RT::PACKAGE)[0]
+        .target_partname(chart_part.partname.base_uri())
+        .unwrap();
+    assert_eq!(workbook.as_str(), "/ppt/charts/chart2.xlsx");
+    let notes = target_of(&prs, &copy, RT::NOTES_SLIDE);
+    assert_ne!(notes, target_of(&prs, &slide_ref, RT::NOTES_SLIDE));
+    let notes_part = prs.package().part(&PackURI::new(&notes).unwrap()).unwrap();
+    let back = notes_part.rels.all_by_reltype(RT::SLIDE)[0]
+        .target_partname(notes_part.partname.base_uri())
+        .unwrap();
+    assert_eq!(back, copy.partname);
+
+    // Layout and image are shared
+    for reltype in [RT::SLIDE_LAYOUT, RT::IMAGE] {
+        assert_eq!(
+            target_of(&prs, &copy, reltype),
+            target_of(&prs, &slide_ref, reltype)
+        );
+    }
+
+    // The result saves and reopens
+    let reopened = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+    assert_eq!(reopened.slide_count().unwrap(), 3);
+}
+
+#[test]
+fn test_duplicate_slide_at_index() {
+    let (mut prs, slide_ref) = deck_with_rich_slide();
+    let copy = prs.duplicate_slide(&slide_ref, Some(0)).unwrap();
+    assert_eq!(prs.slide_index(&copy).unwrap(), 0);
+    assert_eq!(prs.slide_index(&slide_ref).unwrap(), 1);
+
+    let copy = prs.duplicate_slide(&slide_ref, Some(3)).unwrap();
+    assert_eq!(prs.slide_index(&copy).unwrap(), 3);
+
+    assert!(prs.duplicate_slide(&slide_ref, Some(9)).is_err());
+    assert_eq!(prs.slide_count().unwrap(), 4);
+}
@@ -0,0 +1,210 @@
+use crate::chart::data::{CategoryChartData, ChartData};
+use crate::enums::chart::XlChartType;
+use crate::media::Image;
+use crate::opc::constants::relationship_type as RT;
+use crate::presentation::Presentation;
+use crate::shapes::Shape;
+use crate::slide::SlideRef;
+use crate::template::TemplateData;
+use crate::text::ReplaceOptions;
+use crate::units::Emu;
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 4, 5, 6];
+
+fn append_shapes(prs: &mut Presentation, slide_ref: &SlideRef, shapes: &str) {
+    let xml = String::from_utf8(prs.slide_xml(slide_ref).unwrap().to_vec()).unwrap();
+    *prs.slide_xml_mut(slide_ref).unwrap() = xml
+        .replacen("</p:spTree>", &format!("{shapes}</p:spTree>"), 1)
+        .into_bytes();
+}
+
+fn text_box(id: u32, name: &str, runs: &[&str]) -> String {
+    let runs = runs
+        .iter()
+        .map(|t| format!(r#"<a:r><a:rPr lang="en-US"/><a:t>{t}</a:t></a:r>"#))
+        .collect::<Vec<_>>()
+        .concat();
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="{name}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="914400" cy="914400"/></a:xfrm></p:spPr><p:txBody><a:bodyPr/><a:p>{runs}</a:p></p:txBody></p:sp>"#
+    )
+}
+
+fn table(id: u32) -> String {
+    let cell = |t: &str| {
+        format!(
+            r#"<a:tc><a:txBody><a:bodyPr/><a:p><a:r><a:rPr lang="en-US"/><a:t>{t}</a:t></a:r></a:p></a:txBody></a:tc>"#
+        )
+    };
+    format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="{id}" name="Table"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr><p:xfrm><a:off x="0" y="0"/><a:ext cx="1828800" cy="741680"/></p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"><a:tbl><a:tblGrid><a:gridCol w="914400"/><a:gridCol w="914400"/></a:tblGrid><a:tr h="370840">{}{}</a:tr><a:tr h="370840">{}{}</a:tr></a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#,
+        cell("Item"),
+        cell("Qty"),
+        cell("{{#rows}}{{item}}"),
+        cell("{{qty}}"),
+    )
+}
+
+/// A cover slide with text, a table, a logo slot and a chart, and a
+/// per-person slide with notes.
+fn template() -> Presentation {
+    let mut prs = Presentation::new().unwrap();
+    let layouts = prs.slide_layouts().unwrap();
+    let cover = prs.add_slide(&layouts[6]).unwrap();
+    let person = prs.add_slide(&layouts[6]).unwrap();
+
+    let mut data = CategoryChartData::new();
+    data.add_category("Old");
+    data.add_series("Old", &[1.0]);
+    let size = Emu(914_400);
+    prs.add_chart_to_slide(
+        &cover,
+        &data,
+        XlChartType::ColumnClustered,
+        size,
+        size,
+        size,
+        size,
+    )
+    .unwrap();
+    let chart_name = prs.slide_shapes(&cover).unwrap().shapes[0]
+        .name()
+        .to_string();
+    let xml = String::from_utf8(prs.slide_xml(&cover).unwrap().to_vec()).unwrap();
+    *prs.slide_xml_mut(&cover).unwrap() = xml
+        .replacen(&format!("name=\"{chart_name}\""), r#"name="{{sales}}""#, 1)
+        .into_bytes();
+
+    append_shapes(
+        &mut prs,
+        &cover,
+        &format!(
+            "{}{}{}",
+            text_box(10, "Title", &["Report for {{cust", "omer}}"]),
+            table(11),
+            text_box(12, "Logo", &["{{logo}}"]),
+        ),
+    );
+    append_shapes(
+        &mut prs,
+        &person,
+        &text_box(10, "Name", &["{{#people}}Hello {{name}}"]),
+    );
+    let notes = prs.notes_slide_or_create(&person).unwrap();
+    let part = prs.package_mut().part_mut(&notes.partname).unwrap();
+    part.blob = String::from_utf8(part.blob.clone())
+        .unwrap()
+        .replacen(
+            r#"<a:p><a:endParaRPr lang="en-US"/></a:p>"#,
+            r#"<a:p><a:r><a:rPr lang="en-US"/><a:t>Call {{name}} at {{customer}}</a:t></a:r></a:p>"#,
+            1,
+        )
+        .into_bytes();
+    prs
+}
+
+fn data() -> TemplateData {
+    let mut sales = CategoryChartData::new();
+    sales.add_category("Q1");
+    sales.add_category("Q2");
+    sales.add_series("Revenue", &[10.0, 20.0]);
+    TemplateData::new()
+        .with_text("customer", "Acme")
+        .with_list(
+            "rows",
+            vec![
+                TemplateData::new()
+                    .with_text("item", "Widgets")
+                    .with_text("qty", "12"),
+                TemplateData::new()
+                    .with_text("item", "Gadgets")
+                    .with_text("qty", "3"),
+            ],
+        )
+        .with_list(
+            "people",
+            vec![
+                TemplateData::new().with_text("name", "Ann"),
+                TemplateData::new().with_text("name", "Bo"),
+            ],
+        )
+        .with_image("logo", Image::from_bytes(PNG.to_vec(), "image/png"))
+        .with_chart("sales", ChartData::Category(sales))
+}
+
+#[test]
+fn test_fill_template() {
+    let mut prs = template();
+    let person_partname = prs.slides().unwrap()[1].partname.clone();
+    prs.fill_template(&data()).unwrap();
+    let prs = Presentation::from_bytes(&prs.to_bytes().unwrap()).unwrap();
+
+    assert!(prs
+        .find_text("{{", ReplaceOptions::new())
+        .unwrap()
+        .is_empty());
+    let slides = prs.slides().unwrap();
+    assert_eq!(slides.len(), 3);
+    assert!(prs.package().part(&person_partname).is_none());
+
+    let cover = &slides[0];
+    let tree = prs.slide_shapes(cover).unwrap();
+    let text: Vec<String> = tree
+        .shapes
+        .iter()
+        .filter_map(|s| match s {
+            Shape::AutoShape(sp) => sp.text_frame.as_ref().map(|tf| tf.text()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, vec!["Report for Acme"]);
+
+    let table = tree
+        .shapes
+        .iter()
+        .find_map(|s| match s {
+            Shape::GraphicFrame(f) => f.table.as_ref(),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(table.rows.len(), 3);
+    assert_eq!(table.cell(2, 0).text(), "Gadgets");
+
+    let Some(Shape::Picture(logo)) = tree.shapes.iter().find(|s| s.shape_id().0 == 12) else {
+        panic!("logo slot should hold a picture");
+    };
+    let part = prs.package().part(&cover.partname).unwrap();
+    let rel = part.rels.get(logo.image_r_id.as_deref().unwrap()).unwrap();
+    assert_eq!(rel.rel_type, RT::IMAGE);
+
+    let Some(Shape::GraphicFrame(frame)) = tree.shapes.iter().find(|s| s.name() == "{{sales}}")
+    else {
+        panic!("chart frame should be kept");
+    };
+    let chart = prs.chart_for(cover, frame).unwrap().unwrap();
+    let series = chart.series().get(0).unwrap();
+    assert_eq!(series.name(), "Revenue");
+    assert_eq!(series.values(), &[Some(10.0), Some(20.0)]);
+
+    for (slide, name) in slides[1..].iter().zip(["Ann", "Bo"]) {
+        let found = prs
+            .find_text(&format!("Hello {name}"), ReplaceOptions::new())
+            .unwrap();
+        assert_eq!(found[0].slide.partname, slide.partname);
+        let notes = prs
+            .find_text(&format!("Call {name} at Acme"), ReplaceOptions::new())
+            .unwrap();
+        assert_eq!(notes.len(), 1);
+    }
+}
+
+#[test]
+fn test_fill_template_empty_list_removes_slide() {
+    let mut prs = template();
+    let data = data().with_list("people", Vec::new());
+    prs.fill_template(&data).unwrap();
+    assert_eq!(prs.slide_count().unwrap(), 1);
+    assert!(prs
+        .find_text("Call", ReplaceOptions::new())
+        .unwrap()
+        .is_empty());
+}
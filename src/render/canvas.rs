@@ -0,0 +1,330 @@
+//! Drawing scene items onto a pixmap.
+
+use tiny_skia::{
+    FillRule, FilterQuality, GradientStop, LineCap, LineJoin, LinearGradient, Mask, Pattern,
+    Pixmap, PixmapPaint, Point, Rect, Shader, SpreadMode, StrokeDash, Transform,
+};
+
+use crate::export::scene::{
+    Item, Matrix, Paint, Path, PathCmd, PatternKind, SceneImage, Stroke, TextItem,
+};
+use crate::text::font::RgbColor;
+use crate::text::layout::text_width;
+
+use super::fonts::{text_outline, FontBook};
+use super::image::decode;
+
+/// Gray drawn in place of images that cannot be decoded.
+const MISSING_IMAGE: RgbColor = RgbColor::new(204, 204, 204);
+
+/// Side of a pattern cell, in points.
+const PATTERN_CELL: f64 = 8.0;
+
+/// Draws the items of one scene.
+pub(super) struct Canvas<'a> {
+    pub pixmap: Pixmap,
+    fonts: &'a FontBook,
+    /// Decoded images of the scene, `None` for ones that cannot be decoded.
+    images: Vec<Option<Pixmap>>,
+}
+
+impl<'a> Canvas<'a> {
+    pub(super) fn new(pixmap: Pixmap, fonts: &'a FontBook, images: &[SceneImage]) -> Self {
+        Self {
+            pixmap,
+            fonts,
+            images: images
+                .iter()
+                .map(|image| decode(&image.data, &image.content_type))
+                .collect(),
+        }
+    }
+
+    /// Draw `items`, given in the coordinates `transform` maps to pixels.
+    pub(super) fn draw(&mut self, items: &[Item], transform: Transform, mask: Option<&Mask>) {
+        for item in items {
+            match item {
+                Item::Fill(path, paint) => self.fill(path, paint, transform, mask),
+                Item::Stroke(path, stroke) => self.stroke(path, stroke, transform, mask),
+                Item::Text(text) => self.text(text, transform, mask),
+                Item::Image {
+                    index,
+                    x,
+                    y,
+                    width,
+                    height,
+                } => self.image(*index, [*x, *y, *width, *height], transform, mask),
+                Item::Group {
+                    transform: group,
+                    clip,
+                    items,
+                } => {
+                    let transform = transform.pre_concat(to_transform(*group));
+                    let clipped = clip.as_ref().and_then(|clip| {
+                        let path = to_path(clip)?;
+                        let mut clipped = match mask {
+                            Some(mask) => mask.clone(),
+                            None => {
+                                let mut full =
+                                    Mask::new(self.pixmap.width(), self.pixmap.height())?;
+                                full.invert();
+                                full
+                            }
+                        };
+                        clipped.intersect_path(&path, FillRule::Winding, true, transform);
+                        Some(clipped)
+                    });
+                    self.draw(items, transform, clipped.as_ref().or(mask));
+                }
+            }
+        }
+    }
+
+    fn fill(&mut self, path: &Path, paint: &Paint, transform: Transform, mask: Option<&Mask>) {
+        let Some(path) = to_path(path) else {
+            return;
+        };
+        let cell;
+        let shader = match paint {
+            Paint::Solid(color) => Shader::SolidColor(to_color(*color)),
+            Paint::LinearGradient { start, end, stops } => {
+                let stops = stops
+                    .iter()
+                    .map(|&(pos, color)| GradientStop::new(pos as f32, to_color(color)))
+                    .collect();
+                let Some(shader) = LinearGradient::new(
+                    point(*start),
+                    point(*end),
+                    stops,
+                    SpreadMode::Pad,
+                    Transform::identity(),
+                ) else {
+                    return;
+                };
+                shader
+            }
+            Paint::Pattern {
+                kind,
+                foreground,
+                background,
+            } => {
+                // Draw one cell at device resolution and tile it
+                let scale = f64::from(transform.get_scale().0.max(transform.get_scale().1));
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let side = (PATTERN_CELL * scale).round().clamp(1.0, 256.0) as u32;
+                let Some(pixmap) = pattern_cell(*kind, *foreground, *background, side) else {
+                    return;
+                };
+                cell = pixmap;
+                let unit = (PATTERN_CELL / f64::from(side)) as f32;
+                Pattern::new(
+                    cell.as_ref(),
+                    SpreadMode::Repeat,
+                    FilterQuality::Nearest,
+                    1.0,
+                    Transform::from_scale(unit, unit),
+                )
+            }
+        };
+        let paint = tiny_skia::Paint {
+            shader,
+            anti_alias: true,
+            ..tiny_skia::Paint::default()
+        };
+        self.pixmap
+            .fill_path(&path, &paint, FillRule::Winding, transform, mask);
+    }
+
+    fn stroke(&mut self, path: &Path, stroke: &Stroke, transform: Transform, mask: Option<&Mask>) {
+        let Some(path) = to_path(path) else {
+            return;
+        };
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(to_color(stroke.color));
+        paint.anti_alias = true;
+        let mut dash: Vec<f32> = stroke.dash.iter().map(|&d| d as f32).collect();
+        if dash.len() % 2 == 1 {
+            dash.extend_from_within(..);
+        }
+        let style = tiny_skia::Stroke {
+            width: stroke.width as f32,
+            line_cap: if stroke.round_cap {
+                LineCap::Round
+            } else {
+                LineCap::Butt
+            },
+            line_join: if stroke.round_join {
+                LineJoin::Round
+            } else {
+                LineJoin::Miter
+            },
+            dash: StrokeDash::new(dash, 0.0),
+            ..tiny_skia::Stroke::default()
+        };
+        self.pixmap
+            .stroke_path(&path, &paint, &style, transform, mask);
+    }
+
+    fn text(&mut self, text: &TextItem, transform: Transform, mask: Option<&Mask>) {
+        let mut paint = tiny_skia::Paint::default();
+        paint.set_color(to_color(text.color));
+        paint.anti_alias = true;
+        let (x, y, size) = (text.x as f32, text.y as f32, text.size as f32);
+
+        let Some(font) = self
+            .fonts
+            .find(text.font.as_deref(), text.face.bold, text.face.italic)
+        else {
+            // Without a font, text is shown as a bar across its x-height
+            let width = text_width(text.face, text.size, &text.text) as f32;
+            if let Some(rect) = Rect::from_xywh(x, y - size * 0.45, width, size * 0.4) {
+                paint.set_color_rgba8(text.color.r, text.color.g, text.color.b, 96);
+                self.pixmap.fill_rect(rect, &paint, transform, mask);
+            }
+            return;
+        };
+        let Some(outline) = text_outline(&font, &text.text) else {
+            return;
+        };
+        let scale = size / f32::from(font.face.units_per_em());
+        let placed = transform.pre_concat(Transform::from_row(scale, 0.0, 0.0, -scale, x, y));
+        self.pixmap
+            .fill_path(&outline, &paint, FillRule::Winding, placed, mask);
+        if font.fake_bold {
+            let style = tiny_skia::Stroke {
+                width: f32::from(font.face.units_per_em()) * 0.03,
+                line_join: LineJoin::Round,
+                ..tiny_skia::Stroke::default()
+            };
+            self.pixmap
+                .stroke_path(&outline, &paint, &style, placed, mask);
+        }
+    }
+
+    fn image(
+        &mut self,
+        index: usize,
+        [x, y, w, h]: [f64; 4],
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        let Some(Some(image)) = self.images.get(index) else {
+            let placeholder = Path::rect(x, y, w, h);
+            self.fill(&placeholder, &Paint::Solid(MISSING_IMAGE), transform, mask);
+            return;
+        };
+        // Map the image's pixels onto the box
+        let sx = w / f64::from(image.width());
+        let sy = h / f64::from(image.height());
+        let placed = transform.pre_concat(Transform::from_row(
+            sx as f32, 0.0, 0.0, sy as f32, x as f32, y as f32,
+        ));
+        let paint = PixmapPaint {
+            quality: FilterQuality::Bilinear,
+            ..PixmapPaint::default()
+        };
+        self.pixmap
+            .draw_pixmap(0, 0, image.as_ref(), &paint, placed, mask);
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)] // pixel coordinates fit in f32
+fn point((x, y): (f64, f64)) -> Point {
+    Point::from_xy(x as f32, y as f32)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_transform(matrix: Matrix) -> Transform {
+    let [a, b, c, d, e, f] = matrix.0.map(|v| v as f32);
+    Transform::from_row(a, b, c, d, e, f)
+}
+
+fn to_color(color: RgbColor) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, 255)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn to_path(path: &Path) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+    for cmd in &path.0 {
+        match *cmd {
+            PathCmd::MoveTo(x, y) => builder.move_to(x as f32, y as f32),
+            PathCmd::LineTo(x, y) => builder.line_to(x as f32, y as f32),
+            PathCmd::CubicTo(x1, y1, x2, y2, x, y) => builder.cubic_to(
+                x1 as f32, y1 as f32, x2 as f32, y2 as f32, x as f32, y as f32,
+            ),
+            PathCmd::Close => builder.close(),
+        }
+    }
+    builder.finish()
+}
+
+/// One cell of a pattern, `side` pixels square.
+fn pattern_cell(
+    kind: PatternKind,
+    foreground: RgbColor,
+    background: RgbColor,
+    side: u32,
+) -> Option<Pixmap> {
+    let mut cell = Pixmap::new(side, side)?;
+    cell.fill(to_color(background));
+    #[allow(clippy::cast_possible_truncation)]
+    let unit = (f64::from(side) / PATTERN_CELL) as f32;
+    let transform = Transform::from_scale(unit, unit);
+    let mut paint = tiny_skia::Paint::default();
+    paint.set_color(to_color(foreground));
+    paint.anti_alias = true;
+
+    let mut lines = |width: f64, lines: &[(f64, f64, f64, f64)]| {
+        let mut path = Path::default();
+        for &(x1, y1, x2, y2) in lines {
+            path.move_to(x1, y1);
+            path.line_to(x2, y2);
+        }
+        if let Some(path) = to_path(&path) {
+            let stroke = tiny_skia::Stroke {
+                width: width as f32,
+                line_cap: LineCap::Square,
+                ..tiny_skia::Stroke::default()
+            };
+            cell.stroke_path(&path, &paint, &stroke, transform, None);
+        }
+    };
+    // Diagonals continue across cells, so the corners get the ends of the
+    // neighboring cells' lines
+    let down = [
+        (0.0, 0.0, 8.0, 8.0),
+        (-1.0, 7.0, 1.0, 9.0),
+        (7.0, -1.0, 9.0, 1.0),
+    ];
+    let up = [
+        (0.0, 8.0, 8.0, 0.0),
+        (-1.0, 1.0, 1.0, -1.0),
+        (7.0, 9.0, 9.0, 7.0),
+    ];
+    match kind {
+        PatternKind::Dots(coverage) => {
+            // Four square dots per cell covering the given fraction
+            let side = 4.0 * coverage.clamp(0.0, 1.0).sqrt();
+            let offset = (4.0 - side) / 2.0;
+            let mut path = Path::default();
+            for (x, y) in [(0.0, 0.0), (4.0, 0.0), (0.0, 4.0), (4.0, 4.0)] {
+                path.0
+                    .extend(Path::rect(x + offset, y + offset, side, side).0);
+            }
+            if let Some(path) = to_path(&path) {
+                cell.fill_path(&path, &paint, FillRule::Winding, transform, None);
+            }
+        }
+        PatternKind::Horizontal(w) => lines(w, &[(0.0, 4.0, 8.0, 4.0)]),
+        PatternKind::Vertical(w) => lines(w, &[(4.0, 0.0, 4.0, 8.0)]),
+        PatternKind::DownwardDiagonal(w) => lines(w, &down),
+        PatternKind::UpwardDiagonal(w) => lines(w, &up),
+        PatternKind::Cross(w) => lines(w, &[(0.0, 4.0, 8.0, 4.0), (4.0, 0.0, 4.0, 8.0)]),
+        PatternKind::DiagonalCross(w) => {
+            lines(w, &down);
+            lines(w, &up);
+        }
+    }
+    Some(cell)
+}
@@ -0,0 +1,86 @@
+//! Encoding rendered slides as PNG and JPEG files.
+
+use std::io::Write as _;
+
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+
+use crate::error::{PptxError, PptxResult};
+
+/// Encode RGBA pixels as a PNG file.
+pub(super) fn png(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 4;
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks_exact(stride.max(1)) {
+        // Each scanline starts with its filter type; 0 is none. Writing to
+        // a Vec cannot fail.
+        let _ = encoder.write_all(&[0]);
+        let _ = encoder.write_all(row);
+    }
+    let idat = encoder.finish().unwrap_or_default();
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 8-bit RGBA, deflate, standard filters, no interlace
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut out, b"IHDR", &ihdr);
+    chunk(&mut out, b"IDAT", &idat);
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+    let len = u32::try_from(body.len()).unwrap_or(u32::MAX);
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(body);
+    let mut crc = Crc::new();
+    crc.update(kind);
+    crc.update(body);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+/// Encode RGBA pixels as a baseline JPEG file, dropping alpha.
+pub(super) fn jpeg(width: u32, height: u32, pixels: &[u8], quality: u8) -> PptxResult<Vec<u8>> {
+    let side = |pixels: u32| {
+        u16::try_from(pixels).map_err(|_| PptxError::ResourceLimit {
+            message: format!("{pixels} pixels exceeds the JPEG limit of {}", u16::MAX),
+        })
+    };
+    let (width, height) = (side(width)?, side(height)?);
+    let mut out = Vec::new();
+    jpeg_encoder::Encoder::new(&mut out, quality.clamp(1, 100))
+        .encode(pixels, width, height, jpeg_encoder::ColorType::Rgba)
+        .map_err(|e| PptxError::InvalidValue {
+            field: "pixels",
+            value: e.to_string(),
+            expected: "width × height RGBA pixels",
+        })?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_png_round_trip() {
+        let pixels = [255, 0, 0, 255, 0, 0, 255, 128];
+        let file = png(2, 1, &pixels);
+        let decoded = tiny_skia::Pixmap::decode_png(&file).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (2, 1));
+        let first = decoded.pixel(0, 0).unwrap();
+        assert_eq!((first.red(), first.alpha()), (255, 255));
+        assert_eq!(decoded.pixel(1, 0).unwrap().alpha(), 128);
+    }
+
+    #[test]
+    fn writes_jpeg() {
+        let file = jpeg(4, 4, &[200; 64], 80).unwrap();
+        assert!(file.starts_with(&[0xFF, 0xD8]));
+        assert!(jpeg(4, 4, &[200; 8], 80).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! Fonts used to draw text: supplied by the caller or embedded in the
+//! presentation.
+
+use tiny_skia::{Path, PathBuilder};
+use ttf_parser::{Face, OutlineBuilder};
+
+use crate::embedded_font::{font_program, EmbeddedFont};
+
+/// Horizontal shear of synthesized italics (about 12°).
+const ITALIC_SHEAR: f32 = 0.21;
+
+/// A usable font program and the typeface and style it stands for.
+#[derive(Debug)]
+struct FontEntry {
+    /// Typeface name, lowercased for matching.
+    typeface: String,
+    bold: bool,
+    italic: bool,
+    data: Vec<u8>,
+}
+
+/// A font chosen to draw a run, with the styles it lacks and has to be
+/// synthesized.
+pub(super) struct ChosenFont<'a> {
+    pub face: Face<'a>,
+    pub fake_bold: bool,
+    pub fake_italic: bool,
+}
+
+/// The fonts available to draw text with.
+#[derive(Debug, Default)]
+pub(super) struct FontBook {
+    fonts: Vec<FontEntry>,
+    /// Typeface used for text whose font is not available: the first
+    /// supplied font.
+    fallback: Option<String>,
+}
+
+impl FontBook {
+    /// Collect the usable fonts of `supplied` and `embedded`. Supplied
+    /// fonts take precedence over embedded fonts of the same typeface.
+    pub(super) fn new(supplied: &[EmbeddedFont], embedded: &[EmbeddedFont]) -> Self {
+        let mut book = Self::default();
+        for font in supplied {
+            if book.add(font) && book.fallback.is_none() {
+                book.fallback = Some(font.typeface.to_lowercase());
+            }
+        }
+        for font in embedded {
+            book.add(font);
+        }
+        book
+    }
+
+    fn add(&mut self, font: &EmbeddedFont) -> bool {
+        let Some(data) = font_program(&font.font_data) else {
+            return false;
+        };
+        self.fonts.push(FontEntry {
+            typeface: font.typeface.to_lowercase(),
+            bold: font.bold,
+            italic: font.italic,
+            data,
+        });
+        true
+    }
+
+    /// The font to draw `typeface` with: the closest style of that typeface,
+    /// else of the fallback typeface.
+    pub(super) fn find(
+        &self,
+        typeface: Option<&str>,
+        bold: bool,
+        italic: bool,
+    ) -> Option<ChosenFont<'_>> {
+        let wanted = typeface.map(str::to_lowercase);
+        let entry = self
+            .closest(wanted.as_deref(), bold, italic)
+            .or_else(|| self.closest(self.fallback.as_deref(), bold, italic))?;
+        Some(ChosenFont {
+            face: Face::parse(&entry.data, 0).ok()?,
+            fake_bold: bold && !entry.bold,
+            fake_italic: italic && !entry.italic,
+        })
+    }
+
+    fn closest(&self, typeface: Option<&str>, bold: bool, italic: bool) -> Option<&FontEntry> {
+        let typeface = typeface?;
+        self.fonts
+            .iter()
+            .filter(|f| f.typeface == typeface)
+            // A missing weight is easier to fake than a missing slant, and
+            // neither is worth using a bold or italic face for upright text
+            .min_by_key(|f| u8::from(f.italic != italic) * 2 + u8::from(f.bold != bold))
+    }
+}
+
+/// Glyph outlines of a line of text, in font units with y up, placed one
+/// after the other from the origin.
+pub(super) fn text_outline(font: &ChosenFont<'_>, text: &str) -> Option<Path> {
+    let mut outline = Outline {
+        builder: PathBuilder::new(),
+        x: 0.0,
+        shear: if font.fake_italic { ITALIC_SHEAR } else { 0.0 },
+    };
+    for c in text.chars() {
+        let glyph = font.face.glyph_index(c).unwrap_or_default();
+        font.face.outline_glyph(glyph, &mut outline);
+        outline.x += f32::from(font.face.glyph_hor_advance(glyph).unwrap_or(0));
+    }
+    outline.builder.finish()
+}
+
+/// Collects glyph outlines into one path, offset by the pen position.
+struct Outline {
+    builder: PathBuilder,
+    /// Pen position, in font units.
+    x: f32,
+    shear: f32,
+}
+
+impl Outline {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x + self.shear * y, y)
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// A TrueType font whose letters A to Z are all a 500×700 box advancing
+/// 600 units.
+#[cfg(test)]
+pub(crate) fn sample_font() -> Vec<u8> {
+    let be16 = |v: u16| v.to_be_bytes();
+    let mut head = vec![0u8; 54];
+    head[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+    head[12..16].copy_from_slice(&0x5F0F_3CF5u32.to_be_bytes());
+    head[18..20].copy_from_slice(&be16(1000));
+    let mut hhea = vec![0u8; 36];
+    hhea[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+    hhea[4..6].copy_from_slice(&be16(800));
+    hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+    hhea[34..36].copy_from_slice(&be16(2));
+    let maxp = [0, 0, 0x50, 0, 0, 27].to_vec();
+    // Glyph 0 advances 500; the letters share the last metric's 600
+    let mut hmtx = [be16(500), be16(0), be16(600), be16(50)].concat();
+    hmtx.extend([be16(50); 25].concat());
+    // One contour through (50,0) (550,0) (550,700) (50,700), all on-curve
+    let mut letter = [1i16, 50, 0, 550, 700, 3].map(i16::to_be_bytes).concat();
+    letter.extend_from_slice(&[0, 0, 1, 1, 1, 1]);
+    letter.extend(
+        [50i16, 500, 0, -500, 0, 0, 700, 0]
+            .map(i16::to_be_bytes)
+            .concat(),
+    );
+    let glyf = letter.repeat(26);
+    // Short offsets are halved: each letter is 34 bytes
+    let mut loca = be16(0).to_vec();
+    loca.extend((0..=26).flat_map(|i| be16(i * 17)));
+    // Format 4 subtable mapping A..Z to glyphs 1..26
+    let mut cmap = [0u16, 1, 3, 1, 0, 12].map(u16::to_be_bytes).concat();
+    cmap.extend(
+        [4u16, 32, 0, 4, 4, 1, 0, 0x5A, 0xFFFF, 0, 0x41, 0xFFFF]
+            .map(u16::to_be_bytes)
+            .concat(),
+    );
+    cmap.extend(
+        [1u16.wrapping_sub(0x41), 1, 0, 0]
+            .map(u16::to_be_bytes)
+            .concat(),
+    );
+
+    let tables: [(&[u8; 4], Vec<u8>); 7] = [
+        (b"cmap", cmap),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+    ];
+    let mut font = [
+        0x0001_0000u32.to_be_bytes().to_vec(),
+        [be16(7), be16(64), be16(2), be16(48)].concat(),
+    ]
+    .concat();
+    let mut offset = 12 + 16 * tables.len();
+    let mut body = Vec::new();
+    for (tag, data) in &tables {
+        font.extend_from_slice(*tag);
+        font.extend_from_slice(&[0; 4]);
+        font.extend_from_slice(&u32::try_from(offset).unwrap().to_be_bytes());
+        font.extend_from_slice(&u32::try_from(data.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(data);
+        while body.len() % 4 != 0 {
+            body.push(0);
+        }
+        offset = 12 + 16 * tables.len() + body.len();
+    }
+    font.extend(body);
+    font
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_data_that_is_not_a_font() {
+        assert!(font_program(b"not a font").is_none());
+        let book = FontBook::new(
+            &[EmbeddedFont::from_bytes(
+                vec![0; 64],
+                "Broken",
+                false,
+                false,
+            )],
+            &[],
+        );
+        assert!(book.fonts.is_empty());
+        assert!(book.find(Some("Broken"), false, false).is_none());
+    }
+
+    #[test]
+    fn unwraps_xor_encrypted_eot() {
+        let font = sample_font();
+        let mut eot = vec![0u8; 36];
+        let size = u32::try_from(font.len()).unwrap();
+        eot[0..4].copy_from_slice(&(size + 36).to_le_bytes());
+        eot[4..8].copy_from_slice(&size.to_le_bytes());
+        eot[12..16].copy_from_slice(&0x1000_0000u32.to_le_bytes());
+        eot[34..36].copy_from_slice(&[0x4C, 0x50]);
+        eot.extend(font.iter().map(|b| b ^ 0x50));
+        assert_eq!(font_program(&eot), Some(font));
+        // Compressed fonts are skipped
+        eot[12..16].copy_from_slice(&0x4u32.to_le_bytes());
+        assert!(font_program(&eot).is_none());
+    }
+
+    #[test]
+    fn picks_the_closest_style() {
+        let regular = EmbeddedFont::from_bytes(sample_font(), "Sample", false, false);
+        let bold = EmbeddedFont::from_bytes(sample_font(), "Sample", true, false);
+        let book = FontBook::new(&[regular], &[bold]);
+        let chosen = book.find(Some("sample"), true, true).unwrap();
+        assert!(!chosen.fake_bold);
+        assert!(chosen.fake_italic);
+        // Unknown typefaces fall back to the first supplied font
+        let chosen = book.find(Some("Calibri"), false, false).unwrap();
+        assert!(!chosen.fake_bold);
+        assert!(FontBook::new(&[], &[]).find(None, false, false).is_none());
+    }
+
+    #[test]
+    fn outlines_text_with_advances() {
+        let data = sample_font();
+        let font = ChosenFont {
+            face: Face::parse(&data, 0).unwrap(),
+            fake_bold: false,
+            fake_italic: false,
+        };
+        let path = text_outline(&font, "A A").unwrap();
+        let bounds = path.bounds();
+        // Glyph boxes at 50..550 and 1150..1650 (600 + 500 for the space)
+        assert_eq!((bounds.left(), bounds.right()), (50.0, 1650.0));
+        assert_eq!((bounds.top(), bounds.bottom()), (0.0, 700.0));
+    }
+}
@@ -0,0 +1,91 @@
+//! Decoding pictures into pixmaps.
+
+use tiny_skia::{IntSize, Pixmap};
+
+/// Largest number of pixels decoded for one picture.
+const MAX_PIXELS: u64 = 64 * 1024 * 1024;
+
+/// Decode a PNG or JPEG picture, or `None` for other formats and for data
+/// that cannot be decoded.
+pub(super) fn decode(data: &[u8], content_type: &str) -> Option<Pixmap> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // The size is in the IHDR chunk, which comes first
+        let size = |at: usize| -> Option<u64> {
+            Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?).into())
+        };
+        let (width, height) = (size(16)?, size(20)?);
+        if width * height > MAX_PIXELS {
+            return None;
+        }
+        Pixmap::decode_png(data).ok()
+    } else if data.starts_with(&[0xFF, 0xD8]) || content_type == "image/jpeg" {
+        jpeg(data)
+    } else {
+        None
+    }
+}
+
+fn jpeg(data: &[u8]) -> Option<Pixmap> {
+    let mut decoder = jpeg_decoder::Decoder::new(data);
+    decoder.read_info().ok()?;
+    let info = decoder.info()?;
+    if u64::from(info.width) * u64::from(info.height) > MAX_PIXELS {
+        return None;
+    }
+    let pixels = decoder.decode().ok()?;
+    let rgba: Vec<u8> = match info.pixel_format {
+        jpeg_decoder::PixelFormat::L8 => pixels.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+        jpeg_decoder::PixelFormat::L16 => pixels
+            .chunks_exact(2)
+            .flat_map(|l| [l[0], l[0], l[0], 255])
+            .collect(),
+        jpeg_decoder::PixelFormat::RGB24 => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        jpeg_decoder::PixelFormat::CMYK32 => pixels
+            .chunks_exact(4)
+            .flat_map(|p| {
+                let k = 255 - u16::from(p[3]);
+                #[allow(clippy::cast_possible_truncation)] // at most 255
+                let channel = |c: u8| ((255 - u16::from(c)) * k / 255) as u8;
+                [channel(p[0]), channel(p[1]), channel(p[2]), 255]
+            })
+            .collect(),
+    };
+    let size = IntSize::from_wh(u32::from(info.width), u32::from(info.height))?;
+    // Opaque pixels are already premultiplied
+    Pixmap::from_vec(rgba, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_png() {
+        let mut pixmap = Pixmap::new(2, 1).unwrap();
+        pixmap.fill(tiny_skia::Color::from_rgba8(10, 20, 30, 255));
+        let png = pixmap.encode_png().unwrap();
+        let decoded = decode(&png, "image/png").unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (2, 1));
+        assert_eq!(decoded.pixel(1, 0).unwrap().red(), 10);
+    }
+
+    #[test]
+    fn decodes_jpeg() {
+        let mut jpeg = Vec::new();
+        jpeg_encoder::Encoder::new(&mut jpeg, 90)
+            .encode(&[255; 8 * 8 * 3], 8, 8, jpeg_encoder::ColorType::Rgb)
+            .unwrap();
+        let decoded = decode(&jpeg, "image/jpeg").unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (8, 8));
+        assert!(decoded.pixel(4, 4).unwrap().red() > 250);
+    }
+
+    #[test]
+    fn skips_other_formats() {
+        assert!(decode(b"GIF89a", "image/gif").is_none());
+        assert!(decode(&[0xFF, 0xD8, 0x00], "image/jpeg").is_none());
+    }
+}
@@ -0,0 +1,278 @@
+//! Rasterizing slides to bitmaps.
+//!
+//! Slides are drawn from the same layout as the PDF export: backgrounds,
+//! shape geometry with fills and outlines, pictures and text. Text is drawn
+//! with the fonts given in [`RenderOptions`] or embedded in the
+//! presentation; text whose font is not available is shown as a gray bar,
+//! as in thumbnails.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use pptx::render::RenderOptions;
+//! use pptx::Presentation;
+//!
+//! let prs = Presentation::open("deck.pptx").unwrap();
+//! let slides = prs.slides().unwrap();
+//! let image = prs.render_slide(&slides[0], &RenderOptions::new(640)).unwrap();
+//! std::fs::write("slide1.png", image.to_png()).unwrap();
+//! ```
+
+mod canvas;
+mod encode;
+mod fonts;
+mod image;
+
+use tiny_skia::{Pixmap, Transform};
+
+use crate::embedded_font::EmbeddedFont;
+use crate::error::{PptxError, PptxResult};
+use crate::export::scene::{build_scene, page_size, SceneImage};
+use crate::presentation::Presentation;
+use crate::slide::SlideRef;
+
+use canvas::Canvas;
+use fonts::FontBook;
+
+#[cfg(test)]
+pub(crate) use fonts::sample_font;
+
+/// Width of package thumbnails, in pixels.
+pub const THUMBNAIL_WIDTH: u32 = 256;
+
+/// Largest width or height of a rendered bitmap, in pixels.
+const MAX_SIDE: u32 = 16_384;
+
+/// Size and fonts of rendered slides.
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Width of the bitmap in pixels.
+    pub width: u32,
+    /// Height of the bitmap in pixels; `None` keeps the slide's aspect
+    /// ratio.
+    pub height: Option<u32>,
+    /// Fonts to draw text with, matched by typeface name. The first one
+    /// also stands in for fonts that are not available.
+    pub fonts: Vec<EmbeddedFont>,
+    /// Whether fonts embedded in the presentation are used.
+    pub use_embedded_fonts: bool,
+}
+
+impl RenderOptions {
+    /// Create options for bitmaps `width` pixels wide.
+    #[must_use]
+    pub const fn new(width: u32) -> Self {
+        Self {
+            width,
+            height: None,
+            fonts: Vec::new(),
+            use_embedded_fonts: true,
+        }
+    }
+
+    /// Set the height of the bitmap, stretching the slide if it does not
+    /// match the slide's aspect ratio.
+    #[must_use]
+    pub const fn with_height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Add a font to draw text with.
+    #[must_use]
+    pub fn with_font(mut self, font: EmbeddedFont) -> Self {
+        self.fonts.push(font);
+        self
+    }
+
+    /// Set whether fonts embedded in the presentation are used.
+    #[must_use]
+    pub const fn with_embedded_fonts(mut self, use_embedded_fonts: bool) -> Self {
+        self.use_embedded_fonts = use_embedded_fonts;
+        self
+    }
+}
+
+impl Default for RenderOptions {
+    /// Options for thumbnail-sized bitmaps.
+    fn default() -> Self {
+        Self::new(THUMBNAIL_WIDTH)
+    }
+}
+
+/// A rendered slide: RGBA pixels, row by row from the top.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlideImage {
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// Four bytes per pixel, not premultiplied by alpha.
+    pub pixels: Vec<u8>,
+}
+
+impl SlideImage {
+    /// The RGBA color of the pixel at `(x, y)`.
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let at = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels.get(at..at + 4)?.try_into().ok()
+    }
+
+    /// Encode the image as a PNG file.
+    #[must_use]
+    pub fn to_png(&self) -> Vec<u8> {
+        encode::png(self.width, self.height, &self.pixels)
+    }
+
+    /// Encode the image as a baseline JPEG file of the given quality
+    /// (1 to 100), dropping the alpha channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be encoded.
+    pub fn to_jpeg(&self, quality: u8) -> PptxResult<Vec<u8>> {
+        encode::jpeg(self.width, self.height, &self.pixels, quality)
+    }
+}
+
+/// Renders the slides of a presentation with one set of options, loading
+/// the fonts once.
+pub struct Renderer<'a> {
+    prs: &'a Presentation,
+    width: u32,
+    height: Option<u32>,
+    fonts: FontBook,
+}
+
+impl<'a> Renderer<'a> {
+    /// Create a renderer for the slides of `prs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size is zero or too large, or the embedded
+    /// fonts cannot be read.
+    pub fn new(prs: &'a Presentation, options: &RenderOptions) -> PptxResult<Self> {
+        check_side("width", options.width)?;
+        if let Some(height) = options.height {
+            check_side("height", height)?;
+        }
+        let embedded = if options.use_embedded_fonts {
+            prs.embedded_fonts()?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            prs,
+            width: options.width,
+            height: options.height,
+            fonts: FontBook::new(&options.fonts, &embedded),
+        })
+    }
+
+    /// Draw `slide` into a bitmap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide, its layout or its master cannot be
+    /// read.
+    pub fn render(&self, slide: &SlideRef) -> PptxResult<SlideImage> {
+        let scene = build_scene(self.prs, slide)?;
+        let mut canvas = self.canvas(scene.width, scene.height, &scene.images)?;
+        let scale = self.scale(scene.width, scene.height, &canvas);
+        canvas.draw(&scene.items, scale, None);
+        Ok(finish(canvas.pixmap))
+    }
+
+    /// A white bitmap of the slide size, as drawn for presentations without
+    /// slides.
+    pub(crate) fn render_blank(&self) -> PptxResult<SlideImage> {
+        let (width, height) = page_size(self.prs)?;
+        let mut canvas = self.canvas(width, height, &[])?;
+        canvas.pixmap.fill(tiny_skia::Color::WHITE);
+        Ok(finish(canvas.pixmap))
+    }
+
+    /// A canvas for a page of `width` × `height` points.
+    fn canvas(&self, width: f64, height: f64, images: &[SceneImage]) -> PptxResult<Canvas<'_>> {
+        let pixel_height = match self.height {
+            Some(height) => height,
+            None => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let pixels = (f64::from(self.width) * height / width).round().max(1.0) as u32;
+                check_side("height", pixels)?;
+                pixels
+            }
+        };
+        let pixmap = Pixmap::new(self.width, pixel_height)
+            .ok_or_else(|| too_large("height", pixel_height))?;
+        Ok(Canvas::new(pixmap, &self.fonts, images))
+    }
+
+    /// The transform from a page of `width` × `height` points to the
+    /// canvas's pixels.
+    #[allow(clippy::cast_possible_truncation)]
+    fn scale(&self, width: f64, height: f64, canvas: &Canvas<'_>) -> Transform {
+        Transform::from_scale(
+            (f64::from(self.width) / width) as f32,
+            (f64::from(canvas.pixmap.height()) / height) as f32,
+        )
+    }
+}
+
+/// The pixels of a drawn pixmap, with alpha no longer premultiplied.
+fn finish(pixmap: Pixmap) -> SlideImage {
+    let (width, height) = (pixmap.width(), pixmap.height());
+    let mut pixels = pixmap.take();
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = u16::from(pixel[3]);
+        if alpha != 0 && alpha != 255 {
+            for channel in &mut pixel[..3] {
+                #[allow(clippy::cast_possible_truncation)] // at most 255
+                let straight = (u16::from(*channel) * 255 / alpha) as u8;
+                *channel = straight;
+            }
+        }
+    }
+    SlideImage {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// Draw `slide` of `prs` into a bitmap.
+///
+/// # Errors
+///
+/// Returns an error if the size is invalid or the slide cannot be read.
+pub fn render_slide(
+    prs: &Presentation,
+    slide: &SlideRef,
+    options: &RenderOptions,
+) -> PptxResult<SlideImage> {
+    Renderer::new(prs, options)?.render(slide)
+}
+
+fn check_side(field: &'static str, pixels: u32) -> PptxResult<()> {
+    if pixels == 0 {
+        return Err(PptxError::InvalidValue {
+            field,
+            value: pixels.to_string(),
+            expected: "at least 1 pixel",
+        });
+    }
+    if pixels > MAX_SIDE {
+        return Err(too_large(field, pixels));
+    }
+    Ok(())
+}
+
+fn too_large(field: &str, pixels: u32) -> PptxError {
+    PptxError::ResourceLimit {
+        message: format!("{field} of {pixels} pixels exceeds the limit of {MAX_SIDE}"),
+    }
+}
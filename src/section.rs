@@ -1,8 +1,23 @@
 //! Section management for `PowerPoint` presentations.
 //!
-//! Sections are stored in the presentation XML inside `<p:extLst>` as
-//! `<p:sectionLst>` elements. Each section has a name and references
-//! slides by their `p:sldId` relationship IDs.
+//! Sections are stored in the presentation XML inside `<p:extLst>` as a
+//! `<p14:sectionLst>` extension. Each section has a name and a GUID, and
+//! references its slides by their `<p:sldId>` slide IDs.
+
+use quick_xml::escape::unescape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::SlideId;
+use crate::xml_util::splice::{find_element, find_element_in, get_attr, inner_range, splice};
+use crate::xml_util::{attr_value, hashed_guid, local_name, xml_escape};
+
+/// URI of the presentation extension holding the section list.
+const SECTION_LIST_EXT_URI: &str = "{521415D9-36F7-43E2-AB2F-B90AF26B5E84}";
+
+/// Namespace of the `PowerPoint` 2010 extensions.
+const P14_NS: &str = "http://schemas.microsoft.com/office/powerpoint/2010/main";
 
 /// A section in a presentation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,6 +26,9 @@ pub struct Section {
     pub name: String,
     /// The slide indices (0-based) that belong to this section.
     pub slide_indices: Vec<usize>,
+    /// The section's GUID (e.g. `"{8C1F0B2E-...}"`); one is generated when
+    /// the section is written without it.
+    pub id: Option<String>,
 }
 
 impl Section {
@@ -20,6 +38,7 @@ impl Section {
         Self {
             name: name.to_string(),
             slide_indices: vec![first_slide_index],
+            id: None,
         }
     }
 
@@ -29,6 +48,186 @@ impl Section {
         Self {
             name: name.to_string(),
             slide_indices,
+            id: None,
+        }
+    }
+}
+
+/// A section as stored in `<p14:section>`, with its slides by slide ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SectionEntry {
+    pub name: String,
+    pub id: String,
+    pub slide_ids: Vec<SlideId>,
+}
+
+impl SectionEntry {
+    /// A new section, with a GUID that none of `sections` has.
+    pub(crate) fn new(name: &str, slide_ids: Vec<SlideId>, sections: &[Self]) -> Self {
+        let id = (sections.len()..)
+            .map(|seed| hashed_guid(&format!("{seed}:{name}")))
+            .find(|id| sections.iter().all(|s| &s.id != id))
+            .unwrap_or_default();
+        Self {
+            name: name.to_string(),
+            id,
+            slide_ids,
+        }
+    }
+}
+
+/// Parse the `<p14:sectionLst>` of a presentation XML.
+///
+/// Returns an empty list if the presentation has no sections.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed.
+pub(crate) fn parse_sections(presentation_xml: &[u8]) -> PptxResult<Vec<SectionEntry>> {
+    let mut reader = Reader::from_reader(presentation_xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut sections: Vec<SectionEntry> = Vec::new();
+    // Slide IDs inside a section are `<p14:sldId>`, not `<p:sldId>`
+    let mut in_section = false;
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => match local_name(e.name().as_ref()) {
+                b"section" => {
+                    let name = attr_value(e, b"name")?.unwrap_or_default();
+                    sections.push(SectionEntry {
+                        name: unescape(&name)
+                            .map_err(|e| PptxError::InvalidXml(e.to_string()))?
+                            .into_owned(),
+                        id: attr_value(e, b"id")?.unwrap_or_default().into_owned(),
+                        slide_ids: Vec::new(),
+                    });
+                    in_section = true;
+                }
+                b"sldId" if in_section => {
+                    let id = attr_value(e, b"id")?.and_then(|v| v.parse().ok());
+                    if let (Some(section), Some(id)) = (sections.last_mut(), id) {
+                        section.slide_ids.push(SlideId(id));
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if local_name(e.name().as_ref()) == b"section" => {
+                in_section = false;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::Xml(e)),
+            _ => {}
+        }
+    }
+    Ok(sections)
+}
+
+/// Replace the `<p14:sectionLst>` of a presentation XML with `sections`;
+/// an empty list removes it.
+///
+/// Other extensions in `<p:extLst>` are kept as they are.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or has no `</p:presentation>`.
+pub(crate) fn write_sections(
+    presentation_xml: &[u8],
+    sections: &[SectionEntry],
+) -> PptxResult<Vec<u8>> {
+    let mut xml = std::str::from_utf8(presentation_xml)?.to_string();
+
+    // Drop the current section list, and the extension list if that
+    // leaves it empty
+    if let Some(ext_lst) = find_element(&xml, "p:extLst", 0) {
+        let inner = inner_range(&xml, ext_lst.clone());
+        let mut from = inner.start;
+        while let Some(ext) = find_element_in(&xml, "p:ext", from..inner.end) {
+            let start_tag = &xml[ext.start..=ext.start + xml[ext.clone()].find('>').unwrap_or(0)];
+            if get_attr(start_tag, "uri") == Some(SECTION_LIST_EXT_URI) {
+                xml = splice(&xml, ext, "");
+                break;
+            }
+            from = ext.end;
+        }
+        if let Some(ext_lst) = find_element(&xml, "p:extLst", 0) {
+            if xml[inner_range(&xml, ext_lst.clone())].trim().is_empty() {
+                xml = splice(&xml, ext_lst, "");
+            }
+        }
+    }
+    if sections.is_empty() {
+        return Ok(xml.into_bytes());
+    }
+
+    let mut ext =
+        format!(r#"<p:ext uri="{SECTION_LIST_EXT_URI}"><p14:sectionLst xmlns:p14="{P14_NS}">"#);
+    for section in sections {
+        ext.push_str(&format!(
+            r#"<p14:section name="{}" id="{}"><p14:sldIdLst>"#,
+            xml_escape(&section.name),
+            xml_escape(&section.id)
+        ));
+        for slide_id in &section.slide_ids {
+            ext.push_str(&format!(r#"<p14:sldId id="{slide_id}"/>"#));
+        }
+        ext.push_str("</p14:sldIdLst></p14:section>");
+    }
+    ext.push_str("</p14:sectionLst></p:ext>");
+
+    // The section list goes first in an existing extension list, or in a
+    // new one at the end of the presentation
+    let (at, insert) = match find_element(&xml, "p:extLst", 0) {
+        Some(ext_lst) if !xml[..ext_lst.end].ends_with("/>") => {
+            (inner_range(&xml, ext_lst).start, ext)
+        }
+        Some(ext_lst) => {
+            let at = ext_lst.start;
+            xml = splice(&xml, ext_lst, "");
+            (at, format!("<p:extLst>{ext}</p:extLst>"))
+        }
+        None => {
+            let at = xml.rfind("</p:presentation>").ok_or_else(|| {
+                PptxError::InvalidXml(
+                    "presentation XML does not contain </p:presentation>".to_string(),
+                )
+            })?;
+            (at, format!("<p:extLst>{ext}</p:extLst>"))
+        }
+    };
+    xml.insert_str(at, &insert);
+    Ok(xml.into_bytes())
+}
+
+/// Put `moved` in the section of the slide before it in `slide_order`
+/// (the first section if it is now the first slide).
+pub(crate) fn place_moved_slide(
+    sections: &mut [SectionEntry],
+    slide_order: &[SlideId],
+    moved: SlideId,
+) {
+    for section in sections.iter_mut() {
+        section.slide_ids.retain(|&id| id != moved);
+    }
+    let previous = slide_order
+        .iter()
+        .position(|&id| id == moved)
+        .and_then(|pos| pos.checked_sub(1))
+        .map(|pos| slide_order[pos]);
+    let target = previous.and_then(|prev| {
+        sections.iter_mut().find_map(|s| {
+            let at = s.slide_ids.iter().position(|&id| id == prev)?;
+            Some((s, at + 1))
+        })
+    });
+    match target {
+        Some((section, at)) => section.slide_ids.insert(at, moved),
+        None => {
+            if let Some(first) = sections.first_mut() {
+                first.slide_ids.insert(0, moved);
+            }
         }
     }
 }
@@ -37,6 +236,8 @@ impl Section {
 mod tests {
     use super::*;
 
+    const PRESENTATION: &str = r#"<p:presentation xmlns:p="p"><p:sldIdLst><p:sldId id="256" r:id="rId2"/><p:sldId id="257" r:id="rId3"/></p:sldIdLst><p:sldSz cx="9144000" cy="6858000"/></p:presentation>"#;
+
     #[test]
     fn test_section_new() {
         let s = Section::new("Introduction", 0);
@@ -50,4 +251,77 @@ mod tests {
         assert_eq!(s.name, "Chapter 1");
         assert_eq!(s.slide_indices, vec![0, 1, 2]);
     }
+
+    #[test]
+    fn test_section_entry_guid_is_unique() {
+        let first = SectionEntry::new("Intro", Vec::new(), &[]);
+        assert_eq!(first.id.len(), 38);
+        assert!(first.id.starts_with('{') && first.id.ends_with('}'));
+        let second = SectionEntry::new("Intro", Vec::new(), std::slice::from_ref(&first));
+        assert_ne!(second.id, first.id);
+    }
+
+    #[test]
+    fn test_write_and_parse_sections() {
+        let sections = vec![
+            SectionEntry::new("Intro & Overview", vec![SlideId(256)], &[]),
+            SectionEntry::new("Empty", Vec::new(), &[]),
+            SectionEntry::new("Body", vec![SlideId(257)], &[]),
+        ];
+        let xml = write_sections(PRESENTATION.as_bytes(), &sections).unwrap();
+        let text = std::str::from_utf8(&xml).unwrap();
+        assert!(text.contains(
+            r#"<p:sldSz cx="9144000" cy="6858000"/><p:extLst><p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}"><p14:sectionLst xmlns:p14="#
+        ));
+        assert!(text.contains(r#"name="Intro &amp; Overview""#));
+        assert_eq!(parse_sections(&xml).unwrap(), sections);
+
+        // Rewriting replaces the list; removing it drops the empty extLst
+        let xml = write_sections(&xml, &sections[..1]).unwrap();
+        assert_eq!(parse_sections(&xml).unwrap(), sections[..1]);
+        let xml = write_sections(&xml, &[]).unwrap();
+        assert_eq!(std::str::from_utf8(&xml).unwrap(), PRESENTATION);
+    }
+
+    #[test]
+    fn test_write_sections_keeps_other_extensions() {
+        let xml = PRESENTATION.replace(
+            "</p:presentation>",
+            r#"<p:extLst><p:ext uri="{EFAFB233-063F-42B5-8137-9DF3F51BA10A}"><p15:sldGuideLst/></p:ext></p:extLst></p:presentation>"#,
+        );
+        let sections = [SectionEntry::new(
+            "All",
+            vec![SlideId(256), SlideId(257)],
+            &[],
+        )];
+        let written = write_sections(xml.as_bytes(), &sections).unwrap();
+        let text = std::str::from_utf8(&written).unwrap();
+        assert!(text.contains(r#"</p14:sectionLst></p:ext><p:ext uri="{EFAFB233"#));
+        let removed = write_sections(&written, &[]).unwrap();
+        assert_eq!(std::str::from_utf8(&removed).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_place_moved_slide() {
+        let mut sections = vec![
+            SectionEntry::new("A", vec![SlideId(1), SlideId(2)], &[]),
+            SectionEntry::new("B", vec![SlideId(3)], &[]),
+        ];
+        // Slide 1 moved after slide 3 joins section B
+        place_moved_slide(
+            &mut sections,
+            &[SlideId(2), SlideId(3), SlideId(1)],
+            SlideId(1),
+        );
+        assert_eq!(sections[0].slide_ids, [SlideId(2)]);
+        assert_eq!(sections[1].slide_ids, [SlideId(3), SlideId(1)]);
+        // Moved to the front, it joins the first section
+        place_moved_slide(
+            &mut sections,
+            &[SlideId(3), SlideId(2), SlideId(1)],
+            SlideId(3),
+        );
+        assert_eq!(sections[0].slide_ids, [SlideId(3), SlideId(2)]);
+        assert_eq!(sections[1].slide_ids, [SlideId(1)]);
+    }
 }
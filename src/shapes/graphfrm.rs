@@ -1,5 +1,7 @@
 use crate::shapes::placeholder::PlaceholderFormat;
+use crate::table::Table;
 use crate::units::{Emu, ShapeId};
+use crate::xml_util::{xml_escape, WriteXml};
 
 /// A graphic frame shape (`<p:graphicFrame>`).
 ///
@@ -25,6 +27,10 @@ pub struct GraphicFrame {
     /// Relationship ID for the `SmartArt` diagram data part, if this frame
     /// contains a `SmartArt` diagram.
     pub smartart_r_id: Option<String>,
+    /// Relationship ID of the chart part, if this frame contains a chart.
+    pub chart_r_id: Option<String>,
+    /// The table content, if this graphic frame contains a table.
+    pub table: Option<Table>,
 }
 
 impl std::fmt::Display for GraphicFrame {
@@ -40,6 +46,61 @@ impl GraphicFrame {
         self.smartart_r_id.is_some()
             || self.graphic_data_uri.as_deref() == Some(graphic_data_uri::DIAGRAM)
     }
+
+    /// The table hosted by this graphic frame, if any.
+    #[must_use]
+    pub const fn table(&self) -> Option<&Table> {
+        self.table.as_ref()
+    }
+
+    /// Mutable access to the hosted table, if any.
+    ///
+    /// Changes are written back to the slide by
+    /// [`Presentation::update_slide_shapes`](crate::Presentation::update_slide_shapes).
+    pub fn table_mut(&mut self) -> Option<&mut Table> {
+        self.table.as_mut()
+    }
+}
+
+/// Graphic frames are only serialized when they hold a table; charts,
+/// diagrams and OLE objects depend on parts the model does not carry, so
+/// nothing is written for them.
+impl WriteXml for GraphicFrame {
+    fn write_xml<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
+        let Some(ref table) = self.table else {
+            return Ok(());
+        };
+        w.write_str("<p:graphicFrame><p:nvGraphicFramePr>")?;
+        write!(
+            w,
+            r#"<p:cNvPr id="{}" name="{}"/>"#,
+            self.shape_id,
+            xml_escape(&self.name)
+        )?;
+        w.write_str(
+            r#"<p:cNvGraphicFramePr><a:graphicFrameLocks noGrp="1"/></p:cNvGraphicFramePr>"#,
+        )?;
+        if let Some(ref ph) = self.placeholder {
+            w.write_str("<p:nvPr><p:ph")?;
+            if let Some(pt) = ph.ph_type {
+                write!(w, r#" type="{}""#, pt.to_xml_str())?;
+            }
+            if ph.idx.0 > 0 {
+                write!(w, r#" idx="{}""#, ph.idx)?;
+            }
+            w.write_str("/></p:nvPr>")?;
+        } else {
+            w.write_str("<p:nvPr/>")?;
+        }
+        w.write_str("</p:nvGraphicFramePr>")?;
+        write!(
+            w,
+            r#"<p:xfrm><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></p:xfrm>"#,
+            self.left.0, self.top.0, self.width.0, self.height.0
+        )?;
+        w.write_str(&table.to_graphic_data_xml())?;
+        w.write_str("</p:graphicFrame>")
+    }
 }
 
 /// Well-known graphic data URIs.
@@ -4,6 +4,7 @@ use crate::shapes::connector::Connector;
 use crate::shapes::graphfrm::{self, GraphicFrame};
 use crate::shapes::picture::Picture;
 use crate::shapes::Shape;
+use crate::table::Table;
 use crate::units::Emu;
 
 use super::GroupShape;
@@ -192,14 +193,13 @@ impl GroupShape {
 
     /// Add a table (as a `GraphicFrame`) to this group.
     ///
-    /// Creates a `GraphicFrame` with table metadata. The actual table data
-    /// is not embedded in the `GraphicFrame` struct; use the returned shape
-    /// to further configure as needed.
+    /// Creates a `GraphicFrame` holding an empty `rows` x `cols` table; use
+    /// [`GraphicFrame::table_mut`] on the returned shape to fill it in.
     /// Returns a mutable reference to the newly added `Shape`.
     pub fn add_table(
         &mut self,
         rows: usize,
-        _cols: usize,
+        cols: usize,
         left: Emu,
         top: Emu,
         width: Emu,
@@ -225,6 +225,8 @@ impl GroupShape {
             graphic_data_uri: Some(graphfrm::graphic_data_uri::TABLE.to_string()),
             placeholder: None,
             smartart_r_id: None,
+            chart_r_id: None,
+            table: Some(Table::new(rows, cols, width, row_height)),
         }));
 
         self.shapes.push(shape);
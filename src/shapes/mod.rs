@@ -206,12 +206,9 @@ impl WriteXml for Shape {
             Self::GroupShape(s) => s.write_xml(w),
             Self::Connector(s) => s.write_xml(w),
             Self::OleObject(s) => s.write_xml(w),
-            Self::GraphicFrame(_) => {
-                // GraphicFrame (tables, charts, SmartArt) is serialized through
-                // specialized paths in ShapeTree that preserve the original XML.
-                // Direct WriteXml serialization is not supported for this shape type.
-                Ok(())
-            }
+            // Only table frames serialize from the model; charts and SmartArt
+            // are written through specialized paths that preserve the original XML.
+            Self::GraphicFrame(s) => s.write_xml(w),
         }
     }
 }
@@ -16,7 +16,7 @@ use crate::xml_util::{attr_value, local_name_str};
 /// # Errors
 ///
 /// Returns an error if the XML contains malformed attributes.
-pub(super) fn parse_color_from_xml(xml: &[u8]) -> PptxResult<Option<ColorFormat>> {
+pub(crate) fn parse_color_from_xml(xml: &[u8]) -> PptxResult<Option<ColorFormat>> {
     let mut reader = Reader::from_reader(xml);
     reader.config_mut().trim_text(true);
     let mut buf = Vec::new();
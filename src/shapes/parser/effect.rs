@@ -0,0 +1,178 @@
+//! Effect list and 3D scene parsing from OOXML XML.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::dml::color::ColorFormat;
+use crate::dml::effect::{GlowFormat, ShadowFormat, ShadowType};
+use crate::dml::effect3d::{Camera, LightRig, Rotation3D, Scene3D};
+use crate::error::{PptxError, PptxResult};
+use crate::units::Emu;
+
+use crate::xml_util::{attr_value, emu_attr, local_name_str, read_inner_xml};
+
+use super::parse_color_from_xml;
+
+/// Parse the shadow and glow of an `<a:effectLst>` element's children.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed or contains invalid attributes.
+pub(crate) fn parse_effects_from_xml(
+    xml: &[u8],
+) -> PptxResult<(Option<ShadowFormat>, Option<GlowFormat>)> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut shadow = None;
+    let mut glow = None;
+
+    loop {
+        buf.clear();
+        let (e, inner) = match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => {
+                let e = e.into_owned();
+                let tag = local_name_str(e.name().as_ref()).to_string();
+                let inner = read_inner_xml(&mut reader, &tag)
+                    .map_err(|err| PptxError::InvalidXml(format!("{tag}: {err}")))?;
+                (e, inner)
+            }
+            Ok(Event::Empty(e)) => (e.into_owned(), Vec::new()),
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::InvalidXml(format!("effect XML error: {e}"))),
+            _ => continue,
+        };
+        let qn = e.name();
+        match local_name_str(qn.as_ref()) {
+            "outerShdw" | "innerShdw" => shadow = Some(parse_shadow(&e, &inner)?),
+            "glow" => {
+                if let Some(color) = parse_color_from_xml(&inner)? {
+                    let radius = emu_attr(&e, b"rad")?.unwrap_or(Emu(0));
+                    glow = Some(GlowFormat::new(color, radius));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok((shadow, glow))
+}
+
+fn parse_shadow(e: &BytesStart<'_>, inner: &[u8]) -> PptxResult<ShadowFormat> {
+    let shadow_type = if local_name_str(e.name().as_ref()) == "innerShdw" {
+        ShadowType::Inner
+    } else if attr_value(e, b"kx")?.is_some() {
+        ShadowType::Perspective
+    } else {
+        ShadowType::Outer
+    };
+    let color = parse_color_from_xml(inner)?;
+    // The alpha of an RGB color is kept as the shadow's opacity
+    let opacity = match color {
+        Some(ColorFormat::Rgb(_)) => parse_alpha(inner)?,
+        _ => None,
+    };
+    // i64→f64: OOXML angles fit in 53-bit mantissa
+    #[allow(clippy::cast_precision_loss)]
+    let direction = attr_value(e, b"dir")?
+        .and_then(|d| d.parse::<i64>().ok())
+        .map(|d| d as f64 / 60_000.0);
+    Ok(ShadowFormat {
+        shadow_type,
+        color,
+        blur_radius: emu_attr(e, b"blurRad")?,
+        distance: emu_attr(e, b"dist")?,
+        direction,
+        opacity,
+    })
+}
+
+/// The `<a:alpha>` modifier of a color, from 0.0 to 1.0.
+fn parse_alpha(xml: &[u8]) -> PptxResult<Option<f64>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Empty(ref e) | Event::Start(ref e))
+                if local_name_str(e.name().as_ref()) == "alpha" =>
+            {
+                // i64→f64: OOXML percentages fit in 53-bit mantissa
+                #[allow(clippy::cast_precision_loss)]
+                return Ok(attr_value(e, b"val")?
+                    .and_then(|v| v.parse::<i64>().ok())
+                    .map(|v| v as f64 / 100_000.0));
+            }
+            Ok(Event::Eof) | Err(_) => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Parse the camera and light rig of a `<a:scene3d>` element's children.
+///
+/// Returns `None` if either is missing.
+///
+/// # Errors
+///
+/// Returns an error if the XML contains malformed attributes.
+pub(crate) fn parse_scene_3d_from_xml(xml: &[u8]) -> PptxResult<Option<Scene3D>> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+
+    let mut camera: Option<Camera> = None;
+    let mut light_rig: Option<LightRig> = None;
+    // Which of the two a `<a:rot>` belongs to
+    let mut in_camera = false;
+
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => {
+                let qn = e.name();
+                match local_name_str(qn.as_ref()) {
+                    "camera" => {
+                        let mut cam = Camera::new(
+                            attr_value(e, b"prst")?
+                                .as_deref()
+                                .unwrap_or("orthographicFront"),
+                        );
+                        cam.fov = attr_value(e, b"fov")?.and_then(|v| v.parse().ok());
+                        camera = Some(cam);
+                        in_camera = true;
+                    }
+                    "lightRig" => {
+                        light_rig = Some(LightRig::new(
+                            attr_value(e, b"rig")?.as_deref().unwrap_or("threePt"),
+                            attr_value(e, b"dir")?.as_deref().unwrap_or("t"),
+                        ));
+                        in_camera = false;
+                    }
+                    "rot" => {
+                        let angle = |name: &[u8]| -> PptxResult<i64> {
+                            Ok(attr_value(e, name)?
+                                .and_then(|v| v.parse().ok())
+                                .unwrap_or(0))
+                        };
+                        let rot = Rotation3D::new(angle(b"lat")?, angle(b"lon")?, angle(b"rev")?);
+                        if in_camera {
+                            if let Some(cam) = camera.as_mut() {
+                                cam.rot = Some(rot);
+                            }
+                        } else if let Some(rig) = light_rig.as_mut() {
+                            rig.rot = Some(rot);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::InvalidXml(format!("scene3d XML error: {e}"))),
+            _ => {}
+        }
+    }
+    Ok(camera
+        .zip(light_rig)
+        .map(|(camera, light_rig)| Scene3D::new(camera, light_rig)))
+}
@@ -3,8 +3,12 @@
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use crate::dml::fill::{FillFormat, GradientFill, GradientStop, SolidFill};
+use crate::dml::fill::{
+    FillFormat, GradientFill, GradientStop, PatternFill, PictureFill, SolidFill,
+};
+use crate::enums::dml_pattern::MsoPatternType;
 use crate::error::{PptxError, PptxResult};
+use crate::units::RelationshipId;
 
 use crate::xml_util::{attr_value, local_name_str, read_inner_xml};
 
@@ -12,7 +16,8 @@ use super::parse_color_from_xml;
 
 /// Parse fill format from `<p:spPr>` or similar XML containing fill elements.
 ///
-/// Detects `<a:solidFill>`, `<a:noFill/>`, `<a:gradFill>`, and `<a:grpFill/>`.
+/// Detects `<a:solidFill>`, `<a:noFill/>`, `<a:gradFill>`, `<a:pattFill>`,
+/// `<a:blipFill>` and `<a:grpFill/>`.
 pub fn parse_fill_from_xml(sp_pr_bytes: &[u8]) -> PptxResult<Option<FillFormat>> {
     let mut reader = Reader::from_reader(sp_pr_bytes);
     reader.config_mut().trim_text(true);
@@ -40,6 +45,17 @@ pub fn parse_fill_from_xml(sp_pr_bytes: &[u8]) -> PptxResult<Option<FillFormat>>
                             .map_err(|e| PptxError::InvalidXml(format!("gradFill: {e}")))?;
                         return parse_gradient_from_inner(&inner);
                     }
+                    "pattFill" => {
+                        let preset = attr_value(e, b"prst")?.map(|p| p.into_owned());
+                        let inner = read_inner_xml(&mut reader, "pattFill")
+                            .map_err(|e| PptxError::InvalidXml(format!("pattFill: {e}")))?;
+                        return parse_pattern_from_inner(preset.as_deref(), &inner);
+                    }
+                    "blipFill" => {
+                        let inner = read_inner_xml(&mut reader, "blipFill")
+                            .map_err(|e| PptxError::InvalidXml(format!("blipFill: {e}")))?;
+                        return parse_blip_fill_from_inner(&inner);
+                    }
                     _ => {}
                 }
             }
@@ -48,6 +64,10 @@ pub fn parse_fill_from_xml(sp_pr_bytes: &[u8]) -> PptxResult<Option<FillFormat>>
                 let local = local_name_str(qn.as_ref());
                 match local {
                     "noFill" => return Ok(Some(FillFormat::NoFill)),
+                    "pattFill" => {
+                        let preset = attr_value(e, b"prst")?;
+                        return parse_pattern_from_inner(preset.as_deref(), b"");
+                    }
                     "grpFill" => return Ok(Some(FillFormat::Background)),
                     _ => {}
                 }
@@ -122,3 +142,81 @@ pub(super) fn parse_gradient_from_inner(xml: &[u8]) -> PptxResult<Option<FillFor
 
     Ok(Some(FillFormat::Gradient(GradientFill { stops, angle })))
 }
+
+/// Parse the `<a:fgClr>` and `<a:bgClr>` children of a `<a:pattFill>`.
+pub(super) fn parse_pattern_from_inner(
+    preset: Option<&str>,
+    xml: &[u8],
+) -> PptxResult<Option<FillFormat>> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut pattern = PatternFill {
+        preset: preset.and_then(MsoPatternType::from_xml_str),
+        fore_color: None,
+        back_color: None,
+    };
+
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let qn = e.name();
+                let local = local_name_str(qn.as_ref());
+                if local == "fgClr" || local == "bgClr" {
+                    let inner = read_inner_xml(&mut reader, local)
+                        .map_err(|e| PptxError::InvalidXml(format!("{local}: {e}")))?;
+                    let color = parse_color_from_xml(&inner)?;
+                    if local == "fgClr" {
+                        pattern.fore_color = color;
+                    } else {
+                        pattern.back_color = color;
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::InvalidXml(format!("pattern XML error: {e}"))),
+            _ => {}
+        }
+    }
+    Ok(Some(FillFormat::Pattern(pattern)))
+}
+
+/// Parse the children of a `<a:blipFill>`.
+///
+/// Returns `None` when the fill has no embedded image (e.g. a linked one).
+pub(super) fn parse_blip_fill_from_inner(xml: &[u8]) -> PptxResult<Option<FillFormat>> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut buf = Vec::new();
+    let mut image_r_id = None;
+    let mut tile = false;
+
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => {
+                let qn = e.name();
+                match local_name_str(qn.as_ref()) {
+                    "blip" => {
+                        // .ok() is intentional: an unusual rId is treated as absent
+                        image_r_id = attr_value(e, b"r:embed")?
+                            .and_then(|id| RelationshipId::try_from(id.as_ref()).ok());
+                    }
+                    "tile" => tile = true,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(PptxError::InvalidXml(format!("blipFill XML error: {e}"))),
+            _ => {}
+        }
+    }
+    Ok(image_r_id.map(|image_r_id| {
+        FillFormat::Picture(PictureFill {
+            image_r_id,
+            stretch: !tile,
+            tile,
+        })
+    }))
+}
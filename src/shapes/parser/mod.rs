@@ -5,6 +5,7 @@
 //! open .pptx -> parse shapes into structs -> modify -> save.
 
 mod color;
+mod effect;
 mod fill;
 mod line;
 mod text_frame;
@@ -17,7 +18,8 @@ pub use text_frame::parse_text_frame_from_xml;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
-use color::parse_color_from_xml;
+pub(crate) use color::parse_color_from_xml;
+pub(crate) use effect::{parse_effects_from_xml, parse_scene_3d_from_xml};
 
 use crate::dml::fill::FillFormat;
 use crate::dml::line::LineFormat;
@@ -60,6 +62,17 @@ pub fn parse_sp_pr(sp_pr_bytes: &[u8]) -> PptxResult<(Option<FillFormat>, Option
                             .map_err(|e| PptxError::InvalidXml(format!("gradFill: {e}")))?;
                         fill = fill::parse_gradient_from_inner(&inner)?;
                     }
+                    "pattFill" if depth <= 2 => {
+                        let preset = attr_value(e, b"prst")?.map(|p| p.into_owned());
+                        let inner = read_inner_xml(&mut reader, "pattFill")
+                            .map_err(|e| PptxError::InvalidXml(format!("pattFill: {e}")))?;
+                        fill = fill::parse_pattern_from_inner(preset.as_deref(), &inner)?;
+                    }
+                    "blipFill" if depth <= 2 => {
+                        let inner = read_inner_xml(&mut reader, "blipFill")
+                            .map_err(|e| PptxError::InvalidXml(format!("blipFill: {e}")))?;
+                        fill = fill::parse_blip_fill_from_inner(&inner)?;
+                    }
                     "ln" => {
                         let w_attr = attr_value(e, b"w")?;
                         let inner = read_inner_xml(&mut reader, "ln")
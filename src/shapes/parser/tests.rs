@@ -3,10 +3,13 @@ use crate::dml::color::ColorFormat;
 use crate::dml::fill::FillFormat;
 use crate::dml::line::LineFormat;
 use crate::enums::dml::{MsoLineDashStyle, MsoThemeColorIndex, SystemColorVal};
+use crate::enums::dml_pattern::MsoPatternType;
 use crate::enums::text::{
-    MsoAutoSize, MsoTextUnderlineType, MsoVerticalAnchor, PpParagraphAlignment,
+    MsoAutoNumberScheme, MsoAutoSize, MsoBaselineAlignment, MsoTabStopType, MsoTextCaps,
+    MsoTextOrientation, MsoTextUnderlineType, MsoVerticalAnchor, PpParagraphAlignment,
 };
 use crate::text::font::RgbColor;
+use crate::text::{BulletFormat, TabStop};
 use crate::units::Emu;
 use crate::xml_util::WriteXml;
 
@@ -144,6 +147,108 @@ fn test_parse_text_frame_autofit() {
     assert_eq!(tf.font_scale, Some(80.0));
 }
 
+#[test]
+fn test_parse_text_frame_line_spacing_reduction() {
+    let xml = br#"<p:txBody><a:bodyPr><a:normAutofit fontScale="62500" lnSpcReduction="20000"/></a:bodyPr><a:lstStyle/><a:p><a:endParaRPr lang="en-US"/></a:p></p:txBody>"#;
+    let tf = parse_text_frame_from_xml(xml).unwrap().unwrap();
+    assert_eq!(tf.font_scale, Some(62.5));
+    assert_eq!(tf.line_spacing_reduction, Some(20.0));
+}
+
+#[test]
+fn test_parse_paragraph_indents_tabs_and_spacing() {
+    let xml = br#"<p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:pPr marL="342900" indent="-342900" defTabSz="457200" eaLnBrk="0" latinLnBrk="1" fontAlgn="ctr"><a:lnSpc><a:spcPts val="1800"/></a:lnSpc><a:spcBef><a:spcPct val="50000"/></a:spcBef><a:spcAft><a:spcPts val="600"/></a:spcAft><a:buAutoNum type="arabicPeriod"/><a:tabLst><a:tab pos="914400" algn="dec"/><a:tab pos="1828800"/></a:tabLst></a:pPr><a:r><a:t>One</a:t></a:r></a:p><a:p><a:pPr><a:lnSpc><a:spcPct val="150000"/></a:lnSpc></a:pPr></a:p></p:txBody>"#;
+    let tf = parse_text_frame_from_xml(xml).unwrap().unwrap();
+    let p = &tf.paragraphs()[0];
+    assert_eq!(p.margin_left, Some(Emu(342_900)));
+    assert_eq!(p.indent, Some(Emu(-342_900)));
+    assert_eq!(p.default_tab_size, Some(Emu(457_200)));
+    assert_eq!(p.east_asian_line_break, Some(false));
+    assert_eq!(p.latin_line_break, Some(true));
+    assert_eq!(p.font_alignment, Some(MsoBaselineAlignment::Center));
+    assert_eq!(p.line_spacing_pts, Some(18.0));
+    assert_eq!(p.space_before_pct, Some(50.0));
+    assert_eq!(p.space_after, Some(6.0));
+    assert_eq!(
+        p.tab_stops,
+        vec![
+            TabStop::new(Emu(914_400), MsoTabStopType::Decimal),
+            TabStop::new(Emu(1_828_800), MsoTabStopType::Left),
+        ]
+    );
+    assert_eq!(tf.paragraphs()[1].line_spacing, Some(1.5));
+    // Everything is written back as it was read
+    let reparsed = parse_text_frame_from_xml(tf.to_xml_string().as_bytes())
+        .unwrap()
+        .unwrap();
+    for (a, b) in reparsed.paragraphs().iter().zip(tf.paragraphs()) {
+        assert_eq!(a.to_xml_string(), b.to_xml_string());
+    }
+}
+
+#[test]
+fn test_parse_paragraph_bullets() {
+    let xml = br#"<p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:pPr marL="342900" indent="-342900"><a:buClr><a:srgbClr val="C00000"/></a:buClr><a:buSzPct val="75000"/><a:buFont typeface="+mj-lt"/><a:buAutoNum type="romanUcParenBoth" startAt="3"/></a:pPr><a:r><a:rPr lang="en-US"/><a:t>Third</a:t></a:r></a:p><a:p><a:pPr><a:buClrTx/><a:buSzTx/><a:buFontTx/><a:buBlip><a:blip r:embed="rId4"/></a:buBlip></a:pPr><a:r><a:rPr lang="en-US"/><a:t>Pictured</a:t></a:r></a:p><a:p><a:pPr><a:buSzPts val="1400"/><a:buAutoNum type="notAScheme"/></a:pPr></a:p></p:txBody>"#;
+    let tf = parse_text_frame_from_xml(xml).unwrap().unwrap();
+    let [numbered, pictured, unknown] = tf.paragraphs() else {
+        panic!("expected three paragraphs");
+    };
+    assert_eq!(numbered.bullet_color, Some(ColorFormat::rgb(0xC0, 0, 0)));
+    assert_eq!(numbered.bullet_size_pct, Some(75.0));
+    assert_eq!(numbered.bullet_font.as_deref(), Some("+mj-lt"));
+    assert_eq!(
+        numbered.bullet,
+        Some(BulletFormat::AutoNumbered {
+            scheme: MsoAutoNumberScheme::RomanUcParenBoth,
+            start_at: Some(3),
+        })
+    );
+    // The bullet color is not taken for the text color
+    assert_eq!(numbered.runs()[0].font().color, None);
+    assert!(pictured.bullet_color_follows_text);
+    assert!(pictured.bullet_size_follows_text);
+    assert!(pictured.bullet_font_follows_text);
+    assert_eq!(
+        pictured.bullet,
+        Some(BulletFormat::Picture("rId4".to_string()))
+    );
+    assert_eq!(unknown.bullet_size_pts, Some(14.0));
+    assert_eq!(unknown.bullet, None);
+    assert_eq!(tf.bullet_labels(), [Some("(III)".to_string()), None, None]);
+    // Everything is written back as it was read
+    let reparsed = parse_text_frame_from_xml(tf.to_xml_string().as_bytes())
+        .unwrap()
+        .unwrap();
+    assert_eq!(reparsed, tf);
+}
+
+#[test]
+fn test_parse_text_frame_body_properties() {
+    let xml = r#"<p:txBody><a:bodyPr vert="eaVert" numCol="3" spcCol="228600" anchor="t" anchorCtr="1" upright="1"><a:prstTxWarp prst="textWave1"><a:avLst><a:gd name="adj1" fmla="val 12500"/></a:avLst></a:prstTxWarp><a:normAutofit/><a:scene3d><a:camera prst="perspectiveFront" fov="2700000"><a:rot lat="0" lon="1200000" rev="0"/></a:camera><a:lightRig rig="balanced" dir="t"/></a:scene3d></a:bodyPr><a:lstStyle/><a:p><a:r><a:rPr lang="ja-JP"/><a:t>縦書き</a:t></a:r></a:p></p:txBody>"#;
+    let tf = parse_text_frame_from_xml(xml.as_bytes()).unwrap().unwrap();
+    // No wrap attribute: the setting is inherited
+    assert!(tf.word_wrap);
+    assert_eq!(tf.word_wrap_setting(), None);
+    assert_eq!(tf.orientation, Some(MsoTextOrientation::EastAsianVertical));
+    assert_eq!(tf.columns, Some(3));
+    assert_eq!(tf.column_spacing, Some(Emu(228_600)));
+    assert_eq!(tf.anchor_center, Some(true));
+    assert_eq!(tf.upright, Some(true));
+    assert_eq!(tf.auto_size, MsoAutoSize::TextToFitShape);
+    let warp = tf.warp.as_ref().unwrap();
+    assert_eq!(warp.preset, "textWave1");
+    assert_eq!(warp.adjustments, vec![0.125]);
+    let scene = tf.scene_3d.as_ref().unwrap();
+    assert_eq!(scene.camera.fov, Some(2_700_000));
+    assert_eq!(scene.camera.rot.as_ref().unwrap().lon, 1_200_000);
+    assert_eq!(tf.text(), "縦書き");
+
+    let tf2 = parse_text_frame_from_xml(tf.to_xml_string().as_bytes())
+        .unwrap()
+        .unwrap();
+    assert_eq!(tf2, tf);
+}
+
 #[test]
 fn test_parse_sp_pr() {
     let xml = br#"<p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="100" cy="100"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:solidFill><a:srgbClr val="00FF00"/></a:solidFill><a:ln w="12700"><a:solidFill><a:srgbClr val="000000"/></a:solidFill></a:ln></p:spPr>"#;
@@ -191,6 +296,36 @@ fn test_parse_fill_background() {
     assert_eq!(fill, Some(FillFormat::Background));
 }
 
+#[test]
+fn test_parse_pattern_fill() {
+    let xml = br#"<a:spPr><a:pattFill prst="dkDnDiag"><a:fgClr><a:srgbClr val="FF0000"/></a:fgClr><a:bgClr><a:schemeClr val="bg1"/></a:bgClr></a:pattFill></a:spPr>"#;
+    match parse_fill_from_xml(xml).unwrap() {
+        Some(FillFormat::Pattern(pf)) => {
+            assert_eq!(pf.preset, Some(MsoPatternType::DarkDownwardDiagonal));
+            assert_eq!(pf.fore_color, Some(ColorFormat::rgb(255, 0, 0)));
+            assert_eq!(
+                pf.back_color,
+                Some(ColorFormat::theme(MsoThemeColorIndex::Background1))
+            );
+        }
+        other => panic!("Expected Pattern fill, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_picture_fill() {
+    let xml = br#"<p:spPr><a:blipFill rotWithShape="1"><a:blip r:embed="rId3"/><a:tile tx="0" ty="0"/></a:blipFill><a:ln><a:solidFill><a:srgbClr val="000000"/></a:solidFill></a:ln></p:spPr>"#;
+    let (fill, _) = parse_sp_pr(xml).unwrap();
+    match fill {
+        Some(FillFormat::Picture(pf)) => {
+            assert_eq!(pf.image_r_id.as_str(), "rId3");
+            assert!(pf.tile);
+            assert!(!pf.stretch);
+        }
+        other => panic!("Expected Picture fill, got {other:?}"),
+    }
+}
+
 #[test]
 fn test_parse_color_system() {
     let xml = br#"<a:sysClr val="windowText" lastClr="000000"/>"#;
@@ -242,6 +377,40 @@ fn test_round_trip_text_frame() {
     assert_eq!(tf2.paragraphs()[0].runs()[0].font().size, Some(18.0));
 }
 
+#[test]
+fn test_round_trip_run_character_properties() {
+    let xml = r#"<p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="ja-JP" altLang="en-US" sz="2000" kern="1200" cap="all" spc="50" strike="dblStrike" baseline="14000"><a:ln w="9525"><a:solidFill><a:srgbClr val="00FF00"/></a:solidFill></a:ln><a:solidFill><a:srgbClr val="112233"/></a:solidFill><a:effectLst><a:glow rad="63500"><a:schemeClr val="accent1"/></a:glow><a:outerShdw blurRad="38100" dist="25400" dir="2700000"><a:srgbClr val="000000"><a:alpha val="40000"/></a:srgbClr></a:outerShdw></a:effectLst><a:highlight><a:srgbClr val="FFFF00"/></a:highlight><a:latin typeface="Yu Gothic"/><a:ea typeface="Yu Gothic"/><a:cs typeface="Arial"/><a:sym typeface="Symbol"/></a:rPr><a:t>日本語</a:t></a:r></a:p></p:txBody>"#;
+    let tf = parse_text_frame_from_xml(xml.as_bytes()).unwrap().unwrap();
+    let font = tf.paragraphs()[0].runs()[0].font();
+    assert_eq!(font.kerning, Some(12.0));
+    assert_eq!(font.caps, Some(MsoTextCaps::All));
+    assert_eq!(font.character_spacing, Some(0.5));
+    assert_eq!(font.double_strikethrough, Some(true));
+    assert_eq!(font.strikethrough, Some(true));
+    assert_eq!(font.superscript, Some(true));
+    assert_eq!(font.baseline, Some(14.0));
+    // The outline's fill is not taken for the text color
+    assert_eq!(font.color, Some(RgbColor::new(0x11, 0x22, 0x33)));
+    let outline = font.outline.as_ref().unwrap();
+    assert_eq!(outline.width, Some(Emu(9525)));
+    assert_eq!(outline.color, Some(ColorFormat::rgb(0, 255, 0)));
+    assert_eq!(font.glow.as_ref().unwrap().radius, Emu(63_500));
+    let shadow = font.shadow.as_ref().unwrap();
+    assert_eq!(shadow.blur_radius, Some(Emu(38_100)));
+    assert_eq!(shadow.direction, Some(45.0));
+    assert_eq!(shadow.opacity, Some(0.4));
+    assert_eq!(font.highlight, Some(ColorFormat::rgb(255, 255, 0)));
+    assert_eq!(font.east_asian_name.as_deref(), Some("Yu Gothic"));
+    assert_eq!(font.complex_script_name.as_deref(), Some("Arial"));
+    assert_eq!(font.symbol_name.as_deref(), Some("Symbol"));
+
+    // Everything survives writing and reading back
+    let tf2 = parse_text_frame_from_xml(tf.to_xml_string().as_bytes())
+        .unwrap()
+        .unwrap();
+    assert_eq!(tf2.paragraphs()[0].runs()[0].font(), font);
+}
+
 #[test]
 fn test_round_trip_fill_and_line() {
     // Create fill + line, generate XML, parse back
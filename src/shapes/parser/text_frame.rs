@@ -2,6 +2,7 @@
 
 use std::mem;
 
+use quick_xml::escape::resolve_predefined_entity;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
@@ -9,18 +10,29 @@ use crate::dml::color::ColorFormat;
 use crate::dml::fill::FillFormat;
 use crate::enums::dml::MsoThemeColorIndex;
 use crate::enums::text::MsoAutoSize;
-use crate::error::PptxResult;
+use crate::error::{PptxError, PptxResult};
 use crate::text::font::{Font, RgbColor};
 use crate::text::{Paragraph, Run, TextFrame};
 
-use crate::xml_util::{attr_value, local_name_str};
+use crate::xml_util::{attr_value, local_name_str, read_inner_xml};
 
 use crate::dml::color::ThemeColor;
 
+use super::parse_scene_3d_from_xml;
 use super::text_helpers::{
-    has_font_properties, parse_body_pr_attrs, parse_paragraph_props, parse_run_props_attrs,
+    has_font_properties, parse_body_pr_attrs, parse_bullet_prop, parse_paragraph_props,
+    parse_run_props_attrs, parse_run_props_child, parse_spacing_val, parse_tab_stop,
+    parse_text_warp, parse_typeface,
 };
 
+/// The spacing element of `<a:pPr>` being read.
+#[derive(Clone, Copy)]
+enum ParaSpacing {
+    Line,
+    Before,
+    After,
+}
+
 /// Parse a `<p:txBody>` (or `<a:txBody>`) XML fragment into a `TextFrame`.
 ///
 /// # Errors
@@ -34,6 +46,8 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
 
     let mut tf = TextFrame::new();
     tf.paragraphs.clear();
+    // Without a `wrap` attribute the setting is inherited
+    tf.inherit_word_wrap = true;
 
     let mut in_body_pr = false;
     let mut in_paragraph = false;
@@ -42,6 +56,7 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
     let mut in_para_props = false;
     let mut in_t = false;
     let mut in_end_para_rpr = false;
+    let mut para_spacing: Option<ParaSpacing> = None;
 
     let mut current_para = Paragraph::new();
     let mut current_run = Run::new();
@@ -73,6 +88,16 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
                     "spAutoFit" if in_body_pr => {
                         tf.auto_size = MsoAutoSize::ShapeToFitText;
                     }
+                    "prstTxWarp" | "scene3d" if in_body_pr => {
+                        let inner = read_inner_xml(&mut reader, local)
+                            .map_err(|err| PptxError::InvalidXml(format!("{local}: {err}")))?;
+                        depth -= 1;
+                        if local == "scene3d" {
+                            tf.scene_3d = parse_scene_3d_from_xml(&inner)?;
+                        } else {
+                            tf.warp = parse_text_warp(e, &inner)?;
+                        }
+                    }
                     "p" if !in_body_pr => {
                         in_paragraph = true;
                         current_para = Paragraph::new();
@@ -81,6 +106,9 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
                         in_para_props = true;
                         parse_paragraph_props(e, &mut current_para)?;
                     }
+                    "lnSpc" if in_para_props => para_spacing = Some(ParaSpacing::Line),
+                    "spcBef" if in_para_props => para_spacing = Some(ParaSpacing::Before),
+                    "spcAft" if in_para_props => para_spacing = Some(ParaSpacing::After),
                     "defRPr" if in_para_props => {
                         para_font = Font::new();
                         parse_run_props_attrs(e, &mut para_font)?;
@@ -100,6 +128,27 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
                         para_font = Font::new();
                         parse_run_props_attrs(e, &mut para_font)?;
                     }
+                    "ln" | "effectLst" | "highlight"
+                        if in_run_props || in_end_para_rpr || in_para_props =>
+                    {
+                        // Read whole, so fills inside are not taken for the
+                        // text color
+                        let inner = read_inner_xml(&mut reader, local)
+                            .map_err(|err| PptxError::InvalidXml(format!("{local}: {err}")))?;
+                        depth -= 1;
+                        let font = if in_run_props {
+                            &mut current_font
+                        } else {
+                            &mut para_font
+                        };
+                        parse_run_props_child(local, e, &inner, font)?;
+                    }
+                    "buClr" | "buBlip" if in_para_props => {
+                        let inner = read_inner_xml(&mut reader, local)
+                            .map_err(|err| PptxError::InvalidXml(format!("{local}: {err}")))?;
+                        depth -= 1;
+                        parse_bullet_prop(local, e, &inner, &mut current_para)?;
+                    }
                     "solidFill" if in_run_props => {
                         rpr_solid_fill_depth = Some(depth);
                     }
@@ -126,6 +175,7 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
                     in_run_props,
                     in_end_para_rpr,
                     in_para_props,
+                    para_spacing,
                     rpr_solid_fill_depth,
                     para_rpr_solid_fill_depth,
                     &mut current_font,
@@ -135,7 +185,21 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
             }
             Ok(Event::Text(ref t)) if in_t => {
                 if let Ok(text) = t.decode() {
-                    current_run.set_text(&text);
+                    current_run.set_text(&format!("{}{text}", current_run.text()));
+                }
+            }
+            Ok(Event::GeneralRef(ref r)) if in_t => {
+                // Entities split the text of `<a:t>` into several events
+                let resolved = match r.resolve_char_ref() {
+                    Ok(Some(ch)) => Some(ch.to_string()),
+                    _ => r
+                        .decode()
+                        .ok()
+                        .and_then(|name| resolve_predefined_entity(&name))
+                        .map(str::to_string),
+                };
+                if let Some(resolved) = resolved {
+                    current_run.set_text(&format!("{}{resolved}", current_run.text()));
                 }
             }
             Ok(Event::End(ref e)) => {
@@ -144,6 +208,7 @@ pub fn parse_text_frame_from_xml(tx_body_bytes: &[u8]) -> PptxResult<Option<Text
                     &mut in_body_pr,
                     &mut in_paragraph,
                     &mut in_para_props,
+                    &mut para_spacing,
                     &mut in_run,
                     &mut in_run_props,
                     &mut in_end_para_rpr,
@@ -183,6 +248,15 @@ fn parse_norm_autofit(e: &quick_xml::events::BytesStart<'_>, tf: &mut TextFrame)
             }
         }
     }
+    if let Some(reduction) = attr_value(e, b"lnSpcReduction")? {
+        if let Ok(val) = reduction.parse::<i64>() {
+            // i64→f64: OOXML percentages fit in 53-bit mantissa
+            #[allow(clippy::cast_precision_loss)]
+            {
+                tf.line_spacing_reduction = Some(val as f64 / 1000.0);
+            }
+        }
+    }
     Ok(())
 }
 
@@ -199,6 +273,7 @@ fn handle_empty_element(
     in_run_props: bool,
     in_end_para_rpr: bool,
     in_para_props: bool,
+    para_spacing: Option<ParaSpacing>,
     rpr_solid_fill_depth: Option<u32>,
     para_rpr_solid_fill_depth: Option<u32>,
     current_font: &mut Font,
@@ -216,9 +291,39 @@ fn handle_empty_element(
         "spAutoFit" if in_body_pr => {
             tf.auto_size = MsoAutoSize::ShapeToFitText;
         }
+        "prstTxWarp" if in_body_pr => {
+            tf.warp = parse_text_warp(e, &[])?;
+        }
         "pPr" if in_paragraph => {
             parse_paragraph_props(e, current_para)?;
         }
+        "spcPts" | "spcPct" if in_para_props => {
+            let points = local == "spcPts";
+            let val = parse_spacing_val(e)?;
+            match (para_spacing, points) {
+                // Line spacing percentages are stored as a multiplier
+                (Some(ParaSpacing::Line), false) => {
+                    current_para.line_spacing = val.map(|pct| pct / 100.0);
+                }
+                (Some(ParaSpacing::Line), true) => current_para.line_spacing_pts = val,
+                (Some(ParaSpacing::Before), false) => current_para.space_before_pct = val,
+                (Some(ParaSpacing::Before), true) => current_para.space_before = val,
+                (Some(ParaSpacing::After), false) => current_para.space_after_pct = val,
+                (Some(ParaSpacing::After), true) => current_para.space_after = val,
+                (None, _) => {}
+            }
+        }
+        "tab" if in_para_props => {
+            if let Some(tab) = parse_tab_stop(e)? {
+                current_para.tab_stops.push(tab);
+            }
+        }
+        "buClrTx" | "buClr" | "buSzTx" | "buSzPct" | "buSzPts" | "buFontTx" | "buFont"
+        | "buNone" | "buChar" | "buAutoNum"
+            if in_para_props =>
+        {
+            parse_bullet_prop(local, e, &[], current_para)?;
+        }
         "rPr" if in_run => {
             parse_run_props_attrs(e, current_font)?;
         }
@@ -250,15 +355,11 @@ fn handle_empty_element(
                 }
             }
         }
-        "latin" if in_run_props => {
-            if let Some(typeface) = attr_value(e, b"typeface")? {
-                current_font.name = Some(typeface.into_owned());
-            }
+        "latin" | "ea" | "cs" | "sym" if in_run_props => {
+            parse_typeface(local, e, current_font)?;
         }
-        "latin" if in_end_para_rpr || in_para_props => {
-            if let Some(typeface) = attr_value(e, b"typeface")? {
-                para_font.name = Some(typeface.into_owned());
-            }
+        "latin" | "ea" | "cs" | "sym" if in_end_para_rpr || in_para_props => {
+            parse_typeface(local, e, para_font)?;
         }
         "br" if in_paragraph => {
             let mut br_run = Run::new();
@@ -277,6 +378,7 @@ fn handle_end_element(
     in_body_pr: &mut bool,
     in_paragraph: &mut bool,
     in_para_props: &mut bool,
+    para_spacing: &mut Option<ParaSpacing>,
     in_run: &mut bool,
     in_run_props: &mut bool,
     in_end_para_rpr: &mut bool,
@@ -310,6 +412,9 @@ fn handle_end_element(
         "pPr" => {
             *in_para_props = false;
         }
+        "lnSpc" | "spcBef" | "spcAft" => {
+            *para_spacing = None;
+        }
         "defRPr" if *in_para_props => {
             if has_font_properties(para_font) {
                 current_para.font = Some(para_font.clone());
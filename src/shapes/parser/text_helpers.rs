@@ -1,21 +1,27 @@
 //! Helper functions for text frame parsing.
 
-use quick_xml::events::BytesStart;
+use std::borrow::Cow;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
 
 use crate::enums::text::{
+    MsoAutoNumberScheme, MsoBaselineAlignment, MsoTabStopType, MsoTextCaps, MsoTextOrientation,
     MsoTextUnderlineType, MsoVerticalAnchor, PpParagraphAlignment, TextDirection,
 };
 use crate::error::{PptxError, PptxResult};
 use crate::text::font::Font;
-use crate::text::{Paragraph, TextFrame};
+use crate::text::{BulletFormat, Paragraph, TabStop, TextFrame, TextWarp};
 use crate::units::Emu;
 
-use crate::xml_util::attr_value;
+use crate::xml_util::{attr_value, emu_attr, local_name_str};
+
+use super::{parse_color_from_xml, parse_effects_from_xml, parse_line_from_xml};
 
 pub(super) fn parse_body_pr_attrs(e: &BytesStart<'_>, tf: &mut TextFrame) -> PptxResult<()> {
     // wrap
     if let Some(wrap) = attr_value(e, b"wrap")? {
-        tf.word_wrap = wrap != "none";
+        tf.set_word_wrap(Some(wrap != "none"));
     }
     // anchor (vertical alignment)
     if let Some(anchor) = attr_value(e, b"anchor")? {
@@ -42,6 +48,25 @@ pub(super) fn parse_body_pr_attrs(e: &BytesStart<'_>, tf: &mut TextFrame) -> Ppt
             .parse::<i64>()
             .map_err(|_| PptxError::InvalidXml(format!("invalid numeric value: {b}")))?));
     }
+    if let Some(vert) = attr_value(e, b"vert")? {
+        tf.orientation = MsoTextOrientation::from_xml_str(&vert);
+    }
+    // columns
+    if let Some(n) = attr_value(e, b"numCol")? {
+        tf.columns = Some(
+            n.parse::<u32>()
+                .map_err(|_| PptxError::InvalidXml(format!("invalid numeric value: {n}")))?,
+        );
+    }
+    if let Some(spacing) = emu_attr(e, b"spcCol")? {
+        tf.column_spacing = Some(spacing);
+    }
+    if let Some(center) = attr_value(e, b"anchorCtr")? {
+        tf.anchor_center = Some(center == "1" || center == "true");
+    }
+    if let Some(upright) = attr_value(e, b"upright")? {
+        tf.upright = Some(upright == "1" || upright == "true");
+    }
     // rotation
     if let Some(rot) = attr_value(e, b"rot")? {
         if let Ok(val) = rot.parse::<i64>() {
@@ -67,9 +92,84 @@ pub(super) fn parse_paragraph_props(e: &BytesStart<'_>, para: &mut Paragraph) ->
     if let Some(rtl) = attr_value(e, b"rtl")? {
         para.text_direction = TextDirection::from_xml_attr(&rtl);
     }
+    if let Some(margin) = emu_attr(e, b"marL")? {
+        para.margin_left = Some(margin);
+    }
+    if let Some(indent) = emu_attr(e, b"indent")? {
+        para.indent = Some(indent);
+    }
+    if let Some(size) = emu_attr(e, b"defTabSz")? {
+        para.default_tab_size = Some(size);
+    }
+    if let Some(brk) = attr_value(e, b"eaLnBrk")? {
+        para.east_asian_line_break = Some(brk == "1" || brk == "true");
+    }
+    if let Some(brk) = attr_value(e, b"latinLnBrk")? {
+        para.latin_line_break = Some(brk == "1" || brk == "true");
+    }
+    if let Some(algn) = attr_value(e, b"fontAlgn")? {
+        para.font_alignment = MsoBaselineAlignment::from_xml_str(&algn);
+    }
     Ok(())
 }
 
+/// Parse a `<a:prstTxWarp>` element from its start tag and inner XML.
+pub(super) fn parse_text_warp(e: &BytesStart<'_>, inner: &[u8]) -> PptxResult<Option<TextWarp>> {
+    let Some(preset) = attr_value(e, b"prst")? else {
+        return Ok(None);
+    };
+    let mut warp = TextWarp::new(&preset);
+    let mut reader = Reader::from_reader(inner);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Empty(ref gd) | Event::Start(ref gd))
+                if local_name_str(gd.name().as_ref()) == "gd" =>
+            {
+                let value = attr_value(gd, b"fmla")?
+                    .and_then(|f| f.strip_prefix("val ").map(str::to_string))
+                    .and_then(|v| v.trim().parse::<f64>().ok());
+                warp.adjustments
+                    .push(value.map_or(f64::NAN, |v| v / 100_000.0));
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    Ok(Some(warp))
+}
+
+/// Parse an `<a:tab>` element of a `<a:tabLst>`.
+pub(super) fn parse_tab_stop(e: &BytesStart<'_>) -> PptxResult<Option<TabStop>> {
+    let Some(position) = emu_attr(e, b"pos")? else {
+        return Ok(None);
+    };
+    let alignment = attr_value(e, b"algn")?
+        .and_then(|algn| MsoTabStopType::from_xml_str(&algn))
+        .unwrap_or(MsoTabStopType::Left);
+    Ok(Some(TabStop::new(position, alignment)))
+}
+
+/// Parse an `<a:spcPts>` or `<a:spcPct>` value: points for the former,
+/// percent for the latter.
+pub(super) fn parse_spacing_val(e: &BytesStart<'_>) -> PptxResult<Option<f64>> {
+    let Some(val) = attr_value(e, b"val")? else {
+        return Ok(None);
+    };
+    let val = val
+        .parse::<i64>()
+        .map_err(|_| PptxError::InvalidXml(format!("invalid numeric value: {val}")))?;
+    // i64→f64: OOXML spacing values fit in 53-bit mantissa
+    #[allow(clippy::cast_precision_loss)]
+    let val = val as f64;
+    Ok(Some(if e.local_name().as_ref() == b"spcPts" {
+        val / 100.0
+    } else {
+        val / 1000.0
+    }))
+}
+
 pub(super) fn parse_run_props_attrs(e: &BytesStart<'_>, font: &mut Font) -> PptxResult<()> {
     // bold
     if let Some(b) = attr_value(e, b"b")? {
@@ -96,7 +196,31 @@ pub(super) fn parse_run_props_attrs(e: &BytesStart<'_>, font: &mut Font) -> Pptx
     }
     // strikethrough
     if let Some(strike) = attr_value(e, b"strike")? {
-        font.strikethrough = Some(strike == "sngStrike");
+        font.strikethrough = Some(strike != "noStrike");
+        font.double_strikethrough = Some(strike == "dblStrike");
+    }
+    // character spacing and kerning (in hundredths of a point)
+    if let Some(spc) = attr_value(e, b"spc")? {
+        if let Ok(val) = spc.parse::<i64>() {
+            // i64→f64: OOXML spacing values fit in 53-bit mantissa
+            #[allow(clippy::cast_precision_loss)]
+            {
+                font.character_spacing = Some(val as f64 / 100.0);
+            }
+        }
+    }
+    if let Some(kern) = attr_value(e, b"kern")? {
+        if let Ok(val) = kern.parse::<i64>() {
+            // i64→f64: OOXML font size values fit in 53-bit mantissa
+            #[allow(clippy::cast_precision_loss)]
+            {
+                font.kerning = Some(val as f64 / 100.0);
+            }
+        }
+    }
+    // capitalization
+    if let Some(cap) = attr_value(e, b"cap")? {
+        font.caps = MsoTextCaps::from_xml_str(&cap);
     }
     // language
     if let Some(lang) = attr_value(e, b"lang")? {
@@ -110,6 +234,15 @@ pub(super) fn parse_run_props_attrs(e: &BytesStart<'_>, font: &mut Font) -> Pptx
                 std::cmp::Ordering::Less => font.subscript = Some(true),
                 std::cmp::Ordering::Equal => {}
             }
+            // Keep offsets other than the defaults superscript and
+            // subscript are written with
+            if !matches!(val, 0 | 30_000 | -25_000) {
+                // i64→f64: OOXML percentages fit in 53-bit mantissa
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    font.baseline = Some(val as f64 / 1000.0);
+                }
+            }
         }
     }
     Ok(())
@@ -128,4 +261,129 @@ pub(super) const fn has_font_properties(font: &Font) -> bool {
         || font.language_id.is_some()
         || font.fill.is_some()
         || font.hyperlink.is_some()
+        || font.character_spacing.is_some()
+        || font.kerning.is_some()
+        || font.caps.is_some()
+        || font.double_strikethrough.is_some()
+        || font.baseline.is_some()
+        || font.highlight.is_some()
+        || font.outline.is_some()
+        || font.shadow.is_some()
+        || font.glow.is_some()
+        || font.east_asian_name.is_some()
+        || font.complex_script_name.is_some()
+        || font.symbol_name.is_some()
+}
+
+/// Parse a child of `<a:rPr>` (or `<a:defRPr>`, `<a:endParaRPr>`) that has
+/// children of its own, from its attributes and inner XML.
+pub(super) fn parse_run_props_child(
+    local: &str,
+    e: &BytesStart<'_>,
+    inner: &[u8],
+    font: &mut Font,
+) -> PptxResult<()> {
+    match local {
+        "ln" => {
+            let mut outline = parse_line_from_xml(inner)?.unwrap_or_default();
+            if let Some(width) = attr_value(e, b"w")?.and_then(|w| w.parse::<i64>().ok()) {
+                outline.width = Some(Emu(width));
+            }
+            font.outline = Some(outline);
+        }
+        "effectLst" => {
+            (font.shadow, font.glow) = parse_effects_from_xml(inner)?;
+        }
+        "highlight" => {
+            font.highlight = parse_color_from_xml(inner)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Set the bullet property of `para` given by a `<a:bu*>` element of its
+/// `<a:pPr>`; `inner` holds the children of `<a:buClr>` and `<a:buBlip>`.
+pub(super) fn parse_bullet_prop(
+    local: &str,
+    e: &BytesStart<'_>,
+    inner: &[u8],
+    para: &mut Paragraph,
+) -> PptxResult<()> {
+    // Sizes are thousandths of a percent or hundredths of a point
+    #[allow(clippy::cast_precision_loss)]
+    let val = |scale: f64| -> PptxResult<Option<f64>> {
+        Ok(attr_value(e, b"val")?
+            .and_then(|v| v.parse::<i64>().ok())
+            .map(|v| v as f64 / scale))
+    };
+    match local {
+        "buClrTx" => para.bullet_color_follows_text = true,
+        "buClr" => para.bullet_color = parse_color_from_xml(inner)?,
+        "buSzTx" => para.bullet_size_follows_text = true,
+        "buSzPct" => para.bullet_size_pct = val(1000.0)?,
+        "buSzPts" => para.bullet_size_pts = val(100.0)?,
+        "buFontTx" => para.bullet_font_follows_text = true,
+        "buFont" => para.bullet_font = attr_value(e, b"typeface")?.map(Cow::into_owned),
+        "buNone" => para.bullet = Some(BulletFormat::None),
+        "buChar" => {
+            if let Some(ch) = attr_value(e, b"char")?.and_then(|c| c.chars().next()) {
+                para.bullet = Some(BulletFormat::Character(ch));
+            }
+        }
+        "buAutoNum" => {
+            let scheme = attr_value(e, b"type")?;
+            if let Some(scheme) = scheme
+                .as_deref()
+                .and_then(MsoAutoNumberScheme::from_xml_str)
+            {
+                let start_at = attr_value(e, b"startAt")?.and_then(|v| v.parse().ok());
+                para.bullet = Some(BulletFormat::AutoNumbered { scheme, start_at });
+            }
+        }
+        "buBlip" => {
+            if let Some(r_id) = parse_blip_embed(inner)? {
+                para.bullet = Some(BulletFormat::Picture(r_id));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The `r:embed` relationship of the `<a:blip>` among a `<a:buBlip>`'s
+/// children.
+fn parse_blip_embed(xml: &[u8]) -> PptxResult<Option<String>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Empty(ref e) | Event::Start(ref e))
+                if local_name_str(e.name().as_ref()) == "blip" =>
+            {
+                return Ok(attr_value(e, b"r:embed")?.map(Cow::into_owned));
+            }
+            Ok(Event::Eof) => return Ok(None),
+            Err(e) => return Err(PptxError::InvalidXml(format!("buBlip XML error: {e}"))),
+            _ => {}
+        }
+    }
+}
+
+/// Set the typeface of `font` named by a `<a:latin>`, `<a:ea>`, `<a:cs>` or
+/// `<a:sym>` element.
+pub(super) fn parse_typeface(local: &str, e: &BytesStart<'_>, font: &mut Font) -> PptxResult<()> {
+    let Some(typeface) = attr_value(e, b"typeface")? else {
+        return Ok(());
+    };
+    let typeface = Some(typeface.into_owned());
+    match local {
+        "latin" => font.name = typeface,
+        "ea" => font.east_asian_name = typeface,
+        "cs" => font.complex_script_name = typeface,
+        "sym" => font.symbol_name = typeface,
+        _ => {}
+    }
+    Ok(())
 }
@@ -51,6 +51,16 @@ impl Shape {
         }
     }
 
+    /// Get a mutable reference to the inner `GraphicFrame`, if this is one.
+    #[inline]
+    #[must_use]
+    pub fn as_graphic_frame_mut(&mut self) -> Option<&mut GraphicFrame> {
+        match self {
+            Self::GraphicFrame(s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// Get a reference to the inner `GroupShape`, if this is one.
     #[inline]
     #[must_use]
@@ -0,0 +1,207 @@
+//! Removing, replacing and re-stacking individual shapes in slide XML.
+//!
+//! Like the `add_*` functions, these operate directly on the slide XML and
+//! return the updated bytes. Only the affected elements move; everything
+//! else in the slide is kept byte-for-byte.
+
+use std::ops::Range;
+
+use crate::error::{PptxError, PptxResult};
+use crate::shapes::Shape;
+use crate::units::ShapeId;
+use crate::xml_util::splice::splice;
+use crate::xml_util::WriteXml;
+
+use super::parse::parse_shapes_in;
+use super::write_back::scan_container;
+use super::ShapeTree;
+
+/// Where a shape element sits within its parent container.
+struct ShapeLocation {
+    /// Absolute byte ranges of every shape in the parent container
+    /// (`<p:spTree>` or a `<p:grpSp>`), in z-order.
+    siblings: Vec<Range<usize>>,
+    /// Position of the located shape within `siblings`.
+    index: usize,
+}
+
+impl ShapeLocation {
+    fn range(&self) -> Range<usize> {
+        self.siblings[self.index].clone()
+    }
+}
+
+/// Search `container` in `xml` (and, recursively, any group shapes in it)
+/// for the element of shape `shape_id`. `base` is the offset of `xml`
+/// within the full slide XML.
+fn locate_in(
+    xml: &str,
+    container: &str,
+    base: usize,
+    shape_id: ShapeId,
+) -> PptxResult<Option<ShapeLocation>> {
+    let Some(layout) = scan_container(xml, container)? else {
+        return Ok(None);
+    };
+    let slots = layout.shape_slots();
+    let shapes = parse_shapes_in(xml.as_bytes(), container)?;
+    for (index, (slot, shape)) in slots.iter().zip(&shapes).enumerate() {
+        if shape.shape_id() == shape_id {
+            return Ok(Some(ShapeLocation {
+                siblings: slots.iter().map(|r| r.start + base..r.end + base).collect(),
+                index,
+            }));
+        }
+        if matches!(shape, Shape::GroupShape(_)) {
+            let found = locate_in(&xml[slot.clone()], "grpSp", base + slot.start, shape_id)?;
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn locate(xml: &str, shape_id: ShapeId) -> PptxResult<ShapeLocation> {
+    locate_in(xml, "spTree", 0, shape_id)?.ok_or_else(|| PptxError::InvalidValue {
+        field: "shape_id",
+        value: shape_id.to_string(),
+        expected: "the ID of a shape on the slide",
+    })
+}
+
+/// Move the shape at `loc.index` to position `to` among its siblings.
+fn restack(xml: &str, loc: &ShapeLocation, to: usize) -> Vec<u8> {
+    let mut order: Vec<usize> = (0..loc.siblings.len()).collect();
+    let moved = order.remove(loc.index);
+    order.insert(to, moved);
+
+    let mut out = String::with_capacity(xml.len());
+    let mut cursor = 0;
+    for (slot, &source) in loc.siblings.iter().zip(&order) {
+        out.push_str(&xml[cursor..slot.start]);
+        out.push_str(&xml[loc.siblings[source].clone()]);
+        cursor = slot.end;
+    }
+    out.push_str(&xml[cursor..]);
+    out.into_bytes()
+}
+
+impl ShapeTree {
+    /// Return the XML element of the shape with the given ID.
+    ///
+    /// Shapes nested inside groups are found as well.
+    pub(crate) fn shape_xml(slide_xml: &[u8], shape_id: ShapeId) -> PptxResult<String> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        Ok(xml[locate(xml, shape_id)?.range()].to_string())
+    }
+
+    /// Remove the shape with the given ID from the slide XML.
+    ///
+    /// Shapes nested inside a group can be removed too; removing a group
+    /// removes all of its children. Relationships used by the shape are not
+    /// touched here, see [`Presentation::remove_shape`](crate::Presentation::remove_shape)
+    /// for a version that also cleans those up.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be parsed or contains no
+    /// shape with this ID.
+    pub fn remove_shape(slide_xml: &[u8], shape_id: ShapeId) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        let range = locate(xml, shape_id)?.range();
+        Ok(splice(xml, range, "").into_bytes())
+    }
+
+    /// Replace the shape with the given ID by `shape`, keeping its place in
+    /// the z-order.
+    ///
+    /// `shape` is written as-is, including its own shape ID; reuse the old
+    /// ID to keep connections attached to the replaced shape valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be parsed, contains no shape
+    /// with this ID, or `shape` is a graphic frame (which cannot be
+    /// serialized from the model alone).
+    pub fn replace_shape(
+        slide_xml: &[u8],
+        shape_id: ShapeId,
+        shape: &Shape,
+    ) -> PptxResult<Vec<u8>> {
+        let replacement = shape.to_xml_string();
+        if replacement.is_empty() {
+            return Err(PptxError::InvalidValue {
+                field: "shape",
+                value: shape.to_string(),
+                expected: "a shape that can be serialized (not a graphic frame)",
+            });
+        }
+        Self::replace_shape_xml(slide_xml, shape_id, &replacement)
+    }
+
+    /// Replace the element of the shape with the given ID by `replacement`,
+    /// which must be a complete shape element.
+    pub(crate) fn replace_shape_xml(
+        slide_xml: &[u8],
+        shape_id: ShapeId,
+        replacement: &str,
+    ) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        let range = locate(xml, shape_id)?.range();
+        Ok(splice(xml, range, replacement).into_bytes())
+    }
+
+    /// Move the shape with the given ID to the top of the z-order within
+    /// its container (the slide, or the group it belongs to).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be parsed or contains no
+    /// shape with this ID.
+    pub fn bring_to_front(slide_xml: &[u8], shape_id: ShapeId) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        let loc = locate(xml, shape_id)?;
+        Ok(restack(xml, &loc, loc.siblings.len() - 1))
+    }
+
+    /// Move the shape with the given ID to the bottom of the z-order within
+    /// its container.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be parsed or contains no
+    /// shape with this ID.
+    pub fn send_to_back(slide_xml: &[u8], shape_id: ShapeId) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        let loc = locate(xml, shape_id)?;
+        Ok(restack(xml, &loc, 0))
+    }
+
+    /// Move the shape with the given ID one step up in the z-order within
+    /// its container. A shape that is already on top stays where it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be parsed or contains no
+    /// shape with this ID.
+    pub fn move_forward(slide_xml: &[u8], shape_id: ShapeId) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        let loc = locate(xml, shape_id)?;
+        let to = (loc.index + 1).min(loc.siblings.len() - 1);
+        Ok(restack(xml, &loc, to))
+    }
+
+    /// Move the shape with the given ID one step down in the z-order within
+    /// its container. A shape that is already at the bottom stays where it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML cannot be parsed or contains no
+    /// shape with this ID.
+    pub fn move_backward(slide_xml: &[u8], shape_id: ShapeId) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        let loc = locate(xml, shape_id)?;
+        Ok(restack(xml, &loc, loc.index.saturating_sub(1)))
+    }
+}
@@ -1,5 +1,8 @@
+mod edit;
 mod parse;
 mod parse_accum;
+mod table_patch;
+mod write_back;
 mod xml_capture;
 mod xml_gen;
 
@@ -8,12 +11,18 @@ mod tests;
 #[cfg(test)]
 mod tests_add_api;
 #[cfg(test)]
+mod tests_edit;
+#[cfg(test)]
 mod tests_enhanced;
+#[cfg(test)]
+mod tests_write_back;
 
 use crate::enums::shapes::MsoAutoShapeType;
 use crate::enums::shapes::MsoConnectorType;
 use crate::error::{PptxError, PptxResult};
+use crate::shapes::placeholder::PlaceholderFormat;
 use crate::shapes::Shape;
+use crate::text::TextFrame;
 use crate::units::{Emu, ShapeId};
 
 pub(crate) use xml_gen::shape_name_for_prst;
@@ -147,6 +156,32 @@ impl ShapeTree {
         Self::insert_shape_xml(slide_xml, &xml)
     }
 
+    /// Add a placeholder holding `text` to the slide XML.
+    ///
+    /// `placeholder` is usually taken from the matching placeholder of the
+    /// slide's layout, which the new shape inherits its position, size and
+    /// formatting from unless `bounds` are given. Auto-assigns `shape_id`;
+    /// the name is `name` followed by a number. Returns the updated slide
+    /// XML bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PptxError` if the slide XML cannot be parsed or modified.
+    pub fn add_placeholder(
+        slide_xml: &[u8],
+        placeholder: &PlaceholderFormat,
+        name: &str,
+        bounds: Option<(Emu, Emu, Emu, Emu)>,
+        text: &TextFrame,
+    ) -> PptxResult<Vec<u8>> {
+        let tree = Self::from_slide_xml(slide_xml)?;
+        let shape_id = ShapeId(tree.max_shape_id().0 + 1);
+        let name = format!("{name} {}", shape_id.0);
+
+        let xml = Self::new_placeholder_xml(shape_id, &name, placeholder, bounds, text);
+        Self::insert_shape_xml(slide_xml, &xml)
+    }
+
     /// Add a picture shape to the slide XML.
     ///
     /// `image_r_id` is the relationship ID linking the slide to the image part.
@@ -5,10 +5,12 @@ use quick_xml::Reader;
 
 use crate::enums::shapes::{PlaceholderOrientation, PlaceholderSize, PpPlaceholderType};
 use crate::error::{PptxError, PptxResult};
+use crate::shapes::graphfrm::graphic_data_uri;
 use crate::shapes::placeholder::PlaceholderFormat;
 use crate::shapes::Shape;
+use crate::table::Table;
 use crate::units::{PlaceholderIndex, ShapeId};
-use crate::xml_util::{attr_value, attr_value_ns, local_name_owned};
+use crate::xml_util::{attr_value, attr_value_ns, local_name_owned, offset};
 
 use super::parse_accum::{ShapeAccum, ShapeKind};
 use super::xml_capture::{CaptureTarget, XmlCapture};
@@ -59,8 +61,12 @@ fn process_start_element(
             accum.top = parse_i64_attr(e, b"y")?;
         }
         "ext" => {
-            accum.width = parse_i64_attr(e, b"cx")?;
-            accum.height = parse_i64_attr(e, b"cy")?;
+            // `<a:ext uri="...">` entries of an extLst share the local name;
+            // only the transform extent carries `cx`/`cy`.
+            if attr_value(e, b"cx")?.is_some() {
+                accum.width = parse_i64_attr(e, b"cx")?;
+                accum.height = parse_i64_attr(e, b"cy")?;
+            }
         }
         "xfrm" => {
             if let Some(rot_str) = attr_value(e, b"rot")? {
@@ -85,6 +91,16 @@ fn process_start_element(
             // r:embed attribute (namespaced)
             accum.image_r_id = attr_value_ns(e, b"embed")?.map(std::borrow::Cow::into_owned);
         }
+        "srcRect" => {
+            // Crop insets are stored in 1/1000ths of a percent
+            accum.crop = [b"l", b"t", b"r", b"b"].map(|key| {
+                attr_value(e, key)
+                    .ok()
+                    .flatten()
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map_or(0.0, |v| v / 100_000.0)
+            });
+        }
         "graphicData" => {
             accum.graphic_data_uri = attr_value(e, b"uri")?.map(std::borrow::Cow::into_owned);
         }
@@ -92,6 +108,10 @@ fn process_start_element(
             // SmartArt diagram: <dgm:relIds r:dm="rIdN" .../>
             accum.smartart_r_id = attr_value_ns(e, b"dm")?.map(std::borrow::Cow::into_owned);
         }
+        "chart" => {
+            // Chart reference: <c:chart r:id="rIdN"/>
+            accum.chart_r_id = attr_value_ns(e, b"id")?.map(std::borrow::Cow::into_owned);
+        }
         _ => {}
     }
     Ok(())
@@ -117,13 +137,37 @@ fn parse_i64_attr(e: &BytesStart<'_>, key: &[u8]) -> PptxResult<i64> {
         .unwrap_or(0))
 }
 
+/// Map the local name of a shape-tree child to the kind of shape it holds.
+pub(super) fn shape_kind_for(local: &str) -> Option<ShapeKind> {
+    match local {
+        "sp" => Some(ShapeKind::Sp),
+        "pic" => Some(ShapeKind::Pic),
+        "graphicFrame" => Some(ShapeKind::GraphicFrame),
+        "cxnSp" => Some(ShapeKind::CxnSp),
+        "grpSp" => Some(ShapeKind::GrpSp),
+        _ => None,
+    }
+}
+
 /// Parse shapes from slide XML (the full `<p:sld>` or similar element).
 ///
 /// Extracts all shape elements from the `<p:spTree>` within `<p:cSld>`.
-#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>> {
+    parse_shapes_in(xml, "spTree")
+}
+
+/// Parse the shapes that are direct children of the first element whose
+/// local name is `container` (`spTree` for a slide, `grpSp` for a group).
+///
+/// Group shapes are parsed recursively: the bytes of each `<p:grpSp>` are
+/// handed back to this function so that nested children end up in
+/// [`GroupShape::shapes`](crate::shapes::group::GroupShape) rather than
+/// leaking their properties into the group itself.
+#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
+pub(super) fn parse_shapes_in(xml: &[u8], container: &str) -> PptxResult<Vec<Shape>> {
     let mut reader = Reader::from_reader(xml);
-    reader.config_mut().trim_text(true);
+    // Leading and trailing spaces of `<a:t>` text are significant
+    reader.config_mut().trim_text(false);
 
     let mut shapes = Vec::new();
     let mut buf = Vec::new();
@@ -140,14 +184,29 @@ pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>>
     // XML capture state for sub-elements (spPr, txBody, ln)
     let mut capture: Option<XmlCapture> = None;
 
+    // Byte offset where the current top-level group shape starts, and the
+    // stack depth of a nested child shape whose events must be skipped
+    // (they are parsed separately when the group closes).
+    let mut group_start: Option<usize> = None;
+    let mut nested_depth: Option<usize> = None;
+    // Byte offset where the current top-level graphic frame starts; its
+    // table (if any) is parsed from these bytes when the frame closes.
+    let mut frame_start: Option<usize> = None;
+
     loop {
         buf.clear();
+        let event_start = offset(&reader);
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) => {
                 let qname = e.name();
                 let local = local_name_owned(qname.as_ref());
                 let stack_depth = element_stack.len();
 
+                if nested_depth.is_some() {
+                    element_stack.push(ElementCtx { local });
+                    continue;
+                }
+
                 // If we're capturing, record this event AND still process
                 if let Some(ref mut cap) = capture {
                     cap.depth += 1;
@@ -159,7 +218,7 @@ pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>>
                 } else {
                     match &state {
                         ParseState::Seeking => {
-                            if local == "spTree" {
+                            if local == container {
                                 sp_tree_depth = Some(stack_depth);
                                 state = ParseState::InSpTree;
                             }
@@ -167,21 +226,30 @@ pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>>
                         ParseState::InSpTree => {
                             // Direct child of spTree
                             if Some(stack_depth) == sp_tree_depth.map(|d| d + 1) {
-                                let kind = match local.as_str() {
-                                    "sp" => Some(ShapeKind::Sp),
-                                    "pic" => Some(ShapeKind::Pic),
-                                    "graphicFrame" => Some(ShapeKind::GraphicFrame),
-                                    "cxnSp" => Some(ShapeKind::CxnSp),
-                                    "grpSp" => Some(ShapeKind::GrpSp),
-                                    _ => None,
-                                };
-                                if let Some(k) = kind {
+                                if let Some(k) = shape_kind_for(&local) {
+                                    match k {
+                                        ShapeKind::GrpSp => group_start = Some(event_start),
+                                        ShapeKind::GraphicFrame => {
+                                            frame_start = Some(event_start);
+                                        }
+                                        _ => {}
+                                    }
                                     current_shape = Some(ShapeAccum::new(k));
                                     state = ParseState::InShape;
                                 }
                             }
                         }
                         ParseState::InShape => {
+                            // A child shape of a group: skip it here, it is
+                            // parsed from the group's bytes when the group closes.
+                            if group_start.is_some()
+                                && Some(stack_depth) == sp_tree_depth.map(|d| d + 2)
+                                && shape_kind_for(&local).is_some()
+                            {
+                                nested_depth = Some(stack_depth);
+                                element_stack.push(ElementCtx { local });
+                                continue;
+                            }
                             if let Some(ref mut accum) = current_shape {
                                 process_start_element(&local, e, accum)?;
                             }
@@ -208,6 +276,9 @@ pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>>
                 element_stack.push(ElementCtx { local });
             }
             Ok(Event::Empty(ref e)) => {
+                if nested_depth.is_some() {
+                    continue;
+                }
                 let qname = e.name();
                 let local = local_name_owned(qname.as_ref());
 
@@ -224,11 +295,29 @@ pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>>
                 }
             }
             Ok(Event::Text(ref t)) => {
+                if nested_depth.is_some() {
+                    continue;
+                }
                 if let Some(ref mut cap) = capture {
                     cap.push_text(t.as_ref());
                 }
             }
+            Ok(Event::GeneralRef(ref r)) => {
+                if nested_depth.is_some() {
+                    continue;
+                }
+                if let Some(ref mut cap) = capture {
+                    cap.push_general_ref(r.as_ref());
+                }
+            }
             Ok(Event::End(ref e)) => {
+                if let Some(depth) = nested_depth {
+                    element_stack.pop();
+                    if element_stack.len() == depth {
+                        nested_depth = None;
+                    }
+                    continue;
+                }
                 if let Some(ref mut cap) = capture {
                     cap.depth -= 1;
                     if cap.depth == 0 {
@@ -262,7 +351,23 @@ pub(super) fn parse_shapes_from_slide_xml(xml: &[u8]) -> PptxResult<Vec<Shape>>
                         if let Some(sp_depth) = sp_tree_depth {
                             if element_stack.len() == sp_depth + 1 {
                                 // We've closed the shape element
-                                if let Some(accum) = current_shape.take() {
+                                if let Some(mut accum) = current_shape.take() {
+                                    let end = usize::try_from(reader.buffer_position())
+                                        .unwrap_or(xml.len())
+                                        .min(xml.len());
+                                    if let Some(start) = group_start.take() {
+                                        accum.children =
+                                            parse_shapes_in(&xml[start..end], "grpSp")?;
+                                    }
+                                    if let Some(start) = frame_start.take() {
+                                        if accum.graphic_data_uri.as_deref()
+                                            == Some(graphic_data_uri::TABLE)
+                                        {
+                                            // A malformed table leaves the frame without
+                                            // table content rather than failing the slide.
+                                            accum.table = Table::from_xml(&xml[start..end]).ok();
+                                        }
+                                    }
                                     shapes.push(accum.into_shape());
                                 }
                                 state = ParseState::InSpTree;
@@ -6,6 +6,7 @@ use crate::shapes::group::GroupShape;
 use crate::shapes::picture::Picture;
 use crate::shapes::placeholder::PlaceholderFormat;
 use crate::shapes::Shape;
+use crate::table::Table;
 use crate::units::{Emu, ShapeId};
 
 #[derive(Debug, Clone, Copy)]
@@ -35,9 +36,13 @@ pub(super) struct ShapeAccum {
     // pic-specific
     pub(super) image_r_id: Option<String>,
     pub(super) description: Option<String>,
+    // crop insets as fractions, in l/t/r/b order
+    pub(super) crop: [f64; 4],
     // graphicFrame-specific
     pub(super) graphic_data_uri: Option<String>,
     pub(super) smartart_r_id: Option<String>,
+    pub(super) chart_r_id: Option<String>,
+    pub(super) table: Option<Table>,
     // connector-specific
     pub(super) flip_h: bool,
     pub(super) flip_v: bool,
@@ -46,6 +51,8 @@ pub(super) struct ShapeAccum {
     // Raw captured XML for enhanced parsing
     pub(super) sp_pr_xml: Option<Vec<u8>>,
     pub(super) tx_body_xml_bytes: Option<Vec<u8>>,
+    // group-specific: child shapes parsed from the group's own bytes
+    pub(super) children: Vec<Shape>,
 }
 
 impl ShapeAccum {
@@ -64,13 +71,17 @@ impl ShapeAccum {
             has_tx_body: false,
             image_r_id: None,
             description: None,
+            crop: [0.0; 4],
             graphic_data_uri: None,
             smartart_r_id: None,
+            chart_r_id: None,
+            table: None,
             flip_h: false,
             flip_v: false,
             placeholder: None,
             sp_pr_xml: None,
             tx_body_xml_bytes: None,
+            children: Vec::new(),
         }
     }
 
@@ -136,10 +147,10 @@ impl ShapeAccum {
                     image_r_id: self.image_r_id,
                     description: self.description,
                     placeholder: self.placeholder,
-                    crop_left: 0.0,
-                    crop_right: 0.0,
-                    crop_top: 0.0,
-                    crop_bottom: 0.0,
+                    crop_left: self.crop[0],
+                    crop_right: self.crop[2],
+                    crop_top: self.crop[1],
+                    crop_bottom: self.crop[3],
                     line,
                     click_action: None,
                     hover_action: None,
@@ -169,6 +180,8 @@ impl ShapeAccum {
                     graphic_data_uri: self.graphic_data_uri,
                     placeholder: self.placeholder,
                     smartart_r_id: self.smartart_r_id,
+                    chart_r_id: self.chart_r_id,
+                    table: self.table,
                 }))
             }
             ShapeKind::CxnSp => {
@@ -204,7 +217,7 @@ impl ShapeAccum {
                 width: Emu(self.width),
                 height: Emu(self.height),
                 rotation: self.rotation,
-                shapes: Vec::new(),
+                shapes: self.children,
             })),
         }
     }
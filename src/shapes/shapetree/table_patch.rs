@@ -0,0 +1,211 @@
+//! Patching the `<a:tbl>` of a table graphic frame in place.
+//!
+//! When the grid keeps its shape, only the rows, cells and table
+//! properties that actually changed are rewritten, so cell-level markup the
+//! model does not cover (text list styles, extension lists) survives. A
+//! change in the number of rows or columns re-serializes the whole table.
+
+use std::ops::Range;
+
+use crate::table::{Cell, Row, Table};
+use crate::xml_util::splice::{
+    find_element, find_element_in, find_start_tag, inner_range, set_attr, splice,
+};
+use crate::xml_util::xml_escape;
+
+use super::write_back::{expand_empty, patch_text_body};
+
+/// Ranges of the successive `qname` elements found within `within`.
+fn elements_in(xml: &str, qname: &str, within: Range<usize>) -> Vec<Range<usize>> {
+    let mut found = Vec::new();
+    let mut pos = within.start;
+    while let Some(r) = find_element_in(xml, qname, pos..within.end) {
+        pos = r.end;
+        found.push(r);
+    }
+    found
+}
+
+fn flag(on: bool) -> Option<&'static str> {
+    on.then_some("1")
+}
+
+/// Bring the `<a:tbl>` inside `xml` in line with `new`.
+pub(super) fn patch_table(xml: &str, old: &Table, new: &Table) -> String {
+    let Some(tbl) = find_element(xml, "a:tbl", 0) else {
+        return xml.to_string();
+    };
+    let rows = elements_in(xml, "a:tr", inner_range(xml, tbl.clone()));
+    let same_grid = old.columns.len() == new.columns.len()
+        && rows.len() == old.rows.len()
+        && old.rows.len() == new.rows.len()
+        && old
+            .rows
+            .iter()
+            .zip(&new.rows)
+            .all(|(o, n)| o.cells.len() == n.cells.len());
+    if !same_grid {
+        return splice(xml, tbl, &new.to_xml_string());
+    }
+
+    // Rows follow the table properties and grid, so patching them back to
+    // front leaves the offsets of everything before them valid.
+    let mut out = xml.to_string();
+    for (i, range) in rows.into_iter().enumerate().rev() {
+        if old.rows[i] != new.rows[i] {
+            let row = patch_row(&out[range.clone()], &old.rows[i], &new.rows[i]);
+            out = splice(&out, range, &row);
+        }
+    }
+
+    if old.columns != new.columns {
+        if let Some(grid) = find_element(&out, "a:tblGrid", 0) {
+            let cols = elements_in(&out, "a:gridCol", inner_range(&out, grid));
+            for (col, range) in new.columns.iter().zip(cols).rev() {
+                let tag = find_start_tag(&out, "a:gridCol", range.start).unwrap_or(range);
+                let new_tag = set_attr(&out[tag.clone()], "w", Some(&col.width.0.to_string()));
+                out = splice(&out, tag, &new_tag);
+            }
+        }
+    }
+
+    patch_table_props(&out, old, new)
+}
+
+/// Update the banding flags and style ID held by `<a:tblPr>`.
+fn patch_table_props(xml: &str, old: &Table, new: &Table) -> String {
+    let flags = |t: &Table| {
+        [
+            t.first_row,
+            t.first_col,
+            t.last_row,
+            t.last_col,
+            t.horz_banding,
+            t.vert_banding,
+        ]
+    };
+    if flags(old) == flags(new) && old.table_style_id == new.table_style_id {
+        return xml.to_string();
+    }
+    let Some(tbl) = find_element(xml, "a:tbl", 0) else {
+        return xml.to_string();
+    };
+    let tbl_inner = inner_range(xml, tbl);
+    let Some(tbl_pr) = find_element_in(xml, "a:tblPr", tbl_inner.clone()) else {
+        // No properties element yet: insert one generated from the model.
+        let generated = new.to_xml_string();
+        return find_element(&generated, "a:tblPr", 0).map_or_else(
+            || xml.to_string(),
+            |r| splice(xml, tbl_inner.start..tbl_inner.start, &generated[r]),
+        );
+    };
+
+    let mut out = xml.to_string();
+    if old.table_style_id != new.table_style_id {
+        let (out_expanded, tbl_pr) = expand_empty(&out, tbl_pr.clone());
+        out = out_expanded;
+        let inner = inner_range(&out, tbl_pr);
+        let existing = ["a:tableStyleId", "a:tblStyleId"]
+            .iter()
+            .find_map(|t| find_element_in(&out, t, inner.clone()));
+        let replacement = new.table_style_id.as_ref().map_or_else(String::new, |id| {
+            format!("<a:tableStyleId>{}</a:tableStyleId>", xml_escape(id))
+        });
+        let target = existing.unwrap_or(inner.end..inner.end);
+        out = splice(&out, target, &replacement);
+    }
+
+    if flags(old) != flags(new) {
+        if let Some(tag) = find_start_tag(&out, "a:tblPr", tbl_pr.start) {
+            let mut new_tag = out[tag.clone()].to_string();
+            let names = [
+                "firstRow", "firstCol", "lastRow", "lastCol", "bandRow", "bandCol",
+            ];
+            for (name, on) in names.iter().zip(flags(new)) {
+                new_tag = set_attr(&new_tag, name, flag(on));
+            }
+            out = splice(&out, tag, &new_tag);
+        }
+    }
+    out
+}
+
+fn patch_row(xml: &str, old: &Row, new: &Row) -> String {
+    let cells = elements_in(xml, "a:tc", inner_range(xml, 0..xml.len()));
+    if cells.len() != old.cells.len() {
+        let mut row = format!(r#"<a:tr h="{}">"#, new.height.0);
+        new.cells
+            .iter()
+            .for_each(|c| row.push_str(&c.to_xml_string()));
+        row.push_str("</a:tr>");
+        return row;
+    }
+
+    let mut out = xml.to_string();
+    for (j, range) in cells.into_iter().enumerate().rev() {
+        if old.cells[j] != new.cells[j] {
+            let cell = patch_cell(&out[range.clone()], &old.cells[j], &new.cells[j]);
+            out = splice(&out, range, &cell);
+        }
+    }
+    if old.height != new.height {
+        if let Some(tag) = find_start_tag(&out, "a:tr", 0) {
+            let new_tag = set_attr(&out[tag.clone()], "h", Some(&new.height.0.to_string()));
+            out = splice(&out, tag, &new_tag);
+        }
+    }
+    out
+}
+
+fn patch_cell(xml: &str, old: &Cell, new: &Cell) -> String {
+    if find_element(xml, "a:txBody", 0).is_none() {
+        return new.to_xml_string();
+    }
+    let mut out = xml.to_string();
+
+    // Cell properties: margins, anchor, borders and fill all live on
+    // `<a:tcPr>`, which is regenerated as a whole when any of them change.
+    let cell_props = |c: &Cell| {
+        (
+            [c.margin_left, c.margin_right, c.margin_top, c.margin_bottom],
+            c.vertical_anchor,
+            c.borders.clone(),
+            c.fill.clone(),
+        )
+    };
+    if cell_props(old) != cell_props(new) {
+        let generated = new.to_xml_string();
+        if let Some(tc_pr) = find_element(&generated, "a:tcPr", 0) {
+            let inner = inner_range(&out, 0..out.len());
+            let target =
+                find_element_in(&out, "a:tcPr", inner.clone()).unwrap_or(inner.end..inner.end);
+            out = splice(&out, target, &generated[tc_pr]);
+        }
+    }
+
+    if old.text_frame != new.text_frame {
+        out = patch_text_body(
+            &out,
+            "a:txBody",
+            Some(&old.text_frame),
+            Some(&new.text_frame),
+        );
+    }
+
+    let spans = |c: &Cell| (c.grid_span, c.row_span, c.h_merge, c.v_merge);
+    if spans(old) != spans(new) {
+        if let Some(tag) = find_start_tag(&out, "a:tc", 0) {
+            let span = |n: u32| (n > 1).then(|| n.to_string());
+            let mut new_tag = set_attr(
+                &out[tag.clone()],
+                "gridSpan",
+                span(new.grid_span).as_deref(),
+            );
+            new_tag = set_attr(&new_tag, "rowSpan", span(new.row_span).as_deref());
+            new_tag = set_attr(&new_tag, "hMerge", flag(new.h_merge));
+            new_tag = set_attr(&new_tag, "vMerge", flag(new.v_merge));
+            out = splice(&out, tag, &new_tag);
+        }
+    }
+    out
+}
@@ -0,0 +1,91 @@
+use super::*;
+use crate::shapes::connector::Connector;
+
+const SLIDE: &str = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/><p:sp><p:nvSpPr><p:cNvPr id="2" name="A"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr/></p:sp><p:sp><p:nvSpPr><p:cNvPr id="3" name="B"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr/></p:sp><p:grpSp><p:nvGrpSpPr><p:cNvPr id="4" name="G"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/><p:sp><p:nvSpPr><p:cNvPr id="5" name="G1"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr/></p:sp><p:sp><p:nvSpPr><p:cNvPr id="6" name="G2"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr/></p:sp></p:grpSp><p:extLst><p:ext uri="{X}"/></p:extLst></p:spTree></p:cSld></p:sld>"#;
+
+fn names(xml: &[u8]) -> Vec<String> {
+    ShapeTree::from_slide_xml(xml)
+        .unwrap()
+        .iter()
+        .map(|s| s.name().to_string())
+        .collect()
+}
+
+fn group_names(xml: &[u8]) -> Vec<String> {
+    let tree = ShapeTree::from_slide_xml(xml).unwrap();
+    tree.shapes[2]
+        .as_group()
+        .unwrap()
+        .shapes
+        .iter()
+        .map(|s| s.name().to_string())
+        .collect()
+}
+
+#[test]
+fn test_remove_shape() {
+    let out = ShapeTree::remove_shape(SLIDE.as_bytes(), ShapeId(2)).unwrap();
+    assert_eq!(names(&out), ["B", "G"]);
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .contains(r#"<p:ext uri="{X}"/>"#));
+}
+
+#[test]
+fn test_remove_group_child() {
+    let out = ShapeTree::remove_shape(SLIDE.as_bytes(), ShapeId(5)).unwrap();
+    assert_eq!(names(&out), ["A", "B", "G"]);
+    assert_eq!(group_names(&out), ["G2"]);
+}
+
+#[test]
+fn test_remove_unknown_shape_fails() {
+    assert!(ShapeTree::remove_shape(SLIDE.as_bytes(), ShapeId(99)).is_err());
+}
+
+#[test]
+fn test_replace_shape_keeps_position() {
+    let connector = Shape::Connector(Connector::new(
+        ShapeId(3),
+        "C",
+        Emu(0),
+        Emu(0),
+        Emu(10),
+        Emu(10),
+    ));
+    let out = ShapeTree::replace_shape(SLIDE.as_bytes(), ShapeId(3), &connector).unwrap();
+    let tree = ShapeTree::from_slide_xml(&out).unwrap();
+    assert_eq!(tree.shapes[1].name(), "C");
+    assert!(tree.shapes[1].as_connector().is_some());
+    assert_eq!(tree.len(), 3);
+}
+
+#[test]
+fn test_z_order_top_level() {
+    let slide = SLIDE.as_bytes();
+    let out = ShapeTree::bring_to_front(slide, ShapeId(2)).unwrap();
+    assert_eq!(names(&out), ["B", "G", "A"]);
+    let out = ShapeTree::send_to_back(slide, ShapeId(4)).unwrap();
+    assert_eq!(names(&out), ["G", "A", "B"]);
+    let out = ShapeTree::move_forward(slide, ShapeId(2)).unwrap();
+    assert_eq!(names(&out), ["B", "A", "G"]);
+    let out = ShapeTree::move_backward(slide, ShapeId(3)).unwrap();
+    assert_eq!(names(&out), ["B", "A", "G"]);
+    // Non-shape children of spTree stay at the end
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.ends_with(r#"<p:extLst><p:ext uri="{X}"/></p:extLst></p:spTree></p:cSld></p:sld>"#));
+}
+
+#[test]
+fn test_z_order_at_bounds_is_noop() {
+    let slide = SLIDE.as_bytes();
+    assert_eq!(ShapeTree::move_forward(slide, ShapeId(4)).unwrap(), slide);
+    assert_eq!(ShapeTree::move_backward(slide, ShapeId(2)).unwrap(), slide);
+}
+
+#[test]
+fn test_z_order_within_group() {
+    let out = ShapeTree::bring_to_front(SLIDE.as_bytes(), ShapeId(5)).unwrap();
+    assert_eq!(names(&out), ["A", "B", "G"]);
+    assert_eq!(group_names(&out), ["G2", "G1"]);
+}
@@ -208,6 +208,32 @@ fn test_parse_shape_with_text_frame() {
     }
 }
 
+#[test]
+fn test_parse_text_keeps_spaces_entities_and_bullets() {
+    let xml = br#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree>
+<p:sp><p:nvSpPr><p:cNvPr id="2" name="Body"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr/>
+<p:txBody><a:bodyPr/><a:p><a:pPr lvl="1"><a:buAutoNum type="alphaLcParenR"/></a:pPr><a:r><a:t>Fish </a:t></a:r><a:r><a:rPr b="1"/><a:t>&amp; chips</a:t></a:r><a:r><a:t> &#8212; cheap</a:t></a:r></a:p><a:p><a:pPr><a:buNone/></a:pPr><a:r><a:t>Plain</a:t></a:r></a:p></p:txBody>
+</p:sp>
+</p:spTree></p:cSld></p:sld>"#;
+
+    let tree = ShapeTree::from_slide_xml(xml).unwrap();
+    let tf = tree.shapes[0]
+        .as_autoshape()
+        .unwrap()
+        .text_frame
+        .as_ref()
+        .unwrap();
+    let paras = tf.paragraphs();
+    assert_eq!(paras[0].text(), "Fish & chips \u{2014} cheap");
+    assert_eq!(
+        paras[0].bullet,
+        Some(crate::text::BulletFormat::auto_numbered(
+            crate::enums::text::MsoAutoNumberScheme::AlphaLcParenR
+        ))
+    );
+    assert_eq!(paras[1].bullet, Some(crate::text::BulletFormat::None));
+}
+
 #[test]
 fn test_parse_shape_with_no_fill() {
     let xml = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
@@ -0,0 +1,232 @@
+use super::*;
+use crate::dml::color::ColorFormat;
+use crate::dml::fill::FillFormat;
+
+const SLIDE: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+<p:cSld><p:spTree>
+<p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+<p:grpSpPr/>
+<p:sp>
+  <p:nvSpPr><p:cNvPr id="2" name="Title 1"><a:extLst><a:ext uri="{FF2B5EF4-FFF2-40B4-BE49-F238E27FC236}"><a16:creationId xmlns:a16="http://schemas.microsoft.com/office/drawing/2014/main" id="{AAAA}"/></a:ext></a:extLst></p:cNvPr><p:cNvSpPr/><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr>
+  <p:spPr/>
+  <p:txBody><a:bodyPr vert="horz"/><a:lstStyle><a:lvl1pPr algn="ctr"/></a:lstStyle><a:p><a:r><a:rPr lang="en-US"/><a:t>Hello</a:t></a:r></a:p></p:txBody>
+</p:sp>
+<p:sp>
+  <p:nvSpPr><p:cNvPr id="3" name="Box 2"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr>
+  <p:spPr><a:xfrm><a:off x="100" y="200"/><a:ext cx="300" cy="400"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill><a:ln w="12700"><a:solidFill><a:srgbClr val="000000"/></a:solidFill></a:ln><a:extLst><a:ext uri="{X}"><foo:bar xmlns:foo="urn:foo"/></a:ext></a:extLst></p:spPr>
+</p:sp>
+<p:grpSp>
+  <p:nvGrpSpPr><p:cNvPr id="4" name="Group 3"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>
+  <p:grpSpPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="1000" cy="1000"/><a:chOff x="0" y="0"/><a:chExt cx="1000" cy="1000"/></a:xfrm></p:grpSpPr>
+  <p:sp><p:nvSpPr><p:cNvPr id="5" name="Child A"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm><a:off x="10" y="10"/><a:ext cx="20" cy="20"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:sp>
+</p:grpSp>
+<p:graphicFrame>
+  <p:nvGraphicFramePr><p:cNvPr id="6" name="Chart 5"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr>
+  <p:xfrm><a:off x="0" y="0"/><a:ext cx="500" cy="500"/></p:xfrm>
+  <a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" r:id="rId2"/></a:graphicData></a:graphic>
+</p:graphicFrame>
+</p:spTree></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr></p:sld>"#;
+
+fn apply(tree: &ShapeTree) -> String {
+    String::from_utf8(tree.apply_to_slide_xml(SLIDE.as_bytes()).unwrap()).unwrap()
+}
+
+#[test]
+fn test_write_back_unchanged_is_identity() {
+    let tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    assert_eq!(apply(&tree), SLIDE);
+}
+
+#[test]
+fn test_parse_group_children() {
+    let tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    let group = tree.shapes[2].as_group().unwrap();
+    assert_eq!(group.name, "Group 3");
+    assert_eq!(group.shape_id, ShapeId(4));
+    assert_eq!(group.width, Emu(1000));
+    assert_eq!(group.shapes.len(), 1);
+    assert_eq!(group.shapes[0].name(), "Child A");
+    // Shapes after the group are still parsed at the top level
+    assert_eq!(tree.shapes[3].name(), "Chart 5");
+}
+
+#[test]
+fn test_write_back_geometry_and_name() {
+    let mut tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    let sp = tree.shapes[1].as_autoshape_mut().unwrap();
+    sp.name = "Moved & Renamed".to_string();
+    sp.left = Emu(1000);
+    sp.height = Emu(9000);
+    sp.rotation = 45.0;
+
+    let out = apply(&tree);
+    assert!(out.contains(r#"name="Moved &amp; Renamed""#));
+    assert!(out.contains(
+        r#"<a:xfrm rot="2700000"><a:off x="1000" y="200"/><a:ext cx="300" cy="9000"/></a:xfrm>"#
+    ));
+    // Fill, line and extension markup are untouched
+    assert!(out.contains(r#"<a:solidFill><a:srgbClr val="FF0000"/></a:solidFill><a:ln w="12700">"#));
+    assert!(out.contains(r#"<foo:bar xmlns:foo="urn:foo"/>"#));
+
+    let reparsed = ShapeTree::from_slide_xml(out.as_bytes()).unwrap();
+    assert_eq!(reparsed.shapes[1].left(), Emu(1000));
+    assert_eq!(reparsed.shapes[1].height(), Emu(9000));
+}
+
+#[test]
+fn test_write_back_inherited_xfrm_is_created() {
+    let mut tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    let title = tree.shapes[0].as_autoshape_mut().unwrap();
+    title.left = Emu(10);
+    title.top = Emu(20);
+    title.width = Emu(30);
+    title.height = Emu(40);
+
+    let out = apply(&tree);
+    assert!(out.contains(
+        r#"<p:spPr><a:xfrm><a:off x="10" y="20"/><a:ext cx="30" cy="40"/></a:xfrm></p:spPr>"#
+    ));
+    assert!(out.contains("{AAAA}"));
+}
+
+#[test]
+fn test_write_back_text_keeps_body_properties() {
+    let mut tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    let title = tree.shapes[0].as_autoshape_mut().unwrap();
+    title.text_frame.as_mut().unwrap().set_text("Goodbye");
+
+    let out = apply(&tree);
+    assert!(out.contains("<a:t>Goodbye</a:t>"));
+    assert!(!out.contains("<a:t>Hello</a:t>"));
+    assert!(
+        out.contains(r#"<a:bodyPr vert="horz"/><a:lstStyle><a:lvl1pPr algn="ctr"/></a:lstStyle>"#)
+    );
+}
+
+#[test]
+fn test_write_back_fill_replaced_not_line_fill() {
+    let mut tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    let sp = tree.shapes[1].as_autoshape_mut().unwrap();
+    sp.fill = Some(FillFormat::solid(ColorFormat::rgb(0, 0x80, 0)));
+
+    let out = apply(&tree);
+    assert!(out.contains(r#"</a:prstGeom><a:solidFill><a:srgbClr val="008000"/></a:solidFill><a:ln w="12700"><a:solidFill><a:srgbClr val="000000"/></a:solidFill></a:ln>"#));
+    assert!(!out.contains("FF0000"));
+}
+
+#[test]
+fn test_write_back_remove_reorder_and_add() {
+    let mut tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    // Drop the title, move the chart to the back, add a new connector
+    tree.shapes.remove(0);
+    let chart = tree.shapes.pop().unwrap();
+    tree.shapes.insert(0, chart);
+    tree.shapes
+        .push(Shape::Connector(crate::shapes::connector::Connector::new(
+            ShapeId(7),
+            "Connector 6",
+            Emu(0),
+            Emu(0),
+            Emu(100),
+            Emu(100),
+        )));
+
+    let out = apply(&tree);
+    let reparsed = ShapeTree::from_slide_xml(out.as_bytes()).unwrap();
+    let names: Vec<&str> = reparsed.iter().map(Shape::name).collect();
+    assert_eq!(names, ["Chart 5", "Box 2", "Group 3", "Connector 6"]);
+    assert!(!out.contains("Title 1"));
+    assert!(out.contains(
+        r#"<c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" r:id="rId2"/>"#
+    ));
+    assert!(out.contains("<p:clrMapOvr>"));
+}
+
+#[test]
+fn test_write_back_group_child_and_graphic_frame() {
+    let mut tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    let group = tree.shapes[2].as_group_mut().unwrap();
+    group.left = Emu(5000);
+    group.shapes[0].as_autoshape_mut().unwrap().name = "Child B".to_string();
+    tree.shapes[3].as_graphic_frame_mut().unwrap().width = Emu(777);
+
+    let out = apply(&tree);
+    assert!(out
+        .contains(r#"<a:off x="5000" y="0"/><a:ext cx="1000" cy="1000"/><a:chOff x="0" y="0"/>"#));
+    assert!(out.contains(r#"name="Child B""#));
+    assert!(out.contains(r#"<p:xfrm><a:off x="0" y="0"/><a:ext cx="777" cy="500"/></p:xfrm>"#));
+
+    let reparsed = ShapeTree::from_slide_xml(out.as_bytes()).unwrap();
+    let group = reparsed.shapes[2].as_group().unwrap();
+    assert_eq!(group.left, Emu(5000));
+    assert_eq!(group.shapes[0].name(), "Child B");
+}
+
+#[test]
+fn test_write_back_without_sp_tree_fails() {
+    let tree = ShapeTree::default();
+    assert!(tree.apply_to_slide_xml(b"<p:sld/>").is_err());
+}
+
+const TABLE_SLIDE: &str = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name=""/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr/><p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="2" name="Table 1"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr><p:xfrm><a:off x="0" y="0"/><a:ext cx="2000" cy="600"/></p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"><a:tbl><a:tblPr firstRow="1"/><a:tblGrid><a:gridCol w="1000"/><a:gridCol w="1000"/></a:tblGrid><a:tr h="300"><a:tc><a:txBody><a:bodyPr/><a:lstStyle><a:lvl1pPr algn="ctr"/></a:lstStyle><a:p><a:r><a:rPr lang="en-US"/><a:t>Q1</a:t></a:r></a:p></a:txBody><a:tcPr/></a:tc><a:tc><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>Q2</a:t></a:r></a:p></a:txBody><a:tcPr/></a:tc></a:tr></a:tbl></a:graphicData></a:graphic></p:graphicFrame></p:spTree></p:cSld></p:sld>"#;
+
+#[test]
+fn test_parse_table_graphic_frame() {
+    let tree = ShapeTree::from_slide_xml(TABLE_SLIDE.as_bytes()).unwrap();
+    let frame = tree.shapes[0].as_graphic_frame().unwrap();
+    assert!(frame.has_table);
+    let table = frame.table().unwrap();
+    assert_eq!(table.row_count(), 1);
+    assert_eq!(table.cell(0, 1).text(), "Q2");
+    assert!(table.first_row);
+    // Chart frames carry no table
+    let tree = ShapeTree::from_slide_xml(SLIDE.as_bytes()).unwrap();
+    assert!(tree.shapes[3].as_graphic_frame().unwrap().table().is_none());
+}
+
+#[test]
+fn test_write_back_table_cell_text_keeps_list_style() {
+    let mut tree = ShapeTree::from_slide_xml(TABLE_SLIDE.as_bytes()).unwrap();
+    let frame = tree.shapes[0].as_graphic_frame_mut().unwrap();
+    let table = frame.table_mut().unwrap();
+    table.cell_mut(0, 0).set_text("Revenue");
+    table.vert_banding = true;
+    table.columns[1].width = Emu(1500);
+
+    let out = String::from_utf8(tree.apply_to_slide_xml(TABLE_SLIDE.as_bytes()).unwrap()).unwrap();
+    assert!(out.contains(r#"<a:lstStyle><a:lvl1pPr algn="ctr"/></a:lstStyle>"#));
+    assert!(out.contains(r#"<a:tblPr firstRow="1" bandCol="1"/>"#));
+    assert!(out.contains(r#"<a:gridCol w="1500"/>"#));
+
+    let reparsed = ShapeTree::from_slide_xml(out.as_bytes()).unwrap();
+    let table = reparsed.shapes[0]
+        .as_graphic_frame()
+        .unwrap()
+        .table()
+        .unwrap();
+    assert_eq!(table.cell(0, 0).text(), "Revenue");
+    assert_eq!(table.cell(0, 1).text(), "Q2");
+    assert!(table.vert_banding);
+}
+
+#[test]
+fn test_write_back_table_added_row() {
+    let mut tree = ShapeTree::from_slide_xml(TABLE_SLIDE.as_bytes()).unwrap();
+    let table = tree.shapes[0]
+        .as_graphic_frame_mut()
+        .unwrap()
+        .table_mut()
+        .unwrap();
+    table.add_row().cells[0].set_text("new");
+
+    let out = String::from_utf8(tree.apply_to_slide_xml(TABLE_SLIDE.as_bytes()).unwrap()).unwrap();
+    let reparsed = ShapeTree::from_slide_xml(out.as_bytes()).unwrap();
+    let table = reparsed.shapes[0]
+        .as_graphic_frame()
+        .unwrap()
+        .table()
+        .unwrap();
+    assert_eq!(table.row_count(), 2);
+    assert_eq!(table.cell(1, 0).text(), "new");
+}
@@ -0,0 +1,670 @@
+//! Writing an edited [`ShapeTree`] back into the slide XML it was parsed from.
+//!
+//! Shapes are matched to their original elements by shape ID. Unchanged
+//! shapes are copied byte-for-byte; changed shapes only have the affected
+//! sub-elements rewritten, so extension lists, vendor markup and properties
+//! this crate does not model survive the round trip.
+
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::dml::fill::FillFormat;
+use crate::dml::line::LineFormat;
+use crate::error::{PptxError, PptxResult};
+use crate::shapes::autoshape::AutoShape;
+use crate::shapes::connector::Connector;
+use crate::shapes::graphfrm::GraphicFrame;
+use crate::shapes::group::GroupShape;
+use crate::shapes::picture::Picture;
+use crate::shapes::Shape;
+use crate::text::TextFrame;
+use crate::units::Emu;
+use crate::xml_util::splice::{
+    find_element, find_element_in, find_start_tag, inner_range, set_attr, splice,
+};
+use crate::xml_util::{local_name_str, offset, xml_escape, WriteXml};
+
+use super::parse::{parse_shapes_in, shape_kind_for};
+use super::table_patch::patch_table;
+use super::ShapeTree;
+
+/// Fill elements that may appear as direct children of `<p:spPr>`.
+const FILL_TAGS: [&str; 6] = [
+    "a:noFill",
+    "a:solidFill",
+    "a:gradFill",
+    "a:blipFill",
+    "a:pattFill",
+    "a:grpFill",
+];
+
+/// `<p:spPr>` children that follow the line element in schema order.
+const AFTER_LINE_TAGS: [&str; 5] = [
+    "a:effectLst",
+    "a:effectDag",
+    "a:scene3d",
+    "a:sp3d",
+    "a:extLst",
+];
+
+impl ShapeTree {
+    /// Write the shapes of this tree back into `slide_xml`.
+    ///
+    /// Each shape is matched to the element with the same shape ID in the
+    /// original XML:
+    ///
+    /// - unchanged shapes are kept byte-for-byte;
+    /// - changed name, position, size, rotation/flip, preset geometry, fill,
+    ///   line, text, picture crop and image reference are patched in place,
+    ///   leaving every other child element (including `extLst`) untouched;
+    /// - any other change causes the shape to be re-serialized in full;
+    /// - shapes with no match in the original XML are written as new elements;
+    /// - original shapes missing from the tree are removed.
+    ///
+    /// Shapes are emitted in tree order, so reordering `shapes` changes the
+    /// z-order on the slide. Elements of `<p:spTree>` that are not shapes
+    /// (e.g. `mc:AlternateContent`, `p:extLst`) are left where they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slide XML is not valid UTF-8, cannot be parsed,
+    /// or contains no `<p:spTree>` element.
+    pub fn apply_to_slide_xml(&self, slide_xml: &[u8]) -> PptxResult<Vec<u8>> {
+        let xml = std::str::from_utf8(slide_xml)?;
+        Ok(rewrite_container(xml, "spTree", &self.shapes)?.into_bytes())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Container scanning and reassembly
+// ---------------------------------------------------------------------------
+
+/// A direct child element of a shape container.
+struct ChildSpan {
+    range: Range<usize>,
+    is_shape: bool,
+}
+
+/// Byte layout of the direct children of a shape container.
+pub(super) struct ContainerLayout {
+    children: Vec<ChildSpan>,
+    /// Offset of the container's closing tag.
+    close_tag: usize,
+}
+
+impl ContainerLayout {
+    /// Byte ranges of the children that are shapes, in document order.
+    pub(super) fn shape_slots(&self) -> Vec<Range<usize>> {
+        self.children
+            .iter()
+            .filter(|c| c.is_shape)
+            .map(|c| c.range.clone())
+            .collect()
+    }
+}
+
+/// Record the byte span of every direct child of the first element whose
+/// local name is `container`.
+pub(super) fn scan_container(xml: &str, container: &str) -> PptxResult<Option<ContainerLayout>> {
+    let mut reader = Reader::from_str(xml);
+    let mut depth = 0usize;
+    let mut container_depth: Option<usize> = None;
+    let mut open_child: Option<(usize, bool)> = None;
+    let mut children = Vec::new();
+
+    loop {
+        let start = offset(&reader);
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => {
+                let qname = e.name();
+                let local = local_name_str(qname.as_ref());
+                match container_depth {
+                    None if local == container => container_depth = Some(depth),
+                    Some(d) if depth == d + 1 => {
+                        open_child = Some((start, shape_kind_for(local).is_some()));
+                    }
+                    _ => {}
+                }
+                depth += 1;
+            }
+            Ok(Event::Empty(_)) => {
+                // An empty shape element carries nothing the parser turns
+                // into a shape, so it is treated as opaque markup.
+                if container_depth.is_some_and(|d| depth == d + 1) {
+                    children.push(ChildSpan {
+                        range: start..offset(&reader),
+                        is_shape: false,
+                    });
+                }
+            }
+            Ok(Event::End(_)) => {
+                depth = depth.saturating_sub(1);
+                match container_depth {
+                    Some(d) if depth == d + 1 => {
+                        if let Some((child_start, is_shape)) = open_child.take() {
+                            children.push(ChildSpan {
+                                range: child_start..offset(&reader),
+                                is_shape,
+                            });
+                        }
+                    }
+                    Some(d) if depth == d => {
+                        return Ok(Some(ContainerLayout {
+                            children,
+                            close_tag: start,
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => return Ok(None),
+            Err(e) => return Err(PptxError::Xml(e)),
+            _ => {}
+        }
+    }
+}
+
+/// Rewrite the shape children of `container` in `xml` so that they match
+/// `shapes`, keeping all non-shape markup in place.
+fn rewrite_container(xml: &str, container: &str, shapes: &[Shape]) -> PptxResult<String> {
+    let layout = scan_container(xml, container)?.ok_or_else(|| {
+        PptxError::InvalidXml(format!("XML does not contain a <{container}> element"))
+    })?;
+    let originals = parse_shapes_in(xml.as_bytes(), container)?;
+    let slots = layout.shape_slots();
+
+    let mut fragments = Vec::with_capacity(shapes.len());
+    for shape in shapes {
+        let original = slots
+            .iter()
+            .zip(&originals)
+            .find(|(_, orig)| orig.shape_id() == shape.shape_id());
+        let fragment = match original {
+            Some((range, orig)) => patch_shape(&xml[range.clone()], orig, shape)?,
+            None => shape.to_xml_string(),
+        };
+        if !fragment.is_empty() {
+            fragments.push(fragment);
+        }
+    }
+
+    // Fill the original shape slots in tree order; any surplus goes after
+    // the last slot (or before the closing tag when there were none).
+    let mut out = String::with_capacity(xml.len());
+    let mut fragments = fragments.into_iter();
+    let mut cursor = 0;
+    for (i, slot) in slots.iter().enumerate() {
+        out.push_str(&xml[cursor..slot.start]);
+        if i + 1 == slots.len() {
+            fragments.by_ref().for_each(|f| out.push_str(&f));
+        } else if let Some(f) = fragments.next() {
+            out.push_str(&f);
+        }
+        cursor = slot.end;
+    }
+    out.push_str(&xml[cursor..layout.close_tag]);
+    fragments.for_each(|f| out.push_str(&f));
+    out.push_str(&xml[layout.close_tag..]);
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Per-shape patching
+// ---------------------------------------------------------------------------
+
+/// Bring `fragment` (the original XML of `orig`) in line with `cur`.
+fn patch_shape(fragment: &str, orig: &Shape, cur: &Shape) -> PptxResult<String> {
+    if orig == cur {
+        return Ok(fragment.to_string());
+    }
+    let patched = match (orig, cur) {
+        (Shape::AutoShape(o), Shape::AutoShape(c)) => patch_autoshape(fragment, o, c),
+        (Shape::Picture(o), Shape::Picture(c)) => patch_picture(fragment, o, c),
+        (Shape::Connector(o), Shape::Connector(c)) => patch_connector(fragment, o, c),
+        (Shape::GraphicFrame(o), Shape::GraphicFrame(c)) => {
+            Some(patch_graphic_frame(fragment, o, c))
+        }
+        (Shape::GroupShape(o), Shape::GroupShape(c)) => Some(patch_group(fragment, o, c)?),
+        _ => None,
+    };
+    Ok(patched.unwrap_or_else(|| {
+        // Graphic frames cannot be serialized from the model alone, so an
+        // unpatchable change to one keeps the original markup.
+        let full = cur.to_xml_string();
+        if full.is_empty() {
+            fragment.to_string()
+        } else {
+            full
+        }
+    }))
+}
+
+/// Position, size and orientation of a shape as stored in its `xfrm`.
+#[derive(Debug, PartialEq)]
+struct Geometry {
+    left: Emu,
+    top: Emu,
+    width: Emu,
+    height: Emu,
+    rotation: f64,
+    /// `(flipH, flipV)`; `None` for shape types that do not model flips.
+    flip: Option<(bool, bool)>,
+}
+
+macro_rules! geometry_of {
+    ($s:expr) => {
+        Geometry {
+            left: $s.left,
+            top: $s.top,
+            width: $s.width,
+            height: $s.height,
+            rotation: $s.rotation,
+            flip: None,
+        }
+    };
+}
+
+fn patch_autoshape(fragment: &str, o: &AutoShape, c: &AutoShape) -> Option<String> {
+    let mut normalized = c.clone();
+    normalized.name.clone_from(&o.name);
+    normalized.left = o.left;
+    normalized.top = o.top;
+    normalized.width = o.width;
+    normalized.height = o.height;
+    normalized.rotation = o.rotation;
+    normalized.prst_geom.clone_from(&o.prst_geom);
+    normalized.fill.clone_from(&o.fill);
+    normalized.line.clone_from(&o.line);
+    normalized.text_frame.clone_from(&o.text_frame);
+    normalized.tx_body_xml.clone_from(&o.tx_body_xml);
+    if normalized != *o {
+        return None;
+    }
+
+    let mut xml = patch_name(fragment, &o.name, &c.name);
+    let (og, cg) = (geometry_of!(o), geometry_of!(c));
+    if og != cg {
+        xml = patch_xfrm(&xml, Some("p:spPr"), "a:xfrm", &cg);
+    }
+    if o.prst_geom != c.prst_geom {
+        let prst = c.prst_geom.as_ref()?;
+        let tag = find_start_tag(&xml, "a:prstGeom", 0)?;
+        let new_tag = set_attr(&xml[tag.clone()], "prst", Some(&prst.to_string()));
+        xml = splice(&xml, tag, &new_tag);
+    }
+    if o.fill != c.fill {
+        xml = patch_fill(&xml, c.fill.as_ref());
+    }
+    if o.line != c.line {
+        xml = patch_line(&xml, c.line.as_ref());
+    }
+    if o.text_frame != c.text_frame {
+        xml = patch_text_body(
+            &xml,
+            "p:txBody",
+            o.text_frame.as_ref(),
+            c.text_frame.as_ref(),
+        );
+    }
+    Some(xml)
+}
+
+fn patch_picture(fragment: &str, o: &Picture, c: &Picture) -> Option<String> {
+    let mut normalized = c.clone();
+    normalized.name.clone_from(&o.name);
+    normalized.description.clone_from(&o.description);
+    normalized.left = o.left;
+    normalized.top = o.top;
+    normalized.width = o.width;
+    normalized.height = o.height;
+    normalized.rotation = o.rotation;
+    normalized.image_r_id.clone_from(&o.image_r_id);
+    normalized.crop_left = o.crop_left;
+    normalized.crop_top = o.crop_top;
+    normalized.crop_right = o.crop_right;
+    normalized.crop_bottom = o.crop_bottom;
+    normalized.line.clone_from(&o.line);
+    // Cached image bytes are not part of the XML.
+    normalized.image_data.clone_from(&o.image_data);
+    normalized
+        .image_content_type
+        .clone_from(&o.image_content_type);
+    if normalized != *o {
+        return None;
+    }
+
+    let mut xml = patch_name(fragment, &o.name, &c.name);
+    if o.description != c.description {
+        let tag = find_start_tag(&xml, "p:cNvPr", 0)?;
+        let descr = c.description.as_deref().map(xml_escape);
+        let new_tag = set_attr(&xml[tag.clone()], "descr", descr.as_deref());
+        xml = splice(&xml, tag, &new_tag);
+    }
+    let (og, cg) = (geometry_of!(o), geometry_of!(c));
+    if og != cg {
+        xml = patch_xfrm(&xml, Some("p:spPr"), "a:xfrm", &cg);
+    }
+    if o.image_r_id != c.image_r_id {
+        let tag = find_start_tag(&xml, "a:blip", 0)?;
+        let r_id = c.image_r_id.as_deref().map(xml_escape);
+        let new_tag = set_attr(&xml[tag.clone()], "r:embed", r_id.as_deref());
+        xml = splice(&xml, tag, &new_tag);
+    }
+    let crop = |p: &Picture| [p.crop_left, p.crop_top, p.crop_right, p.crop_bottom];
+    if crop(o) != crop(c) {
+        xml = patch_src_rect(&xml, crop(c))?;
+    }
+    if o.line != c.line {
+        xml = patch_line(&xml, c.line.as_ref());
+    }
+    Some(xml)
+}
+
+fn patch_connector(fragment: &str, o: &Connector, c: &Connector) -> Option<String> {
+    let mut normalized = c.clone();
+    normalized.name.clone_from(&o.name);
+    normalized.left = o.left;
+    normalized.top = o.top;
+    normalized.width = o.width;
+    normalized.height = o.height;
+    normalized.rotation = o.rotation;
+    normalized.flip_h = o.flip_h;
+    normalized.flip_v = o.flip_v;
+    normalized.prst_geom.clone_from(&o.prst_geom);
+    normalized.line.clone_from(&o.line);
+    if normalized != *o {
+        return None;
+    }
+
+    let mut xml = patch_name(fragment, &o.name, &c.name);
+    let og = Geometry {
+        flip: Some((o.flip_h, o.flip_v)),
+        ..geometry_of!(o)
+    };
+    let cg = Geometry {
+        flip: Some((c.flip_h, c.flip_v)),
+        ..geometry_of!(c)
+    };
+    if og != cg {
+        xml = patch_xfrm(&xml, Some("p:spPr"), "a:xfrm", &cg);
+    }
+    if o.prst_geom != c.prst_geom {
+        let prst = c.prst_geom.as_ref()?;
+        let tag = find_start_tag(&xml, "a:prstGeom", 0)?;
+        let new_tag = set_attr(&xml[tag.clone()], "prst", Some(&prst.to_string()));
+        xml = splice(&xml, tag, &new_tag);
+    }
+    if o.line != c.line {
+        xml = patch_line(&xml, c.line.as_ref());
+    }
+    Some(xml)
+}
+
+/// Graphic frames keep their `<a:graphic>` payload verbatim; only the
+/// name and frame geometry are written back.
+fn patch_graphic_frame(fragment: &str, o: &GraphicFrame, c: &GraphicFrame) -> String {
+    let mut xml = patch_name(fragment, &o.name, &c.name);
+    let (og, cg) = (geometry_of!(o), geometry_of!(c));
+    if og != cg {
+        xml = patch_xfrm(&xml, None, "p:xfrm", &cg);
+    }
+    if let (Some(ot), Some(ct)) = (&o.table, &c.table) {
+        if ot != ct {
+            xml = patch_table(&xml, ot, ct);
+        }
+    }
+    xml
+}
+
+fn patch_group(fragment: &str, o: &GroupShape, c: &GroupShape) -> PptxResult<String> {
+    let mut xml = patch_name(fragment, &o.name, &c.name);
+    let (og, cg) = (geometry_of!(o), geometry_of!(c));
+    if og != cg {
+        xml = patch_xfrm(&xml, Some("p:grpSpPr"), "a:xfrm", &cg);
+    }
+    if o.shapes != c.shapes {
+        xml = rewrite_container(&xml, "grpSp", &c.shapes)?;
+    }
+    Ok(xml)
+}
+
+// ---------------------------------------------------------------------------
+// Sub-element patch helpers
+// ---------------------------------------------------------------------------
+
+/// Update the `name` attribute of the shape's `<p:cNvPr>`.
+fn patch_name(xml: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return xml.to_string();
+    }
+    find_start_tag(xml, "p:cNvPr", 0).map_or_else(
+        || xml.to_string(),
+        |tag| {
+            let new_tag = set_attr(&xml[tag.clone()], "name", Some(&xml_escape(new)));
+            splice(xml, tag, &new_tag)
+        },
+    )
+}
+
+/// Turn an empty element (`<x .../>`) into an open/close pair so that
+/// children can be inserted. Returns the updated XML and element range.
+pub(super) fn expand_empty(xml: &str, element: Range<usize>) -> (String, Range<usize>) {
+    let tag = &xml[element.clone()];
+    if !tag.ends_with("/>") {
+        return (xml.to_string(), element);
+    }
+    let name_end = tag[1..]
+        .find(|ch: char| ch.is_whitespace() || ch == '/' || ch == '>')
+        .map_or(tag.len(), |p| p + 1);
+    let qname = &tag[1..name_end];
+    let expanded = format!("{}></{qname}>", &tag[..tag.len() - 2]);
+    let range = element.start..element.start + expanded.len();
+    (splice(xml, element, &expanded), range)
+}
+
+/// Write position, size and orientation into the shape's transform.
+///
+/// `props_tag` is the properties element holding the transform (`p:spPr`,
+/// `p:grpSpPr`), or `None` when the transform is a direct child of the
+/// shape (`p:xfrm` on a graphic frame). A missing transform is created.
+fn patch_xfrm(xml: &str, props_tag: Option<&str>, xfrm_tag: &str, g: &Geometry) -> String {
+    let (xml, scope) = match props_tag {
+        Some(tag) => {
+            let Some(props) = find_element(xml, tag, 0) else {
+                return xml.to_string();
+            };
+            let (expanded, props) = expand_empty(xml, props);
+            let scope = inner_range(&expanded, props);
+            (expanded, scope)
+        }
+        None => (xml.to_string(), 0..xml.len()),
+    };
+
+    let off = format!(r#"<a:off x="{}" y="{}"/>"#, g.left.0, g.top.0);
+    let ext = format!(r#"<a:ext cx="{}" cy="{}"/>"#, g.width.0, g.height.0);
+
+    let Some(xfrm) = find_element_in(&xml, xfrm_tag, scope.clone()) else {
+        // Placeholders usually inherit their transform; give this one its own.
+        let mut start_tag = format!("<{xfrm_tag}>");
+        start_tag = set_xfrm_attrs(&start_tag, g);
+        let new_xfrm = format!("{start_tag}{off}{ext}</{xfrm_tag}>");
+        return splice(&xml, scope.start..scope.start, &new_xfrm);
+    };
+    let (mut xml, xfrm) = expand_empty(&xml, xfrm);
+
+    let inner = inner_range(&xml, xfrm.clone());
+    let off_range = find_element_in(&xml, "a:off", inner.clone());
+    xml = splice(&xml, off_range.unwrap_or(inner.start..inner.start), &off);
+
+    // Re-locate the transform: the offset edit may have shifted its end.
+    let Some(xfrm) = find_element(&xml, xfrm_tag, xfrm.start) else {
+        return xml;
+    };
+    let inner = inner_range(&xml, xfrm.clone());
+    let ext_range = find_element_in(&xml, "a:ext", inner.clone()).unwrap_or_else(|| {
+        let at = find_element_in(&xml, "a:off", inner.clone()).map_or(inner.end, |r| r.end);
+        at..at
+    });
+    xml = splice(&xml, ext_range, &ext);
+
+    if let Some(tag) = find_start_tag(&xml, xfrm_tag, xfrm.start) {
+        let new_tag = set_xfrm_attrs(&xml[tag.clone()], g);
+        xml = splice(&xml, tag, &new_tag);
+    }
+    xml
+}
+
+fn set_xfrm_attrs(start_tag: &str, g: &Geometry) -> String {
+    // f64→i64: rotation degrees * 60000 fits in i64
+    #[allow(clippy::cast_possible_truncation)]
+    let rot = (g.rotation * 60000.0).round() as i64;
+    let rot = (rot != 0).then(|| rot.to_string());
+    let mut tag = set_attr(start_tag, "rot", rot.as_deref());
+    if let Some((flip_h, flip_v)) = g.flip {
+        tag = set_attr(&tag, "flipH", flip_h.then_some("1"));
+        tag = set_attr(&tag, "flipV", flip_v.then_some("1"));
+    }
+    tag
+}
+
+/// Replace (or insert, or remove) the fill element of `<p:spPr>`.
+fn patch_fill(xml: &str, fill: Option<&FillFormat>) -> String {
+    let Some(sp_pr) = find_element(xml, "p:spPr", 0) else {
+        return xml.to_string();
+    };
+    let (xml, sp_pr) = expand_empty(xml, sp_pr);
+    let inner = inner_range(&xml, sp_pr);
+
+    // Schema order: xfrm, geometry, fill, ln, effects, scene3d, sp3d, extLst.
+    let region_start = ["a:xfrm", "a:prstGeom", "a:custGeom"]
+        .iter()
+        .filter_map(|t| find_element_in(&xml, t, inner.clone()).map(|r| r.end))
+        .max()
+        .unwrap_or(inner.start);
+    let region_end = std::iter::once("a:ln")
+        .chain(AFTER_LINE_TAGS)
+        .filter_map(|t| find_element_in(&xml, t, region_start..inner.end).map(|r| r.start))
+        .min()
+        .unwrap_or(inner.end);
+    let existing = FILL_TAGS
+        .iter()
+        .filter_map(|t| find_element_in(&xml, t, region_start..region_end))
+        .min_by_key(|r| r.start);
+
+    let new_fill = fill.map(WriteXml::to_xml_string).unwrap_or_default();
+    let target = existing.unwrap_or(region_start..region_start);
+    splice(&xml, target, &new_fill)
+}
+
+/// Replace (or insert, or remove) the `<a:ln>` element of `<p:spPr>`.
+fn patch_line(xml: &str, line: Option<&LineFormat>) -> String {
+    let Some(sp_pr) = find_element(xml, "p:spPr", 0) else {
+        return xml.to_string();
+    };
+    let (xml, sp_pr) = expand_empty(xml, sp_pr);
+    let inner = inner_range(&xml, sp_pr);
+
+    let mut new_line = String::new();
+    if let Some(line) = line {
+        // fmt::Write for String is infallible; the result is intentionally ignored.
+        let _ = line.write_xml(&mut new_line);
+    }
+    let target = find_element_in(&xml, "a:ln", inner.clone()).unwrap_or_else(|| {
+        let at = AFTER_LINE_TAGS
+            .iter()
+            .filter_map(|t| find_element_in(&xml, t, inner.clone()).map(|r| r.start))
+            .min()
+            .unwrap_or(inner.end);
+        at..at
+    });
+    splice(&xml, target, &new_line)
+}
+
+/// Rewrite the `<a:srcRect>` crop of a picture's `<p:blipFill>`.
+fn patch_src_rect(xml: &str, crop: [f64; 4]) -> Option<String> {
+    let mut src_rect = String::new();
+    if crop.iter().any(|v| *v != 0.0) {
+        src_rect.push_str("<a:srcRect");
+        for (key, value) in ["l", "t", "r", "b"].iter().zip(crop) {
+            if value != 0.0 {
+                // f64→i64: crop fractions * 100000 are small values
+                #[allow(clippy::cast_possible_truncation)]
+                let v = (value * 100_000.0).round() as i64;
+                src_rect.push_str(&format!(r#" {key}="{v}""#));
+            }
+        }
+        src_rect.push_str("/>");
+    }
+    let blip_fill = find_element(xml, "p:blipFill", 0)?;
+    let inner = inner_range(xml, blip_fill);
+    let target = find_element_in(xml, "a:srcRect", inner.clone()).unwrap_or_else(|| {
+        let at = find_element_in(xml, "a:blip", inner.clone()).map_or(inner.start, |r| r.end);
+        at..at
+    });
+    Some(splice(xml, target, &src_rect))
+}
+
+/// Bring the text body (`body_tag`: `<p:txBody>` for shapes, `<a:txBody>`
+/// for table cells) of a fragment in line with `new`.
+///
+/// Paragraphs and `<a:bodyPr>` are replaced independently so that an
+/// edit to the text keeps the original body properties and list styles.
+pub(super) fn patch_text_body(
+    xml: &str,
+    body_tag: &str,
+    old: Option<&TextFrame>,
+    new: Option<&TextFrame>,
+) -> String {
+    let serialize = |tf: &TextFrame| {
+        let mut s = String::new();
+        // fmt::Write for String is infallible; the result is intentionally ignored.
+        let _ = tf.write_xml_with_tag(&mut s, body_tag);
+        s
+    };
+    let existing = find_element(xml, body_tag, 0);
+    let (Some(old), Some(new), Some(body)) = (old, new, existing.clone()) else {
+        return match (new, existing) {
+            (None, Some(body)) => splice(xml, body, ""),
+            (Some(new), Some(body)) => splice(xml, body, &serialize(new)),
+            (Some(new), None) => {
+                // txBody follows spPr and the optional style element.
+                let at = ["p:style", "p:spPr"]
+                    .iter()
+                    .find_map(|t| find_element(xml, t, 0).map(|r| r.end))
+                    .unwrap_or_else(|| xml.rfind("</").unwrap_or(xml.len()));
+                splice(xml, at..at, &serialize(new))
+            }
+            (None, None) => xml.to_string(),
+        };
+    };
+
+    let mut xml = xml.to_string();
+    if old.paragraphs != new.paragraphs {
+        let inner = inner_range(&xml, body.clone());
+        let start = ["a:lstStyle", "a:bodyPr"]
+            .iter()
+            .find_map(|t| find_element_in(&xml, t, inner.clone()).map(|r| r.end))
+            .unwrap_or(inner.start);
+        let paragraphs: String = new.paragraphs.iter().map(|p| p.to_xml_string()).collect();
+        xml = splice(&xml, start..inner.end, &paragraphs);
+    }
+
+    let body_props = |tf: &TextFrame| TextFrame {
+        paragraphs: Vec::new(),
+        ..tf.clone()
+    };
+    if body_props(old) != body_props(new) {
+        let mut body_pr = String::new();
+        // fmt::Write for String is infallible; the result is intentionally ignored.
+        let _ = new.write_body_pr(&mut body_pr);
+        if let Some(body) = find_element(&xml, body_tag, 0) {
+            let inner = inner_range(&xml, body);
+            let target = find_element_in(&xml, "a:bodyPr", inner.clone())
+                .unwrap_or(inner.start..inner.start);
+            xml = splice(&xml, target, &body_pr);
+        }
+    }
+    xml
+}
@@ -73,4 +73,11 @@ impl XmlCapture {
     pub(super) fn push_text(&mut self, text: &[u8]) {
         self.xml.extend_from_slice(text);
     }
+
+    /// Re-emit an entity or character reference such as `&amp;`.
+    pub(super) fn push_general_ref(&mut self, name: &[u8]) {
+        self.xml.push(b'&');
+        self.xml.extend_from_slice(name);
+        self.xml.push(b';');
+    }
 }
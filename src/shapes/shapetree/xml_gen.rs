@@ -1,5 +1,7 @@
 //! XML generation methods for creating shape elements.
 
+use crate::shapes::placeholder::PlaceholderFormat;
+use crate::text::TextFrame;
 use crate::units::{Emu, ShapeId};
 use crate::xml_util::xml_escape;
 
@@ -88,6 +90,37 @@ impl ShapeTree {
         )
     }
 
+    /// Generate XML for a new placeholder `<p:sp>` element holding `text`.
+    ///
+    /// The placeholder inherits its position and size from the layout
+    /// unless `bounds` (left, top, width, height) are given.
+    #[must_use]
+    pub fn new_placeholder_xml(
+        shape_id: ShapeId,
+        name: &str,
+        placeholder: &PlaceholderFormat,
+        bounds: Option<(Emu, Emu, Emu, Emu)>,
+        text: &TextFrame,
+    ) -> String {
+        let sp_pr = bounds.map_or_else(
+            || "<p:spPr/>".to_string(),
+            |(left, top, width, height)| {
+                format!(
+                    r#"<p:spPr><a:xfrm><a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/></a:xfrm></p:spPr>"#,
+                    left.0, top.0, width.0, height.0
+                )
+            },
+        );
+        format!(
+            r#"<p:sp xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:nvSpPr><p:cNvPr id="{}" name="{}"/><p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr><p:nvPr>{}</p:nvPr></p:nvSpPr>{}{}</p:sp>"#,
+            shape_id,
+            xml_escape(name),
+            placeholder.to_xml_string(),
+            sp_pr,
+            text.to_xml_string(),
+        )
+    }
+
     /// Generate XML for a new `<p:sp>` element (auto shape with preset geometry).
     #[must_use]
     pub fn new_autoshape_xml(
@@ -0,0 +1,418 @@
+//! Slide show settings for presentations.
+
+use std::fmt;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::dml::color::ColorFormat;
+use crate::error::{PptxError, PptxResult};
+use crate::shapes::parser::parse_color_from_xml;
+use crate::xml_util::{attr_value, local_name, offset, WriteXml};
+
+/// URI of the `<p:showPr>` extension holding the laser pointer color.
+pub(crate) const LASER_COLOR_EXT_URI: &str = "{2FDB2607-1784-4EEB-B798-7EB5836EED8A}";
+
+/// Namespace of the `PowerPoint` 2013 extensions.
+const P15_NS: &str = "http://schemas.microsoft.com/office/powerpoint/2012/main";
+
+/// Inactivity, in milliseconds, after which a kiosk show restarts unless
+/// told otherwise.
+pub const DEFAULT_KIOSK_RESTART_MS: u32 = 300_000;
+
+/// How a slide show is run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShowType {
+    /// Presented by a speaker, full screen.
+    Presented,
+    /// Browsed by an individual, in a window.
+    Browsed {
+        /// Whether the window shows a scrollbar.
+        show_scrollbar: bool,
+    },
+    /// Browsed at a kiosk, full screen and looping until Esc.
+    Kiosk {
+        /// Inactivity, in milliseconds, after which the show restarts.
+        restart_ms: u32,
+    },
+}
+
+/// The slides a slide show runs through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlideRange {
+    /// Every slide.
+    All,
+    /// The slides from `start` to `end` (0-based indices, inclusive).
+    Range { start: usize, end: usize },
+    /// The slides of the custom show with this ID.
+    CustomShow(u32),
+}
+
+/// Slide show settings for a presentation.
+///
+/// Maps to the `<p:showPr>` element in presProps.xml.
+///
+/// # Examples
+///
+/// ```
+/// use pptx::show_settings::{ShowSettings, ShowType, DEFAULT_KIOSK_RESTART_MS};
+/// use pptx::dml::color::ColorFormat;
+///
+/// let settings = ShowSettings::new()
+///     .with_show_type(ShowType::Kiosk { restart_ms: DEFAULT_KIOSK_RESTART_MS })
+///     .with_loop_until_esc(true)
+///     .with_show_without_narration(true)
+///     .with_pen_color(ColorFormat::rgb(0, 0x70, 0xC0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShowSettings {
+    /// How the show is run.
+    pub show_type: ShowType,
+    /// Whether the show starts over after the last slide, until Esc.
+    pub loop_until_esc: bool,
+    /// Whether recorded narration is left out.
+    pub show_without_narration: bool,
+    /// Whether animations are left out.
+    pub show_without_animation: bool,
+    /// The slides the show runs through.
+    pub slide_range: SlideRange,
+    /// Whether slides advance on their rehearsed timings.
+    pub use_timings: bool,
+    /// The color of the annotation pen, if set.
+    pub pen_color: Option<ColorFormat>,
+    /// The color of the laser pointer, if set.
+    pub laser_color: Option<ColorFormat>,
+}
+
+impl ShowSettings {
+    /// Create new show settings with `PowerPoint`'s defaults.
+    ///
+    /// Defaults: presented by a speaker, all slides, no looping, with
+    /// narration and animation, using timings, no pen or laser color.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            show_type: ShowType::Presented,
+            loop_until_esc: false,
+            show_without_narration: false,
+            show_without_animation: false,
+            slide_range: SlideRange::All,
+            use_timings: true,
+            pen_color: None,
+            laser_color: None,
+        }
+    }
+
+    /// Set how the show is run.
+    #[must_use]
+    pub const fn with_show_type(mut self, show_type: ShowType) -> Self {
+        self.show_type = show_type;
+        self
+    }
+
+    /// Set whether the show loops until Esc.
+    #[must_use]
+    pub const fn with_loop_until_esc(mut self, loop_until_esc: bool) -> Self {
+        self.loop_until_esc = loop_until_esc;
+        self
+    }
+
+    /// Set whether recorded narration is left out.
+    #[must_use]
+    pub const fn with_show_without_narration(mut self, without: bool) -> Self {
+        self.show_without_narration = without;
+        self
+    }
+
+    /// Set whether animations are left out.
+    #[must_use]
+    pub const fn with_show_without_animation(mut self, without: bool) -> Self {
+        self.show_without_animation = without;
+        self
+    }
+
+    /// Set the slides the show runs through.
+    #[must_use]
+    pub const fn with_slide_range(mut self, range: SlideRange) -> Self {
+        self.slide_range = range;
+        self
+    }
+
+    /// Set whether slides advance on their rehearsed timings.
+    #[must_use]
+    pub const fn with_use_timings(mut self, use_timings: bool) -> Self {
+        self.use_timings = use_timings;
+        self
+    }
+
+    /// Set the color of the annotation pen.
+    #[must_use]
+    pub fn with_pen_color(mut self, color: ColorFormat) -> Self {
+        self.pen_color = Some(color);
+        self
+    }
+
+    /// Set the color of the laser pointer.
+    #[must_use]
+    pub fn with_laser_color(mut self, color: ColorFormat) -> Self {
+        self.laser_color = Some(color);
+        self
+    }
+
+    /// Parse show settings from a `<p:showPr>` element.
+    ///
+    /// Attributes and elements that are absent take their schema defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed.
+    pub(crate) fn from_xml(xml: &[u8]) -> PptxResult<Self> {
+        let mut settings = Self::new();
+        let mut reader = Reader::from_reader(xml);
+        let mut buf = Vec::new();
+
+        // Where the content of the open pen or laser color element starts
+        let mut color_start = None;
+        loop {
+            buf.clear();
+            let before = offset(&reader);
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e) | Event::Empty(ref e)) => {
+                    let flag = |key: &[u8], default: bool| -> PptxResult<bool> {
+                        Ok(attr_value(e, key)?.map_or(default, |v| v == "1" || v == "true"))
+                    };
+                    let number = |key: &[u8]| -> PptxResult<Option<usize>> {
+                        Ok(attr_value(e, key)?.and_then(|v| v.parse().ok()))
+                    };
+                    match local_name(e.name().as_ref()) {
+                        b"showPr" => {
+                            settings.loop_until_esc = flag(b"loop", false)?;
+                            settings.show_without_narration = !flag(b"showNarration", false)?;
+                            settings.show_without_animation = !flag(b"showAnimation", true)?;
+                            settings.use_timings = flag(b"useTimings", true)?;
+                        }
+                        b"present" => settings.show_type = ShowType::Presented,
+                        b"browse" => {
+                            settings.show_type = ShowType::Browsed {
+                                show_scrollbar: flag(b"showScrollbar", true)?,
+                            };
+                        }
+                        b"kiosk" => {
+                            settings.show_type = ShowType::Kiosk {
+                                restart_ms: attr_value(e, b"restart")?
+                                    .and_then(|v| v.parse().ok())
+                                    .unwrap_or(DEFAULT_KIOSK_RESTART_MS),
+                            };
+                        }
+                        b"sldAll" => settings.slide_range = SlideRange::All,
+                        b"sldRg" => {
+                            // `st` and `end` are 1-based slide numbers
+                            let start = number(b"st")?.unwrap_or(1);
+                            let end = number(b"end")?.unwrap_or(start);
+                            settings.slide_range = SlideRange::Range {
+                                start: start.saturating_sub(1),
+                                end: end.saturating_sub(1),
+                            };
+                        }
+                        b"custShow" => {
+                            settings.slide_range = SlideRange::CustomShow(
+                                attr_value(e, b"id")?
+                                    .and_then(|v| v.parse().ok())
+                                    .unwrap_or_default(),
+                            );
+                        }
+                        b"penClr" | b"laserClr" => {
+                            color_start = Some(offset(&reader));
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(ref e)) => {
+                    let name = e.name();
+                    let local = local_name(name.as_ref());
+                    if let (b"penClr" | b"laserClr", Some(start)) = (local, color_start.take()) {
+                        // Any color element is kept, not just sRGB ones
+                        let color = parse_color_from_xml(&xml[start..before])?;
+                        if local == b"penClr" {
+                            settings.pen_color = color;
+                        } else {
+                            settings.laser_color = color;
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(PptxError::Xml(e)),
+                _ => {}
+            }
+        }
+        Ok(settings)
+    }
+}
+
+impl Default for ShowSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteXml for ShowSettings {
+    fn write_xml<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        w.write_str("<p:showPr")?;
+        if self.loop_until_esc {
+            w.write_str(r#" loop="1""#)?;
+        }
+        if !self.show_without_narration {
+            w.write_str(r#" showNarration="1""#)?;
+        }
+        if self.show_without_animation {
+            w.write_str(r#" showAnimation="0""#)?;
+        }
+        if !self.use_timings {
+            w.write_str(r#" useTimings="0""#)?;
+        }
+        w.write_str(">")?;
+
+        match self.show_type {
+            ShowType::Presented => w.write_str("<p:present/>")?,
+            ShowType::Browsed { show_scrollbar } => {
+                w.write_str("<p:browse")?;
+                if !show_scrollbar {
+                    w.write_str(r#" showScrollbar="0""#)?;
+                }
+                w.write_str("/>")?;
+            }
+            ShowType::Kiosk { restart_ms } => {
+                w.write_str("<p:kiosk")?;
+                if restart_ms != DEFAULT_KIOSK_RESTART_MS {
+                    write!(w, r#" restart="{restart_ms}""#)?;
+                }
+                w.write_str("/>")?;
+            }
+        }
+
+        match self.slide_range {
+            SlideRange::All => w.write_str("<p:sldAll/>")?,
+            SlideRange::Range { start, end } => {
+                write!(w, r#"<p:sldRg st="{}" end="{}"/>"#, start + 1, end + 1)?;
+            }
+            SlideRange::CustomShow(id) => write!(w, r#"<p:custShow id="{id}"/>"#)?,
+        }
+
+        if let Some(color) = &self.pen_color {
+            w.write_str("<p:penClr>")?;
+            color.write_xml(w)?;
+            w.write_str("</p:penClr>")?;
+        }
+
+        if let Some(color) = &self.laser_color {
+            write!(
+                w,
+                r#"<p:extLst><p:ext uri="{LASER_COLOR_EXT_URI}"><p15:laserClr xmlns:p15="{P15_NS}">"#
+            )?;
+            color.write_xml(w)?;
+            w.write_str("</p15:laserClr></p:ext></p:extLst>")?;
+        }
+
+        w.write_str("</p:showPr>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_show_settings() {
+        let settings = ShowSettings::new();
+        assert_eq!(settings.show_type, ShowType::Presented);
+        assert_eq!(settings.slide_range, SlideRange::All);
+        assert!(!settings.loop_until_esc);
+        assert!(settings.use_timings);
+        assert_eq!(
+            settings.to_xml_string(),
+            r#"<p:showPr showNarration="1"><p:present/><p:sldAll/></p:showPr>"#
+        );
+    }
+
+    #[test]
+    fn test_kiosk_xml() {
+        let settings = ShowSettings::new()
+            .with_show_type(ShowType::Kiosk { restart_ms: 60_000 })
+            .with_loop_until_esc(true)
+            .with_show_without_narration(true)
+            .with_show_without_animation(true)
+            .with_use_timings(false);
+        assert_eq!(
+            settings.to_xml_string(),
+            r#"<p:showPr loop="1" showAnimation="0" useTimings="0"><p:kiosk restart="60000"/><p:sldAll/></p:showPr>"#
+        );
+    }
+
+    #[test]
+    fn test_browsed_range_and_colors_xml() {
+        let settings = ShowSettings::new()
+            .with_show_type(ShowType::Browsed {
+                show_scrollbar: false,
+            })
+            .with_slide_range(SlideRange::Range { start: 1, end: 3 })
+            .with_pen_color(ColorFormat::rgb(0xFF, 0, 0))
+            .with_laser_color(ColorFormat::rgb(0, 0xFF, 0));
+        let xml = settings.to_xml_string();
+        assert!(xml.contains(r#"<p:browse showScrollbar="0"/><p:sldRg st="2" end="4"/>"#));
+        assert!(xml.contains(r#"<p:penClr><a:srgbClr val="FF0000"/></p:penClr>"#));
+        assert!(xml.contains(r#"<p15:laserClr xmlns:p15=""#));
+    }
+
+    #[test]
+    fn test_roundtrip_xml() {
+        let settings = ShowSettings::new()
+            .with_show_type(ShowType::Kiosk {
+                restart_ms: DEFAULT_KIOSK_RESTART_MS,
+            })
+            .with_loop_until_esc(true)
+            .with_slide_range(SlideRange::CustomShow(2))
+            .with_pen_color(ColorFormat::rgb(1, 2, 3))
+            .with_laser_color(ColorFormat::rgb(4, 5, 6));
+        let xml = format!(
+            r#"<p:presentationPr xmlns:a="a" xmlns:p="p">{}</p:presentationPr>"#,
+            settings.to_xml_string()
+        );
+        assert_eq!(ShowSettings::from_xml(xml.as_bytes()).unwrap(), settings);
+    }
+
+    #[test]
+    fn test_preset_and_scheme_colors_roundtrip() {
+        let xml = format!(
+            r#"<p:showPr><p:present/><p:sldAll/><p:penClr><a:prstClr val="red"/></p:penClr><p:extLst><p:ext uri="{LASER_COLOR_EXT_URI}"><p15:laserClr><a:schemeClr val="accent2"/></p15:laserClr></p:ext></p:extLst></p:showPr>"#
+        );
+        let settings = ShowSettings::from_xml(xml.as_bytes()).unwrap();
+        assert!(matches!(settings.pen_color, Some(ColorFormat::Preset(_))));
+        assert!(matches!(settings.laser_color, Some(ColorFormat::Theme(_))));
+
+        let written = settings.to_xml_string();
+        assert!(written.contains(r#"<p:penClr><a:prstClr val="red"/></p:penClr>"#));
+        assert!(written.contains(r#"<a:schemeClr val="accent2"/></p15:laserClr>"#));
+    }
+
+    #[test]
+    fn test_parse_schema_defaults() {
+        // Absent attributes take their schema defaults, which differ from
+        // what PowerPoint writes for narration
+        let settings = ShowSettings::from_xml(b"<p:showPr/>").unwrap();
+        assert!(settings.show_without_narration);
+        assert!(!settings.show_without_animation);
+        assert!(settings.use_timings);
+        assert_eq!(settings.show_type, ShowType::Presented);
+
+        let settings =
+            ShowSettings::from_xml(br#"<p:showPr><p:browse/><p:sldRg st="3" end="5"/></p:showPr>"#)
+                .unwrap();
+        assert_eq!(
+            settings.show_type,
+            ShowType::Browsed {
+                show_scrollbar: true
+            }
+        );
+        assert_eq!(settings.slide_range, SlideRange::Range { start: 2, end: 4 });
+    }
+}
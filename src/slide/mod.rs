@@ -5,6 +5,8 @@ mod parse;
 #[allow(clippy::redundant_pub_crate)]
 mod parse_pres;
 #[allow(clippy::redundant_pub_crate)]
+mod props;
+#[allow(clippy::redundant_pub_crate)]
 mod query;
 mod types;
 #[allow(clippy::redundant_pub_crate)]
@@ -29,6 +31,9 @@ pub(crate) use parse_pres::{
     parse_layout_name, parse_slide_ids, parse_slide_master_ids, parse_slide_size,
 };
 
+// Re-export slide property functions.
+pub(crate) use props::{is_hidden_slide, parse_slide_properties, write_slide_properties};
+
 // Re-export query/manipulation functions.
 pub(crate) use query::extract_layout_r_ids;
 pub use query::{get_layout_by_name, placeholder_shapes_from_layout};
@@ -36,21 +36,18 @@ fn parse_id_list(
 
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e) | Event::Empty(ref e)) => {
-                let qn = e.name();
-                let ln = local_name(qn.as_ref());
-                if ln == list_tag {
-                    in_list = true;
-                } else if ln == item_tag && in_list {
-                    let (r_id, id) = parse_sld_id_attrs(e)?;
-                    ids.push((r_id.to_string(), id));
-                }
+            Ok(Event::Start(ref e)) if local_name(e.name().as_ref()) == list_tag => {
+                in_list = true;
             }
-            Ok(Event::End(ref e)) => {
-                let qn = e.name();
-                if local_name(qn.as_ref()) == list_tag {
-                    in_list = false;
-                }
+            // Only the first list counts: sections in `<p:extLst>` have
+            // their own `<p14:sldIdLst>`
+            Ok(Event::Empty(ref e)) if local_name(e.name().as_ref()) == list_tag => break,
+            Ok(Event::End(ref e)) if local_name(e.name().as_ref()) == list_tag => break,
+            Ok(Event::Start(ref e) | Event::Empty(ref e))
+                if in_list && local_name(e.name().as_ref()) == item_tag =>
+            {
+                let (r_id, id) = parse_sld_id_attrs(e)?;
+                ids.push((r_id.to_string(), id));
             }
             Ok(Event::Eof) => break,
             Err(e) => return Err(PptxError::Xml(e)),
@@ -0,0 +1,229 @@
+//! Reading and writing the slide show attributes of a slide.
+//!
+//! These live on the `<p:sld>` root (`show`, `showMasterSp`), on the
+//! `<p:cSld>` element (`name`), and on the `<p:transition>` element
+//! (`advClick`, `advTm`).
+
+use quick_xml::escape::unescape;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::SlideId;
+use crate::xml_util::splice::{find_start_tag, get_attr, set_attr, splice};
+use crate::xml_util::xml_escape;
+
+use super::SlideProperties;
+
+/// Elements that follow `<p:transition>` in a slide.
+const AFTER_TRANSITION: &[&str] = &["p:timing", "p:extLst"];
+
+/// Parse the properties of a slide from its XML.
+///
+/// `slide_id` and `has_notes_slide` come from the presentation and the
+/// slide's relationships, not from the slide XML.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8, the name cannot be unescaped,
+/// or the advance time is not a number.
+pub(crate) fn parse_slide_properties(
+    slide_xml: &[u8],
+    slide_id: SlideId,
+    has_notes_slide: bool,
+) -> PptxResult<SlideProperties> {
+    let xml = std::str::from_utf8(slide_xml)?;
+    let root = find_start_tag(xml, "p:sld", 0).map(|tag| &xml[tag]);
+    let name = find_start_tag(xml, "p:cSld", 0)
+        .and_then(|tag| get_attr(&xml[tag], "name"))
+        .map(unescape)
+        .transpose()
+        .map_err(|e| PptxError::InvalidXml(e.to_string()))?
+        .unwrap_or_default()
+        .into_owned();
+    let transition = find_start_tag(xml, "p:transition", 0).map(|tag| &xml[tag]);
+    let advance_after = transition
+        .and_then(|tag| get_attr(tag, "advTm"))
+        .map(|value| {
+            value.parse().map_err(|_| PptxError::InvalidValue {
+                field: "advTm",
+                value: value.to_string(),
+                expected: "a non-negative number of milliseconds",
+            })
+        })
+        .transpose()?;
+
+    Ok(SlideProperties {
+        slide_id,
+        name,
+        has_notes_slide,
+        hidden: !bool_attr(root, "show", true),
+        show_master_shapes: bool_attr(root, "showMasterSp", true),
+        advance_on_click: bool_attr(transition, "advClick", true),
+        advance_after,
+    })
+}
+
+/// Write the name, visibility, master shapes flag and advance settings of
+/// `props` to a slide XML.
+///
+/// Attributes that hold their default value are removed. A `<p:transition>`
+/// is added only when the advance settings need one; every existing
+/// transition, including the alternatives of an `mc:AlternateContent`, is
+/// updated.
+///
+/// # Errors
+///
+/// Returns an error if the XML is not UTF-8 or has no `<p:sld>` root.
+pub(crate) fn write_slide_properties(
+    slide_xml: &[u8],
+    props: &SlideProperties,
+) -> PptxResult<Vec<u8>> {
+    let mut xml = std::str::from_utf8(slide_xml)?.to_string();
+
+    let root = find_start_tag(&xml, "p:sld", 0)
+        .ok_or_else(|| PptxError::InvalidXml("slide XML has no <p:sld> root".to_string()))?;
+    let mut tag = set_attr(&xml[root.clone()], "show", props.hidden.then_some("0"));
+    tag = set_attr(
+        &tag,
+        "showMasterSp",
+        (!props.show_master_shapes).then_some("0"),
+    );
+    xml = splice(&xml, root, &tag);
+
+    if let Some(c_sld) = find_start_tag(&xml, "p:cSld", 0) {
+        let name = xml_escape(&props.name);
+        let tag = set_attr(
+            &xml[c_sld.clone()],
+            "name",
+            (!name.is_empty()).then_some(name.as_str()),
+        );
+        xml = splice(&xml, c_sld, &tag);
+    }
+
+    let adv_tm = props.advance_after.map(|ms| ms.to_string());
+    let adv_click = (!props.advance_on_click).then_some("0");
+    let mut from = 0;
+    let mut found = false;
+    while let Some(transition) = find_start_tag(&xml, "p:transition", from) {
+        let original = &xml[transition.clone()];
+        let mut tag = set_attr(original, "advClick", adv_click);
+        tag = set_attr(&tag, "advTm", adv_tm.as_deref());
+        found = true;
+        // A transition left with nothing but the defaults was only there
+        // for the advance settings
+        if tag == "<p:transition/>" && tag != original {
+            from = transition.start;
+            xml = splice(&xml, transition, "");
+            continue;
+        }
+        from = transition.start + tag.len();
+        xml = splice(&xml, transition, &tag);
+    }
+    if !found && (adv_tm.is_some() || adv_click.is_some()) {
+        let mut transition = String::from("<p:transition");
+        if let Some(adv_click) = adv_click {
+            transition.push_str(&format!(r#" advClick="{adv_click}""#));
+        }
+        if let Some(adv_tm) = &adv_tm {
+            transition.push_str(&format!(r#" advTm="{adv_tm}""#));
+        }
+        transition.push_str("/>");
+
+        // Top-level elements after the transition follow the common slide data
+        let c_sld_end = xml.find("</p:cSld>").unwrap_or(0);
+        let at = AFTER_TRANSITION
+            .iter()
+            .filter_map(|qname| find_start_tag(&xml, qname, c_sld_end).map(|tag| tag.start))
+            .min()
+            .or_else(|| xml.rfind("</p:sld>"))
+            .ok_or_else(|| {
+                PptxError::InvalidXml("slide XML does not contain </p:sld>".to_string())
+            })?;
+        xml.insert_str(at, &transition);
+    }
+
+    Ok(xml.into_bytes())
+}
+
+/// Whether a slide XML hides the slide in slide shows.
+pub(crate) fn is_hidden_slide(slide_xml: &str) -> bool {
+    let root = find_start_tag(slide_xml, "p:sld", 0).map(|tag| &slide_xml[tag]);
+    !bool_attr(root, "show", true)
+}
+
+/// Read the `xsd:boolean` attribute `name` of a start tag.
+fn bool_attr(tag: Option<&str>, name: &str, default: bool) -> bool {
+    match tag.and_then(|tag| get_attr(tag, name)) {
+        Some("0" | "false") => false,
+        Some("1" | "true") => true,
+        _ => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLIDE: &str = r#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree/></p:cSld><p:clrMapOvr><a:masterClrMapping/></p:clrMapOvr><p:timing/></p:sld>"#;
+
+    #[test]
+    fn test_parse_defaults() {
+        let props = parse_slide_properties(SLIDE.as_bytes(), SlideId(256), false).unwrap();
+        assert_eq!(props.slide_id, SlideId(256));
+        assert_eq!(props.name, "");
+        assert!(!props.hidden);
+        assert!(props.show_master_shapes);
+        assert!(props.advance_on_click);
+        assert_eq!(props.advance_after, None);
+        assert!(!is_hidden_slide(SLIDE));
+    }
+
+    #[test]
+    fn test_write_and_parse() {
+        let mut props = parse_slide_properties(SLIDE.as_bytes(), SlideId(256), false).unwrap();
+        props.name = "Q&A".to_string();
+        props.hidden = true;
+        props.show_master_shapes = false;
+        props.advance_after = Some(4000);
+        props.advance_on_click = false;
+        let xml = write_slide_properties(SLIDE.as_bytes(), &props).unwrap();
+        let text = std::str::from_utf8(&xml).unwrap();
+        assert!(text.starts_with(r#"<p:sld xmlns:a="#));
+        assert!(text.contains(r#" show="0" showMasterSp="0">"#));
+        assert!(text.contains(r#"<p:cSld name="Q&amp;A">"#));
+        assert!(
+            text.contains(r#"</p:clrMapOvr><p:transition advClick="0" advTm="4000"/><p:timing/>"#)
+        );
+        assert!(is_hidden_slide(text));
+        assert_eq!(
+            parse_slide_properties(&xml, SlideId(256), false).unwrap(),
+            props
+        );
+
+        // Writing the defaults back removes the attributes
+        let defaults = parse_slide_properties(SLIDE.as_bytes(), SlideId(256), false).unwrap();
+        let xml = write_slide_properties(&xml, &defaults).unwrap();
+        assert_eq!(std::str::from_utf8(&xml).unwrap(), SLIDE);
+    }
+
+    #[test]
+    fn test_write_updates_every_transition() {
+        let slide = SLIDE.replace(
+            "<p:timing/>",
+            r#"<mc:AlternateContent><mc:Choice Requires="p14"><p:transition spd="slow" p14:dur="2000" advTm="1000"><p:fade/></p:transition></mc:Choice><mc:Fallback><p:transition spd="slow" advTm="1000"><p:fade/></p:transition></mc:Fallback></mc:AlternateContent><p:timing/>"#,
+        );
+        let mut props = parse_slide_properties(slide.as_bytes(), SlideId(256), false).unwrap();
+        assert_eq!(props.advance_after, Some(1000));
+        props.advance_after = Some(2500);
+        let xml = write_slide_properties(slide.as_bytes(), &props).unwrap();
+        let text = std::str::from_utf8(&xml).unwrap();
+        assert_eq!(text.matches(r#"advTm="2500""#).count(), 2);
+        assert_eq!(text.matches("<p:transition").count(), 2);
+        assert!(text.contains(r#"<p:transition spd="slow" p14:dur="2000" advTm="2500">"#));
+    }
+
+    #[test]
+    fn test_parse_invalid_advance_time() {
+        let slide = SLIDE.replace("<p:timing/>", r#"<p:transition advTm="soon"/>"#);
+        assert!(parse_slide_properties(slide.as_bytes(), SlideId(256), false).is_err());
+    }
+}
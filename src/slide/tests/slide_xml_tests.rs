@@ -12,6 +12,16 @@ fn test_parse_slide_ids_empty() {
     assert!(ids.is_empty());
 }
 
+#[test]
+fn test_parse_slide_ids_ignores_sections() {
+    let xml = br#"<p:presentation xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+<p:sldIdLst><p:sldId id="256" r:id="rId2"/></p:sldIdLst>
+<p:extLst><p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}"><p14:sectionLst xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main"><p14:section name="A" id="{00000000-0000-0000-0000-000000000000}"><p14:sldIdLst><p14:sldId id="256"/></p14:sldIdLst></p14:section></p14:sectionLst></p:ext></p:extLst>
+</p:presentation>"#;
+    let ids = parse_slide_ids(xml).unwrap();
+    assert_eq!(ids, vec![("rId2".to_string(), SlideId(256))]);
+}
+
 #[test]
 fn test_parse_slide_master_ids() {
     let xml = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
@@ -186,10 +196,16 @@ fn test_slide_properties_struct() {
         slide_id: SlideId(256),
         name: "Test Slide".to_string(),
         has_notes_slide: true,
+        hidden: false,
+        show_master_shapes: true,
+        advance_on_click: true,
+        advance_after: Some(3000),
     };
     assert_eq!(props.slide_id, SlideId(256));
     assert_eq!(props.name, "Test Slide");
     assert!(props.has_notes_slide);
+    assert!(!props.hidden);
+    assert_eq!(props.advance_after, Some(3000));
 }
 
 // =========================================================
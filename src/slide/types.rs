@@ -58,7 +58,7 @@ pub struct NotesMasterRef {
 }
 
 /// Properties of a slide.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SlideProperties {
     /// The numeric slide ID from the presentation XML.
     pub slide_id: SlideId,
@@ -66,6 +66,17 @@ pub struct SlideProperties {
     pub name: String,
     /// Whether this slide has an associated notes slide.
     pub has_notes_slide: bool,
+    /// Whether the slide is skipped in slide shows (`<p:sld show="0">`).
+    pub hidden: bool,
+    /// Whether the shapes of the slide master and layout are shown on the
+    /// slide (`showMasterSp`).
+    pub show_master_shapes: bool,
+    /// Whether a mouse click advances to the next slide (`advClick` of
+    /// `<p:transition>`).
+    pub advance_on_click: bool,
+    /// Auto-advance to the next slide after this many milliseconds (`advTm`
+    /// of `<p:transition>`).
+    pub advance_after: Option<u32>,
 }
 
 /// A parsed notes slide with its shapes and text content.
@@ -3,8 +3,10 @@
 use crate::dml::color::ColorFormat;
 use crate::dml::fill::FillFormat;
 use crate::enums::text::MsoVerticalAnchor;
+use crate::shapes::parser::parse_line_from_xml;
 use crate::text::TextFrame;
 use crate::units::Emu;
+use crate::xml_util::splice::{find_element, set_attr, splice};
 use crate::xml_util::WriteXml;
 
 /// A table cell.
@@ -39,6 +41,8 @@ pub struct Cell {
 }
 
 /// Border formatting for the four sides of a cell.
+///
+/// `None` leaves a side to the table style.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct CellBorders {
     pub left: Option<CellBorder>,
@@ -50,10 +54,37 @@ pub struct CellBorders {
 /// A single cell border.
 #[derive(Debug, Clone, PartialEq)]
 pub struct CellBorder {
-    /// Border color.
-    pub color: ColorFormat,
+    /// Border color, or `None` for no line (`<a:noFill/>`), which hides
+    /// the border the table style would draw.
+    pub color: Option<ColorFormat>,
     /// Border width in EMU.
     pub width: Emu,
+    /// The `<a:lnL>`, `<a:lnR>`, ... element the border was read from.
+    /// Line properties that are not modeled, such as the dash style and
+    /// compound line type, are written back from it.
+    pub(crate) xml: Option<String>,
+}
+
+impl CellBorder {
+    /// Create a solid border of the given color and width.
+    #[must_use]
+    pub const fn solid(color: ColorFormat, width: Emu) -> Self {
+        Self {
+            color: Some(color),
+            width,
+            xml: None,
+        }
+    }
+
+    /// Create a border with no line.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            color: None,
+            width: Emu(12700),
+            xml: None,
+        }
+    }
 }
 
 impl Cell {
@@ -232,9 +263,52 @@ fn write_border_xml<W: std::fmt::Write>(
     tag: &str,
     border: &CellBorder,
 ) -> std::fmt::Result {
+    if let Some(xml) = &border.xml {
+        return w.write_str(&patch_border_xml(xml, tag, border));
+    }
     write!(w, r#"<{} w="{}">"#, tag, border.width.0)?;
-    w.write_str("<a:solidFill>")?;
-    border.color.write_xml(w)?;
-    w.write_str("</a:solidFill>")?;
+    w.write_str(&border_fill_xml(border.color.as_ref()))?;
     write!(w, "</{tag}>")
 }
+
+/// The line fill of a border: a solid color, or no line.
+fn border_fill_xml(color: Option<&ColorFormat>) -> String {
+    color.map_or_else(
+        || "<a:noFill/>".to_string(),
+        |color| format!("<a:solidFill>{}</a:solidFill>", color.to_xml_string()),
+    )
+}
+
+/// The element a border was read from, as `tag`, with the border's width
+/// and, if it changed, its color.
+fn patch_border_xml(xml: &str, tag: &str, border: &CellBorder) -> String {
+    let open_end = xml.find('>').map_or(xml.len(), |gt| gt + 1);
+    let name_len = xml[1..open_end]
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(0);
+    let open = set_attr(&xml[..open_end], "w", Some(&border.width.0.to_string()));
+    let attrs = open[1 + name_len..]
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    let mut inner = if open.ends_with("/>") {
+        String::new()
+    } else {
+        let close = xml.rfind("</").unwrap_or(xml.len()).max(open_end);
+        xml[open_end..close].to_string()
+    };
+
+    let color = parse_line_from_xml(xml.as_bytes())
+        .ok()
+        .flatten()
+        .and_then(|line| line.color);
+    if border.color != color {
+        // The line fill comes first in a line's properties
+        let fill = ["a:noFill", "a:solidFill", "a:gradFill", "a:pattFill"]
+            .iter()
+            .filter_map(|qname| find_element(&inner, qname, 0))
+            .min_by_key(|range| range.start)
+            .unwrap_or(0..0);
+        inner = splice(&inner, fill, &border_fill_xml(border.color.as_ref()));
+    }
+    format!("<{tag}{attrs}>{inner}</{tag}>")
+}
@@ -8,6 +8,7 @@
 //! for its text content.
 
 mod cell;
+mod parse;
 
 pub use cell::{Cell, CellBorder, CellBorders};
 
@@ -55,7 +56,7 @@ pub struct Table {
     /// Whether columns should have alternating shading.
     pub vert_banding: bool,
     /// Table style GUID (e.g. `"{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}"`).
-    /// When set, emitted as `<a:tblStyleId>` inside `<a:tblPr>`.
+    /// When set, emitted as `<a:tableStyleId>` inside `<a:tblPr>`.
     pub table_style_id: Option<String>,
 }
 
@@ -202,7 +203,7 @@ impl Table {
         }
         if let Some(ref style_id) = self.table_style_id {
             w.write_char('>')?;
-            write!(w, "<a:tblStyleId>{style_id}</a:tblStyleId>")?;
+            write!(w, "<a:tableStyleId>{style_id}</a:tableStyleId>")?;
             w.write_str("</a:tblPr>")?;
         } else {
             w.write_str("/>")?;
@@ -0,0 +1,227 @@
+//! Parsing of `<a:tbl>` XML into the [`Table`] model.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::enums::text::MsoVerticalAnchor;
+use crate::error::{PptxError, PptxResult};
+use crate::shapes::parser::{parse_fill_from_xml, parse_line_from_xml, parse_text_frame_from_xml};
+use crate::units::Emu;
+use crate::xml_util::{attr_value, emu_attr, local_name_str, offset};
+
+use super::{Cell, CellBorder, Column, Row, Table};
+
+/// Read an `xsd:boolean` attribute (`"1"` or `"true"`).
+fn bool_attr(e: &BytesStart<'_>, key: &[u8]) -> PptxResult<bool> {
+    Ok(matches!(attr_value(e, key)?.as_deref(), Some("1" | "true")))
+}
+
+fn apply_tbl_pr(e: &BytesStart<'_>, table: &mut Table) -> PptxResult<()> {
+    table.first_row = bool_attr(e, b"firstRow")?;
+    table.first_col = bool_attr(e, b"firstCol")?;
+    table.last_row = bool_attr(e, b"lastRow")?;
+    table.last_col = bool_attr(e, b"lastCol")?;
+    table.horz_banding = bool_attr(e, b"bandRow")?;
+    table.vert_banding = bool_attr(e, b"bandCol")?;
+    Ok(())
+}
+
+/// Start a cell from the attributes of its `<a:tc>` element.
+fn new_cell(e: &BytesStart<'_>) -> PptxResult<Cell> {
+    let span = |key: &[u8]| -> PptxResult<u32> {
+        Ok(attr_value(e, key)?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1))
+    };
+    Ok(Cell {
+        grid_span: span(b"gridSpan")?,
+        row_span: span(b"rowSpan")?,
+        h_merge: bool_attr(e, b"hMerge")?,
+        v_merge: bool_attr(e, b"vMerge")?,
+        // Margins are only set when the file specifies them.
+        margin_left: None,
+        margin_right: None,
+        margin_top: None,
+        margin_bottom: None,
+        ..Cell::new()
+    })
+}
+
+fn apply_tc_pr_attrs(e: &BytesStart<'_>, cell: &mut Cell) -> PptxResult<()> {
+    cell.margin_left = emu_attr(e, b"marL")?;
+    cell.margin_right = emu_attr(e, b"marR")?;
+    cell.margin_top = emu_attr(e, b"marT")?;
+    cell.margin_bottom = emu_attr(e, b"marB")?;
+    cell.vertical_anchor =
+        attr_value(e, b"anchor")?.and_then(|v| MsoVerticalAnchor::from_xml_str(&v));
+    Ok(())
+}
+
+/// Parse the borders and fill from the children of a `<a:tcPr>` element.
+fn apply_tc_pr_children(xml: &[u8], cell: &mut Cell) -> PptxResult<()> {
+    let mut reader = Reader::from_reader(xml);
+    let mut depth = 0usize;
+    loop {
+        let start = offset(&reader);
+        let (e, is_empty) = match reader.read_event()? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(_) => {
+                depth = depth.saturating_sub(1);
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        if depth != 1 {
+            depth += usize::from(!is_empty);
+            continue;
+        }
+        if !is_empty {
+            reader.read_to_end(e.name())?;
+        }
+        let element = &xml[start..offset(&reader)];
+        let qname = e.name();
+        let local = local_name_str(qname.as_ref());
+        match local {
+            "lnL" | "lnR" | "lnT" | "lnB" => {
+                // Every border is kept, including one that turns the line
+                // off, with the XML it was read from
+                let border = CellBorder {
+                    color: parse_line_from_xml(element)?.and_then(|line| line.color),
+                    width: emu_attr(&e, b"w").ok().flatten().unwrap_or(Emu(12700)),
+                    xml: Some(String::from_utf8_lossy(element).into_owned()),
+                };
+                let side = match local {
+                    "lnL" => &mut cell.borders.left,
+                    "lnR" => &mut cell.borders.right,
+                    "lnT" => &mut cell.borders.top,
+                    _ => &mut cell.borders.bottom,
+                };
+                *side = Some(border);
+            }
+            "noFill" | "solidFill" | "gradFill" | "pattFill" | "blipFill" | "grpFill" => {
+                cell.fill = parse_fill_from_xml(element)?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl Table {
+    /// Parse a table from XML containing an `<a:tbl>` element.
+    ///
+    /// `xml` may be the `<a:tbl>` element itself or any fragment that
+    /// contains one, such as a whole `<p:graphicFrame>`. Attributes that
+    /// are absent in the XML are left unset (e.g. cell margins are `None`
+    /// and all banding flags `false`), so that writing the table back does
+    /// not introduce formatting the original did not have.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed or contains no `<a:tbl>` element.
+    pub fn from_xml(xml: &[u8]) -> PptxResult<Self> {
+        let mut reader = Reader::from_reader(xml);
+        let mut table: Option<Self> = None;
+        let mut row: Option<Row> = None;
+        let mut cell: Option<Cell> = None;
+
+        loop {
+            let start = offset(&reader);
+            match reader.read_event()? {
+                Event::Start(ref e) => {
+                    let qname = e.name();
+                    match (local_name_str(qname.as_ref()), table.as_mut()) {
+                        ("tbl", None) => table = Some(Self::empty()),
+                        ("tblPr", Some(t)) => apply_tbl_pr(e, t)?,
+                        // `tblStyleId` is accepted for tables written by
+                        // older versions of this crate.
+                        ("tableStyleId" | "tblStyleId", Some(t)) => {
+                            let id = reader.read_text(e.name())?;
+                            t.table_style_id = Some(id.trim().to_string());
+                        }
+                        ("tr", Some(_)) => {
+                            row = Some(Row {
+                                height: emu_attr(e, b"h")?.unwrap_or(Emu(0)),
+                                cells: Vec::new(),
+                            });
+                        }
+                        ("tc", Some(_)) => cell = Some(new_cell(e)?),
+                        ("txBody", Some(_)) => {
+                            if let Some(c) = cell.as_mut() {
+                                reader.read_to_end(e.name())?;
+                                let body = &xml[start..offset(&reader)];
+                                c.text_frame = parse_text_frame_from_xml(body)?.unwrap_or_default();
+                            }
+                        }
+                        ("tcPr", Some(_)) => {
+                            if let Some(c) = cell.as_mut() {
+                                apply_tc_pr_attrs(e, c)?;
+                                reader.read_to_end(e.name())?;
+                                apply_tc_pr_children(&xml[start..offset(&reader)], c)?;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Empty(ref e) => {
+                    let qname = e.name();
+                    match (local_name_str(qname.as_ref()), table.as_mut()) {
+                        ("tblPr", Some(t)) => apply_tbl_pr(e, t)?,
+                        ("gridCol", Some(t)) => t.columns.push(Column {
+                            width: emu_attr(e, b"w")?.unwrap_or(Emu(0)),
+                        }),
+                        ("tc", Some(_)) => {
+                            if let Some(r) = row.as_mut() {
+                                r.cells.push(new_cell(e)?);
+                            }
+                        }
+                        ("tcPr", Some(_)) => {
+                            if let Some(c) = cell.as_mut() {
+                                apply_tc_pr_attrs(e, c)?;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(ref e) => {
+                    let qname = e.name();
+                    match local_name_str(qname.as_ref()) {
+                        "tc" => {
+                            if let (Some(r), Some(c)) = (row.as_mut(), cell.take()) {
+                                r.cells.push(c);
+                            }
+                        }
+                        "tr" => {
+                            if let (Some(t), Some(r)) = (table.as_mut(), row.take()) {
+                                t.rows.push(r);
+                            }
+                        }
+                        "tbl" if table.is_some() => break,
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        table.ok_or_else(|| PptxError::InvalidXml("no <a:tbl> element found".to_string()))
+    }
+
+    /// A table with no rows, no columns and all flags cleared.
+    const fn empty() -> Self {
+        Self {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            first_row: false,
+            first_col: false,
+            last_row: false,
+            last_col: false,
+            horz_banding: false,
+            vert_banding: false,
+            table_style_id: None,
+        }
+    }
+}
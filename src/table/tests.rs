@@ -108,14 +108,13 @@ fn test_cell_with_fill() {
 #[test]
 fn test_cell_with_borders() {
     let mut cell = Cell::new();
-    cell.borders.top = Some(CellBorder {
-        color: ColorFormat::rgb(0, 0, 0),
-        width: Emu(12700),
-    });
+    cell.borders.top = Some(CellBorder::solid(ColorFormat::rgb(0, 0, 0), Emu(12700)));
+    cell.borders.left = Some(CellBorder::none());
     let xml = cell.to_xml_string();
     assert!(xml.contains("<a:lnT"));
     assert!(xml.contains(r#"w="12700""#));
     assert!(xml.contains("000000"));
+    assert!(xml.contains(r#"<a:lnL w="12700"><a:noFill/></a:lnL>"#));
 }
 
 #[test]
@@ -202,7 +201,7 @@ fn test_cell_split_xml_no_span_attrs() {
 fn test_table_no_style_id() {
     let t = Table::new(1, 1, Emu(2000000), Emu(300000));
     let xml = t.to_xml_string();
-    assert!(!xml.contains("tblStyleId"));
+    assert!(!xml.contains("tableStyleId"));
     // tblPr should be self-closing
     assert!(xml.contains("<a:tblPr"));
     assert!(xml.contains("/>"));
@@ -213,7 +212,7 @@ fn test_table_with_style_id() {
     let mut t = Table::new(1, 1, Emu(2000000), Emu(300000));
     t.table_style_id = Some("{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}".to_string());
     let xml = t.to_xml_string();
-    assert!(xml.contains("<a:tblStyleId>{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}</a:tblStyleId>"));
+    assert!(xml.contains("<a:tableStyleId>{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}</a:tableStyleId>"));
     assert!(xml.contains("</a:tblPr>"));
 }
 
@@ -252,3 +251,101 @@ fn test_cell_vertical_anchor_bottom() {
     let xml = cell.to_xml_string();
     assert!(xml.contains(r#"anchor="b""#));
 }
+
+const TABLE_FRAME: &str = r#"<p:graphicFrame xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><p:nvGraphicFramePr><p:cNvPr id="4" name="Table 3"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr><p:xfrm><a:off x="0" y="0"/><a:ext cx="4000000" cy="740000"/></p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"><a:tbl><a:tblPr firstRow="1" bandRow="1"><a:tableStyleId>{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}</a:tableStyleId></a:tblPr><a:tblGrid><a:gridCol w="1000000"/><a:gridCol w="3000000"/></a:tblGrid><a:tr h="370000"><a:tc gridSpan="2"><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>Header</a:t></a:r></a:p></a:txBody><a:tcPr marL="45720" anchor="ctr"><a:lnB w="25400"><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:lnB><a:solidFill><a:srgbClr val="00FF00"/></a:solidFill></a:tcPr></a:tc><a:tc hMerge="1"><a:txBody><a:bodyPr/><a:lstStyle/><a:p/></a:txBody><a:tcPr/></a:tc></a:tr><a:tr h="370000"><a:tc><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>A</a:t></a:r></a:p></a:txBody><a:tcPr/></a:tc><a:tc><a:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="en-US"/><a:t>B</a:t></a:r></a:p></a:txBody><a:tcPr/></a:tc></a:tr></a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#;
+
+#[test]
+fn test_table_from_xml_structure() {
+    let t = Table::from_xml(TABLE_FRAME.as_bytes()).unwrap();
+    assert_eq!(t.row_count(), 2);
+    assert_eq!(t.col_count(), 2);
+    assert_eq!(t.columns[1].width, Emu(3000000));
+    assert_eq!(t.rows[0].height, Emu(370000));
+    assert_eq!(t.cell(0, 0).text(), "Header");
+    assert_eq!(t.cell(1, 1).text(), "B");
+}
+
+#[test]
+fn test_table_from_xml_properties() {
+    let t = Table::from_xml(TABLE_FRAME.as_bytes()).unwrap();
+    assert!(t.first_row);
+    assert!(t.horz_banding);
+    assert!(!t.first_col);
+    assert!(!t.vert_banding);
+    assert_eq!(
+        t.table_style_id.as_deref(),
+        Some("{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}")
+    );
+}
+
+#[test]
+fn test_table_from_xml_cell_formatting() {
+    let t = Table::from_xml(TABLE_FRAME.as_bytes()).unwrap();
+    let origin = t.cell(0, 0);
+    assert_eq!(origin.grid_span, 2);
+    assert!(origin.is_merge_origin());
+    assert!(t.cell(0, 1).is_spanned());
+    assert_eq!(origin.margin_left, Some(Emu(45720)));
+    assert!(origin.margin_right.is_none());
+    assert_eq!(origin.vertical_anchor, Some(MsoVerticalAnchor::Middle));
+    let bottom = origin.borders.bottom.as_ref().unwrap();
+    assert_eq!(bottom.width, Emu(25400));
+    assert_eq!(bottom.color, Some(ColorFormat::rgb(0xFF, 0, 0)));
+    assert!(origin.borders.top.is_none());
+    assert_eq!(
+        origin.fill,
+        Some(FillFormat::solid(ColorFormat::rgb(0, 0xFF, 0)))
+    );
+}
+
+#[test]
+fn test_table_from_xml_keeps_unmodeled_borders() {
+    let no_line = r#"<a:lnL w="12700" cmpd="sng"><a:noFill/></a:lnL>"#;
+    let dashed = r#"<a:lnT w="25400" cmpd="dbl"><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill><a:prstDash val="dash"/></a:lnT>"#;
+    let xml = TABLE_FRAME.replace(
+        r#"<a:tcPr marL="45720" anchor="ctr">"#,
+        &format!(r#"<a:tcPr marL="45720" anchor="ctr">{no_line}{dashed}"#),
+    );
+    let mut t = Table::from_xml(xml.as_bytes()).unwrap();
+    let borders = &t.cell(0, 0).borders;
+    assert_eq!(borders.left.as_ref().unwrap().color, None);
+    assert_eq!(
+        borders.top.as_ref().unwrap().color,
+        Some(ColorFormat::rgb(0xFF, 0, 0))
+    );
+
+    // Borders that were not edited are written back as they were
+    let cell_xml = t.cell(0, 0).to_xml_string();
+    assert!(cell_xml.contains(no_line));
+    assert!(cell_xml.contains(dashed));
+
+    // An edited border keeps its dash and compound type
+    let top = t.cell_mut(0, 0).borders.top.as_mut().unwrap();
+    top.color = Some(ColorFormat::rgb(0, 0, 0xFF));
+    top.width = Emu(12700);
+    assert!(t.cell(0, 0).to_xml_string().contains(
+        r#"<a:lnT w="12700" cmpd="dbl"><a:solidFill><a:srgbClr val="0000FF"/></a:solidFill><a:prstDash val="dash"/></a:lnT>"#
+    ));
+}
+
+#[test]
+fn test_table_from_xml_roundtrip() {
+    let mut t = Table::new(2, 3, Emu(6000000), Emu(300000));
+    t.cell_mut(1, 2).set_text("x");
+    t.cell_mut(0, 0).merge_with(2, 1);
+    t.cell_mut(0, 1).h_merge = true;
+    t.table_style_id = Some("{ABC}".to_string());
+    let parsed = Table::from_xml(t.to_xml_string().as_bytes()).unwrap();
+    assert_eq!(parsed.columns, t.columns);
+    assert_eq!(parsed.cell(1, 2).text(), "x");
+    assert_eq!(parsed.cell(0, 0).grid_span, 2);
+    assert!(parsed.cell(0, 1).h_merge);
+    assert_eq!(parsed.cell(0, 0).margin_left, t.cell(0, 0).margin_left);
+    assert_eq!(parsed.first_row, t.first_row);
+    assert_eq!(parsed.table_style_id, t.table_style_id);
+}
+
+#[test]
+fn test_table_from_xml_without_table_fails() {
+    assert!(Table::from_xml(b"<a:p/>").is_err());
+}
@@ -0,0 +1,159 @@
+//! Filling template decks from key/value data.
+//!
+//! A template is an ordinary presentation whose text holds tokens:
+//!
+//! - `{{key}}` is replaced by the text value `key`, wherever text appears:
+//!   text boxes, placeholders, group members, table cells, notes and chart
+//!   titles. The replacement keeps the formatting of the token.
+//! - `{{#key}}` in a table row repeats that row once per item of the list
+//!   `key`; tokens in the row are filled from the item first, then from the
+//!   enclosing data.
+//! - `{{#key}}` anywhere else on a slide clones the slide once per item of
+//!   the list `key`, filling each copy from its item.
+//! - A shape whose text or name is `{{key}}` is replaced by the image `key`,
+//!   keeping its position and placeholder. A picture named (or described)
+//!   `{{key}}` has its image swapped.
+//! - A chart whose name is `{{key}}` gets the chart data `key`.
+//!
+//! The closing token `{{/key}}` may be used to mark the end of a repeated
+//! section for readability; it is removed along with `{{#key}}`. Tokens with
+//! no matching value are left in place.
+//!
+//! See [`Presentation::fill_template`](crate::Presentation::fill_template).
+
+pub(crate) mod xml;
+
+use std::collections::BTreeMap;
+
+use crate::chart::ChartData;
+use crate::media::Image;
+
+/// A value that fills a template token.
+#[derive(Debug, Clone)]
+pub enum TemplateValue {
+    /// Text substituted for `{{key}}`.
+    Text(String),
+    /// Items for a repeated table row or slide, `{{#key}}`.
+    List(Vec<TemplateData>),
+    /// An image for a picture slot named `{{key}}`.
+    Image(Image),
+    /// Data for a chart named `{{key}}`.
+    Chart(ChartData),
+}
+
+/// Key/value data filling a template.
+///
+/// # Examples
+///
+/// ```
+/// use pptx::template::TemplateData;
+///
+/// let data = TemplateData::new()
+///     .with_text("customer_name", "Acme Corp")
+///     .with_list(
+///         "rows",
+///         vec![
+///             TemplateData::new().with_text("item", "Widgets").with_text("qty", "12"),
+///             TemplateData::new().with_text("item", "Gadgets").with_text("qty", "3"),
+///         ],
+///     );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateData {
+    values: BTreeMap<String, TemplateValue>,
+}
+
+impl TemplateData {
+    /// Create empty template data.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Set the value of `key`, replacing any previous value.
+    pub fn insert(&mut self, key: impl Into<String>, value: TemplateValue) {
+        self.values.insert(key.into(), value);
+    }
+
+    /// Get the value of `key`.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&TemplateValue> {
+        self.values.get(key)
+    }
+
+    /// Iterate over the keys and values, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TemplateValue)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Set a text value.
+    #[must_use]
+    pub fn with_text(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.insert(key, TemplateValue::Text(value.into()));
+        self
+    }
+
+    /// Set a list value.
+    #[must_use]
+    pub fn with_list(mut self, key: impl Into<String>, items: Vec<Self>) -> Self {
+        self.insert(key, TemplateValue::List(items));
+        self
+    }
+
+    /// Set an image value.
+    #[must_use]
+    pub fn with_image(mut self, key: impl Into<String>, image: Image) -> Self {
+        self.insert(key, TemplateValue::Image(image));
+        self
+    }
+
+    /// Set a chart data value.
+    #[must_use]
+    pub fn with_chart(mut self, key: impl Into<String>, data: ChartData) -> Self {
+        self.insert(key, TemplateValue::Chart(data));
+        self
+    }
+
+    /// These values overlaid with those of `item`, for filling a repeated
+    /// section.
+    pub(crate) fn scoped(&self, item: &Self) -> Self {
+        let mut scope = self.clone();
+        scope
+            .values
+            .extend(item.values.iter().map(|(k, v)| (k.clone(), v.clone())));
+        scope
+    }
+
+    /// The text values.
+    pub(crate) fn texts(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.iter().filter_map(|(k, v)| match v {
+            TemplateValue::Text(text) => Some((k, text.as_str())),
+            _ => None,
+        })
+    }
+
+    /// The list values.
+    pub(crate) fn lists(&self) -> impl Iterator<Item = (&str, &[Self])> {
+        self.iter().filter_map(|(k, v)| match v {
+            TemplateValue::List(items) => Some((k, items.as_slice())),
+            _ => None,
+        })
+    }
+}
+
+/// `{{key}}`.
+pub(crate) fn token(key: &str) -> String {
+    format!("{{{{{key}}}}}")
+}
+
+/// `{{#key}}`.
+pub(crate) fn section_start(key: &str) -> String {
+    format!("{{{{#{key}}}}}")
+}
+
+/// `{{/key}}`.
+pub(crate) fn section_end(key: &str) -> String {
+    format!("{{{{/{key}}}}}")
+}
@@ -0,0 +1,158 @@
+//! Slide XML edits for filling templates.
+
+use crate::error::PptxResult;
+use crate::text::search::{find_in_xml, replace_in_xml, ReplaceOptions};
+use crate::xml_util::splice::{find_element, find_element_in, find_start_tag, set_attr, splice};
+
+use super::{section_end, section_start, token, TemplateData};
+
+/// Whether `xml` contains `pattern` in the text of its paragraphs.
+fn contains_text(xml: &str, pattern: &str) -> PptxResult<bool> {
+    Ok(!find_in_xml(xml, pattern, ReplaceOptions::new())?.is_empty())
+}
+
+/// Replace the `{{key}}` token of every text value in `data`.
+pub(crate) fn fill_texts(xml: &str, data: &TemplateData) -> PptxResult<String> {
+    let mut xml = xml.to_string();
+    for (key, value) in data.texts() {
+        xml = replace_in_xml(&xml, &token(key), value, ReplaceOptions::new())?.0;
+    }
+    Ok(xml)
+}
+
+/// Remove the `{{#key}}` and `{{/key}}` tokens.
+pub(crate) fn remove_section_tokens(xml: &str, key: &str) -> PptxResult<String> {
+    let xml = replace_in_xml(xml, &section_start(key), "", ReplaceOptions::new())?.0;
+    Ok(replace_in_xml(&xml, &section_end(key), "", ReplaceOptions::new())?.0)
+}
+
+/// Whether `{{#key}}` appears in slide XML outside any table, making the
+/// whole slide a repeated section.
+pub(crate) fn has_slide_section(slide_xml: &str, key: &str) -> PptxResult<bool> {
+    let mut xml = slide_xml.to_string();
+    while let Some(table) = find_element(&xml, "a:tbl", 0) {
+        xml = splice(&xml, table, "");
+    }
+    contains_text(&xml, &section_start(key))
+}
+
+/// Repeat each table row holding `{{#key}}` once per item of the list
+/// `key` in `data`, filling each copy from its item.
+///
+/// The graphic frame of a table that gained or lost rows is resized to the
+/// new total row height.
+pub(crate) fn repeat_table_rows(slide_xml: &str, data: &TemplateData) -> PptxResult<String> {
+    let mut out = String::with_capacity(slide_xml.len());
+    let mut cursor = 0;
+    while let Some(frame) = find_element(slide_xml, "p:graphicFrame", cursor) {
+        out.push_str(&slide_xml[cursor..frame.start]);
+        let frame_xml = &slide_xml[frame.clone()];
+        match repeat_rows_in_frame(frame_xml, data)? {
+            Some(updated) => out.push_str(&updated),
+            None => out.push_str(frame_xml),
+        }
+        cursor = frame.end;
+    }
+    out.push_str(&slide_xml[cursor..]);
+    Ok(out)
+}
+
+/// The frame with its repeated rows expanded, or `None` if its table has
+/// none (or it holds no table).
+fn repeat_rows_in_frame(frame_xml: &str, data: &TemplateData) -> PptxResult<Option<String>> {
+    let Some(table) = find_element(frame_xml, "a:tbl", 0) else {
+        return Ok(None);
+    };
+    let mut table_xml = String::new();
+    let mut cursor = table.start;
+    let mut changed = false;
+    while let Some(row) = find_element_in(frame_xml, "a:tr", cursor..table.end) {
+        table_xml.push_str(&frame_xml[cursor..row.start]);
+        let row_xml = &frame_xml[row.clone()];
+        let mut section = None;
+        for (key, items) in data.lists() {
+            if contains_text(row_xml, &section_start(key))? {
+                section = Some((key, items));
+                break;
+            }
+        }
+        match section {
+            Some((key, items)) => {
+                let row_xml = remove_section_tokens(row_xml, key)?;
+                for item in items {
+                    table_xml.push_str(&fill_texts(&row_xml, &data.scoped(item))?);
+                }
+                changed = true;
+            }
+            None => table_xml.push_str(row_xml),
+        }
+        cursor = row.end;
+    }
+    if !changed {
+        return Ok(None);
+    }
+    table_xml.push_str(&frame_xml[cursor..table.end]);
+
+    let height = row_heights(&table_xml);
+    let mut frame_xml = splice(frame_xml, table, &table_xml);
+    let ext = find_element(&frame_xml, "p:xfrm", 0).and_then(|xfrm| {
+        find_start_tag(&frame_xml, "a:ext", xfrm.start).filter(|e| e.end <= xfrm.end)
+    });
+    if let Some(ext) = ext {
+        let tag = set_attr(&frame_xml[ext.clone()], "cy", Some(&height.to_string()));
+        frame_xml = splice(&frame_xml, ext, &tag);
+    }
+    Ok(Some(frame_xml))
+}
+
+/// Sum of the `h` attributes of the rows in `table_xml`.
+fn row_heights(table_xml: &str) -> i64 {
+    let mut total = 0;
+    let mut pos = 0;
+    while let Some(tag) = find_start_tag(table_xml, "a:tr", pos) {
+        let tag_xml = &table_xml[tag.clone()];
+        total += tag_xml
+            .split_once(" h=\"")
+            .and_then(|(_, rest)| rest.split('"').next())
+            .and_then(|h| h.parse::<i64>().ok())
+            .unwrap_or(0);
+        pos = tag.end;
+    }
+    total
+}
+
+/// A `<p:pic>` showing image `r_id` in place of `shape_xml`.
+///
+/// The shape's ID, name, placeholder reference and shape properties are
+/// kept, so a picture replacing a placeholder inherits its position.
+pub(crate) fn picture_in_place_of(shape_xml: &str, r_id: &str) -> String {
+    let element = |qname: &str, default: &'static str| {
+        find_element(shape_xml, qname, 0).map_or(default, |r| &shape_xml[r])
+    };
+    let nv_pr = element("p:nvPr", "<p:nvPr/>");
+    let locks = if find_start_tag(nv_pr, "p:ph", 0).is_some() {
+        r#"<a:picLocks noGrp="1" noChangeAspect="1"/>"#
+    } else {
+        r#"<a:picLocks noChangeAspect="1"/>"#
+    };
+    format!(
+        r#"<p:pic><p:nvPicPr>{}<p:cNvPicPr>{locks}</p:cNvPicPr>{nv_pr}</p:nvPicPr><p:blipFill><a:blip r:embed="{r_id}"/><a:stretch><a:fillRect/></a:stretch></p:blipFill>{}</p:pic>"#,
+        element("p:cNvPr", "<p:cNvPr/>"),
+        element("p:spPr", "<p:spPr/>"),
+    )
+}
+
+/// `picture_xml` with its image changed to `r_id`.
+pub(crate) fn with_picture_image(picture_xml: &str, r_id: &str) -> String {
+    match find_start_tag(picture_xml, "a:blip", 0) {
+        Some(blip) => {
+            let tag = set_attr(&picture_xml[blip.clone()], "r:embed", Some(r_id));
+            splice(picture_xml, blip, &tag)
+        }
+        None => picture_xml.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "xml_tests.rs"]
+mod tests;
@@ -0,0 +1,78 @@
+use super::*;
+
+fn cell(text: &str) -> String {
+    format!(
+        r#"<a:tc><a:txBody><a:bodyPr/><a:p><a:r><a:rPr lang="en-US"/><a:t>{text}</a:t></a:r></a:p></a:txBody></a:tc>"#
+    )
+}
+
+fn table_frame(rows: &[(&str, &str)]) -> String {
+    let rows = rows
+        .iter()
+        .map(|(a, b)| format!(r#"<a:tr h="370840">{}{}</a:tr>"#, cell(a), cell(b)))
+        .collect::<Vec<_>>()
+        .concat();
+    format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="4" name="Table 3"/></p:nvGraphicFramePr><p:xfrm><a:off x="0" y="0"/><a:ext cx="6096000" cy="741680"/></p:xfrm><a:graphic><a:graphicData><a:tbl><a:tblGrid><a:gridCol w="3048000"/><a:gridCol w="3048000"/></a:tblGrid>{rows}</a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#
+    )
+}
+
+fn rows_data() -> TemplateData {
+    TemplateData::new().with_text("currency", "EUR").with_list(
+        "rows",
+        vec![
+            TemplateData::new().with_text("item", "Widgets"),
+            TemplateData::new().with_text("item", "Gadgets"),
+            TemplateData::new().with_text("item", "Gizmos"),
+        ],
+    )
+}
+
+#[test]
+fn test_repeat_table_rows() {
+    let xml = table_frame(&[
+        ("Item", "Currency"),
+        ("{{#rows}}{{item}}", "{{currency}}{{/rows}}"),
+    ]);
+    let out = repeat_table_rows(&xml, &rows_data()).unwrap();
+    assert_eq!(out.matches("<a:tr ").count(), 4);
+    for item in ["Widgets", "Gadgets", "Gizmos"] {
+        assert!(out.contains(&format!("<a:t>{item}</a:t>")));
+    }
+    assert_eq!(out.matches("<a:t>EUR</a:t>").count(), 3);
+    assert!(!out.contains("{{"));
+    assert!(out.contains(r#"<a:ext cx="6096000" cy="1483360"/>"#));
+}
+
+#[test]
+fn test_repeat_table_rows_leaves_other_tables() {
+    let xml = table_frame(&[("Item", "{{currency}}")]);
+    assert_eq!(repeat_table_rows(&xml, &rows_data()).unwrap(), xml);
+}
+
+#[test]
+fn test_slide_section_ignores_tables() {
+    let table = table_frame(&[("{{#rows}}", "")]);
+    assert!(!has_slide_section(&table, "rows").unwrap());
+    let text = r#"<p:sp><p:txBody><a:p><a:r><a:t>{{#rows}}</a:t></a:r></a:p></p:txBody></p:sp>"#;
+    assert!(has_slide_section(&format!("{table}{text}"), "rows").unwrap());
+}
+
+#[test]
+fn test_picture_in_place_of_placeholder() {
+    let sp = r#"<p:sp><p:nvSpPr><p:cNvPr id="3" name="Picture Placeholder 2"/><p:cNvSpPr/><p:nvPr><p:ph type="pic" idx="1"/></p:nvPr></p:nvSpPr><p:spPr/><p:txBody><a:bodyPr/><a:p><a:r><a:t>{{logo}}</a:t></a:r></a:p></p:txBody></p:sp>"#;
+    let pic = picture_in_place_of(sp, "rId7");
+    assert_eq!(
+        pic,
+        r#"<p:pic><p:nvPicPr><p:cNvPr id="3" name="Picture Placeholder 2"/><p:cNvPicPr><a:picLocks noGrp="1" noChangeAspect="1"/></p:cNvPicPr><p:nvPr><p:ph type="pic" idx="1"/></p:nvPr></p:nvPicPr><p:blipFill><a:blip r:embed="rId7"/><a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr/></p:pic>"#
+    );
+}
+
+#[test]
+fn test_with_picture_image() {
+    let pic = r#"<p:pic><p:blipFill><a:blip r:embed="rId2"/></p:blipFill></p:pic>"#;
+    assert_eq!(
+        with_picture_image(pic, "rId9"),
+        r#"<p:pic><p:blipFill><a:blip r:embed="rId9"/></p:blipFill></p:pic>"#
+    );
+}
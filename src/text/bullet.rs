@@ -1,15 +1,423 @@
 //! Bullet format types for paragraph bullets.
 
+use crate::enums::text::MsoAutoNumberScheme;
+use crate::text::paragraph::Paragraph;
+
 /// Specifies the bullet format for a paragraph.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq)]
 pub enum BulletFormat {
     /// A character bullet (e.g. `'•'`, `'–'`).
     Character(char),
-    /// An auto-numbered bullet (e.g. `"arabicPeriod"`, `"alphaLcParenR"`).
-    AutoNumbered(String),
+    /// An auto-numbered bullet (`<a:buAutoNum>`).
+    AutoNumbered {
+        /// The numbering scheme (e.g. `1.`, `a)`, `(iv)`).
+        scheme: MsoAutoNumberScheme,
+        /// The number of the first paragraph of the list (`startAt`);
+        /// `None` starts at 1.
+        start_at: Option<u32>,
+    },
     /// A picture bullet via relationship ID (`<a:buBlip>`).
+    ///
+    /// See [`Presentation::add_bullet_image`](crate::Presentation::add_bullet_image)
+    /// for adding the image part.
     Picture(String),
     /// No bullet (explicitly suppressed).
     None,
 }
+
+impl BulletFormat {
+    /// An auto-numbered bullet starting at 1.
+    #[must_use]
+    pub const fn auto_numbered(scheme: MsoAutoNumberScheme) -> Self {
+        Self::AutoNumbered {
+            scheme,
+            start_at: None,
+        }
+    }
+
+    /// The text shown for this bullet on the `index`th (zero-based)
+    /// paragraph of its list.
+    ///
+    /// Returns `None` for picture bullets and suppressed bullets.
+    #[must_use]
+    pub fn label(&self, index: u32) -> Option<String> {
+        match self {
+            Self::Character(c) => Some(c.to_string()),
+            Self::AutoNumbered { scheme, start_at } => Some(auto_number_label(
+                *scheme,
+                start_at.unwrap_or(1).saturating_add(index),
+            )),
+            Self::Picture(_) | Self::None => None,
+        }
+    }
+}
+
+/// The rendered label of number `n` in a numbering scheme, e.g. `"iv)"`
+/// for 4 in [`MsoAutoNumberScheme::RomanLcParenR`].
+///
+/// Numbers a scheme has no symbol for (such as 0, or circled numbers past
+/// 20) fall back to Arabic digits.
+#[must_use]
+pub fn auto_number_label(scheme: MsoAutoNumberScheme, n: u32) -> String {
+    let (numeral, punct) = scheme_parts(scheme);
+    let number = numeral.format(n).unwrap_or_else(|| n.to_string());
+    match punct {
+        Punct::ParenBoth => format!("({number})"),
+        Punct::ParenR => format!("{number})"),
+        Punct::Period => format!("{number}."),
+        Punct::Plain => number,
+        Punct::WidePeriod => format!("{number}\u{ff0e}"),
+        Punct::Minus => format!("{number}-"),
+    }
+}
+
+/// The bullet label of each paragraph, counting auto-numbered paragraphs
+/// within their list.
+///
+/// A list continues while paragraphs at its level keep its scheme; a
+/// paragraph at a shallower level ends the lists nested below it, and any
+/// other bullet at the same level restarts the count. Empty paragraphs show
+/// no bullet and leave the count alone.
+pub(crate) fn bullet_labels(paragraphs: &[Paragraph]) -> Vec<Option<String>> {
+    // (scheme, start, index) of the open list at each level
+    let mut lists: Vec<Option<(MsoAutoNumberScheme, Option<u32>, u32)>> = Vec::new();
+    paragraphs
+        .iter()
+        .map(|para| {
+            if para.runs().iter().all(|r| r.text().is_empty()) {
+                return None;
+            }
+            let level = usize::from(para.level);
+            lists.resize(lists.len().max(level + 1), None);
+            lists.truncate(level + 1);
+            let bullet = para.bullet.as_ref()?;
+            let index = match bullet {
+                BulletFormat::AutoNumbered { scheme, start_at } => {
+                    let index = match lists[level] {
+                        Some((s, start, i)) if s == *scheme && start == *start_at => i + 1,
+                        _ => 0,
+                    };
+                    lists[level] = Some((*scheme, *start_at, index));
+                    index
+                }
+                _ => {
+                    lists[level] = None;
+                    0
+                }
+            };
+            bullet.label(index)
+        })
+        .collect()
+}
+
+/// How the number itself is written.
+#[derive(Clone, Copy)]
+enum Numeral {
+    Arabic,
+    /// Fullwidth digits.
+    ArabicWide,
+    AlphaLower,
+    AlphaUpper,
+    RomanLower,
+    RomanUpper,
+    /// `①`–`⑳`.
+    Circled,
+    /// `❶`–`❿` then `⓫`–`⓴`.
+    CircledBlack,
+    /// Chinese numerals.
+    EastAsian,
+    /// Arabic letters in alphabetical order.
+    ArabicAlpha,
+    /// Arabic letters in abjad order.
+    ArabicAbjad,
+    Hebrew,
+    ThaiAlpha,
+    ThaiDigits,
+    HindiConsonants,
+    HindiVowels,
+    HindiDigits,
+}
+
+/// The punctuation around a number.
+#[derive(Clone, Copy)]
+enum Punct {
+    ParenBoth,
+    ParenR,
+    Period,
+    Plain,
+    /// Fullwidth period.
+    WidePeriod,
+    Minus,
+}
+
+fn scheme_parts(scheme: MsoAutoNumberScheme) -> (Numeral, Punct) {
+    use MsoAutoNumberScheme as S;
+    match scheme {
+        S::AlphaLcParenBoth => (Numeral::AlphaLower, Punct::ParenBoth),
+        S::AlphaUcParenBoth => (Numeral::AlphaUpper, Punct::ParenBoth),
+        S::AlphaLcParenR => (Numeral::AlphaLower, Punct::ParenR),
+        S::AlphaUcParenR => (Numeral::AlphaUpper, Punct::ParenR),
+        S::AlphaLcPeriod => (Numeral::AlphaLower, Punct::Period),
+        S::AlphaUcPeriod => (Numeral::AlphaUpper, Punct::Period),
+        S::ArabicParenBoth => (Numeral::Arabic, Punct::ParenBoth),
+        S::ArabicParenR => (Numeral::Arabic, Punct::ParenR),
+        S::ArabicPeriod => (Numeral::Arabic, Punct::Period),
+        S::ArabicPlain => (Numeral::Arabic, Punct::Plain),
+        S::RomanLcParenBoth => (Numeral::RomanLower, Punct::ParenBoth),
+        S::RomanUcParenBoth => (Numeral::RomanUpper, Punct::ParenBoth),
+        S::RomanLcParenR => (Numeral::RomanLower, Punct::ParenR),
+        S::RomanUcParenR => (Numeral::RomanUpper, Punct::ParenR),
+        S::RomanLcPeriod => (Numeral::RomanLower, Punct::Period),
+        S::RomanUcPeriod => (Numeral::RomanUpper, Punct::Period),
+        S::CircleNumDbPlain | S::CircleNumWdWhitePlain => (Numeral::Circled, Punct::Plain),
+        S::CircleNumWdBlackPlain => (Numeral::CircledBlack, Punct::Plain),
+        S::ArabicDbPeriod => (Numeral::ArabicWide, Punct::WidePeriod),
+        S::ArabicDbPlain => (Numeral::ArabicWide, Punct::Plain),
+        S::Ea1ChsPeriod | S::Ea1ChtPeriod | S::Ea1JpnChsDbPeriod | S::Ea1JpnKorPeriod => {
+            (Numeral::EastAsian, Punct::WidePeriod)
+        }
+        S::Ea1ChsPlain | S::Ea1ChtPlain | S::Ea1JpnKorPlain => (Numeral::EastAsian, Punct::Plain),
+        S::Arabic1Minus => (Numeral::ArabicAlpha, Punct::Minus),
+        S::Arabic2Minus => (Numeral::ArabicAbjad, Punct::Minus),
+        S::Hebrew2Minus => (Numeral::Hebrew, Punct::Minus),
+        S::ThaiAlphaPeriod => (Numeral::ThaiAlpha, Punct::Period),
+        S::ThaiAlphaParenR => (Numeral::ThaiAlpha, Punct::ParenR),
+        S::ThaiAlphaParenBoth => (Numeral::ThaiAlpha, Punct::ParenBoth),
+        S::ThaiNumPeriod => (Numeral::ThaiDigits, Punct::Period),
+        S::ThaiNumParenR => (Numeral::ThaiDigits, Punct::ParenR),
+        S::ThaiNumParenBoth => (Numeral::ThaiDigits, Punct::ParenBoth),
+        S::HindiAlphaPeriod => (Numeral::HindiConsonants, Punct::Period),
+        S::HindiNumPeriod => (Numeral::HindiDigits, Punct::Period),
+        S::HindiNumParenR => (Numeral::HindiDigits, Punct::ParenR),
+        S::HindiAlpha1Period => (Numeral::HindiVowels, Punct::Period),
+    }
+}
+
+const LATIN: [&str; 26] = [
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z",
+];
+const ARABIC_ALPHA: [&str; 28] = [
+    "ا", "ب", "ت", "ث", "ج", "ح", "خ", "د", "ذ", "ر", "ز", "س", "ش", "ص", "ض", "ط", "ظ", "ع", "غ",
+    "ف", "ق", "ك", "ل", "م", "ن", "ه", "و", "ي",
+];
+const ARABIC_ABJAD: [&str; 28] = [
+    "ا", "ب", "ج", "د", "ه", "و", "ز", "ح", "ط", "ي", "ك", "ل", "م", "ن", "س", "ع", "ف", "ص", "ق",
+    "ر", "ش", "ت", "ث", "خ", "ذ", "ض", "ظ", "غ",
+];
+const THAI_ALPHA: [&str; 42] = [
+    "ก", "ข", "ค", "ฆ", "ง", "จ", "ฉ", "ช", "ซ", "ฌ", "ญ", "ฎ", "ฏ", "ฐ", "ฑ", "ฒ", "ณ", "ด", "ต",
+    "ถ", "ท", "ธ", "น", "บ", "ป", "ผ", "ฝ", "พ", "ฟ", "ภ", "ม", "ย", "ร", "ล", "ว", "ศ", "ษ", "ส",
+    "ห", "ฬ", "อ", "ฮ",
+];
+const HINDI_CONSONANTS: [&str; 33] = [
+    "क", "ख", "ग", "घ", "ङ", "च", "छ", "ज", "झ", "ञ", "ट", "ठ", "ड", "ढ", "ण", "त", "थ", "द", "ध",
+    "न", "प", "फ", "ब", "भ", "म", "य", "र", "ल", "व", "श", "ष", "स", "ह",
+];
+const HINDI_VOWELS: [&str; 13] = [
+    "अ", "आ", "इ", "ई", "उ", "ऊ", "ऋ", "ए", "ऐ", "ओ", "औ", "अं", "अः",
+];
+
+impl Numeral {
+    /// Write `n`, or `None` if this numeral has no form for it.
+    fn format(self, n: u32) -> Option<String> {
+        if n == 0 && !matches!(self, Self::Arabic | Self::ArabicWide) {
+            return None;
+        }
+        Some(match self {
+            Self::Arabic => n.to_string(),
+            Self::ArabicWide => shift_digits(n, '\u{ff10}'),
+            Self::AlphaLower => letters(&LATIN, n),
+            Self::AlphaUpper => letters(&LATIN, n).to_uppercase(),
+            Self::RomanLower => roman(n)?.to_lowercase(),
+            Self::RomanUpper => roman(n)?,
+            Self::Circled => char::from_u32(0x2460 + n - 1)
+                .filter(|_| n <= 20)?
+                .to_string(),
+            Self::CircledBlack => match n {
+                1..=10 => char::from_u32(0x2776 + n - 1)?.to_string(),
+                11..=20 => char::from_u32(0x24eb + n - 11)?.to_string(),
+                _ => return None,
+            },
+            Self::EastAsian => east_asian(n)?,
+            Self::ArabicAlpha => letters(&ARABIC_ALPHA, n),
+            Self::ArabicAbjad => letters(&ARABIC_ABJAD, n),
+            Self::Hebrew => hebrew(n)?,
+            Self::ThaiAlpha => letters(&THAI_ALPHA, n),
+            Self::ThaiDigits => shift_digits(n, '\u{0e50}'),
+            Self::HindiConsonants => letters(&HINDI_CONSONANTS, n),
+            Self::HindiVowels => letters(&HINDI_VOWELS, n),
+            Self::HindiDigits => shift_digits(n, '\u{0966}'),
+        })
+    }
+}
+
+/// Letter numbering: after the last letter, letters repeat (`aa`, `bb`…).
+fn letters(alphabet: &[&str], n: u32) -> String {
+    let len = alphabet.len();
+    let i = (n - 1) as usize;
+    alphabet[i % len].repeat(i / len + 1)
+}
+
+/// The decimal digits of `n` moved to the script whose zero is `zero`.
+fn shift_digits(n: u32, zero: char) -> String {
+    n.to_string()
+        .chars()
+        .filter_map(|d| char::from_u32(u32::from(zero) + d.to_digit(10)?))
+        .collect()
+}
+
+fn roman(n: u32) -> Option<String> {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    if n >= 4000 {
+        return None;
+    }
+    let mut rest = n;
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while rest >= value {
+            out.push_str(numeral);
+            rest -= value;
+        }
+    }
+    Some(out)
+}
+
+/// Chinese numerals up to 99 (`十一`, `二十`, `九十九`).
+fn east_asian(n: u32) -> Option<String> {
+    const DIGITS: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    let (tens, ones) = (n / 10, (n % 10) as usize);
+    Some(match tens {
+        0 => DIGITS[ones].to_string(),
+        1..=9 => {
+            let mut out = String::new();
+            if tens > 1 {
+                out.push_str(DIGITS[tens as usize]);
+            }
+            out.push('十');
+            if ones > 0 {
+                out.push_str(DIGITS[ones]);
+            }
+            out
+        }
+        _ => return None,
+    })
+}
+
+/// Hebrew numerals up to 999, with 15 and 16 written `טו` and `טז`.
+fn hebrew(n: u32) -> Option<String> {
+    const ONES: [&str; 10] = ["", "א", "ב", "ג", "ד", "ה", "ו", "ז", "ח", "ט"];
+    const TENS: [&str; 10] = ["", "י", "כ", "ל", "מ", "נ", "ס", "ע", "פ", "צ"];
+    const HUNDREDS: [&str; 10] = ["", "ק", "ר", "ש", "ת", "תק", "תר", "תש", "תת", "תתק"];
+    if n >= 1000 {
+        return None;
+    }
+    let (hundreds, rest) = ((n / 100) as usize, n % 100);
+    let tail = match rest {
+        15 => "טו".to_string(),
+        16 => "טז".to_string(),
+        _ => format!(
+            "{}{}",
+            TENS[(rest / 10) as usize],
+            ONES[(rest % 10) as usize]
+        ),
+    };
+    Some(format!("{}{tail}", HUNDREDS[hundreds]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_number_labels() {
+        use MsoAutoNumberScheme as S;
+        assert_eq!(auto_number_label(S::ArabicPeriod, 3), "3.");
+        assert_eq!(auto_number_label(S::ArabicPlain, 12), "12");
+        assert_eq!(auto_number_label(S::AlphaLcParenR, 2), "b)");
+        assert_eq!(auto_number_label(S::AlphaUcParenBoth, 28), "(BB)");
+        assert_eq!(auto_number_label(S::RomanUcPeriod, 14), "XIV.");
+        assert_eq!(auto_number_label(S::RomanLcParenBoth, 4), "(iv)");
+        assert_eq!(auto_number_label(S::CircleNumDbPlain, 3), "③");
+        assert_eq!(auto_number_label(S::CircleNumWdBlackPlain, 12), "⓬");
+        assert_eq!(auto_number_label(S::CircleNumDbPlain, 21), "21");
+        assert_eq!(auto_number_label(S::ArabicDbPeriod, 10), "１０．");
+        assert_eq!(auto_number_label(S::Ea1ChsPeriod, 21), "二十一．");
+        assert_eq!(auto_number_label(S::Ea1JpnKorPlain, 10), "十");
+        assert_eq!(auto_number_label(S::Hebrew2Minus, 15), "טו-");
+        assert_eq!(auto_number_label(S::ThaiNumParenR, 7), "๗)");
+        assert_eq!(auto_number_label(S::HindiNumPeriod, 25), "२५.");
+        assert_eq!(auto_number_label(S::HindiAlpha1Period, 12), "अं.");
+        assert_eq!(auto_number_label(S::RomanUcPeriod, 0), "0.");
+    }
+
+    #[test]
+    fn test_bullet_label() {
+        let bullet = BulletFormat::AutoNumbered {
+            scheme: MsoAutoNumberScheme::AlphaUcPeriod,
+            start_at: Some(3),
+        };
+        assert_eq!(bullet.label(0).as_deref(), Some("C."));
+        assert_eq!(bullet.label(2).as_deref(), Some("E."));
+        assert_eq!(BulletFormat::Character('•').label(5).as_deref(), Some("•"));
+        assert_eq!(BulletFormat::Picture("rId2".into()).label(0), None);
+        assert_eq!(BulletFormat::None.label(0), None);
+    }
+
+    #[test]
+    fn test_bullet_labels_count_within_lists() {
+        let numbered = BulletFormat::auto_numbered(MsoAutoNumberScheme::ArabicPeriod);
+        let nested = BulletFormat::auto_numbered(MsoAutoNumberScheme::AlphaLcParenR);
+        let para = |text: &str, level: u8, bullet: Option<&BulletFormat>| {
+            let mut p = Paragraph::new();
+            p.level = level;
+            p.bullet = bullet.cloned();
+            p.add_run().set_text(text);
+            p
+        };
+        let paragraphs = [
+            para("one", 0, Some(&numbered)),
+            para("", 0, Some(&numbered)),
+            para("two", 0, Some(&numbered)),
+            para("a", 1, Some(&nested)),
+            para("b", 1, Some(&nested)),
+            para("three", 0, Some(&numbered)),
+            para("a again", 1, Some(&nested)),
+            para("dot", 0, Some(&BulletFormat::Character('•'))),
+            para("one again", 0, Some(&numbered)),
+            para("plain", 0, None),
+        ];
+        let labels = bullet_labels(&paragraphs);
+        let labels: Vec<_> = labels.iter().map(Option::as_deref).collect();
+        assert_eq!(
+            labels,
+            [
+                Some("1."),
+                None,
+                Some("2."),
+                Some("a)"),
+                Some("b)"),
+                Some("3."),
+                Some("a)"),
+                Some("•"),
+                Some("1."),
+                None,
+            ]
+        );
+    }
+}
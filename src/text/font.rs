@@ -2,9 +2,12 @@
 
 use std::fmt;
 
+use crate::dml::color::ColorFormat;
+use crate::dml::effect::{GlowFormat, ShadowFormat};
 use crate::dml::fill::FillFormat;
+use crate::dml::line::LineFormat;
 use crate::enums::dml::MsoColorType;
-use crate::enums::text::MsoTextUnderlineType;
+use crate::enums::text::{MsoTextCaps, MsoTextUnderlineType};
 use crate::error::PptxError;
 use crate::shapes::action::Hyperlink;
 use crate::xml_util::{xml_escape, WriteXml};
@@ -101,6 +104,33 @@ pub struct Font {
     pub fill: Option<FillFormat>,
     /// Hyperlink associated with this font (alternative to Run-level hyperlink).
     pub hyperlink: Option<Hyperlink>,
+    /// Extra space between characters in points (`spc`); negative to condense.
+    pub character_spacing: Option<f64>,
+    /// Smallest font size in points that is kerned (`kern`); `0.0` turns
+    /// kerning off.
+    pub kerning: Option<f64>,
+    /// Capitalization (`cap`).
+    pub caps: Option<MsoTextCaps>,
+    /// Double strikethrough flag. When `true`, takes precedence over
+    /// `strikethrough`.
+    pub double_strikethrough: Option<bool>,
+    /// Baseline offset as a percentage of the font size (e.g. `30.0`). When
+    /// set, it replaces the default offsets of `superscript` and `subscript`.
+    pub baseline: Option<f64>,
+    /// Highlight color behind the text (`<a:highlight>`).
+    pub highlight: Option<ColorFormat>,
+    /// Outline of the characters (`<a:ln>`).
+    pub outline: Option<LineFormat>,
+    /// Shadow of the text.
+    pub shadow: Option<ShadowFormat>,
+    /// Glow around the text.
+    pub glow: Option<GlowFormat>,
+    /// East Asian typeface (`<a:ea>`), used for CJK characters.
+    pub east_asian_name: Option<String>,
+    /// Complex script typeface (`<a:cs>`), used for e.g. Arabic and Hebrew.
+    pub complex_script_name: Option<String>,
+    /// Symbol typeface (`<a:sym>`).
+    pub symbol_name: Option<String>,
 }
 
 impl Font {
@@ -185,7 +215,9 @@ impl Font {
             write!(w, r#" u="{}""#, underline.to_xml_str())?;
         }
 
-        if let Some(strike) = self.strikethrough {
+        if self.double_strikethrough == Some(true) {
+            w.write_str(r#" strike="dblStrike""#)?;
+        } else if let Some(strike) = self.strikethrough {
             write!(
                 w,
                 r#" strike="{}""#,
@@ -193,8 +225,29 @@ impl Font {
             )?;
         }
 
-        // Superscript takes precedence over subscript if both are set
-        if self.superscript == Some(true) {
+        if let Some(kern) = self.kerning {
+            #[allow(clippy::cast_possible_truncation)] // intentional f64→i64 for OOXML units
+            let kern = (kern * 100.0) as i64;
+            write!(w, r#" kern="{kern}""#)?;
+        }
+
+        if let Some(caps) = self.caps {
+            write!(w, r#" cap="{}""#, caps.to_xml_str())?;
+        }
+
+        if let Some(spacing) = self.character_spacing {
+            #[allow(clippy::cast_possible_truncation)] // intentional f64→i64 for OOXML units
+            let spc = (spacing * 100.0) as i64;
+            write!(w, r#" spc="{spc}""#)?;
+        }
+
+        // An explicit baseline wins; superscript takes precedence over
+        // subscript if both are set
+        if let Some(baseline) = self.baseline {
+            #[allow(clippy::cast_possible_truncation)] // intentional f64→i64 for OOXML units
+            let baseline = (baseline * 1000.0) as i64;
+            write!(w, r#" baseline="{baseline}""#)?;
+        } else if self.superscript == Some(true) {
             w.write_str(r#" baseline="30000""#)?;
         } else if self.subscript == Some(true) {
             w.write_str(r#" baseline="-25000""#)?;
@@ -203,14 +256,26 @@ impl Font {
         w.write_str(r#" dirty="0""#)?;
 
         // Determine if we have child elements
-        let has_children = self.fill.is_some()
+        let outline = self.outline.as_ref().and_then(LineFormat::to_xml_string);
+        let has_children = outline.is_some()
+            || self.fill.is_some()
             || self.color.is_some()
+            || self.shadow.is_some()
+            || self.glow.is_some()
+            || self.highlight.is_some()
             || self.name.is_some()
+            || self.east_asian_name.is_some()
+            || self.complex_script_name.is_some()
+            || self.symbol_name.is_some()
             || self.hyperlink.is_some();
 
         if has_children {
             w.write_char('>')?;
 
+            if let Some(ref outline) = outline {
+                w.write_str(outline)?;
+            }
+
             // Fill takes precedence over simple color when both are set
             if let Some(ref fill) = self.fill {
                 fill.write_xml(w)?;
@@ -222,8 +287,32 @@ impl Font {
                 )?;
             }
 
-            if let Some(ref name) = self.name {
-                write!(w, r#"<a:latin typeface="{}"/>"#, xml_escape(name))?;
+            if self.glow.is_some() || self.shadow.is_some() {
+                w.write_str("<a:effectLst>")?;
+                if let Some(ref glow) = self.glow {
+                    glow.write_xml(w)?;
+                }
+                if let Some(ref shadow) = self.shadow {
+                    shadow.write_effect(w)?;
+                }
+                w.write_str("</a:effectLst>")?;
+            }
+
+            if let Some(ref color) = self.highlight {
+                w.write_str("<a:highlight>")?;
+                color.write_xml(w)?;
+                w.write_str("</a:highlight>")?;
+            }
+
+            for (tag, name) in [
+                ("latin", &self.name),
+                ("ea", &self.east_asian_name),
+                ("cs", &self.complex_script_name),
+                ("sym", &self.symbol_name),
+            ] {
+                if let Some(name) = name {
+                    write!(w, r#"<a:{tag} typeface="{}"/>"#, xml_escape(name))?;
+                }
             }
 
             if let Some(ref hlink) = self.hyperlink {
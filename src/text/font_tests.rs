@@ -1,8 +1,8 @@
 //! Tests for the `Font` and `RgbColor` types.
 
 use super::*;
-use crate::enums::text::MsoTextUnderlineType;
-use crate::units::RelationshipId;
+use crate::enums::text::{MsoTextCaps, MsoTextUnderlineType};
+use crate::units::{Emu, RelationshipId};
 
 #[test]
 fn test_rgb_color_new() {
@@ -239,3 +239,55 @@ fn test_font_hyperlink_with_tooltip() {
     assert!(xml.contains(r#"tooltip="Click here""#));
     assert!(xml.contains(r#"r:id="rId2""#));
 }
+
+#[test]
+fn test_font_character_properties() {
+    let mut f = Font::new();
+    f.character_spacing = Some(-1.5);
+    f.kerning = Some(12.0);
+    f.caps = Some(MsoTextCaps::Small);
+    f.strikethrough = Some(true);
+    f.double_strikethrough = Some(true);
+    f.baseline = Some(10.0);
+    f.superscript = Some(true);
+    let xml = f.to_xml_string();
+    assert!(xml.contains(r#" strike="dblStrike" kern="1200" cap="small" spc="-150""#));
+    // The explicit baseline replaces the superscript default
+    assert!(xml.contains(r#" baseline="10000""#));
+    assert!(!xml.contains("30000"));
+}
+
+#[test]
+fn test_font_effects_highlight_and_script_typefaces() {
+    let mut f = Font::new();
+    f.color = Some(RgbColor::new(0, 0, 0));
+    f.outline = Some(LineFormat::solid(ColorFormat::rgb(255, 0, 0), Emu(6350)));
+    f.glow = Some(GlowFormat::new(ColorFormat::rgb(255, 255, 0), Emu(38_100)));
+    f.shadow = Some(ShadowFormat::outer(
+        ColorFormat::rgb(0, 0, 0),
+        Emu(12_700),
+        Emu(12_700),
+        45.0,
+    ));
+    f.highlight = Some(ColorFormat::rgb(255, 255, 0));
+    f.name = Some("Calibri".to_string());
+    f.east_asian_name = Some("MS Mincho".to_string());
+    f.complex_script_name = Some("Arial".to_string());
+    f.symbol_name = Some("Wingdings".to_string());
+    let xml = f.to_xml_string();
+    // Children follow the schema order
+    let ln = xml.find("<a:ln w=\"6350\">").unwrap();
+    let fill = xml
+        .find("<a:solidFill><a:srgbClr val=\"000000\"/>")
+        .unwrap();
+    let effects = xml
+        .find("<a:effectLst><a:glow rad=\"38100\"><a:srgbClr val=\"FFFF00\"/></a:glow><a:outerShdw")
+        .unwrap();
+    let highlight = xml
+        .find("<a:highlight><a:srgbClr val=\"FFFF00\"/></a:highlight>")
+        .unwrap();
+    assert!(ln < fill && fill < effects && effects < highlight);
+    assert!(xml.contains(
+        r#"<a:latin typeface="Calibri"/><a:ea typeface="MS Mincho"/><a:cs typeface="Arial"/><a:sym typeface="Wingdings"/></a:rPr>"#
+    ));
+}
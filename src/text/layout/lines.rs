@@ -0,0 +1,693 @@
+//! Line breaking of text frames, with the formatting text inherits.
+
+use crate::dml::fill::FillFormat;
+use crate::enums::text::{
+    MsoAutoSize, MsoTextCaps, MsoTextUnderlineType, MsoVerticalAnchor, PpParagraphAlignment,
+};
+use crate::text::bullet::BulletFormat;
+use crate::text::font::{Font, RgbColor};
+use crate::text::{Paragraph, TextFrame};
+use crate::theme::Colors;
+use crate::units::{pt_to_emu, Emu};
+
+use super::metrics::{family_for, measure, FontFace, ThemeFonts};
+use super::{FontSet, TextExtent};
+
+/// Indent added per paragraph level, in points.
+const LEVEL_INDENT: f64 = 27.0;
+
+/// Text formatting that applies where runs and paragraphs set none.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TextDefaults {
+    pub size: f64,
+    pub color: RgbColor,
+    pub bold: bool,
+    pub font_name: Option<String>,
+    /// Typefaces that theme font references (`+mj-lt`, `+mn-lt`) stand for.
+    pub theme_fonts: ThemeFonts,
+    pub anchor: MsoVerticalAnchor,
+    pub alignment: PpParagraphAlignment,
+    /// Inherited styles of the outline levels.
+    pub levels: ListStyle,
+}
+
+impl Default for TextDefaults {
+    fn default() -> Self {
+        Self {
+            size: 18.0,
+            color: RgbColor::new(0, 0, 0),
+            bold: false,
+            font_name: None,
+            theme_fonts: ThemeFonts::default(),
+            anchor: MsoVerticalAnchor::Top,
+            alignment: PpParagraphAlignment::Left,
+            levels: ListStyle::default(),
+        }
+    }
+}
+
+/// Formatting of one outline level (`<a:lvl1pPr>` ... `<a:lvl9pPr>`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct LevelStyle {
+    /// Font size in points.
+    pub size: Option<f64>,
+    pub bold: Option<bool>,
+    /// Latin typeface, possibly a theme reference such as `+mj-lt`.
+    pub font: Option<String>,
+    pub color: Option<RgbColor>,
+    pub alignment: Option<PpParagraphAlignment>,
+    /// `Some(None)` when bullets are turned off.
+    pub bullet: Option<Option<char>>,
+    /// Left margin of the text, in points.
+    pub margin_left: Option<f64>,
+    /// First-line indent relative to the margin (negative for a hanging
+    /// bullet), in points.
+    pub indent: Option<f64>,
+}
+
+impl LevelStyle {
+    /// Fill the properties this style leaves unset from `base`.
+    fn or(&self, base: &Self) -> Self {
+        Self {
+            size: self.size.or(base.size),
+            bold: self.bold.or(base.bold),
+            font: self.font.clone().or_else(|| base.font.clone()),
+            color: self.color.or(base.color),
+            alignment: self.alignment.or(base.alignment),
+            bullet: self.bullet.or(base.bullet),
+            margin_left: self.margin_left.or(base.margin_left),
+            indent: self.indent.or(base.indent),
+        }
+    }
+}
+
+/// Styles of the nine outline levels.
+pub(crate) type ListStyle = [LevelStyle; 9];
+
+/// `overrides` on top of `base`, level by level.
+pub(crate) fn merge(base: &ListStyle, overrides: &ListStyle) -> ListStyle {
+    std::array::from_fn(|i| overrides[i].or(&base[i]))
+}
+
+/// Insets of a text frame, with the `PowerPoint` defaults for unset ones.
+pub(crate) fn frame_insets(tf: &TextFrame) -> [f64; 4] {
+    insets([
+        tf.margin_left,
+        tf.margin_top,
+        tf.margin_right,
+        tf.margin_bottom,
+    ])
+}
+
+/// Left, top, right and bottom insets in points, defaulting to 0.1" on
+/// the sides and 0.05" at the top and bottom.
+pub(crate) fn insets(margins: [Option<Emu>; 4]) -> [f64; 4] {
+    let [l, t, r, b] = margins;
+    let pt = |m: Option<Emu>, default: f64| m.map_or(default, |m| m.to_pt());
+    [pt(l, 7.2), pt(t, 3.6), pt(r, 7.2), pt(b, 3.6)]
+}
+
+/// A run of text with uniform formatting on one line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Piece {
+    pub text: String,
+    pub face: FontFace,
+    pub font: Option<String>,
+    pub size: f64,
+    pub color: RgbColor,
+    pub underline: bool,
+    pub strike: bool,
+    /// Offset of the baseline, positive downwards.
+    pub shift: f64,
+}
+
+impl Piece {
+    pub(crate) fn width(&self, fonts: Option<&FontSet>) -> f64 {
+        measure(
+            fonts,
+            self.font.as_deref(),
+            self.face,
+            self.size,
+            &self.text,
+        )
+    }
+
+    fn same_style(&self, other: &Self) -> bool {
+        self.face == other.face
+            && self.font == other.font
+            && self.size == other.size
+            && self.color == other.color
+            && self.underline == other.underline
+            && self.strike == other.strike
+            && self.shift == other.shift
+    }
+}
+
+/// A line of text, as broken by [`break_lines`].
+#[derive(Debug)]
+pub(crate) struct Line {
+    pub pieces: Vec<Piece>,
+    /// Largest font size on the line.
+    pub size: f64,
+    /// Extra space above the line (space before its paragraph).
+    pub before: f64,
+    pub after: f64,
+    pub spacing: f64,
+    /// Indent of the text from the left inset.
+    pub indent: f64,
+    pub alignment: PpParagraphAlignment,
+    /// The bullet, with its indent from the left inset.
+    pub bullet: Option<(Piece, f64)>,
+}
+
+impl Line {
+    pub(crate) fn height(&self) -> f64 {
+        self.before + self.size * 1.2 * self.spacing + self.after
+    }
+
+    /// Width of the text, not counting trailing spaces.
+    pub(crate) fn width(&self, fonts: Option<&FontSet>) -> f64 {
+        let Some((last, rest)) = self.pieces.split_last() else {
+            return 0.0;
+        };
+        let trimmed = Piece {
+            text: last.text.trim_end().to_string(),
+            ..last.clone()
+        };
+        rest.iter().map(|p| p.width(fonts)).sum::<f64>() + trimmed.width(fonts)
+    }
+
+    /// Width of the text including trailing spaces.
+    fn full_width(&self, fonts: Option<&FontSet>) -> f64 {
+        self.pieces.iter().map(|p| p.width(fonts)).sum()
+    }
+
+    fn push(&mut self, piece: Piece) {
+        // An empty line is as tall as its paragraph's text; a line with
+        // text as tall as its largest run
+        self.size = if self.pieces.is_empty() {
+            piece.size
+        } else {
+            self.size.max(piece.size)
+        };
+        match self.pieces.last_mut() {
+            Some(last) if last.same_style(&piece) => last.text.push_str(&piece.text),
+            _ => self.pieces.push(piece),
+        }
+    }
+}
+
+/// Lays out the paragraphs of a frame into lines.
+struct Layout<'a> {
+    colors: &'a Colors,
+    defaults: &'a TextDefaults,
+    /// Fonts text is measured with, besides the standard faces.
+    fonts: Option<&'a FontSet>,
+    scale: f64,
+    /// Factor line spacing is reduced by to fit the shape.
+    spacing_scale: f64,
+    /// Room for text on a line, or `None` when lines do not wrap.
+    wrap_width: Option<f64>,
+    lines: Vec<Line>,
+}
+
+impl<'a> Layout<'a> {
+    /// A layout of the paragraphs of `tf` in a box `width` points wide
+    /// without its insets.
+    fn new(
+        tf: &TextFrame,
+        width: f64,
+        defaults: &'a TextDefaults,
+        colors: &'a Colors,
+        fonts: Option<&'a FontSet>,
+    ) -> Self {
+        let (scale, spacing_scale) = autofit_scales(tf);
+        Self {
+            colors,
+            defaults,
+            fonts,
+            scale,
+            spacing_scale,
+            wrap_width: tf.word_wrap.then_some(width),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Formatting of a run: its own font, then the paragraph's, then the
+    /// level style, then the defaults.
+    fn piece(
+        &self,
+        text: &str,
+        run_font: &Font,
+        para_font: Option<&Font>,
+        level: &LevelStyle,
+    ) -> Piece {
+        let pick = |f: fn(&Font) -> Option<bool>| f(run_font).or_else(|| para_font.and_then(f));
+        let name = run_font
+            .name
+            .as_deref()
+            .or_else(|| para_font.and_then(|f| f.name.as_deref()))
+            .or(level.font.as_deref())
+            .or(self.defaults.font_name.as_deref());
+        let font = self.defaults.theme_fonts.resolve(name);
+        let mut size = run_font
+            .size
+            .or_else(|| para_font.and_then(|f| f.size))
+            .or(level.size)
+            .unwrap_or(self.defaults.size)
+            * self.scale;
+        let color = self
+            .font_color(run_font)
+            .or_else(|| para_font.and_then(|f| self.font_color(f)))
+            .or(level.color)
+            .unwrap_or(self.defaults.color);
+        let mut shift = 0.0;
+        if pick(|f| f.superscript) == Some(true) {
+            shift = -size * 0.33;
+            size *= 0.67;
+        } else if pick(|f| f.subscript) == Some(true) {
+            shift = size * 0.1;
+            size *= 0.67;
+        }
+        let underline = run_font
+            .underline
+            .or_else(|| para_font.and_then(|f| f.underline))
+            .is_some_and(|u| u != MsoTextUnderlineType::None);
+        let caps = run_font.caps.or_else(|| para_font.and_then(|f| f.caps));
+        Piece {
+            text: if caps == Some(MsoTextCaps::All) {
+                text.to_uppercase()
+            } else {
+                text.to_string()
+            },
+            font: font.map(str::to_string),
+            face: FontFace {
+                family: family_for(font),
+                bold: pick(|f| f.bold)
+                    .or(level.bold)
+                    .unwrap_or(self.defaults.bold),
+                italic: pick(|f| f.italic).unwrap_or(false),
+            },
+            size,
+            color,
+            underline,
+            strike: pick(|f| f.strikethrough) == Some(true),
+            shift,
+        }
+    }
+
+    fn font_color(&self, font: &Font) -> Option<RgbColor> {
+        font.color.or_else(|| match &font.fill {
+            Some(FillFormat::Solid(solid)) => Some(self.colors.resolve(&solid.color)),
+            _ => None,
+        })
+    }
+
+    /// The bullet of a paragraph, from its own format or its level style;
+    /// `label` is the paragraph's number in its list.
+    ///
+    /// Unless set on the bullet, color and size follow the first run.
+    fn bullet(&self, para: &Paragraph, label: Option<&str>, level: &LevelStyle) -> Option<Piece> {
+        let text = match &para.bullet {
+            Some(BulletFormat::Character(c)) => c.to_string(),
+            Some(BulletFormat::AutoNumbered { .. }) => label?.to_string(),
+            Some(_) => return None,
+            None => level.bullet.flatten()?.to_string(),
+        };
+        let first_font = para
+            .runs()
+            .first()
+            .map_or_else(Font::new, |r| r.font().clone());
+        let mut piece = self.piece(&text, &first_font, para.font.as_ref(), level);
+        piece.underline = false;
+        piece.strike = false;
+        piece.shift = 0.0;
+        if let Some(color) = para
+            .bullet_color
+            .as_ref()
+            .filter(|_| !para.bullet_color_follows_text)
+        {
+            piece.color = self.colors.resolve(color);
+        }
+        if !para.bullet_size_follows_text {
+            if let Some(pct) = para.bullet_size_pct {
+                piece.size *= pct / 100.0;
+            } else if let Some(pts) = para.bullet_size_pts {
+                piece.size = pts * self.scale;
+            }
+        }
+        Some(piece)
+    }
+
+    fn paragraph(&mut self, para: &Paragraph, label: Option<&str>) {
+        let para_font = para.font.as_ref();
+        let level_index = usize::from(para.level).min(8);
+        let level = &self.defaults.levels[level_index];
+        let base = self.piece("", &Font::new(), para_font, level);
+        let bullet = self.bullet(para, label, level);
+
+        // Text starts at the margin; the first line (and its bullet) at the
+        // margin plus the indent
+        let (margin, indent) = indents(para, level_index, level);
+        let spacing = paragraph_spacing(para, base.size);
+        let (first_indent, bullet) = match bullet {
+            Some(b) if indent < 0.0 => (margin, Some((b, margin + indent))),
+            Some(b) => {
+                let room = base.size.max(b.width(self.fonts) * 2.0);
+                (margin + indent + room, Some((b, margin + indent)))
+            }
+            None => (margin + indent, None),
+        };
+        let alignment = para
+            .alignment
+            .or(level.alignment)
+            .unwrap_or(self.defaults.alignment);
+        let new_line = |first: bool| Line {
+            pieces: Vec::new(),
+            size: base.size,
+            spacing: spacing.line * self.spacing_scale,
+            indent: if first { first_indent } else { margin },
+            alignment,
+            before: if first { spacing.before } else { 0.0 },
+            after: 0.0,
+            bullet: if first { bullet.clone() } else { None },
+        };
+
+        let mut line = new_line(true);
+        for run in para.runs() {
+            if run.is_line_break {
+                self.lines
+                    .push(std::mem::replace(&mut line, new_line(false)));
+                continue;
+            }
+            for word in words(run.text()) {
+                let piece = self.piece(word, run.font(), para_font, level);
+                let Some(wrap_width) = self.wrap_width else {
+                    line.push(piece);
+                    continue;
+                };
+                let avail = (wrap_width - line.indent).max(1.0);
+                let trimmed = Piece {
+                    text: word.trim_end().to_string(),
+                    ..piece.clone()
+                }
+                .width(self.fonts);
+                if !line.pieces.is_empty() && line.full_width(self.fonts) + trimmed > avail {
+                    self.lines
+                        .push(std::mem::replace(&mut line, new_line(false)));
+                }
+                let avail = (wrap_width - line.indent).max(1.0);
+                if trimmed > avail {
+                    // A word longer than a whole line is broken anywhere
+                    for c in word.chars() {
+                        let part = Piece {
+                            text: c.to_string(),
+                            ..piece.clone()
+                        };
+                        if !line.pieces.is_empty()
+                            && line.full_width(self.fonts) + part.width(self.fonts) > avail
+                        {
+                            self.lines
+                                .push(std::mem::replace(&mut line, new_line(false)));
+                        }
+                        line.push(part);
+                    }
+                } else {
+                    line.push(piece);
+                }
+            }
+        }
+        line.after = spacing.after;
+        self.lines.push(line);
+    }
+}
+
+/// The left margin of a paragraph's text and the indent of its first line,
+/// in points: the paragraph's own, else its level's.
+fn indents(para: &Paragraph, level_index: usize, level: &LevelStyle) -> (f64, f64) {
+    #[allow(clippy::cast_precision_loss)] // level is at most 8
+    let margin = para
+        .margin_left
+        .map(|m| m.to_pt())
+        .or(level.margin_left)
+        .unwrap_or(level_index as f64 * LEVEL_INDENT);
+    let indent = para
+        .indent
+        .map(|i| i.to_pt())
+        .or(level.indent)
+        .unwrap_or(0.0);
+    (margin, indent)
+}
+
+/// Spacing of a paragraph with text of `size` points.
+struct Spacing {
+    /// Line spacing as a multiple of single spacing.
+    line: f64,
+    /// Space before and after, in points.
+    before: f64,
+    after: f64,
+}
+
+fn paragraph_spacing(para: &Paragraph, size: f64) -> Spacing {
+    // Percentages are of a single-spaced line
+    let line_height = size * 1.2;
+    let space = |pts: Option<f64>, pct: Option<f64>| {
+        pts.or_else(|| pct.map(|pct| line_height * pct / 100.0))
+            .unwrap_or(0.0)
+    };
+    Spacing {
+        line: para
+            .line_spacing
+            .or_else(|| para.line_spacing_pts.map(|pts| pts / line_height))
+            .unwrap_or(1.0),
+        before: space(para.space_before, para.space_before_pct),
+        after: space(para.space_after, para.space_after_pct),
+    }
+}
+
+/// Split text into words, each keeping the spaces that follow it.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let word_end = rest
+            .char_indices()
+            .find(|&(_, c)| c == ' ')
+            .map_or(rest.len(), |(i, _)| i);
+        let end = rest[word_end..]
+            .char_indices()
+            .find(|&(_, c)| c != ' ')
+            .map_or(rest.len(), |(i, _)| word_end + i);
+        let (word, tail) = rest.split_at(end);
+        rest = tail;
+        Some(word)
+    })
+}
+
+/// Break the paragraphs of `tf` into lines of text `width` points wide,
+/// measuring runs whose typeface is in `fonts` with that font.
+pub(crate) fn break_lines(
+    tf: &TextFrame,
+    width: f64,
+    defaults: &TextDefaults,
+    colors: &Colors,
+    fonts: Option<&FontSet>,
+) -> Vec<Line> {
+    let mut layout = Layout::new(tf, width, defaults, colors, fonts);
+    for (para, label) in tf.paragraphs().iter().zip(tf.bullet_labels()) {
+        layout.paragraph(para, label.as_deref());
+    }
+    layout.lines
+}
+
+/// The factors text and its line spacing are shrunk by to fit the shape.
+fn autofit_scales(tf: &TextFrame) -> (f64, f64) {
+    if tf.auto_size != MsoAutoSize::TextToFitShape {
+        return (1.0, 1.0);
+    }
+    let scale = tf
+        .font_scale
+        .filter(|pct| *pct > 0.0)
+        .map_or(1.0, |pct| pct / 100.0);
+    let spacing = tf
+        .line_spacing_reduction
+        .filter(|pct| (0.0..100.0).contains(pct))
+        .map_or(1.0, |pct| 1.0 - pct / 100.0);
+    (scale, spacing)
+}
+
+/// Font scales and line spacing reductions `PowerPoint` tries, in percent,
+/// to make text fit its shape.
+const AUTOFIT_STEPS: [(f64, f64); 13] = [
+    (100.0, 0.0),
+    (100.0, 10.0),
+    (92.5, 10.0),
+    (92.5, 20.0),
+    (85.0, 20.0),
+    (77.5, 20.0),
+    (70.0, 20.0),
+    (62.5, 20.0),
+    (55.0, 20.0),
+    (47.5, 20.0),
+    (40.0, 20.0),
+    (32.5, 20.0),
+    (25.0, 20.0),
+];
+
+/// Measure the text of `tf` in a shape `width` wide.
+pub(crate) fn measure_text(
+    tf: &TextFrame,
+    width: Emu,
+    defaults: &TextDefaults,
+    fonts: &FontSet,
+) -> TextExtent {
+    let [left, top, right, bottom] = frame_insets(tf);
+    let inner_width = (width.to_pt() - left - right).max(0.0);
+    let lines = break_lines(tf, inner_width, defaults, &Colors::default(), Some(fonts));
+    let text_width = lines
+        .iter()
+        .map(|line| line.indent + line.width(Some(fonts)))
+        .fold(0.0, f64::max);
+    let text_height: f64 = lines.iter().map(Line::height).sum();
+    TextExtent {
+        width: pt_to_emu(text_width + left + right),
+        height: pt_to_emu(text_height + top + bottom),
+        lines: lines.len(),
+    }
+}
+
+/// Shrink the text of `tf` to fit a shape `width` by `height` in the
+/// steps `PowerPoint` takes, returning whether it fits.
+pub(crate) fn fit_text(
+    tf: &mut TextFrame,
+    width: Emu,
+    height: Emu,
+    defaults: &TextDefaults,
+    fonts: &FontSet,
+) -> bool {
+    tf.auto_size = MsoAutoSize::TextToFitShape;
+    let as_option = |pct: f64| (pct != 100.0 && pct != 0.0).then_some(pct);
+    for (scale, reduction) in AUTOFIT_STEPS {
+        tf.font_scale = as_option(scale);
+        tf.line_spacing_reduction = as_option(reduction);
+        if measure_text(tf, width, defaults, fonts).height <= height {
+            return true;
+        }
+    }
+    false
+}
+
+/// Formatting of a run with what it inherits resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RunStyle {
+    /// The typeface, with theme fonts resolved.
+    pub font: Option<String>,
+    /// Font size in points.
+    pub size: f64,
+    pub bold: bool,
+    pub italic: bool,
+    pub color: RgbColor,
+    pub underline: bool,
+    pub strike: bool,
+    /// Offset of the baseline, negative for superscripts.
+    pub shift: f64,
+}
+
+impl From<Piece> for RunStyle {
+    fn from(piece: Piece) -> Self {
+        Self {
+            font: piece.font,
+            size: piece.size,
+            bold: piece.face.bold,
+            italic: piece.face.italic,
+            color: piece.color,
+            underline: piece.underline,
+            strike: piece.strike,
+            shift: piece.shift,
+        }
+    }
+}
+
+/// Formatting of a paragraph with what it inherits resolved; lengths are
+/// in points.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParagraphStyle {
+    pub alignment: PpParagraphAlignment,
+    /// Left margin of the text.
+    pub margin: f64,
+    /// Where the first line (or its bullet) starts, relative to the margin.
+    pub indent: f64,
+    /// The bullet character and its formatting.
+    pub bullet: Option<(String, RunStyle)>,
+    pub space_before: f64,
+    pub space_after: f64,
+    /// Line spacing as a multiple of single spacing.
+    pub line_spacing: f64,
+    /// Formatting of an empty run of the paragraph.
+    pub base: RunStyle,
+}
+
+/// Resolves the formatting of paragraphs and runs in a text frame, for
+/// exporters that keep text as text rather than placing each line.
+pub(crate) struct TextStyler<'a> {
+    layout: Layout<'a>,
+}
+
+impl<'a> TextStyler<'a> {
+    pub(crate) fn new(tf: &TextFrame, defaults: &'a TextDefaults, colors: &'a Colors) -> Self {
+        let mut layout = Layout::new(tf, 0.0, defaults, colors, None);
+        layout.wrap_width = None;
+        Self { layout }
+    }
+
+    fn level(&self, para: &Paragraph) -> (usize, &LevelStyle) {
+        let index = usize::from(para.level).min(8);
+        (index, &self.layout.defaults.levels[index])
+    }
+
+    /// The formatting of `para`, whose number in its list is `label` (see
+    /// [`TextFrame::bullet_labels`]).
+    pub(crate) fn paragraph(&self, para: &Paragraph, label: Option<&str>) -> ParagraphStyle {
+        let (index, level) = self.level(para);
+        let (margin, indent) = indents(para, index, level);
+        let base = self.run(para, &Font::new());
+        let spacing = paragraph_spacing(para, base.size);
+        ParagraphStyle {
+            alignment: para
+                .alignment
+                .or(level.alignment)
+                .unwrap_or(self.layout.defaults.alignment),
+            margin,
+            indent,
+            bullet: self
+                .layout
+                .bullet(para, label, level)
+                .map(|piece| (piece.text.clone(), piece.into())),
+            space_before: spacing.before,
+            space_after: spacing.after,
+            line_spacing: spacing.line,
+            base,
+        }
+    }
+
+    /// The formatting of a run with font `font` in `para`.
+    pub(crate) fn run(&self, para: &Paragraph, font: &Font) -> RunStyle {
+        let (_, level) = self.level(para);
+        self.layout
+            .piece("", font, para.font.as_ref(), level)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_words_keeping_spaces() {
+        let words: Vec<_> = words("one  two three").collect();
+        assert_eq!(words, vec!["one  ", "two ", "three"]);
+    }
+}
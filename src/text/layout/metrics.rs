@@ -0,0 +1,231 @@
+//! Metrics of the standard typefaces used to lay out text.
+//!
+//! Text is measured with the widths of the standard Helvetica, Times and
+//! Courier faces that every PDF reader provides, so that the layout matches
+//! what is drawn. Italic faces are measured with their upright widths.
+
+use crate::xml_util::splice::{find_element, find_start_tag, get_attr};
+
+use super::FontSet;
+
+/// Advance widths of characters 32 to 126, in 1/1000 em.
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, //
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, //
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, //
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+const TIMES: [u16; 95] = [
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278, //
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444, //
+    921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722, //
+    556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500, //
+    333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500, //
+    500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541,
+];
+
+const TIMES_BOLD: [u16; 95] = [
+    250, 333, 555, 500, 500, 1000, 833, 278, 333, 333, 500, 570, 250, 333, 250, 278, //
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 333, 333, 570, 570, 570, 500, //
+    930, 722, 667, 722, 722, 667, 611, 778, 778, 389, 500, 778, 667, 944, 722, 778, //
+    611, 778, 722, 556, 667, 722, 722, 1000, 722, 722, 667, 333, 278, 333, 581, 500, //
+    333, 500, 556, 444, 556, 444, 333, 500, 556, 278, 333, 556, 278, 833, 556, 500, //
+    556, 556, 444, 389, 333, 556, 500, 722, 500, 500, 444, 394, 220, 394, 520,
+];
+
+/// One of the three standard typeface families.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum FontFamily {
+    Sans,
+    Serif,
+    Mono,
+}
+
+/// A face of one of the standard typefaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FontFace {
+    pub family: FontFamily,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+/// The heading and body typefaces of a theme (`<a:majorFont>` and
+/// `<a:minorFont>`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ThemeFonts {
+    pub major: Option<String>,
+    pub minor: Option<String>,
+}
+
+impl ThemeFonts {
+    /// Read the Latin typefaces of a theme part's font scheme.
+    pub(crate) fn parse(theme_xml: &str) -> Self {
+        let latin = |scheme: &str| {
+            let range = find_element(theme_xml, scheme, 0)?;
+            let tag = find_start_tag(&theme_xml[range.clone()], "a:latin", 0)?;
+            get_attr(&theme_xml[range][tag], "typeface")
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            major: latin("a:majorFont"),
+            minor: latin("a:minorFont"),
+        }
+    }
+
+    /// The typeface `name` stands for: theme references (`+mj-lt`,
+    /// `+mn-ea`, ...) become the theme's fonts, and no name at all the body
+    /// font.
+    pub(crate) fn resolve<'a>(&'a self, name: Option<&'a str>) -> Option<&'a str> {
+        match name {
+            Some(n) if n.starts_with("+mj-") => self.major.as_deref(),
+            Some(n) if n.starts_with("+mn-") => self.minor.as_deref(),
+            Some(n) => Some(n),
+            None => self.minor.as_deref(),
+        }
+    }
+}
+
+/// Choose the standard family closest to a font name. Theme font
+/// references (`+mj-lt`, `+mn-lt`) and unknown names map to sans-serif.
+pub(crate) fn family_for(name: Option<&str>) -> FontFamily {
+    let Some(name) = name else {
+        return FontFamily::Sans;
+    };
+    let lower = name.to_ascii_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+    if has(&["courier", "consolas", "mono", "console", "code"]) {
+        FontFamily::Mono
+    } else if has(&[
+        "times",
+        "georgia",
+        "garamond",
+        "cambria",
+        "palatino",
+        "baskerville",
+        "book antiqua",
+        "century",
+        "bodoni",
+        "didot",
+    ]) || (lower.contains("serif") && !lower.contains("sans"))
+    {
+        FontFamily::Serif
+    } else {
+        FontFamily::Sans
+    }
+}
+
+/// Advance width of `c` in 1/1000 em.
+fn char_width(face: FontFace, c: char) -> u16 {
+    let table = match (face.family, face.bold) {
+        (FontFamily::Mono, _) => return 600,
+        (FontFamily::Sans, false) => &HELVETICA,
+        (FontFamily::Sans, true) => &HELVETICA_BOLD,
+        (FontFamily::Serif, false) => &TIMES,
+        (FontFamily::Serif, true) => &TIMES_BOLD,
+    };
+    let lookup = |c: char| table[c as usize - 32];
+    match c {
+        ' '..='~' => lookup(c),
+        '\u{a0}' => lookup(' '),
+        '\u{2022}' => 350,
+        '\u{2013}' => lookup('_'),
+        '\u{2014}' => 1000,
+        '\u{2018}' | '\u{2019}' | '\u{201a}' => lookup(','),
+        '\u{201c}' | '\u{201d}' | '\u{201e}' => lookup('"'),
+        '\u{2026}' => 1000,
+        '\u{20ac}' => lookup('0'),
+        // Accented Latin letters are about as wide as their base letter
+        'À'..='Å' => lookup('A'),
+        'à'..='å' => lookup('a'),
+        'È'..='Ë' => lookup('E'),
+        'è'..='ë' => lookup('e'),
+        'Ì'..='Ï' => lookup('I'),
+        'ì'..='ï' => lookup('i'),
+        'Ò'..='Ö' | 'Ø' => lookup('O'),
+        'ò'..='ö' | 'ø' => lookup('o'),
+        'Ù'..='Ü' => lookup('U'),
+        'ù'..='ü' => lookup('u'),
+        'Ç' => lookup('C'),
+        'ç' => lookup('c'),
+        'Ñ' => lookup('N'),
+        'ñ' => lookup('n'),
+        'ß' => lookup('B'),
+        _ => lookup('n'),
+    }
+}
+
+/// Width of `text` in points at `size` points.
+pub(crate) fn text_width(face: FontFace, size: f64, text: &str) -> f64 {
+    let units: u32 = text.chars().map(|c| u32::from(char_width(face, c))).sum();
+    f64::from(units) * size / 1000.0
+}
+
+/// Width of `text` in points at `size` points: with the metrics of `font`
+/// when `fonts` has it, else with the standard face closest to it.
+pub(crate) fn measure(
+    fonts: Option<&FontSet>,
+    font: Option<&str>,
+    face: FontFace,
+    size: f64,
+    text: &str,
+) -> f64 {
+    fonts
+        .zip(font)
+        .and_then(|(fonts, font)| fonts.advance(font, face.bold, face.italic, text))
+        .map_or_else(|| text_width(face, size, text), |em| em * size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SANS: FontFace = FontFace {
+        family: FontFamily::Sans,
+        bold: false,
+        italic: false,
+    };
+
+    #[test]
+    fn measures_standard_widths() {
+        assert!((text_width(SANS, 10.0, "Hi") - 9.44).abs() < 1e-9);
+        let mono = FontFace {
+            family: FontFamily::Mono,
+            ..SANS
+        };
+        assert!((text_width(mono, 10.0, "abc") - 18.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn maps_font_names_to_families() {
+        assert_eq!(family_for(Some("Times New Roman")), FontFamily::Serif);
+        assert_eq!(family_for(Some("Noto Sans")), FontFamily::Sans);
+        assert_eq!(family_for(Some("DejaVu Serif")), FontFamily::Serif);
+        assert_eq!(family_for(Some("Consolas")), FontFamily::Mono);
+        assert_eq!(family_for(Some("+mn-lt")), FontFamily::Sans);
+        assert_eq!(family_for(None), FontFamily::Sans);
+    }
+
+    #[test]
+    fn resolves_theme_fonts() {
+        let fonts = ThemeFonts::parse(
+            r#"<a:fontScheme name="Office"><a:majorFont><a:latin typeface="Cambria"/></a:majorFont><a:minorFont><a:latin typeface="Calibri"/><a:ea typeface=""/></a:minorFont></a:fontScheme>"#,
+        );
+        assert_eq!(fonts.resolve(Some("+mj-lt")), Some("Cambria"));
+        assert_eq!(fonts.resolve(Some("+mn-ea")), Some("Calibri"));
+        assert_eq!(fonts.resolve(Some("Arial")), Some("Arial"));
+        assert_eq!(fonts.resolve(None), Some("Calibri"));
+    }
+}
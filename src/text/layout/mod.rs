@@ -0,0 +1,511 @@
+//! Measuring text with font metrics, to fit text to its shape.
+//!
+//! A [`FontSet`] holds the TrueType and OpenType fonts text is measured
+//! with: fonts installed on the system, font files or bytes supplied by the
+//! caller, or fonts embedded in a presentation. Text frames are broken into
+//! lines with the advance widths of those fonts, honoring word wrap,
+//! insets, indentation and spacing, and measured the way `PowerPoint` lays
+//! them out. Runs whose typeface is not in the set are measured with the
+//! standard face closest to it.
+//!
+//! [`TextFrame::measure`] reports the room text needs and
+//! [`TextFrame::shrink_to_fit`] computes the font scale and line spacing
+//! reduction `PowerPoint` applies to text that fits its shape.
+//! [`Presentation::autofit_slide`](crate::Presentation::autofit_slide)
+//! does both for the shapes of a slide, with the formatting they inherit.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use pptx::text::layout::FontSet;
+//! use pptx::text::TextFrame;
+//! use pptx::units::Emu;
+//!
+//! let fonts = FontSet::system();
+//! let mut tf = TextFrame::new();
+//! tf.set_text("A long line of text that has to fit a small box");
+//! let fits = tf.shrink_to_fit(Emu(1_828_800), Emu(457_200), &fonts);
+//! println!("fits: {fits}, font scale: {:?}", tf.font_scale);
+//! ```
+
+mod lines;
+mod metrics;
+
+pub(crate) use lines::{
+    break_lines, fit_text, frame_insets, insets, measure_text, merge, Line, ListStyle, Piece,
+    RunStyle, TextDefaults, TextStyler,
+};
+pub(crate) use metrics::{text_width, FontFace, FontFamily, ThemeFonts};
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use ttf_parser::{name_id, Face, Language};
+
+use crate::embedded_font::{font_program, EmbeddedFont};
+use crate::error::{PptxError, PptxResult};
+use crate::text::TextFrame;
+use crate::units::Emu;
+
+/// Largest font file read, in bytes.
+const MAX_FONT_SIZE: u64 = 100 * 1024 * 1024;
+
+/// How deep font directories are searched.
+const MAX_DIR_DEPTH: usize = 8;
+
+/// Fonts that text is measured with.
+#[derive(Debug, Clone, Default)]
+pub struct FontSet {
+    fonts: Vec<FontEntry>,
+}
+
+/// One face of a font program.
+#[derive(Debug, Clone)]
+struct FontEntry {
+    /// Family name, lowercased for matching.
+    typeface: String,
+    bold: bool,
+    italic: bool,
+    /// Index of the face in a font collection.
+    index: u32,
+    source: FontSource,
+}
+
+/// Where the program of a face comes from. Files are read again when the
+/// face is first used, so that scanning the system fonts keeps none of
+/// them in memory.
+#[derive(Debug, Clone)]
+enum FontSource {
+    Data(Arc<[u8]>),
+    File(PathBuf, OnceLock<Option<Arc<[u8]>>>),
+}
+
+impl FontSource {
+    fn data(&self) -> Option<&[u8]> {
+        match self {
+            Self::Data(data) => Some(data),
+            Self::File(path, data) => data
+                .get_or_init(|| read_font_file(path).ok().map(Arc::from))
+                .as_deref(),
+        }
+    }
+}
+
+impl FontSet {
+    /// Create an empty font set; all text is measured with the standard
+    /// faces.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { fonts: Vec::new() }
+    }
+
+    /// Create a font set of the fonts installed on the system.
+    ///
+    /// See [`add_system_fonts`](Self::add_system_fonts).
+    #[must_use]
+    pub fn system() -> Self {
+        let mut fonts = Self::new();
+        fonts.add_system_fonts();
+        fonts
+    }
+
+    /// Add a font under the typeface and style it was given, such as a font
+    /// embedded in a presentation. Embedded OpenType (`.fntdata`) fonts
+    /// are unwrapped; compressed ones cannot be used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the font data is not a usable font program.
+    pub fn add_font(&mut self, font: &EmbeddedFont) -> PptxResult<()> {
+        let data = font_program(&font.font_data).ok_or_else(|| PptxError::InvalidValue {
+            field: "font_data",
+            value: font.typeface.clone(),
+            expected: "TrueType, OpenType or uncompressed Embedded OpenType font data",
+        })?;
+        self.fonts.push(FontEntry {
+            typeface: font.typeface.to_lowercase(),
+            bold: font.bold,
+            italic: font.italic,
+            index: 0,
+            source: FontSource::Data(Arc::from(data)),
+        });
+        Ok(())
+    }
+
+    /// Add a font, builder style.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the font data is not a usable font program.
+    pub fn with_font(mut self, font: &EmbeddedFont) -> PptxResult<Self> {
+        self.add_font(font)?;
+        Ok(self)
+    }
+
+    /// Add the faces of a TrueType or OpenType font, or of a font
+    /// collection, under the family names and styles the font declares.
+    ///
+    /// Returns the number of faces added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a font.
+    pub fn add_font_data(&mut self, data: Vec<u8>) -> PptxResult<usize> {
+        let data: Arc<[u8]> = Arc::from(data);
+        let added = self.add_faces(&data, || FontSource::Data(Arc::clone(&data)));
+        if added == 0 {
+            return Err(PptxError::InvalidValue {
+                field: "font_data",
+                value: format!("{} bytes", data.len()),
+                expected: "TrueType or OpenType font data",
+            });
+        }
+        Ok(added)
+    }
+
+    /// Add the faces of a font file (`.ttf`, `.otf`, `.ttc` or `.otc`).
+    ///
+    /// Returns the number of faces added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is larger than 100 MB or
+    /// is not a font.
+    pub fn add_font_file(&mut self, path: impl AsRef<Path>) -> PptxResult<usize> {
+        let path = path.as_ref();
+        let data = read_font_file(path)?;
+        let added = self.add_faces(&data, || {
+            FontSource::File(path.to_path_buf(), OnceLock::new())
+        });
+        if added == 0 {
+            return Err(PptxError::InvalidValue {
+                field: "path",
+                value: path.display().to_string(),
+                expected: "a TrueType or OpenType font file",
+            });
+        }
+        Ok(added)
+    }
+
+    /// Add the fonts installed on the system: the font directories of
+    /// Windows, macOS and Linux (including the user's own) are searched
+    /// for font files. Files that cannot be read are skipped.
+    ///
+    /// Returns the number of faces added.
+    pub fn add_system_fonts(&mut self) -> usize {
+        let mut files = Vec::new();
+        for dir in system_font_dirs() {
+            collect_font_files(&dir, 0, &mut files);
+        }
+        files
+            .iter()
+            .filter_map(|path| self.add_font_file(path).ok())
+            .sum()
+    }
+
+    /// The number of faces in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Whether the set has no faces.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    /// Whether the set has a face of `typeface` (matched without regard to
+    /// case).
+    #[must_use]
+    pub fn contains(&self, typeface: &str) -> bool {
+        let typeface = typeface.to_lowercase();
+        self.fonts.iter().any(|f| f.typeface == typeface)
+    }
+
+    /// Width of `text` in em of the closest face of `typeface`, or `None`
+    /// when the set has no usable face of that typeface.
+    pub(crate) fn advance(
+        &self,
+        typeface: &str,
+        bold: bool,
+        italic: bool,
+        text: &str,
+    ) -> Option<f64> {
+        let (data, index) = self.program(self.closest(typeface, bold, italic)?)?;
+        let face = Face::parse(data, index).ok()?;
+        let units: u32 = text
+            .chars()
+            .map(|c| {
+                let glyph = face.glyph_index(c).unwrap_or_default();
+                u32::from(face.glyph_hor_advance(glyph).unwrap_or(0))
+            })
+            .sum();
+        Some(f64::from(units) / f64::from(face.units_per_em()))
+    }
+
+    /// Position of the face of `typeface` closest to the style asked for.
+    pub(crate) fn closest(&self, typeface: &str, bold: bool, italic: bool) -> Option<usize> {
+        let typeface = typeface.to_lowercase();
+        self.fonts
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.typeface == typeface)
+            .min_by_key(|(_, f)| u8::from(f.italic != italic) * 2 + u8::from(f.bold != bold))
+            .map(|(position, _)| position)
+    }
+
+    /// The font program of the face at `position` and the index of the
+    /// face in it, or `None` when its file cannot be read.
+    pub(crate) fn program(&self, position: usize) -> Option<(&[u8], u32)> {
+        let entry = self.fonts.get(position)?;
+        Some((entry.source.data()?, entry.index))
+    }
+
+    /// Whether the face at `position` is bold and whether it is italic.
+    pub(crate) fn style(&self, position: usize) -> (bool, bool) {
+        self.fonts
+            .get(position)
+            .map_or((false, false), |f| (f.bold, f.italic))
+    }
+
+    /// Add every face of `data` that has a family name.
+    fn add_faces(&mut self, data: &[u8], source: impl Fn() -> FontSource) -> usize {
+        let count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+        let mut added = 0;
+        for index in 0..count {
+            let Ok(face) = Face::parse(data, index) else {
+                continue;
+            };
+            let Some(family) = family_name(&face) else {
+                continue;
+            };
+            self.fonts.push(FontEntry {
+                typeface: family.to_lowercase(),
+                bold: face.is_bold(),
+                italic: face.is_italic(),
+                index,
+                source: source(),
+            });
+            added += 1;
+        }
+        added
+    }
+}
+
+/// The family name of a face, in US English when it has one.
+fn family_name(face: &Face<'_>) -> Option<String> {
+    let names = face
+        .names()
+        .into_iter()
+        .filter(|name| name.name_id == name_id::FAMILY);
+    let mut fallback = None;
+    for name in names {
+        let Some(text) = name.to_string() else {
+            continue;
+        };
+        if name.language() == Language::English_UnitedStates {
+            return Some(text);
+        }
+        fallback.get_or_insert(text);
+    }
+    fallback
+}
+
+fn read_font_file(path: &Path) -> PptxResult<Vec<u8>> {
+    let metadata = std::fs::metadata(path).map_err(PptxError::Io)?;
+    if metadata.len() > MAX_FONT_SIZE {
+        return Err(PptxError::ResourceLimit {
+            message: format!(
+                "font file size {} bytes exceeds the limit of {} bytes",
+                metadata.len(),
+                MAX_FONT_SIZE
+            ),
+        });
+    }
+    std::fs::read(path).map_err(PptxError::Io)
+}
+
+/// Directories fonts are installed in.
+fn system_font_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = [
+        "/usr/share/fonts",
+        "/usr/local/share/fonts",
+        "/System/Library/Fonts",
+        "/Library/Fonts",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect();
+    let env = |name: &str| std::env::var_os(name).map(PathBuf::from);
+    if let Some(home) = env("HOME") {
+        dirs.push(home.join(".fonts"));
+        dirs.push(home.join(".local/share/fonts"));
+        dirs.push(home.join("Library/Fonts"));
+    }
+    if let Some(data_home) = env("XDG_DATA_HOME") {
+        dirs.push(data_home.join("fonts"));
+    }
+    let windows = env("WINDIR").unwrap_or_else(|| PathBuf::from(r"C:\Windows"));
+    dirs.push(windows.join("Fonts"));
+    if let Some(local) = env("LOCALAPPDATA") {
+        dirs.push(local.join(r"Microsoft\Windows\Fonts"));
+    }
+    dirs
+}
+
+/// Collect the font files under `dir`.
+fn collect_font_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            if depth < MAX_DIR_DEPTH {
+                collect_font_files(&path, depth + 1, files);
+            }
+            continue;
+        }
+        let is_font = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                ["ttf", "otf", "ttc", "otc"]
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            });
+        if is_font {
+            files.push(path);
+        }
+    }
+}
+
+/// The room a text frame's text takes up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextExtent {
+    /// Width of the widest line, including the left and right insets.
+    pub width: Emu,
+    /// Height of all lines, including the top and bottom insets.
+    pub height: Emu,
+    /// The number of lines the text breaks into.
+    pub lines: usize,
+}
+
+impl TextFrame {
+    /// Measure the text when its shape is `width` wide, breaking lines at
+    /// that width when the frame wraps words.
+    ///
+    /// Text is measured with the formatting it sets itself, 18 point text
+    /// where it sets none (as in a text box), and the frame's current font
+    /// scale and line spacing reduction.
+    #[must_use]
+    pub fn measure(&self, width: Emu, fonts: &FontSet) -> TextExtent {
+        measure_text(self, width, &TextDefaults::default(), fonts)
+    }
+
+    /// Shrink the text to fit a shape `width` by `height`, the way
+    /// `PowerPoint` does for "shrink text on overflow": line spacing is
+    /// reduced by up to 20% and the font scaled down in steps of 7.5% until
+    /// the text fits, down to 25%.
+    ///
+    /// Sets [`auto_size`](Self::auto_size) to `TextToFitShape` and
+    /// [`font_scale`](Self::font_scale) and
+    /// [`line_spacing_reduction`](Self::line_spacing_reduction) to the
+    /// result (`None` when the text fits as it is). Returns whether the
+    /// text fits.
+    pub fn shrink_to_fit(&mut self, width: Emu, height: Emu, fonts: &FontSet) -> bool {
+        fit_text(self, width, height, &TextDefaults::default(), fonts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::text::MsoAutoSize;
+    use crate::render::sample_font;
+
+    fn sample_set() -> FontSet {
+        FontSet::new()
+            .with_font(&EmbeddedFont::from_bytes(
+                sample_font(),
+                "Sample",
+                false,
+                false,
+            ))
+            .unwrap()
+    }
+
+    #[test]
+    fn measures_with_font_advances() {
+        let fonts = sample_set();
+        assert!(fonts.contains("SAMPLE"));
+        // Letters advance 600 and the space 500 units of 1000
+        assert_eq!(fonts.advance("sample", true, false, "AB C"), Some(2.3));
+        assert_eq!(fonts.advance("Calibri", false, false, "AB"), None);
+    }
+
+    #[test]
+    fn rejects_data_that_is_not_a_font() {
+        let mut fonts = FontSet::new();
+        assert!(fonts.add_font_data(vec![0; 64]).is_err());
+        let broken = EmbeddedFont::from_bytes(vec![1, 2, 3], "Broken", false, false);
+        assert!(fonts.add_font(&broken).is_err());
+        assert!(fonts.is_empty());
+    }
+
+    #[test]
+    fn measures_wrapped_lines() {
+        let fonts = sample_set();
+        let mut tf = TextFrame::new();
+        tf.set_text("AAAA AAAA");
+        tf.margin_left = Some(Emu(0));
+        tf.margin_right = Some(Emu(0));
+        tf.margin_top = Some(Emu(0));
+        tf.margin_bottom = Some(Emu(0));
+        tf.paragraphs_mut()[0].runs_mut()[0].font_mut().name = Some("Sample".to_string());
+        tf.paragraphs_mut()[0].runs_mut()[0].font_mut().size = Some(10.0);
+
+        // Each word is 24pt wide at 10pt, the space 5pt
+        let wide = tf.measure(Emu(60 * 12_700), &fonts);
+        assert_eq!(wide.lines, 1);
+        assert_eq!(wide.width, Emu(53 * 12_700));
+        assert_eq!(wide.height, Emu(12 * 12_700));
+        let narrow = tf.measure(Emu(40 * 12_700), &fonts);
+        assert_eq!(narrow.lines, 2);
+        assert_eq!(narrow.width, Emu(24 * 12_700));
+        assert_eq!(narrow.height, Emu(24 * 12_700));
+    }
+
+    #[test]
+    fn shrinks_text_in_powerpoint_steps() {
+        let fonts = sample_set();
+        let mut tf = TextFrame::new();
+        tf.set_text("AAAA\nAAAA\nAAAA\nAAAA");
+        for para in tf.paragraphs_mut() {
+            para.runs_mut()[0].font_mut().name = Some("Sample".to_string());
+        }
+        let width = Emu(914_400);
+
+        // Four 18pt lines take 86.4pt plus 7.2pt of insets
+        assert!(tf.shrink_to_fit(width, Emu(100 * 12_700), &fonts));
+        assert_eq!(tf.auto_size, MsoAutoSize::TextToFitShape);
+        assert_eq!((tf.font_scale, tf.line_spacing_reduction), (None, None));
+
+        assert!(tf.shrink_to_fit(width, Emu(85 * 12_700), &fonts));
+        assert_eq!(
+            (tf.font_scale, tf.line_spacing_reduction),
+            (None, Some(10.0))
+        );
+
+        assert!(tf.shrink_to_fit(width, Emu(60 * 12_700), &fonts));
+        assert_eq!(
+            (tf.font_scale, tf.line_spacing_reduction),
+            (Some(70.0), Some(20.0))
+        );
+
+        assert!(!tf.shrink_to_fit(width, Emu(10 * 12_700), &fonts));
+        assert_eq!(tf.font_scale, Some(25.0));
+    }
+}
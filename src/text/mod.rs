@@ -2,12 +2,15 @@
 
 pub mod bullet;
 pub mod font;
+pub mod layout;
 pub mod paragraph;
 pub mod run;
+pub mod search;
 pub mod text_frame;
 
-pub use bullet::BulletFormat;
+pub use bullet::{auto_number_label, BulletFormat};
 pub use font::{Font, RgbColor};
-pub use paragraph::Paragraph;
+pub use paragraph::{Paragraph, TabStop};
 pub use run::Run;
-pub use text_frame::TextFrame;
+pub use search::ReplaceOptions;
+pub use text_frame::{TextFrame, TextWarp};
@@ -1,10 +1,13 @@
 //! Paragraph type for text paragraphs within a text frame.
 
 use crate::dml::color::ColorFormat;
-use crate::enums::text::{PpParagraphAlignment, TextDirection};
+use crate::enums::text::{
+    MsoBaselineAlignment, MsoTabStopType, PpParagraphAlignment, TextDirection,
+};
 use crate::text::bullet::BulletFormat;
 use crate::text::font::Font;
 use crate::text::run::Run;
+use crate::units::Emu;
 use crate::xml_util::{xml_escape, xml_escape_char};
 use crate::WriteXml;
 
@@ -19,28 +22,81 @@ pub struct Paragraph {
     pub alignment: Option<PpParagraphAlignment>,
     /// Indent level (0-8).
     pub level: u8,
+    /// Left margin of the text (`marL`).
+    pub margin_left: Option<Emu>,
+    /// Indent of the first line relative to the left margin (`indent`);
+    /// negative for a hanging indent.
+    pub indent: Option<Emu>,
     /// Space before the paragraph, in points.
     pub space_before: Option<f64>,
+    /// Space before the paragraph as a percentage of the text size
+    /// (`<a:spcPct>`, e.g. 50.0 for 50%). Ignored if `space_before` is set.
+    pub space_before_pct: Option<f64>,
     /// Space after the paragraph, in points.
     pub space_after: Option<f64>,
+    /// Space after the paragraph as a percentage of the text size
+    /// (`<a:spcPct>`, e.g. 50.0 for 50%). Ignored if `space_after` is set.
+    pub space_after_pct: Option<f64>,
     /// Line spacing multiplier (e.g. 1.5 for 150% line spacing).
     pub line_spacing: Option<f64>,
+    /// Exact line spacing in points (`<a:spcPts>`). Ignored if
+    /// `line_spacing` is set.
+    pub line_spacing_pts: Option<f64>,
+    /// Tab stops (`<a:tabLst>`), in order of position.
+    pub tab_stops: Vec<TabStop>,
+    /// Distance between default tab stops (`defTabSz`).
+    pub default_tab_size: Option<Emu>,
+    /// Whether East Asian text may break lines inside words (`eaLnBrk`).
+    pub east_asian_line_break: Option<bool>,
+    /// Whether Latin text may break lines inside words (`latinLnBrk`).
+    pub latin_line_break: Option<bool>,
+    /// Vertical alignment of runs of different sizes (`fontAlgn`).
+    pub font_alignment: Option<MsoBaselineAlignment>,
     /// Bullet format for this paragraph.
     pub bullet: Option<BulletFormat>,
     /// Bullet color (`<a:buClr>`).
     pub bullet_color: Option<ColorFormat>,
+    /// Whether the bullet takes the color of the first run (`<a:buClrTx>`).
+    /// Overrides `bullet_color`.
+    pub bullet_color_follows_text: bool,
     /// Bullet font typeface (`<a:buFont>`).
     pub bullet_font: Option<String>,
+    /// Whether the bullet takes the font of the first run (`<a:buFontTx>`).
+    /// Overrides `bullet_font`.
+    pub bullet_font_follows_text: bool,
     /// Bullet size as a percentage of the text size (`<a:buSzPct>`, e.g. 100.0 for 100%).
     pub bullet_size_pct: Option<f64>,
     /// Bullet size in points (`<a:buSzPts>`, e.g. 12.0 for 12pt).
     pub bullet_size_pts: Option<f64>,
+    /// Whether the bullet takes the size of the first run (`<a:buSzTx>`).
+    /// Overrides `bullet_size_pct` and `bullet_size_pts`.
+    pub bullet_size_follows_text: bool,
     /// Default run properties for this paragraph (`<a:defRPr>`).
     pub font: Option<Font>,
     /// Text direction (LTR or RTL) for this paragraph.
     pub text_direction: Option<TextDirection>,
 }
 
+/// A tab stop of a paragraph, corresponding to the `<a:tab>` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabStop {
+    /// Distance of the tab stop from the left edge of the text frame.
+    pub position: Emu,
+    /// How text is aligned at the tab stop.
+    pub alignment: MsoTabStopType,
+}
+
+impl TabStop {
+    /// Create a tab stop at `position` with the given alignment.
+    #[must_use]
+    pub const fn new(position: Emu, alignment: MsoTabStopType) -> Self {
+        Self {
+            position,
+            alignment,
+        }
+    }
+}
+
 impl Paragraph {
     /// Create a new empty paragraph.
     #[must_use]
@@ -121,6 +177,32 @@ impl Paragraph {
         self.bullet = Some(bullet);
     }
 
+    /// Set a hanging indent: text starts `margin` from the left and the
+    /// first line (with its bullet or number) `hang` to the left of that.
+    pub fn set_hanging_indent(&mut self, margin: Emu, hang: Emu) {
+        self.margin_left = Some(margin);
+        self.indent = Some(Emu(-hang.0));
+    }
+
+    /// Add a tab stop, keeping tab stops in order of position.
+    pub fn add_tab_stop(&mut self, position: Emu, alignment: MsoTabStopType) {
+        let index = self.tab_stops.partition_point(|t| t.position <= position);
+        self.tab_stops
+            .insert(index, TabStop::new(position, alignment));
+    }
+
+    /// Whether any bullet property is set.
+    fn has_bullet_props(&self) -> bool {
+        self.bullet.is_some()
+            || self.bullet_color.is_some()
+            || self.bullet_color_follows_text
+            || self.bullet_font.is_some()
+            || self.bullet_font_follows_text
+            || self.bullet_size_pct.is_some()
+            || self.bullet_size_pts.is_some()
+            || self.bullet_size_follows_text
+    }
+
     /// Generate the `<a:p>` XML element string.
     #[must_use]
     #[allow(clippy::too_many_lines)]
@@ -128,17 +210,24 @@ impl Paragraph {
         let mut xml = String::from("<a:p>");
 
         // <a:pPr> - only emit if there are properties to set
-        let has_ppr = self.alignment.is_some()
+        let has_attrs = self.alignment.is_some()
             || self.level > 0
-            || self.text_direction.is_some()
+            || self.margin_left.is_some()
+            || self.indent.is_some()
+            || self.default_tab_size.is_some()
+            || self.east_asian_line_break.is_some()
+            || self.latin_line_break.is_some()
+            || self.font_alignment.is_some()
+            || self.text_direction.is_some();
+        let has_ppr = has_attrs
             || self.space_before.is_some()
+            || self.space_before_pct.is_some()
             || self.space_after.is_some()
+            || self.space_after_pct.is_some()
             || self.line_spacing.is_some()
-            || self.bullet.is_some()
-            || self.bullet_color.is_some()
-            || self.bullet_font.is_some()
-            || self.bullet_size_pct.is_some()
-            || self.bullet_size_pts.is_some()
+            || self.line_spacing_pts.is_some()
+            || !self.tab_stops.is_empty()
+            || self.has_bullet_props()
             || self.font.is_some();
 
         if has_ppr {
@@ -152,18 +241,42 @@ impl Paragraph {
                 xml.push_str(&format!(r#" lvl="{}""#, self.level));
             }
 
+            if let Some(margin) = self.margin_left {
+                xml.push_str(&format!(r#" marL="{}""#, margin.0));
+            }
+
+            if let Some(indent) = self.indent {
+                xml.push_str(&format!(r#" indent="{}""#, indent.0));
+            }
+
+            if let Some(size) = self.default_tab_size {
+                xml.push_str(&format!(r#" defTabSz="{}""#, size.0));
+            }
+
             if let Some(dir) = self.text_direction {
                 xml.push_str(&format!(r#" rtl="{}""#, dir.to_xml_attr()));
             }
 
+            if let Some(brk) = self.east_asian_line_break {
+                xml.push_str(&format!(r#" eaLnBrk="{}""#, u8::from(brk)));
+            }
+
+            if let Some(algn) = self.font_alignment {
+                xml.push_str(&format!(r#" fontAlgn="{}""#, algn.to_xml_str()));
+            }
+
+            if let Some(brk) = self.latin_line_break {
+                xml.push_str(&format!(r#" latinLnBrk="{}""#, u8::from(brk)));
+            }
+
             let has_children = self.space_before.is_some()
+                || self.space_before_pct.is_some()
                 || self.space_after.is_some()
+                || self.space_after_pct.is_some()
                 || self.line_spacing.is_some()
-                || self.bullet.is_some()
-                || self.bullet_color.is_some()
-                || self.bullet_font.is_some()
-                || self.bullet_size_pct.is_some()
-                || self.bullet_size_pts.is_some()
+                || self.line_spacing_pts.is_some()
+                || !self.tab_stops.is_empty()
+                || self.has_bullet_props()
                 || self.font.is_some();
 
             if has_children {
@@ -175,31 +288,33 @@ impl Paragraph {
                     // intentional f64→i64 for OOXML units
                     let val = (spacing * 100_000.0) as i64;
                     xml.push_str(&format!(r#"<a:lnSpc><a:spcPct val="{val}"/></a:lnSpc>"#));
+                } else if let Some(pts) = self.line_spacing_pts {
+                    xml.push_str(&format!("<a:lnSpc>{}</a:lnSpc>", spacing_pts(pts)));
                 }
 
                 if let Some(before) = self.space_before {
-                    // Space before in hundredths of a point
-                    #[allow(clippy::cast_possible_truncation)]
-                    // intentional f64→i64 for OOXML units
-                    let val = (before * 100.0) as i64;
-                    xml.push_str(&format!(r#"<a:spcBef><a:spcPts val="{val}"/></a:spcBef>"#));
+                    xml.push_str(&format!("<a:spcBef>{}</a:spcBef>", spacing_pts(before)));
+                } else if let Some(pct) = self.space_before_pct {
+                    xml.push_str(&format!("<a:spcBef>{}</a:spcBef>", spacing_pct(pct)));
                 }
 
                 if let Some(after) = self.space_after {
-                    // Space after in hundredths of a point
-                    #[allow(clippy::cast_possible_truncation)]
-                    // intentional f64→i64 for OOXML units
-                    let val = (after * 100.0) as i64;
-                    xml.push_str(&format!(r#"<a:spcAft><a:spcPts val="{val}"/></a:spcAft>"#));
+                    xml.push_str(&format!("<a:spcAft>{}</a:spcAft>", spacing_pts(after)));
+                } else if let Some(pct) = self.space_after_pct {
+                    xml.push_str(&format!("<a:spcAft>{}</a:spcAft>", spacing_pct(pct)));
                 }
 
                 // Bullet color
-                if let Some(ref color) = self.bullet_color {
+                if self.bullet_color_follows_text {
+                    xml.push_str("<a:buClrTx/>");
+                } else if let Some(ref color) = self.bullet_color {
                     xml.push_str(&format!("<a:buClr>{}</a:buClr>", color.to_xml_string()));
                 }
 
                 // Bullet size
-                if let Some(pct) = self.bullet_size_pct {
+                if self.bullet_size_follows_text {
+                    xml.push_str("<a:buSzTx/>");
+                } else if let Some(pct) = self.bullet_size_pct {
                     #[allow(clippy::cast_possible_truncation)]
                     // intentional f64→i64 for OOXML units
                     let val = (pct * 1000.0) as i64;
@@ -212,7 +327,9 @@ impl Paragraph {
                 }
 
                 // Bullet font
-                if let Some(ref font_name) = self.bullet_font {
+                if self.bullet_font_follows_text {
+                    xml.push_str("<a:buFontTx/>");
+                } else if let Some(ref font_name) = self.bullet_font {
                     xml.push_str(&format!(
                         r#"<a:buFont typeface="{}"/>"#,
                         xml_escape(font_name)
@@ -228,11 +345,15 @@ impl Paragraph {
                                 xml_escape_char(*ch)
                             ));
                         }
-                        BulletFormat::AutoNumbered(numbering_type) => {
+                        BulletFormat::AutoNumbered { scheme, start_at } => {
                             xml.push_str(&format!(
-                                r#"<a:buAutoNum type="{}"/>"#,
-                                xml_escape(numbering_type)
+                                r#"<a:buAutoNum type="{}""#,
+                                scheme.to_xml_str()
                             ));
+                            if let Some(start) = start_at {
+                                xml.push_str(&format!(r#" startAt="{start}""#));
+                            }
+                            xml.push_str("/>");
                         }
                         BulletFormat::Picture(r_id) => {
                             xml.push_str(&format!(
@@ -246,6 +367,19 @@ impl Paragraph {
                     }
                 }
 
+                // Tab stops
+                if !self.tab_stops.is_empty() {
+                    xml.push_str("<a:tabLst>");
+                    for tab in &self.tab_stops {
+                        xml.push_str(&format!(
+                            r#"<a:tab pos="{}" algn="{}"/>"#,
+                            tab.position.0,
+                            tab.alignment.to_xml_str()
+                        ));
+                    }
+                    xml.push_str("</a:tabLst>");
+                }
+
                 // Default run properties
                 if let Some(ref font) = self.font {
                     // Reuse Font::to_xml_string() but replace <a:rPr with <a:defRPr
@@ -273,6 +407,24 @@ impl Paragraph {
     }
 }
 
+/// An `<a:spcPts>` element for a spacing in points.
+fn spacing_pts(pts: f64) -> String {
+    // Hundredths of a point
+    #[allow(clippy::cast_possible_truncation)]
+    // intentional f64→i64 for OOXML units
+    let val = (pts * 100.0) as i64;
+    format!(r#"<a:spcPts val="{val}"/>"#)
+}
+
+/// An `<a:spcPct>` element for a spacing in percent.
+fn spacing_pct(pct: f64) -> String {
+    // Thousandths of a percent
+    #[allow(clippy::cast_possible_truncation)]
+    // intentional f64→i64 for OOXML units
+    let val = (pct * 1000.0) as i64;
+    format!(r#"<a:spcPct val="{val}"/>"#)
+}
+
 #[cfg(test)]
 #[path = "paragraph_tests.rs"]
 mod tests;
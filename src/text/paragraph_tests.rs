@@ -2,7 +2,9 @@
 
 use super::*;
 use crate::dml::color::ColorFormat;
-use crate::enums::text::{PpParagraphAlignment, TextDirection};
+use crate::enums::text::{
+    MsoAutoNumberScheme, MsoBaselineAlignment, MsoTabStopType, PpParagraphAlignment, TextDirection,
+};
 use crate::text::font::Font;
 
 #[test]
@@ -189,12 +191,43 @@ fn test_bullet_size_pct_takes_precedence() {
 #[test]
 fn test_bullet_autonumbered() {
     let mut p = Paragraph::new();
-    p.set_bullet(BulletFormat::AutoNumbered("arabicPeriod".to_string()));
+    p.set_bullet(BulletFormat::auto_numbered(
+        MsoAutoNumberScheme::ArabicPeriod,
+    ));
     p.add_run().set_text("Numbered");
     let xml = p.to_xml_string();
     assert!(xml.contains(r#"<a:buAutoNum type="arabicPeriod"/>"#));
 }
 
+#[test]
+fn test_bullet_autonumbered_start_at() {
+    let mut p = Paragraph::new();
+    p.set_bullet(BulletFormat::AutoNumbered {
+        scheme: MsoAutoNumberScheme::RomanUcParenBoth,
+        start_at: Some(4),
+    });
+    let xml = p.to_xml_string();
+    assert!(xml.contains(r#"<a:buAutoNum type="romanUcParenBoth" startAt="4"/>"#));
+}
+
+#[test]
+fn test_bullet_follows_text() {
+    let mut p = Paragraph::new();
+    p.bullet_color = Some(ColorFormat::rgb(255, 0, 0));
+    p.bullet_color_follows_text = true;
+    p.bullet_size_pct = Some(80.0);
+    p.bullet_size_follows_text = true;
+    p.bullet_font = Some("Wingdings".to_string());
+    p.bullet_font_follows_text = true;
+    p.set_bullet(BulletFormat::Character('\u{2022}'));
+    let xml = p.to_xml_string();
+    assert!(
+        xml.contains(r#"<a:pPr><a:buClrTx/><a:buSzTx/><a:buFontTx/><a:buChar char="•"/></a:pPr>"#)
+    );
+    assert!(!xml.contains("buClr>"));
+    assert!(!xml.contains("Wingdings"));
+}
+
 #[test]
 fn test_paragraph_add_line_break() {
     let mut p = Paragraph::new();
@@ -305,3 +338,40 @@ fn test_paragraph_rtl_with_language() {
     assert!(xml.contains(r#"rtl="1""#));
     assert!(xml.contains(r#"lang="ar-SA""#));
 }
+
+#[test]
+fn test_paragraph_hanging_indent_and_tab_stops() {
+    let mut p = Paragraph::new();
+    p.set_hanging_indent(Emu(342_900), Emu(342_900));
+    p.add_tab_stop(Emu(1_828_800), MsoTabStopType::Decimal);
+    p.add_tab_stop(Emu(914_400), MsoTabStopType::Left);
+    p.default_tab_size = Some(Emu(457_200));
+    p.set_bullet(BulletFormat::auto_numbered(
+        MsoAutoNumberScheme::ArabicPeriod,
+    ));
+    p.font = Some(Font::new());
+    let xml = p.to_xml_string();
+    assert!(xml.starts_with(
+        r#"<a:p><a:pPr marL="342900" indent="-342900" defTabSz="457200"><a:buAutoNum"#
+    ));
+    // Tab stops come in order of position, before the default run properties
+    assert!(xml.contains(
+        r#"<a:tabLst><a:tab pos="914400" algn="l"/><a:tab pos="1828800" algn="dec"/></a:tabLst><a:defRPr"#
+    ));
+}
+
+#[test]
+fn test_paragraph_spacing_units_and_line_breaks() {
+    let mut p = Paragraph::new();
+    p.line_spacing_pts = Some(18.0);
+    p.space_before_pct = Some(50.0);
+    p.space_after = Some(6.0);
+    p.east_asian_line_break = Some(false);
+    p.latin_line_break = Some(true);
+    p.font_alignment = Some(MsoBaselineAlignment::Baseline);
+    let xml = p.to_xml_string();
+    assert!(xml.contains(r#" eaLnBrk="0" fontAlgn="base" latinLnBrk="1">"#));
+    assert!(xml.contains(
+        r#"<a:lnSpc><a:spcPts val="1800"/></a:lnSpc><a:spcBef><a:spcPct val="50000"/></a:spcBef><a:spcAft><a:spcPts val="600"/></a:spcAft>"#
+    ));
+}
@@ -0,0 +1,411 @@
+//! Finding and replacing text in the `<a:p>` paragraphs of any part XML.
+//!
+//! Text is matched across run boundaries: the runs of a paragraph are read
+//! as one string, broken only by line breaks and fields. A replacement is
+//! written into the first run a match touches, so it takes that run's
+//! formatting, and the matched text is removed from the runs that follow.
+//! Everything outside the edited `<a:t>` elements is left byte-for-byte.
+
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{PptxError, PptxResult};
+use crate::units::ShapeId;
+use crate::xml_util::{attr_value, local_name_str, offset, xml_escape};
+
+/// Options for matching text.
+///
+/// # Examples
+///
+/// ```
+/// use pptx::text::search::ReplaceOptions;
+///
+/// let options = ReplaceOptions::new()
+///     .with_match_case(false)
+///     .with_whole_words(true);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplaceOptions {
+    /// Whether upper and lower case must match exactly.
+    pub match_case: bool,
+    /// Whether a match must start and end at word boundaries.
+    pub whole_words: bool,
+}
+
+impl ReplaceOptions {
+    /// Create options matching case-sensitively anywhere in a word.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            match_case: true,
+            whole_words: false,
+        }
+    }
+
+    /// Set whether upper and lower case must match exactly.
+    #[must_use]
+    pub const fn with_match_case(mut self, value: bool) -> Self {
+        self.match_case = value;
+        self
+    }
+
+    /// Set whether a match must start and end at word boundaries.
+    #[must_use]
+    pub const fn with_whole_words(mut self, value: bool) -> Self {
+        self.whole_words = value;
+        self
+    }
+}
+
+impl Default for ReplaceOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A match within one paragraph of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct XmlMatch {
+    /// ID of the innermost shape holding the paragraph, if any.
+    pub shape_id: Option<ShapeId>,
+    /// Index of the paragraph among those of its shape (or of the part,
+    /// outside shapes), in document order.
+    pub paragraph: usize,
+    /// Indices of the `<a:r>` runs of the paragraph the match spans.
+    pub runs: Range<usize>,
+    /// The matched text.
+    pub text: String,
+}
+
+/// A run of a paragraph.
+struct RunText {
+    /// The whole `<a:r>` element.
+    element: Range<usize>,
+    /// Content of its `<a:t>` (an empty range where the text would go).
+    text_range: Range<usize>,
+    text: Vec<char>,
+}
+
+/// One piece of a paragraph: a run, or something text cannot match across.
+enum Piece {
+    Run(RunText),
+    Break,
+}
+
+struct ParagraphText {
+    shape_id: Option<ShapeId>,
+    index: usize,
+    pieces: Vec<Piece>,
+}
+
+/// Whether `local` names an element that is a shape of its own.
+fn is_shape(local: &str) -> bool {
+    matches!(local, "sp" | "pic" | "cxnSp" | "graphicFrame" | "grpSp")
+}
+
+/// Read every `<a:p>` paragraph of `xml`, with its runs.
+fn scan_paragraphs(xml: &str) -> PptxResult<Vec<ParagraphText>> {
+    let mut reader = Reader::from_str(xml);
+    let mut depth = 0usize;
+    // (depth, shape id) of each open shape element
+    let mut shapes: Vec<(usize, Option<ShapeId>)> = Vec::new();
+    // Paragraph counters, per shape (by stack position) and outside shapes
+    let mut counters: Vec<usize> = vec![0];
+    let mut paragraphs = Vec::new();
+    let mut current: Option<(usize, ParagraphText)> = None;
+    // (depth, element start, text range) of the open <a:r>
+    let mut run: Option<(usize, usize, Option<Range<usize>>)> = None;
+    let mut t_start: Option<usize> = None;
+
+    loop {
+        let start = offset(&reader);
+        let event = reader.read_event().map_err(PptxError::Xml)?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                let qname = e.name();
+                let local = local_name_str(qname.as_ref());
+                let end = offset(&reader);
+                if !is_empty && is_shape(local) {
+                    shapes.push((depth, None));
+                    counters.push(0);
+                } else if local == "cNvPr" {
+                    if let Some((_, id @ None)) = shapes.last_mut() {
+                        *id = attr_value(e, b"id")?
+                            .and_then(|v| v.parse().ok())
+                            .map(ShapeId);
+                    }
+                }
+                match qname.as_ref() {
+                    b"a:p" if !is_empty && current.is_none() => {
+                        let counter = counters.last_mut().map_or(0, |c| {
+                            *c += 1;
+                            *c - 1
+                        });
+                        let shape_id = shapes.last().and_then(|(_, id)| *id);
+                        current = Some((
+                            depth,
+                            ParagraphText {
+                                shape_id,
+                                index: counter,
+                                pieces: Vec::new(),
+                            },
+                        ));
+                    }
+                    b"a:p" if is_empty => {
+                        if let Some(c) = counters.last_mut() {
+                            *c += 1;
+                        }
+                    }
+                    b"a:r" if current.as_ref().is_some_and(|(d, _)| depth == d + 1) => {
+                        if !is_empty {
+                            run = Some((depth, start, None));
+                        }
+                    }
+                    b"a:t" if run.is_some() => {
+                        if is_empty {
+                            // <a:t/> holds no text; a replacement is not
+                            // written into it
+                            if let Some((_, _, text)) = &mut run {
+                                *text = Some(end..end);
+                            }
+                        } else {
+                            t_start = Some(end);
+                        }
+                    }
+                    b"a:br" | b"a:fld" if current.as_ref().is_some_and(|(d, _)| depth == d + 1) => {
+                        if let Some((_, p)) = &mut current {
+                            p.pieces.push(Piece::Break);
+                        }
+                    }
+                    _ => {}
+                }
+                if !is_empty {
+                    depth += 1;
+                }
+            }
+            Event::End(ref e) => {
+                depth = depth.saturating_sub(1);
+                let end = offset(&reader);
+                match e.name().as_ref() {
+                    b"a:t" => {
+                        if let (Some(t), Some((_, _, text))) = (t_start.take(), &mut run) {
+                            *text = Some(t..start);
+                        }
+                    }
+                    b"a:r" if run.as_ref().is_some_and(|(d, _, _)| *d == depth) => {
+                        if let (Some((_, run_start, Some(text_range))), Some((_, p))) =
+                            (run.take(), &mut current)
+                        {
+                            let raw = &xml[text_range.clone()];
+                            let text = quick_xml::escape::unescape(raw)
+                                .map_err(|e| PptxError::InvalidXml(e.to_string()))?;
+                            p.pieces.push(Piece::Run(RunText {
+                                element: run_start..end,
+                                text_range,
+                                text: text.chars().collect(),
+                            }));
+                        }
+                        run = None;
+                    }
+                    b"a:p" if current.as_ref().is_some_and(|(d, _)| *d == depth) => {
+                        if let Some((_, p)) = current.take() {
+                            paragraphs.push(p);
+                        }
+                    }
+                    _ => {}
+                }
+                if shapes.last().is_some_and(|(d, _)| *d == depth) {
+                    shapes.pop();
+                    counters.pop();
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(paragraphs)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Non-overlapping matches of `pattern` in `text`, as char ranges.
+fn match_ranges(text: &[char], pattern: &[char], options: ReplaceOptions) -> Vec<Range<usize>> {
+    let eq =
+        |a: char, b: char| a == b || (!options.match_case && a.to_lowercase().eq(b.to_lowercase()));
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i + pattern.len() <= text.len() {
+        let end = i + pattern.len();
+        let matches = text[i..end].iter().zip(pattern).all(|(a, b)| eq(*a, *b))
+            && (!options.whole_words
+                || ((i == 0 || !is_word_char(text[i - 1]))
+                    && (end == text.len() || !is_word_char(text[end]))));
+        if matches {
+            found.push(i..end);
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    found
+}
+
+/// The runs of a paragraph between breaks, as (index of the first run,
+/// runs).
+fn segments(pieces: &[Piece]) -> Vec<(usize, Vec<&RunText>)> {
+    let mut segments = Vec::new();
+    let mut run_index = 0;
+    let mut current: Option<(usize, Vec<&RunText>)> = None;
+    for piece in pieces {
+        match piece {
+            Piece::Run(run) => {
+                current
+                    .get_or_insert_with(|| (run_index, Vec::new()))
+                    .1
+                    .push(run);
+                run_index += 1;
+            }
+            Piece::Break => segments.extend(current.take()),
+        }
+    }
+    segments.extend(current);
+    segments
+}
+
+/// Which run (by position in `runs`) holds each char of their joined text.
+fn run_of_each_char(runs: &[&RunText]) -> Vec<usize> {
+    runs.iter()
+        .enumerate()
+        .flat_map(|(i, r)| std::iter::repeat_n(i, r.text.len()))
+        .collect()
+}
+
+fn check_pattern(pattern: &str) -> PptxResult<Vec<char>> {
+    if pattern.is_empty() {
+        return Err(PptxError::InvalidValue {
+            field: "pattern",
+            value: String::new(),
+            expected: "non-empty text",
+        });
+    }
+    Ok(pattern.chars().collect())
+}
+
+/// Find `pattern` in the paragraphs of `xml`.
+///
+/// # Errors
+///
+/// Returns an error if the pattern is empty or the XML is malformed.
+pub(crate) fn find_in_xml(
+    xml: &str,
+    pattern: &str,
+    options: ReplaceOptions,
+) -> PptxResult<Vec<XmlMatch>> {
+    let pattern = check_pattern(pattern)?;
+    let mut found = Vec::new();
+    for paragraph in scan_paragraphs(xml)? {
+        for (first_run, runs) in segments(&paragraph.pieces) {
+            let text: Vec<char> = runs.iter().flat_map(|r| r.text.iter().copied()).collect();
+            let owner = run_of_each_char(&runs);
+            for range in match_ranges(&text, &pattern, options) {
+                found.push(XmlMatch {
+                    shape_id: paragraph.shape_id,
+                    paragraph: paragraph.index,
+                    runs: first_run + owner[range.start]..first_run + owner[range.end - 1] + 1,
+                    text: text[range].iter().collect(),
+                });
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Replace `pattern` with `replacement` in the paragraphs of `xml`.
+///
+/// Runs emptied by a replacement are removed. Returns the new XML and the
+/// number of replacements made.
+///
+/// # Errors
+///
+/// Returns an error if the pattern is empty or the XML is malformed.
+pub(crate) fn replace_in_xml(
+    xml: &str,
+    pattern: &str,
+    replacement: &str,
+    options: ReplaceOptions,
+) -> PptxResult<(String, usize)> {
+    let pattern = check_pattern(pattern)?;
+    let replacement: Vec<char> = replacement.chars().collect();
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    let mut count = 0;
+
+    for paragraph in scan_paragraphs(xml)? {
+        for (_, runs) in segments(&paragraph.pieces) {
+            let text: Vec<char> = runs.iter().flat_map(|r| r.text.iter().copied()).collect();
+            let matches = match_ranges(&text, &pattern, options);
+            if matches.is_empty() {
+                continue;
+            }
+            count += matches.len();
+            let owner = run_of_each_char(&runs);
+            let starts: Vec<usize> = runs
+                .iter()
+                .scan(0, |pos, r| {
+                    let start = *pos;
+                    *pos += r.text.len();
+                    Some(start)
+                })
+                .collect();
+
+            // Work from the last match back so earlier offsets stay valid
+            let mut new_texts: Vec<Vec<char>> = runs.iter().map(|r| r.text.clone()).collect();
+            for range in matches.iter().rev() {
+                let (first, last) = (owner[range.start], owner[range.end - 1]);
+                let end_in_last = range.end - starts[last];
+                let start_in_first = range.start - starts[first];
+                if first == last {
+                    new_texts[first]
+                        .splice(start_in_first..end_in_last, replacement.iter().copied());
+                } else {
+                    new_texts[last].drain(..end_in_last);
+                    for text in &mut new_texts[first + 1..last] {
+                        text.clear();
+                    }
+                    new_texts[first].splice(start_in_first.., replacement.iter().copied());
+                }
+            }
+
+            for (run, new_text) in runs.iter().zip(new_texts) {
+                if new_text == run.text {
+                    continue;
+                }
+                if new_text.is_empty() {
+                    edits.push((run.element.clone(), String::new()));
+                } else {
+                    let new_text: String = new_text.into_iter().collect();
+                    edits.push((run.text_range.clone(), xml_escape(&new_text)));
+                }
+            }
+        }
+    }
+
+    edits.sort_by_key(|(r, _)| r.start);
+    let mut out = String::with_capacity(xml.len());
+    let mut cursor = 0;
+    for (range, text) in edits {
+        out.push_str(&xml[cursor..range.start]);
+        out.push_str(&text);
+        cursor = range.end;
+    }
+    out.push_str(&xml[cursor..]);
+    Ok((out, count))
+}
+
+#[cfg(test)]
+#[path = "search_tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+use super::*;
+
+fn shape(id: u32, paragraphs: &str) -> String {
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="Shape {id}"/></p:nvSpPr><p:txBody><a:bodyPr/>{paragraphs}</p:txBody></p:sp>"#
+    )
+}
+
+fn run(attrs: &str, text: &str) -> String {
+    format!(r#"<a:r><a:rPr lang="en-US"{attrs}/><a:t>{text}</a:t></a:r>"#)
+}
+
+#[test]
+fn test_find_across_runs() {
+    let xml = shape(
+        4,
+        &format!(
+            "<a:p>{}</a:p><a:p>{}{}{}</a:p>",
+            run("", "Intro"),
+            run("", "Hello Wo"),
+            run(r#" b="1""#, "rl"),
+            run("", "d again"),
+        ),
+    );
+    let found = find_in_xml(&xml, "World", ReplaceOptions::new()).unwrap();
+    assert_eq!(
+        found,
+        vec![XmlMatch {
+            shape_id: Some(ShapeId(4)),
+            paragraph: 1,
+            runs: 0..3,
+            text: "World".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_find_stops_at_breaks_and_decodes_entities() {
+    let xml = shape(
+        2,
+        &format!(
+            "<a:p>{}<a:br/>{}</a:p>",
+            run("", "Fish &amp;"),
+            run("", " Chips")
+        ),
+    );
+    assert!(find_in_xml(&xml, "& Chips", ReplaceOptions::new())
+        .unwrap()
+        .is_empty());
+    let found = find_in_xml(&xml, "Fish &", ReplaceOptions::new()).unwrap();
+    assert_eq!(found[0].text, "Fish &");
+}
+
+#[test]
+fn test_find_case_and_whole_words() {
+    let xml = shape(2, &format!("<a:p>{}</a:p>", run("", "cat Cat concat")));
+    assert_eq!(
+        find_in_xml(&xml, "cat", ReplaceOptions::new())
+            .unwrap()
+            .len(),
+        2
+    );
+    let options = ReplaceOptions::new()
+        .with_match_case(false)
+        .with_whole_words(true);
+    assert_eq!(find_in_xml(&xml, "CAT", options).unwrap().len(), 2);
+}
+
+#[test]
+fn test_find_empty_pattern_is_error() {
+    assert!(find_in_xml("<a:p/>", "", ReplaceOptions::new()).is_err());
+}
+
+#[test]
+fn test_replace_keeps_first_run_formatting() {
+    let xml = shape(
+        3,
+        &format!(
+            "<a:p>{}{}{}</a:p>",
+            run(r#" b="1""#, "Say Hel"),
+            run(r#" i="1""#, "lo"),
+            run("", " there")
+        ),
+    );
+    let (out, count) = replace_in_xml(&xml, "Hello", "Bye <all>", ReplaceOptions::new()).unwrap();
+    assert_eq!(count, 1);
+    // The emptied italic run is removed; the rest is untouched
+    assert!(out.contains(r#"<a:rPr lang="en-US" b="1"/><a:t>Say Bye &lt;all&gt;</a:t>"#));
+    assert!(!out.contains(r#"i="1""#));
+    assert!(out.contains("<a:t> there</a:t>"));
+    assert!(out.starts_with(r#"<p:sp><p:nvSpPr><p:cNvPr id="3""#));
+}
+
+#[test]
+fn test_replace_multiple_matches_in_one_run() {
+    let xml = shape(3, &format!("<a:p>{}</a:p>", run("", "a-a-a")));
+    let (out, count) = replace_in_xml(&xml, "a", "bb", ReplaceOptions::new()).unwrap();
+    assert_eq!(count, 3);
+    assert!(out.contains("<a:t>bb-bb-bb</a:t>"));
+}
+
+#[test]
+fn test_group_and_table_paragraphs() {
+    let xml = format!(
+        r#"<p:spTree><p:grpSp><p:nvGrpSpPr><p:cNvPr id="1" name="Group"/></p:nvGrpSpPr>{}</p:grpSp><p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="9" name="Table"/></p:nvGraphicFramePr><a:graphic><a:graphicData><a:tbl><a:tr><a:tc><a:txBody><a:p>{}</a:p></a:txBody></a:tc><a:tc><a:txBody><a:p>{}</a:p></a:txBody></a:tc></a:tr></a:tbl></a:graphicData></a:graphic></p:graphicFrame></p:spTree>"#,
+        shape(5, &format!("<a:p>{}</a:p>", run("", "key"))),
+        run("", "key"),
+        run("", "key"),
+    );
+    let found = find_in_xml(&xml, "key", ReplaceOptions::new()).unwrap();
+    let locations: Vec<_> = found.iter().map(|m| (m.shape_id, m.paragraph)).collect();
+    assert_eq!(
+        locations,
+        vec![
+            (Some(ShapeId(5)), 0),
+            (Some(ShapeId(9)), 0),
+            (Some(ShapeId(9)), 1)
+        ]
+    );
+}
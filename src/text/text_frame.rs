@@ -2,9 +2,11 @@
 
 use std::fmt;
 
-use crate::enums::text::{MsoAutoSize, MsoVerticalAnchor};
+use crate::dml::effect3d::Scene3D;
+use crate::enums::text::{MsoAutoSize, MsoTextOrientation, MsoVerticalAnchor};
 use crate::text::paragraph::Paragraph;
 use crate::units::Emu;
+use crate::xml_util::xml_escape;
 use crate::WriteXml;
 
 /// A text body within a shape, corresponding to the `<p:txBody>` element.
@@ -15,6 +17,12 @@ use crate::WriteXml;
 pub struct TextFrame {
     pub(crate) paragraphs: Vec<Paragraph>,
     /// Whether text wraps at the shape boundary.
+    ///
+    /// While word wrap is inherited (see
+    /// [`word_wrap_setting`](Self::word_wrap_setting)), writing `true` here
+    /// is ignored and the frame keeps inheriting; writing `false` turns
+    /// wrapping off. Use [`set_word_wrap`](Self::set_word_wrap) to set
+    /// wrapping explicitly either way.
     pub word_wrap: bool,
     /// Automatic sizing behavior.
     pub auto_size: MsoAutoSize,
@@ -33,6 +41,47 @@ pub struct TextFrame {
     /// Font scale for normAutofit, as a percentage (e.g. 80.0 for 80%).
     /// When set along with `auto_size = TextToFitShape`, emits `<a:normAutofit fontScale="X"/>`.
     pub font_scale: Option<f64>,
+    /// Line spacing reduction for normAutofit, as a percentage (e.g. 20.0
+    /// for 20%). Emitted as the `lnSpcReduction` attribute.
+    pub line_spacing_reduction: Option<f64>,
+    /// Number of text columns (`numCol`, 1-16).
+    pub columns: Option<u32>,
+    /// Space between text columns (`spcCol`).
+    pub column_spacing: Option<Emu>,
+    /// Direction text flows in (`vert`).
+    pub orientation: Option<MsoTextOrientation>,
+    /// Whether text stays upright when the shape is rotated (`upright`).
+    pub upright: Option<bool>,
+    /// Whether the text is centered horizontally in the frame, as a block,
+    /// keeping its paragraph alignment (`anchorCtr`).
+    pub anchor_center: Option<bool>,
+    /// `WordArt` warp of the text (`<a:prstTxWarp>`).
+    pub warp: Option<TextWarp>,
+    /// 3D scene of the text (`<a:scene3d>`).
+    pub scene_3d: Option<Scene3D>,
+    /// Whether wrapping is left to the layout or master while `word_wrap`
+    /// keeps its default of `true`.
+    pub(crate) inherit_word_wrap: bool,
+}
+
+/// A preset `WordArt` warp, corresponding to the `<a:prstTxWarp>` element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextWarp {
+    /// Preset name (e.g. `"textArchUp"`, `"textWave1"`).
+    pub preset: String,
+    /// Adjustment values (`adj1`, `adj2`, ...), as fractions.
+    pub adjustments: Vec<f64>,
+}
+
+impl TextWarp {
+    /// Create a warp with the given preset and default adjustments.
+    #[must_use]
+    pub fn new(preset: &str) -> Self {
+        Self {
+            preset: preset.to_string(),
+            adjustments: Vec::new(),
+        }
+    }
 }
 
 impl Default for TextFrame {
@@ -48,6 +97,15 @@ impl Default for TextFrame {
             vertical_anchor: None,
             rotation: None,
             font_scale: None,
+            line_spacing_reduction: None,
+            columns: None,
+            column_spacing: None,
+            orientation: None,
+            upright: None,
+            anchor_center: None,
+            warp: None,
+            scene_3d: None,
+            inherit_word_wrap: false,
         }
     }
 }
@@ -70,6 +128,42 @@ impl TextFrame {
             .join("\n")
     }
 
+    /// The word wrap set on this text frame, or `None` if it is inherited
+    /// from the layout or master.
+    ///
+    /// Text frames parsed from a `<a:bodyPr>` without a `wrap` attribute
+    /// inherit word wrap until `word_wrap` is set to `false` or
+    /// [`set_word_wrap`](Self::set_word_wrap) is called.
+    #[must_use]
+    pub const fn word_wrap_setting(&self) -> Option<bool> {
+        if self.inherit_word_wrap && self.word_wrap {
+            None
+        } else {
+            Some(self.word_wrap)
+        }
+    }
+
+    /// Set word wrap, or with `None` inherit it from the layout or master.
+    ///
+    /// `Some(_)` stops inheriting, so `Some(true)` writes `wrap="square"`.
+    pub fn set_word_wrap(&mut self, word_wrap: Option<bool>) {
+        self.word_wrap = word_wrap.unwrap_or(true);
+        self.inherit_word_wrap = word_wrap.is_none();
+    }
+
+    /// The bullet label shown on each paragraph, e.g. `"3."` on the third
+    /// paragraph of an [`MsoAutoNumberScheme::ArabicPeriod`] list.
+    ///
+    /// Only the paragraphs' own bullets are considered; bullets inherited
+    /// from placeholders and list styles are not. Picture bullets, empty
+    /// paragraphs and paragraphs without a bullet have no label.
+    ///
+    /// [`MsoAutoNumberScheme::ArabicPeriod`]: crate::enums::text::MsoAutoNumberScheme::ArabicPeriod
+    #[must_use]
+    pub fn bullet_labels(&self) -> Vec<Option<String>> {
+        super::bullet::bullet_labels(&self.paragraphs)
+    }
+
     /// Replace all content with a single paragraph containing the given text.
     ///
     /// If the text contains newline characters, each line becomes a separate
@@ -118,6 +212,8 @@ impl TextFrame {
     ///
     /// `font_scale_pct` is in percent (e.g. 80.0 for 80%).  If `None`, the
     /// font scale attribute is omitted and `PowerPoint` will compute it.
+    /// [`shrink_to_fit`](Self::shrink_to_fit) computes the scale from the
+    /// text's font metrics instead.
     pub fn fit_text(&mut self, font_scale_pct: Option<f64>) {
         self.auto_size = MsoAutoSize::TextToFitShape;
         self.font_scale = font_scale_pct;
@@ -134,11 +230,31 @@ impl TextFrame {
     pub fn write_xml_with_tag<W: fmt::Write>(&self, w: &mut W, body_tag: &str) -> fmt::Result {
         write!(w, "<{body_tag}>")?;
 
-        // <a:bodyPr>
+        self.write_body_pr(w)?;
+
+        // <a:lstStyle/>
+        w.write_str("<a:lstStyle/>")?;
+
+        // <a:p> elements
+        for para in &self.paragraphs {
+            w.write_str(&para.to_xml_string())?;
+        }
+
+        write!(w, "</{body_tag}>")
+    }
+
+    /// Write just the `<a:bodyPr>` element for this text frame.
+    pub(crate) fn write_body_pr<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
         w.write_str("<a:bodyPr")?;
 
-        let wrap = if self.word_wrap { "square" } else { "none" };
-        write!(w, r#" wrap="{wrap}""#)?;
+        if let Some(orientation) = self.orientation {
+            write!(w, r#" vert="{}""#, orientation.to_xml_str())?;
+        }
+
+        if let Some(word_wrap) = self.word_wrap_setting() {
+            let wrap = if word_wrap { "square" } else { "none" };
+            write!(w, r#" wrap="{wrap}""#)?;
+        }
 
         if let Some(l) = self.margin_left {
             write!(w, r#" lIns="{l}""#)?;
@@ -160,15 +276,47 @@ impl TextFrame {
             write!(w, r#" rot="{rot}""#)?;
         }
 
+        if let Some(columns) = self.columns {
+            write!(w, r#" numCol="{columns}""#)?;
+        }
+        if let Some(spacing) = self.column_spacing {
+            write!(w, r#" spcCol="{spacing}""#)?;
+        }
+
         if let Some(anchor) = self.vertical_anchor {
             write!(w, r#" anchor="{}""#, anchor.to_xml_str())?;
         }
+        if let Some(center) = self.anchor_center {
+            write!(w, r#" anchorCtr="{}""#, u8::from(center))?;
+        }
+
+        if let Some(upright) = self.upright {
+            write!(w, r#" upright="{}""#, u8::from(upright))?;
+        }
 
         // Determine if bodyPr needs child elements
-        let has_autofit_child = self.auto_size != MsoAutoSize::None;
+        let has_children =
+            self.auto_size != MsoAutoSize::None || self.warp.is_some() || self.scene_3d.is_some();
 
-        if has_autofit_child {
+        if has_children {
             w.write_char('>')?;
+            if let Some(ref warp) = self.warp {
+                write!(w, r#"<a:prstTxWarp prst="{}">"#, xml_escape(&warp.preset))?;
+                if warp.adjustments.is_empty() {
+                    w.write_str("<a:avLst/>")?;
+                } else {
+                    w.write_str("<a:avLst>")?;
+                    for (i, val) in warp.adjustments.iter().enumerate() {
+                        // Adjustment values are stored as 1/100000ths
+                        #[allow(clippy::cast_possible_truncation)]
+                        // intentional f64→i64 for OOXML units
+                        let adj = (*val * 100_000.0) as i64;
+                        write!(w, r#"<a:gd name="adj{}" fmla="val {adj}"/>"#, i + 1)?;
+                    }
+                    w.write_str("</a:avLst>")?;
+                }
+                w.write_str("</a:prstTxWarp>")?;
+            }
             match self.auto_size {
                 MsoAutoSize::TextToFitShape => {
                     w.write_str("<a:normAutofit")?;
@@ -179,6 +327,13 @@ impl TextFrame {
                         let val = (scale * 1000.0) as i64;
                         write!(w, r#" fontScale="{val}""#)?;
                     }
+                    if let Some(reduction) = self.line_spacing_reduction {
+                        // lnSpcReduction is in 1000ths of a percent as well
+                        #[allow(clippy::cast_possible_truncation)]
+                        // intentional f64→i64 for OOXML units
+                        let val = (reduction * 1000.0) as i64;
+                        write!(w, r#" lnSpcReduction="{val}""#)?;
+                    }
                     w.write_str("/>")?;
                 }
                 MsoAutoSize::ShapeToFitText => {
@@ -186,20 +341,13 @@ impl TextFrame {
                 }
                 MsoAutoSize::None => {}
             }
-            w.write_str("</a:bodyPr>")?;
+            if let Some(ref scene) = self.scene_3d {
+                scene.write_xml(w)?;
+            }
+            w.write_str("</a:bodyPr>")
         } else {
-            w.write_str("/>")?;
-        }
-
-        // <a:lstStyle/>
-        w.write_str("<a:lstStyle/>")?;
-
-        // <a:p> elements
-        for para in &self.paragraphs {
-            w.write_str(&para.to_xml_string())?;
+            w.write_str("/>")
         }
-
-        write!(w, "</{body_tag}>")
     }
 
     /// Generate the `<p:txBody>` XML element string.
@@ -2,7 +2,7 @@
 
 use super::*;
 use crate::dml::color::ColorFormat;
-use crate::enums::text::{MsoAutoSize, MsoTextUnderlineType};
+use crate::enums::text::{MsoAutoSize, MsoTextOrientation, MsoTextUnderlineType};
 use crate::shapes::action::Hyperlink;
 use crate::text::bullet::BulletFormat;
 use crate::text::font::{Font, RgbColor};
@@ -98,6 +98,51 @@ fn test_textframe_to_xml_no_wrap() {
     assert!(xml.contains(r#"wrap="none""#));
 }
 
+#[test]
+fn test_textframe_to_xml_inherited_wrap() {
+    let mut tf = TextFrame::new();
+    tf.set_word_wrap(None);
+    assert!(tf.word_wrap);
+    assert_eq!(tf.word_wrap_setting(), None);
+    assert!(!tf.to_xml_string().contains("wrap="));
+
+    // Turning wrapping off overrides the inherited setting
+    tf.word_wrap = false;
+    assert_eq!(tf.word_wrap_setting(), Some(false));
+    assert!(tf.to_xml_string().contains(r#"wrap="none""#));
+
+    // Writing `true` to the field keeps inheriting; the accessor does not
+    tf.set_word_wrap(None);
+    tf.word_wrap = true;
+    assert_eq!(tf.word_wrap_setting(), None);
+    tf.set_word_wrap(Some(true));
+    assert_eq!(tf.word_wrap_setting(), Some(true));
+    assert!(tf.to_xml_string().contains(r#"wrap="square""#));
+}
+
+#[test]
+fn test_textframe_to_xml_columns_orientation_warp_and_scene() {
+    let mut tf = TextFrame::new();
+    tf.columns = Some(2);
+    tf.column_spacing = Some(Emu(457_200));
+    tf.orientation = Some(MsoTextOrientation::EastAsianVertical);
+    tf.upright = Some(true);
+    tf.anchor_center = Some(false);
+    tf.auto_size = MsoAutoSize::ShapeToFitText;
+    let mut warp = TextWarp::new("textArchUp");
+    warp.adjustments.push(0.5);
+    tf.warp = Some(warp);
+    tf.scene_3d = Some(Scene3D::default());
+    let xml = tf.to_xml_string();
+    assert!(xml.contains(r#" vert="eaVert" wrap="square""#));
+    assert!(xml.contains(r#" numCol="2" spcCol="457200""#));
+    assert!(xml.contains(r#" anchorCtr="0" upright="1">"#));
+    // Children follow the schema order: warp, autofit, scene
+    assert!(xml.contains(
+        r#"<a:prstTxWarp prst="textArchUp"><a:avLst><a:gd name="adj1" fmla="val 50000"/></a:avLst></a:prstTxWarp><a:spAutoFit/><a:scene3d><a:camera prst="orthographicFront"/>"#
+    ));
+}
+
 #[test]
 fn test_textframe_to_xml_auto_size() {
     let mut tf = TextFrame::new();
@@ -188,6 +233,15 @@ fn test_fit_text_with_scale() {
     assert!(xml.contains(r#"<a:normAutofit fontScale="80000"/>"#));
 }
 
+#[test]
+fn test_fit_text_line_spacing_reduction() {
+    let mut tf = TextFrame::new();
+    tf.fit_text(Some(85.0));
+    tf.line_spacing_reduction = Some(20.0);
+    let xml = tf.to_xml_string();
+    assert!(xml.contains(r#"<a:normAutofit fontScale="85000" lnSpcReduction="20000"/>"#));
+}
+
 #[test]
 fn test_fit_text_100_percent() {
     let mut tf = TextFrame::new();
@@ -0,0 +1,173 @@
+//! Resolving `DrawingML` colors to RGB.
+
+use crate::dml::color::ColorFormat;
+use crate::error::PptxResult;
+use crate::shapes::parser::parse_color_from_xml;
+use crate::text::font::RgbColor;
+
+use super::ThemeColorScheme;
+
+/// Turns colors into RGB using the presentation's theme.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Colors {
+    scheme: ThemeColorScheme,
+}
+
+impl Colors {
+    pub(crate) const fn new(scheme: ThemeColorScheme) -> Self {
+        Self { scheme }
+    }
+
+    /// A color of the theme by its scheme name (`"accent1"`, `"tx1"`, ...).
+    pub(crate) fn scheme(&self, name: &str) -> RgbColor {
+        self.scheme.by_name(name).unwrap_or(BLACK)
+    }
+
+    pub(crate) fn resolve(&self, color: &ColorFormat) -> RgbColor {
+        match color {
+            ColorFormat::Rgb(rgb) => *rgb,
+            ColorFormat::Theme(theme) => {
+                let base = self.scheme(theme.theme_color.to_xml_str());
+                theme
+                    .brightness
+                    .map_or(base, |brightness| with_brightness(base, brightness))
+            }
+            ColorFormat::Hsl(hsl) => {
+                hsl_to_rgb(hsl.hue, hsl.saturation / 100.0, hsl.luminance / 100.0)
+            }
+            ColorFormat::System(system) => system
+                .last_color
+                .as_deref()
+                .and_then(|hex| RgbColor::from_hex(hex).ok())
+                .unwrap_or_else(|| match system.val.to_xml_str() {
+                    "window" | "btnHighlight" | "highlightText" => WHITE,
+                    _ => BLACK,
+                }),
+            ColorFormat::Preset(preset) => preset_color(preset.val.to_xml_str()),
+        }
+    }
+
+    /// The first color element in `xml`, resolved.
+    pub(crate) fn resolve_xml(&self, xml: &str) -> PptxResult<Option<RgbColor>> {
+        Ok(parse_color_from_xml(xml.as_bytes())?.map(|color| self.resolve(&color)))
+    }
+}
+
+pub(crate) const BLACK: RgbColor = RgbColor::new(0, 0, 0);
+pub(crate) const WHITE: RgbColor = RgbColor::new(255, 255, 255);
+
+/// `color` mixed with white, keeping `amount` (0.0 to 1.0) of the color.
+pub(crate) fn tint(color: RgbColor, amount: f64) -> RgbColor {
+    let mix = |c: u8| channel(f64::from(c) * amount + 255.0 * (1.0 - amount));
+    RgbColor::new(mix(color.r), mix(color.g), mix(color.b))
+}
+
+/// Lighten (positive) or darken (negative) a color, as `PowerPoint` does
+/// for theme color variations.
+fn with_brightness(color: RgbColor, brightness: f64) -> RgbColor {
+    let (h, s, l) = rgb_to_hsl(color);
+    let l = if brightness >= 0.0 {
+        l + (1.0 - l) * brightness
+    } else {
+        l * (1.0 + brightness)
+    };
+    hsl_to_rgb(h, s, l.clamp(0.0, 1.0))
+}
+
+fn channel(value: f64) -> u8 {
+    // f64→u8: the value is clamped to the channel range first
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let c = value.round().clamp(0.0, 255.0) as u8;
+    c
+}
+
+/// Hue in degrees, saturation and lightness from 0.0 to 1.0.
+fn rgb_to_hsl(color: RgbColor) -> (f64, f64, f64) {
+    let r = f64::from(color.r) / 255.0;
+    let g = f64::from(color.g) / 255.0;
+    let b = f64::from(color.b) / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if (max - r).abs() < f64::EPSILON {
+        60.0 * ((g - b) / d).rem_euclid(6.0)
+    } else if (max - g).abs() < f64::EPSILON {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (h, s, l)
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> RgbColor {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let hp = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (hp.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match hp {
+        hp if hp < 1.0 => (c, x, 0.0),
+        hp if hp < 2.0 => (x, c, 0.0),
+        hp if hp < 3.0 => (0.0, c, x),
+        hp if hp < 4.0 => (0.0, x, c),
+        hp if hp < 5.0 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    RgbColor::new(
+        channel((r + m) * 255.0),
+        channel((g + m) * 255.0),
+        channel((b + m) * 255.0),
+    )
+}
+
+/// The common preset colors; others come out black.
+fn preset_color(name: &str) -> RgbColor {
+    let (r, g, b) = match name {
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "lime" => (0, 255, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" | "aqua" => (0, 255, 255),
+        "magenta" | "fuchsia" => (255, 0, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "dkGray" | "darkGray" => (169, 169, 169),
+        "ltGray" | "lightGray" => (211, 211, 211),
+        "navy" => (0, 0, 128),
+        "maroon" => (128, 0, 0),
+        "purple" => (128, 0, 128),
+        "teal" => (0, 128, 128),
+        "olive" => (128, 128, 0),
+        "orange" => (255, 165, 0),
+        _ => (0, 0, 0),
+    };
+    RgbColor::new(r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::dml::MsoThemeColorIndex;
+
+    #[test]
+    fn theme_color_with_brightness() {
+        let colors = Colors::default();
+        let plain = ColorFormat::theme(MsoThemeColorIndex::Accent1);
+        assert_eq!(colors.resolve(&plain), RgbColor::new(79, 129, 189));
+        let lighter = ColorFormat::theme_with_brightness(MsoThemeColorIndex::Text1, 0.5);
+        assert_eq!(colors.resolve(&lighter), RgbColor::new(128, 128, 128));
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        let color = RgbColor::new(79, 129, 189);
+        let (h, s, l) = rgb_to_hsl(color);
+        assert_eq!(hsl_to_rgb(h, s, l), color);
+    }
+}
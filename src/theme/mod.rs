@@ -10,6 +10,7 @@
 //! - hlink (hyperlink)
 //! - folHlink (followed hyperlink)
 
+pub(crate) mod colors;
 mod parser;
 
 #[cfg(test)]
@@ -20,6 +21,7 @@ use std::fmt::Write;
 use crate::error::{PptxError, PptxResult};
 use crate::text::font::RgbColor;
 
+pub(crate) use colors::Colors;
 pub use parser::parse_theme_color_scheme;
 
 /// The 12 theme color slots from `<a:clrScheme>`.
@@ -52,6 +52,13 @@ const EMUS_PER_MM: f64 = 36_000.0;
 const EMUS_PER_CENTIPOINT: f64 = 127.0;
 const EMUS_PER_TWIP: f64 = 635.0;
 
+/// Convert points to EMU, to the nearest EMU.
+pub(crate) fn pt_to_emu(pt: f64) -> Emu {
+    // Slide coordinates are far below the range of EMU
+    #[allow(clippy::cast_possible_truncation)]
+    Emu((pt * EMUS_PER_PT).round() as i64)
+}
+
 impl Emu {
     // Precision loss acceptable for display/measurement purposes
     #[must_use]
@@ -4,16 +4,20 @@
 //! helpers, and common XML parsing helpers used by multiple modules.
 
 mod escape;
+pub(crate) mod splice;
 
 pub use escape::{write_xml_escaped, xml_escape, xml_escape_char};
 
 use std::borrow::Cow;
-use std::fmt;
+use std::fmt::{self, Write};
 
+use quick_xml::escape::resolve_predefined_entity;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
+use sha1::{Digest, Sha1};
 
 use crate::error::{PptxError, PptxResult};
+use crate::units::Emu;
 
 /// Trait for types that can serialize themselves as XML.
 ///
@@ -129,6 +133,21 @@ pub fn attr_value_ns<'a>(
     Ok(None)
 }
 
+/// Read an EMU-valued attribute; absent or non-numeric values yield `None`.
+///
+/// # Errors
+///
+/// Returns `PptxError::XmlAttr` if any attribute in the element is malformed.
+pub(crate) fn emu_attr(e: &BytesStart<'_>, key: &[u8]) -> PptxResult<Option<Emu>> {
+    Ok(attr_value(e, key)?.and_then(|v| v.parse().ok()).map(Emu))
+}
+
+/// The byte offset a reader over an in-memory buffer has reached.
+pub(crate) fn offset(reader: &Reader<&[u8]>) -> usize {
+    // u64→usize: offsets are bounded by the length of the in-memory slice
+    usize::try_from(reader.buffer_position()).unwrap_or(0)
+}
+
 /// Read all inner XML of the currently-open element as raw bytes.
 ///
 /// The reader must be positioned just after the `Start` event of the element.
@@ -197,6 +216,85 @@ pub fn read_inner_xml(
     Ok(inner)
 }
 
+/// Collect the relationship IDs referenced from `xml`, in document order and
+/// without duplicates.
+///
+/// Every attribute in the `r:` (officeDocument relationships) namespace is
+/// a relationship reference: `r:embed`, `r:link`, `r:id`, `r:dm`, `r:pict`
+/// and so on.
+///
+/// # Errors
+///
+/// Returns an error if the XML or one of its attributes is malformed.
+pub(crate) fn relationship_ids(xml: &[u8]) -> PptxResult<Vec<String>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut ids: Vec<String> = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                for attr_result in e.attributes() {
+                    let attr = attr_result?;
+                    if attr.key.as_ref().starts_with(b"r:") {
+                        let value = std::str::from_utf8(&attr.value)?;
+                        if !ids.iter().any(|id| id == value) {
+                            ids.push(value.to_string());
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(ids)
+}
+
+/// Append the characters of a text or entity reference event to `out`.
+///
+/// Entities split element text into several events, so readers that
+/// collect text feed each event through here.
+///
+/// # Errors
+///
+/// Returns an error if the text cannot be decoded or a character
+/// reference is invalid.
+pub(crate) fn append_text(event: &Event<'_>, out: &mut String) -> PptxResult<()> {
+    match event {
+        Event::Text(t) => out.push_str(&t.decode().map_err(quick_xml::Error::from)?),
+        Event::GeneralRef(r) => {
+            if let Some(ch) = r.resolve_char_ref()? {
+                out.push(ch);
+            } else {
+                let entity = r.decode().map_err(quick_xml::Error::from)?;
+                out.push_str(resolve_predefined_entity(&entity).unwrap_or_default());
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// A GUID in registry format (`{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`),
+/// hashed from `seed` so that the same seed always gives the same GUID.
+pub(crate) fn hashed_guid(seed: &str) -> String {
+    let hash = Sha1::digest(seed.as_bytes());
+    let mut hex = String::with_capacity(32);
+    for b in &hash[..16] {
+        write!(hex, "{b:02X}")
+            .unwrap_or_else(|_| unreachable!("fmt::Write for String is infallible"));
+    }
+    format!(
+        "{{{}-{}-{}-{}-{}}}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,4 +361,12 @@ mod tests {
             }
         }
     }
+
+    // --- relationship_ids tests ---
+
+    #[test]
+    fn relationship_ids_collects_r_namespace_attrs() {
+        let xml = br#"<p:pic><p:nvPicPr><p:cNvPr id="2" name="P"><a:hlinkClick r:id="rId4"/></p:cNvPr></p:nvPicPr><p:blipFill><a:blip r:embed="rId3"/></p:blipFill><a:blip r:embed="rId3" r:link="rId5"/></p:pic>"#;
+        assert_eq!(relationship_ids(xml).unwrap(), ["rId4", "rId3", "rId5"]);
+    }
 }
@@ -0,0 +1,199 @@
+//! String-level helpers for locating and patching elements in serialized XML.
+//!
+//! These are used where an edit must leave the surrounding markup
+//! byte-for-byte intact (unknown extensions, vendor namespaces, formatting),
+//! which a parse/re-serialize round trip through typed structs cannot do.
+
+use std::ops::Range;
+
+/// Whether the byte following a tag name terminates it (so that `<a:ext`
+/// does not match `<a:extLst`).
+fn is_name_boundary(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\r' | b'\n' | b'>' | b'/')
+}
+
+/// Find the next start (or empty-element) tag named `qname` at or after
+/// `from`, returning the byte range of the tag itself (`<qname ...>`).
+pub(crate) fn find_start_tag(xml: &str, qname: &str, from: usize) -> Option<Range<usize>> {
+    let open = format!("<{qname}");
+    let bytes = xml.as_bytes();
+    let mut pos = from;
+    while let Some(rel) = xml.get(pos..)?.find(&open) {
+        let start = pos + rel;
+        let after = start + open.len();
+        if bytes.get(after).copied().is_some_and(is_name_boundary) {
+            let gt = xml[after..].find('>')? + after;
+            return Some(start..gt + 1);
+        }
+        pos = after;
+    }
+    None
+}
+
+/// Find the next complete element named `qname` at or after `from`.
+///
+/// The returned range spans from `<qname` through the matching `</qname>`
+/// (or the `/>` of an empty element). Nested elements of the same name are
+/// balanced correctly.
+pub(crate) fn find_element(xml: &str, qname: &str, from: usize) -> Option<Range<usize>> {
+    let first = find_start_tag(xml, qname, from)?;
+    if xml[..first.end].ends_with("/>") {
+        return Some(first);
+    }
+    let close = format!("</{qname}>");
+    let mut depth = 1usize;
+    let mut pos = first.end;
+    loop {
+        let next_close = xml[pos..].find(&close).map(|r| pos + r)?;
+        match find_start_tag(xml, qname, pos) {
+            Some(open) if open.start < next_close => {
+                if !xml[..open.end].ends_with("/>") {
+                    depth += 1;
+                }
+                pos = open.end;
+            }
+            _ => {
+                depth -= 1;
+                pos = next_close + close.len();
+                if depth == 0 {
+                    return Some(first.start..pos);
+                }
+            }
+        }
+    }
+}
+
+/// Find the next complete element named `qname` that lies entirely within
+/// `within`.
+pub(crate) fn find_element_in(
+    xml: &str,
+    qname: &str,
+    within: Range<usize>,
+) -> Option<Range<usize>> {
+    find_element(xml, qname, within.start).filter(|r| r.end <= within.end)
+}
+
+/// Return the content range of a non-empty element span, i.e. the bytes
+/// between its start tag and its end tag. Empty elements yield an empty
+/// range positioned just before their `/>`.
+pub(crate) fn inner_range(xml: &str, element: Range<usize>) -> Range<usize> {
+    let slice = &xml[element.clone()];
+    let Some(gt) = slice.find('>') else {
+        return element.end..element.end;
+    };
+    if slice[..=gt].ends_with("/>") {
+        let at = element.start + gt - 1;
+        return at..at;
+    }
+    let close_start = slice.rfind("</").map_or(element.end, |p| element.start + p);
+    element.start + gt + 1..close_start
+}
+
+/// Set (or, with `None`, remove) an attribute on a start tag string.
+///
+/// `value` must already be XML-escaped. Works for both `<x ...>` and
+/// `<x .../>` forms.
+pub(crate) fn set_attr(start_tag: &str, name: &str, value: Option<&str>) -> String {
+    let needle = format!(" {name}=\"");
+    let existing = start_tag.find(&needle).and_then(|at| {
+        let val_start = at + needle.len();
+        start_tag[val_start..]
+            .find('"')
+            .map(|len| (at, val_start + len + 1))
+    });
+    match (existing, value) {
+        (Some((at, end)), Some(v)) => {
+            format!("{}{needle}{v}\"{}", &start_tag[..at], &start_tag[end..])
+        }
+        (Some((at, end)), None) => format!("{}{}", &start_tag[..at], &start_tag[end..]),
+        (None, Some(v)) => {
+            let insert_at = if start_tag.ends_with("/>") {
+                start_tag.len() - 2
+            } else {
+                start_tag.len() - 1
+            };
+            format!(
+                "{}{needle}{v}\"{}",
+                &start_tag[..insert_at],
+                &start_tag[insert_at..]
+            )
+        }
+        (None, None) => start_tag.to_string(),
+    }
+}
+
+/// The (still XML-escaped) value of an attribute on a start tag string.
+pub(crate) fn get_attr<'a>(start_tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!(" {name}=\"");
+    let val_start = start_tag.find(&needle)? + needle.len();
+    let len = start_tag[val_start..].find('"')?;
+    Some(&start_tag[val_start..val_start + len])
+}
+
+/// Replace `range` in `xml` with `replacement`, returning the new string.
+pub(crate) fn splice(xml: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut out = String::with_capacity(xml.len() - range.len() + replacement.len());
+    out.push_str(&xml[..range.start]);
+    out.push_str(replacement);
+    out.push_str(&xml[range.end..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_start_tag_respects_name_boundary() {
+        let xml = r#"<a:extLst><a:ext uri="x"/></a:extLst>"#;
+        let r = find_start_tag(xml, "a:ext", 0).unwrap();
+        assert_eq!(&xml[r], r#"<a:ext uri="x"/>"#);
+    }
+
+    #[test]
+    fn find_element_balances_nesting() {
+        let xml = "<g><g><x/></g><y/></g><z/>";
+        let r = find_element(xml, "g", 0).unwrap();
+        assert_eq!(&xml[r], "<g><g><x/></g><y/></g>");
+    }
+
+    #[test]
+    fn find_element_empty() {
+        let xml = r#"<p><a:off x="1" y="2"/></p>"#;
+        let r = find_element(xml, "a:off", 0).unwrap();
+        assert_eq!(&xml[r], r#"<a:off x="1" y="2"/>"#);
+    }
+
+    #[test]
+    fn inner_range_of_element() {
+        let xml = "<a><b/>text</a>";
+        let r = inner_range(xml, 0..xml.len());
+        assert_eq!(&xml[r], "<b/>text");
+    }
+
+    #[test]
+    fn get_attr_reads_value() {
+        let tag = r#"<a:off x="10" y="-20"/>"#;
+        assert_eq!(get_attr(tag, "x"), Some("10"));
+        assert_eq!(get_attr(tag, "y"), Some("-20"));
+        assert_eq!(get_attr(tag, "cx"), None);
+    }
+
+    #[test]
+    fn set_attr_replace_insert_remove() {
+        let tag = r#"<p:cNvPr id="2" name="Old"/>"#;
+        assert_eq!(
+            set_attr(tag, "name", Some("New")),
+            r#"<p:cNvPr id="2" name="New"/>"#
+        );
+        assert_eq!(
+            set_attr(tag, "descr", Some("d")),
+            r#"<p:cNvPr id="2" name="Old" descr="d"/>"#
+        );
+        assert_eq!(set_attr(tag, "name", None), r#"<p:cNvPr id="2"/>"#);
+        assert_eq!(
+            set_attr("<a:xfrm>", "rot", Some("60000")),
+            r#"<a:xfrm rot="60000">"#
+        );
+    }
+}